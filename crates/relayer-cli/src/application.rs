@@ -148,6 +148,10 @@ impl Application for CliApp {
 
         tracing::info!("running Hermes v{}", clap::crate_version!());
 
+        // Every command may submit transactions, so the audit log is opened for all of them
+        ibc_relayer::audit::init(&config.audit)
+            .map_err(|e| FrameworkErrorKind::ComponentError.context(e))?;
+
         self.config.set_once(config);
 
         Ok(())
@@ -195,13 +199,16 @@ impl Application for CliApp {
         let enable_json = self.debug_enabled(DebugSection::ProfilingJson);
         ibc_relayer::util::profiling::enable(enable_console, enable_json);
 
+        // Never broadcast any transaction in dry-run mode
+        ibc_relayer::util::dry_run::enable(command.dry_run);
+
         if command.json {
             // Enable JSON by using the crate-level `Tracing`
-            let tracing = JsonTracing::new(config.global, &self.debug_sections)?;
+            let tracing = JsonTracing::new(config.global, &config.tracing, &self.debug_sections)?;
             Ok(vec![Box::new(terminal), Box::new(tracing)])
         } else {
             // Use abscissa's tracing, which pretty-prints to the terminal obeying log levels
-            let tracing = PrettyTracing::new(config.global, &self.debug_sections)?;
+            let tracing = PrettyTracing::new(config.global, &config.tracing, &self.debug_sections)?;
             Ok(vec![Box::new(terminal), Box::new(tracing)])
         }
     }
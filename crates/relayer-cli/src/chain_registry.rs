@@ -129,11 +129,13 @@ where
             url: websocket_address,
             batch_delay: default::batch_delay(),
         },
+        fallback_endpoints: Vec::new(),
         rpc_timeout: default::rpc_timeout(),
         trusted_node: default::trusted_node(),
         genesis_restart: None,
         account_prefix: chain_data.bech32_prefix,
         key_name: String::new(),
+        key_names: Vec::new(),
         key_store_type: Store::default(),
         key_store_folder: None,
         store_prefix: "ibc".to_string(),
@@ -156,7 +158,14 @@ where
             price: avg_gas_price,
             denom: asset.base.to_owned(),
         },
+        dynamic_gas_price: None,
+        fee_budget: None,
         packet_filter: packet_filter.unwrap_or_default(),
+        light_block_cache: Default::default(),
+        rate_limit: None,
+        remote_signer: None,
+        wallet: None,
+        solo_machine: None,
         address_type: AddressType::default(),
         sequential_batch_tx: false,
         extension_options: Vec::new(),
@@ -1,6 +1,11 @@
 //! Various utilities for the Hermes CLI
 
 use alloc::sync::Arc;
+use std::fs;
+use std::ops::RangeInclusive;
+use std::path::Path;
+
+use dialoguer::Password;
 use eyre::eyre;
 use tokio::runtime::Runtime as TokioRuntime;
 use tracing::debug;
@@ -13,10 +18,12 @@ use ibc_relayer::{
         counterparty::{channel_connection_client, ChannelConnectionClient},
         handle::{BaseChainHandle, ChainHandle},
     },
-    config::Config,
+    config::{ChainConfig, Config},
+    keyring::{self, Store},
     spawn,
 };
 use ibc_relayer_types::core::ics02_client::client_state::ClientState;
+use ibc_relayer_types::core::ics04_channel::packet::Sequence;
 use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
 
 use crate::error::Error;
@@ -186,3 +193,100 @@ pub fn check_can_send_on_channel<Chain: ChainHandle>(
 
     Ok(())
 }
+
+/// Unlock the encrypted key store if any of the given chains is configured to use it.
+///
+/// The passphrase is read from the given file if any, otherwise from the
+/// `HERMES_KEYRING_PASSPHRASE` environment variable, and as a last resort
+/// the user is prompted for it.
+pub fn unlock_key_store<'a>(
+    chains: impl IntoIterator<Item = &'a ChainConfig>,
+    passphrase_file: Option<&Path>,
+) -> Result<(), Error> {
+    let uses_encrypted_store = chains
+        .into_iter()
+        .any(|chain| chain.key_store_type == Store::Encrypted);
+
+    if !uses_encrypted_store {
+        return Ok(());
+    }
+
+    let passphrase = match passphrase_file {
+        Some(path) => fs::read_to_string(path)
+            .map_err(Error::io)?
+            .trim_end_matches(&['\r', '\n'][..])
+            .to_string(),
+        None if keyring::encrypted::is_unlocked() => return Ok(()),
+        None => Password::new()
+            .with_prompt("Key store passphrase")
+            .interact()
+            .map_err(Error::io)?,
+    };
+
+    keyring::encrypted::unlock(passphrase).map_err(Error::key_ring)
+}
+
+/// Parse a range of packet sequences, either a single sequence such as `5`,
+/// a range such as `1..100`, which includes its end, or a range open on
+/// either side, such as `300..` or `..20`.
+pub fn parse_packet_sequences(input: &str) -> Result<RangeInclusive<Sequence>, Error> {
+    fn parse_sequence(input: &str) -> Result<Sequence, Error> {
+        input
+            .trim()
+            .parse::<u64>()
+            .map(Sequence::from)
+            .map_err(|_| Error::cli_arg(format!("invalid packet sequence '{input}'")))
+    }
+
+    let range = match input.split_once("..") {
+        None => {
+            let sequence = parse_sequence(input)?;
+            sequence..=sequence
+        }
+        Some((start, end)) => {
+            let start = match start.trim() {
+                "" => Sequence::from(1),
+                start => parse_sequence(start)?,
+            };
+
+            let end = match end.trim() {
+                "" => Sequence::from(u64::MAX),
+                end => parse_sequence(end)?,
+            };
+
+            start..=end
+        }
+    };
+
+    if range.is_empty() {
+        return Err(Error::cli_arg(format!(
+            "invalid packet sequence range '{input}', its start is greater than its end"
+        )));
+    }
+
+    Ok(range)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_packet_sequences;
+
+    use ibc_relayer_types::core::ics04_channel::packet::Sequence;
+
+    #[test]
+    fn parse_packet_sequence_ranges() {
+        let seq = Sequence::from;
+
+        assert_eq!(parse_packet_sequences("5").unwrap(), seq(5)..=seq(5));
+        assert_eq!(parse_packet_sequences("1..100").unwrap(), seq(1)..=seq(100));
+        assert_eq!(
+            parse_packet_sequences("300..").unwrap(),
+            seq(300)..=seq(u64::MAX)
+        );
+        assert_eq!(parse_packet_sequences("..20").unwrap(), seq(1)..=seq(20));
+
+        assert!(parse_packet_sequences("").is_err());
+        assert!(parse_packet_sequences("10..5").is_err());
+        assert!(parse_packet_sequences("1..a").is_err());
+    }
+}
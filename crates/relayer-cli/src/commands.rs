@@ -4,6 +4,7 @@ mod clear;
 mod completions;
 mod config;
 mod create;
+mod diagnose;
 mod fee;
 mod health;
 mod keys;
@@ -18,7 +19,7 @@ mod version;
 
 use self::{
     clear::ClearCmds, completions::CompletionsCmd, config::ConfigCmd, create::CreateCmds,
-    fee::FeeCmd, health::HealthCheckCmd, keys::KeysCmd, listen::ListenCmd,
+    diagnose::DiagnoseCmds, fee::FeeCmd, health::HealthCheckCmd, keys::KeysCmd, listen::ListenCmd,
     misbehaviour::MisbehaviourCmd, query::QueryCmd, start::StartCmd, tx::TxCmd, update::UpdateCmds,
     upgrade::UpgradeCmds, version::VersionCmd,
 };
@@ -65,6 +66,10 @@ pub enum CliCmd {
     #[clap(subcommand)]
     Clear(ClearCmds),
 
+    /// Diagnose objects, such as ordered channels blocked by a packet.
+    #[clap(subcommand)]
+    Diagnose(DiagnoseCmds),
+
     /// Start the relayer in multi-chain mode.
     ///
     /// Relays packets and open handshake messages between all chains in the config.
@@ -1,3 +1,5 @@
+use std::ops::RangeInclusive;
+
 use abscissa_core::clap::Parser;
 use abscissa_core::config::Override;
 use abscissa_core::{Command, FrameworkErrorKind, Runnable};
@@ -6,11 +8,12 @@ use ibc_relayer::chain::handle::{BaseChainHandle, ChainHandle};
 use ibc_relayer::config::Config;
 use ibc_relayer::link::error::LinkError;
 use ibc_relayer::link::{Link, LinkParameters};
+use ibc_relayer_types::core::ics04_channel::packet::Sequence;
 use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
 use ibc_relayer_types::events::IbcEvent;
 
 use crate::application::app_config;
-use crate::cli_utils::spawn_chain_counterparty;
+use crate::cli_utils::{parse_packet_sequences, spawn_chain_counterparty};
 use crate::conclude::Output;
 
 /// `clear` subcommands
@@ -52,6 +55,17 @@ pub struct ClearPacketsCmd {
     )]
     channel_id: ChannelId,
 
+    #[clap(
+        long = "packet-sequences",
+        value_name = "SEQUENCES",
+        value_delimiter = ',',
+        parse(try_from_str = parse_packet_sequences),
+        help = "Only clear the packets sent from the given chain with these sequences, \
+                comma separated, either single sequences or ranges such as `1..100` or `300..` \
+                (default: all the pending packets in both directions)"
+    )]
+    packet_sequences: Vec<RangeInclusive<Sequence>>,
+
     #[clap(
         long = "key-name",
         help = "use the given signing key for the specified chain (default: `key_name` config)"
@@ -126,21 +140,32 @@ impl Runnable for ClearPacketsCmd {
             Err(e) => Output::error(e).exit(),
         };
 
+        // When specific sequences are given, only the packets sent from the given chain
+        // are cleared, namely their recv or timeout messages on the forward link and their
+        // acknowledgements on the reverse link. The packets sent from the counterparty chain
+        // can be cleared by identifying the channel by its other end.
+        let sequences = &self.packet_sequences;
+        let clear_counterparty = sequences.is_empty();
+
         // Schedule RecvPacket messages for pending packets in both directions.
         // This may produce pending acks which will be processed in the next phase.
         run_and_collect_events("forward recv and timeout", &mut ev_list, || {
-            fwd_link.relay_recv_packet_and_timeout_messages()
-        });
-        run_and_collect_events("reverse recv and timeout", &mut ev_list, || {
-            rev_link.relay_recv_packet_and_timeout_messages()
+            fwd_link.relay_recv_packet_and_timeout_messages(sequences)
         });
+        if clear_counterparty {
+            run_and_collect_events("reverse recv and timeout", &mut ev_list, || {
+                rev_link.relay_recv_packet_and_timeout_messages(&[])
+            });
+        }
 
         // Schedule AckPacket messages in both directions.
-        run_and_collect_events("forward ack", &mut ev_list, || {
-            fwd_link.relay_ack_packet_messages()
-        });
+        if clear_counterparty {
+            run_and_collect_events("forward ack", &mut ev_list, || {
+                fwd_link.relay_ack_packet_messages(&[])
+            });
+        }
         run_and_collect_events("reverse ack", &mut ev_list, || {
-            rev_link.relay_ack_packet_messages()
+            rev_link.relay_ack_packet_messages(sequences)
         });
 
         Output::success(ev_list).exit()
@@ -164,6 +189,7 @@ mod tests {
     use std::str::FromStr;
 
     use abscissa_core::clap::Parser;
+    use ibc_relayer_types::core::ics04_channel::packet::Sequence;
     use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
 
     #[test]
@@ -173,6 +199,7 @@ mod tests {
                 chain_id: ChainId::from_string("chain_id"),
                 port_id: PortId::from_str("port_id").unwrap(),
                 channel_id: ChannelId::from_str("channel-07").unwrap(),
+                packet_sequences: vec![],
                 key_name: None,
                 counterparty_key_name: None,
             },
@@ -195,6 +222,7 @@ mod tests {
                 chain_id: ChainId::from_string("chain_id"),
                 port_id: PortId::from_str("port_id").unwrap(),
                 channel_id: ChannelId::from_str("channel-07").unwrap(),
+                packet_sequences: vec![],
                 key_name: None,
                 counterparty_key_name: None
             },
@@ -217,6 +245,7 @@ mod tests {
                 chain_id: ChainId::from_string("chain_id"),
                 port_id: PortId::from_str("port_id").unwrap(),
                 channel_id: ChannelId::from_str("channel-07").unwrap(),
+                packet_sequences: vec![],
                 key_name: Some("key_name".to_owned()),
                 counterparty_key_name: None,
             },
@@ -241,6 +270,7 @@ mod tests {
                 chain_id: ChainId::from_string("chain_id"),
                 port_id: PortId::from_str("port_id").unwrap(),
                 channel_id: ChannelId::from_str("channel-07").unwrap(),
+                packet_sequences: vec![],
                 key_name: None,
                 counterparty_key_name: Some("counterparty_key_name".to_owned()),
             },
@@ -258,6 +288,51 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_clear_packets_sequences() {
+        assert_eq!(
+            ClearPacketsCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                port_id: PortId::from_str("port_id").unwrap(),
+                channel_id: ChannelId::from_str("channel-07").unwrap(),
+                packet_sequences: vec![
+                    Sequence::from(1)..=Sequence::from(100),
+                    Sequence::from(205)..=Sequence::from(205),
+                    Sequence::from(300)..=Sequence::from(u64::MAX),
+                ],
+                key_name: None,
+                counterparty_key_name: None,
+            },
+            ClearPacketsCmd::parse_from([
+                "test",
+                "--chain",
+                "chain_id",
+                "--port",
+                "port_id",
+                "--channel",
+                "channel-07",
+                "--packet-sequences",
+                "1..100,205,300.."
+            ])
+        )
+    }
+
+    #[test]
+    fn test_clear_packets_invalid_sequences() {
+        assert!(ClearPacketsCmd::try_parse_from([
+            "test",
+            "--chain",
+            "chain_id",
+            "--port",
+            "port_id",
+            "--channel",
+            "channel-07",
+            "--packet-sequences",
+            "100..1"
+        ])
+        .is_err())
+    }
+
     #[test]
     fn test_clear_packets_no_chan() {
         assert!(ClearPacketsCmd::try_parse_from([
@@ -0,0 +1,185 @@
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+use serde::Serialize;
+use tracing::warn;
+
+use ibc_relayer::chain::handle::BaseChainHandle;
+use ibc_relayer::link::{ChannelDiagnosis, Link, LinkParameters};
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+use ibc_relayer_types::events::IbcEvent;
+
+use crate::application::app_config;
+use crate::cli_utils::spawn_chain_counterparty;
+use crate::conclude::Output;
+
+/// `diagnose` subcommands
+#[derive(Command, Debug, Parser, Runnable)]
+pub enum DiagnoseCmds {
+    /// Diagnose an ordered channel which does not make progress, by identifying
+    /// the packet sent from the given chain which blocks the channel, and by
+    /// simulating its relaying to surface the error preventing it.
+    Channel(DiagnoseChannelCmd),
+}
+
+#[derive(Debug, Parser, Command, PartialEq, Eq)]
+pub struct DiagnoseChannelCmd {
+    #[clap(
+        long = "chain",
+        required = true,
+        value_name = "CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the chain sending the packets"
+    )]
+    chain_id: ChainId,
+
+    #[clap(
+        long = "port",
+        required = true,
+        value_name = "PORT_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the port"
+    )]
+    port_id: PortId,
+
+    #[clap(
+        long = "channel",
+        alias = "chan",
+        required = true,
+        value_name = "CHANNEL_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the channel"
+    )]
+    channel_id: ChannelId,
+
+    #[clap(
+        long = "recover",
+        help = "Time out the blocking packet on the given chain if it has expired. \
+                Timing out a packet on an ordered channel closes the channel"
+    )]
+    recover: bool,
+}
+
+/// The diagnosis of a channel, along with the events of the timeout
+/// submitted to recover from the blocking packet.
+#[derive(Debug, Serialize)]
+struct ChannelRecovery {
+    diagnosis: ChannelDiagnosis,
+    events: Vec<IbcEvent>,
+}
+
+impl Runnable for DiagnoseChannelCmd {
+    fn run(&self) {
+        let config = app_config();
+
+        let chains = match spawn_chain_counterparty::<BaseChainHandle>(
+            &config,
+            &self.chain_id,
+            &self.port_id,
+            &self.channel_id,
+        ) {
+            Ok((chains, _)) => chains,
+            Err(e) => Output::error(e).exit(),
+        };
+
+        let opts = LinkParameters {
+            src_port_id: self.port_id.clone(),
+            src_channel_id: self.channel_id.clone(),
+        };
+
+        let link = match Link::new_from_opts(chains.src, chains.dst, opts, false, false) {
+            Ok(link) => link,
+            Err(e) => Output::error(e).exit(),
+        };
+
+        let diagnosis = match link.a_to_b.diagnose_ordered_channel() {
+            Ok(diagnosis) => diagnosis,
+            Err(e) => Output::error(e).exit(),
+        };
+
+        if !self.recover {
+            Output::success(diagnosis).exit()
+        }
+
+        let sequence = match &diagnosis.blocking_packet {
+            Some(packet) if packet.timed_out => packet.sequence,
+            Some(packet) => Output::error(format!(
+                "packet {} blocking the channel has not timed out, \
+                 it can only be recovered from by receiving it on the counterparty chain",
+                packet.sequence
+            ))
+            .exit(),
+            None => Output::error("no packet is blocking the channel").exit(),
+        };
+
+        warn!(
+            "timing out packet {sequence}, which closes channel {}/{} on chain {}",
+            self.port_id, self.channel_id, self.chain_id
+        );
+
+        match link.relay_recv_packet_and_timeout_messages(&[sequence..=sequence]) {
+            Ok(events) => Output::success(ChannelRecovery { diagnosis, events }).exit(),
+            Err(e) => Output::error(e).exit(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DiagnoseChannelCmd;
+
+    use std::str::FromStr;
+
+    use abscissa_core::clap::Parser;
+    use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+
+    #[test]
+    fn test_diagnose_channel_required_only() {
+        assert_eq!(
+            DiagnoseChannelCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                port_id: PortId::from_str("port_id").unwrap(),
+                channel_id: ChannelId::from_str("channel-07").unwrap(),
+                recover: false,
+            },
+            DiagnoseChannelCmd::parse_from([
+                "test",
+                "--chain",
+                "chain_id",
+                "--port",
+                "port_id",
+                "--channel",
+                "channel-07"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_diagnose_channel_recover() {
+        assert_eq!(
+            DiagnoseChannelCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                port_id: PortId::from_str("port_id").unwrap(),
+                channel_id: ChannelId::from_str("channel-07").unwrap(),
+                recover: true,
+            },
+            DiagnoseChannelCmd::parse_from([
+                "test",
+                "--chain",
+                "chain_id",
+                "--port",
+                "port_id",
+                "--chan",
+                "channel-07",
+                "--recover"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_diagnose_channel_no_channel() {
+        assert!(DiagnoseChannelCmd::try_parse_from([
+            "test", "--chain", "chain_id", "--port", "port_id"
+        ])
+        .is_err())
+    }
+}
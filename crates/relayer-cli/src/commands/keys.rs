@@ -6,6 +6,7 @@ mod add;
 mod balance;
 mod delete;
 mod list;
+mod migrate;
 
 /// `keys` subcommand
 #[derive(Command, Debug, Parser, Runnable)]
@@ -19,6 +20,9 @@ pub enum KeysCmd {
     /// List keys configured on a chain
     List(list::KeysListCmd),
 
+    /// Migrate the plaintext keys of a chain to the encrypted key store
+    Migrate(migrate::KeysMigrateCmd),
+
     /// Query balance for a key from a configured chain. If no key is given, the key is retrieved from the configuration file.
     Balance(balance::KeyBalanceCmd),
 }
@@ -12,14 +12,13 @@ use hdpath::StandardHDPath;
 use ibc_relayer::{
     chain::ChainType,
     config::{ChainConfig, Config},
-    keyring::{
-        AnySigningKeyPair, KeyRing, Secp256k1KeyPair, SigningKeyPair, SigningKeyPairSized, Store,
-    },
+    keyring::{AnySigningKeyPair, KeyRing, Secp256k1KeyPair, SigningKeyPair, SigningKeyPairSized},
 };
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 use tracing::warn;
 
 use crate::application::app_config;
+use crate::cli_utils::unlock_key_store;
 use crate::conclude::Output;
 
 /// The data structure that represents the arguments when invoking the `keys add` CLI command.
@@ -131,6 +130,10 @@ impl Runnable for KeysAddCmd {
             Ok(result) => result,
         };
 
+        if let Err(e) = unlock_key_store([&opts.config], None) {
+            Output::error(format!("failed to unlock the key store: {e}")).exit()
+        }
+
         // Check if --key-file or --mnemonic-file was given as input.
         match (self.key_file.clone(), self.mnemonic_file.clone()) {
             (Some(key_file), _) => {
@@ -199,9 +202,9 @@ pub fn add_key(
     overwrite: bool,
 ) -> eyre::Result<AnySigningKeyPair> {
     let key_pair = match config.r#type {
-        ChainType::CosmosSdk => {
+        ChainType::CosmosSdk | ChainType::Mock | ChainType::SoloMachine => {
             let mut keyring = KeyRing::new_secp256k1(
-                Store::Test,
+                config.key_store_type.persistent(),
                 &config.account_prefix,
                 &config.id,
                 &config.key_store_folder,
@@ -232,9 +235,9 @@ pub fn restore_key(
         fs::read_to_string(mnemonic).map_err(|_| eyre!("error reading the mnemonic file"))?;
 
     let key_pair = match config.r#type {
-        ChainType::CosmosSdk => {
+        ChainType::CosmosSdk | ChainType::Mock | ChainType::SoloMachine => {
             let mut keyring = KeyRing::new_secp256k1(
-                Store::Test,
+                config.key_store_type.persistent(),
                 &config.account_prefix,
                 &config.id,
                 &config.key_store_folder,
@@ -5,11 +5,12 @@ use eyre::eyre;
 use ibc_relayer::{
     chain::ChainType,
     config::{ChainConfig, Config},
-    keyring::{KeyRing, Store},
+    keyring::KeyRing,
 };
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 
 use crate::application::app_config;
+use crate::cli_utils::unlock_key_store;
 use crate::conclude::Output;
 
 #[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
@@ -92,6 +93,10 @@ impl Runnable for KeysDeleteCmd {
             Ok(result) => result,
         };
 
+        if let Err(e) = unlock_key_store([&opts.config], None) {
+            Output::error(format!("failed to unlock the key store: {e}")).exit()
+        }
+
         match opts.id {
             KeysDeleteId::All => match delete_all_keys(&opts.config) {
                 Ok(_) => {
@@ -114,9 +119,9 @@ impl Runnable for KeysDeleteCmd {
 
 pub fn delete_key(config: &ChainConfig, key_name: &str) -> eyre::Result<()> {
     match config.r#type {
-        ChainType::CosmosSdk => {
+        ChainType::CosmosSdk | ChainType::Mock | ChainType::SoloMachine => {
             let mut keyring = KeyRing::new_secp256k1(
-                Store::Test,
+                config.key_store_type.persistent(),
                 &config.account_prefix,
                 &config.id,
                 &config.key_store_folder,
@@ -129,9 +134,9 @@ pub fn delete_key(config: &ChainConfig, key_name: &str) -> eyre::Result<()> {
 
 pub fn delete_all_keys(config: &ChainConfig) -> eyre::Result<()> {
     match config.r#type {
-        ChainType::CosmosSdk => {
+        ChainType::CosmosSdk | ChainType::Mock | ChainType::SoloMachine => {
             let mut keyring = KeyRing::new_secp256k1(
-                Store::Test,
+                config.key_store_type.persistent(),
                 &config.account_prefix,
                 &config.id,
                 &config.key_store_folder,
@@ -4,6 +4,7 @@ use core::fmt::Write;
 use abscissa_core::clap::Parser;
 use abscissa_core::{Command, Runnable};
 
+use crate::cli_utils::unlock_key_store;
 use crate::conclude::Output;
 use crate::{application::app_config, conclude::json};
 use ibc_relayer::{
@@ -45,6 +46,10 @@ impl Runnable for KeysListCmd {
             Ok(result) => result,
         };
 
+        if let Err(e) = unlock_key_store([&opts.chain_config], None) {
+            Output::error(format!("failed to unlock the key store: {e}")).exit()
+        }
+
         match list_keys(&opts.chain_config) {
             Ok(keys) if json() => {
                 let keys = keys.into_iter().collect::<HashMap<_, _>>();
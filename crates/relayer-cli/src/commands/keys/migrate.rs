@@ -0,0 +1,105 @@
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+
+use ibc_relayer::keyring::{migrate_to_encrypted, Store};
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+use crate::application::app_config;
+use crate::cli_utils::unlock_key_store;
+use crate::conclude::Output;
+
+/// The data structure that represents the arguments when invoking the `keys migrate` CLI command.
+///
+/// `keys migrate --chain <CHAIN_ID> [--remove-plaintext]`
+///
+/// Encrypts every key of the plaintext `keyring-test` store of the chain
+/// and moves it into the chain's encrypted key store.
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct KeysMigrateCmd {
+    #[clap(
+        long = "chain",
+        required = true,
+        value_name = "CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the chain"
+    )]
+    chain_id: ChainId,
+
+    #[clap(
+        long = "remove-plaintext",
+        help = "Remove the plaintext key files once they have been migrated"
+    )]
+    remove_plaintext: bool,
+}
+
+impl Runnable for KeysMigrateCmd {
+    fn run(&self) {
+        let config = app_config();
+
+        let mut chain_config = match config.find_chain(&self.chain_id) {
+            Some(chain_config) => chain_config.clone(),
+            None => Output::error(format!(
+                "chain '{}' not found in configuration file",
+                self.chain_id
+            ))
+            .exit(),
+        };
+
+        // The target of the migration is always the encrypted store,
+        // regardless of the store currently configured for the chain.
+        chain_config.key_store_type = Store::Encrypted;
+
+        if let Err(e) = unlock_key_store([&chain_config], None) {
+            Output::error(format!("failed to unlock the key store: {e}")).exit()
+        }
+
+        match migrate_to_encrypted(&chain_config, self.remove_plaintext) {
+            Ok(migrated) if migrated.is_empty() => {
+                Output::success_msg(format!("No keys to migrate on chain {}", chain_config.id))
+                    .exit()
+            }
+            Ok(migrated) => Output::success_msg(format!(
+                "Migrated keys [{}] on chain {} to the encrypted key store",
+                migrated.join(", "),
+                chain_config.id
+            ))
+            .exit(),
+            Err(e) => Output::error(e).exit(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::KeysMigrateCmd;
+
+    use abscissa_core::clap::Parser;
+    use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+    #[test]
+    fn test_keys_migrate() {
+        assert_eq!(
+            KeysMigrateCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                remove_plaintext: false
+            },
+            KeysMigrateCmd::parse_from(["test", "--chain", "chain_id"])
+        )
+    }
+
+    #[test]
+    fn test_keys_migrate_remove_plaintext() {
+        assert_eq!(
+            KeysMigrateCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                remove_plaintext: true
+            },
+            KeysMigrateCmd::parse_from(["test", "--chain", "chain_id", "--remove-plaintext"])
+        )
+    }
+
+    #[test]
+    fn test_keys_migrate_no_chain() {
+        assert!(KeysMigrateCmd::try_parse_from(["test"]).is_err())
+    }
+}
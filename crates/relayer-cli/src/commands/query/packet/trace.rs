@@ -0,0 +1,193 @@
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+use serde::Deserialize;
+
+use ibc_relayer::link::journal::PacketTrace;
+use ibc_relayer_types::core::ics04_channel::packet::Sequence;
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+
+use crate::conclude::Output;
+use crate::error::Error;
+use crate::prelude::*;
+
+/// The response of the REST API of a running Hermes instance.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "status", content = "result")]
+#[serde(rename_all = "lowercase")]
+enum RestResponse {
+    Success(Option<Box<PacketTrace>>),
+    Error(serde_json::Value),
+}
+
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct QueryPacketTraceCmd {
+    #[clap(
+        long = "chain",
+        required = true,
+        value_name = "CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the chain which sent the packet"
+    )]
+    chain_id: ChainId,
+
+    #[clap(
+        long = "port",
+        required = true,
+        value_name = "PORT_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the port which sent the packet"
+    )]
+    port_id: PortId,
+
+    #[clap(
+        long = "channel",
+        visible_alias = "chan",
+        required = true,
+        value_name = "CHANNEL_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the channel which sent the packet"
+    )]
+    channel_id: ChannelId,
+
+    #[clap(
+        long = "sequence",
+        visible_alias = "seq",
+        required = true,
+        value_name = "SEQUENCE",
+        help_heading = "REQUIRED",
+        help = "Sequence of the packet to trace"
+    )]
+    sequence: Sequence,
+}
+
+impl QueryPacketTraceCmd {
+    /// The packet journal lives in the memory of the running Hermes instance,
+    /// so it is queried through the instance's REST API.
+    fn execute(&self) -> Result<Option<Box<PacketTrace>>, Error> {
+        let config = app_config();
+
+        if !config.rest.enabled {
+            return Err(Error::cli_arg(
+                "the REST API must be enabled in the configuration to query the packet journal \
+                 of the running Hermes instance"
+                    .to_owned(),
+            ));
+        }
+
+        let url = format!(
+            "http://{}:{}/packet/trace",
+            config.rest.host, config.rest.port
+        );
+
+        let to_error = |e: reqwest::Error| {
+            Error::cli_arg(format!("failed to query the REST API at {url}: {e}"))
+        };
+
+        let response: RestResponse = reqwest::blocking::Client::new()
+            .get(&url)
+            .query(&[
+                ("chain", self.chain_id.to_string()),
+                ("port", self.port_id.to_string()),
+                ("channel", self.channel_id.to_string()),
+                ("sequence", self.sequence.to_string()),
+            ])
+            .send()
+            .and_then(|response| response.error_for_status())
+            .and_then(|response| response.json())
+            .map_err(to_error)?;
+
+        match response {
+            RestResponse::Success(trace) => Ok(trace),
+            RestResponse::Error(e) => Err(Error::cli_arg(format!(
+                "the REST API at {url} returned an error: {e}"
+            ))),
+        }
+    }
+}
+
+impl Runnable for QueryPacketTraceCmd {
+    fn run(&self) {
+        match self.execute() {
+            Ok(Some(trace)) => Output::success(trace).exit(),
+            Ok(None) => Output::error(format!(
+                "packet {} sent on {}/{} from chain {} is not in the packet journal",
+                self.sequence, self.port_id, self.channel_id, self.chain_id
+            ))
+            .exit(),
+            Err(e) => Output::error(e).exit(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::QueryPacketTraceCmd;
+
+    use std::str::FromStr;
+
+    use abscissa_core::clap::Parser;
+    use ibc_relayer_types::core::{
+        ics04_channel::packet::Sequence,
+        ics24_host::identifier::{ChainId, ChannelId, PortId},
+    };
+
+    #[test]
+    fn test_query_packet_trace_required_only() {
+        assert_eq!(
+            QueryPacketTraceCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                port_id: PortId::from_str("port_id").unwrap(),
+                channel_id: ChannelId::from_str("channel-07").unwrap(),
+                sequence: Sequence::from(42),
+            },
+            QueryPacketTraceCmd::parse_from([
+                "test",
+                "--chain",
+                "chain_id",
+                "--port",
+                "port_id",
+                "--channel",
+                "channel-07",
+                "--sequence",
+                "42"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_query_packet_trace_aliases() {
+        assert_eq!(
+            QueryPacketTraceCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                port_id: PortId::from_str("port_id").unwrap(),
+                channel_id: ChannelId::from_str("channel-07").unwrap(),
+                sequence: Sequence::from(42),
+            },
+            QueryPacketTraceCmd::parse_from([
+                "test",
+                "--chain",
+                "chain_id",
+                "--port",
+                "port_id",
+                "--chan",
+                "channel-07",
+                "--seq",
+                "42"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_query_packet_trace_no_seq() {
+        assert!(QueryPacketTraceCmd::try_parse_from([
+            "test",
+            "--chain",
+            "chain_id",
+            "--port",
+            "port_id",
+            "--channel",
+            "channel-07"
+        ])
+        .is_err())
+    }
+}
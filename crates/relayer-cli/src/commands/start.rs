@@ -1,7 +1,9 @@
 use ibc_relayer::supervisor::SupervisorOptions;
 use ibc_relayer::util::debug_section::DebugSection;
+use ibc_relayer::util::dry_run;
 use std::error::Error;
 use std::io;
+use std::path::PathBuf;
 
 use abscissa_core::clap::Parser;
 use abscissa_core::{Command, Runnable};
@@ -13,6 +15,7 @@ use ibc_relayer::registry::SharedRegistry;
 use ibc_relayer::rest;
 use ibc_relayer::supervisor::{cmd::SupervisorCmd, spawn_supervisor, SupervisorHandle};
 
+use crate::cli_utils::unlock_key_store;
 use crate::conclude::json;
 use crate::conclude::Output;
 use crate::prelude::*;
@@ -24,6 +27,13 @@ pub struct StartCmd {
         help = "Force a full scan of the chains for clients, connections and channels"
     )]
     full_scan: bool,
+
+    #[clap(
+        long = "passphrase-file",
+        value_name = "PASSPHRASE_FILE",
+        help = "Path to a file containing the passphrase of the encrypted key store"
+    )]
+    passphrase_file: Option<PathBuf>,
 }
 
 impl Runnable for StartCmd {
@@ -54,11 +64,20 @@ impl Runnable for StartCmd {
             open_or_create_profile_file(Path::new(&path_str));
         }
 
+        if dry_run::is_enabled() {
+            warn!("running in dry-run mode, transactions will be simulated but never broadcast");
+        }
+
         let config = (*app_config()).clone();
 
+        unlock_key_store(&config.chains, self.passphrase_file.as_deref()).unwrap_or_else(|e| {
+            Output::error(format!("failed to unlock the key store: {e}")).exit()
+        });
+
         let options = SupervisorOptions {
             force_full_scan: self.full_scan,
             health_check: true,
+            config_path: crate::config::config_path(),
         };
 
         let supervisor_handle = make_supervisor::<CachingChainHandle>(config, options)
@@ -84,13 +103,13 @@ impl Runnable for StartCmd {
 }
 
 /// Register the SIGHUP and SIGUSR1 signals, and notify the supervisor.
-/// - [DEPRECATED] SIGHUP: Trigger a reload of the configuration.
+/// - SIGHUP: Ask the supervisor to reload the configuration from the configuration file.
 /// - SIGUSR1: Ask the supervisor to dump its state and print it to the console.
 fn register_signals(tx_cmd: Sender<SupervisorCmd>) -> Result<(), io::Error> {
     use signal_hook::{consts::signal::*, iterator::Signals};
 
     let sigs = vec![
-        SIGHUP,  // Reload of configuration (disabled)
+        SIGHUP,  // Reload of configuration
         SIGUSR1, // Dump state
     ];
 
@@ -99,10 +118,18 @@ fn register_signals(tx_cmd: Sender<SupervisorCmd>) -> Result<(), io::Error> {
     std::thread::spawn(move || {
         for signal in &mut signals {
             match signal {
-                SIGHUP => warn!(
-                    "configuration reloading via SIGHUP has been disabled, \
-                     the signal handler will be removed in the future"
-                ),
+                SIGHUP => {
+                    info!("reloading configuration (triggered by SIGHUP)");
+
+                    let (tx, rx) = crossbeam_channel::bounded(1);
+                    tx_cmd.try_send(SupervisorCmd::ReloadConfig(tx)).unwrap();
+
+                    std::thread::spawn(move || {
+                        if let Ok(Err(e)) = rx.recv() {
+                            warn!("configuration was not reloaded, the previous configuration remains in use: {}", e);
+                        }
+                    });
+                }
                 SIGUSR1 => {
                     info!("dumping state (triggered by SIGUSR1)");
 
@@ -149,7 +176,8 @@ fn spawn_rest_server(config: &Config) -> Option<rest::Receiver> {
     let (tx, rx) = crossbeam_channel::unbounded();
 
     spawn_blocking(async move {
-        let result = ibc_relayer_rest::spawn((rest.host.as_str(), rest.port), tx);
+        let result =
+            ibc_relayer_rest::spawn((rest.host.as_str(), rest.port), tx, rest.auth_token.clone());
 
         match result {
             Ok(handle) => {
@@ -236,6 +264,13 @@ fn make_supervisor<Chain: ChainHandle>(
     config: Config,
     options: SupervisorOptions,
 ) -> Result<SupervisorHandle, Box<dyn Error + Send + Sync>> {
+    // Open the state store before spawning the chain runtimes,
+    // so that their event sources can resume from the stored heights.
+    // Nothing is relayed in dry-run mode, so there is no state to persist.
+    if !dry_run::is_enabled() {
+        ibc_relayer::store::init(&config.state_store)?;
+    }
+
     let registry = SharedRegistry::<Chain>::new(config.clone());
 
     spawn_telemetry_server(&config);
@@ -249,12 +284,17 @@ fn make_supervisor<Chain: ChainHandle>(
 mod tests {
     use super::StartCmd;
 
+    use std::path::PathBuf;
+
     use abscissa_core::clap::Parser;
 
     #[test]
     fn test_start_required_only() {
         assert_eq!(
-            StartCmd { full_scan: false },
+            StartCmd {
+                full_scan: false,
+                passphrase_file: None
+            },
             StartCmd::parse_from(["test"])
         )
     }
@@ -262,8 +302,22 @@ mod tests {
     #[test]
     fn test_start_full_scan() {
         assert_eq!(
-            StartCmd { full_scan: true },
+            StartCmd {
+                full_scan: true,
+                passphrase_file: None
+            },
             StartCmd::parse_from(["test", "--full-scan"])
         )
     }
+
+    #[test]
+    fn test_start_passphrase_file() {
+        assert_eq!(
+            StartCmd {
+                full_scan: false,
+                passphrase_file: Some(PathBuf::from("/tmp/passphrase"))
+            },
+            StartCmd::parse_from(["test", "--passphrase-file", "/tmp/passphrase"])
+        )
+    }
 }
@@ -44,6 +44,27 @@ pub enum TxCmd {
     /// Confirm the closing of a channel (ChannelCloseConfirm)
     ChanCloseConfirm(channel::TxChanCloseConfirmCmd),
 
+    /// Initiate the upgrade of a channel (ChannelUpgradeInit)
+    ChanUpgradeInit(channel::TxChanUpgradeInitCmd),
+
+    /// Relay the channel upgrade attempt (ChannelUpgradeTry)
+    ChanUpgradeTry(channel::TxChanUpgradeTryCmd),
+
+    /// Relay acknowledgment of a channel upgrade attempt (ChannelUpgradeAck)
+    ChanUpgradeAck(channel::TxChanUpgradeAckCmd),
+
+    /// Relay confirmation of a channel upgrade (ChannelUpgradeConfirm)
+    ChanUpgradeConfirm(channel::TxChanUpgradeConfirmCmd),
+
+    /// Complete the upgrade of a channel (ChannelUpgradeOpen)
+    ChanUpgradeOpen(channel::TxChanUpgradeOpenCmd),
+
+    /// Abort a timed out channel upgrade (ChannelUpgradeTimeout)
+    ChanUpgradeTimeout(channel::TxChanUpgradeTimeoutCmd),
+
+    /// Cancel a failed channel upgrade (ChannelUpgradeCancel)
+    ChanUpgradeCancel(channel::TxChanUpgradeCancelCmd),
+
     /// Send a fungible token transfer test transaction (ICS20 MsgTransfer)
     FtTransfer(transfer::TxIcs20MsgTransferCmd),
 
@@ -6,6 +6,7 @@ use ibc_relayer::chain::requests::{IncludeProof, QueryConnectionRequest, QueryHe
 use ibc_relayer::channel::{Channel, ChannelSide};
 use ibc_relayer_types::core::ics03_connection::connection::ConnectionEnd;
 use ibc_relayer_types::core::ics04_channel::channel::Ordering;
+use ibc_relayer_types::core::ics04_channel::version::Version;
 use ibc_relayer_types::core::ics24_host::identifier::{
     ChainId, ChannelId, ClientId, ConnectionId, PortId,
 };
@@ -666,18 +667,776 @@ impl Runnable for TxChanCloseConfirmCmd {
     }
 }
 
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct TxChanUpgradeInitCmd {
+    #[clap(
+        long = "dst-chain",
+        required = true,
+        value_name = "DST_CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the destination chain"
+    )]
+    dst_chain_id: ChainId,
+
+    #[clap(
+        long = "src-chain",
+        required = true,
+        value_name = "SRC_CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the source chain"
+    )]
+    src_chain_id: ChainId,
+
+    #[clap(
+        long = "dst-connection",
+        visible_alias = "dst-conn",
+        required = true,
+        value_name = "DST_CONNECTION_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the destination connection"
+    )]
+    dst_conn_id: ConnectionId,
+
+    #[clap(
+        long = "dst-port",
+        required = true,
+        value_name = "DST_PORT_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the destination port"
+    )]
+    dst_port_id: PortId,
+
+    #[clap(
+        long = "src-port",
+        required = true,
+        value_name = "SRC_PORT_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the source port"
+    )]
+    src_port_id: PortId,
+
+    #[clap(
+        long = "dst-channel",
+        visible_alias = "dst-chan",
+        required = true,
+        value_name = "DST_CHANNEL_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the destination channel (required)"
+    )]
+    dst_chan_id: ChannelId,
+
+    #[clap(
+        long = "src-channel",
+        visible_alias = "src-chan",
+        required = true,
+        value_name = "SRC_CHANNEL_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the source channel (required)"
+    )]
+    src_chan_id: ChannelId,
+
+    #[clap(
+        long = "version",
+        value_name = "VERSION",
+        help = "Version proposed for the upgraded channel (default: the current channel version)"
+    )]
+    version: Option<Version>,
+
+    #[clap(
+        long = "ordering",
+        value_name = "ORDERING",
+        help = "Ordering proposed for the upgraded channel, valid options 'unordered' and 'ordered' \
+                (default: the current channel ordering)"
+    )]
+    ordering: Option<Ordering>,
+
+    #[clap(
+        long = "connection-hops",
+        value_name = "CONNECTION_HOPS",
+        value_delimiter = ',',
+        help = "Connection hops proposed for the upgraded channel, comma separated \
+                (default: the current channel connection hops)"
+    )]
+    connection_hops: Option<Vec<ConnectionId>>,
+}
+
+impl Runnable for TxChanUpgradeInitCmd {
+    fn run(&self) {
+        let config = app_config();
+
+        let chains = match ChainHandlePair::spawn(&config, &self.src_chain_id, &self.dst_chain_id) {
+            Ok(chains) => chains,
+            Err(e) => Output::error(e).exit(),
+        };
+
+        // Retrieve the connection
+        let dst_connection = match chains.dst.query_connection(
+            QueryConnectionRequest {
+                connection_id: self.dst_conn_id.clone(),
+                height: QueryHeight::Latest,
+            },
+            IncludeProof::No,
+        ) {
+            Ok((connection, _)) => connection,
+            Err(e) => Output::error(e).exit(),
+        };
+
+        let channel = Channel {
+            connection_delay: Default::default(),
+            ordering: Ordering::default(),
+            a_side: ChannelSide::new(
+                chains.src,
+                ClientId::default(),
+                ConnectionId::default(),
+                self.src_port_id.clone(),
+                Some(self.src_chan_id.clone()),
+                None,
+            ),
+            b_side: ChannelSide::new(
+                chains.dst,
+                dst_connection.client_id().clone(),
+                self.dst_conn_id.clone(),
+                self.dst_port_id.clone(),
+                Some(self.dst_chan_id.clone()),
+                None,
+            ),
+        };
+
+        info!("message ChanUpgradeInit: {}", channel);
+
+        let res: Result<IbcEvent, Error> = channel
+            .build_chan_upgrade_init_and_send(
+                self.version.clone(),
+                self.ordering,
+                self.connection_hops.clone(),
+            )
+            .map_err(Error::channel);
+
+        match res {
+            Ok(receipt) => Output::success(receipt).exit(),
+            Err(e) => Output::error(e).exit(),
+        }
+    }
+}
+
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct TxChanUpgradeTryCmd {
+    #[clap(
+        long = "dst-chain",
+        required = true,
+        value_name = "DST_CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the destination chain"
+    )]
+    dst_chain_id: ChainId,
+
+    #[clap(
+        long = "src-chain",
+        required = true,
+        value_name = "SRC_CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the source chain"
+    )]
+    src_chain_id: ChainId,
+
+    #[clap(
+        long = "dst-connection",
+        visible_alias = "dst-conn",
+        required = true,
+        value_name = "DST_CONNECTION_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the destination connection"
+    )]
+    dst_conn_id: ConnectionId,
+
+    #[clap(
+        long = "dst-port",
+        required = true,
+        value_name = "DST_PORT_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the destination port"
+    )]
+    dst_port_id: PortId,
+
+    #[clap(
+        long = "src-port",
+        required = true,
+        value_name = "SRC_PORT_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the source port"
+    )]
+    src_port_id: PortId,
+
+    #[clap(
+        long = "dst-channel",
+        visible_alias = "dst-chan",
+        required = true,
+        value_name = "DST_CHANNEL_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the destination channel (required)"
+    )]
+    dst_chan_id: ChannelId,
+
+    #[clap(
+        long = "src-channel",
+        visible_alias = "src-chan",
+        required = true,
+        value_name = "SRC_CHANNEL_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the source channel (required)"
+    )]
+    src_chan_id: ChannelId,
+}
+
+impl Runnable for TxChanUpgradeTryCmd {
+    fn run(&self) {
+        tx_chan_cmd!(
+            "ChanUpgradeTry",
+            build_chan_upgrade_try_and_send,
+            self,
+            |chains: ChainHandlePair, dst_connection: ConnectionEnd| {
+                Channel {
+                    connection_delay: Default::default(),
+                    ordering: Ordering::default(),
+                    a_side: ChannelSide::new(
+                        chains.src,
+                        ClientId::default(),
+                        ConnectionId::default(),
+                        self.src_port_id.clone(),
+                        Some(self.src_chan_id.clone()),
+                        None,
+                    ),
+                    b_side: ChannelSide::new(
+                        chains.dst,
+                        dst_connection.client_id().clone(),
+                        self.dst_conn_id.clone(),
+                        self.dst_port_id.clone(),
+                        Some(self.dst_chan_id.clone()),
+                        None,
+                    ),
+                }
+            }
+        );
+    }
+}
+
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct TxChanUpgradeAckCmd {
+    #[clap(
+        long = "dst-chain",
+        required = true,
+        value_name = "DST_CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the destination chain"
+    )]
+    dst_chain_id: ChainId,
+
+    #[clap(
+        long = "src-chain",
+        required = true,
+        value_name = "SRC_CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the source chain"
+    )]
+    src_chain_id: ChainId,
+
+    #[clap(
+        long = "dst-connection",
+        visible_alias = "dst-conn",
+        required = true,
+        value_name = "DST_CONNECTION_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the destination connection"
+    )]
+    dst_conn_id: ConnectionId,
+
+    #[clap(
+        long = "dst-port",
+        required = true,
+        value_name = "DST_PORT_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the destination port"
+    )]
+    dst_port_id: PortId,
+
+    #[clap(
+        long = "src-port",
+        required = true,
+        value_name = "SRC_PORT_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the source port"
+    )]
+    src_port_id: PortId,
+
+    #[clap(
+        long = "dst-channel",
+        visible_alias = "dst-chan",
+        required = true,
+        value_name = "DST_CHANNEL_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the destination channel (required)"
+    )]
+    dst_chan_id: ChannelId,
+
+    #[clap(
+        long = "src-channel",
+        visible_alias = "src-chan",
+        required = true,
+        value_name = "SRC_CHANNEL_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the source channel (required)"
+    )]
+    src_chan_id: ChannelId,
+}
+
+impl Runnable for TxChanUpgradeAckCmd {
+    fn run(&self) {
+        tx_chan_cmd!(
+            "ChanUpgradeAck",
+            build_chan_upgrade_ack_and_send,
+            self,
+            |chains: ChainHandlePair, dst_connection: ConnectionEnd| {
+                Channel {
+                    connection_delay: Default::default(),
+                    ordering: Ordering::default(),
+                    a_side: ChannelSide::new(
+                        chains.src,
+                        ClientId::default(),
+                        ConnectionId::default(),
+                        self.src_port_id.clone(),
+                        Some(self.src_chan_id.clone()),
+                        None,
+                    ),
+                    b_side: ChannelSide::new(
+                        chains.dst,
+                        dst_connection.client_id().clone(),
+                        self.dst_conn_id.clone(),
+                        self.dst_port_id.clone(),
+                        Some(self.dst_chan_id.clone()),
+                        None,
+                    ),
+                }
+            }
+        );
+    }
+}
+
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct TxChanUpgradeConfirmCmd {
+    #[clap(
+        long = "dst-chain",
+        required = true,
+        value_name = "DST_CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the destination chain"
+    )]
+    dst_chain_id: ChainId,
+
+    #[clap(
+        long = "src-chain",
+        required = true,
+        value_name = "SRC_CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the source chain"
+    )]
+    src_chain_id: ChainId,
+
+    #[clap(
+        long = "dst-connection",
+        visible_alias = "dst-conn",
+        required = true,
+        value_name = "DST_CONNECTION_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the destination connection"
+    )]
+    dst_conn_id: ConnectionId,
+
+    #[clap(
+        long = "dst-port",
+        required = true,
+        value_name = "DST_PORT_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the destination port"
+    )]
+    dst_port_id: PortId,
+
+    #[clap(
+        long = "src-port",
+        required = true,
+        value_name = "SRC_PORT_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the source port"
+    )]
+    src_port_id: PortId,
+
+    #[clap(
+        long = "dst-channel",
+        visible_alias = "dst-chan",
+        required = true,
+        value_name = "DST_CHANNEL_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the destination channel (required)"
+    )]
+    dst_chan_id: ChannelId,
+
+    #[clap(
+        long = "src-channel",
+        visible_alias = "src-chan",
+        required = true,
+        value_name = "SRC_CHANNEL_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the source channel (required)"
+    )]
+    src_chan_id: ChannelId,
+}
+
+impl Runnable for TxChanUpgradeConfirmCmd {
+    fn run(&self) {
+        tx_chan_cmd!(
+            "ChanUpgradeConfirm",
+            build_chan_upgrade_confirm_and_send,
+            self,
+            |chains: ChainHandlePair, dst_connection: ConnectionEnd| {
+                Channel {
+                    connection_delay: Default::default(),
+                    ordering: Ordering::default(),
+                    a_side: ChannelSide::new(
+                        chains.src,
+                        ClientId::default(),
+                        ConnectionId::default(),
+                        self.src_port_id.clone(),
+                        Some(self.src_chan_id.clone()),
+                        None,
+                    ),
+                    b_side: ChannelSide::new(
+                        chains.dst,
+                        dst_connection.client_id().clone(),
+                        self.dst_conn_id.clone(),
+                        self.dst_port_id.clone(),
+                        Some(self.dst_chan_id.clone()),
+                        None,
+                    ),
+                }
+            }
+        );
+    }
+}
+
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct TxChanUpgradeOpenCmd {
+    #[clap(
+        long = "dst-chain",
+        required = true,
+        value_name = "DST_CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the destination chain"
+    )]
+    dst_chain_id: ChainId,
+
+    #[clap(
+        long = "src-chain",
+        required = true,
+        value_name = "SRC_CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the source chain"
+    )]
+    src_chain_id: ChainId,
+
+    #[clap(
+        long = "dst-connection",
+        visible_alias = "dst-conn",
+        required = true,
+        value_name = "DST_CONNECTION_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the destination connection"
+    )]
+    dst_conn_id: ConnectionId,
+
+    #[clap(
+        long = "dst-port",
+        required = true,
+        value_name = "DST_PORT_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the destination port"
+    )]
+    dst_port_id: PortId,
+
+    #[clap(
+        long = "src-port",
+        required = true,
+        value_name = "SRC_PORT_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the source port"
+    )]
+    src_port_id: PortId,
+
+    #[clap(
+        long = "dst-channel",
+        visible_alias = "dst-chan",
+        required = true,
+        value_name = "DST_CHANNEL_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the destination channel (required)"
+    )]
+    dst_chan_id: ChannelId,
+
+    #[clap(
+        long = "src-channel",
+        visible_alias = "src-chan",
+        required = true,
+        value_name = "SRC_CHANNEL_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the source channel (required)"
+    )]
+    src_chan_id: ChannelId,
+}
+
+impl Runnable for TxChanUpgradeOpenCmd {
+    fn run(&self) {
+        tx_chan_cmd!(
+            "ChanUpgradeOpen",
+            build_chan_upgrade_open_and_send,
+            self,
+            |chains: ChainHandlePair, dst_connection: ConnectionEnd| {
+                Channel {
+                    connection_delay: Default::default(),
+                    ordering: Ordering::default(),
+                    a_side: ChannelSide::new(
+                        chains.src,
+                        ClientId::default(),
+                        ConnectionId::default(),
+                        self.src_port_id.clone(),
+                        Some(self.src_chan_id.clone()),
+                        None,
+                    ),
+                    b_side: ChannelSide::new(
+                        chains.dst,
+                        dst_connection.client_id().clone(),
+                        self.dst_conn_id.clone(),
+                        self.dst_port_id.clone(),
+                        Some(self.dst_chan_id.clone()),
+                        None,
+                    ),
+                }
+            }
+        );
+    }
+}
+
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct TxChanUpgradeTimeoutCmd {
+    #[clap(
+        long = "dst-chain",
+        required = true,
+        value_name = "DST_CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the destination chain"
+    )]
+    dst_chain_id: ChainId,
+
+    #[clap(
+        long = "src-chain",
+        required = true,
+        value_name = "SRC_CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the source chain"
+    )]
+    src_chain_id: ChainId,
+
+    #[clap(
+        long = "dst-connection",
+        visible_alias = "dst-conn",
+        required = true,
+        value_name = "DST_CONNECTION_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the destination connection"
+    )]
+    dst_conn_id: ConnectionId,
+
+    #[clap(
+        long = "dst-port",
+        required = true,
+        value_name = "DST_PORT_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the destination port"
+    )]
+    dst_port_id: PortId,
+
+    #[clap(
+        long = "src-port",
+        required = true,
+        value_name = "SRC_PORT_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the source port"
+    )]
+    src_port_id: PortId,
+
+    #[clap(
+        long = "dst-channel",
+        visible_alias = "dst-chan",
+        required = true,
+        value_name = "DST_CHANNEL_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the destination channel (required)"
+    )]
+    dst_chan_id: ChannelId,
+
+    #[clap(
+        long = "src-channel",
+        visible_alias = "src-chan",
+        required = true,
+        value_name = "SRC_CHANNEL_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the source channel (required)"
+    )]
+    src_chan_id: ChannelId,
+}
+
+impl Runnable for TxChanUpgradeTimeoutCmd {
+    fn run(&self) {
+        tx_chan_cmd!(
+            "ChanUpgradeTimeout",
+            build_chan_upgrade_timeout_and_send,
+            self,
+            |chains: ChainHandlePair, dst_connection: ConnectionEnd| {
+                Channel {
+                    connection_delay: Default::default(),
+                    ordering: Ordering::default(),
+                    a_side: ChannelSide::new(
+                        chains.src,
+                        ClientId::default(),
+                        ConnectionId::default(),
+                        self.src_port_id.clone(),
+                        Some(self.src_chan_id.clone()),
+                        None,
+                    ),
+                    b_side: ChannelSide::new(
+                        chains.dst,
+                        dst_connection.client_id().clone(),
+                        self.dst_conn_id.clone(),
+                        self.dst_port_id.clone(),
+                        Some(self.dst_chan_id.clone()),
+                        None,
+                    ),
+                }
+            }
+        );
+    }
+}
+
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct TxChanUpgradeCancelCmd {
+    #[clap(
+        long = "dst-chain",
+        required = true,
+        value_name = "DST_CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the destination chain"
+    )]
+    dst_chain_id: ChainId,
+
+    #[clap(
+        long = "src-chain",
+        required = true,
+        value_name = "SRC_CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the source chain"
+    )]
+    src_chain_id: ChainId,
+
+    #[clap(
+        long = "dst-connection",
+        visible_alias = "dst-conn",
+        required = true,
+        value_name = "DST_CONNECTION_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the destination connection"
+    )]
+    dst_conn_id: ConnectionId,
+
+    #[clap(
+        long = "dst-port",
+        required = true,
+        value_name = "DST_PORT_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the destination port"
+    )]
+    dst_port_id: PortId,
+
+    #[clap(
+        long = "src-port",
+        required = true,
+        value_name = "SRC_PORT_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the source port"
+    )]
+    src_port_id: PortId,
+
+    #[clap(
+        long = "dst-channel",
+        visible_alias = "dst-chan",
+        required = true,
+        value_name = "DST_CHANNEL_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the destination channel (required)"
+    )]
+    dst_chan_id: ChannelId,
+
+    #[clap(
+        long = "src-channel",
+        visible_alias = "src-chan",
+        required = true,
+        value_name = "SRC_CHANNEL_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the source channel (required)"
+    )]
+    src_chan_id: ChannelId,
+}
+
+impl Runnable for TxChanUpgradeCancelCmd {
+    fn run(&self) {
+        tx_chan_cmd!(
+            "ChanUpgradeCancel",
+            build_chan_upgrade_cancel_and_send,
+            self,
+            |chains: ChainHandlePair, dst_connection: ConnectionEnd| {
+                Channel {
+                    connection_delay: Default::default(),
+                    ordering: Ordering::default(),
+                    a_side: ChannelSide::new(
+                        chains.src,
+                        ClientId::default(),
+                        ConnectionId::default(),
+                        self.src_port_id.clone(),
+                        Some(self.src_chan_id.clone()),
+                        None,
+                    ),
+                    b_side: ChannelSide::new(
+                        chains.dst,
+                        dst_connection.client_id().clone(),
+                        self.dst_conn_id.clone(),
+                        self.dst_port_id.clone(),
+                        Some(self.dst_chan_id.clone()),
+                        None,
+                    ),
+                }
+            }
+        );
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::{
         TxChanCloseConfirmCmd, TxChanCloseInitCmd, TxChanOpenAckCmd, TxChanOpenConfirmCmd,
-        TxChanOpenInitCmd, TxChanOpenTryCmd,
+        TxChanOpenInitCmd, TxChanOpenTryCmd, TxChanUpgradeInitCmd, TxChanUpgradeTryCmd,
     };
 
     use std::str::FromStr;
 
     use abscissa_core::clap::Parser;
     use ibc_relayer_types::core::{
-        ics04_channel::channel::Ordering,
+        ics04_channel::{channel::Ordering, version::Version},
         ics24_host::identifier::{ChainId, ChannelId, ConnectionId, PortId},
     };
 
@@ -1861,4 +2620,155 @@ mod tests {
         ])
         .is_err())
     }
+
+    #[test]
+    fn test_chan_upgrade_init_required_only() {
+        assert_eq!(
+            TxChanUpgradeInitCmd {
+                dst_chain_id: ChainId::from_string("chain_b"),
+                src_chain_id: ChainId::from_string("chain_a"),
+                dst_conn_id: ConnectionId::from_str("connection_b").unwrap(),
+                dst_port_id: PortId::from_str("port_b").unwrap(),
+                src_port_id: PortId::from_str("port_a").unwrap(),
+                dst_chan_id: ChannelId::from_str("channel_b").unwrap(),
+                src_chan_id: ChannelId::from_str("channel_a").unwrap(),
+                version: None,
+                ordering: None,
+                connection_hops: None
+            },
+            TxChanUpgradeInitCmd::parse_from([
+                "test",
+                "--dst-chain",
+                "chain_b",
+                "--src-chain",
+                "chain_a",
+                "--dst-connection",
+                "connection_b",
+                "--dst-port",
+                "port_b",
+                "--src-port",
+                "port_a",
+                "--dst-channel",
+                "channel_b",
+                "--src-channel",
+                "channel_a"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_chan_upgrade_init_fields() {
+        assert_eq!(
+            TxChanUpgradeInitCmd {
+                dst_chain_id: ChainId::from_string("chain_b"),
+                src_chain_id: ChainId::from_string("chain_a"),
+                dst_conn_id: ConnectionId::from_str("connection_b").unwrap(),
+                dst_port_id: PortId::from_str("port_b").unwrap(),
+                src_port_id: PortId::from_str("port_a").unwrap(),
+                dst_chan_id: ChannelId::from_str("channel_b").unwrap(),
+                src_chan_id: ChannelId::from_str("channel_a").unwrap(),
+                version: Some(Version::new("ics29-1".to_string())),
+                ordering: Some(Ordering::Ordered),
+                connection_hops: Some(vec![
+                    ConnectionId::from_str("connection_b").unwrap(),
+                    ConnectionId::from_str("connection_c").unwrap()
+                ])
+            },
+            TxChanUpgradeInitCmd::parse_from([
+                "test",
+                "--dst-chain",
+                "chain_b",
+                "--src-chain",
+                "chain_a",
+                "--dst-connection",
+                "connection_b",
+                "--dst-port",
+                "port_b",
+                "--src-port",
+                "port_a",
+                "--dst-channel",
+                "channel_b",
+                "--src-channel",
+                "channel_a",
+                "--version",
+                "ics29-1",
+                "--ordering",
+                "ordered",
+                "--connection-hops",
+                "connection_b,connection_c"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_chan_upgrade_init_no_b_channel() {
+        assert!(TxChanUpgradeInitCmd::try_parse_from([
+            "test",
+            "--dst-chain",
+            "chain_b",
+            "--src-chain",
+            "chain_a",
+            "--dst-connection",
+            "connection_b",
+            "--dst-port",
+            "port_b",
+            "--src-port",
+            "port_a",
+            "--src-channel",
+            "channel_a"
+        ])
+        .is_err())
+    }
+
+    #[test]
+    fn test_chan_upgrade_try() {
+        assert_eq!(
+            TxChanUpgradeTryCmd {
+                dst_chain_id: ChainId::from_string("chain_b"),
+                src_chain_id: ChainId::from_string("chain_a"),
+                dst_conn_id: ConnectionId::from_str("connection_b").unwrap(),
+                dst_port_id: PortId::from_str("port_b").unwrap(),
+                src_port_id: PortId::from_str("port_a").unwrap(),
+                dst_chan_id: ChannelId::from_str("channel_b").unwrap(),
+                src_chan_id: ChannelId::from_str("channel_a").unwrap()
+            },
+            TxChanUpgradeTryCmd::parse_from([
+                "test",
+                "--dst-chain",
+                "chain_b",
+                "--src-chain",
+                "chain_a",
+                "--dst-connection",
+                "connection_b",
+                "--dst-port",
+                "port_b",
+                "--src-port",
+                "port_a",
+                "--dst-channel",
+                "channel_b",
+                "--src-channel",
+                "channel_a"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_chan_upgrade_try_no_a_channel() {
+        assert!(TxChanUpgradeTryCmd::try_parse_from([
+            "test",
+            "--dst-chain",
+            "chain_b",
+            "--src-chain",
+            "chain_a",
+            "--dst-connection",
+            "connection_b",
+            "--dst-port",
+            "port_b",
+            "--src-port",
+            "port_a",
+            "--dst-channel",
+            "channel_b"
+        ])
+        .is_err())
+    }
 }
@@ -73,6 +73,14 @@ pub struct TxCreateClientCmd {
     /// and trusted validator set is sufficient for a commit to be accepted going forward.
     #[clap(long = "trust-threshold", value_name = "TRUST_THRESHOLD", parse(try_from_str = parse_trust_threshold))]
     trust_threshold: Option<TrustThreshold>,
+
+    /// Create a Wasm client wrapping the client, with the given hex-encoded code hash.
+    ///
+    /// The code hash identifies the Wasm light client contract, which must
+    /// already be stored on the host chain.
+    // The type is spelled out as `std::vec::Vec` for clap to parse it as a single value.
+    #[clap(long = "wasm-code-hash", value_name = "WASM_CODE_HASH", parse(try_from_str = parse_wasm_code_hash))]
+    wasm_code_hash: Option<std::vec::Vec<u8>>,
 }
 
 /// Sample to run this tx:
@@ -96,6 +104,7 @@ impl Runnable for TxCreateClientCmd {
             max_clock_drift: self.clock_drift.map(Into::into),
             trusting_period: self.trusting_period.map(Into::into),
             trust_threshold: self.trust_threshold.map(Into::into),
+            wasm_code_hash: self.wasm_code_hash.clone(),
         };
 
         // Trigger client creation via the "build" interface, so that we obtain the resulting event
@@ -520,6 +529,20 @@ fn parse_trust_threshold(input: &str) -> Result<TrustThreshold, Error> {
         .map_err(|e| Error::cli_arg(format!("invalid trust threshold fraction: {e}")))
 }
 
+fn parse_wasm_code_hash(input: &str) -> Result<Vec<u8>, Error> {
+    let code_hash = subtle_encoding::hex::decode(input.trim())
+        .map_err(|e| Error::cli_arg(format!("invalid hex-encoded code hash: {e}")))?;
+
+    if code_hash.len() != 32 {
+        return Err(Error::cli_arg(format!(
+            "expected a code hash of 32 bytes, got {} bytes",
+            code_hash.len()
+        )));
+    }
+
+    Ok(code_hash)
+}
+
 type UpgradeClientResult = Result<Vec<IbcEvent>, Error>;
 type UpgradeClientsForChainResult = Result<Vec<UpgradeClientResult>, Error>;
 
@@ -596,8 +619,8 @@ impl OutputBuffer {
 #[cfg(test)]
 mod tests {
     use super::{
-        parse_trust_threshold, TxCreateClientCmd, TxUpdateClientCmd, TxUpgradeClientCmd,
-        TxUpgradeClientsCmd,
+        parse_trust_threshold, parse_wasm_code_hash, TxCreateClientCmd, TxUpdateClientCmd,
+        TxUpgradeClientCmd, TxUpgradeClientsCmd,
     };
 
     use std::str::FromStr;
@@ -622,6 +645,15 @@ mod tests {
         assert_eq!(threshold.denominator(), 5);
     }
 
+    #[test]
+    fn test_parse_wasm_code_hash() {
+        let code_hash = parse_wasm_code_hash(&"ab".repeat(32)).unwrap();
+        assert_eq!(code_hash, vec![0xab; 32]);
+
+        assert!(parse_wasm_code_hash("abcd").is_err());
+        assert!(parse_wasm_code_hash(&"zz".repeat(32)).is_err());
+    }
+
     #[test]
     fn test_create_client_required_only() {
         assert_eq!(
@@ -630,7 +662,8 @@ mod tests {
                 src_chain_id: ChainId::from_string("reference_chain"),
                 clock_drift: None,
                 trusting_period: None,
-                trust_threshold: None
+                trust_threshold: None,
+                wasm_code_hash: None
             },
             TxCreateClientCmd::parse_from([
                 "test",
@@ -650,7 +683,8 @@ mod tests {
                 src_chain_id: ChainId::from_string("reference_chain"),
                 clock_drift: Some("5s".parse::<Duration>().unwrap()),
                 trusting_period: None,
-                trust_threshold: None
+                trust_threshold: None,
+                wasm_code_hash: None
             },
             TxCreateClientCmd::parse_from([
                 "test",
@@ -668,7 +702,8 @@ mod tests {
                 src_chain_id: ChainId::from_string("reference_chain"),
                 clock_drift: Some("3s".parse::<Duration>().unwrap()),
                 trusting_period: None,
-                trust_threshold: None
+                trust_threshold: None,
+                wasm_code_hash: None
             },
             TxCreateClientCmd::parse_from([
                 "test",
@@ -690,7 +725,8 @@ mod tests {
                 src_chain_id: ChainId::from_string("reference_chain"),
                 clock_drift: None,
                 trusting_period: Some("5s".parse::<Duration>().unwrap()),
-                trust_threshold: None
+                trust_threshold: None,
+                wasm_code_hash: None
             },
             TxCreateClientCmd::parse_from([
                 "test",
@@ -708,7 +744,8 @@ mod tests {
                 src_chain_id: ChainId::from_string("reference_chain"),
                 clock_drift: None,
                 trusting_period: Some("3s".parse::<Duration>().unwrap()),
-                trust_threshold: None
+                trust_threshold: None,
+                wasm_code_hash: None
             },
             TxCreateClientCmd::parse_from([
                 "test",
@@ -730,7 +767,8 @@ mod tests {
                 src_chain_id: ChainId::from_string("reference_chain"),
                 clock_drift: None,
                 trusting_period: None,
-                trust_threshold: Some(TrustThreshold::new(1, 2).unwrap())
+                trust_threshold: Some(TrustThreshold::new(1, 2).unwrap()),
+                wasm_code_hash: None
             },
             TxCreateClientCmd::parse_from([
                 "test",
@@ -752,7 +790,8 @@ mod tests {
                 src_chain_id: ChainId::from_string("reference_chain"),
                 clock_drift: Some("5s".parse::<Duration>().unwrap()),
                 trusting_period: Some("3s".parse::<Duration>().unwrap()),
-                trust_threshold: Some(TrustThreshold::new(1, 2).unwrap())
+                trust_threshold: Some(TrustThreshold::new(1, 2).unwrap()),
+                wasm_code_hash: None
             },
             TxCreateClientCmd::parse_from([
                 "test",
@@ -770,6 +809,29 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_create_client_wasm_code_hash() {
+        assert_eq!(
+            TxCreateClientCmd {
+                dst_chain_id: ChainId::from_string("host_chain"),
+                src_chain_id: ChainId::from_string("reference_chain"),
+                clock_drift: None,
+                trusting_period: None,
+                trust_threshold: None,
+                wasm_code_hash: Some(vec![0xab; 32])
+            },
+            TxCreateClientCmd::parse_from([
+                "test",
+                "--host-chain",
+                "host_chain",
+                "--reference-chain",
+                "reference_chain",
+                "--wasm-code-hash",
+                &"ab".repeat(32)
+            ])
+        )
+    }
+
     #[test]
     fn test_create_client_no_host_chain() {
         assert!(TxCreateClientCmd::try_parse_from([
@@ -1,13 +1,16 @@
+use std::ops::RangeInclusive;
+
 use abscissa_core::clap::Parser;
 use abscissa_core::{Command, Runnable};
 use ibc_relayer_types::core::ics02_client::height::Height;
+use ibc_relayer_types::core::ics04_channel::packet::Sequence;
 
 use ibc_relayer::chain::handle::ChainHandle;
 use ibc_relayer::link::{Link, LinkParameters};
 use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
 use ibc_relayer_types::events::IbcEvent;
 
-use crate::cli_utils::ChainHandlePair;
+use crate::cli_utils::{parse_packet_sequences, ChainHandlePair};
 use crate::conclude::Output;
 use crate::error::Error;
 use crate::prelude::*;
@@ -51,6 +54,17 @@ pub struct TxPacketRecvCmd {
     )]
     src_channel_id: ChannelId,
 
+    #[clap(
+        long = "packet-sequences",
+        value_name = "SEQUENCES",
+        value_delimiter = ',',
+        parse(try_from_str = parse_packet_sequences),
+        help = "Only relay the packets with these sequences, comma separated, \
+                either single sequences or ranges such as `1..100` or `300..` \
+                (default: all the pending packets)"
+    )]
+    packet_sequences: Vec<RangeInclusive<Sequence>>,
+
     #[clap(
         long = "packet-data-query-height",
         help = "Exact height at which the packet data is queried via block_results RPC"
@@ -82,6 +96,7 @@ impl Runnable for TxPacketRecvCmd {
 
         let res: Result<Vec<IbcEvent>, Error> = link
             .relay_recv_packet_and_timeout_messages_with_packet_data_query_height(
+                &self.packet_sequences,
                 packet_data_query_height,
             )
             .map_err(Error::link);
@@ -132,6 +147,17 @@ pub struct TxPacketAckCmd {
     )]
     src_channel_id: ChannelId,
 
+    #[clap(
+        long = "packet-sequences",
+        value_name = "SEQUENCES",
+        value_delimiter = ',',
+        parse(try_from_str = parse_packet_sequences),
+        help = "Only relay the acknowledgements with these sequences, comma separated, \
+                either single sequences or ranges such as `1..100` or `300..` \
+                (default: all the pending acknowledgements)"
+    )]
+    packet_sequences: Vec<RangeInclusive<Sequence>>,
+
     #[clap(
         long = "packet-data-query-height",
         help = "Exact height at which the packet data is queried via block_results RPC"
@@ -162,7 +188,10 @@ impl Runnable for TxPacketAckCmd {
             .map(|height| Height::new(link.a_to_b.src_chain().id().version(), height).unwrap());
 
         let res: Result<Vec<IbcEvent>, Error> = link
-            .relay_ack_packet_messages_with_packet_data_query_height(packet_data_query_height)
+            .relay_ack_packet_messages_with_packet_data_query_height(
+                &self.packet_sequences,
+                packet_data_query_height,
+            )
             .map_err(Error::link);
 
         match res {
@@ -179,6 +208,7 @@ mod tests {
     use std::str::FromStr;
 
     use abscissa_core::clap::Parser;
+    use ibc_relayer_types::core::ics04_channel::packet::Sequence;
     use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
 
     #[test]
@@ -189,6 +219,7 @@ mod tests {
                 src_chain_id: ChainId::from_string("chain_sender"),
                 src_port_id: PortId::from_str("port_sender").unwrap(),
                 src_channel_id: ChannelId::from_str("channel_sender").unwrap(),
+                packet_sequences: vec![],
                 packet_data_query_height: None
             },
             TxPacketRecvCmd::parse_from([
@@ -213,6 +244,7 @@ mod tests {
                 src_chain_id: ChainId::from_string("chain_sender"),
                 src_port_id: PortId::from_str("port_sender").unwrap(),
                 src_channel_id: ChannelId::from_str("channel_sender").unwrap(),
+                packet_sequences: vec![],
                 packet_data_query_height: None
             },
             TxPacketRecvCmd::parse_from([
@@ -236,6 +268,7 @@ mod tests {
                 src_chain_id: ChainId::from_string("chain_sender"),
                 src_port_id: PortId::from_str("port_sender").unwrap(),
                 src_channel_id: ChannelId::from_str("channel_sender").unwrap(),
+                packet_sequences: vec![],
                 packet_data_query_height: Some(5),
             },
             TxPacketRecvCmd::parse_from([
@@ -254,6 +287,36 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_packet_recv_packet_sequences() {
+        assert_eq!(
+            TxPacketRecvCmd {
+                dst_chain_id: ChainId::from_string("chain_receiver"),
+                src_chain_id: ChainId::from_string("chain_sender"),
+                src_port_id: PortId::from_str("port_sender").unwrap(),
+                src_channel_id: ChannelId::from_str("channel_sender").unwrap(),
+                packet_sequences: vec![
+                    Sequence::from(1)..=Sequence::from(10),
+                    Sequence::from(42)..=Sequence::from(42),
+                ],
+                packet_data_query_height: None,
+            },
+            TxPacketRecvCmd::parse_from([
+                "test",
+                "--dst-chain",
+                "chain_receiver",
+                "--src-chain",
+                "chain_sender",
+                "--src-port",
+                "port_sender",
+                "--src-channel",
+                "channel_sender",
+                "--packet-sequences",
+                "1..10,42"
+            ])
+        )
+    }
+
     #[test]
     fn test_packet_recv_no_sender_channel() {
         assert!(TxPacketRecvCmd::try_parse_from([
@@ -318,6 +381,7 @@ mod tests {
                 src_chain_id: ChainId::from_string("chain_sender"),
                 src_port_id: PortId::from_str("port_sender").unwrap(),
                 src_channel_id: ChannelId::from_str("channel_sender").unwrap(),
+                packet_sequences: vec![],
                 packet_data_query_height: None
             },
             TxPacketAckCmd::parse_from([
@@ -342,6 +406,7 @@ mod tests {
                 src_chain_id: ChainId::from_string("chain_sender"),
                 src_port_id: PortId::from_str("port_sender").unwrap(),
                 src_channel_id: ChannelId::from_str("channel_sender").unwrap(),
+                packet_sequences: vec![],
                 packet_data_query_height: None
             },
             TxPacketAckCmd::parse_from([
@@ -1,10 +1,23 @@
 //! Various components for internal use by the Abscissa subsystem.
 
 use abscissa_core::{Component, FrameworkError, FrameworkErrorKind};
-use tracing_subscriber::{filter::EnvFilter, util::SubscriberInitExt, FmtSubscriber};
+use opentelemetry::sdk::{trace::Tracer, Resource};
+use opentelemetry::KeyValue;
+use opentelemetry_otlp::WithExportConfig;
+use tokio::runtime::Runtime;
+use tracing::{Metadata, Subscriber};
+use tracing_opentelemetry::OpenTelemetryLayer;
+use tracing_subscriber::{
+    filter::{filter_fn, EnvFilter, FilterFn, Filtered},
+    fmt,
+    layer::SubscriberExt,
+    registry::LookupSpan,
+    util::SubscriberInitExt,
+    Layer,
+};
 
 use ibc_relayer::{
-    config::{GlobalConfig, LogLevel},
+    config::{GlobalConfig, LogLevel, TracingConfig},
     util::debug_section::DebugSection,
 };
 
@@ -20,29 +33,42 @@ const HERMES_LOG_VAR: &str = "RUST_LOG";
 /// - Customizing the log output level, for filtering the output produced via tracing macros
 ///   (`debug!`, `info!`, etc.) or abscissa macros (`status_err`, `status_info`, etc.).
 /// - Enabling JSON-formatted output without coloring
+/// - Exporting the spans to an OpenTelemetry collector, if enabled
 #[derive(Component, Debug)]
-pub struct JsonTracing;
+pub struct JsonTracing {
+    _otlp_runtime: Option<Runtime>,
+}
 
 impl JsonTracing {
     /// Creates a new [`JsonTracing`] component
-    pub fn new(cfg: GlobalConfig, debug_sections: &[DebugSection]) -> Result<Self, FrameworkError> {
+    pub fn new(
+        cfg: GlobalConfig,
+        tracing_cfg: &TracingConfig,
+        debug_sections: &[DebugSection],
+    ) -> Result<Self, FrameworkError> {
         let filter = build_tracing_filter(cfg.log_level, debug_sections)?;
+        let (otlp_layer, otlp_runtime) = build_otlp_layer(tracing_cfg)?.unzip();
         // Note: JSON formatter is un-affected by ANSI 'color' option. Set to 'false'.
         let use_color = false;
 
-        // Construct a tracing subscriber with the supplied filter and enable reloading.
-        let builder = FmtSubscriber::builder()
+        // Construct a tracing subscriber with the supplied filter.
+        // The filter only applies to the output, the exported spans have their own filter.
+        let fmt_layer = fmt::layer()
             .with_target(false)
-            .with_env_filter(filter)
             .with_writer(std::io::stdout)
             .with_ansi(use_color)
             .with_thread_ids(true)
-            .json();
+            .json()
+            .with_filter(filter);
 
-        let subscriber = builder.finish();
-        subscriber.init();
+        tracing_subscriber::registry()
+            .with(fmt_layer)
+            .with(otlp_layer)
+            .init();
 
-        Ok(Self)
+        Ok(Self {
+            _otlp_runtime: otlp_runtime,
+        })
     }
 }
 
@@ -54,26 +80,109 @@ impl JsonTracing {
 /// - Customizing the log output level, for filtering the output produced via tracing macros
 ///   (`debug!`, `info!`, etc.) or abscissa macros (`status_err`, `status_info`, etc.).
 /// - Enabling pretty output with coloring
-pub struct PrettyTracing;
+/// - Exporting the spans to an OpenTelemetry collector, if enabled
+pub struct PrettyTracing {
+    _otlp_runtime: Option<Runtime>,
+}
 
 impl PrettyTracing {
     /// Creates a new [`PrettyTracing`] component
-    pub fn new(cfg: GlobalConfig, debug_sections: &[DebugSection]) -> Result<Self, FrameworkError> {
+    pub fn new(
+        cfg: GlobalConfig,
+        tracing_cfg: &TracingConfig,
+        debug_sections: &[DebugSection],
+    ) -> Result<Self, FrameworkError> {
         let filter = build_tracing_filter(cfg.log_level, debug_sections)?;
+        let (otlp_layer, otlp_runtime) = build_otlp_layer(tracing_cfg)?.unzip();
 
-        // Construct a tracing subscriber with the supplied filter and enable reloading.
-        let builder = FmtSubscriber::builder()
+        // Construct a tracing subscriber with the supplied filter.
+        // The filter only applies to the output, the exported spans have their own filter.
+        let fmt_layer = fmt::layer()
             .with_target(false)
-            .with_env_filter(filter)
             .with_writer(std::io::stderr)
             .with_ansi(enable_ansi())
-            .with_thread_ids(true);
+            .with_thread_ids(true)
+            .with_filter(filter);
+
+        tracing_subscriber::registry()
+            .with(fmt_layer)
+            .with(otlp_layer)
+            .init();
+
+        Ok(Self {
+            _otlp_runtime: otlp_runtime,
+        })
+    }
+}
+
+/// The layer exporting the spans to an OpenTelemetry collector
+type OtlpLayer<S> = Filtered<OpenTelemetryLayer<S, Tracer>, FilterFn<OtlpFilter>, S>;
 
-        let subscriber = builder.finish();
-        subscriber.init();
+type OtlpFilter = fn(&Metadata<'_>) -> bool;
 
-        Ok(Self)
+/// Whether a span or event is exported to the OpenTelemetry collector.
+///
+/// All the spans of the relayer crates are exported, whatever the log level, such that
+/// the `trace` spans which carry the `tracking_id` of the relaying operations are exported
+/// without enabling `trace` logs. Events are only logged, and not exported.
+fn is_exported(metadata: &Metadata<'_>) -> bool {
+    metadata.is_span()
+        && TARGET_CRATES
+            .iter()
+            .any(|target| metadata.target().starts_with(target))
+}
+
+/// Builds the layer exporting the spans to the OpenTelemetry collector configured
+/// in the `[tracing]` section, if enabled.
+///
+/// The exporter runs on its own Tokio runtime, which is returned along with the layer
+/// and must be kept alive for as long as spans are exported.
+fn build_otlp_layer<S>(
+    tracing_cfg: &TracingConfig,
+) -> Result<Option<(OtlpLayer<S>, Runtime)>, FrameworkError>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    if !tracing_cfg.enabled {
+        return Ok(None);
     }
+
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(1)
+        .thread_name("otlp-exporter")
+        .enable_all()
+        .build()
+        .map_err(|e| FrameworkErrorKind::ComponentError.context(Error::otlp_runtime(e)))?;
+
+    // The exporter spawns its tasks on the runtime it is installed from
+    let tracer = runtime.block_on(async {
+        let exporter = opentelemetry_otlp::new_exporter()
+            .tonic()
+            .with_endpoint(&tracing_cfg.endpoint);
+
+        let trace_config =
+            opentelemetry::sdk::trace::config().with_resource(Resource::new([KeyValue::new(
+                "service.name",
+                tracing_cfg.service_name.clone(),
+            )]));
+
+        opentelemetry_otlp::new_pipeline()
+            .tracing()
+            .with_exporter(exporter)
+            .with_trace_config(trace_config)
+            .install_batch(opentelemetry::runtime::Tokio)
+    });
+
+    let tracer = tracer.map_err(|e| {
+        FrameworkErrorKind::ComponentError
+            .context(Error::otlp_exporter(tracing_cfg.endpoint.clone(), e))
+    })?;
+
+    let layer = tracing_opentelemetry::layer()
+        .with_tracer(tracer)
+        .with_filter(filter_fn(is_exported as OtlpFilter));
+
+    Ok(Some((layer, runtime)))
 }
 
 /// Check if both stdout and stderr are proper terminal (tty),
@@ -80,6 +80,9 @@ pub fn exit_with(out: Output) -> ! {
         println!("{} {}", status, out.result);
     }
 
+    // Export the spans which are still buffered, if any
+    opentelemetry::global::shutdown_tracer_provider();
+
     // The return code
     if status == Status::Error {
         std::process::exit(1);
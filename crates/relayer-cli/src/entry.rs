@@ -51,6 +51,13 @@ pub struct EntryPoint {
     )]
     pub debug: Vec<CliDebugSection>,
 
+    /// Build and simulate the transactions, but never broadcast them
+    #[clap(
+        long = "dry-run",
+        help = "Simulate the transactions and log their estimated fees instead of broadcasting them"
+    )]
+    pub dry_run: bool,
+
     /// Subcommand to execute.
     ///
     /// The `command` option will delegate option parsing to the command type,
@@ -4,15 +4,18 @@ use tracing::error;
 
 use crossbeam_channel as channel;
 
-use ibc_relayer::supervisor::dump_state::SupervisorState;
+use ibc_relayer::supervisor::{dump_state::SupervisorState, reload::ConfigChanges};
+use ibc_relayer::worker::{wallet::LowBalanceAlert, PausePolicy};
 use ibc_relayer::{
     config::ChainConfig,
+    link::journal::{PacketKey, PacketTrace},
+    object::Object,
     rest::{
         request::{reply_channel, ReplySender, Request, VersionInfo},
         RestApiError,
     },
 };
-use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, ClientId, PortId};
 
 pub const NAME: &str = env!(
     "CARGO_PKG_NAME",
@@ -64,6 +67,90 @@ pub fn supervisor_state(
     submit_request(sender, |reply_to| Request::State { reply_to })
 }
 
+pub fn reload_config(sender: &channel::Sender<Request>) -> Result<ConfigChanges, RestApiError> {
+    submit_request(sender, |reply_to| Request::ReloadConfig { reply_to })
+}
+
+pub fn clear_packets(
+    sender: &channel::Sender<Request>,
+    chain_id: ChainId,
+    port_id: PortId,
+    channel_id: ChannelId,
+) -> Result<Vec<Object>, RestApiError> {
+    submit_request(sender, |reply_to| Request::ClearPackets {
+        chain_id,
+        port_id,
+        channel_id,
+        reply_to,
+    })
+}
+
+pub fn update_client(
+    sender: &channel::Sender<Request>,
+    chain_id: ChainId,
+    client_id: ClientId,
+) -> Result<(), RestApiError> {
+    submit_request(sender, |reply_to| Request::UpdateClient {
+        chain_id,
+        client_id,
+        reply_to,
+    })
+}
+
+pub fn pause_worker(
+    sender: &channel::Sender<Request>,
+    object: Object,
+    policy: PausePolicy,
+) -> Result<(), RestApiError> {
+    submit_request(sender, |reply_to| Request::PauseWorker {
+        object,
+        policy,
+        reply_to,
+    })
+}
+
+pub fn resume_worker(
+    sender: &channel::Sender<Request>,
+    object: Object,
+) -> Result<(), RestApiError> {
+    submit_request(sender, |reply_to| Request::ResumeWorker {
+        object,
+        reply_to,
+    })
+}
+
+pub fn pause_chain(
+    sender: &channel::Sender<Request>,
+    chain_id: &str,
+    policy: PausePolicy,
+) -> Result<(), RestApiError> {
+    submit_request(sender, |reply_to| Request::PauseChain {
+        chain_id: ChainId::from_string(chain_id),
+        policy,
+        reply_to,
+    })
+}
+
+pub fn resume_chain(sender: &channel::Sender<Request>, chain_id: &str) -> Result<(), RestApiError> {
+    submit_request(sender, |reply_to| Request::ResumeChain {
+        chain_id: ChainId::from_string(chain_id),
+        reply_to,
+    })
+}
+
+pub fn low_balance_alerts(
+    sender: &channel::Sender<Request>,
+) -> Result<Vec<LowBalanceAlert>, RestApiError> {
+    submit_request(sender, |reply_to| Request::LowBalanceAlerts { reply_to })
+}
+
+pub fn packet_trace(
+    sender: &channel::Sender<Request>,
+    key: PacketKey,
+) -> Result<Option<PacketTrace>, RestApiError> {
+    submit_request(sender, |reply_to| Request::PacketTrace { key, reply_to })
+}
+
 pub fn assemble_version_info(sender: &channel::Sender<Request>) -> Vec<VersionInfo> {
     // Fetch the relayer library version
     let lib_version = submit_request(sender, |reply_to| Request::Version { reply_to })
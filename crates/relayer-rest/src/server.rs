@@ -1,28 +1,50 @@
 use std::{
     error::Error,
     net::{SocketAddr, ToSocketAddrs},
+    sync::Arc,
 };
 
-use axum::{extract::Path, response::IntoResponse, routing::get, Extension, Json, Router, Server};
+use axum::{
+    extract::{Path, Query, State},
+    http::{header::AUTHORIZATION, Request as HttpRequest, StatusCode},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Extension, Json, Router, Server,
+};
 use crossbeam_channel as channel;
 use serde::{Deserialize, Serialize};
 use tokio::task::JoinHandle;
 
 use ibc_relayer::{
+    link::journal::PacketKey,
+    object::Object,
     rest::{request::Request, RestApiError},
     supervisor::dump_state::SupervisorState,
+    worker::PausePolicy,
 };
+use ibc_relayer_types::core::ics04_channel::packet::Sequence;
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, ClientId, PortId};
 
-use crate::handle::{all_chain_ids, assemble_version_info, chain_config, supervisor_state};
+use crate::handle::{
+    all_chain_ids, assemble_version_info, chain_config, clear_packets, low_balance_alerts,
+    packet_trace, pause_chain, pause_worker, reload_config, resume_chain, resume_worker,
+    supervisor_state, update_client,
+};
 
 pub type BoxError = Box<dyn Error + Send + Sync>;
 
+/// Spawn the REST server on the given address.
+///
+/// The endpoints which act on the relayer require the requests to carry
+/// the given `auth_token` as a bearer token, and are disabled if it is not set.
 pub fn spawn(
     addr: impl ToSocketAddrs,
     sender: channel::Sender<Request>,
+    auth_token: Option<String>,
 ) -> Result<JoinHandle<()>, BoxError> {
     let addr = addr.to_socket_addrs()?.next().unwrap();
-    let handle = tokio::spawn(run(addr, sender));
+    let handle = tokio::spawn(run(addr, sender, auth_token.map(Arc::from)));
     Ok(handle)
 }
 
@@ -68,14 +90,167 @@ async fn get_state(
     Json(JsonResult::from(state))
 }
 
+async fn get_alerts(Extension(sender): Extension<Sender>) -> impl IntoResponse {
+    let alerts = low_balance_alerts(&sender);
+    Json(JsonResult::from(alerts))
+}
+
+#[derive(Debug, Deserialize)]
+struct PacketTraceParams {
+    chain: ChainId,
+    port: PortId,
+    channel: ChannelId,
+    sequence: u64,
+}
+
+async fn get_packet_trace(
+    Extension(sender): Extension<Sender>,
+    Query(params): Query<PacketTraceParams>,
+) -> impl IntoResponse {
+    let key = PacketKey {
+        chain_id: params.chain,
+        port_id: params.port,
+        channel_id: params.channel,
+        sequence: Sequence::from(params.sequence),
+    };
+
+    let trace = packet_trace(&sender, key);
+    Json(JsonResult::from(trace))
+}
+
+async fn post_reload(Extension(sender): Extension<Sender>) -> impl IntoResponse {
+    let changes = reload_config(&sender);
+    Json(JsonResult::from(changes))
+}
+
+#[derive(Debug, Deserialize)]
+struct ClearPacketsParams {
+    chain: ChainId,
+    port: PortId,
+    channel: ChannelId,
+}
+
+async fn post_clear_packets(
+    Extension(sender): Extension<Sender>,
+    Json(params): Json<ClearPacketsParams>,
+) -> impl IntoResponse {
+    let objects = clear_packets(&sender, params.chain, params.port, params.channel);
+    Json(JsonResult::from(objects))
+}
+
+#[derive(Debug, Deserialize)]
+struct UpdateClientParams {
+    chain: ChainId,
+    client: ClientId,
+}
+
+async fn post_update_client(
+    Extension(sender): Extension<Sender>,
+    Json(params): Json<UpdateClientParams>,
+) -> impl IntoResponse {
+    let result = update_client(&sender, params.chain, params.client);
+    Json(JsonResult::from(result))
+}
+
+#[derive(Debug, Deserialize)]
+struct PauseParams {
+    #[serde(default)]
+    policy: PausePolicy,
+}
+
+async fn post_pause_worker(
+    Extension(sender): Extension<Sender>,
+    Query(params): Query<PauseParams>,
+    Json(object): Json<Object>,
+) -> impl IntoResponse {
+    let result = pause_worker(&sender, object, params.policy);
+    Json(JsonResult::from(result))
+}
+
+async fn post_resume_worker(
+    Extension(sender): Extension<Sender>,
+    Json(object): Json<Object>,
+) -> impl IntoResponse {
+    let result = resume_worker(&sender, object);
+    Json(JsonResult::from(result))
+}
+
+async fn post_pause_chain(
+    Path(id): Path<String>,
+    Extension(sender): Extension<Sender>,
+    Query(params): Query<PauseParams>,
+) -> impl IntoResponse {
+    let result = pause_chain(&sender, &id, params.policy);
+    Json(JsonResult::from(result))
+}
+
+async fn post_resume_chain(
+    Path(id): Path<String>,
+    Extension(sender): Extension<Sender>,
+) -> impl IntoResponse {
+    let result = resume_chain(&sender, &id);
+    Json(JsonResult::from(result))
+}
+
+/// Reject the requests which do not carry the configured bearer token,
+/// or all requests if there is no token configured.
+async fn authorize<B>(
+    State(auth_token): State<AuthToken>,
+    request: HttpRequest<B>,
+    next: Next<B>,
+) -> Response {
+    let auth_token = match auth_token {
+        Some(auth_token) => auth_token,
+        None => return error_response(StatusCode::FORBIDDEN, RestApiError::WriteDisabled),
+    };
+
+    let bearer = request
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+
+    match bearer {
+        Some(token) if constant_time_eq(token.as_bytes(), auth_token.as_bytes()) => {
+            next.run(request).await
+        }
+        _ => error_response(StatusCode::UNAUTHORIZED, RestApiError::Unauthorized),
+    }
+}
+
+fn error_response(status: StatusCode, error: RestApiError) -> Response {
+    let result: JsonResult<(), _> = JsonResult::Error(error);
+    (status, Json(result)).into_response()
+}
+
+/// Compare two byte strings in a time which only depends on their length,
+/// so as not to leak how much of the token was guessed right.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
 type Sender = channel::Sender<Request>;
+type AuthToken = Option<Arc<str>>;
+
+async fn run(addr: SocketAddr, sender: Sender, auth_token: AuthToken) {
+    let write_routes = Router::new()
+        .route("/reload", post(post_reload))
+        .route("/packets/clear", post(post_clear_packets))
+        .route("/client/update", post(post_update_client))
+        .route("/worker/pause", post(post_pause_worker))
+        .route("/worker/resume", post(post_resume_worker))
+        .route("/chain/:id/pause", post(post_pause_chain))
+        .route("/chain/:id/resume", post(post_resume_chain))
+        .route_layer(middleware::from_fn_with_state(auth_token, authorize));
 
-async fn run(addr: SocketAddr, sender: Sender) {
     let app = Router::new()
         .route("/version", get(get_version))
         .route("/chains", get(get_chains))
         .route("/chain/:id", get(get_chain))
         .route("/state", get(get_state))
+        .route("/alerts", get(get_alerts))
+        .route("/packet/trace", get(get_packet_trace))
+        .merge(write_routes)
         .layer(Extension(sender));
 
     Server::bind(&addr)
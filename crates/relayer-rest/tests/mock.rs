@@ -1,11 +1,15 @@
-use std::{fmt::Debug, str::FromStr, time::Duration};
+use std::{collections::HashMap, fmt::Debug, str::FromStr, time::Duration};
 
+use reqwest::{Client, RequestBuilder};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
 use ibc_relayer::{
     config::ChainConfig,
+    link::journal::{PacketKey, PacketStep, PacketTrace},
+    object::{Object, Packet},
     rest::request::{Request, VersionInfo},
-    supervisor::dump_state::SupervisorState,
+    supervisor::{dump_state::SupervisorState, reload::ConfigChanges},
+    worker::{wallet::LowBalanceAlert, PausePolicy},
 };
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 
@@ -24,14 +28,39 @@ enum JsonResult<R, E> {
     Error(E),
 }
 
+const AUTH_TOKEN: &str = "mock-token";
+
 async fn run_test<R, F>(port: u16, path: &str, expected: R, handler: F)
 where
     R: Serialize + DeserializeOwned + Debug + PartialEq,
     F: FnOnce(Request) -> TestResult + Send + 'static,
+{
+    let path = path.to_string();
+
+    run_request_test(
+        port,
+        Some(AUTH_TOKEN),
+        move |client, addr| client.get(format!("{addr}{path}")),
+        expected,
+        handler,
+    )
+    .await
+}
+
+async fn run_request_test<R, B, F>(
+    port: u16,
+    auth_token: Option<&str>,
+    build_request: B,
+    expected: R,
+    handler: F,
+) where
+    R: Serialize + DeserializeOwned + Debug + PartialEq,
+    B: FnOnce(Client, String) -> RequestBuilder,
+    F: FnOnce(Request) -> TestResult + Send + 'static,
 {
     let (tx, rx) = crossbeam_channel::unbounded();
 
-    let handle = spawn(("127.0.0.1", port), tx).unwrap();
+    let handle = spawn(("127.0.0.1", port), tx, auth_token.map(String::from)).unwrap();
 
     std::thread::spawn(move || match rx.recv() {
         Ok(r) => match handler(r) {
@@ -43,7 +72,8 @@ where
 
     tokio::time::sleep(Duration::from_millis(500)).await;
 
-    let response = reqwest::get(&format!("http://127.0.0.1:{port}{path}"))
+    let response = build_request(Client::new(), format!("http://127.0.0.1:{port}"))
+        .send()
         .await
         .unwrap()
         .json::<R>()
@@ -55,6 +85,28 @@ where
     drop(handle);
 }
 
+/// Check that the request is rejected by the server without being forwarded to the relayer
+async fn run_rejected_test<B>(port: u16, auth_token: Option<&str>, build_request: B, expected: u16)
+where
+    B: FnOnce(Client, String) -> RequestBuilder,
+{
+    let (tx, rx) = crossbeam_channel::unbounded();
+
+    let handle = spawn(("127.0.0.1", port), tx, auth_token.map(String::from)).unwrap();
+
+    tokio::time::sleep(Duration::from_millis(500)).await;
+
+    let response = build_request(Client::new(), format!("http://127.0.0.1:{port}"))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status().as_u16(), expected);
+    assert!(rx.try_recv().is_err());
+
+    drop(handle);
+}
+
 #[tokio::test]
 async fn version() {
     let version = VersionInfo {
@@ -142,3 +194,308 @@ async fn state() {
     })
     .await;
 }
+
+#[tokio::test]
+async fn alerts() {
+    let alert = LowBalanceAlert {
+        chain_id: "mock-0".parse().unwrap(),
+        key_name: "testkey".to_string(),
+        account: "cosmos1jyc3tqfnf3nfjqx4pmmkpel8ep5l2w2nt2xmrk".to_string(),
+        balance: "1000".to_string(),
+        denom: "stake".to_string(),
+        min_balance: 5000,
+    };
+    let result: JsonResult<_, ()> = JsonResult::Success(vec![alert.clone()]);
+
+    run_test(19105, "/alerts", result, |req| match req {
+        Request::LowBalanceAlerts { reply_to } => {
+            reply_to.send(Ok(vec![alert])).unwrap();
+            TestResult::Success
+        }
+        req => TestResult::WrongRequest(req),
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn packet_trace() {
+    let key = PacketKey {
+        chain_id: "mock-0".parse().unwrap(),
+        port_id: "transfer".parse().unwrap(),
+        channel_id: "channel-0".parse().unwrap(),
+        sequence: 42.into(),
+    };
+    let trace = PacketTrace {
+        key: key.clone(),
+        dst_chain_id: "mock-1".parse().unwrap(),
+        dst_port_id: "transfer".parse().unwrap(),
+        dst_channel_id: "channel-1".parse().unwrap(),
+        sent: None,
+        recv: PacketStep {
+            attempts: 2,
+            ..Default::default()
+        },
+        ack: PacketStep::default(),
+        timeout: PacketStep::default(),
+        last_error: Some("account sequence mismatch".to_string()),
+    };
+    let result: JsonResult<_, ()> = JsonResult::Success(Some(trace.clone()));
+
+    run_test(
+        19115,
+        "/packet/trace?chain=mock-0&port=transfer&channel=channel-0&sequence=42",
+        result,
+        move |req| match req {
+            Request::PacketTrace { key: k, reply_to } if k == key => {
+                reply_to.send(Ok(Some(trace))).unwrap();
+                TestResult::Success
+            }
+            req => TestResult::WrongRequest(req),
+        },
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn reload() {
+    let changes = ConfigChanges {
+        added: vec!["mock-1".parse().unwrap()],
+        filter_updated: vec!["mock-0".parse().unwrap()],
+        ..Default::default()
+    };
+    let result: JsonResult<_, ()> = JsonResult::Success(changes.clone());
+
+    run_request_test(
+        19106,
+        Some(AUTH_TOKEN),
+        |client, addr| {
+            client
+                .post(format!("{addr}/reload"))
+                .bearer_auth(AUTH_TOKEN)
+        },
+        result,
+        |req| match req {
+            Request::ReloadConfig { reply_to } => {
+                reply_to.send(Ok(changes)).unwrap();
+                TestResult::Success
+            }
+            req => TestResult::WrongRequest(req),
+        },
+    )
+    .await;
+}
+
+fn packet_object() -> Object {
+    Object::Packet(Packet {
+        dst_chain_id: "mock-1".parse().unwrap(),
+        src_chain_id: "mock-0".parse().unwrap(),
+        src_channel_id: "channel-0".parse().unwrap(),
+        src_port_id: "transfer".parse().unwrap(),
+    })
+}
+
+#[tokio::test]
+async fn clear_packets() {
+    let result: JsonResult<_, ()> = JsonResult::Success(vec![packet_object()]);
+
+    run_request_test(
+        19107,
+        Some(AUTH_TOKEN),
+        |client, addr| {
+            client
+                .post(format!("{addr}/packets/clear"))
+                .bearer_auth(AUTH_TOKEN)
+                .json(&HashMap::from([
+                    ("chain", "mock-0"),
+                    ("port", "transfer"),
+                    ("channel", "channel-0"),
+                ]))
+        },
+        result,
+        |req| match req {
+            Request::ClearPackets {
+                chain_id,
+                port_id,
+                channel_id,
+                reply_to,
+            } if chain_id.as_str() == "mock-0"
+                && port_id.as_str() == "transfer"
+                && channel_id.as_str() == "channel-0" =>
+            {
+                reply_to.send(Ok(vec![packet_object()])).unwrap();
+                TestResult::Success
+            }
+            req => TestResult::WrongRequest(req),
+        },
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn update_client() {
+    let result: JsonResult<_, ()> = JsonResult::Success(());
+
+    run_request_test(
+        19108,
+        Some(AUTH_TOKEN),
+        |client, addr| {
+            client
+                .post(format!("{addr}/client/update"))
+                .bearer_auth(AUTH_TOKEN)
+                .json(&HashMap::from([
+                    ("chain", "mock-0"),
+                    ("client", "07-tendermint-0"),
+                ]))
+        },
+        result,
+        |req| match req {
+            Request::UpdateClient {
+                chain_id,
+                client_id,
+                reply_to,
+            } if chain_id.as_str() == "mock-0" && client_id.as_str() == "07-tendermint-0" => {
+                reply_to.send(Ok(())).unwrap();
+                TestResult::Success
+            }
+            req => TestResult::WrongRequest(req),
+        },
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn pause_worker() {
+    let result: JsonResult<_, ()> = JsonResult::Success(());
+
+    run_request_test(
+        19109,
+        Some(AUTH_TOKEN),
+        |client, addr| {
+            client
+                .post(format!("{addr}/worker/pause"))
+                .bearer_auth(AUTH_TOKEN)
+                .json(&packet_object())
+        },
+        result,
+        |req| match req {
+            Request::PauseWorker {
+                object,
+                policy: PausePolicy::Buffer,
+                reply_to,
+            } if object == packet_object() => {
+                reply_to.send(Ok(())).unwrap();
+                TestResult::Success
+            }
+            req => TestResult::WrongRequest(req),
+        },
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn resume_worker() {
+    let result: JsonResult<_, ()> = JsonResult::Success(());
+
+    run_request_test(
+        19110,
+        Some(AUTH_TOKEN),
+        |client, addr| {
+            client
+                .post(format!("{addr}/worker/resume"))
+                .bearer_auth(AUTH_TOKEN)
+                .json(&packet_object())
+        },
+        result,
+        |req| match req {
+            Request::ResumeWorker { object, reply_to } if object == packet_object() => {
+                reply_to.send(Ok(())).unwrap();
+                TestResult::Success
+            }
+            req => TestResult::WrongRequest(req),
+        },
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn pause_chain() {
+    let result: JsonResult<_, ()> = JsonResult::Success(());
+
+    run_request_test(
+        19113,
+        Some(AUTH_TOKEN),
+        |client, addr| {
+            client
+                .post(format!("{addr}/chain/mock-0/pause?policy=drop"))
+                .bearer_auth(AUTH_TOKEN)
+        },
+        result,
+        |req| match req {
+            Request::PauseChain {
+                chain_id,
+                policy: PausePolicy::Drop,
+                reply_to,
+            } if chain_id.as_str() == "mock-0" => {
+                reply_to.send(Ok(())).unwrap();
+                TestResult::Success
+            }
+            req => TestResult::WrongRequest(req),
+        },
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn resume_chain() {
+    let result: JsonResult<_, ()> = JsonResult::Success(());
+
+    run_request_test(
+        19114,
+        Some(AUTH_TOKEN),
+        |client, addr| {
+            client
+                .post(format!("{addr}/chain/mock-0/resume"))
+                .bearer_auth(AUTH_TOKEN)
+        },
+        result,
+        |req| match req {
+            Request::ResumeChain { chain_id, reply_to } if chain_id.as_str() == "mock-0" => {
+                reply_to.send(Ok(())).unwrap();
+                TestResult::Success
+            }
+            req => TestResult::WrongRequest(req),
+        },
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn unauthorized() {
+    run_rejected_test(
+        19111,
+        Some(AUTH_TOKEN),
+        |client, addr| {
+            client
+                .post(format!("{addr}/worker/pause"))
+                .bearer_auth("wrong-token")
+                .json(&packet_object())
+        },
+        401,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn write_disabled() {
+    run_rejected_test(
+        19112,
+        None,
+        |client, addr| {
+            client
+                .post(format!("{addr}/reload"))
+                .bearer_auth(AUTH_TOKEN)
+        },
+        403,
+    )
+    .await;
+}
@@ -0,0 +1,210 @@
+use std::time::Duration;
+
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::protobuf::Protobuf;
+use prost::Message;
+use serde::{Deserialize, Serialize};
+
+use crate::clients::ics06_solomachine::consensus_state::{ConsensusState, RawConsensusState};
+use crate::clients::ics06_solomachine::error::Error;
+use crate::clients::ics06_solomachine::header::Header;
+use crate::core::ics02_client::client_state::{
+    ClientState as Ics2ClientState, UpgradeOptions as CoreUpgradeOptions,
+};
+use crate::core::ics02_client::client_type::ClientType;
+use crate::core::ics02_client::error::Error as Ics02Error;
+use crate::core::ics24_host::identifier::ChainId;
+use crate::Height;
+
+pub const SOLOMACHINE_CLIENT_STATE_TYPE_URL: &str = "/ibc.lightclients.solomachine.v3.ClientState";
+
+/// Encoding of a solo machine client state, `ibc.lightclients.solomachine.v3.ClientState`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct RawClientState {
+    #[prost(uint64, tag = "1")]
+    pub sequence: u64,
+    #[prost(bool, tag = "2")]
+    pub is_frozen: bool,
+    #[prost(message, optional, tag = "3")]
+    pub consensus_state: Option<RawConsensusState>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClientState {
+    /// The sequence of the next signature the client expects.
+    pub sequence: u64,
+    pub is_frozen: bool,
+    pub consensus_state: ConsensusState,
+}
+
+impl ClientState {
+    pub fn new(sequence: u64, consensus_state: ConsensusState) -> Self {
+        Self {
+            sequence,
+            is_frozen: false,
+            consensus_state,
+        }
+    }
+
+    /// The height of a solo machine client, whose revision height is its sequence.
+    pub fn latest_height(&self) -> Height {
+        Height::new(0, self.sequence).expect("solo machine sequences start at 1")
+    }
+
+    /// The client state resulting from an update with the given header.
+    pub fn with_header(self, header: Header) -> Self {
+        Self {
+            sequence: self.sequence + 1,
+            consensus_state: header.into(),
+            ..self
+        }
+    }
+}
+
+impl Ics2ClientState for ClientState {
+    /// Solo machines have no chain identifier, but
+    /// the relayer uses their diversifier as such.
+    fn chain_id(&self) -> ChainId {
+        ChainId::from_string(&self.consensus_state.diversifier)
+    }
+
+    fn client_type(&self) -> ClientType {
+        ClientType::Solomachine
+    }
+
+    fn latest_height(&self) -> Height {
+        self.latest_height()
+    }
+
+    fn frozen_height(&self) -> Option<Height> {
+        self.is_frozen.then(|| self.latest_height())
+    }
+
+    /// Solo machines cannot be upgraded.
+    fn upgrade(
+        &mut self,
+        _upgrade_height: Height,
+        _upgrade_options: &dyn CoreUpgradeOptions,
+        _chain_id: ChainId,
+    ) {
+    }
+
+    /// Solo machine clients never expire.
+    fn expired(&self, _elapsed: Duration) -> bool {
+        false
+    }
+}
+
+impl Protobuf<RawClientState> for ClientState {}
+
+impl TryFrom<RawClientState> for ClientState {
+    type Error = Error;
+
+    fn try_from(raw: RawClientState) -> Result<Self, Self::Error> {
+        if raw.sequence == 0 {
+            return Err(Error::invalid_raw_client_state(
+                "sequence cannot be 0".into(),
+            ));
+        }
+
+        Ok(Self {
+            sequence: raw.sequence,
+            is_frozen: raw.is_frozen,
+            consensus_state: raw
+                .consensus_state
+                .ok_or_else(|| Error::invalid_raw_client_state("missing consensus state".into()))?
+                .try_into()?,
+        })
+    }
+}
+
+impl From<ClientState> for RawClientState {
+    fn from(value: ClientState) -> Self {
+        RawClientState {
+            sequence: value.sequence,
+            is_frozen: value.is_frozen,
+            consensus_state: Some(value.consensus_state.into()),
+        }
+    }
+}
+
+impl Protobuf<Any> for ClientState {}
+
+impl TryFrom<Any> for ClientState {
+    type Error = Ics02Error;
+
+    fn try_from(raw: Any) -> Result<Self, Self::Error> {
+        fn decode_client_state(buf: &[u8]) -> Result<ClientState, Error> {
+            RawClientState::decode(buf)
+                .map_err(Error::decode)?
+                .try_into()
+        }
+
+        match raw.type_url.as_str() {
+            SOLOMACHINE_CLIENT_STATE_TYPE_URL => {
+                decode_client_state(&raw.value).map_err(Into::into)
+            }
+            _ => Err(Ics02Error::unknown_client_state_type(raw.type_url)),
+        }
+    }
+}
+
+impl From<ClientState> for Any {
+    fn from(client_state: ClientState) -> Self {
+        Any {
+            type_url: SOLOMACHINE_CLIENT_STATE_TYPE_URL.to_string(),
+            value: Protobuf::<RawClientState>::encode_vec(&client_state),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ibc_proto::google::protobuf::Any;
+    use test_log::test;
+
+    use super::ClientState;
+    use crate::clients::ics06_solomachine::consensus_state::{ConsensusState, PublicKey};
+    use crate::clients::ics06_solomachine::header::Header;
+    use crate::timestamp::Timestamp;
+    use crate::Height;
+
+    fn dummy_client_state() -> ClientState {
+        let consensus_state = ConsensusState::new(
+            PublicKey::secp256k1(&[2u8; 33]),
+            "solo".to_string(),
+            Timestamp::from_nanoseconds(1).unwrap(),
+        );
+
+        ClientState::new(1, consensus_state)
+    }
+
+    #[test]
+    fn client_state_any_round_trip() {
+        let client_state = dummy_client_state();
+
+        let any: Any = client_state.clone().into();
+        assert_eq!(ClientState::try_from(any).unwrap(), client_state);
+    }
+
+    #[test]
+    fn header_rotates_the_key_and_increments_the_sequence() {
+        let client_state = dummy_client_state();
+        let new_public_key = PublicKey::secp256k1(&[3u8; 33]);
+
+        let header = Header {
+            sequence: client_state.sequence,
+            timestamp: Timestamp::from_nanoseconds(2).unwrap(),
+            signature: Vec::new(),
+            new_public_key: new_public_key.clone(),
+            new_diversifier: "solo-2".to_string(),
+        };
+
+        let updated = client_state.with_header(header.clone());
+
+        assert_eq!(updated.latest_height(), header.height());
+        assert_eq!(updated.latest_height(), Height::new(0, 2).unwrap());
+        assert_eq!(updated.consensus_state.public_key, new_public_key);
+        assert_eq!(updated.consensus_state.diversifier, "solo-2");
+    }
+}
@@ -0,0 +1,218 @@
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::protobuf::Protobuf;
+use prost::Message;
+use serde::{Deserialize, Serialize};
+
+use crate::clients::ics06_solomachine::error::Error;
+use crate::clients::ics06_solomachine::header::Header;
+use crate::core::ics02_client::client_type::ClientType;
+use crate::core::ics02_client::error::Error as Ics02Error;
+use crate::core::ics23_commitment::commitment::CommitmentRoot;
+use crate::timestamp::Timestamp;
+
+pub const SOLOMACHINE_CONSENSUS_STATE_TYPE_URL: &str =
+    "/ibc.lightclients.solomachine.v3.ConsensusState";
+
+/// Encoding of a solo machine consensus state, `ibc.lightclients.solomachine.v3.ConsensusState`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct RawConsensusState {
+    /// The public key of the solo machine, encoded as `Any`.
+    #[prost(message, optional, tag = "1")]
+    pub public_key: Option<Any>,
+    #[prost(string, tag = "2")]
+    pub diversifier: String,
+    #[prost(uint64, tag = "3")]
+    pub timestamp: u64,
+}
+
+/// Type URL of the Cosmos SDK secp256k1 public keys, the only keys
+/// the relayer can sign with on behalf of a solo machine.
+pub const SECP256K1_PUBLIC_KEY_TYPE_URL: &str = "/cosmos.crypto.secp256k1.PubKey";
+
+/// The public key of a solo machine, as a protobuf `Any` holding a Cosmos SDK public key.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PublicKey {
+    pub type_url: String,
+    pub value: Vec<u8>,
+}
+
+impl PublicKey {
+    /// Wrap the given compressed secp256k1 public key.
+    pub fn secp256k1(key_bytes: &[u8]) -> Self {
+        // A Cosmos SDK public key is a message whose only field is the key bytes
+        let mut value = Vec::new();
+        key_bytes
+            .to_vec()
+            .encode(&mut value)
+            .expect("encoding into a Vec never fails");
+
+        Self {
+            type_url: SECP256K1_PUBLIC_KEY_TYPE_URL.to_string(),
+            value,
+        }
+    }
+
+    /// The raw bytes of a secp256k1 public key.
+    pub fn secp256k1_bytes(&self) -> Result<Vec<u8>, Error> {
+        if self.type_url != SECP256K1_PUBLIC_KEY_TYPE_URL {
+            return Err(Error::invalid_public_key(format!(
+                "unsupported public key type '{}'",
+                self.type_url
+            )));
+        }
+
+        Vec::<u8>::decode(self.value.as_slice()).map_err(Error::decode)
+    }
+}
+
+impl From<Any> for PublicKey {
+    fn from(any: Any) -> Self {
+        Self {
+            type_url: any.type_url,
+            value: any.value,
+        }
+    }
+}
+
+impl From<PublicKey> for Any {
+    fn from(public_key: PublicKey) -> Self {
+        Any {
+            type_url: public_key.type_url,
+            value: public_key.value,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConsensusState {
+    /// The key which signs the next header and proofs.
+    pub public_key: PublicKey,
+    /// An arbitrary string chosen by the solo machine and added to its sign bytes,
+    /// so that a key can be reused across solo machines without replay.
+    pub diversifier: String,
+    pub timestamp: Timestamp,
+    /// Solo machines have no commitment root, this is always empty.
+    root: CommitmentRoot,
+}
+
+impl ConsensusState {
+    pub fn new(public_key: PublicKey, diversifier: String, timestamp: Timestamp) -> Self {
+        Self {
+            public_key,
+            diversifier,
+            timestamp,
+            root: CommitmentRoot::from(Vec::new()),
+        }
+    }
+}
+
+impl crate::core::ics02_client::consensus_state::ConsensusState for ConsensusState {
+    fn client_type(&self) -> ClientType {
+        ClientType::Solomachine
+    }
+
+    fn root(&self) -> &CommitmentRoot {
+        &self.root
+    }
+
+    fn timestamp(&self) -> Timestamp {
+        self.timestamp
+    }
+}
+
+impl Protobuf<RawConsensusState> for ConsensusState {}
+
+impl TryFrom<RawConsensusState> for ConsensusState {
+    type Error = Error;
+
+    fn try_from(raw: RawConsensusState) -> Result<Self, Self::Error> {
+        let public_key = raw
+            .public_key
+            .ok_or_else(|| Error::invalid_raw_consensus_state("missing public key".into()))?;
+
+        Ok(Self::new(
+            public_key.into(),
+            raw.diversifier,
+            Timestamp::from_nanoseconds(raw.timestamp).map_err(Error::invalid_timestamp)?,
+        ))
+    }
+}
+
+impl From<ConsensusState> for RawConsensusState {
+    fn from(value: ConsensusState) -> Self {
+        RawConsensusState {
+            public_key: Some(value.public_key.into()),
+            diversifier: value.diversifier,
+            timestamp: value.timestamp.nanoseconds(),
+        }
+    }
+}
+
+impl Protobuf<Any> for ConsensusState {}
+
+impl TryFrom<Any> for ConsensusState {
+    type Error = Ics02Error;
+
+    fn try_from(raw: Any) -> Result<Self, Self::Error> {
+        fn decode_consensus_state(buf: &[u8]) -> Result<ConsensusState, Error> {
+            RawConsensusState::decode(buf)
+                .map_err(Error::decode)?
+                .try_into()
+        }
+
+        match raw.type_url.as_str() {
+            SOLOMACHINE_CONSENSUS_STATE_TYPE_URL => {
+                decode_consensus_state(&raw.value).map_err(Into::into)
+            }
+            _ => Err(Ics02Error::unknown_consensus_state_type(raw.type_url)),
+        }
+    }
+}
+
+impl From<ConsensusState> for Any {
+    fn from(consensus_state: ConsensusState) -> Self {
+        Any {
+            type_url: SOLOMACHINE_CONSENSUS_STATE_TYPE_URL.to_string(),
+            value: Protobuf::<RawConsensusState>::encode_vec(&consensus_state),
+        }
+    }
+}
+
+impl From<Header> for ConsensusState {
+    fn from(header: Header) -> Self {
+        Self::new(
+            header.new_public_key,
+            header.new_diversifier,
+            header.timestamp,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ibc_proto::google::protobuf::Any;
+    use test_log::test;
+
+    use super::{ConsensusState, PublicKey};
+    use crate::timestamp::Timestamp;
+
+    #[test]
+    fn public_key_round_trip() {
+        let key_bytes = [2u8; 33];
+        let public_key = PublicKey::secp256k1(&key_bytes);
+
+        assert_eq!(public_key.secp256k1_bytes().unwrap(), key_bytes.to_vec());
+    }
+
+    #[test]
+    fn consensus_state_any_round_trip() {
+        let consensus_state = ConsensusState::new(
+            PublicKey::secp256k1(&[3u8; 33]),
+            "solo".to_string(),
+            Timestamp::from_nanoseconds(1_700_000_000_000_000_000).unwrap(),
+        );
+
+        let any: Any = consensus_state.clone().into();
+        assert_eq!(ConsensusState::try_from(any).unwrap(), consensus_state);
+    }
+}
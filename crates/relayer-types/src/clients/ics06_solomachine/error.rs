@@ -0,0 +1,43 @@
+use flex_error::{define_error, TraceError};
+
+use crate::core::ics02_client::error::Error as Ics02Error;
+use crate::timestamp::ParseTimestampError;
+
+define_error! {
+    #[derive(Debug, PartialEq, Eq)]
+    Error {
+        InvalidRawClientState
+            { reason: String }
+            |e| { format_args!("invalid raw client state: {}", e.reason) },
+
+        InvalidRawConsensusState
+            { reason: String }
+            |e| { format_args!("invalid raw consensus state: {}", e.reason) },
+
+        InvalidRawHeader
+            { reason: String }
+            |e| { format_args!("invalid raw header: {}", e.reason) },
+
+        InvalidRawMisbehaviour
+            { reason: String }
+            |e| { format_args!("invalid raw misbehaviour: {}", e.reason) },
+
+        InvalidPublicKey
+            { reason: String }
+            |e| { format_args!("invalid public key: {}", e.reason) },
+
+        InvalidTimestamp
+            [ ParseTimestampError ]
+            |_| { "invalid timestamp" },
+
+        Decode
+            [ TraceError<prost::DecodeError> ]
+            |_| { "decode error" },
+    }
+}
+
+impl From<Error> for Ics02Error {
+    fn from(e: Error) -> Self {
+        Self::client_specific(e.to_string())
+    }
+}
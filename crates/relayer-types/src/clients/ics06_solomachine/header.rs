@@ -0,0 +1,140 @@
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::protobuf::Protobuf;
+use prost::Message;
+use serde::{Deserialize, Serialize};
+
+use crate::clients::ics06_solomachine::consensus_state::PublicKey;
+use crate::clients::ics06_solomachine::error::Error;
+use crate::clients::ics06_solomachine::proof::{header_data, sign_bytes, SENTINEL_HEADER_PATH};
+use crate::core::ics02_client::client_type::ClientType;
+use crate::core::ics02_client::error::Error as Ics02Error;
+use crate::timestamp::Timestamp;
+use crate::Height;
+
+pub const SOLOMACHINE_HEADER_TYPE_URL: &str = "/ibc.lightclients.solomachine.v3.Header";
+
+/// Encoding of a solo machine header, `ibc.lightclients.solomachine.v3.Header`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct RawHeader {
+    #[prost(uint64, tag = "1")]
+    pub timestamp: u64,
+    #[prost(bytes = "vec", tag = "2")]
+    pub signature: Vec<u8>,
+    #[prost(message, optional, tag = "3")]
+    pub new_public_key: Option<Any>,
+    #[prost(string, tag = "4")]
+    pub new_diversifier: String,
+}
+
+/// A solo machine header, which switches the client to a new key and diversifier.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Header {
+    /// The sequence of the client when the header was signed.
+    /// The client verifies the header against its own sequence, hence the sequence
+    /// is not part of the encoded header, and is zero in decoded headers.
+    pub sequence: u64,
+    pub timestamp: Timestamp,
+    /// The signature of the header data, as encoded by [`signature_data`].
+    ///
+    /// [`signature_data`]: crate::clients::ics06_solomachine::proof::signature_data
+    pub signature: Vec<u8>,
+    pub new_public_key: PublicKey,
+    pub new_diversifier: String,
+}
+
+impl Header {
+    /// The bytes to sign for a header at the given sequence, with the current
+    /// diversifier of the client.
+    pub fn sign_bytes(
+        sequence: u64,
+        timestamp: Timestamp,
+        diversifier: &str,
+        new_public_key: &PublicKey,
+        new_diversifier: &str,
+    ) -> Vec<u8> {
+        sign_bytes(
+            sequence,
+            timestamp,
+            diversifier,
+            SENTINEL_HEADER_PATH.as_bytes().to_vec(),
+            header_data(new_public_key, new_diversifier),
+        )
+    }
+
+    /// The height of the client once updated with this header.
+    pub fn height(&self) -> Height {
+        Height::new(0, self.sequence + 1).expect("non-zero height")
+    }
+}
+
+impl crate::core::ics02_client::header::Header for Header {
+    fn client_type(&self) -> ClientType {
+        ClientType::Solomachine
+    }
+
+    fn height(&self) -> Height {
+        self.height()
+    }
+
+    fn timestamp(&self) -> Timestamp {
+        self.timestamp
+    }
+}
+
+impl Protobuf<RawHeader> for Header {}
+
+impl TryFrom<RawHeader> for Header {
+    type Error = Error;
+
+    fn try_from(raw: RawHeader) -> Result<Self, Self::Error> {
+        let new_public_key = raw
+            .new_public_key
+            .ok_or_else(|| Error::invalid_raw_header("missing new public key".into()))?;
+
+        Ok(Self {
+            sequence: 0,
+            timestamp: Timestamp::from_nanoseconds(raw.timestamp)
+                .map_err(Error::invalid_timestamp)?,
+            signature: raw.signature,
+            new_public_key: new_public_key.into(),
+            new_diversifier: raw.new_diversifier,
+        })
+    }
+}
+
+impl From<Header> for RawHeader {
+    fn from(value: Header) -> Self {
+        RawHeader {
+            timestamp: value.timestamp.nanoseconds(),
+            signature: value.signature,
+            new_public_key: Some(value.new_public_key.into()),
+            new_diversifier: value.new_diversifier,
+        }
+    }
+}
+
+impl Protobuf<Any> for Header {}
+
+impl TryFrom<Any> for Header {
+    type Error = Ics02Error;
+
+    fn try_from(raw: Any) -> Result<Self, Ics02Error> {
+        match raw.type_url.as_str() {
+            SOLOMACHINE_HEADER_TYPE_URL => decode_header(&raw.value).map_err(Into::into),
+            _ => Err(Ics02Error::unknown_header_type(raw.type_url)),
+        }
+    }
+}
+
+impl From<Header> for Any {
+    fn from(header: Header) -> Self {
+        Any {
+            type_url: SOLOMACHINE_HEADER_TYPE_URL.to_string(),
+            value: Protobuf::<RawHeader>::encode_vec(&header),
+        }
+    }
+}
+
+pub fn decode_header(buf: &[u8]) -> Result<Header, Error> {
+    RawHeader::decode(buf).map_err(Error::decode)?.try_into()
+}
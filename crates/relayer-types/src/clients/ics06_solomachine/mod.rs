@@ -0,0 +1,14 @@
+//! ICS 06: Solo Machine Client implements a client verification algorithm for
+//! standalone machines, such as phones, browsers or custodial services, which
+//! authenticate their state with the signature of a single key instead of a
+//! consensus algorithm.
+//!
+//! The height of a solo machine client is the sequence of the next signature it
+//! expects, and each proof or header verified by the client consumes a sequence.
+
+pub mod client_state;
+pub mod consensus_state;
+pub mod error;
+pub mod header;
+pub mod misbehaviour;
+pub mod proof;
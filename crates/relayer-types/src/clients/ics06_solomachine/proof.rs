@@ -0,0 +1,164 @@
+//! The messages a solo machine signs, and the encoding of its signatures.
+//!
+//! A solo machine proves a value by signing it along with the path of the value,
+//! its current sequence, a timestamp and its diversifier. The counterparty client
+//! rebuilds these sign bytes from its own state when verifying the proof, hence
+//! the encoding must match the one of ibc-go byte for byte.
+
+use ibc_proto::cosmos::tx::signing::v1beta1::signature_descriptor::{
+    data::{Single, Sum},
+    Data as SignatureData,
+};
+use ibc_proto::google::protobuf::Any;
+use prost::Message;
+
+use crate::clients::ics06_solomachine::consensus_state::PublicKey;
+use crate::core::ics23_commitment::commitment::{CommitmentPrefix, CommitmentProofBytes};
+use crate::core::ics24_host::path::Path;
+use crate::proofs::ProofError;
+use crate::timestamp::Timestamp;
+
+/// The path signed along with the data of a header.
+pub const SENTINEL_HEADER_PATH: &str = "solomachine:header";
+
+/// Encoding of the bytes signed by a solo machine, `ibc.lightclients.solomachine.v3.SignBytes`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct RawSignBytes {
+    #[prost(uint64, tag = "1")]
+    pub sequence: u64,
+    #[prost(uint64, tag = "2")]
+    pub timestamp: u64,
+    #[prost(string, tag = "3")]
+    pub diversifier: String,
+    #[prost(bytes = "vec", tag = "4")]
+    pub path: Vec<u8>,
+    #[prost(bytes = "vec", tag = "5")]
+    pub data: Vec<u8>,
+}
+
+/// Encoding of the data signed by a header, `ibc.lightclients.solomachine.v3.HeaderData`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct RawHeaderData {
+    #[prost(message, optional, tag = "1")]
+    pub new_pub_key: Option<Any>,
+    #[prost(string, tag = "2")]
+    pub new_diversifier: String,
+}
+
+/// Encoding of a solo machine proof,
+/// `ibc.lightclients.solomachine.v3.TimestampedSignatureData`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct RawTimestampedSignatureData {
+    #[prost(bytes = "vec", tag = "1")]
+    pub signature_data: Vec<u8>,
+    #[prost(uint64, tag = "2")]
+    pub timestamp: u64,
+}
+
+/// The path at which a solo machine proves a value, ie. the string
+/// representation of the ICS-23 Merkle path of the value.
+pub fn merkle_path(prefix: &CommitmentPrefix, path: &Path) -> Vec<u8> {
+    let prefix = String::from_utf8_lossy(prefix.as_bytes());
+    let path = path.to_string();
+
+    [prefix.as_ref(), path.as_str()]
+        .iter()
+        .map(|key| format!("/{}", escape_path_segment(key)))
+        .collect::<String>()
+        .into_bytes()
+}
+
+/// Percent-encode a key of a Merkle path, as Go's `url.PathEscape` does.
+fn escape_path_segment(segment: &str) -> String {
+    fn is_unescaped(byte: u8) -> bool {
+        byte.is_ascii_alphanumeric()
+            || matches!(
+                byte,
+                b'-' | b'_' | b'.' | b'~' | b'$' | b'&' | b'+' | b':' | b'=' | b'@'
+            )
+    }
+
+    segment
+        .bytes()
+        .map(|byte| {
+            if is_unescaped(byte) {
+                char::from(byte).to_string()
+            } else {
+                format!("%{byte:02X}")
+            }
+        })
+        .collect()
+}
+
+/// The bytes a solo machine signs to prove `data` at `path`.
+pub fn sign_bytes(
+    sequence: u64,
+    timestamp: Timestamp,
+    diversifier: &str,
+    path: Vec<u8>,
+    data: Vec<u8>,
+) -> Vec<u8> {
+    RawSignBytes {
+        sequence,
+        timestamp: timestamp.nanoseconds(),
+        diversifier: diversifier.to_string(),
+        path,
+        data,
+    }
+    .encode_to_vec()
+}
+
+/// The data signed by a header, ie. the key and diversifier the solo machine switches to.
+pub fn header_data(new_public_key: &PublicKey, new_diversifier: &str) -> Vec<u8> {
+    RawHeaderData {
+        new_pub_key: Some(new_public_key.clone().into()),
+        new_diversifier: new_diversifier.to_string(),
+    }
+    .encode_to_vec()
+}
+
+/// Encode a signature made by a single key, as expected in headers and proofs.
+pub fn signature_data(signature: Vec<u8>) -> Vec<u8> {
+    SignatureData {
+        sum: Some(Sum::Single(Single {
+            // SIGN_MODE_UNSPECIFIED, the mode is not checked by solo machine clients
+            mode: 0,
+            signature,
+        })),
+    }
+    .encode_to_vec()
+}
+
+/// Build the proof carrying the given signature, made at the given timestamp.
+pub fn signature_proof(
+    signature: Vec<u8>,
+    timestamp: Timestamp,
+) -> Result<CommitmentProofBytes, ProofError> {
+    RawTimestampedSignatureData {
+        signature_data: signature_data(signature),
+        timestamp: timestamp.nanoseconds(),
+    }
+    .encode_to_vec()
+    .try_into()
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use super::merkle_path;
+    use crate::core::ics23_commitment::commitment::CommitmentPrefix;
+    use crate::core::ics24_host::identifier::ConnectionId;
+    use crate::core::ics24_host::path::{ConnectionsPath, Path};
+
+    #[test]
+    fn merkle_path_escapes_the_keys() {
+        let prefix = CommitmentPrefix::try_from(b"ibc".to_vec()).unwrap();
+        let path = Path::Connections(ConnectionsPath(ConnectionId::new(0)));
+
+        assert_eq!(
+            merkle_path(&prefix, &path),
+            b"/ibc/connections%2Fconnection-0".to_vec()
+        );
+    }
+}
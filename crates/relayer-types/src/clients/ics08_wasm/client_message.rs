@@ -0,0 +1,106 @@
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::protobuf::Protobuf;
+use prost::Message;
+
+use crate::clients::ics08_wasm::error::Error;
+use crate::core::ics02_client::error::Error as Ics02Error;
+
+pub const WASM_CLIENT_MESSAGE_TYPE_URL: &str = "/ibc.lightclients.wasm.v1.ClientMessage";
+
+/// Encoding of a Wasm client message, `ibc.lightclients.wasm.v1.ClientMessage`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct RawClientMessage {
+    /// The wrapped header or misbehaviour, encoded as `Any`.
+    #[prost(bytes = "vec", tag = "1")]
+    pub data: Vec<u8>,
+}
+
+/// A header or misbehaviour of the wrapped client, which the
+/// contract receives in the update messages of a Wasm client.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClientMessage {
+    pub data: Any,
+}
+
+impl ClientMessage {
+    pub fn new(data: Any) -> Self {
+        Self { data }
+    }
+
+    /// The wrapped header or misbehaviour.
+    pub fn into_inner(self) -> Any {
+        self.data
+    }
+}
+
+impl Protobuf<RawClientMessage> for ClientMessage {}
+
+impl TryFrom<RawClientMessage> for ClientMessage {
+    type Error = Error;
+
+    fn try_from(raw: RawClientMessage) -> Result<Self, Self::Error> {
+        let data = Any::decode(raw.data.as_slice()).map_err(Error::decode)?;
+
+        if data.type_url.is_empty() {
+            return Err(Error::invalid_raw_client_message(
+                "missing wrapped message".into(),
+            ));
+        }
+
+        Ok(Self { data })
+    }
+}
+
+impl From<ClientMessage> for RawClientMessage {
+    fn from(value: ClientMessage) -> Self {
+        RawClientMessage {
+            data: value.data.encode_to_vec(),
+        }
+    }
+}
+
+impl Protobuf<Any> for ClientMessage {}
+
+impl TryFrom<Any> for ClientMessage {
+    type Error = Ics02Error;
+
+    fn try_from(raw: Any) -> Result<Self, Self::Error> {
+        fn decode_client_message(buf: &[u8]) -> Result<ClientMessage, Error> {
+            RawClientMessage::decode(buf)
+                .map_err(Error::decode)?
+                .try_into()
+        }
+
+        match raw.type_url.as_str() {
+            WASM_CLIENT_MESSAGE_TYPE_URL => decode_client_message(&raw.value).map_err(Into::into),
+            _ => Err(Ics02Error::unknown_header_type(raw.type_url)),
+        }
+    }
+}
+
+impl From<ClientMessage> for Any {
+    fn from(client_message: ClientMessage) -> Self {
+        Any {
+            type_url: WASM_CLIENT_MESSAGE_TYPE_URL.to_string(),
+            value: Protobuf::<RawClientMessage>::encode_vec(&client_message),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ibc_proto::google::protobuf::Any;
+    use test_log::test;
+
+    use super::ClientMessage;
+    use crate::clients::ics07_tendermint::header::test_util::get_dummy_ics07_header;
+
+    #[test]
+    fn client_message_wraps_a_header() {
+        let header: Any = get_dummy_ics07_header().into();
+        let message: Any = ClientMessage::new(header.clone()).into();
+
+        let decoded = ClientMessage::try_from(message).unwrap();
+        assert_eq!(decoded.into_inner(), header);
+    }
+}
@@ -0,0 +1,204 @@
+use std::time::Duration;
+
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::core::client::v1::Height as RawHeight;
+use ibc_proto::protobuf::Protobuf;
+use prost::Message;
+use serde::{Deserialize, Serialize};
+
+use crate::clients::ics07_tendermint::client_state::{
+    ClientState as TmClientState, TENDERMINT_CLIENT_STATE_TYPE_URL,
+};
+use crate::clients::ics08_wasm::error::Error;
+use crate::core::ics02_client::client_state::{ClientState as Ics2ClientState, UpgradeOptions};
+use crate::core::ics02_client::client_type::ClientType;
+use crate::core::ics02_client::error::Error as Ics02Error;
+use crate::core::ics24_host::identifier::ChainId;
+use crate::Height;
+
+pub const WASM_CLIENT_STATE_TYPE_URL: &str = "/ibc.lightclients.wasm.v1.ClientState";
+
+/// Encoding of a Wasm client state, `ibc.lightclients.wasm.v1.ClientState`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct RawClientState {
+    /// The wrapped client state, encoded as `Any`.
+    #[prost(bytes = "vec", tag = "1")]
+    pub data: Vec<u8>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub code_hash: Vec<u8>,
+    #[prost(message, optional, tag = "3")]
+    pub latest_height: Option<RawHeight>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ClientState {
+    /// The hash of the code of the contract implementing the client.
+    #[serde(serialize_with = "crate::serializers::ser_hex_upper")]
+    pub code_hash: Vec<u8>,
+    pub latest_height: Height,
+    /// The Tendermint client state wrapped by the contract.
+    pub inner: TmClientState,
+}
+
+impl ClientState {
+    /// Wrap the given Tendermint client state for the contract with the given code hash.
+    pub fn new(code_hash: Vec<u8>, inner: TmClientState) -> Self {
+        Self {
+            code_hash,
+            latest_height: inner.latest_height,
+            inner,
+        }
+    }
+
+    pub fn latest_height(&self) -> Height {
+        self.latest_height
+    }
+}
+
+impl Ics2ClientState for ClientState {
+    fn chain_id(&self) -> ChainId {
+        self.inner.chain_id()
+    }
+
+    fn client_type(&self) -> ClientType {
+        ClientType::Wasm
+    }
+
+    fn latest_height(&self) -> Height {
+        self.latest_height
+    }
+
+    fn frozen_height(&self) -> Option<Height> {
+        self.inner.frozen_height()
+    }
+
+    /// Upgrades the wrapped client state, leaving the contract unchanged.
+    fn upgrade(
+        &mut self,
+        upgrade_height: Height,
+        upgrade_options: &dyn UpgradeOptions,
+        chain_id: ChainId,
+    ) {
+        self.inner
+            .upgrade(upgrade_height, upgrade_options, chain_id);
+        self.latest_height = upgrade_height;
+    }
+
+    fn expired(&self, elapsed: Duration) -> bool {
+        self.inner.expired(elapsed)
+    }
+}
+
+impl Protobuf<RawClientState> for ClientState {}
+
+impl TryFrom<RawClientState> for ClientState {
+    type Error = Error;
+
+    fn try_from(raw: RawClientState) -> Result<Self, Self::Error> {
+        let latest_height = raw
+            .latest_height
+            .ok_or_else(|| Error::invalid_raw_client_state("missing latest height".into()))?
+            .try_into()
+            .map_err(|_| Error::invalid_raw_client_state("invalid latest height".into()))?;
+
+        let data = Any::decode(raw.data.as_slice()).map_err(Error::decode)?;
+
+        let inner = match data.type_url.as_str() {
+            TENDERMINT_CLIENT_STATE_TYPE_URL => {
+                TmClientState::try_from(data).map_err(Error::wrapped_state)?
+            }
+            _ => return Err(Error::unsupported_wrapped_type(data.type_url)),
+        };
+
+        Ok(Self {
+            code_hash: raw.code_hash,
+            latest_height,
+            inner,
+        })
+    }
+}
+
+impl From<ClientState> for RawClientState {
+    fn from(value: ClientState) -> Self {
+        RawClientState {
+            data: Any::from(value.inner).encode_to_vec(),
+            code_hash: value.code_hash,
+            latest_height: Some(value.latest_height.into()),
+        }
+    }
+}
+
+impl Protobuf<Any> for ClientState {}
+
+impl TryFrom<Any> for ClientState {
+    type Error = Ics02Error;
+
+    fn try_from(raw: Any) -> Result<Self, Self::Error> {
+        fn decode_client_state(buf: &[u8]) -> Result<ClientState, Error> {
+            RawClientState::decode(buf)
+                .map_err(Error::decode)?
+                .try_into()
+        }
+
+        match raw.type_url.as_str() {
+            WASM_CLIENT_STATE_TYPE_URL => decode_client_state(&raw.value).map_err(Into::into),
+            _ => Err(Ics02Error::unknown_client_state_type(raw.type_url)),
+        }
+    }
+}
+
+impl From<ClientState> for Any {
+    fn from(client_state: ClientState) -> Self {
+        Any {
+            type_url: WASM_CLIENT_STATE_TYPE_URL.to_string(),
+            value: Protobuf::<RawClientState>::encode_vec(&client_state),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ibc_proto::google::protobuf::Any;
+    use prost::Message;
+    use test_log::test;
+
+    use super::{ClientState, RawClientState, WASM_CLIENT_STATE_TYPE_URL};
+    use crate::clients::ics07_tendermint::client_state::test_util::get_dummy_tendermint_client_state;
+    use crate::clients::ics07_tendermint::header::test_util::get_dummy_tendermint_header;
+    use crate::core::ics02_client::client_state::ClientState as _;
+    use crate::core::ics02_client::client_type::ClientType;
+
+    #[test]
+    fn client_state_any_round_trip() {
+        let inner = get_dummy_tendermint_client_state(get_dummy_tendermint_header());
+        let client_state = ClientState::new(vec![0xab; 32], inner.clone());
+
+        let any: Any = client_state.clone().into();
+        let decoded = ClientState::try_from(any).unwrap();
+
+        assert_eq!(decoded, client_state);
+        assert_eq!(decoded.inner, inner);
+        assert_eq!(decoded.client_type(), ClientType::Wasm);
+        assert_eq!(decoded.chain_id(), inner.chain_id());
+    }
+
+    #[test]
+    fn client_state_wrapping_an_unsupported_type_fails() {
+        let raw = RawClientState {
+            data: Any {
+                type_url: "/ibc.lightclients.grandpa.v1.ClientState".to_string(),
+                value: vec![],
+            }
+            .encode_to_vec(),
+            code_hash: vec![0xab; 32],
+            latest_height: Some(crate::Height::new(1, 10).unwrap().into()),
+        };
+
+        let any = Any {
+            type_url: WASM_CLIENT_STATE_TYPE_URL.to_string(),
+            value: raw.encode_to_vec(),
+        };
+
+        assert!(ClientState::try_from(any).is_err());
+    }
+}
@@ -0,0 +1,38 @@
+use flex_error::{define_error, TraceError};
+
+use crate::core::ics02_client::error::Error as Ics02Error;
+
+define_error! {
+    #[derive(Debug, PartialEq, Eq)]
+    Error {
+        InvalidRawClientState
+            { reason: String }
+            |e| { format_args!("invalid raw client state: {}", e.reason) },
+
+        InvalidRawConsensusState
+            { reason: String }
+            |e| { format_args!("invalid raw consensus state: {}", e.reason) },
+
+        InvalidRawClientMessage
+            { reason: String }
+            |e| { format_args!("invalid raw client message: {}", e.reason) },
+
+        UnsupportedWrappedType
+            { type_url: String }
+            |e| { format_args!("unsupported type wrapped by the Wasm client: {}", e.type_url) },
+
+        WrappedState
+            [ Ics02Error ]
+            |_| { "invalid state wrapped by the Wasm client" },
+
+        Decode
+            [ TraceError<prost::DecodeError> ]
+            |_| { "decode error" },
+    }
+}
+
+impl From<Error> for Ics02Error {
+    fn from(e: Error) -> Self {
+        Self::client_specific(e.to_string())
+    }
+}
@@ -0,0 +1,11 @@
+//! ICS 08: Wasm Client wraps the state of a light client implemented by a CosmWasm
+//! contract, stored on the host chain under the hash of its code.
+//!
+//! The states and messages of the wrapped client are encoded as `Any` in the `data`
+//! field of their Wasm counterparts. The relayer only supports contracts wrapping
+//! Tendermint clients, which it otherwise handles as regular Tendermint clients.
+
+pub mod client_message;
+pub mod client_state;
+pub mod consensus_state;
+pub mod error;
@@ -0,0 +1,159 @@
+use std::time::Duration;
+
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::core::client::v1::Height as RawHeight;
+use ibc_proto::protobuf::Protobuf;
+use prost::Message;
+use serde::{Deserialize, Serialize};
+
+use crate::core::ics02_client::client_state::{ClientState as Ics2ClientState, UpgradeOptions};
+use crate::core::ics02_client::client_type::ClientType;
+use crate::core::ics02_client::error::Error;
+use crate::core::ics24_host::identifier::ChainId;
+use crate::Height;
+
+pub const LOCALHOST_CLIENT_STATE_TYPE_URL: &str = "/ibc.lightclients.localhost.v2.ClientState";
+
+/// Encoding of a localhost client state, `ibc.lightclients.localhost.v2.ClientState`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct RawClientState {
+    #[prost(message, optional, tag = "1")]
+    pub latest_height: Option<RawHeight>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClientState {
+    /// The chain hosting the client.
+    ///
+    /// It is not part of the encoding of the client state, hence a decoded
+    /// client state has a default chain identifier until it is set with
+    /// [`ClientState::with_chain_id`] by the chain it was queried from.
+    pub chain_id: ChainId,
+    /// The latest height of the host, which the host updates at every block.
+    pub latest_height: Height,
+}
+
+impl ClientState {
+    pub fn new(chain_id: ChainId, latest_height: Height) -> Self {
+        Self {
+            chain_id,
+            latest_height,
+        }
+    }
+
+    pub fn with_chain_id(self, chain_id: ChainId) -> Self {
+        Self { chain_id, ..self }
+    }
+
+    pub fn latest_height(&self) -> Height {
+        self.latest_height
+    }
+}
+
+impl Ics2ClientState for ClientState {
+    fn chain_id(&self) -> ChainId {
+        self.chain_id.clone()
+    }
+
+    fn client_type(&self) -> ClientType {
+        ClientType::Localhost
+    }
+
+    fn latest_height(&self) -> Height {
+        self.latest_height
+    }
+
+    /// The localhost client cannot be frozen, since it cannot be misbehaving.
+    fn frozen_height(&self) -> Option<Height> {
+        None
+    }
+
+    /// The localhost client follows the upgrades of its host.
+    fn upgrade(
+        &mut self,
+        upgrade_height: Height,
+        _upgrade_options: &dyn UpgradeOptions,
+        chain_id: ChainId,
+    ) {
+        self.chain_id = chain_id;
+        self.latest_height = upgrade_height;
+    }
+
+    /// The localhost client never expires.
+    fn expired(&self, _elapsed: Duration) -> bool {
+        false
+    }
+}
+
+impl Protobuf<RawClientState> for ClientState {}
+
+impl TryFrom<RawClientState> for ClientState {
+    type Error = Error;
+
+    fn try_from(raw: RawClientState) -> Result<Self, Self::Error> {
+        let latest_height = raw
+            .latest_height
+            .ok_or_else(Error::missing_raw_client_state)?
+            .try_into()?;
+
+        Ok(Self {
+            chain_id: ChainId::default(),
+            latest_height,
+        })
+    }
+}
+
+impl From<ClientState> for RawClientState {
+    fn from(value: ClientState) -> Self {
+        RawClientState {
+            latest_height: Some(value.latest_height.into()),
+        }
+    }
+}
+
+impl Protobuf<Any> for ClientState {}
+
+impl TryFrom<Any> for ClientState {
+    type Error = Error;
+
+    fn try_from(raw: Any) -> Result<Self, Self::Error> {
+        match raw.type_url.as_str() {
+            LOCALHOST_CLIENT_STATE_TYPE_URL => RawClientState::decode(raw.value.as_ref())
+                .map_err(|e| Error::client_specific(e.to_string()))?
+                .try_into(),
+            _ => Err(Error::unknown_client_state_type(raw.type_url)),
+        }
+    }
+}
+
+impl From<ClientState> for Any {
+    fn from(client_state: ClientState) -> Self {
+        Any {
+            type_url: LOCALHOST_CLIENT_STATE_TYPE_URL.to_string(),
+            value: Protobuf::<RawClientState>::encode_vec(&client_state),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ibc_proto::google::protobuf::Any;
+    use test_log::test;
+
+    use super::ClientState;
+    use crate::core::ics24_host::identifier::ChainId;
+    use crate::Height;
+
+    #[test]
+    fn client_state_any_round_trip_drops_the_chain_id() {
+        let chain_id = ChainId::new("ibc".to_string(), 1);
+        let client_state = ClientState::new(chain_id.clone(), Height::new(1, 10).unwrap());
+
+        let any: Any = client_state.clone().into();
+        let decoded = ClientState::try_from(any).unwrap();
+
+        assert_eq!(decoded.latest_height(), client_state.latest_height());
+        assert_eq!(decoded.chain_id, ChainId::default());
+        assert_eq!(decoded.with_chain_id(chain_id), client_state);
+    }
+}
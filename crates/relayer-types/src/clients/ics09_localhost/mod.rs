@@ -0,0 +1,71 @@
+//! ICS 09: Loopback Client allows a chain to verify its own state, so that two
+//! modules on the same chain can communicate over IBC.
+//!
+//! The localhost client is a singleton identified by `09-localhost`, bound to the
+//! `connection-localhost` connection which is always open. It reads the host's
+//! store directly, so it has no consensus states nor headers, and it accepts
+//! the [`SENTINEL_PROOF`] in place of any proof.
+
+use crate::core::ics23_commitment::commitment::CommitmentProofBytes;
+use crate::proofs::{ConsensusProof, Proofs};
+
+pub mod client_state;
+
+/// The proof accepted by the localhost client for any (non-)membership.
+pub const SENTINEL_PROOF: &[u8] = &[0x01];
+
+/// Yields the sentinel proof as the bytes of a commitment proof.
+pub fn sentinel_proof() -> CommitmentProofBytes {
+    SENTINEL_PROOF
+        .to_vec()
+        .try_into()
+        .expect("the sentinel proof is not empty")
+}
+
+/// Replaces each of the given proofs with the sentinel proof, retaining their heights.
+pub fn sentinel_proofs(proofs: &Proofs) -> Proofs {
+    let consensus_proof = proofs.consensus_proof().map(|proof| {
+        ConsensusProof::new(sentinel_proof(), proof.height())
+            .expect("building a consensus proof is infallible")
+    });
+
+    Proofs::new(
+        sentinel_proof(),
+        proofs.client_proof().as_ref().map(|_| sentinel_proof()),
+        consensus_proof,
+        proofs.other_proof().as_ref().map(|_| sentinel_proof()),
+        proofs.height(),
+    )
+    .expect("building proofs is infallible")
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use super::{sentinel_proofs, SENTINEL_PROOF};
+    use crate::core::ics23_commitment::commitment::CommitmentProofBytes;
+    use crate::proofs::Proofs;
+    use crate::Height;
+
+    #[test]
+    fn sentinel_proofs_keep_the_shape_and_height() {
+        let proof: CommitmentProofBytes = vec![0xaa; 32].try_into().unwrap();
+        let height = Height::new(1, 42).unwrap();
+        let proofs = Proofs::new(proof.clone(), None, None, Some(proof), height).unwrap();
+
+        let sentinel = sentinel_proofs(&proofs);
+
+        assert_eq!(sentinel.height(), height);
+        assert_eq!(
+            Vec::<u8>::from(sentinel.object_proof().clone()),
+            SENTINEL_PROOF
+        );
+        assert!(sentinel.client_proof().is_none());
+        assert!(sentinel.consensus_proof().is_none());
+        assert_eq!(
+            sentinel.other_proof().clone().map(Vec::<u8>::from),
+            Some(SENTINEL_PROOF.to_vec())
+        );
+    }
+}
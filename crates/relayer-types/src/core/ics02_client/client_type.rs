@@ -7,6 +7,9 @@ use super::error::Error;
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum ClientType {
     Tendermint = 1,
+    Solomachine = 6,
+    Wasm = 8,
+    Localhost = 9,
 
     #[cfg(any(test, feature = "mocks"))]
     Mock = 9999,
@@ -14,6 +17,9 @@ pub enum ClientType {
 
 impl ClientType {
     const TENDERMINT_STR: &'static str = "07-tendermint";
+    const SOLOMACHINE_STR: &'static str = "06-solomachine";
+    const WASM_STR: &'static str = "08-wasm";
+    const LOCALHOST_STR: &'static str = "09-localhost";
 
     #[cfg_attr(not(test), allow(dead_code))]
     const MOCK_STR: &'static str = "9999-mock";
@@ -22,6 +28,9 @@ impl ClientType {
     pub fn as_str(&self) -> &'static str {
         match self {
             Self::Tendermint => Self::TENDERMINT_STR,
+            Self::Solomachine => Self::SOLOMACHINE_STR,
+            Self::Wasm => Self::WASM_STR,
+            Self::Localhost => Self::LOCALHOST_STR,
 
             #[cfg(any(test, feature = "mocks"))]
             Self::Mock => Self::MOCK_STR,
@@ -41,6 +50,9 @@ impl core::str::FromStr for ClientType {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             Self::TENDERMINT_STR => Ok(Self::Tendermint),
+            Self::SOLOMACHINE_STR => Ok(Self::Solomachine),
+            Self::WASM_STR => Ok(Self::Wasm),
+            Self::LOCALHOST_STR => Ok(Self::Localhost),
 
             #[cfg(any(test, feature = "mocks"))]
             Self::MOCK_STR => Ok(Self::Mock),
@@ -68,6 +80,36 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_solomachine_client_type() {
+        let client_type = ClientType::from_str("06-solomachine");
+
+        match client_type {
+            Ok(ClientType::Solomachine) => (),
+            _ => panic!("parse failed"),
+        }
+    }
+
+    #[test]
+    fn parse_wasm_client_type() {
+        let client_type = ClientType::from_str("08-wasm");
+
+        match client_type {
+            Ok(ClientType::Wasm) => (),
+            _ => panic!("parse failed"),
+        }
+    }
+
+    #[test]
+    fn parse_localhost_client_type() {
+        let client_type = ClientType::from_str("09-localhost");
+
+        match client_type {
+            Ok(ClientType::Localhost) => (),
+            _ => panic!("parse failed"),
+        }
+    }
+
     #[test]
     fn parse_mock_client_type() {
         let client_type = ClientType::from_str("9999-mock");
@@ -101,6 +143,14 @@ mod tests {
         assert_eq!(client_type_from_str, client_type);
     }
 
+    #[test]
+    fn parse_solomachine_as_string_result() {
+        let client_type = ClientType::Solomachine;
+        let type_string = client_type.as_str();
+        let client_type_from_str = ClientType::from_str(type_string).unwrap();
+        assert_eq!(client_type_from_str, client_type);
+    }
+
     #[test]
     fn parse_tendermint_as_string_result() {
         let client_type = ClientType::Tendermint;
@@ -11,6 +11,7 @@ use ibc_proto::ibc::core::channel::v1::{
     IdentifiedChannel as RawIdentifiedChannel,
 };
 
+use crate::core::ics04_channel::packet::Sequence;
 use crate::core::ics04_channel::{error::Error, version::Version};
 use crate::core::ics24_host::identifier::{ChannelId, ConnectionId, PortId};
 
@@ -72,6 +73,25 @@ impl From<IdentifiedChannelEnd> for RawIdentifiedChannel {
     }
 }
 
+/// Encoding of a channel end which supports upgrades, `ibc.core.channel.v1.Channel`
+/// as defined by ibc-go v8, which adds the `upgrade_sequence` field.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct RawUpgradableChannel {
+    #[prost(int32, tag = "1")]
+    pub state: i32,
+    #[prost(int32, tag = "2")]
+    pub ordering: i32,
+    #[prost(message, optional, tag = "3")]
+    pub counterparty: Option<RawCounterparty>,
+    #[prost(string, repeated, tag = "4")]
+    pub connection_hops: Vec<String>,
+    #[prost(string, tag = "5")]
+    pub version: String,
+    /// The latest upgrade attempt performed by this channel.
+    #[prost(uint64, tag = "6")]
+    pub upgrade_sequence: u64,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ChannelEnd {
     pub state: State,
@@ -79,14 +99,20 @@ pub struct ChannelEnd {
     pub remote: Counterparty,
     pub connection_hops: Vec<ConnectionId>,
     pub version: Version,
+    pub upgrade_sequence: Sequence,
 }
 
 impl Display for ChannelEnd {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
         write!(
             f,
-            "ChannelEnd {{ state: {}, ordering: {}, remote: {}, connection_hops: {}, version: {} }}",
-            self.state, self.ordering, self.remote, PrettySlice(&self.connection_hops), self.version
+            "ChannelEnd {{ state: {}, ordering: {}, remote: {}, connection_hops: {}, version: {}, upgrade_sequence: {} }}",
+            self.state,
+            self.ordering,
+            self.remote,
+            PrettySlice(&self.connection_hops),
+            self.version,
+            self.upgrade_sequence
         )
     }
 }
@@ -99,11 +125,46 @@ impl Default for ChannelEnd {
             remote: Counterparty::default(),
             connection_hops: Vec::new(),
             version: Version::default(),
+            upgrade_sequence: Sequence::default(),
         }
     }
 }
 
-impl Protobuf<RawChannel> for ChannelEnd {}
+impl Protobuf<RawUpgradableChannel> for ChannelEnd {}
+
+impl TryFrom<RawUpgradableChannel> for ChannelEnd {
+    type Error = Error;
+
+    fn try_from(value: RawUpgradableChannel) -> Result<Self, Self::Error> {
+        let upgrade_sequence = Sequence::from(value.upgrade_sequence);
+
+        let channel_end = ChannelEnd::try_from(RawChannel {
+            state: value.state,
+            ordering: value.ordering,
+            counterparty: value.counterparty,
+            connection_hops: value.connection_hops,
+            version: value.version,
+        })?;
+
+        Ok(channel_end.with_upgrade_sequence(upgrade_sequence))
+    }
+}
+
+impl From<ChannelEnd> for RawUpgradableChannel {
+    fn from(value: ChannelEnd) -> Self {
+        let upgrade_sequence = value.upgrade_sequence.into();
+        let raw_channel = RawChannel::from(value);
+
+        RawUpgradableChannel {
+            state: raw_channel.state,
+            ordering: raw_channel.ordering,
+            counterparty: raw_channel.counterparty,
+            connection_hops: raw_channel.connection_hops,
+            version: raw_channel.version,
+            upgrade_sequence,
+        }
+    }
+}
 
 impl TryFrom<RawChannel> for ChannelEnd {
     type Error = Error;
@@ -174,6 +235,15 @@ impl ChannelEnd {
             remote,
             connection_hops,
             version,
+            upgrade_sequence: Sequence::default(),
+        }
+    }
+
+    /// Sets the sequence of the latest upgrade attempt of this channel end.
+    pub fn with_upgrade_sequence(self, upgrade_sequence: Sequence) -> Self {
+        Self {
+            upgrade_sequence,
+            ..self
         }
     }
 
@@ -215,6 +285,10 @@ impl ChannelEnd {
         &self.version
     }
 
+    pub fn upgrade_sequence(&self) -> Sequence {
+        self.upgrade_sequence
+    }
+
     pub fn validate_basic(&self) -> Result<(), Error> {
         if self.connection_hops.len() != 1 {
             return Err(Error::invalid_connection_hops_length(
@@ -378,6 +452,8 @@ pub enum State {
     TryOpen = 2,
     Open = 3,
     Closed = 4,
+    Flushing = 5,
+    FlushComplete = 6,
 }
 
 impl State {
@@ -389,6 +465,8 @@ impl State {
             Self::TryOpen => "TRYOPEN",
             Self::Open => "OPEN",
             Self::Closed => "CLOSED",
+            Self::Flushing => "FLUSHING",
+            Self::FlushComplete => "FLUSHCOMPLETE",
         }
     }
 
@@ -400,6 +478,8 @@ impl State {
             2 => Ok(Self::TryOpen),
             3 => Ok(Self::Open),
             4 => Ok(Self::Closed),
+            5 => Ok(Self::Flushing),
+            6 => Ok(Self::FlushComplete),
             _ => Err(Error::unknown_state(s)),
         }
     }
@@ -414,6 +494,12 @@ impl State {
         self == State::Closed
     }
 
+    /// Returns whether or not this channel state is part of an
+    /// ongoing upgrade, ie. `Flushing` or `FlushComplete`.
+    pub fn is_upgrading(self) -> bool {
+        matches!(self, State::Flushing | State::FlushComplete)
+    }
+
     /// Returns whether or not the channel with this state
     /// has progressed less or the same than the argument.
     ///
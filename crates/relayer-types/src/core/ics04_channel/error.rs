@@ -343,7 +343,29 @@ define_error! {
 
         AbciConversionFailed
             { abci_event: String }
-            | e | { format_args!("Failed to convert abci event to IbcEvent: {}", e.abci_event)}
+            | e | { format_args!("Failed to convert abci event to IbcEvent: {}", e.abci_event)},
+
+        MissingUpgradeFields
+            | _ | { "missing upgrade fields" },
+
+        MissingUpgradeTimeout
+            | _ | { "missing upgrade timeout" },
+
+        MissingUpgrade
+            | _ | { "missing channel upgrade" },
+
+        MissingErrorReceipt
+            | _ | { "missing upgrade error receipt" },
+
+        InvalidUpgradeTimeout
+            | _ | { "upgrade timeout height and timestamp cannot both be zero" },
+
+        InvalidUpgradeTimeoutTimestamp
+            [ crate::timestamp::ParseTimestampError ]
+            | _ | { "invalid upgrade timeout timestamp value" },
+
+        EmptyConnectionHops
+            | _ | { "connection hops cannot be empty" },
     }
 }
 
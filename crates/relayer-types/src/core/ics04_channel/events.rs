@@ -5,8 +5,10 @@ use std::fmt::{Display, Error as FmtError, Formatter};
 use std::str;
 use tendermint::abci;
 
+use crate::core::ics04_channel::channel::Ordering;
 use crate::core::ics04_channel::error::Error;
-use crate::core::ics04_channel::packet::Packet;
+use crate::core::ics04_channel::packet::{Packet, Sequence};
+use crate::core::ics04_channel::version::Version;
 use crate::core::ics24_host::identifier::{ChannelId, ConnectionId, PortId};
 use crate::events::{Error as EventError, IbcEvent, IbcEventType};
 
@@ -19,6 +21,12 @@ pub const PORT_ID_ATTRIBUTE_KEY: &str = "port_id";
 pub const COUNTERPARTY_CHANNEL_ID_ATTRIBUTE_KEY: &str = "counterparty_channel_id";
 pub const COUNTERPARTY_PORT_ID_ATTRIBUTE_KEY: &str = "counterparty_port_id";
 
+/// Channel upgrade event attribute keys
+pub const UPGRADE_SEQUENCE_ATTRIBUTE_KEY: &str = "upgrade_sequence";
+pub const UPGRADE_CONNECTION_HOPS_ATTRIBUTE_KEY: &str = "upgrade_connection_hops";
+pub const UPGRADE_VERSION_ATTRIBUTE_KEY: &str = "upgrade_version";
+pub const UPGRADE_ORDERING_ATTRIBUTE_KEY: &str = "upgrade_ordering";
+
 /// Packet event attribute keys
 pub const PKT_SEQ_ATTRIBUTE_KEY: &str = "packet_sequence";
 pub const PKT_DATA_ATTRIBUTE_KEY: &str = "packet_data";
@@ -514,6 +522,192 @@ impl_from_ibc_to_abci_event!(
     CloseConfirm
 );
 
+/// Attributes of the events emitted during the channel upgrade handshake.
+///
+/// Unlike the open and close handshake events, these do not carry the connection
+/// identifier. The upgrade fields are only emitted by the events which propose or
+/// accept an upgrade, and are left to their default values otherwise.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct UpgradeAttributes {
+    pub port_id: PortId,
+    pub channel_id: ChannelId,
+    pub counterparty_port_id: PortId,
+    pub counterparty_channel_id: Option<ChannelId>,
+    pub upgrade_sequence: Sequence,
+    pub upgrade_connection_hops: Vec<ConnectionId>,
+    pub upgrade_version: Version,
+    pub upgrade_ordering: Ordering,
+}
+
+impl UpgradeAttributes {
+    pub fn port_id(&self) -> &PortId {
+        &self.port_id
+    }
+
+    pub fn channel_id(&self) -> &ChannelId {
+        &self.channel_id
+    }
+
+    pub fn counterparty_port_id(&self) -> &PortId {
+        &self.counterparty_port_id
+    }
+
+    pub fn counterparty_channel_id(&self) -> Option<&ChannelId> {
+        self.counterparty_channel_id.as_ref()
+    }
+
+    pub fn upgrade_sequence(&self) -> Sequence {
+        self.upgrade_sequence
+    }
+}
+
+impl Display for UpgradeAttributes {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match &self.counterparty_channel_id {
+            Some(counterparty_channel_id) => write!(f, "UpgradeAttributes {{ port_id: {}, channel_id: {}, counterparty_port_id: {}, counterparty_channel_id: {}, upgrade_sequence: {}, upgrade_connection_hops: {}, upgrade_version: {}, upgrade_ordering: {} }}", self.port_id, self.channel_id, self.counterparty_port_id, counterparty_channel_id, self.upgrade_sequence, PrettySlice(&self.upgrade_connection_hops), self.upgrade_version, self.upgrade_ordering),
+            None => write!(f, "UpgradeAttributes {{ port_id: {}, channel_id: {}, counterparty_port_id: {}, counterparty_channel_id: None, upgrade_sequence: {}, upgrade_connection_hops: {}, upgrade_version: {}, upgrade_ordering: {} }}", self.port_id, self.channel_id, self.counterparty_port_id, self.upgrade_sequence, PrettySlice(&self.upgrade_connection_hops), self.upgrade_version, self.upgrade_ordering),
+        }
+    }
+}
+
+/// Convert upgrade attributes to Tendermint ABCI tags
+impl From<UpgradeAttributes> for Vec<abci::EventAttribute> {
+    fn from(a: UpgradeAttributes) -> Self {
+        let mut attributes = vec![];
+        let port_id = (PORT_ID_ATTRIBUTE_KEY, a.port_id.as_str()).into();
+        attributes.push(port_id);
+        let channel_id = (CHANNEL_ID_ATTRIBUTE_KEY, a.channel_id.as_str()).into();
+        attributes.push(channel_id);
+        let counterparty_port_id = (
+            COUNTERPARTY_PORT_ID_ATTRIBUTE_KEY,
+            a.counterparty_port_id.as_str(),
+        )
+            .into();
+        attributes.push(counterparty_port_id);
+        if let Some(channel_id) = a.counterparty_channel_id {
+            let channel_id = (COUNTERPARTY_CHANNEL_ID_ATTRIBUTE_KEY, channel_id.as_str()).into();
+            attributes.push(channel_id);
+        }
+        let upgrade_sequence = (
+            UPGRADE_SEQUENCE_ATTRIBUTE_KEY,
+            a.upgrade_sequence.to_string(),
+        )
+            .into();
+        attributes.push(upgrade_sequence);
+        if !a.upgrade_connection_hops.is_empty() {
+            let connection_hops = a
+                .upgrade_connection_hops
+                .iter()
+                .map(|hop| hop.as_str())
+                .collect::<Vec<_>>()
+                .join(",");
+            let connection_hops = (UPGRADE_CONNECTION_HOPS_ATTRIBUTE_KEY, connection_hops).into();
+            attributes.push(connection_hops);
+            let version = (UPGRADE_VERSION_ATTRIBUTE_KEY, a.upgrade_version.to_string()).into();
+            attributes.push(version);
+            let ordering = (UPGRADE_ORDERING_ATTRIBUTE_KEY, a.upgrade_ordering.as_str()).into();
+            attributes.push(ordering);
+        }
+        attributes
+    }
+}
+
+/// A channel upgrade was proposed, the channel end remains `OPEN`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct UpgradeInit(pub UpgradeAttributes);
+
+/// The counterparty proposal was accepted, the channel end moved to `FLUSHING`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct UpgradeTry(pub UpgradeAttributes);
+
+/// The counterparty acceptance was acknowledged, the channel end moved to
+/// `FLUSHING` or `FLUSHCOMPLETE`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct UpgradeAck(pub UpgradeAttributes);
+
+/// Both channel ends agreed on the upgrade, the channel end moved to
+/// `FLUSHCOMPLETE`, or directly to `OPEN` if the counterparty already flushed.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct UpgradeConfirm(pub UpgradeAttributes);
+
+/// The upgrade completed, the channel end is `OPEN` with the upgraded parameters.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct UpgradeOpen(pub UpgradeAttributes);
+
+/// The upgrade timed out on the counterparty and was aborted.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct UpgradeTimeout(pub UpgradeAttributes);
+
+/// The upgrade was cancelled following an error receipt written by the counterparty.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct UpgradeCancel(pub UpgradeAttributes);
+
+/// The upgrade failed and an error receipt was written for the counterparty to cancel it.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct UpgradeError(pub UpgradeAttributes);
+
+/// The last in-flight packet of an upgrading channel end was acknowledged or timed out,
+/// the channel end moved from `FLUSHING` to `FLUSHCOMPLETE`.
+///
+/// This event carries no upgrade sequence nor upgrade fields.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct FlushComplete(pub UpgradeAttributes);
+
+macro_rules! impl_upgrade_event {
+    ($($event:ident => $variant:ident),+ $(,)?) => {
+        $(impl $event {
+            pub fn attributes(&self) -> &UpgradeAttributes {
+                &self.0
+            }
+        }
+
+        impl Display for $event {
+            fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+                write!(f, "{} {{ {} }}", stringify!($event), self.0)
+            }
+        }
+
+        impl From<UpgradeAttributes> for $event {
+            fn from(attrs: UpgradeAttributes) -> Self {
+                Self(attrs)
+            }
+        }
+
+        impl From<$event> for IbcEvent {
+            fn from(v: $event) -> Self {
+                IbcEvent::$variant(v)
+            }
+        }
+
+        impl EventType for $event {
+            fn event_type() -> IbcEventType {
+                IbcEventType::$variant
+            }
+        }
+
+        impl From<$event> for abci::Event {
+            fn from(v: $event) -> Self {
+                Self {
+                    kind: <$event>::event_type().as_str().to_owned(),
+                    attributes: v.0.into(),
+                }
+            }
+        })+
+    };
+}
+
+impl_upgrade_event!(
+    UpgradeInit => UpgradeInitChannel,
+    UpgradeTry => UpgradeTryChannel,
+    UpgradeAck => UpgradeAckChannel,
+    UpgradeConfirm => UpgradeConfirmChannel,
+    UpgradeOpen => UpgradeOpenChannel,
+    UpgradeTimeout => UpgradeTimeoutChannel,
+    UpgradeCancel => UpgradeCancelChannel,
+    UpgradeError => UpgradeErrorChannel,
+    FlushComplete => FlushCompleteChannel,
+);
+
 #[derive(Clone, Debug, PartialEq, Eq, Serialize)]
 pub struct SendPacket {
     pub packet: Packet,
@@ -0,0 +1,168 @@
+use ibc_proto::ibc::core::client::v1::Height as RawHeight;
+use ibc_proto::protobuf::Protobuf;
+
+use crate::core::ics04_channel::channel::{ChannelEnd, RawUpgradableChannel};
+use crate::core::ics04_channel::error::Error;
+use crate::core::ics24_host::identifier::{ChannelId, PortId};
+use crate::proofs::Proofs;
+use crate::signer::Signer;
+use crate::tx_msg::Msg;
+
+pub const TYPE_URL: &str = "/ibc.core.channel.v1.MsgChannelUpgradeTimeout";
+
+/// Encoding of `ibc.core.channel.v1.MsgChannelUpgradeTimeout`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct RawMsgChannelUpgradeTimeout {
+    #[prost(string, tag = "1")]
+    pub port_id: String,
+    #[prost(string, tag = "2")]
+    pub channel_id: String,
+    #[prost(message, optional, tag = "3")]
+    pub counterparty_channel: Option<RawUpgradableChannel>,
+    #[prost(bytes = "vec", tag = "4")]
+    pub proof_channel: Vec<u8>,
+    #[prost(message, optional, tag = "5")]
+    pub proof_height: Option<RawHeight>,
+    #[prost(string, tag = "6")]
+    pub signer: String,
+}
+
+///
+/// Message definition to abort a channel upgrade whose timeout has elapsed
+/// on the counterparty chain (`ChanUpgradeTimeout` datagram).
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MsgChannelUpgradeTimeout {
+    pub port_id: PortId,
+    pub channel_id: ChannelId,
+    pub counterparty_channel: ChannelEnd,
+    pub proofs: Proofs,
+    pub signer: Signer,
+}
+
+impl Msg for MsgChannelUpgradeTimeout {
+    type ValidationError = Error;
+    type Raw = RawMsgChannelUpgradeTimeout;
+
+    fn route(&self) -> String {
+        crate::keys::ROUTER_KEY.to_string()
+    }
+
+    fn type_url(&self) -> String {
+        TYPE_URL.to_string()
+    }
+}
+
+impl Protobuf<RawMsgChannelUpgradeTimeout> for MsgChannelUpgradeTimeout {}
+
+impl TryFrom<RawMsgChannelUpgradeTimeout> for MsgChannelUpgradeTimeout {
+    type Error = Error;
+
+    fn try_from(raw_msg: RawMsgChannelUpgradeTimeout) -> Result<Self, Self::Error> {
+        let proofs = Proofs::new(
+            raw_msg
+                .proof_channel
+                .try_into()
+                .map_err(Error::invalid_proof)?,
+            None,
+            None,
+            None,
+            raw_msg
+                .proof_height
+                .and_then(|raw_height| raw_height.try_into().ok())
+                .ok_or_else(Error::missing_height)?,
+        )
+        .map_err(Error::invalid_proof)?;
+
+        Ok(MsgChannelUpgradeTimeout {
+            port_id: raw_msg.port_id.parse().map_err(Error::identifier)?,
+            channel_id: raw_msg.channel_id.parse().map_err(Error::identifier)?,
+            counterparty_channel: raw_msg
+                .counterparty_channel
+                .ok_or_else(Error::missing_channel)?
+                .try_into()?,
+            proofs,
+            signer: raw_msg.signer.parse().map_err(Error::signer)?,
+        })
+    }
+}
+
+impl From<MsgChannelUpgradeTimeout> for RawMsgChannelUpgradeTimeout {
+    fn from(domain_msg: MsgChannelUpgradeTimeout) -> Self {
+        RawMsgChannelUpgradeTimeout {
+            port_id: domain_msg.port_id.to_string(),
+            channel_id: domain_msg.channel_id.to_string(),
+            counterparty_channel: Some(domain_msg.counterparty_channel.into()),
+            proof_channel: domain_msg.proofs.object_proof().clone().into(),
+            proof_height: Some(domain_msg.proofs.height().into()),
+            signer: domain_msg.signer.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod test_util {
+    use ibc_proto::ibc::core::client::v1::Height;
+
+    use super::RawMsgChannelUpgradeTimeout;
+    use crate::core::ics04_channel::channel::test_util::get_dummy_raw_counterparty;
+    use crate::core::ics04_channel::channel::RawUpgradableChannel;
+    use crate::core::ics24_host::identifier::{ChannelId, ConnectionId, PortId};
+    use crate::test_utils::{get_dummy_bech32_account, get_dummy_proof};
+
+    /// Returns a dummy `RawMsgChannelUpgradeTimeout`, for testing only!
+    pub fn get_dummy_raw_msg_chan_upgrade_timeout(
+        proof_height: u64,
+    ) -> RawMsgChannelUpgradeTimeout {
+        RawMsgChannelUpgradeTimeout {
+            port_id: PortId::default().to_string(),
+            channel_id: ChannelId::default().to_string(),
+            counterparty_channel: Some(RawUpgradableChannel {
+                state: 3,
+                ordering: 1,
+                counterparty: Some(get_dummy_raw_counterparty()),
+                connection_hops: vec![ConnectionId::default().to_string()],
+                version: "ics20-1".to_string(),
+                upgrade_sequence: 1,
+            }),
+            proof_channel: get_dummy_proof(),
+            proof_height: Some(Height {
+                revision_number: 0,
+                revision_height: proof_height,
+            }),
+            signer: get_dummy_bech32_account(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use crate::core::ics04_channel::msgs::chan_upgrade_timeout::test_util::get_dummy_raw_msg_chan_upgrade_timeout;
+    use crate::core::ics04_channel::msgs::chan_upgrade_timeout::{
+        MsgChannelUpgradeTimeout, RawMsgChannelUpgradeTimeout,
+    };
+
+    #[test]
+    fn parse_channel_upgrade_timeout_msg() {
+        let default_raw_msg = get_dummy_raw_msg_chan_upgrade_timeout(10);
+        assert!(MsgChannelUpgradeTimeout::try_from(default_raw_msg.clone()).is_ok());
+
+        let missing_channel = RawMsgChannelUpgradeTimeout {
+            counterparty_channel: None,
+            ..default_raw_msg
+        };
+        assert!(MsgChannelUpgradeTimeout::try_from(missing_channel).is_err());
+    }
+
+    #[test]
+    fn to_and_from() {
+        let raw = get_dummy_raw_msg_chan_upgrade_timeout(15);
+        let msg = MsgChannelUpgradeTimeout::try_from(raw.clone()).unwrap();
+        let raw_back = RawMsgChannelUpgradeTimeout::from(msg.clone());
+        let msg_back = MsgChannelUpgradeTimeout::try_from(raw_back.clone()).unwrap();
+        assert_eq!(raw, raw_back);
+        assert_eq!(msg, msg_back);
+    }
+}
@@ -0,0 +1,349 @@
+//! Types used by the ICS-04 channel upgradability handshake, which allows
+//! an open channel to renegotiate its version, ordering and connection hops.
+
+use std::str::FromStr;
+
+use ibc_proto::ibc::core::client::v1::Height as RawHeight;
+use ibc_proto::protobuf::Protobuf;
+use serde::{Deserialize, Serialize};
+
+use crate::core::ics02_client::height::Height;
+use crate::core::ics04_channel::channel::Ordering;
+use crate::core::ics04_channel::error::Error;
+use crate::core::ics04_channel::packet::Sequence;
+use crate::core::ics04_channel::timeout::TimeoutHeight;
+use crate::core::ics04_channel::version::Version;
+use crate::core::ics24_host::identifier::ConnectionId;
+use crate::timestamp::Timestamp;
+
+/// Encoding of an upgrade timeout, `ibc.core.channel.v1.Timeout`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct RawTimeout {
+    #[prost(message, optional, tag = "1")]
+    pub height: Option<RawHeight>,
+    #[prost(uint64, tag = "2")]
+    pub timestamp: u64,
+}
+
+/// Encoding of the upgradable fields of a channel, `ibc.core.channel.v1.UpgradeFields`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct RawUpgradeFields {
+    #[prost(int32, tag = "1")]
+    pub ordering: i32,
+    #[prost(string, repeated, tag = "2")]
+    pub connection_hops: Vec<String>,
+    #[prost(string, tag = "3")]
+    pub version: String,
+}
+
+/// Encoding of a channel upgrade, `ibc.core.channel.v1.Upgrade`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct RawUpgrade {
+    #[prost(message, optional, tag = "1")]
+    pub fields: Option<RawUpgradeFields>,
+    #[prost(message, optional, tag = "2")]
+    pub timeout: Option<RawTimeout>,
+    #[prost(uint64, tag = "3")]
+    pub next_sequence_send: u64,
+}
+
+/// Encoding of an upgrade error receipt, `ibc.core.channel.v1.ErrorReceipt`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct RawErrorReceipt {
+    #[prost(uint64, tag = "1")]
+    pub sequence: u64,
+    #[prost(string, tag = "2")]
+    pub message: String,
+}
+
+/// The height and/or timestamp on the counterparty chain after which
+/// an upgrade attempt is considered to have timed out.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UpgradeTimeout {
+    pub height: TimeoutHeight,
+    pub timestamp: Timestamp,
+}
+
+impl UpgradeTimeout {
+    pub fn new(height: TimeoutHeight, timestamp: Timestamp) -> Result<Self, Error> {
+        if height == TimeoutHeight::Never && timestamp == Timestamp::none() {
+            return Err(Error::invalid_upgrade_timeout());
+        }
+
+        Ok(Self { height, timestamp })
+    }
+
+    /// Whether the timeout has elapsed on the counterparty chain, given its
+    /// latest height and timestamp.
+    pub fn has_elapsed(&self, height: Height, timestamp: &Timestamp) -> bool {
+        let height_elapsed = match self.height {
+            TimeoutHeight::At(timeout_height) => height >= timeout_height,
+            TimeoutHeight::Never => false,
+        };
+
+        let timestamp_elapsed = self.timestamp != Timestamp::none()
+            && *timestamp != Timestamp::none()
+            && !self.timestamp.after(timestamp);
+
+        height_elapsed || timestamp_elapsed
+    }
+}
+
+impl Protobuf<RawTimeout> for UpgradeTimeout {}
+
+impl TryFrom<RawTimeout> for UpgradeTimeout {
+    type Error = Error;
+
+    fn try_from(value: RawTimeout) -> Result<Self, Self::Error> {
+        let height =
+            TimeoutHeight::try_from(value.height).map_err(|_| Error::invalid_timeout_height())?;
+
+        let timestamp = Timestamp::from_nanoseconds(value.timestamp)
+            .map_err(Error::invalid_upgrade_timeout_timestamp)?;
+
+        Self::new(height, timestamp)
+    }
+}
+
+impl From<UpgradeTimeout> for RawTimeout {
+    fn from(value: UpgradeTimeout) -> Self {
+        RawTimeout {
+            height: value.height.into(),
+            timestamp: value.timestamp.nanoseconds(),
+        }
+    }
+}
+
+/// The channel parameters which can be changed by an upgrade.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UpgradeFields {
+    pub ordering: Ordering,
+    pub connection_hops: Vec<ConnectionId>,
+    pub version: Version,
+}
+
+impl UpgradeFields {
+    pub fn new(ordering: Ordering, connection_hops: Vec<ConnectionId>, version: Version) -> Self {
+        Self {
+            ordering,
+            connection_hops,
+            version,
+        }
+    }
+}
+
+impl Protobuf<RawUpgradeFields> for UpgradeFields {}
+
+impl TryFrom<RawUpgradeFields> for UpgradeFields {
+    type Error = Error;
+
+    fn try_from(value: RawUpgradeFields) -> Result<Self, Self::Error> {
+        let ordering = Ordering::from_i32(value.ordering)?;
+
+        let connection_hops = value
+            .connection_hops
+            .iter()
+            .map(|hop| ConnectionId::from_str(hop))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(Error::identifier)?;
+
+        if connection_hops.is_empty() {
+            return Err(Error::empty_connection_hops());
+        }
+
+        Ok(Self::new(ordering, connection_hops, value.version.into()))
+    }
+}
+
+impl From<UpgradeFields> for RawUpgradeFields {
+    fn from(value: UpgradeFields) -> Self {
+        RawUpgradeFields {
+            ordering: value.ordering as i32,
+            connection_hops: value
+                .connection_hops
+                .iter()
+                .map(|hop| hop.to_string())
+                .collect(),
+            version: value.version.to_string(),
+        }
+    }
+}
+
+/// An upgrade proposed for a channel end, as stored under
+/// the `channelUpgrades/upgrades` path of the chain.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Upgrade {
+    pub fields: UpgradeFields,
+    pub timeout: UpgradeTimeout,
+    pub next_sequence_send: Sequence,
+}
+
+impl Protobuf<RawUpgrade> for Upgrade {}
+
+impl TryFrom<RawUpgrade> for Upgrade {
+    type Error = Error;
+
+    fn try_from(value: RawUpgrade) -> Result<Self, Self::Error> {
+        let fields = value
+            .fields
+            .ok_or_else(Error::missing_upgrade_fields)?
+            .try_into()?;
+
+        let timeout = value
+            .timeout
+            .ok_or_else(Error::missing_upgrade_timeout)?
+            .try_into()?;
+
+        Ok(Self {
+            fields,
+            timeout,
+            next_sequence_send: value.next_sequence_send.into(),
+        })
+    }
+}
+
+impl From<Upgrade> for RawUpgrade {
+    fn from(value: Upgrade) -> Self {
+        RawUpgrade {
+            fields: Some(value.fields.into()),
+            timeout: Some(value.timeout.into()),
+            next_sequence_send: value.next_sequence_send.into(),
+        }
+    }
+}
+
+/// Receipt written by a chain when an upgrade attempt fails, which allows
+/// the counterparty to cancel the upgrade at the same sequence.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ErrorReceipt {
+    pub sequence: Sequence,
+    pub message: String,
+}
+
+impl Protobuf<RawErrorReceipt> for ErrorReceipt {}
+
+impl TryFrom<RawErrorReceipt> for ErrorReceipt {
+    type Error = Error;
+
+    fn try_from(value: RawErrorReceipt) -> Result<Self, Self::Error> {
+        Ok(Self {
+            sequence: value.sequence.into(),
+            message: value.message,
+        })
+    }
+}
+
+impl From<ErrorReceipt> for RawErrorReceipt {
+    fn from(value: ErrorReceipt) -> Self {
+        RawErrorReceipt {
+            sequence: value.sequence.into(),
+            message: value.message,
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod test_util {
+    use ibc_proto::ibc::core::client::v1::Height as RawHeight;
+
+    use super::{RawErrorReceipt, RawTimeout, RawUpgrade, RawUpgradeFields};
+    use crate::core::ics24_host::identifier::ConnectionId;
+
+    /// Returns dummy `RawUpgradeFields`, for testing only!
+    pub fn get_dummy_raw_upgrade_fields() -> RawUpgradeFields {
+        RawUpgradeFields {
+            ordering: 1,
+            connection_hops: vec![ConnectionId::default().to_string()],
+            version: "{\"fee_version\":\"ics29-1\",\"app_version\":\"ics20-1\"}".to_string(),
+        }
+    }
+
+    /// Returns a dummy `RawUpgrade`, for testing only!
+    pub fn get_dummy_raw_upgrade() -> RawUpgrade {
+        RawUpgrade {
+            fields: Some(get_dummy_raw_upgrade_fields()),
+            timeout: Some(RawTimeout {
+                height: Some(RawHeight {
+                    revision_number: 0,
+                    revision_height: 100,
+                }),
+                timestamp: 0,
+            }),
+            next_sequence_send: 1,
+        }
+    }
+
+    /// Returns a dummy `RawErrorReceipt`, for testing only!
+    pub fn get_dummy_raw_error_receipt() -> RawErrorReceipt {
+        RawErrorReceipt {
+            sequence: 1,
+            message: "upgrade handshake failed".to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use super::test_util::get_dummy_raw_upgrade;
+    use super::{RawTimeout, RawUpgrade, RawUpgradeFields, Upgrade, UpgradeTimeout};
+    use crate::core::ics02_client::height::Height;
+    use crate::core::ics04_channel::timeout::TimeoutHeight;
+    use crate::timestamp::Timestamp;
+
+    #[test]
+    fn upgrade_to_and_from() {
+        let raw = get_dummy_raw_upgrade();
+        let upgrade = Upgrade::try_from(raw.clone()).unwrap();
+        let raw_back = RawUpgrade::from(upgrade.clone());
+        let upgrade_back = Upgrade::try_from(raw_back.clone()).unwrap();
+        assert_eq!(raw, raw_back);
+        assert_eq!(upgrade, upgrade_back);
+    }
+
+    #[test]
+    fn upgrade_rejects_invalid_params() {
+        let raw = get_dummy_raw_upgrade();
+
+        let no_timeout = RawUpgrade {
+            timeout: Some(RawTimeout {
+                height: None,
+                timestamp: 0,
+            }),
+            ..raw.clone()
+        };
+        assert!(Upgrade::try_from(no_timeout).is_err());
+
+        let no_fields = RawUpgrade {
+            fields: None,
+            ..raw.clone()
+        };
+        assert!(Upgrade::try_from(no_fields).is_err());
+
+        let no_hops = RawUpgrade {
+            fields: Some(RawUpgradeFields {
+                connection_hops: vec![],
+                ..raw.fields.clone().unwrap()
+            }),
+            ..raw
+        };
+        assert!(Upgrade::try_from(no_hops).is_err());
+    }
+
+    #[test]
+    fn upgrade_timeout_elapsed() {
+        let height = |h| Height::new(0, h).unwrap();
+        let timestamp = |t| Timestamp::from_nanoseconds(t).unwrap();
+
+        let at_height =
+            UpgradeTimeout::new(TimeoutHeight::At(height(10)), Timestamp::none()).unwrap();
+        assert!(!at_height.has_elapsed(height(9), &timestamp(1000)));
+        assert!(at_height.has_elapsed(height(10), &timestamp(1000)));
+
+        let at_timestamp = UpgradeTimeout::new(TimeoutHeight::Never, timestamp(1000)).unwrap();
+        assert!(!at_timestamp.has_elapsed(height(100), &timestamp(999)));
+        assert!(at_timestamp.has_elapsed(height(1), &timestamp(1000)));
+        assert!(!at_timestamp.has_elapsed(height(100), &Timestamp::none()));
+    }
+}
@@ -170,12 +170,30 @@ impl ClientId {
     pub fn prefix(client_type: ClientType) -> &'static str {
         match client_type {
             ClientType::Tendermint => ClientType::Tendermint.as_str(),
+            ClientType::Solomachine => ClientType::Solomachine.as_str(),
+            ClientType::Wasm => ClientType::Wasm.as_str(),
+            ClientType::Localhost => ClientType::Localhost.as_str(),
 
             #[cfg(any(test, feature = "mocks"))]
             ClientType::Mock => ClientType::Mock.as_str(),
         }
     }
 
+    /// The identifier of the localhost client, of which each chain has a single instance.
+    ///
+    /// ```
+    /// # use ibc_relayer_types::core::ics24_host::identifier::ClientId;
+    /// assert_eq!(&ClientId::localhost(), "09-localhost");
+    /// ```
+    pub fn localhost() -> Self {
+        Self(ClientType::Localhost.as_str().to_string())
+    }
+
+    /// Whether this is the identifier of the localhost client.
+    pub fn is_localhost(&self) -> bool {
+        self.as_str() == ClientType::Localhost.as_str()
+    }
+
     /// Get this identifier as a borrowed byte slice
     pub fn as_bytes(&self) -> &[u8] {
         self.0.as_bytes()
@@ -241,6 +259,22 @@ impl ConnectionId {
         "connection"
     }
 
+    /// The identifier of the localhost connection, which is open on every chain
+    /// supporting the localhost client and is bound to that client.
+    ///
+    /// ```
+    /// # use ibc_relayer_types::core::ics24_host::identifier::ConnectionId;
+    /// assert_eq!(&ConnectionId::localhost(), "connection-localhost");
+    /// ```
+    pub fn localhost() -> Self {
+        Self(format!("{}-localhost", Self::prefix()))
+    }
+
+    /// Whether this is the identifier of the localhost connection.
+    pub fn is_localhost(&self) -> bool {
+        self == &Self::localhost()
+    }
+
     /// Get this identifier as a borrowed `&str`
     pub fn as_str(&self) -> &str {
         &self.0
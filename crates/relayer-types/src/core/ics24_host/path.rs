@@ -42,6 +42,8 @@ pub enum Path {
     Acks(AcksPath),
     Receipts(ReceiptsPath),
     Upgrade(ClientUpgradePath),
+    ChannelUpgrades(ChannelUpgradesPath),
+    ChannelUpgradeErrors(ChannelUpgradeErrorsPath),
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Display)]
@@ -112,6 +114,14 @@ pub struct ReceiptsPath {
     pub sequence: Sequence,
 }
 
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Display)]
+#[display(fmt = "channelUpgrades/upgrades/ports/{_0}/channels/{_1}")]
+pub struct ChannelUpgradesPath(pub PortId, pub ChannelId);
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Display)]
+#[display(fmt = "channelUpgrades/upgradeError/ports/{_0}/channels/{_1}")]
+pub struct ChannelUpgradeErrorsPath(pub PortId, pub ChannelId);
+
 /// Paths that are specific for client upgrades.
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Display)]
 pub enum ClientUpgradePath {
@@ -166,6 +176,7 @@ impl FromStr for Path {
             .or_else(|| parse_acks(&components))
             .or_else(|| parse_receipts(&components))
             .or_else(|| parse_upgrades(&components))
+            .or_else(|| parse_channel_upgrades(&components))
             .ok_or_else(|| PathError::parse_failure(s.to_string()))
     }
 }
@@ -576,6 +587,37 @@ fn parse_upgrades(components: &[&str]) -> Option<Path> {
     }
 }
 
+fn parse_channel_upgrades(components: &[&str]) -> Option<Path> {
+    if components.len() != 6 {
+        return None;
+    }
+
+    if components[0] != "channelUpgrades" {
+        return None;
+    }
+
+    let port = parse_ports(&components[2..=3]);
+    let channel = parse_channels(&components[4..=5]);
+
+    let port_id = if let Some(Path::Ports(PortsPath(port_id))) = port {
+        port_id
+    } else {
+        return None;
+    };
+
+    let channel_id = if let Some(SubPath::Channels(channel_id)) = channel {
+        channel_id
+    } else {
+        return None;
+    };
+
+    match components[1] {
+        "upgrades" => Some(ChannelUpgradesPath(port_id, channel_id).into()),
+        "upgradeError" => Some(ChannelUpgradeErrorsPath(port_id, channel_id).into()),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -778,6 +820,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn channel_upgrade_paths_parse() {
+        let path = Path::from_str("channelUpgrades/upgrades/ports/defaultPort/channels/channel-0");
+
+        assert_eq!(
+            path.unwrap(),
+            Path::ChannelUpgrades(ChannelUpgradesPath(PortId::default(), ChannelId::default())),
+        );
+
+        let path =
+            Path::from_str("channelUpgrades/upgradeError/ports/defaultPort/channels/channel-0");
+
+        assert_eq!(
+            path.unwrap(),
+            Path::ChannelUpgradeErrors(ChannelUpgradeErrorsPath(
+                PortId::default(),
+                ChannelId::default()
+            )),
+        );
+
+        let path = Path::from_str("channelUpgrades/unknown/ports/defaultPort/channels/channel-0");
+
+        assert!(path.is_err());
+    }
+
     #[test]
     fn test_parse_seqs_fn() {
         let path = "nextSequenceSend/ports/defaultPort/channels/channel-0";
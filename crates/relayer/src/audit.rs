@@ -0,0 +1,504 @@
+//! Append-only audit log of the transactions submitted by the relayer.
+//!
+//! Every transaction broadcast to a chain is recorded as one JSON line as soon as it is
+//! broadcast, with the messages and packets it carries, the fees it pays, and the tracking id
+//! of the operation which submitted it. Once the transaction is seen committed, it is recorded
+//! again with the gas it used and the result of its execution. The log file is rotated
+//! when it grows above a given size, or after a given period.
+//!
+//! The entries of the transactions whose commit is not awaited by the operation which
+//! submitted them are kept for a while, so that they can be recorded again once their
+//! commit is observed when querying them by hash.
+
+pub mod error;
+
+pub use error::Error;
+
+use core::time::Duration;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use ibc_proto::cosmos::tx::v1beta1::Fee;
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::core::channel::v1::{
+    MsgAcknowledgement, MsgRecvPacket, MsgTimeout, MsgTimeoutOnClose, Packet as RawPacket,
+};
+use moka::sync::Cache;
+use once_cell::sync::{Lazy, OnceCell};
+use prost::Message;
+use serde::{Deserialize, Serialize};
+use tendermint_rpc::endpoint::broadcast::tx_sync::Response;
+use tendermint_rpc::endpoint::tx::Response as TxResponse;
+use tracing::{info, warn};
+
+use ibc_relayer_types::core::ics04_channel::msgs::{
+    acknowledgement, recv_packet, timeout, timeout_on_close,
+};
+use ibc_relayer_types::core::ics04_channel::packet::Sequence;
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+
+use crate::chain::tracking::TrackingId;
+use crate::config::AuditConfig;
+
+pub const AUDIT_LOG_DEFAULT_PATH: &str = ".hermes/audit/txs.jsonl";
+
+/// How many entries of transactions awaiting their commit are kept at most
+const MAX_PENDING_ENTRIES: u64 = 10_000;
+
+/// How long the entry of a transaction awaiting its commit is kept at most
+const PENDING_ENTRY_TTL: Duration = Duration::from_secs(60 * 60);
+
+static AUDIT_LOG: OnceCell<Mutex<AuditLog>> = OnceCell::new();
+
+/// The entries of the transactions awaiting their commit, by chain and transaction hash
+static PENDING: Lazy<Cache<(ChainId, String), TxAuditEntry>> = Lazy::new(|| {
+    Cache::builder()
+        .max_capacity(MAX_PENDING_ENTRIES)
+        .time_to_live(PENDING_ENTRY_TTL)
+        .build()
+});
+
+/// The progress of a transaction recorded in the audit log
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TxAuditStatus {
+    /// The transaction was broadcast, with the result of its `CheckTx`
+    Broadcast,
+
+    /// The transaction was committed, with the result of its `DeliverTx`
+    Committed,
+}
+
+/// A transaction submitted by the relayer
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxAuditEntry {
+    /// When the transaction was recorded with its current status
+    #[serde(with = "humantime_serde")]
+    pub timestamp: SystemTime,
+    pub status: TxAuditStatus,
+    pub chain_id: ChainId,
+    pub tracking_id: String,
+    pub tx_hash: String,
+    pub signer: String,
+
+    /// The type URLs of the messages of the transaction
+    pub messages: Vec<String>,
+
+    /// The packets received, acknowledged or timed out by the transaction
+    pub packets: Vec<AuditedPacket>,
+
+    pub gas_wanted: u64,
+
+    /// The gas used by the transaction, if it was committed
+    pub gas_used: Option<u64>,
+
+    pub fee: Vec<AuditedCoin>,
+
+    /// The height at which the transaction was committed, if it was
+    pub height: Option<u64>,
+
+    /// The result code of `CheckTx`, or of `DeliverTx` if the transaction was committed
+    pub code: u32,
+
+    /// The log of the chain, if the transaction failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub log: Option<String>,
+}
+
+/// A packet, identified by the port and channel it was sent from, and its sequence
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditedPacket {
+    pub port_id: PortId,
+    pub channel_id: ChannelId,
+    pub sequence: Sequence,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditedCoin {
+    pub amount: String,
+    pub denom: String,
+}
+
+impl TxAuditEntry {
+    /// The entry of a transaction which was just broadcast, with the result of its `CheckTx`
+    pub fn new(
+        chain_id: &ChainId,
+        tracking_id: TrackingId,
+        signer: String,
+        messages: &[Any],
+        fee: &Fee,
+        response: &Response,
+    ) -> Self {
+        Self {
+            timestamp: SystemTime::now(),
+            status: TxAuditStatus::Broadcast,
+            chain_id: chain_id.clone(),
+            tracking_id: tracking_id.to_string(),
+            tx_hash: response.hash.to_string(),
+            signer,
+            messages: messages.iter().map(|m| m.type_url.clone()).collect(),
+            packets: messages.iter().filter_map(packet_of).collect(),
+            gas_wanted: fee.gas_limit,
+            gas_used: None,
+            fee: fee
+                .amount
+                .iter()
+                .map(|coin| AuditedCoin {
+                    amount: coin.amount.clone(),
+                    denom: coin.denom.clone(),
+                })
+                .collect(),
+            height: None,
+            code: response.code.value(),
+            log: response.code.is_err().then(|| response.log.clone()),
+        }
+    }
+
+    /// Update the entry with the result of the transaction once it is committed
+    pub fn set_committed(&mut self, response: &TxResponse) {
+        let result = &response.tx_result;
+
+        self.timestamp = SystemTime::now();
+        self.status = TxAuditStatus::Committed;
+        self.gas_used = u64::try_from(result.gas_used).ok();
+        self.height = Some(response.height.value());
+        self.code = result.code.value();
+        self.log = result.code.is_err().then(|| result.log.clone());
+    }
+}
+
+/// The packet carried by a message receiving, acknowledging or timing out a packet
+fn packet_of(message: &Any) -> Option<AuditedPacket> {
+    let value = message.value.as_slice();
+
+    let packet = match message.type_url.as_str() {
+        recv_packet::TYPE_URL => MsgRecvPacket::decode(value).ok()?.packet,
+        acknowledgement::TYPE_URL => MsgAcknowledgement::decode(value).ok()?.packet,
+        timeout::TYPE_URL => MsgTimeout::decode(value).ok()?.packet,
+        timeout_on_close::TYPE_URL => MsgTimeoutOnClose::decode(value).ok()?.packet,
+        _ => None,
+    }?;
+
+    let RawPacket {
+        sequence,
+        source_port,
+        source_channel,
+        ..
+    } = packet;
+
+    Some(AuditedPacket {
+        port_id: source_port.parse().ok()?,
+        channel_id: source_channel.parse().ok()?,
+        sequence: Sequence::from(sequence),
+    })
+}
+
+/// Open the audit log, if it is enabled in the given configuration.
+///
+/// The log is shared by the whole process, and subsequent calls have no effect.
+pub fn init(config: &AuditConfig) -> Result<(), Error> {
+    if !config.enabled || AUDIT_LOG.get().is_some() {
+        return Ok(());
+    }
+
+    let path = match &config.path {
+        Some(path) => path.clone(),
+        None => {
+            let home = dirs_next::home_dir().ok_or_else(Error::home_location_unavailable)?;
+            home.join(AUDIT_LOG_DEFAULT_PATH)
+        }
+    };
+
+    let log = AuditLog::open(
+        path,
+        config.max_file_size.get_bytes(),
+        config.rotation_period,
+    )?;
+
+    info!(path = %log.path.display(), "opened audit log of transactions");
+
+    let _ = AUDIT_LOG.set(Mutex::new(log));
+
+    Ok(())
+}
+
+/// Whether the audit log is enabled
+pub fn is_enabled() -> bool {
+    AUDIT_LOG.get().is_some()
+}
+
+/// Append the given entry to the audit log, if it is enabled
+pub fn record(entry: &TxAuditEntry) {
+    if let Some(log) = AUDIT_LOG.get() {
+        if let Err(e) = log.lock().unwrap().append(entry) {
+            warn!(
+                chain = %entry.chain_id,
+                tx_hash = %entry.tx_hash,
+                "failed to record transaction in the audit log: {e}"
+            );
+        }
+    }
+}
+
+/// Keep the entry of a transaction, already recorded as broadcast, until its commit is observed
+/// by [`record_committed`], unless it failed `CheckTx`
+pub fn track_commit(entry: TxAuditEntry) {
+    if is_enabled() && entry.code == 0 {
+        PENDING.insert((entry.chain_id.clone(), entry.tx_hash.clone()), entry);
+    }
+}
+
+/// Record again the entry of a transaction kept by [`track_commit`],
+/// with the result of the transaction now that it is committed
+pub fn record_committed(chain_id: &ChainId, response: &TxResponse) {
+    let key = (chain_id.clone(), response.hash.to_string());
+
+    if let Some(mut entry) = PENDING.remove(&key) {
+        entry.set_committed(response);
+        record(&entry);
+    }
+}
+
+/// Forget the entry of a transaction kept by [`track_commit`],
+/// once its commit is not awaited anymore
+pub fn untrack_commit(chain_id: &ChainId, tx_hash: &str) {
+    PENDING.invalidate(&(chain_id.clone(), tx_hash.to_string()));
+}
+
+/// A JSON-lines file, rotated by size and by age
+struct AuditLog {
+    path: PathBuf,
+    file: File,
+    size: u64,
+    opened_at: SystemTime,
+    max_file_size: u64,
+    rotation_period: Duration,
+}
+
+impl AuditLog {
+    fn open(path: PathBuf, max_file_size: u64, rotation_period: Duration) -> Result<Self, Error> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| Error::io(parent.to_path_buf(), e))?;
+        }
+
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .map_err(|e| Error::io(path.clone(), e))?;
+
+        let metadata = file.metadata().map_err(|e| Error::io(path.clone(), e))?;
+
+        // A file left by a previous run is rotated according to when it was created
+        let opened_at = metadata.created().unwrap_or_else(|_| SystemTime::now());
+
+        Ok(Self {
+            path,
+            file,
+            size: metadata.len(),
+            opened_at,
+            max_file_size,
+            rotation_period,
+        })
+    }
+
+    fn append(&mut self, entry: &TxAuditEntry) -> Result<(), Error> {
+        let mut line = serde_json::to_vec(entry).map_err(Error::encode)?;
+        line.push(b'\n');
+
+        if self.should_rotate(line.len() as u64) {
+            self.rotate()?;
+        }
+
+        self.file
+            .write_all(&line)
+            .map_err(|e| Error::io(self.path.clone(), e))?;
+
+        self.size += line.len() as u64;
+
+        Ok(())
+    }
+
+    fn should_rotate(&self, line_len: u64) -> bool {
+        if self.size == 0 {
+            return false;
+        }
+
+        let age = self.opened_at.elapsed().unwrap_or_default();
+
+        self.size + line_len > self.max_file_size || age >= self.rotation_period
+    }
+
+    /// Move the current file aside, suffixed with the current time, and start a new one
+    fn rotate(&mut self) -> Result<(), Error> {
+        let rotated = rotated_path(&self.path, SystemTime::now());
+
+        fs::rename(&self.path, &rotated).map_err(|e| Error::io(rotated, e))?;
+
+        *self = Self::open(self.path.clone(), self.max_file_size, self.rotation_period)?;
+
+        // The file may inherit the creation time of the rotated one on some file systems
+        self.opened_at = SystemTime::now();
+
+        Ok(())
+    }
+}
+
+/// The path of a rotated file, eg. `txs.jsonl.1686578531123` for `txs.jsonl`
+fn rotated_path(path: &Path, now: SystemTime) -> PathBuf {
+    let millis = now
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+
+    let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(format!(".{millis}"));
+
+    path.with_file_name(file_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ibc_proto::cosmos::base::v1beta1::Coin;
+    use ibc_proto::ibc::core::client::v1::MsgUpdateClient;
+    use ibc_relayer_types::core::ics02_client::msgs::update_client;
+    use tendermint::abci::Code;
+
+    fn recv_packet(sequence: u64) -> Any {
+        Any {
+            type_url: recv_packet::TYPE_URL.to_string(),
+            value: MsgRecvPacket {
+                packet: Some(RawPacket {
+                    sequence,
+                    source_port: "transfer".to_string(),
+                    source_channel: "channel-0".to_string(),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }
+            .encode_to_vec(),
+        }
+    }
+
+    fn update_client() -> Any {
+        Any {
+            type_url: update_client::TYPE_URL.to_string(),
+            value: MsgUpdateClient::default().encode_to_vec(),
+        }
+    }
+
+    fn entry(code: u32) -> TxAuditEntry {
+        let fee = Fee {
+            amount: vec![Coin {
+                denom: "stake".to_string(),
+                amount: "2500".to_string(),
+            }],
+            gas_limit: 250_000,
+            ..Default::default()
+        };
+
+        let response = Response {
+            code: Code::from(code),
+            data: Default::default(),
+            log: "out of gas".to_string(),
+            hash: Default::default(),
+        };
+
+        TxAuditEntry::new(
+            &ChainId::from_string("ibc-0"),
+            TrackingId::new_static("audit"),
+            "cosmos1relayer".to_string(),
+            &[update_client(), recv_packet(7), recv_packet(8)],
+            &fee,
+            &response,
+        )
+    }
+
+    fn temp_log_path(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("hermes-audit-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir.join("txs.jsonl")
+    }
+
+    fn files_in(path: &Path) -> usize {
+        fs::read_dir(path.parent().unwrap()).unwrap().count()
+    }
+
+    #[test]
+    fn entry_of_broadcast_tx() {
+        let entry = entry(0);
+
+        assert_eq!(
+            entry.messages,
+            vec![
+                update_client::TYPE_URL,
+                recv_packet::TYPE_URL,
+                recv_packet::TYPE_URL
+            ]
+        );
+        assert_eq!(
+            entry
+                .packets
+                .iter()
+                .map(|p| (p.port_id.as_str(), p.channel_id.to_string(), p.sequence))
+                .collect::<Vec<_>>(),
+            vec![
+                ("transfer", "channel-0".to_string(), Sequence::from(7)),
+                ("transfer", "channel-0".to_string(), Sequence::from(8)),
+            ]
+        );
+        assert_eq!(entry.status, TxAuditStatus::Broadcast);
+        assert_eq!(entry.gas_wanted, 250_000);
+        assert_eq!(entry.gas_used, None);
+        assert_eq!(entry.fee[0].amount, "2500");
+        assert_eq!(entry.log, None);
+
+        assert_eq!(self::entry(11).log.as_deref(), Some("out of gas"));
+    }
+
+    #[test]
+    fn rotated_when_above_max_size() {
+        let path = temp_log_path("size");
+        let mut log = AuditLog::open(path.clone(), 1024, Duration::from_secs(3600)).unwrap();
+
+        for _ in 0..5 {
+            log.append(&entry(0)).unwrap();
+        }
+
+        assert!(files_in(&path) > 1);
+
+        for file in fs::read_dir(path.parent().unwrap()).unwrap() {
+            let file = file.unwrap().path();
+            let content = fs::read_to_string(&file).unwrap();
+
+            assert!(content.len() <= 1024, "{} is too large", file.display());
+
+            for line in content.lines() {
+                let parsed: TxAuditEntry = serde_json::from_str(line).unwrap();
+                assert_eq!(parsed.packets.len(), 2);
+            }
+        }
+
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn rotated_after_period() {
+        let path = temp_log_path("period");
+        let mut log = AuditLog::open(path.clone(), u64::MAX, Duration::ZERO).unwrap();
+
+        log.append(&entry(0)).unwrap();
+        assert_eq!(files_in(&path), 1);
+
+        std::thread::sleep(Duration::from_millis(2));
+        log.append(&entry(0)).unwrap();
+        assert_eq!(files_in(&path), 2);
+
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}
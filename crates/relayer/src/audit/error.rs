@@ -0,0 +1,19 @@
+use std::path::PathBuf;
+
+use flex_error::{define_error, TraceError};
+
+define_error! {
+    Error {
+        Io
+            { path: PathBuf }
+            [ TraceError<std::io::Error> ]
+            |e| { format!("I/O error on audit log file '{}'", e.path.display()) },
+
+        Encode
+            [ TraceError<serde_json::Error> ]
+            |_| { "failed to encode audit log entry" },
+
+        HomeLocationUnavailable
+            |_| { "home location is unavailable" },
+    }
+}
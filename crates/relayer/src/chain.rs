@@ -1,10 +1,13 @@
+pub mod budget;
 pub mod client;
 pub mod cosmos;
 pub mod counterparty;
 pub mod endpoint;
 pub mod handle;
+pub mod mock;
 pub mod requests;
 pub mod runtime;
+pub mod solomachine;
 pub mod tracking;
 
 use serde::{de::Error, Deserialize, Serialize};
@@ -18,6 +21,14 @@ use serde::{de::Error, Deserialize, Serialize};
 pub enum ChainType {
     /// Chains based on the Cosmos SDK
     CosmosSdk,
+
+    /// In-process mock chains, for testing the relayer without any full node.
+    /// Only available when the relayer is built with the `mock` feature.
+    Mock,
+
+    /// Solo machines, whose keys are in the Hermes keyring and
+    /// whose IBC state is persisted by the relayer.
+    SoloMachine,
 }
 
 impl<'de> Deserialize<'de> for ChainType {
@@ -30,9 +41,14 @@ impl<'de> Deserialize<'de> for ChainType {
 
         match s.as_str() {
             "cosmossdk" => Ok(Self::CosmosSdk),
+            "mock" => Ok(Self::Mock),
+            "solomachine" => Ok(Self::SoloMachine),
 
             // NOTE(new): Add a case here
-            _ => Err(D::Error::unknown_variant(&original, &["cosmos-sdk"])), // NOTE(new): mention the new variant here
+            _ => Err(D::Error::unknown_variant(
+                &original,
+                &["cosmos-sdk", "mock", "solo-machine"],
+            )), // NOTE(new): mention the new variant here
         }
     }
 }
@@ -58,6 +74,12 @@ mod tests {
         assert!(matches!(parse("cosmossdk"), Ok(CosmosSdk)));
         assert!(matches!(parse("cosmos-sdk"), Ok(CosmosSdk)));
 
+        assert!(matches!(parse("Mock"), Ok(Mock)));
+        assert!(matches!(parse("mock"), Ok(Mock)));
+
+        assert!(matches!(parse("SoloMachine"), Ok(SoloMachine)));
+        assert!(matches!(parse("solo-machine"), Ok(SoloMachine)));
+
         // NOTE(new): Add tests here
 
         assert!(matches!(parse("hello-world"), Err(_)));
@@ -0,0 +1,294 @@
+//! Tracking of the fees spent by the relayer on a chain against the
+//! `fee_budget` configured for that chain.
+//!
+//! The spending is only tracked in memory, hence the budget is fully
+//! replenished, and a new period starts, whenever Hermes is restarted.
+
+use core::time::Duration;
+use std::sync::{Mutex, MutexGuard};
+use std::time::Instant;
+
+use ibc_proto::cosmos::tx::v1beta1::Fee;
+use ibc_proto::google::protobuf::Any;
+use ibc_relayer_types::core::ics02_client::msgs::{misbehaviour, update_client};
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+use serde::{Deserialize, Serialize};
+
+use crate::config::FeeBudget;
+use crate::error::Error;
+use crate::telemetry;
+
+/// The type URLs of the messages which are submitted even when the budget
+/// is exhausted, as they are required to keep the clients up to date and secure.
+const EXEMPT_TYPE_URLS: [&str; 2] = [update_client::TYPE_URL, misbehaviour::TYPE_URL];
+
+/// The state of the fee budget of a chain in the current period.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeeBudgetStatus {
+    pub amount: u64,
+    pub denom: String,
+    pub remaining: u64,
+    #[serde(with = "humantime_serde")]
+    pub period: Duration,
+    #[serde(with = "humantime_serde")]
+    pub resets_in: Duration,
+}
+
+#[derive(Debug)]
+struct Spending {
+    period_start: Instant,
+    spent: u64,
+
+    /// The fees of the transactions being submitted, which count against
+    /// the budget until they are either spent or released
+    reserved: u64,
+}
+
+/// Tracks the fees spent on a chain over the current period of its fee budget,
+/// and prevents the submission of transactions which would exceed the budget,
+/// unless they only update clients or submit misbehaviour.
+#[derive(Debug)]
+pub struct FeeBudgetTracker {
+    chain_id: ChainId,
+    budget: FeeBudget,
+    spending: Mutex<Spending>,
+}
+
+impl FeeBudgetTracker {
+    pub fn new(chain_id: ChainId, budget: FeeBudget) -> Self {
+        telemetry!(
+            fee_budget_remaining,
+            &chain_id,
+            &budget.denom,
+            budget.amount
+        );
+
+        Self {
+            chain_id,
+            budget,
+            spending: Mutex::new(Spending {
+                period_start: Instant::now(),
+                spent: 0,
+                reserved: 0,
+            }),
+        }
+    }
+
+    /// Reserves the fee of a transaction with the given messages, failing with
+    /// [`Error::fee_budget_exhausted`] if this would exceed the budget of the current period.
+    ///
+    /// The fee counts against the budget until the returned reservation is either
+    /// [spent](FeeReservation::spend) once the transaction is submitted, or dropped
+    /// if it was not, such that concurrent submissions cannot overrun the budget.
+    pub fn reserve(&self, fee: &Fee, messages: &[Any]) -> Result<FeeReservation<'_>, Error> {
+        let exempt = messages
+            .iter()
+            .all(|msg| EXEMPT_TYPE_URLS.contains(&msg.type_url.as_str()));
+
+        let amount = self.amount_of(fee);
+        let now = Instant::now();
+        let mut spending = self.current_spending(now);
+
+        let committed = spending.spent.saturating_add(spending.reserved);
+
+        if !exempt && committed.saturating_add(amount) > self.budget.amount {
+            return Err(Error::fee_budget_exhausted(
+                self.chain_id.clone(),
+                self.budget.amount,
+                self.budget.denom.clone(),
+                self.resets_in(&spending, now),
+            ));
+        }
+
+        spending.reserved = spending.reserved.saturating_add(amount);
+
+        Ok(FeeReservation {
+            tracker: self,
+            amount,
+        })
+    }
+
+    pub fn status(&self) -> FeeBudgetStatus {
+        let now = Instant::now();
+        let spending = self.current_spending(now);
+
+        FeeBudgetStatus {
+            amount: self.budget.amount,
+            denom: self.budget.denom.clone(),
+            remaining: self.budget.amount.saturating_sub(spending.spent),
+            period: self.budget.period,
+            resets_in: self.resets_in(&spending, now),
+        }
+    }
+
+    fn spend(&self, amount: u64) {
+        let mut spending = self.current_spending(Instant::now());
+        spending.reserved = spending.reserved.saturating_sub(amount);
+        spending.spent = spending.spent.saturating_add(amount);
+
+        telemetry!(
+            fee_budget_remaining,
+            &self.chain_id,
+            &self.budget.denom,
+            self.budget.amount.saturating_sub(spending.spent)
+        );
+    }
+
+    fn release(&self, amount: u64) {
+        let mut spending = self.spending.lock().expect("poisoned lock");
+        spending.reserved = spending.reserved.saturating_sub(amount);
+    }
+
+    /// Returns the spending of the current period, starting a new period
+    /// if the previous one has elapsed.
+    fn current_spending(&self, now: Instant) -> MutexGuard<'_, Spending> {
+        let mut spending = self.spending.lock().expect("poisoned lock");
+
+        let elapsed = now.saturating_duration_since(spending.period_start);
+
+        if elapsed >= self.budget.period {
+            // Start the new period at the last multiple of the period,
+            // such that periods do not drift when no fees are spent for a while.
+            let periods = elapsed.as_nanos() / self.budget.period.as_nanos().max(1);
+            let offset = self.budget.period.as_nanos() * periods;
+
+            // The reservations of the transactions being submitted carry over
+            spending.period_start += Duration::from_nanos(offset as u64);
+            spending.spent = 0;
+
+            telemetry!(
+                fee_budget_remaining,
+                &self.chain_id,
+                &self.budget.denom,
+                self.budget.amount
+            );
+        }
+
+        spending
+    }
+
+    fn resets_in(&self, spending: &Spending, now: Instant) -> Duration {
+        (spending.period_start + self.budget.period).saturating_duration_since(now)
+    }
+
+    fn amount_of(&self, fee: &Fee) -> u64 {
+        fee.amount
+            .iter()
+            .filter(|coin| coin.denom == self.budget.denom)
+            .filter_map(|coin| coin.amount.parse::<u64>().ok())
+            .fold(0, u64::saturating_add)
+    }
+}
+
+/// The fee of a transaction being submitted, reserved against the fee budget of the chain.
+///
+/// The fee is released if the reservation is dropped without being spent.
+#[derive(Debug)]
+pub struct FeeReservation<'a> {
+    tracker: &'a FeeBudgetTracker,
+    amount: u64,
+}
+
+impl FeeReservation<'_> {
+    /// Records the reserved fee as spent, once the transaction was submitted
+    pub fn spend(mut self) {
+        let amount = core::mem::take(&mut self.amount);
+        self.tracker.spend(amount);
+    }
+}
+
+impl Drop for FeeReservation<'_> {
+    fn drop(&mut self) {
+        if self.amount > 0 {
+            self.tracker.release(self.amount);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ibc_proto::cosmos::base::v1beta1::Coin;
+
+    fn tracker() -> FeeBudgetTracker {
+        FeeBudgetTracker::new(
+            ChainId::new("chain".to_string(), 0),
+            FeeBudget {
+                amount: 100,
+                denom: "stake".to_string(),
+                period: Duration::from_secs(3600),
+            },
+        )
+    }
+
+    fn fee(amount: u64) -> Fee {
+        Fee {
+            amount: vec![Coin {
+                denom: "stake".to_string(),
+                amount: amount.to_string(),
+            }],
+            gas_limit: 100_000,
+            payer: String::new(),
+            granter: String::new(),
+        }
+    }
+
+    fn msg(type_url: &str) -> Any {
+        Any {
+            type_url: type_url.to_string(),
+            value: vec![],
+        }
+    }
+
+    #[test]
+    fn budget_exhausted_except_for_client_messages() {
+        let tracker = tracker();
+        let recv = [
+            msg(update_client::TYPE_URL),
+            msg("/ibc.core.channel.v1.MsgRecvPacket"),
+        ];
+
+        tracker.reserve(&fee(60), &recv).unwrap().spend();
+
+        assert!(tracker.reserve(&fee(40), &recv).is_ok());
+        assert!(tracker.reserve(&fee(41), &recv).is_err());
+
+        tracker
+            .reserve(&fee(50), &[msg(update_client::TYPE_URL)])
+            .unwrap()
+            .spend();
+
+        let status = tracker.status();
+        assert_eq!(status.remaining, 0);
+        assert!(status.resets_in <= Duration::from_secs(3600));
+    }
+
+    #[test]
+    fn reserved_fees_count_until_released() {
+        let tracker = tracker();
+        let recv = [msg("/ibc.core.channel.v1.MsgRecvPacket")];
+
+        let first = tracker.reserve(&fee(60), &recv).unwrap();
+        assert!(tracker.reserve(&fee(60), &recv).is_err());
+
+        drop(first);
+        assert_eq!(tracker.status().remaining, 100);
+
+        let second = tracker.reserve(&fee(60), &recv).unwrap();
+        second.spend();
+        assert_eq!(tracker.status().remaining, 40);
+    }
+
+    #[test]
+    fn budget_replenished_after_period() {
+        let tracker = tracker();
+        tracker.reserve(&fee(100), &[]).unwrap().spend();
+
+        let later = Instant::now() + Duration::from_secs(3 * 3600 + 60);
+        let spending = tracker.current_spending(later);
+
+        assert_eq!(spending.spent, 0);
+        assert!(tracker.resets_in(&spending, later) <= Duration::from_secs(3600 - 60));
+    }
+}
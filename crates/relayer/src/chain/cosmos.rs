@@ -8,16 +8,19 @@ use core::{
 };
 use futures::future::join_all;
 use num_bigint::BigInt;
-use std::{cmp::Ordering, thread};
+use std::{cmp::Ordering, sync::Mutex, thread, time::Instant};
 
+use prost::Message;
 use tokio::runtime::Runtime as TokioRuntime;
 use tonic::codegen::http::Uri;
 use tonic::metadata::AsciiMetadataValue;
-use tracing::{error, instrument, trace, warn};
+use tracing::{debug, error, info, instrument, trace, warn};
 
 use ibc_proto::cosmos::{
-    base::node::v1beta1::ConfigResponse, staking::v1beta1::Params as StakingParams,
+    bank::v1beta1::MsgSend, base::node::v1beta1::ConfigResponse, base::v1beta1::Coin,
+    staking::v1beta1::Params as StakingParams, tx::v1beta1::Fee,
 };
+use ibc_proto::google::protobuf::Any;
 
 use ibc_proto::interchain_security::ccv::consumer::v1::Params as CcvConsumerParams;
 
@@ -26,6 +29,7 @@ use ibc_proto::ibc::apps::fee::v1::{
 };
 use ibc_proto::protobuf::Protobuf;
 use ibc_relayer_types::applications::ics31_icq::response::CrossChainQueryResponse;
+use ibc_relayer_types::applications::transfer::msgs::send;
 use ibc_relayer_types::clients::ics07_tendermint::client_state::{
     AllowUpdate, ClientState as TmClientState,
 };
@@ -39,14 +43,16 @@ use ibc_relayer_types::core::ics03_connection::connection::{
 };
 use ibc_relayer_types::core::ics04_channel::channel::{ChannelEnd, IdentifiedChannelEnd};
 use ibc_relayer_types::core::ics04_channel::packet::Sequence;
+use ibc_relayer_types::core::ics04_channel::upgrade::{ErrorReceipt, Upgrade};
 use ibc_relayer_types::core::ics23_commitment::commitment::CommitmentPrefix;
 use ibc_relayer_types::core::ics23_commitment::merkle::MerkleProof;
 use ibc_relayer_types::core::ics24_host::identifier::{
     ChainId, ChannelId, ClientId, ConnectionId, PortId,
 };
 use ibc_relayer_types::core::ics24_host::path::{
-    AcksPath, ChannelEndsPath, ClientConsensusStatePath, ClientStatePath, CommitmentsPath,
-    ConnectionsPath, ReceiptsPath, SeqRecvsPath,
+    AcksPath, ChannelEndsPath, ChannelUpgradeErrorsPath, ChannelUpgradesPath,
+    ClientConsensusStatePath, ClientStatePath, CommitmentsPath, ConnectionsPath, ReceiptsPath,
+    SeqRecvsPath,
 };
 use ibc_relayer_types::core::ics24_host::{
     ClientUpgradePath, Path, IBC_QUERY_PATH, SDK_UPGRADE_QUERY_PATH,
@@ -61,28 +67,34 @@ use tendermint_light_client::verifier::types::LightBlock as TmLightBlock;
 use tendermint_rpc::client::CompatMode;
 use tendermint_rpc::endpoint::broadcast::tx_sync::Response;
 use tendermint_rpc::endpoint::status;
-use tendermint_rpc::{Client, HttpClient, Order};
+use tendermint_rpc::{Client, HttpClient, Order, Url};
 
 use crate::account::Balance;
+use crate::chain::budget::FeeBudgetStatus;
 use crate::chain::client::ClientSettings;
 use crate::chain::cosmos::batch::{
     send_batched_messages_and_wait_check_tx, send_batched_messages_and_wait_commit,
-    sequential_send_batched_messages_and_wait_commit,
+    sequential_send_batched_messages_and_wait_commit, simulate_batched_messages,
 };
 use crate::chain::cosmos::encode::key_pair_to_signer;
+use crate::chain::cosmos::estimate::{estimate_tx_fees, simulate_tx_gas};
+use crate::chain::cosmos::failover::{EndpointError, Failover, PROBE_TIMEOUT};
 use crate::chain::cosmos::fee::maybe_register_counterparty_payee;
-use crate::chain::cosmos::gas::{calculate_fee, mul_ceil};
-use crate::chain::cosmos::query::account::get_or_fetch_account;
+use crate::chain::cosmos::gas::{calculate_fee, dynamic_gas_price, mul_ceil, DynamicGasPriceCache};
 use crate::chain::cosmos::query::balance::{query_all_balances, query_balance};
 use crate::chain::cosmos::query::consensus_state::query_consensus_state_heights;
 use crate::chain::cosmos::query::custom::cross_chain_query_via_rpc;
 use crate::chain::cosmos::query::denom_trace::query_denom_trace;
 use crate::chain::cosmos::query::fee::query_incentivized_packet;
+use crate::chain::cosmos::query::fee_market::query_base_fee;
 use crate::chain::cosmos::query::status::query_status;
 use crate::chain::cosmos::query::tx::{
     filter_matching_event, query_packets_from_block, query_packets_from_txs, query_txs,
 };
 use crate::chain::cosmos::query::{abci_query, fetch_version_specs, packet_query, QueryResponse};
+use crate::chain::cosmos::rate_limit::{RateLimiter, ThrottleError};
+use crate::chain::cosmos::signer::SigningAccounts;
+use crate::chain::cosmos::tx::simple_send_tx;
 use crate::chain::cosmos::types::account::Account;
 use crate::chain::cosmos::types::config::TxConfig;
 use crate::chain::cosmos::types::gas::{
@@ -93,16 +105,18 @@ use crate::chain::handle::Subscription;
 use crate::chain::requests::*;
 use crate::chain::tracking::TrackedMsgs;
 use crate::client_state::{AnyClientState, IdentifiedAnyClientState};
-use crate::config::{parse_gas_prices, ChainConfig, GasPrice};
+use crate::config::{parse_gas_prices, ChainConfig, DynamicGasPrice, GasPrice};
 use crate::consensus_state::AnyConsensusState;
 use crate::denom::DenomTrace;
 use crate::error::Error;
 use crate::event::source::{EventSource, TxEventSourceCmd};
 use crate::event::IbcEventWithHeight;
 use crate::keyring::{KeyRing, Secp256k1KeyPair, SigningKeyPair};
+use crate::light_client::cache::LightBlockCache;
 use crate::light_client::tendermint::LightClient as TmLightClient;
 use crate::light_client::{LightClient, Verified};
 use crate::misbehaviour::MisbehaviourEvidence;
+use crate::util::dry_run;
 use crate::util::pretty::{
     PrettyIdentifiedChannel, PrettyIdentifiedClientState, PrettyIdentifiedConnection,
 };
@@ -112,10 +126,13 @@ pub mod client;
 pub mod compatibility;
 pub mod encode;
 pub mod estimate;
+pub mod failover;
 pub mod fee;
 pub mod gas;
 pub mod query;
+pub mod rate_limit;
 pub mod retry;
+pub mod signer;
 pub mod simulate;
 pub mod tx;
 pub mod types;
@@ -142,15 +159,40 @@ pub struct CosmosSdkChain {
     tx_config: TxConfig,
     rpc_client: HttpClient,
     compat_mode: CompatMode,
+
+    /// Addresses of the full node in use, which are those of a fallback node
+    /// rather than the configured ones after switching over to it
+    rpc_addr: Url,
     grpc_addr: Uri,
     light_client: TmLightClient,
+
+    /// Light blocks fetched by the light client, kept across verifications
+    /// and across switches to another full node
+    light_block_cache: Arc<LightBlockCache>,
+
     rt: Arc<TokioRuntime>,
     keybase: KeyRing<Secp256k1KeyPair>,
 
-    /// A cached copy of the account information
-    account: Option<Account>,
+    /// Cached copies of the account information of the keys signing transactions,
+    /// in the order of `ChainConfig::signing_key_names`
+    accounts: Vec<Option<Account>>,
+
+    /// Index of the account to which the next batch of messages is assigned
+    next_account: usize,
 
     tx_monitor_cmd: Option<TxEventSourceCmd>,
+
+    /// Health of the full nodes of the chain, used to switch over to
+    /// a fallback node when the node in use is unhealthy
+    failover: Mutex<Failover>,
+
+    /// Gas price derived from the fee market of the chain, for the latest block
+    /// at which it was queried, when `dynamic_gas_price` is configured
+    gas_price_cache: DynamicGasPriceCache,
+
+    /// Rate limits of the requests sent to the full node in use,
+    /// shared with the transactions submitted through `tx_config`
+    rate_limiter: Arc<RateLimiter>,
 }
 
 impl CosmosSdkChain {
@@ -170,6 +212,15 @@ impl CosmosSdkChain {
             .map_err(Error::key_base)
     }
 
+    /// The key pairs of all the keys signing transactions, starting with the main key
+    fn signing_keys(&self) -> Result<Vec<Secp256k1KeyPair>, Error> {
+        self.config
+            .signing_key_names()
+            .into_iter()
+            .map(|key_name| self.keybase().get_key(key_name).map_err(Error::key_base))
+            .collect()
+    }
+
     /// Fetches the trusting period as a `Duration` from the chain config.
     /// If no trusting period exists in the config, the trusting period is calculated
     /// as two-thirds of the `unbonding_period`.
@@ -236,7 +287,7 @@ impl CosmosSdkChain {
             .map_err(|e| {
                 Error::config_validation_json_rpc(
                     self.id().clone(),
-                    self.config.rpc_addr.to_string(),
+                    self.rpc_addr.to_string(),
                     "/consensus_params".to_string(),
                     e,
                 )
@@ -283,6 +334,39 @@ impl CosmosSdkChain {
             ));
         }
 
+        // The static gas price is used when the base fee cannot be queried,
+        // so it must not be greater than the maximum dynamic gas price.
+        if let Some(dynamic_gas_price) = &self.config.dynamic_gas_price {
+            if dynamic_gas_price.max < self.config.gas_price.price {
+                return Err(Error::config_validation_dynamic_gas_price_max_low(
+                    self.id().clone(),
+                    dynamic_gas_price.max,
+                    self.config.gas_price.price,
+                ));
+            }
+        }
+
+        // Fees are paid in the denomination of the gas price, so the budget
+        // must be in the same denomination for the spent fees to count towards it.
+        if let Some(fee_budget) = &self.config.fee_budget {
+            if fee_budget.denom != self.config.gas_price.denom {
+                return Err(Error::config_validation_fee_budget(
+                    self.id().clone(),
+                    format!(
+                        "denomination '{}' differs from the gas price denomination '{}'",
+                        fee_budget.denom, self.config.gas_price.denom
+                    ),
+                ));
+            }
+
+            if fee_budget.period.is_zero() {
+                return Err(Error::config_validation_fee_budget(
+                    self.id().clone(),
+                    "period must be greater than zero".to_string(),
+                ));
+            }
+        }
+
         Ok(())
     }
 
@@ -296,10 +380,17 @@ impl CosmosSdkChain {
 
         use crate::config::EventSourceMode as Mode;
 
-        let (event_source, monitor_tx) = match &self.config.event_source {
+        // Subscribe to the WebSocket endpoint of the full node in use,
+        // which is not the configured one after a switch to a fallback node
+        let active_websocket_addr = {
+            let failover = self.failover.lock().expect("poisoned lock");
+            failover.endpoints(failover.active()).websocket_addr.clone()
+        };
+
+        let (mut event_source, monitor_tx) = match &self.config.event_source {
             Mode::Push { url, batch_delay } => EventSource::websocket(
                 self.config.id.clone(),
-                url.clone(),
+                active_websocket_addr.unwrap_or_else(|| url.clone()),
                 self.compat_mode,
                 *batch_delay,
                 self.rt.clone(),
@@ -313,11 +404,185 @@ impl CosmosSdkChain {
         }
         .map_err(Error::event_source)?;
 
+        // Replay the events missed since the relayer last processed a block of this chain
+        if let Some(height) = crate::store::resume_height(&self.config.id) {
+            info!(
+                chain = %self.config.id,
+                "resuming processing of events after height {height}"
+            );
+
+            event_source.resume_from(TmHeight::from(height), self.rpc_client.clone());
+        }
+
         thread::spawn(move || event_source.run());
 
         Ok(monitor_tx)
     }
 
+    /// Record a successful request to the full node in use
+    fn record_endpoint_success(&self, latest_height: u64, latency: Duration) {
+        let mut failover = self.failover.lock().expect("poisoned lock");
+        let active = failover.active();
+
+        failover.record_success(active, latest_height, latency);
+    }
+
+    /// Record a failed request to the full node in use
+    fn record_endpoint_error(&self) {
+        let mut failover = self.failover.lock().expect("poisoned lock");
+        let active = failover.active();
+
+        failover.record_error(active);
+    }
+
+    /// Record a failed request to the full node in use,
+    /// unless the error was reported by the application rather than the node
+    fn record_endpoint_result<T, E: EndpointError>(&self, result: &Result<T, E>) {
+        if matches!(result, Err(e) if e.is_endpoint_failure()) {
+            self.record_endpoint_error();
+        }
+    }
+
+    /// Query the latest height of all the full nodes of the chain concurrently,
+    /// and record their health.
+    ///
+    /// The failover state is not locked while the nodes are probed,
+    /// so that requests to the node in use are not held up by the probes.
+    fn probe_endpoints(&self) {
+        let nodes: Vec<_> = {
+            let failover = self.failover.lock().expect("poisoned lock");
+
+            if !failover.is_enabled() {
+                return;
+            }
+
+            failover.nodes().cloned().collect()
+        };
+
+        let probe_timeout = core::cmp::min(self.config.rpc_timeout, PROBE_TIMEOUT);
+
+        let probes = nodes.iter().map(|node| async move {
+            let start = Instant::now();
+
+            let mut rpc_client = HttpClient::new(node.rpc_addr.clone()).ok()?;
+            rpc_client.set_compat_mode(self.compat_mode);
+
+            // Probes count towards the rate limits of the chain like any other request
+            let info = self
+                .rate_limiter
+                .throttle(async {
+                    tokio::time::timeout(probe_timeout, rpc_client.abci_info())
+                        .await
+                        .unwrap_or_else(|_| Err(tendermint_rpc::Error::timeout(probe_timeout)))
+                })
+                .await
+                .ok()?;
+
+            Some((info.last_block_height.value(), start.elapsed()))
+        });
+
+        let results = self.rt.block_on(join_all(probes));
+
+        let mut failover = self.failover.lock().expect("poisoned lock");
+
+        for (index, (node, result)) in nodes.iter().zip(results).enumerate() {
+            match result {
+                Some((height, latency)) => failover.record_success(index, height, latency),
+                None => {
+                    trace!(rpc_addr = %node.rpc_addr, "full node did not respond to probe");
+                    failover.record_error(index)
+                }
+            }
+
+            crate::telemetry!(
+                endpoint_active,
+                self.id(),
+                &node.rpc_addr.to_string(),
+                index == failover.active()
+            );
+        }
+
+        failover.probed(Instant::now());
+    }
+
+    /// Switch queries, transactions and the event source over to the given full node
+    fn switch_endpoint(&mut self, node: usize) -> Result<(), Error> {
+        let endpoints = self
+            .failover
+            .lock()
+            .expect("poisoned lock")
+            .endpoints(node)
+            .clone();
+
+        let mut rpc_client = HttpClient::new(endpoints.rpc_addr.clone())
+            .map_err(|e| Error::rpc(endpoints.rpc_addr.clone(), e))?;
+
+        rpc_client.set_compat_mode(self.compat_mode);
+
+        let grpc_addr = Uri::from_str(&endpoints.grpc_addr.to_string())
+            .map_err(|e| Error::invalid_uri(endpoints.grpc_addr.to_string(), e))?;
+
+        // The configuration of the chain is left as is, so that it keeps
+        // reporting the configured full node rather than the one in use
+        let mut node_config = self.config.clone();
+        node_config.rpc_addr = endpoints.rpc_addr.clone();
+        node_config.grpc_addr = endpoints.grpc_addr.clone();
+
+        let rate_limiter = Arc::new(RateLimiter::new(
+            self.config.id.clone(),
+            self.config.rate_limit.as_ref(),
+        ));
+
+        let node_info = self
+            .rt
+            .block_on(rate_limiter.throttle(fetch_node_info(&rpc_client, &node_config)))?;
+        let light_client =
+            TmLightClient::from_config(&node_config, node_info.id, self.light_block_cache.clone())?;
+
+        if let Some(tx_monitor_cmd) = &self.tx_monitor_cmd {
+            tx_monitor_cmd
+                .switch_endpoint(rpc_client.clone(), endpoints.websocket_addr.clone())
+                .map_err(Error::event_source)?;
+        }
+
+        warn!(
+            chain = %self.id(),
+            from = %self.rpc_addr,
+            to = %endpoints.rpc_addr,
+            "full node is unhealthy, switching over to another node"
+        );
+
+        crate::telemetry!(endpoint_switch, self.id());
+        crate::telemetry!(
+            endpoint_active,
+            self.id(),
+            &self.rpc_addr.to_string(),
+            false
+        );
+        crate::telemetry!(
+            endpoint_active,
+            self.id(),
+            &endpoints.rpc_addr.to_string(),
+            true
+        );
+
+        self.tx_config.rpc_address = endpoints.rpc_addr.clone();
+        self.tx_config.grpc_address = grpc_addr.clone();
+        self.tx_config.rate_limiter = rate_limiter.clone();
+
+        self.rpc_client = rpc_client;
+        self.rpc_addr = endpoints.rpc_addr.clone();
+        self.grpc_addr = grpc_addr;
+        self.light_client = light_client;
+        self.rate_limiter = rate_limiter;
+
+        self.failover.lock().expect("poisoned lock").switch_to(node);
+
+        info!(chain = %self.id(), "switched over to full node {}", endpoints.rpc_addr);
+
+        Ok(())
+    }
+
     /// Query the chain staking parameters
     pub fn query_ccv_consumer_chain_params(&self) -> Result<CcvConsumerParams, Error> {
         crate::time!(
@@ -514,9 +779,16 @@ impl CosmosSdkChain {
         }
     }
 
-    /// Run a future to completion on the Tokio runtime.
-    fn block_on<F: Future>(&self, f: F) -> F::Output {
-        self.rt.block_on(f)
+    /// Run a request to the full node, subject to the rate limits of the chain,
+    /// and record whether the node failed to serve it
+    fn block_on<T, E, F>(&self, f: F) -> Result<T, E>
+    where
+        E: ThrottleError + EndpointError,
+        F: Future<Output = Result<T, E>>,
+    {
+        let result = self.rt.block_on(self.rate_limiter.throttle(f));
+        self.record_endpoint_result(&result);
+        result
     }
 
     fn query(
@@ -537,7 +809,7 @@ impl CosmosSdkChain {
 
         let response = self.block_on(abci_query(
             &self.rpc_client,
-            &self.config.rpc_addr,
+            &self.rpc_addr,
             IBC_QUERY_PATH.to_string(),
             data.to_string(),
             height_query.into(),
@@ -576,7 +848,7 @@ impl CosmosSdkChain {
 
         let response: QueryResponse = self.block_on(abci_query(
             &self.rpc_client,
-            &self.config.rpc_addr,
+            &self.rpc_addr,
             path,
             Path::Upgrade(query_data).to_string(),
             query_height.into(),
@@ -603,11 +875,11 @@ impl CosmosSdkChain {
 
         let status = self
             .block_on(self.rpc_client.status())
-            .map_err(|e| Error::rpc(self.config.rpc_addr.clone(), e))?;
+            .map_err(|e| Error::rpc(self.rpc_addr.clone(), e))?;
 
         if status.sync_info.catching_up {
             return Err(Error::chain_not_caught_up(
-                self.config.rpc_addr.to_string(),
+                self.rpc_addr.to_string(),
                 self.config().id.clone(),
             ));
         }
@@ -625,15 +897,125 @@ impl CosmosSdkChain {
         );
         crate::telemetry!(query, self.id(), "query_latest_height");
 
-        let status = self.rt.block_on(query_status(
-            self.id(),
-            &self.rpc_client,
-            &self.config.rpc_addr,
-        ))?;
+        let status = self.block_on(query_status(self.id(), &self.rpc_client, &self.rpc_addr))?;
 
         Ok(status.height)
     }
 
+    /// Update the gas price used to submit transactions from the base fee
+    /// of the chain, when `dynamic_gas_price` is configured.
+    ///
+    /// Falls back to the static `gas_price` if the base fee cannot be queried.
+    async fn refresh_gas_price(&mut self) {
+        let config = match self.config.dynamic_gas_price.clone() {
+            Some(config) => config,
+            None => return,
+        };
+
+        let price = match self.query_dynamic_gas_price(&config).await {
+            Ok(price) => price,
+            Err(e) => {
+                warn!(
+                    chain = %self.id(),
+                    "failed to query the base fee, falling back to the configured gas price {}: {e}",
+                    self.config.gas_price,
+                );
+
+                self.config.gas_price.price
+            }
+        };
+
+        self.tx_config.gas_config.set_gas_price(price);
+    }
+
+    async fn query_dynamic_gas_price(&mut self, config: &DynamicGasPrice) -> Result<f64, Error> {
+        let height = self
+            .rate_limiter
+            .throttle(self.rpc_client.abci_info())
+            .await
+            .map_err(|e| Error::rpc(self.rpc_addr.clone(), e))?
+            .last_block_height
+            .value();
+
+        if let Some(price) = self.gas_price_cache.get(height) {
+            return Ok(price);
+        }
+
+        let base_fee = query_base_fee(
+            &self.rpc_client,
+            &self.rpc_addr,
+            &self.rate_limiter,
+            &self.config.gas_price.denom,
+        )
+        .await?;
+
+        let price = dynamic_gas_price(base_fee, config);
+
+        debug!(
+            chain = %self.id(),
+            height,
+            base_fee,
+            price,
+            "derived gas price from the base fee of the chain"
+        );
+
+        self.gas_price_cache.set(height, price);
+
+        Ok(price)
+    }
+
+    async fn do_estimate_fee(&mut self, tracked_msgs: TrackedMsgs) -> Result<Fee, Error> {
+        self.refresh_gas_price().await;
+
+        let key_pairs = self.signing_keys()?;
+
+        let mut signers = SigningAccounts::fetch(
+            &self.grpc_addr,
+            &self.rate_limiter,
+            key_pairs,
+            &mut self.accounts,
+            self.next_account,
+        )
+        .await?;
+
+        let main_account = signers.main_account();
+
+        estimate_tx_fees(
+            &self.tx_config,
+            &main_account.key_pair,
+            main_account.account,
+            &self.config.memo_prefix,
+            &tracked_msgs.msgs,
+        )
+        .await
+    }
+
+    async fn do_simulate_messages(&mut self, tracked_msgs: TrackedMsgs) -> Result<u64, Error> {
+        self.refresh_gas_price().await;
+
+        let key_pairs = self.signing_keys()?;
+
+        let mut signers = SigningAccounts::fetch(
+            &self.grpc_addr,
+            &self.rate_limiter,
+            key_pairs,
+            &mut self.accounts,
+            self.next_account,
+        )
+        .await?;
+
+        let main_account = signers.main_account();
+
+        simulate_tx_gas(
+            &self.tx_config,
+            &main_account.key_pair,
+            main_account.account,
+            &self.config.memo_prefix,
+            &tracked_msgs.msgs,
+        )
+        .await
+    }
+
     #[instrument(
         name = "send_messages_and_wait_commit",
         level = "error",
@@ -654,35 +1036,62 @@ impl CosmosSdkChain {
             }
         );
 
+        let tracking_id = tracked_msgs.tracking_id();
+        let unordered_channel = tracked_msgs.unordered_channel;
         let proto_msgs = tracked_msgs.msgs;
 
-        let key_pair = self.key()?;
-        let key_account = key_pair.account();
+        self.refresh_gas_price().await;
+
+        let key_pairs = self.signing_keys()?;
 
-        let account =
-            get_or_fetch_account(&self.grpc_addr, &key_account, &mut self.account).await?;
+        let mut signers = SigningAccounts::fetch(
+            &self.grpc_addr,
+            &self.rate_limiter,
+            key_pairs,
+            &mut self.accounts,
+            self.next_account,
+        )
+        .await?;
 
-        if self.config.sequential_batch_tx {
+        if dry_run::is_enabled() {
+            simulate_batched_messages(
+                &self.tx_config,
+                &mut signers,
+                &self.config.memo_prefix,
+                proto_msgs,
+                tracking_id,
+            )
+            .await?;
+
+            return Ok(Vec::new());
+        }
+
+        let result = if self.config.sequential_batch_tx {
             sequential_send_batched_messages_and_wait_commit(
                 &self.rpc_client,
                 &self.tx_config,
-                &key_pair,
-                account,
+                &mut signers,
                 &self.config.memo_prefix,
                 proto_msgs,
+                tracking_id,
             )
             .await
         } else {
             send_batched_messages_and_wait_commit(
                 &self.rpc_client,
                 &self.tx_config,
-                &key_pair,
-                account,
+                &mut signers,
                 &self.config.memo_prefix,
                 proto_msgs,
+                tracking_id,
+                unordered_channel,
             )
             .await
-        }
+        };
+
+        self.next_account = signers.next();
+
+        result
     }
 
     #[instrument(
@@ -705,23 +1114,50 @@ impl CosmosSdkChain {
             }
         );
 
+        let tracking_id = tracked_msgs.tracking_id();
+        let unordered_channel = tracked_msgs.unordered_channel;
         let proto_msgs = tracked_msgs.msgs;
 
-        let key_pair = self.key()?;
-        let key_account = key_pair.account();
+        self.refresh_gas_price().await;
+
+        let key_pairs = self.signing_keys()?;
+
+        let mut signers = SigningAccounts::fetch(
+            &self.grpc_addr,
+            &self.rate_limiter,
+            key_pairs,
+            &mut self.accounts,
+            self.next_account,
+        )
+        .await?;
+
+        if dry_run::is_enabled() {
+            simulate_batched_messages(
+                &self.tx_config,
+                &mut signers,
+                &self.config.memo_prefix,
+                proto_msgs,
+                tracking_id,
+            )
+            .await?;
 
-        let account =
-            get_or_fetch_account(&self.grpc_addr, &key_account, &mut self.account).await?;
+            return Ok(Vec::new());
+        }
 
-        send_batched_messages_and_wait_check_tx(
+        let result = send_batched_messages_and_wait_check_tx(
             &self.rpc_client,
             &self.tx_config,
-            &key_pair,
-            account,
+            &mut signers,
             &self.config.memo_prefix,
             proto_msgs,
+            tracking_id,
+            unordered_channel,
         )
-        .await
+        .await;
+
+        self.next_account = signers.next();
+
+        result
     }
 
     fn query_packet_from_block(
@@ -746,7 +1182,7 @@ impl CosmosSdkChain {
 
         let response = self
             .block_on(self.rpc_client.block_results(tm_height))
-            .map_err(|e| Error::rpc(self.config.rpc_addr.clone(), e))?;
+            .map_err(|e| Error::rpc(self.rpc_addr.clone(), e))?;
 
         let response_height = ICSHeight::new(self.id().version(), u64::from(response.height))
             .map_err(|_| Error::invalid_height_no_source())?;
@@ -809,7 +1245,7 @@ impl CosmosSdkChain {
                     // blocks first.
                     Order::Descending,
                 ))
-                .map_err(|e| Error::rpc(self.config.rpc_addr.clone(), e))?;
+                .map_err(|e| Error::rpc(self.rpc_addr.clone(), e))?;
 
             for block in response.blocks.into_iter().map(|response| response.block) {
                 let response_height =
@@ -848,7 +1284,13 @@ impl ChainEndpoint for CosmosSdkChain {
         let mut rpc_client = HttpClient::new(config.rpc_addr.clone())
             .map_err(|e| Error::rpc(config.rpc_addr.clone(), e))?;
 
-        let node_info = rt.block_on(fetch_node_info(&rpc_client, &config))?;
+        let tx_config = TxConfig::try_from(&config)?;
+
+        // The requests sent while submitting transactions share the rate limits of the queries
+        let rate_limiter = tx_config.rate_limiter.clone();
+
+        let node_info =
+            rt.block_on(rate_limiter.throttle(fetch_node_info(&rpc_client, &config)))?;
 
         let compat_mode = CompatMode::from_version(node_info.version).unwrap_or_else(|e| {
             warn!("Unsupported tendermint version, will use v0.37 compatibility mode but relaying might not work as desired: {e}");
@@ -856,21 +1298,37 @@ impl ChainEndpoint for CosmosSdkChain {
         });
         rpc_client.set_compat_mode(compat_mode);
 
-        let light_client = TmLightClient::from_config(&config, node_info.id)?;
+        let light_block_cache = Arc::new(LightBlockCache::new(
+            config.id.clone(),
+            &config.light_block_cache,
+        ));
+
+        // Compare the latest light block persisted by a previous run with the block
+        // of the full node at the same height, which differ if the chain was restarted.
+        // The cache is cleared as well when the block of the full node cannot be fetched.
+        light_block_cache.validate_persisted(|block| {
+            rt.block_on(rate_limiter.throttle(rpc_client.commit(block.height())))
+                .map(|commit| {
+                    commit.signed_header.header.hash() == block.signed_header.header.hash()
+                })
+                .unwrap_or(false)
+        });
+
+        let light_client =
+            TmLightClient::from_config(&config, node_info.id, light_block_cache.clone())?;
 
         // Initialize key store and load key
-        let keybase = KeyRing::new_secp256k1(
-            config.key_store_type,
-            &config.account_prefix,
-            &config.id,
-            &config.key_store_folder,
-        )
-        .map_err(Error::key_base)?;
+        let keybase =
+            KeyRing::from_config(config.key_store_type, &config).map_err(Error::key_base)?;
 
         let grpc_addr = Uri::from_str(&config.grpc_addr.to_string())
             .map_err(|e| Error::invalid_uri(config.grpc_addr.to_string(), e))?;
 
-        let tx_config = TxConfig::try_from(&config)?;
+        let accounts = vec![None; config.signing_key_names().len()];
+
+        let rpc_addr = config.rpc_addr.clone();
+
+        let failover = Mutex::new(Failover::from_config(&config));
 
         // Retrieve the version specification of this chain
 
@@ -878,13 +1336,19 @@ impl ChainEndpoint for CosmosSdkChain {
             config,
             rpc_client,
             compat_mode,
+            rpc_addr,
             grpc_addr,
             light_client,
+            light_block_cache,
             rt,
             keybase,
             tx_config,
-            account: None,
+            accounts,
+            next_account: 0,
             tx_monitor_cmd: None,
+            failover,
+            gas_price_cache: DynamicGasPriceCache::default(),
+            rate_limiter,
         };
 
         Ok(chain)
@@ -932,6 +1396,8 @@ impl ChainEndpoint for CosmosSdkChain {
     /// Exits early if any health check fails, without doing any
     /// further checks.
     fn health_check(&self) -> Result<HealthCheck, Error> {
+        self.probe_endpoints();
+
         if let Err(e) = do_health_check(self) {
             warn!("Health checkup for chain '{}' failed", self.id());
             warn!("    Reason: {}", e.detail());
@@ -951,6 +1417,27 @@ impl ChainEndpoint for CosmosSdkChain {
         Ok(HealthCheck::Healthy)
     }
 
+    fn check_endpoints(&mut self) -> Result<(), Error> {
+        let needs_probe = self
+            .failover
+            .lock()
+            .expect("poisoned lock")
+            .needs_probe(Instant::now());
+
+        if !needs_probe {
+            return Ok(());
+        }
+
+        self.probe_endpoints();
+
+        let selected = self.failover.lock().expect("poisoned lock").select();
+
+        match selected {
+            Some(node) => self.switch_endpoint(node),
+            None => Ok(()),
+        }
+    }
+
     /// Fetch a header from the chain at the given height and verify it.
     fn verify_header(
         &mut self,
@@ -1007,7 +1494,9 @@ impl ChainEndpoint for CosmosSdkChain {
     ) -> Result<Vec<IbcEventWithHeight>, Error> {
         let runtime = self.rt.clone();
 
-        runtime.block_on(self.do_send_messages_and_wait_commit(tracked_msgs))
+        let result = runtime.block_on(self.do_send_messages_and_wait_commit(tracked_msgs));
+        self.record_endpoint_result(&result);
+        result
     }
 
     fn send_messages_and_wait_check_tx(
@@ -1016,7 +1505,25 @@ impl ChainEndpoint for CosmosSdkChain {
     ) -> Result<Vec<Response>, Error> {
         let runtime = self.rt.clone();
 
-        runtime.block_on(self.do_send_messages_and_wait_check_tx(tracked_msgs))
+        let result = runtime.block_on(self.do_send_messages_and_wait_check_tx(tracked_msgs));
+        self.record_endpoint_result(&result);
+        result
+    }
+
+    fn estimate_fee(&mut self, tracked_msgs: TrackedMsgs) -> Result<Fee, Error> {
+        let runtime = self.rt.clone();
+
+        let result = runtime.block_on(self.do_estimate_fee(tracked_msgs));
+        self.record_endpoint_result(&result);
+        result
+    }
+
+    fn simulate_messages(&mut self, tracked_msgs: TrackedMsgs) -> Result<u64, Error> {
+        let runtime = self.rt.clone();
+
+        let result = runtime.block_on(self.do_simulate_messages(tracked_msgs));
+        self.record_endpoint_result(&result);
+        result
     }
 
     /// Get the account for the signer
@@ -1098,17 +1605,21 @@ impl ChainEndpoint for CosmosSdkChain {
         );
         crate::telemetry!(query, self.id(), "query_application_status");
 
+        let start = Instant::now();
+
         // We cannot rely on `/status` endpoint to provide details about the latest block.
         // Instead, we need to pull block height via `/abci_info` and then fetch block
         // metadata at the given height via `/blockchain` endpoint.
         let abci_info = self
             .block_on(self.rpc_client.abci_info())
-            .map_err(|e| Error::rpc(self.config.rpc_addr.clone(), e))?;
+            .map_err(|e| Error::rpc(self.rpc_addr.clone(), e))?;
 
         // Query `/header` endpoint to pull the latest block that the application committed.
         let response = self
             .block_on(self.rpc_client.header(abci_info.last_block_height))
-            .map_err(|e| Error::rpc(self.config.rpc_addr.clone(), e))?;
+            .map_err(|e| Error::rpc(self.rpc_addr.clone(), e))?;
+
+        self.record_endpoint_success(abci_info.last_block_height.value(), start.elapsed());
 
         let height = ICSHeight::new(
             ChainId::chain_version(response.header.chain_id.as_str()),
@@ -1164,6 +1675,10 @@ impl ChainEndpoint for CosmosSdkChain {
                     })
                     .ok()
             })
+            .map(|c| IdentifiedAnyClientState {
+                client_state: c.client_state.hosted_on(&self.config().id),
+                ..c
+            })
             .collect();
 
         // Sort by client identifier counter
@@ -1190,7 +1705,9 @@ impl ChainEndpoint for CosmosSdkChain {
             request.height,
             matches!(include_proof, IncludeProof::Yes),
         )?;
-        let client_state = AnyClientState::decode_vec(&res.value).map_err(Error::decode)?;
+        let client_state = AnyClientState::decode_vec(&res.value)
+            .map_err(Error::decode)?
+            .hosted_on(&self.config().id);
 
         match include_proof {
             IncludeProof::Yes => {
@@ -1295,7 +1812,13 @@ impl ChainEndpoint for CosmosSdkChain {
 
         let consensus_state = AnyConsensusState::decode_vec(&res.value).map_err(Error::decode)?;
 
-        if !matches!(consensus_state, AnyConsensusState::Tendermint(_)) {
+        // Cosmos SDK chains also host the clients of solo machines, and Wasm clients
+        if !matches!(
+            consensus_state,
+            AnyConsensusState::Tendermint(_)
+                | AnyConsensusState::Solomachine(_)
+                | AnyConsensusState::Wasm(_)
+        ) {
             return Err(Error::consensus_state_type_mismatch(
                 ClientType::Tendermint,
                 consensus_state.client_type(),
@@ -1619,6 +2142,66 @@ impl ChainEndpoint for CosmosSdkChain {
         }
     }
 
+    fn query_upgrade(
+        &self,
+        request: QueryUpgradeRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(Upgrade, Option<MerkleProof>), Error> {
+        crate::time!(
+            "query_upgrade",
+            {
+                "src_chain": self.config().id.to_string(),
+            }
+        );
+        crate::telemetry!(query, self.id(), "query_upgrade");
+
+        let res = self.query(
+            ChannelUpgradesPath(request.port_id, request.channel_id),
+            request.height,
+            matches!(include_proof, IncludeProof::Yes),
+        )?;
+
+        let upgrade = Upgrade::decode_vec(&res.value).map_err(Error::decode)?;
+
+        match include_proof {
+            IncludeProof::Yes => {
+                let proof = res.proof.ok_or_else(Error::empty_response_proof)?;
+                Ok((upgrade, Some(proof)))
+            }
+            IncludeProof::No => Ok((upgrade, None)),
+        }
+    }
+
+    fn query_upgrade_error(
+        &self,
+        request: QueryUpgradeErrorRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(ErrorReceipt, Option<MerkleProof>), Error> {
+        crate::time!(
+            "query_upgrade_error",
+            {
+                "src_chain": self.config().id.to_string(),
+            }
+        );
+        crate::telemetry!(query, self.id(), "query_upgrade_error");
+
+        let res = self.query(
+            ChannelUpgradeErrorsPath(request.port_id, request.channel_id),
+            request.height,
+            matches!(include_proof, IncludeProof::Yes),
+        )?;
+
+        let error_receipt = ErrorReceipt::decode_vec(&res.value).map_err(Error::decode)?;
+
+        match include_proof {
+            IncludeProof::Yes => {
+                let proof = res.proof.ok_or_else(Error::empty_response_proof)?;
+                Ok((error_receipt, Some(proof)))
+            }
+            IncludeProof::No => Ok((error_receipt, None)),
+        }
+    }
+
     fn query_channel_client_state(
         &self,
         request: QueryChannelClientStateRequest,
@@ -1651,7 +2234,11 @@ impl ChainEndpoint for CosmosSdkChain {
 
         let client_state: Option<IdentifiedAnyClientState> = response
             .identified_client_state
-            .map_or_else(|| None, |proto_cs| proto_cs.try_into().ok());
+            .map_or_else(|| None, |proto_cs| proto_cs.try_into().ok())
+            .map(|c: IdentifiedAnyClientState| IdentifiedAnyClientState {
+                client_state: c.client_state.hosted_on(&self.config().id),
+                ..c
+            });
 
         Ok(client_state)
     }
@@ -1974,7 +2561,7 @@ impl ChainEndpoint for CosmosSdkChain {
         self.block_on(query_txs(
             self.id(),
             &self.rpc_client,
-            &self.config.rpc_addr,
+            &self.rpc_addr,
             request,
         ))
     }
@@ -2007,14 +2594,17 @@ impl ChainEndpoint for CosmosSdkChain {
             Qualified::Equal(_) => self.block_on(query_packets_from_block(
                 self.id(),
                 &self.rpc_client,
-                &self.config.rpc_addr,
+                &self.rpc_addr,
                 &request,
             )),
             Qualified::SmallerEqual(_) => {
-                let tx_events = self.block_on(query_packets_from_txs(
+                // Each sequence is queried separately, hence the requests
+                // are rate limited one by one by `query_packets_from_txs`
+                let tx_events = self.rt.block_on(query_packets_from_txs(
                     self.id(),
                     &self.rpc_client,
-                    &self.config.rpc_addr,
+                    &self.rpc_addr,
+                    &self.rate_limiter,
                     &request,
                 ))?;
 
@@ -2085,7 +2675,7 @@ impl ChainEndpoint for CosmosSdkChain {
             })
         };
 
-        let header = header.map_err(|e| Error::rpc(self.config.rpc_addr.clone(), e))?;
+        let header = header.map_err(|e| Error::rpc(self.rpc_addr.clone(), e))?;
         Ok(header.into())
     }
 
@@ -2173,7 +2763,7 @@ impl ChainEndpoint for CosmosSdkChain {
             &self.rpc_client,
             &self.tx_config,
             &key_pair,
-            &mut self.account,
+            &mut self.accounts[0],
             &self.config.memo_prefix,
             channel_id,
             port_id,
@@ -2182,13 +2772,52 @@ impl ChainEndpoint for CosmosSdkChain {
         ))
     }
 
+    fn send_coins(
+        &mut self,
+        from_key_name: &str,
+        to_address: &str,
+        amount: u128,
+        denom: &str,
+    ) -> Result<(), Error> {
+        let key_pair = self
+            .keybase()
+            .get_key(from_key_name)
+            .map_err(|e| Error::key_not_found(from_key_name.to_string(), e))?;
+
+        let message = MsgSend {
+            from_address: key_pair.account(),
+            to_address: to_address.to_string(),
+            amount: vec![Coin {
+                denom: denom.to_string(),
+                amount: amount.to_string(),
+            }],
+        };
+
+        let messages = vec![Any {
+            type_url: send::TYPE_URL.to_string(),
+            value: message.encode_to_vec(),
+        }];
+
+        self.rt.block_on(simple_send_tx(
+            &self.rpc_client,
+            &self.tx_config,
+            &key_pair,
+            messages,
+        ))?;
+
+        Ok(())
+    }
+
     fn cross_chain_query(
         &self,
         requests: Vec<CrossChainQueryRequest>,
     ) -> Result<Vec<CrossChainQueryResponse>, Error> {
         let tasks = requests
             .into_iter()
-            .map(|req| cross_chain_query_via_rpc(&self.rpc_client, req))
+            .map(|req| {
+                self.rate_limiter
+                    .throttle(cross_chain_query_via_rpc(&self.rpc_client, req))
+            })
             .collect::<Vec<_>>();
 
         let joined_tasks = join_all(tasks);
@@ -2209,6 +2838,14 @@ impl ChainEndpoint for CosmosSdkChain {
             self.block_on(query_incentivized_packet(&self.grpc_addr, request))?;
         Ok(incentivized_response)
     }
+
+    fn query_fee_budget(&self) -> Result<Option<FeeBudgetStatus>, Error> {
+        Ok(self
+            .tx_config
+            .fee_budget
+            .as_ref()
+            .map(|fee_budget| fee_budget.status()))
+    }
 }
 
 fn sort_events_by_sequence(events: &mut [IbcEventWithHeight]) {
@@ -2263,7 +2900,7 @@ fn client_id_suffix(client_id: &ClientId) -> Option<u64> {
 fn do_health_check(chain: &CosmosSdkChain) -> Result<(), Error> {
     let chain_id = chain.id();
     let grpc_address = chain.grpc_addr.to_string();
-    let rpc_address = chain.config.rpc_addr.to_string();
+    let rpc_address = chain.rpc_addr.to_string();
 
     chain.block_on(chain.rpc_client.health()).map_err(|e| {
         Error::health_check_json_rpc(
@@ -1,25 +1,33 @@
 use core::mem;
 
+use futures::future::try_join_all;
+use ibc_proto::cosmos::tx::v1beta1::Fee;
 use ibc_proto::google::protobuf::Any;
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 use ibc_relayer_types::events::IbcEvent;
 use ibc_relayer_types::Height;
+use itertools::Itertools;
 use prost::Message;
 use tendermint_rpc::endpoint::broadcast::tx_sync::Response;
 use tendermint_rpc::HttpClient;
-use tracing::debug;
+use tracing::{debug, info};
 
+use crate::audit::{self, TxAuditEntry, TxAuditStatus};
 use crate::chain::cosmos::encode::encoded_tx_metrics;
+use crate::chain::cosmos::estimate::estimate_tx_fees;
 use crate::chain::cosmos::gas::gas_amount_to_fee;
 use crate::chain::cosmos::retry::send_tx_with_account_sequence_retry;
+use crate::chain::cosmos::signer::{are_independent, SigningAccount, SigningAccounts};
 use crate::chain::cosmos::types::account::Account;
 use crate::chain::cosmos::types::config::TxConfig;
 use crate::chain::cosmos::types::tx::{TxStatus, TxSyncResult};
 use crate::chain::cosmos::wait::wait_for_block_commits;
+use crate::chain::tracking::TrackingId;
 use crate::config::types::Memo;
 use crate::error::Error;
 use crate::event::IbcEventWithHeight;
-use crate::keyring::Secp256k1KeyPair;
+use crate::keyring::{Secp256k1KeyPair, SigningKeyPair};
+use crate::util::pretty::PrettyFee;
 
 /**
    Broadcast messages as multiple batched transactions to the chain all at once,
@@ -31,26 +39,42 @@ use crate::keyring::Secp256k1KeyPair;
 pub async fn send_batched_messages_and_wait_commit(
     rpc_client: &HttpClient,
     config: &TxConfig,
-    key_pair: &Secp256k1KeyPair,
-    account: &mut Account,
+    signers: &mut SigningAccounts<'_>,
     tx_memo: &Memo,
     messages: Vec<Any>,
+    tracking_id: TrackingId,
+    unordered_channel: bool,
 ) -> Result<Vec<IbcEventWithHeight>, Error> {
     if messages.is_empty() {
         return Ok(Vec::new());
     }
 
-    let mut tx_sync_results =
-        send_messages_as_batches(rpc_client, config, key_pair, account, tx_memo, messages).await?;
+    let mut tx_sync_results = send_messages_as_batches(
+        rpc_client,
+        config,
+        signers,
+        tx_memo,
+        messages,
+        tracking_id,
+        unordered_channel,
+    )
+    .await?;
 
-    wait_for_block_commits(
+    let result = wait_for_block_commits(
         &config.chain_id,
         rpc_client,
         &config.rpc_address,
+        &config.rate_limiter,
         &config.rpc_timeout,
         &mut tx_sync_results,
     )
-    .await?;
+    .await;
+
+    for tx_sync_result in &tx_sync_results {
+        record_commit(tx_sync_result);
+    }
+
+    result?;
 
     let events = tx_sync_results
         .into_iter()
@@ -68,17 +92,22 @@ pub async fn send_batched_messages_and_wait_commit(
 pub async fn sequential_send_batched_messages_and_wait_commit(
     rpc_client: &HttpClient,
     config: &TxConfig,
-    key_pair: &Secp256k1KeyPair,
-    account: &mut Account,
+    signers: &mut SigningAccounts<'_>,
     tx_memo: &Memo,
     messages: Vec<Any>,
+    tracking_id: TrackingId,
 ) -> Result<Vec<IbcEventWithHeight>, Error> {
     if messages.is_empty() {
         return Ok(Vec::new());
     }
 
     let tx_sync_results = sequential_send_messages_as_batches(
-        rpc_client, config, key_pair, account, tx_memo, messages,
+        rpc_client,
+        config,
+        signers,
+        tx_memo,
+        messages,
+        tracking_id,
     )
     .await?;
 
@@ -93,38 +122,114 @@ pub async fn sequential_send_batched_messages_and_wait_commit(
 pub async fn send_batched_messages_and_wait_check_tx(
     rpc_client: &HttpClient,
     config: &TxConfig,
-    key_pair: &Secp256k1KeyPair,
-    account: &mut Account,
+    signers: &mut SigningAccounts<'_>,
     tx_memo: &Memo,
     messages: Vec<Any>,
+    tracking_id: TrackingId,
+    unordered_channel: bool,
 ) -> Result<Vec<Response>, Error> {
     if messages.is_empty() {
         return Ok(Vec::new());
     }
 
-    let batches = batch_messages(config, key_pair, account, tx_memo, messages)?;
+    let main_account = signers.main_account();
+    let batches = batch_messages(
+        config,
+        &main_account.key_pair,
+        main_account.account,
+        tx_memo,
+        messages,
+    )?;
+
+    // The commit of the transactions is not awaited, so they are recorded again
+    // once the pending transactions processor observes their commit
+    let responses = broadcast_batches(
+        rpc_client,
+        config,
+        signers,
+        tx_memo,
+        batches,
+        tracking_id,
+        unordered_channel,
+    )
+    .await?
+    .into_iter()
+    .map(|broadcast| {
+        if let Some(entry) = broadcast.audit {
+            audit::track_commit(entry);
+        }
 
-    let mut responses = Vec::new();
+        broadcast.response
+    })
+    .collect();
 
-    for batch in batches {
-        let response = send_tx_with_account_sequence_retry(
-            rpc_client, config, key_pair, account, tx_memo, &batch,
+    Ok(responses)
+}
+
+/**
+   Simulate the transactions which would be broadcast for the given messages,
+   and log the estimated fee of each of them, without broadcasting them.
+   This is used in dry-run mode, in place of the functions above.
+*/
+pub async fn simulate_batched_messages(
+    config: &TxConfig,
+    signers: &mut SigningAccounts<'_>,
+    tx_memo: &Memo,
+    messages: Vec<Any>,
+    tracking_id: TrackingId,
+) -> Result<(), Error> {
+    if messages.is_empty() {
+        return Ok(());
+    }
+
+    let main_account = signers.main_account();
+    let batches = batch_messages(
+        config,
+        &main_account.key_pair,
+        main_account.account,
+        tx_memo,
+        messages,
+    )?;
+
+    let batch_count = batches.len();
+
+    for (index, batch) in batches.into_iter().enumerate() {
+        let fee = estimate_tx_fees(
+            config,
+            &main_account.key_pair,
+            main_account.account,
+            tx_memo,
+            &batch,
         )
         .await?;
 
-        responses.push(response);
+        let messages = batch.iter().map(|msg| msg.type_url.as_str()).join(", ");
+
+        info!(
+            chain = %config.chain_id,
+            %tracking_id,
+            signer = %main_account.key_pair.account(),
+            messages = %messages,
+            gas_limit = fee.gas_limit,
+            fee = %PrettyFee(&fee),
+            "dry run: not broadcasting tx {}/{} with {} messages",
+            index + 1,
+            batch_count,
+            batch.len(),
+        );
     }
 
-    Ok(responses)
+    Ok(())
 }
 
 async fn send_messages_as_batches(
     rpc_client: &HttpClient,
     config: &TxConfig,
-    key_pair: &Secp256k1KeyPair,
-    account: &mut Account,
+    signers: &mut SigningAccounts<'_>,
     tx_memo: &Memo,
     messages: Vec<Any>,
+    tracking_id: TrackingId,
+    unordered_channel: bool,
 ) -> Result<Vec<TxSyncResult>, Error> {
     if messages.is_empty() {
         return Ok(Vec::new());
@@ -132,29 +237,35 @@ async fn send_messages_as_batches(
 
     let message_count = messages.len();
 
-    let batches = batch_messages(config, key_pair, account, tx_memo, messages)?;
+    let main_account = signers.main_account();
+    let batches = batch_messages(
+        config,
+        &main_account.key_pair,
+        main_account.account,
+        tx_memo,
+        messages,
+    )?;
 
     debug!(
-        "sending {} messages as {} batches to chain {} in parallel",
+        "sending {} messages as {} batches to chain {}",
         message_count,
         batches.len(),
         config.chain_id
     );
 
-    let mut tx_sync_results = Vec::new();
-
-    for batch in batches {
-        let message_count = batch.len();
-
-        let response = send_tx_with_account_sequence_retry(
-            rpc_client, config, key_pair, account, tx_memo, &batch,
-        )
-        .await?;
-
-        let tx_sync_result = response_to_tx_sync_result(&config.chain_id, message_count, response);
-
-        tx_sync_results.push(tx_sync_result);
-    }
+    let tx_sync_results = broadcast_batches(
+        rpc_client,
+        config,
+        signers,
+        tx_memo,
+        batches,
+        tracking_id,
+        unordered_channel,
+    )
+    .await?
+    .into_iter()
+    .map(|broadcast| response_to_tx_sync_result(&config.chain_id, broadcast))
+    .collect();
 
     Ok(tx_sync_results)
 }
@@ -162,10 +273,10 @@ async fn send_messages_as_batches(
 async fn sequential_send_messages_as_batches(
     rpc_client: &HttpClient,
     config: &TxConfig,
-    key_pair: &Secp256k1KeyPair,
-    account: &mut Account,
+    signers: &mut SigningAccounts<'_>,
     tx_memo: &Memo,
     messages: Vec<Any>,
+    tracking_id: TrackingId,
 ) -> Result<Vec<TxSyncResult>, Error> {
     if messages.is_empty() {
         return Ok(Vec::new());
@@ -173,7 +284,14 @@ async fn sequential_send_messages_as_batches(
 
     let message_count = messages.len();
 
-    let batches = batch_messages(config, key_pair, account, tx_memo, messages)?;
+    let main_account = signers.main_account();
+    let batches = batch_messages(
+        config,
+        &main_account.key_pair,
+        main_account.account,
+        tx_memo,
+        messages,
+    )?;
 
     debug!(
         "sending {} messages as {} batches to chain {} in serial",
@@ -187,33 +305,171 @@ async fn sequential_send_messages_as_batches(
     for batch in batches {
         let message_count = batch.len();
 
-        let response = send_tx_with_account_sequence_retry(
-            rpc_client, config, key_pair, account, tx_memo, &batch,
+        let (index, batch) = signers.select(batch)?;
+        let signer = &mut signers.accounts_mut()[index];
+
+        let (response, fee) = send_tx_with_account_sequence_retry(
+            rpc_client,
+            config,
+            &signer.key_pair,
+            signer.account,
+            tx_memo,
+            &batch,
         )
         .await?;
 
-        let tx_sync_result = response_to_tx_sync_result(&config.chain_id, message_count, response);
+        let broadcast = BroadcastBatch {
+            message_count,
+            audit: record_broadcast(config, tracking_id, signer, &batch, &fee, &response),
+            response,
+        };
 
-        tx_sync_results.push(tx_sync_result);
+        tx_sync_results.push(response_to_tx_sync_result(&config.chain_id, broadcast));
 
-        wait_for_block_commits(
+        let result = wait_for_block_commits(
             &config.chain_id,
             rpc_client,
             &config.rpc_address,
+            &config.rate_limiter,
             &config.rpc_timeout,
             &mut tx_sync_results,
         )
-        .await?;
+        .await;
+
+        if let Some(tx_sync_result) = tx_sync_results.last() {
+            record_commit(tx_sync_result);
+        }
+
+        result?;
     }
 
     Ok(tx_sync_results)
 }
 
-fn response_to_tx_sync_result(
-    chain_id: &ChainId,
+/// A transaction broadcast for a batch of messages
+struct BroadcastBatch {
     message_count: usize,
     response: Response,
-) -> TxSyncResult {
+    /// The entry of the transaction in the audit log, if it is enabled
+    audit: Option<TxAuditEntry>,
+}
+
+/// Broadcast each batch with the account it is assigned to, and return the
+/// transaction broadcast for every batch, in the order of the batches.
+///
+/// The batches are only spread across the accounts, which broadcast their batches
+/// concurrently, if they are independent of each other, see [`are_independent`].
+/// Otherwise they are all signed by the main account and broadcast one after another,
+/// such that the sequence of the account makes the chain include them in order.
+async fn broadcast_batches(
+    rpc_client: &HttpClient,
+    config: &TxConfig,
+    signers: &mut SigningAccounts<'_>,
+    tx_memo: &Memo,
+    batches: Vec<Vec<Any>>,
+    tracking_id: TrackingId,
+    unordered_channel: bool,
+) -> Result<Vec<BroadcastBatch>, Error> {
+    let independent = batches
+        .iter()
+        .all(|batch| are_independent(batch, unordered_channel));
+
+    let assigned = signers.assign(batches, independent)?;
+
+    let broadcasts =
+        signers
+            .accounts_mut()
+            .iter_mut()
+            .zip(assigned)
+            .map(|(signer, batches)| async move {
+                let mut responses = Vec::with_capacity(batches.len());
+
+                for (position, batch) in batches {
+                    let (response, fee) = send_tx_with_account_sequence_retry(
+                        rpc_client,
+                        config,
+                        &signer.key_pair,
+                        signer.account,
+                        tx_memo,
+                        &batch,
+                    )
+                    .await?;
+
+                    let broadcast = BroadcastBatch {
+                        message_count: batch.len(),
+                        audit: record_broadcast(
+                            config,
+                            tracking_id,
+                            signer,
+                            &batch,
+                            &fee,
+                            &response,
+                        ),
+                        response,
+                    };
+
+                    responses.push((position, broadcast));
+                }
+
+                Ok::<_, Error>(responses)
+            });
+
+    let mut responses: Vec<_> = try_join_all(broadcasts)
+        .await?
+        .into_iter()
+        .flatten()
+        .collect();
+
+    responses.sort_by_key(|(position, _)| *position);
+
+    Ok(responses
+        .into_iter()
+        .map(|(_, broadcast)| broadcast)
+        .collect())
+}
+
+/// Record a transaction which was just broadcast in the audit log, if it is enabled,
+/// and return its entry to be recorded again once the transaction is committed
+fn record_broadcast(
+    config: &TxConfig,
+    tracking_id: TrackingId,
+    signer: &SigningAccount<'_>,
+    batch: &[Any],
+    fee: &Fee,
+    response: &Response,
+) -> Option<TxAuditEntry> {
+    audit::is_enabled().then(|| {
+        let entry = TxAuditEntry::new(
+            &config.chain_id,
+            tracking_id,
+            signer.key_pair.account(),
+            batch,
+            fee,
+            response,
+        );
+
+        audit::record(&entry);
+
+        entry
+    })
+}
+
+/// Record the transaction in the audit log again if it was committed
+fn record_commit(tx_sync_result: &TxSyncResult) {
+    if let Some(entry) = &tx_sync_result.audit {
+        if entry.status == TxAuditStatus::Committed {
+            audit::record(entry);
+        }
+    }
+}
+
+fn response_to_tx_sync_result(chain_id: &ChainId, broadcast: BroadcastBatch) -> TxSyncResult {
+    let BroadcastBatch {
+        message_count,
+        response,
+        audit,
+    } = broadcast;
+
     if response.code.is_err() {
         // Note: we don't have any height information in this case. This hack will fix itself
         // once we remove the `ChainError` event (which is not actually an event)
@@ -228,12 +484,14 @@ fn response_to_tx_sync_result(
             response,
             events: events_per_tx,
             status: TxStatus::ReceivedResponse,
+            audit,
         }
     } else {
         TxSyncResult {
             response,
             events: Vec::new(),
             status: TxStatus::Pending { message_count },
+            audit,
         }
     }
 }
@@ -6,6 +6,7 @@ use tracing::{debug, error, span, warn, Level};
 
 use crate::chain::cosmos::encode::sign_tx;
 use crate::chain::cosmos::gas::gas_amount_to_fee;
+use crate::chain::cosmos::rate_limit::RateLimiter;
 use crate::chain::cosmos::simulate::send_tx_simulate;
 use crate::chain::cosmos::types::account::Account;
 use crate::chain::cosmos::types::config::TxConfig;
@@ -24,35 +25,67 @@ pub async fn estimate_tx_fees(
 ) -> Result<Fee, Error> {
     let gas_config = &config.gas_config;
 
-    debug!(
-        "max fee, for use in tx simulation: {}",
-        PrettyFee(&gas_config.max_fee)
-    );
+    let tx = build_simulation_tx(config, key_pair, account, tx_memo, messages)?;
+
+    let estimated_fee = estimate_fee_with_tx(
+        gas_config,
+        &config.grpc_address,
+        &config.rate_limiter,
+        &config.chain_id,
+        tx,
+    )
+    .await?;
+
+    Ok(estimated_fee)
+}
+
+/// Simulate the tx submitting the given messages and return the gas it uses.
+///
+/// Unlike [`estimate_tx_fees`], any simulation failure is propagated to the caller,
+/// even the ones which may not prevent the tx from being accepted once submitted.
+pub async fn simulate_tx_gas(
+    config: &TxConfig,
+    key_pair: &Secp256k1KeyPair,
+    account: &Account,
+    tx_memo: &Memo,
+    messages: &[Any],
+) -> Result<u64, Error> {
+    let tx = build_simulation_tx(config, key_pair, account, tx_memo, messages)?;
 
-    let signed_tx = sign_tx(
-        config,
-        key_pair,
-        account,
-        tx_memo,
-        messages,
-        &gas_config.max_fee,
-    )?;
+    let response = config
+        .rate_limiter
+        .throttle(send_tx_simulate(&config.grpc_address, tx))
+        .await?;
 
-    let tx = Tx {
+    Ok(response
+        .gas_info
+        .map_or(config.gas_config.default_gas, |gas_info| gas_info.gas_used))
+}
+
+fn build_simulation_tx(
+    config: &TxConfig,
+    key_pair: &Secp256k1KeyPair,
+    account: &Account,
+    tx_memo: &Memo,
+    messages: &[Any],
+) -> Result<Tx, Error> {
+    let max_fee = &config.gas_config.max_fee;
+
+    debug!("max fee, for use in tx simulation: {}", PrettyFee(max_fee));
+
+    let signed_tx = sign_tx(config, key_pair, account, tx_memo, messages, max_fee)?;
+
+    Ok(Tx {
         body: Some(signed_tx.body),
         auth_info: Some(signed_tx.auth_info),
         signatures: signed_tx.signatures,
-    };
-
-    let estimated_fee =
-        estimate_fee_with_tx(gas_config, &config.grpc_address, &config.chain_id, tx).await?;
-
-    Ok(estimated_fee)
+    })
 }
 
 async fn estimate_fee_with_tx(
     gas_config: &GasConfig,
     grpc_address: &Uri,
+    rate_limiter: &RateLimiter,
     chain_id: &ChainId,
     tx: Tx,
 ) -> Result<Fee, Error> {
@@ -64,7 +97,7 @@ async fn estimate_fee_with_tx(
             }
 
         );
-        estimate_gas_with_tx(gas_config, grpc_address, tx).await
+        estimate_gas_with_tx(gas_config, grpc_address, rate_limiter, tx).await
     }?;
 
     if estimated_gas > gas_config.max_gas {
@@ -103,9 +136,11 @@ async fn estimate_fee_with_tx(
 async fn estimate_gas_with_tx(
     gas_config: &GasConfig,
     grpc_address: &Uri,
+    rate_limiter: &RateLimiter,
     tx: Tx,
 ) -> Result<u64, Error> {
-    let simulated_gas = send_tx_simulate(grpc_address, tx)
+    let simulated_gas = rate_limiter
+        .throttle(send_tx_simulate(grpc_address, tx))
         .await
         .map(|sr| sr.gas_info);
 
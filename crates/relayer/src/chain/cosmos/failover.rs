@@ -0,0 +1,386 @@
+//! Failover between the full nodes of a chain.
+//!
+//! The health of each node is tracked from its latest height, the latency of its
+//! responses and its recent error rate. The chain keeps using the same node for as
+//! long as it is healthy, and switches to the healthiest of the other nodes otherwise.
+
+use core::cmp::Ordering;
+use core::time::Duration;
+use std::time::Instant;
+
+use tendermint_rpc::{Url, WebSocketClientUrl};
+
+use crate::chain::cosmos::rate_limit::ThrottleError;
+use crate::config::{ChainConfig, EventSourceMode};
+use crate::error::{Error, ErrorDetail};
+
+/// How many blocks a node can lag behind the most advanced node
+/// before it is considered unhealthy
+pub const MAX_BLOCK_LAG: u64 = 5;
+
+/// Error rate above which a node is considered unhealthy
+const MAX_ERROR_RATE: f64 = 0.5;
+
+/// Weight of the latest observation in the moving averages
+/// of the latency and error rate of a node
+const SMOOTHING: f64 = 0.3;
+
+/// How often all the nodes are probed while the node in use is healthy
+pub const PROBE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// How long to wait for a node to respond to a probe, so that a single
+/// unresponsive node does not delay the probing of the others
+pub const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Errors which may signal that the full node in use is unreachable or failing,
+/// as opposed to errors reported by the application for a well-formed request
+pub trait EndpointError {
+    fn is_endpoint_failure(&self) -> bool;
+}
+
+impl EndpointError for tonic::Status {
+    fn is_endpoint_failure(&self) -> bool {
+        matches!(
+            self.code(),
+            tonic::Code::Unavailable | tonic::Code::DeadlineExceeded | tonic::Code::Unknown
+        ) && !self.is_throttled()
+    }
+}
+
+impl EndpointError for tonic::transport::Error {
+    fn is_endpoint_failure(&self) -> bool {
+        true
+    }
+}
+
+impl EndpointError for tendermint_rpc::Error {
+    fn is_endpoint_failure(&self) -> bool {
+        // JSON-RPC errors are responses of a reachable node to an invalid request
+        !matches!(
+            self.detail(),
+            tendermint_rpc::error::ErrorDetail::Response(_)
+        )
+    }
+}
+
+impl EndpointError for Error {
+    fn is_endpoint_failure(&self) -> bool {
+        match self.detail() {
+            ErrorDetail::Rpc(e) => e.source.is_endpoint_failure(),
+            ErrorDetail::GrpcStatus(e) => e.status.is_endpoint_failure(),
+            ErrorDetail::GrpcTransport(_) | ErrorDetail::WebSocket(_) => true,
+            _ => false,
+        }
+    }
+}
+
+/// The endpoints of a full node of the chain
+#[derive(Clone, Debug, PartialEq)]
+pub struct NodeEndpoints {
+    pub rpc_addr: Url,
+    pub grpc_addr: Url,
+
+    /// Only set when the chain uses a push-based event source
+    pub websocket_addr: Option<WebSocketClientUrl>,
+}
+
+/// The observed health of a full node
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct NodeHealth {
+    pub latest_height: Option<u64>,
+    pub latency: Option<Duration>,
+    pub error_rate: f64,
+}
+
+impl NodeHealth {
+    /// Record a successful response of the node
+    pub fn record_success(&mut self, latest_height: u64, latency: Duration) {
+        self.latest_height = Some(latest_height);
+
+        self.latency = Some(match self.latency {
+            Some(average) => average.mul_f64(1.0 - SMOOTHING) + latency.mul_f64(SMOOTHING),
+            None => latency,
+        });
+
+        self.error_rate *= 1.0 - SMOOTHING;
+    }
+
+    /// Record a failed request to the node
+    pub fn record_error(&mut self) {
+        self.error_rate = self.error_rate * (1.0 - SMOOTHING) + SMOOTHING;
+    }
+
+    /// How many blocks the node lags behind the given height,
+    /// or `None` if the latest height of the node is unknown
+    fn lag(&self, max_height: u64) -> Option<u64> {
+        self.latest_height
+            .map(|height| max_height.saturating_sub(height))
+    }
+
+    fn is_healthy(&self, max_height: u64) -> bool {
+        self.error_rate < MAX_ERROR_RATE
+            && self
+                .lag(max_height)
+                .map_or(false, |lag| lag <= MAX_BLOCK_LAG)
+    }
+
+    /// Compare the health of two nodes, the healthiest node being the smallest
+    fn cmp(&self, other: &Self, max_height: u64) -> Ordering {
+        self.lag(max_height)
+            .cmp(&other.lag(max_height))
+            .then(self.error_rate.total_cmp(&other.error_rate))
+            .then(self.latency.cmp(&other.latency))
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Node {
+    endpoints: NodeEndpoints,
+    health: NodeHealth,
+}
+
+/// Tracks the health of the full nodes of a chain, and selects the node to use
+#[derive(Clone, Debug)]
+pub struct Failover {
+    nodes: Vec<Node>,
+    active: usize,
+    last_probe: Option<Instant>,
+}
+
+impl Failover {
+    /// Track the nodes of the given chain, starting with the primary node,
+    /// followed by the fallback nodes in the order they are configured.
+    pub fn from_config(config: &ChainConfig) -> Self {
+        let push_mode = matches!(config.event_source, EventSourceMode::Push { .. });
+
+        let primary = NodeEndpoints {
+            rpc_addr: config.rpc_addr.clone(),
+            grpc_addr: config.grpc_addr.clone(),
+            websocket_addr: match &config.event_source {
+                EventSourceMode::Push { url, .. } => Some(url.clone()),
+                EventSourceMode::Pull { .. } => None,
+            },
+        };
+
+        let fallbacks = config.fallback_endpoints.iter().map(|e| NodeEndpoints {
+            rpc_addr: e.rpc_addr.clone(),
+            grpc_addr: e.grpc_addr.clone(),
+            websocket_addr: if push_mode {
+                e.websocket_addr
+                    .clone()
+                    .or_else(|| websocket_addr_for(&e.rpc_addr))
+            } else {
+                None
+            },
+        });
+
+        let nodes = core::iter::once(primary)
+            .chain(fallbacks)
+            .map(|endpoints| Node {
+                endpoints,
+                health: NodeHealth::default(),
+            })
+            .collect();
+
+        Self {
+            nodes,
+            active: 0,
+            last_probe: None,
+        }
+    }
+
+    /// Whether there is any fallback node to switch to
+    pub fn is_enabled(&self) -> bool {
+        self.nodes.len() > 1
+    }
+
+    /// The endpoints of all the nodes, starting with the primary node
+    pub fn nodes(&self) -> impl Iterator<Item = &NodeEndpoints> {
+        self.nodes.iter().map(|node| &node.endpoints)
+    }
+
+    /// The index of the node in use
+    pub fn active(&self) -> usize {
+        self.active
+    }
+
+    pub fn endpoints(&self, node: usize) -> &NodeEndpoints {
+        &self.nodes[node].endpoints
+    }
+
+    pub fn health(&self, node: usize) -> &NodeHealth {
+        &self.nodes[node].health
+    }
+
+    pub fn record_success(&mut self, node: usize, latest_height: u64, latency: Duration) {
+        self.nodes[node]
+            .health
+            .record_success(latest_height, latency);
+    }
+
+    pub fn record_error(&mut self, node: usize) {
+        self.nodes[node].health.record_error();
+    }
+
+    /// Record that all the nodes were probed at the given time
+    pub fn probed(&mut self, now: Instant) {
+        self.last_probe = Some(now);
+    }
+
+    /// Whether the nodes must be probed again, either because they were last probed
+    /// too long ago, or because the node in use is not healthy anymore
+    pub fn needs_probe(&self, now: Instant) -> bool {
+        if !self.is_enabled() {
+            return false;
+        }
+
+        let probe_due = self.last_probe.map_or(true, |last_probe| {
+            now.duration_since(last_probe) >= PROBE_INTERVAL
+        });
+
+        probe_due || !self.nodes[self.active].health.is_healthy(self.max_height())
+    }
+
+    /// The node to switch to, if the node in use is unhealthy and another node is healthy
+    pub fn select(&self) -> Option<usize> {
+        let max_height = self.max_height();
+
+        if self.nodes[self.active].health.is_healthy(max_height) {
+            return None;
+        }
+
+        self.nodes
+            .iter()
+            .enumerate()
+            .filter(|(index, node)| *index != self.active && node.health.is_healthy(max_height))
+            .min_by(|(_, a), (_, b)| a.health.cmp(&b.health, max_height))
+            .map(|(index, _)| index)
+    }
+
+    pub fn switch_to(&mut self, node: usize) {
+        self.active = node;
+    }
+
+    fn max_height(&self) -> u64 {
+        self.nodes
+            .iter()
+            .filter_map(|node| node.health.latest_height)
+            .max()
+            .unwrap_or_default()
+    }
+}
+
+/// The WebSocket address of a node, assuming it is served
+/// at the `/websocket` path of its RPC address
+fn websocket_addr_for(rpc_addr: &Url) -> Option<WebSocketClientUrl> {
+    let rpc_addr = rpc_addr.to_string();
+
+    let websocket_addr = match rpc_addr.split_once("://") {
+        Some(("https", rest)) => format!("wss://{}/websocket", rest.trim_end_matches('/')),
+        Some(("http", rest)) => format!("ws://{}/websocket", rest.trim_end_matches('/')),
+        _ => return None,
+    };
+
+    websocket_addr.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::config::{load, FallbackEndpoint};
+
+    fn config(fallbacks: usize) -> ChainConfig {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/config/fixtures/relayer_conf_example.toml"
+        );
+
+        let mut config = load(path).unwrap().chains.remove(0);
+
+        config.fallback_endpoints = (0..fallbacks)
+            .map(|i| FallbackEndpoint {
+                rpc_addr: format!("http://127.0.0.{}:26657", i + 2).parse().unwrap(),
+                grpc_addr: format!("http://127.0.0.{}:9090", i + 2).parse().unwrap(),
+                websocket_addr: None,
+            })
+            .collect();
+
+        config
+    }
+
+    fn probe(failover: &mut Failover, heights: &[u64]) {
+        for (node, height) in heights.iter().enumerate() {
+            failover.record_success(node, *height, Duration::from_millis(100));
+        }
+    }
+
+    #[test]
+    fn disabled_without_fallbacks() {
+        let failover = Failover::from_config(&config(0));
+
+        assert!(!failover.is_enabled());
+        assert!(!failover.needs_probe(Instant::now()));
+    }
+
+    #[test]
+    fn fallback_websocket_addr_is_derived() {
+        let failover = Failover::from_config(&config(1));
+
+        assert_eq!(
+            failover.endpoints(1).websocket_addr,
+            Some("ws://127.0.0.2:26657/websocket".parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn keeps_healthy_node() {
+        let mut failover = Failover::from_config(&config(2));
+
+        probe(&mut failover, &[100, 101, 102]);
+
+        assert_eq!(failover.select(), None);
+    }
+
+    #[test]
+    fn switches_from_lagging_node() {
+        let mut failover = Failover::from_config(&config(2));
+
+        probe(&mut failover, &[90, 99, 100]);
+
+        assert_eq!(failover.select(), Some(2));
+    }
+
+    #[test]
+    fn switches_from_failing_node() {
+        let mut failover = Failover::from_config(&config(2));
+
+        probe(&mut failover, &[100, 100, 100]);
+
+        failover.probed(Instant::now());
+
+        // A single error does not make the node unhealthy
+        failover.record_error(0);
+        assert!(!failover.needs_probe(Instant::now()));
+        assert_eq!(failover.select(), None);
+
+        failover.record_error(0);
+        assert!(failover.needs_probe(Instant::now()));
+
+        // Both fallbacks are at the same height, prefer the one with the fewest errors
+        failover.record_error(1);
+        assert_eq!(failover.select(), Some(2));
+    }
+
+    #[test]
+    fn stays_when_no_node_is_healthy() {
+        let mut failover = Failover::from_config(&config(1));
+
+        for _ in 0..3 {
+            failover.record_error(0);
+            failover.record_error(1);
+        }
+
+        assert_eq!(failover.select(), None);
+    }
+}
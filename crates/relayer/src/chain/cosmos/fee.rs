@@ -3,12 +3,14 @@ use ibc_relayer_types::core::ics24_host::identifier::{ChannelId, PortId};
 use ibc_relayer_types::signer::Signer;
 use tendermint_rpc::HttpClient;
 
+use crate::audit::{self, TxAuditEntry};
 use crate::chain::cosmos::query::account::get_or_fetch_account;
 use crate::chain::cosmos::query::fee::query_counterparty_payee;
 use crate::chain::cosmos::retry::send_tx_with_account_sequence_retry;
 use crate::chain::cosmos::types::account::Account;
 use crate::chain::cosmos::types::config::TxConfig;
 use crate::chain::cosmos::wait::wait_tx_succeed;
+use crate::chain::tracking::TrackingId;
 use crate::config::types::Memo;
 use crate::error::Error;
 use crate::keyring::{Secp256k1KeyPair, SigningKeyPair};
@@ -26,10 +28,22 @@ pub async fn maybe_register_counterparty_payee(
     counterparty_payee: &Signer,
 ) -> Result<(), Error> {
     let key_account = key_pair.account();
-    let account = get_or_fetch_account(&tx_config.grpc_address, &key_account, m_account).await?;
+    let account = get_or_fetch_account(
+        &tx_config.grpc_address,
+        &tx_config.rate_limiter,
+        &key_account,
+        m_account,
+    )
+    .await?;
 
-    let current_counterparty_payee =
-        query_counterparty_payee(&tx_config.grpc_address, channel_id, address).await?;
+    let current_counterparty_payee = tx_config
+        .rate_limiter
+        .throttle(query_counterparty_payee(
+            &tx_config.grpc_address,
+            channel_id,
+            address,
+        ))
+        .await?;
 
     match &current_counterparty_payee {
         Some(current_counterparty_payee)
@@ -38,31 +52,49 @@ pub async fn maybe_register_counterparty_payee(
             Ok(())
         }
         _ => {
-            let message = build_register_counterparty_payee_message(
+            let messages = [build_register_counterparty_payee_message(
                 address,
                 counterparty_payee,
                 channel_id,
                 port_id,
             )
-            .map_err(Error::ics29)?;
+            .map_err(Error::ics29)?];
 
-            let response = send_tx_with_account_sequence_retry(
-                rpc_client,
-                tx_config,
-                key_pair,
-                account,
-                tx_memo,
-                &[message],
+            let (response, fee) = send_tx_with_account_sequence_retry(
+                rpc_client, tx_config, key_pair, account, tx_memo, &messages,
             )
             .await?;
 
-            wait_tx_succeed(
+            let mut audit_entry = audit::is_enabled().then(|| {
+                let entry = TxAuditEntry::new(
+                    &tx_config.chain_id,
+                    TrackingId::new_static("register counterparty payee"),
+                    key_account,
+                    &messages,
+                    &fee,
+                    &response,
+                );
+
+                audit::record(&entry);
+
+                entry
+            });
+
+            let result = wait_tx_succeed(
                 rpc_client,
                 &tx_config.rpc_address,
+                &tx_config.rate_limiter,
                 &tx_config.rpc_timeout,
                 &response.hash,
             )
-            .await?;
+            .await;
+
+            if let (Some(entry), Ok(committed)) = (&mut audit_entry, &result) {
+                entry.set_committed(committed);
+                audit::record(entry);
+            }
+
+            result?;
 
             Ok(())
         }
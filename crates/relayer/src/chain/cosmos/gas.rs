@@ -5,7 +5,7 @@ use num_bigint::BigInt;
 use num_rational::BigRational;
 
 use crate::chain::cosmos::types::gas::GasConfig;
-use crate::config::GasPrice;
+use crate::config::{DynamicGasPrice, GasPrice};
 
 pub fn gas_amount_to_fee(config: &GasConfig, gas_amount: u64) -> Fee {
     let adjusted_gas_limit = adjust_estimated_gas(AdjustGas {
@@ -34,6 +34,32 @@ pub fn calculate_fee(adjusted_gas_amount: u64, gas_price: &GasPrice) -> Coin {
     }
 }
 
+/// The gas price to pay given the base fee of the chain's fee market,
+/// multiplied by the configured factor and bounded by the configured maximum
+pub fn dynamic_gas_price(base_fee: f64, config: &DynamicGasPrice) -> f64 {
+    (base_fee * config.multiplier.to_f64()).min(config.max)
+}
+
+/// The gas price derived from the base fee of the chain's fee market,
+/// cached for the block at which the base fee was queried
+#[derive(Clone, Debug, Default)]
+pub struct DynamicGasPriceCache {
+    cached: Option<(u64, f64)>,
+}
+
+impl DynamicGasPriceCache {
+    /// The gas price derived at the given height, if any
+    pub fn get(&self, height: u64) -> Option<f64> {
+        self.cached
+            .filter(|(cached_height, _)| *cached_height == height)
+            .map(|(_, price)| price)
+    }
+
+    pub fn set(&mut self, height: u64, price: f64) {
+        self.cached = Some((height, price));
+    }
+}
+
 /// Multiply `a` with `f` and round the result up to the nearest integer.
 pub fn mul_ceil(a: u64, f: f64) -> BigInt {
     assert!(f.is_finite());
@@ -98,7 +124,10 @@ fn adjust_estimated_gas(
 
 #[cfg(test)]
 mod tests {
-    use super::{adjust_estimated_gas, AdjustGas};
+    use super::{adjust_estimated_gas, dynamic_gas_price, AdjustGas, DynamicGasPriceCache};
+
+    use crate::config::gas_multiplier::GasMultiplier;
+    use crate::config::DynamicGasPrice;
 
     #[test]
     fn adjust_zero_gas() {
@@ -154,4 +183,25 @@ mod tests {
 
         assert_eq!(adjusted_gas, u64::MAX);
     }
+
+    #[test]
+    fn dynamic_gas_price_is_bounded() {
+        let config = DynamicGasPrice {
+            multiplier: GasMultiplier::new(1.5).unwrap(),
+            max: 0.1,
+        };
+
+        assert_eq!(dynamic_gas_price(0.04, &config), 0.06);
+        assert_eq!(dynamic_gas_price(0.08, &config), 0.1);
+    }
+
+    #[test]
+    fn dynamic_gas_price_is_cached_per_block() {
+        let mut cache = DynamicGasPriceCache::default();
+        assert_eq!(cache.get(10), None);
+
+        cache.set(10, 0.025);
+        assert_eq!(cache.get(10), Some(0.025));
+        assert_eq!(cache.get(11), None);
+    }
 }
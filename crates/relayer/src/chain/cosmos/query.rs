@@ -20,6 +20,7 @@ pub mod consensus_state;
 pub mod custom;
 pub mod denom_trace;
 pub mod fee;
+pub mod fee_market;
 pub mod status;
 pub mod tx;
 
@@ -4,6 +4,7 @@ use ibc_proto::cosmos::auth::v1beta1::{BaseAccount, EthAccount, QueryAccountRequ
 use prost::Message;
 use tracing::info;
 
+use crate::chain::cosmos::rate_limit::RateLimiter;
 use crate::chain::cosmos::types::account::Account;
 use crate::config::default::max_grpc_decoding_size;
 use crate::error::Error;
@@ -12,14 +13,17 @@ use crate::error::Error;
 /// Otherwise query for the account information, update the `Option` to `Some`,
 /// and return the underlying `&mut` reference.
 pub async fn get_or_fetch_account<'a>(
-    grpc_address: &'a Uri,
-    account_address: &'a str,
+    grpc_address: &Uri,
+    rate_limiter: &RateLimiter,
+    account_address: &str,
     m_account: &'a mut Option<Account>,
 ) -> Result<&'a mut Account, Error> {
     match m_account {
         Some(account) => Ok(account),
         None => {
-            let account = query_account(grpc_address, account_address).await?;
+            let account = rate_limiter
+                .throttle(query_account(grpc_address, account_address))
+                .await?;
             *m_account = Some(account.into());
 
             Ok(m_account
@@ -33,10 +37,13 @@ pub async fn get_or_fetch_account<'a>(
 /// account and updating the `&mut` reference.
 pub async fn refresh_account<'a>(
     grpc_address: &Uri,
+    rate_limiter: &RateLimiter,
     account_address: &str,
     m_account: &'a mut Account,
 ) -> Result<(), Error> {
-    let account = query_account(grpc_address, account_address).await?;
+    let account = rate_limiter
+        .throttle(query_account(grpc_address, account_address))
+        .await?;
 
     info!(
         sequence = %account.sequence,
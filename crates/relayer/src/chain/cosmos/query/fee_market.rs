@@ -0,0 +1,145 @@
+//! Queries of the base fee of the fee market modules deployed on Cosmos chains,
+//! namely the `feemarket` module and the Osmosis `txfees` module.
+
+use ibc_proto::cosmos::base::v1beta1::DecCoin;
+use prost::Message;
+use tendermint_rpc::{Client, HttpClient, Url};
+use tracing::debug;
+
+use crate::chain::cosmos::rate_limit::RateLimiter;
+use crate::error::Error;
+
+const FEEMARKET_GAS_PRICE_PATH: &str = "/feemarket.feemarket.v1.Query/GasPrice";
+const OSMOSIS_EIP_BASE_FEE_PATH: &str = "/osmosis.txfees.v1beta1.Query/GetEipBaseFee";
+
+/// `sdk.Dec` values are encoded in protobuf as integers scaled by 10^18
+const DEC_SCALE: f64 = 1e18;
+
+#[derive(Clone, PartialEq, Message)]
+struct GasPriceRequest {
+    #[prost(string, tag = "1")]
+    denom: String,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct GasPriceResponse {
+    #[prost(message, optional, tag = "1")]
+    price: Option<DecCoin>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct QueryEipBaseFeeRequest {}
+
+#[derive(Clone, PartialEq, Message)]
+struct QueryEipBaseFeeResponse {
+    #[prost(string, tag = "1")]
+    base_fee: String,
+}
+
+/// Query the current base fee of the chain, in the given denomination,
+/// from the `feemarket` module, or from the Osmosis `txfees` module
+/// if the chain does not run the former.
+pub async fn query_base_fee(
+    rpc_client: &HttpClient,
+    rpc_address: &Url,
+    rate_limiter: &RateLimiter,
+    denom: &str,
+) -> Result<f64, Error> {
+    match rate_limiter
+        .throttle(query_feemarket_gas_price(rpc_client, rpc_address, denom))
+        .await
+    {
+        Ok(base_fee) => Ok(base_fee),
+        Err(e) => {
+            debug!("failed to query the base fee from the `feemarket` module, trying the Osmosis `txfees` module: {e}");
+
+            rate_limiter
+                .throttle(query_osmosis_base_fee(rpc_client, rpc_address))
+                .await
+        }
+    }
+}
+
+async fn query_feemarket_gas_price(
+    rpc_client: &HttpClient,
+    rpc_address: &Url,
+    denom: &str,
+) -> Result<f64, Error> {
+    let request = GasPriceRequest {
+        denom: denom.to_string(),
+    };
+
+    let response: GasPriceResponse =
+        query(rpc_client, rpc_address, FEEMARKET_GAS_PRICE_PATH, request).await?;
+
+    let price = response
+        .price
+        .filter(|price| price.denom == denom)
+        .ok_or_else(|| Error::invalid_base_fee(format!("no gas price in denom {denom}")))?;
+
+    parse_dec(&price.amount)
+}
+
+async fn query_osmosis_base_fee(rpc_client: &HttpClient, rpc_address: &Url) -> Result<f64, Error> {
+    let response: QueryEipBaseFeeResponse = query(
+        rpc_client,
+        rpc_address,
+        OSMOSIS_EIP_BASE_FEE_PATH,
+        QueryEipBaseFeeRequest {},
+    )
+    .await?;
+
+    parse_dec(&response.base_fee)
+}
+
+async fn query<Request, Response>(
+    rpc_client: &HttpClient,
+    rpc_address: &Url,
+    path: &str,
+    request: Request,
+) -> Result<Response, Error>
+where
+    Request: Message,
+    Response: Message + Default,
+{
+    let response = rpc_client
+        .abci_query(Some(path.to_string()), request.encode_to_vec(), None, false)
+        .await
+        .map_err(|e| Error::rpc(rpc_address.clone(), e))?;
+
+    if !response.code.is_ok() {
+        return Err(Error::abci_query(response));
+    }
+
+    Response::decode(response.value.as_slice())
+        .map_err(|e| Error::protobuf_decode(path.to_string(), e))
+}
+
+/// Parse an `sdk.Dec`, which is encoded either as a decimal number,
+/// or as an integer scaled by 10^18.
+fn parse_dec(value: &str) -> Result<f64, Error> {
+    let parsed = if value.contains('.') {
+        value.parse::<f64>()
+    } else {
+        value.parse::<f64>().map(|value| value / DEC_SCALE)
+    };
+
+    parsed
+        .ok()
+        .filter(|value| value.is_finite() && *value >= 0.0)
+        .ok_or_else(|| Error::invalid_base_fee(value.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_dec;
+
+    #[test]
+    fn parse_scaled_and_decimal_base_fees() {
+        assert_eq!(parse_dec("25000000000000000").unwrap(), 0.025);
+        assert_eq!(parse_dec("0.0025").unwrap(), 0.0025);
+
+        assert!(parse_dec("").is_err());
+        assert!(parse_dec("-1.0").is_err());
+    }
+}
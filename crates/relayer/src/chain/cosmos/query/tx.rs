@@ -9,7 +9,9 @@ use tendermint_rpc::endpoint::tx::Response as TxResponse;
 use tendermint_rpc::{Client, HttpClient, Order, Url};
 use tracing::warn;
 
+use crate::audit;
 use crate::chain::cosmos::query::{header_query, packet_query, tx_hash_query};
+use crate::chain::cosmos::rate_limit::RateLimiter;
 use crate::chain::cosmos::types::events;
 use crate::chain::requests::{
     QueryClientEventRequest, QueryHeight, QueryPacketEventDataRequest, QueryTxHash, QueryTxRequest,
@@ -90,6 +92,10 @@ pub async fn query_txs(
                 Ok(vec![])
             } else {
                 let tx = response.txs.remove(0);
+
+                // Pending transactions are queried by hash until they are committed
+                audit::record_committed(chain_id, &tx);
+
                 Ok(all_ibc_events_from_tx_search_response(chain_id, tx))
             }
         }
@@ -110,6 +116,7 @@ pub async fn query_packets_from_txs(
     chain_id: &ChainId,
     rpc_client: &HttpClient,
     rpc_address: &Url,
+    rate_limiter: &RateLimiter,
     request: &QueryPacketEventDataRequest,
 ) -> Result<Vec<IbcEventWithHeight>, Error> {
     crate::time!(
@@ -124,8 +131,14 @@ pub async fn query_packets_from_txs(
 
     for seq in &request.sequences {
         // Query the latest 10 txs which include the event specified in the query request
-        let response = rpc_client
-            .tx_search(packet_query(request, *seq), false, 1, 10, Order::Descending)
+        let response = rate_limiter
+            .throttle(rpc_client.tx_search(
+                packet_query(request, *seq),
+                false,
+                1,
+                10,
+                Order::Descending,
+            ))
             .await
             .map_err(|e| Error::rpc(rpc_address.clone(), e))?;
 
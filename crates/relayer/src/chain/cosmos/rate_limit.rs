@@ -0,0 +1,285 @@
+//! Rate limiting of the requests sent to the full node of a chain.
+//!
+//! Requests are spaced out to stay below the `rate_limit` configured for the chain,
+//! if any. Whenever the full node reports that it is rate limiting Hermes, all the
+//! requests to that node are paused, for a duration which doubles on every
+//! consecutive rejection, until the node accepts requests again.
+
+use core::future::Future;
+use core::time::Duration;
+use std::sync::Mutex;
+use std::time::Instant;
+
+use tokio::sync::Semaphore;
+use tracing::warn;
+
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+use crate::config::RateLimitConfig;
+use crate::error::{Error, ErrorDetail};
+use crate::telemetry;
+
+/// How long requests are paused the first time the full node rate limits Hermes
+const MIN_BACKOFF: Duration = Duration::from_secs(1);
+
+/// The longest requests are paused when the full node keeps rate limiting Hermes
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Errors which may signal that the full node rejected a request
+/// because it rate limits Hermes
+pub trait ThrottleError {
+    fn is_throttled(&self) -> bool;
+}
+
+impl ThrottleError for tonic::Status {
+    fn is_throttled(&self) -> bool {
+        // A gRPC server reports `ResourceExhausted`, while an HTTP 429 response,
+        // e.g. from a proxy in front of the node, is mapped to `Unavailable` by tonic
+        match self.code() {
+            tonic::Code::ResourceExhausted => true,
+            tonic::Code::Unavailable => self.message().contains("429"),
+            _ => false,
+        }
+    }
+}
+
+impl ThrottleError for tonic::transport::Error {
+    fn is_throttled(&self) -> bool {
+        false
+    }
+}
+
+impl ThrottleError for tendermint_rpc::Error {
+    fn is_throttled(&self) -> bool {
+        self.detail().is_throttled()
+    }
+}
+
+impl ThrottleError for tendermint_rpc::error::ErrorDetail {
+    fn is_throttled(&self) -> bool {
+        // The RPC client does not expose the HTTP status of the responses, hence
+        // only the rejections reported as JSON-RPC errors can be recognized
+        match self {
+            tendermint_rpc::error::ErrorDetail::Response(e) => {
+                let error = &e.source;
+
+                i32::from(error.code()) == 429
+                    || [Some(error.message()), error.data()]
+                        .into_iter()
+                        .flatten()
+                        .any(is_rate_limit_message)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl ThrottleError for Error {
+    fn is_throttled(&self) -> bool {
+        match self.detail() {
+            ErrorDetail::Rpc(e) => e.source.is_throttled(),
+            ErrorDetail::GrpcStatus(e) => e.status.is_throttled(),
+            _ => false,
+        }
+    }
+}
+
+fn is_rate_limit_message(message: &str) -> bool {
+    let message = message.to_lowercase();
+    message.contains("too many requests") || message.contains("rate limit")
+}
+
+#[derive(Debug)]
+struct State {
+    /// Number of requests which can be sent right away
+    tokens: f64,
+    last_refill: Instant,
+
+    /// How long requests are paused the next time the node rate limits Hermes
+    backoff: Duration,
+    paused_until: Option<Instant>,
+}
+
+/// Limits the requests sent to a full node, both to the configured rate limits
+/// and to the rate limits enforced by the node.
+#[derive(Debug)]
+pub struct RateLimiter {
+    chain_id: ChainId,
+    requests_per_second: Option<f64>,
+    concurrency: Option<Semaphore>,
+    state: Mutex<State>,
+}
+
+impl RateLimiter {
+    pub fn new(chain_id: ChainId, config: Option<&RateLimitConfig>) -> Self {
+        let requests_per_second = config
+            .and_then(|config| config.max_requests_per_second)
+            .map(|max| f64::from(max.get()));
+
+        let concurrency = config
+            .and_then(|config| config.max_concurrent_requests)
+            .map(|max| Semaphore::new(max.get()));
+
+        Self {
+            chain_id,
+            requests_per_second,
+            concurrency,
+            state: Mutex::new(State {
+                tokens: requests_per_second.unwrap_or_default(),
+                last_refill: Instant::now(),
+                backoff: MIN_BACKOFF,
+                paused_until: None,
+            }),
+        }
+    }
+
+    /// Send a request once the rate limits allow it, and pause the subsequent
+    /// requests if the full node rejects it because it rate limits Hermes
+    pub async fn throttle<T, E, F>(&self, request: F) -> Result<T, E>
+    where
+        E: ThrottleError,
+        F: Future<Output = Result<T, E>>,
+    {
+        let _permit = match &self.concurrency {
+            Some(semaphore) => Some(
+                semaphore
+                    .acquire()
+                    .await
+                    .expect("the semaphore is never closed"),
+            ),
+            None => None,
+        };
+
+        let mut delayed = false;
+
+        while let Some(delay) = self.try_acquire(Instant::now()) {
+            if !delayed {
+                telemetry!(throttled_query, &self.chain_id, "rate_limit");
+                delayed = true;
+            }
+
+            tokio::time::sleep(delay).await;
+        }
+
+        let result = request.await;
+
+        match &result {
+            Err(e) if e.is_throttled() => self.back_off(Instant::now()),
+            _ => self.state.lock().expect("poisoned lock").backoff = MIN_BACKOFF,
+        }
+
+        result
+    }
+
+    /// Take the right to send a request now, or return how long to wait before trying again
+    fn try_acquire(&self, now: Instant) -> Option<Duration> {
+        let mut state = self.state.lock().expect("poisoned lock");
+
+        if let Some(paused_until) = state.paused_until {
+            if paused_until > now {
+                return Some(paused_until - now);
+            }
+
+            state.paused_until = None;
+        }
+
+        let requests_per_second = self.requests_per_second?;
+
+        // Allow bursts of up to one second worth of requests
+        let elapsed = now.saturating_duration_since(state.last_refill);
+        state.tokens =
+            (state.tokens + elapsed.as_secs_f64() * requests_per_second).min(requests_per_second);
+        state.last_refill = now;
+
+        if state.tokens >= 1.0 {
+            state.tokens -= 1.0;
+            None
+        } else {
+            Some(Duration::from_secs_f64(
+                (1.0 - state.tokens) / requests_per_second,
+            ))
+        }
+    }
+
+    fn back_off(&self, now: Instant) {
+        let mut state = self.state.lock().expect("poisoned lock");
+
+        // Requests which were in flight when the pause started do not extend it
+        if state.paused_until.map_or(false, |until| until > now) {
+            return;
+        }
+
+        let backoff = state.backoff;
+        state.paused_until = Some(now + backoff);
+        state.backoff = (backoff * 2).min(MAX_BACKOFF);
+
+        warn!(
+            chain = %self.chain_id,
+            "full node is rate limiting requests, pausing requests for {}",
+            humantime::format_duration(backoff)
+        );
+
+        telemetry!(throttled_query, &self.chain_id, "node");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use core::num::NonZeroU32;
+
+    fn limiter(max_requests_per_second: Option<u32>) -> RateLimiter {
+        let config = RateLimitConfig {
+            max_requests_per_second: max_requests_per_second.and_then(NonZeroU32::new),
+            max_concurrent_requests: None,
+        };
+
+        RateLimiter::new(ChainId::new("chain".to_string(), 0), Some(&config))
+    }
+
+    #[test]
+    fn requests_spaced_out_after_burst() {
+        let limiter = limiter(Some(2));
+        let now = Instant::now();
+
+        assert_eq!(limiter.try_acquire(now), None);
+        assert_eq!(limiter.try_acquire(now), None);
+        assert_eq!(limiter.try_acquire(now), Some(Duration::from_millis(500)));
+
+        let later = now + Duration::from_millis(500);
+        assert_eq!(limiter.try_acquire(later), None);
+        assert!(limiter.try_acquire(later).is_some());
+    }
+
+    #[test]
+    fn back_off_doubles_until_max() {
+        let limiter = limiter(None);
+        let mut now = Instant::now();
+
+        assert_eq!(limiter.try_acquire(now), None);
+
+        let mut expected = MIN_BACKOFF;
+        for _ in 0..8 {
+            limiter.back_off(now);
+            assert_eq!(limiter.try_acquire(now), Some(expected));
+
+            // Further rejections during the pause do not extend it
+            limiter.back_off(now);
+            assert_eq!(limiter.try_acquire(now), Some(expected));
+
+            now += expected;
+            assert_eq!(limiter.try_acquire(now), None);
+
+            expected = (expected * 2).min(MAX_BACKOFF);
+        }
+    }
+
+    #[test]
+    fn throttled_errors() {
+        assert!(tonic::Status::resource_exhausted("slow down").is_throttled());
+        assert!(tonic::Status::unavailable("unexpected HTTP status code 429").is_throttled());
+        assert!(!tonic::Status::unavailable("connection refused").is_throttled());
+        assert!(!tonic::Status::not_found("no such channel").is_throttled());
+    }
+}
@@ -3,6 +3,7 @@ use std::thread;
 
 use tracing::{debug, error, instrument, warn};
 
+use ibc_proto::cosmos::tx::v1beta1::Fee;
 use ibc_proto::google::protobuf::Any;
 use tendermint::abci::Code;
 use tendermint_rpc::endpoint::broadcast::tx_sync::Response;
@@ -36,6 +37,8 @@ const INCORRECT_ACCOUNT_SEQUENCE_ERR: u32 = 32;
 ///
 /// We treat both cases by re-fetching the account sequence number
 /// from the full node and retrying once with the new account s.n.
+///
+/// Returns the response of the broadcast, along with the fee paid by the transaction.
 #[instrument(
     name = "send_tx_with_account_sequence_retry",
     level = "error",
@@ -52,7 +55,7 @@ pub async fn send_tx_with_account_sequence_retry(
     account: &mut Account,
     tx_memo: &Memo,
     messages: &[Any],
-) -> Result<Response, Error> {
+) -> Result<(Response, Fee), Error> {
     time!(
         "send_tx_with_account_sequence_retry",
         {
@@ -81,7 +84,7 @@ async fn do_send_tx_with_account_sequence_retry(
     account: &mut Account,
     tx_memo: &Memo,
     messages: &[Any],
-) -> Result<Response, Error> {
+) -> Result<(Response, Fee), Error> {
     match estimate_fee_and_send_tx(rpc_client, config, key_pair, account, tx_memo, messages).await {
         // Gas estimation failed with account sequence mismatch during gas estimation.
         // It indicates that the account sequence cached by hermes is stale (got < expected).
@@ -100,7 +103,7 @@ async fn do_send_tx_with_account_sequence_retry(
         }
 
         // Gas estimation succeeded but broadcast_tx_sync failed with a retry-able error.
-        Ok(ref response) if response.code == Code::from(INCORRECT_ACCOUNT_SEQUENCE_ERR) => {
+        Ok((ref response, _)) if response.code == Code::from(INCORRECT_ACCOUNT_SEQUENCE_ERR) => {
             warn!(
                 ?response,
                 "failed to broadcast tx because of a mismatched account sequence number, \
@@ -115,7 +118,7 @@ async fn do_send_tx_with_account_sequence_retry(
 
         // Gas estimation succeeded and broadcast_tx_sync was either successful or has failed with
         // an unrecoverable error.
-        Ok(response) => {
+        Ok((response, fee)) => {
             debug!("gas estimation succeeded");
 
             // Gas estimation and broadcast_tx_sync were successful.
@@ -134,7 +137,7 @@ async fn do_send_tx_with_account_sequence_retry(
                         increasing account sequence number"
                     );
 
-                    Ok(response)
+                    Ok((response, fee))
                 }
 
                 // Gas estimation succeeded, but broadcast_tx_sync failed with unrecoverable error.
@@ -147,7 +150,7 @@ async fn do_send_tx_with_account_sequence_retry(
                         "failed to broadcast tx with unrecoverable error"
                     );
 
-                    Ok(response)
+                    Ok((response, fee))
                 }
             }
         }
@@ -168,10 +171,16 @@ async fn refresh_account_and_retry_send_tx_with_account_sequence(
     account: &mut Account,
     tx_memo: &Memo,
     messages: &[Any],
-) -> Result<Response, Error> {
+) -> Result<(Response, Fee), Error> {
     let key_account = key_pair.account();
     // Re-fetch the account sequence number
-    refresh_account(&config.grpc_address, &key_account, account).await?;
+    refresh_account(
+        &config.grpc_address,
+        &config.rate_limiter,
+        &key_account,
+        account,
+    )
+    .await?;
 
     // Retry after delay
     thread::sleep(Duration::from_millis(ACCOUNT_SEQUENCE_RETRY_DELAY));
@@ -0,0 +1,412 @@
+//! Selection of the account which signs each transaction submitted to a chain,
+//! for chains configured with more than one key.
+//!
+//! Spreading transactions across multiple accounts lifts the limits imposed by the
+//! single account sequence of a key, such as the number of transactions from the
+//! same account which the mempool of a node accepts at once.
+
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::core::channel::v1::{
+    MsgAcknowledgement, MsgRecvPacket, MsgTimeout, MsgTimeoutOnClose,
+};
+use ibc_proto::ibc::core::client::v1::MsgUpdateClient;
+use ibc_relayer_types::core::ics02_client::msgs::update_client;
+use ibc_relayer_types::core::ics04_channel::msgs::{
+    acknowledgement, recv_packet, timeout, timeout_on_close,
+};
+use prost::Message;
+use tonic::transport::Uri;
+use tracing::warn;
+
+use crate::chain::cosmos::query::account::get_or_fetch_account;
+use crate::chain::cosmos::rate_limit::RateLimiter;
+use crate::chain::cosmos::types::account::Account;
+use crate::error::Error;
+use crate::keyring::{Secp256k1KeyPair, SigningKeyPair};
+
+/// The batches of messages assigned to an account,
+/// along with their position in the batches to submit
+pub type AssignedBatches = Vec<(usize, Vec<Any>)>;
+
+/// A key signing transactions, along with the cached information of its account
+pub struct SigningAccount<'a> {
+    pub key_pair: Secp256k1KeyPair,
+    pub account: &'a mut Account,
+}
+
+/// The accounts which sign the transactions submitted to a chain.
+///
+/// Messages are always built with the address of the first account, the one of
+/// the chain's main key, as their signer. Batches made only of messages whose
+/// signer can be substituted are assigned to the accounts in a round-robin fashion,
+/// while the other batches are always signed by the first account.
+///
+/// Transactions signed by different accounts may be included by the chain in any order,
+/// whereas the sequence of an account orders its own transactions. Batches which depend
+/// on each other, see [`are_independent`], must therefore be signed by the same account.
+pub struct SigningAccounts<'a> {
+    accounts: Vec<SigningAccount<'a>>,
+    next: usize,
+}
+
+impl<'a> SigningAccounts<'a> {
+    /// Sign with the given accounts, the next round-robin batch being
+    /// assigned to the account at index `next`.
+    pub fn new(accounts: Vec<SigningAccount<'a>>, next: usize) -> Self {
+        assert!(
+            !accounts.is_empty(),
+            "at least one signing account is required"
+        );
+
+        let next = next % accounts.len();
+
+        Self { accounts, next }
+    }
+
+    /// Pair each key with its cached account, fetching the accounts which
+    /// have not been cached yet.
+    ///
+    /// The keys other than the main key whose account cannot be fetched,
+    /// e.g. because it does not exist on chain yet, are left out.
+    pub async fn fetch(
+        grpc_address: &Uri,
+        rate_limiter: &RateLimiter,
+        key_pairs: Vec<Secp256k1KeyPair>,
+        cached_accounts: &'a mut [Option<Account>],
+        next: usize,
+    ) -> Result<SigningAccounts<'a>, Error> {
+        assert_eq!(
+            key_pairs.len(),
+            cached_accounts.len(),
+            "every signing key must have a cached account entry"
+        );
+
+        let mut accounts = Vec::with_capacity(key_pairs.len());
+
+        for (index, (key_pair, cached_account)) in
+            key_pairs.into_iter().zip(cached_accounts).enumerate()
+        {
+            let key_account = key_pair.account();
+
+            match get_or_fetch_account(grpc_address, rate_limiter, &key_account, cached_account)
+                .await
+            {
+                Ok(account) => accounts.push(SigningAccount { key_pair, account }),
+                Err(e) if index > 0 => {
+                    warn!(
+                        account = %key_account,
+                        "skipping signing account which could not be fetched: {e}"
+                    );
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(Self::new(accounts, next))
+    }
+
+    /// The index of the account to which the next round-robin batch will be assigned,
+    /// to be persisted across submissions
+    pub fn next(&self) -> usize {
+        self.next
+    }
+
+    /// The account of the main key, which built the messages
+    pub fn main_account(&mut self) -> &mut SigningAccount<'a> {
+        &mut self.accounts[0]
+    }
+
+    pub fn accounts_mut(&mut self) -> &mut [SigningAccount<'a>] {
+        &mut self.accounts
+    }
+
+    /// Select the account which signs the given batch of messages, returning
+    /// its index along with the batch updated to be signed by that account.
+    pub fn select(&mut self, batch: Vec<Any>) -> Result<(usize, Vec<Any>), Error> {
+        if self.accounts.len() == 1 || !batch.iter().all(has_substitutable_signer) {
+            return Ok((0, batch));
+        }
+
+        let index = self.next;
+        self.next = (index + 1) % self.accounts.len();
+
+        if index == 0 {
+            return Ok((0, batch));
+        }
+
+        let signer = self.accounts[index].key_pair.account();
+
+        let batch = batch
+            .into_iter()
+            .map(|message| with_signer(message, &signer))
+            .collect::<Result<_, _>>()?;
+
+        Ok((index, batch))
+    }
+
+    /// Assign every batch to an account, in order, returning for each account
+    /// the batches it signs along with their position in the given batches.
+    ///
+    /// The batches are only spread across the accounts if they are `independent`,
+    /// otherwise they are all signed by the first account, in order.
+    pub fn assign(
+        &mut self,
+        batches: Vec<Vec<Any>>,
+        independent: bool,
+    ) -> Result<Vec<AssignedBatches>, Error> {
+        let mut assigned = vec![Vec::new(); self.accounts.len()];
+
+        for (position, batch) in batches.into_iter().enumerate() {
+            let (index, batch) = if independent {
+                self.select(batch)?
+            } else {
+                (0, batch)
+            };
+
+            assigned[index].push((position, batch));
+        }
+
+        Ok(assigned)
+    }
+}
+
+/// Whether the given messages may be included by the chain in any order, such that their
+/// batches can be signed by different accounts and broadcast concurrently.
+///
+/// This only holds for packet messages on an unordered channel, since the packets of an
+/// ordered channel must be delivered in sequence, and packet messages cannot be included
+/// before the client update which installs the consensus state verifying their proofs.
+pub fn are_independent(messages: &[Any], unordered_channel: bool) -> bool {
+    unordered_channel
+        && messages.iter().all(|message| {
+            [
+                recv_packet::TYPE_URL,
+                acknowledgement::TYPE_URL,
+                timeout::TYPE_URL,
+                timeout_on_close::TYPE_URL,
+            ]
+            .contains(&message.type_url.as_str())
+        })
+}
+
+/// Whether the relayer address is the only address held by the message,
+/// such that any relayer account may sign it.
+fn has_substitutable_signer(message: &Any) -> bool {
+    [
+        recv_packet::TYPE_URL,
+        acknowledgement::TYPE_URL,
+        timeout::TYPE_URL,
+        timeout_on_close::TYPE_URL,
+        update_client::TYPE_URL,
+    ]
+    .contains(&message.type_url.as_str())
+}
+
+fn with_signer(message: Any, signer: &str) -> Result<Any, Error> {
+    fn set_signer<M: Message + Default>(
+        message: Any,
+        signer: &str,
+        set: impl FnOnce(&mut M, String),
+    ) -> Result<Any, Error> {
+        let mut decoded = M::decode(message.value.as_slice())
+            .map_err(|e| Error::protobuf_decode(message.type_url.clone(), e))?;
+
+        set(&mut decoded, signer.to_string());
+
+        Ok(Any {
+            type_url: message.type_url,
+            value: decoded.encode_to_vec(),
+        })
+    }
+
+    match message.type_url.as_str() {
+        recv_packet::TYPE_URL => {
+            set_signer(message, signer, |m: &mut MsgRecvPacket, s| m.signer = s)
+        }
+        acknowledgement::TYPE_URL => {
+            set_signer(message, signer, |m: &mut MsgAcknowledgement, s| {
+                m.signer = s
+            })
+        }
+        timeout::TYPE_URL => set_signer(message, signer, |m: &mut MsgTimeout, s| m.signer = s),
+        timeout_on_close::TYPE_URL => {
+            set_signer(message, signer, |m: &mut MsgTimeoutOnClose, s| m.signer = s)
+        }
+        update_client::TYPE_URL => {
+            set_signer(message, signer, |m: &mut MsgUpdateClient, s| m.signer = s)
+        }
+        _ => Ok(message),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{are_independent, SigningAccount, SigningAccounts};
+
+    use std::fs;
+
+    use ibc_proto::google::protobuf::Any;
+    use ibc_proto::ibc::core::channel::v1::MsgRecvPacket;
+    use ibc_relayer_types::core::ics02_client::msgs::update_client;
+    use ibc_relayer_types::core::ics04_channel::msgs::recv_packet;
+    use prost::Message;
+
+    use crate::chain::cosmos::types::account::{
+        Account, AccountAddress, AccountNumber, AccountSequence,
+    };
+    use crate::config::AddressType;
+    use crate::keyring::{Secp256k1KeyPair, SigningKeyPair};
+
+    fn key_pair(index: u32) -> Secp256k1KeyPair {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/config/fixtures/relayer-seed.json"
+        );
+        let seed_file_content = fs::read_to_string(path).unwrap();
+        let key_file: serde_json::Value = serde_json::from_str(&seed_file_content).unwrap();
+        let hd_path = format!("m/44'/118'/0'/0/{index}").parse().unwrap();
+
+        Secp256k1KeyPair::from_mnemonic(
+            key_file["mnemonic"].as_str().unwrap(),
+            &hd_path,
+            &AddressType::Cosmos,
+            "cosmos",
+        )
+        .unwrap()
+    }
+
+    fn account(key_pair: &Secp256k1KeyPair) -> Account {
+        Account {
+            address: AccountAddress::new(key_pair.account()),
+            number: AccountNumber::new(0),
+            sequence: AccountSequence::new(0),
+        }
+    }
+
+    fn recv_packet(signer: &str) -> Any {
+        Any {
+            type_url: recv_packet::TYPE_URL.to_string(),
+            value: MsgRecvPacket {
+                signer: signer.to_string(),
+                ..Default::default()
+            }
+            .encode_to_vec(),
+        }
+    }
+
+    fn signer_of(message: &Any) -> String {
+        MsgRecvPacket::decode(message.value.as_slice())
+            .unwrap()
+            .signer
+    }
+
+    #[test]
+    fn batches_are_assigned_round_robin() {
+        let (main_key, other_key) = (key_pair(0), key_pair(1));
+        let (main_address, other_address) = (main_key.account(), other_key.account());
+        let (mut main_account, mut other_account) = (account(&main_key), account(&other_key));
+
+        let mut signers = SigningAccounts::new(
+            vec![
+                SigningAccount {
+                    key_pair: main_key,
+                    account: &mut main_account,
+                },
+                SigningAccount {
+                    key_pair: other_key,
+                    account: &mut other_account,
+                },
+            ],
+            1,
+        );
+
+        let batches = vec![vec![recv_packet(&main_address)]; 3];
+        let assigned = signers.assign(batches, true).unwrap();
+
+        let positions = |index: usize| -> Vec<usize> {
+            assigned[index]
+                .iter()
+                .map(|(position, _)| *position)
+                .collect()
+        };
+        assert_eq!(positions(0), vec![1]);
+        assert_eq!(positions(1), vec![0, 2]);
+        assert_eq!(signers.next(), 0);
+
+        assert_eq!(signer_of(&assigned[0][0].1[0]), main_address);
+        assert_eq!(signer_of(&assigned[1][0].1[0]), other_address);
+        assert_eq!(signer_of(&assigned[1][1].1[0]), other_address);
+    }
+
+    #[test]
+    fn dependent_batches_are_signed_by_main_account() {
+        let (main_key, other_key) = (key_pair(0), key_pair(1));
+        let main_address = main_key.account();
+        let (mut main_account, mut other_account) = (account(&main_key), account(&other_key));
+
+        let mut signers = SigningAccounts::new(
+            vec![
+                SigningAccount {
+                    key_pair: main_key,
+                    account: &mut main_account,
+                },
+                SigningAccount {
+                    key_pair: other_key,
+                    account: &mut other_account,
+                },
+            ],
+            1,
+        );
+
+        let update_client = Any {
+            type_url: update_client::TYPE_URL.to_string(),
+            value: Vec::new(),
+        };
+        let messages = vec![update_client, recv_packet(&main_address)];
+
+        assert!(!are_independent(&messages, true));
+        assert!(!are_independent(&messages[1..], false));
+        assert!(are_independent(&messages[1..], true));
+
+        let batches = messages.into_iter().map(|message| vec![message]).collect();
+        let assigned = signers.assign(batches, false).unwrap();
+
+        assert_eq!(assigned[0].len(), 2);
+        assert!(assigned[1].is_empty());
+        assert_eq!(signers.next(), 1);
+    }
+
+    #[test]
+    fn batches_with_other_messages_are_signed_by_main_account() {
+        let (main_key, other_key) = (key_pair(0), key_pair(1));
+        let main_address = main_key.account();
+        let (mut main_account, mut other_account) = (account(&main_key), account(&other_key));
+
+        let mut signers = SigningAccounts::new(
+            vec![
+                SigningAccount {
+                    key_pair: main_key,
+                    account: &mut main_account,
+                },
+                SigningAccount {
+                    key_pair: other_key,
+                    account: &mut other_account,
+                },
+            ],
+            1,
+        );
+
+        let transfer = Any {
+            type_url: "/ibc.applications.transfer.v1.MsgTransfer".to_string(),
+            value: vec![1, 2, 3],
+        };
+        let batch = vec![recv_packet(&main_address), transfer.clone()];
+
+        let (index, signed) = signers.select(batch).unwrap();
+
+        assert_eq!(index, 0);
+        assert_eq!(signed[1], transfer);
+        assert_eq!(signer_of(&signed[0]), main_address);
+        assert_eq!(signers.next(), 1);
+    }
+}
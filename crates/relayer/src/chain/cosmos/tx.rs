@@ -1,9 +1,12 @@
 use ibc_proto::cosmos::tx::v1beta1::Fee;
 use ibc_proto::google::protobuf::Any;
 use ibc_relayer_types::events::IbcEvent;
+use itertools::Itertools;
 use tendermint_rpc::endpoint::broadcast::tx_sync::Response;
 use tendermint_rpc::{Client, HttpClient, Url};
+use tracing::info;
 
+use crate::audit::{self, TxAuditEntry};
 use crate::chain::cosmos::encode::sign_and_encode_tx;
 use crate::chain::cosmos::estimate::estimate_tx_fees;
 use crate::chain::cosmos::query::account::query_account;
@@ -11,13 +14,19 @@ use crate::chain::cosmos::query::tx::all_ibc_events_from_tx_search_response;
 use crate::chain::cosmos::types::account::Account;
 use crate::chain::cosmos::types::config::TxConfig;
 use crate::chain::cosmos::wait::wait_tx_succeed;
+use crate::chain::tracking::TrackingId;
 use crate::config::types::Memo;
 use crate::error::Error;
 use crate::event::IbcEventWithHeight;
 use crate::keyring::{Secp256k1KeyPair, SigningKeyPair};
+use crate::util::dry_run;
+use crate::util::pretty::PrettyFee;
 
 use super::batch::send_batched_messages_and_wait_commit;
+use super::signer::{SigningAccount, SigningAccounts};
 
+/// Estimate the fee of a transaction made of the given messages, and broadcast it,
+/// returning the response of the broadcast along with the fee paid by the transaction.
 pub async fn estimate_fee_and_send_tx(
     rpc_client: &HttpClient,
     config: &TxConfig,
@@ -25,15 +34,37 @@ pub async fn estimate_fee_and_send_tx(
     account: &Account,
     tx_memo: &Memo,
     messages: &[Any],
-) -> Result<Response, Error> {
+) -> Result<(Response, Fee), Error> {
     let fee = estimate_tx_fees(config, key_pair, account, tx_memo, messages).await?;
 
-    send_tx_with_fee(
+    // The fee is reserved until the outcome of the broadcast is known, so that
+    // the transactions broadcast concurrently cannot overrun the budget together
+    let reservation = config
+        .fee_budget
+        .as_ref()
+        .map(|fee_budget| fee_budget.reserve(&fee, messages))
+        .transpose()?;
+
+    let response = send_tx_with_fee(
         rpc_client, config, key_pair, account, tx_memo, messages, &fee,
     )
-    .await
+    .await?;
+
+    // The fee is deducted as soon as the transaction passes `CheckTx`,
+    // even if its execution fails afterwards.
+    if let Some(reservation) = reservation {
+        if response.code.is_ok() {
+            reservation.spend();
+        }
+    }
+
+    Ok((response, fee))
 }
 
+/// Sign and broadcast a transaction with the given fee.
+///
+/// Every transaction broadcast to a chain goes through this function, which
+/// fails without broadcasting anything in dry-run mode.
 async fn send_tx_with_fee(
     rpc_client: &HttpClient,
     config: &TxConfig,
@@ -43,15 +74,33 @@ async fn send_tx_with_fee(
     messages: &[Any],
     fee: &Fee,
 ) -> Result<Response, Error> {
+    if dry_run::is_enabled() {
+        let messages = messages.iter().map(|msg| msg.type_url.as_str()).join(", ");
+
+        info!(
+            chain = %config.chain_id,
+            signer = %key_pair.account(),
+            messages = %messages,
+            gas_limit = fee.gas_limit,
+            fee = %PrettyFee(fee),
+            "dry run: not broadcasting tx",
+        );
+
+        return Err(Error::dry_run(config.chain_id.clone()));
+    }
+
     let tx_bytes = sign_and_encode_tx(config, key_pair, account, tx_memo, messages, fee)?;
 
-    let response = broadcast_tx_sync(rpc_client, &config.rpc_address, tx_bytes).await?;
+    let response = config
+        .rate_limiter
+        .throttle(broadcast_tx_sync(rpc_client, &config.rpc_address, tx_bytes))
+        .await?;
 
     Ok(response)
 }
 
 /// Perform a `broadcast_tx_sync`, and return the corresponding deserialized response data.
-pub async fn broadcast_tx_sync(
+async fn broadcast_tx_sync(
     rpc_client: &HttpClient,
     rpc_address: &Url,
     data: Vec<u8>,
@@ -83,11 +132,13 @@ pub async fn simple_send_tx(
     messages: Vec<Any>,
 ) -> Result<Vec<IbcEventWithHeight>, Error> {
     let key_account = key_pair.account();
-    let account = query_account(&config.grpc_address, &key_account)
+    let account = config
+        .rate_limiter
+        .throttle(query_account(&config.grpc_address, &key_account))
         .await?
         .into();
 
-    let response = estimate_fee_and_send_tx(
+    let (response, fee) = estimate_fee_and_send_tx(
         rpc_client,
         config,
         key_pair,
@@ -97,17 +148,40 @@ pub async fn simple_send_tx(
     )
     .await?;
 
+    let mut audit_entry = audit::is_enabled().then(|| {
+        let entry = TxAuditEntry::new(
+            &config.chain_id,
+            TrackingId::new_static("simple send tx"),
+            key_account,
+            &messages,
+            &fee,
+            &response,
+        );
+
+        audit::record(&entry);
+
+        entry
+    });
+
     if response.code.is_err() {
         return Err(Error::check_tx(response));
     }
 
-    let response = wait_tx_succeed(
+    let result = wait_tx_succeed(
         rpc_client,
         &config.rpc_address,
+        &config.rate_limiter,
         &config.rpc_timeout,
         &response.hash,
     )
-    .await?;
+    .await;
+
+    if let (Some(entry), Ok(committed)) = (&mut audit_entry, &result) {
+        entry.set_committed(committed);
+        audit::record(entry);
+    }
+
+    let response = result?;
 
     let events = all_ibc_events_from_tx_search_response(&config.chain_id, response);
 
@@ -121,17 +195,28 @@ pub async fn batched_send_tx(
     messages: Vec<Any>,
 ) -> Result<Vec<IbcEventWithHeight>, Error> {
     let key_account = key_pair.account();
-    let mut account = query_account(&config.grpc_address, &key_account)
+    let mut account = config
+        .rate_limiter
+        .throttle(query_account(&config.grpc_address, &key_account))
         .await?
         .into();
 
+    let mut signers = SigningAccounts::new(
+        vec![SigningAccount {
+            key_pair: key_pair.clone(),
+            account: &mut account,
+        }],
+        0,
+    );
+
     let events = send_batched_messages_and_wait_commit(
         rpc_client,
         config,
-        key_pair,
-        &mut account,
+        &mut signers,
         &Memo::default(),
         messages,
+        TrackingId::new_static("batched send tx"),
+        false,
     )
     .await?;
 
@@ -143,3 +228,105 @@ pub async fn batched_send_tx(
 
     Ok(events)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::send_tx_with_fee;
+
+    use std::fs;
+    use std::io::ErrorKind;
+    use std::net::TcpListener;
+
+    use ibc_proto::cosmos::bank::v1beta1::MsgSend;
+    use ibc_proto::cosmos::base::v1beta1::Coin;
+    use ibc_proto::google::protobuf::Any;
+    use ibc_relayer_types::applications::transfer::msgs::send;
+    use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+    use prost::Message;
+    use serial_test::serial;
+    use tendermint_rpc::HttpClient;
+
+    use crate::chain::cosmos::gas::gas_amount_to_fee;
+    use crate::chain::cosmos::types::account::{
+        Account, AccountAddress, AccountNumber, AccountSequence,
+    };
+    use crate::chain::cosmos::types::config::TxConfig;
+    use crate::config;
+    use crate::config::types::Memo;
+    use crate::error::ErrorDetail;
+    use crate::keyring::{Secp256k1KeyPair, SigningKeyPair};
+    use crate::util::dry_run;
+
+    #[test]
+    #[serial]
+    fn dry_run_top_up_broadcasts_nothing() {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/config/fixtures/relayer_conf_example.toml"
+        );
+        let config = config::load(path).expect("could not parse config");
+        let chain_id = ChainId::from_string("chain_A");
+        let mut tx_config = TxConfig::try_from(config.find_chain(&chain_id).unwrap())
+            .expect("could not obtain tx config");
+
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/config/fixtures/relayer-seed.json"
+        );
+        let seed_file_content = fs::read_to_string(path).unwrap();
+        let hd_path = "m/44'/118'/0'/0/0".parse().unwrap();
+        let treasury = Secp256k1KeyPair::from_seed_file(&seed_file_content, &hd_path).unwrap();
+
+        let account = Account {
+            address: AccountAddress::new(treasury.account()),
+            number: AccountNumber::new(0),
+            sequence: AccountSequence::new(0),
+        };
+
+        // The top-up sent by the wallet worker from the treasury account
+        let top_up = Any {
+            type_url: send::TYPE_URL.to_string(),
+            value: MsgSend {
+                from_address: treasury.account(),
+                to_address: "cosmos1relayer".to_string(),
+                amount: vec![Coin {
+                    denom: "stake".to_string(),
+                    amount: "1000".to_string(),
+                }],
+            }
+            .encode_to_vec(),
+        };
+
+        // The full node, which must never receive the transaction
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        listener.set_nonblocking(true).unwrap();
+
+        let rpc_address = format!("http://{}", listener.local_addr().unwrap());
+        tx_config.rpc_address = rpc_address.parse().unwrap();
+        let rpc_client = HttpClient::new(rpc_address.as_str()).unwrap();
+
+        let fee = gas_amount_to_fee(&tx_config.gas_config, tx_config.gas_config.default_gas);
+
+        dry_run::enable(true);
+
+        let result = tokio::runtime::Runtime::new()
+            .unwrap()
+            .block_on(send_tx_with_fee(
+                &rpc_client,
+                &tx_config,
+                &treasury,
+                &account,
+                &Memo::default(),
+                &[top_up],
+                &fee,
+            ));
+
+        dry_run::enable(false);
+
+        assert!(matches!(
+            result.unwrap_err().detail(),
+            ErrorDetail::DryRun(_)
+        ));
+        assert_eq!(listener.accept().unwrap_err().kind(), ErrorKind::WouldBlock);
+    }
+}
@@ -1,3 +1,4 @@
+use alloc::sync::Arc;
 use core::str::FromStr;
 use core::time::Duration;
 use http::Uri;
@@ -5,6 +6,8 @@ use ibc_proto::google::protobuf::Any;
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 use tendermint_rpc::Url;
 
+use crate::chain::budget::FeeBudgetTracker;
+use crate::chain::cosmos::rate_limit::RateLimiter;
 use crate::chain::cosmos::types::gas::GasConfig;
 use crate::config::types::{MaxMsgNum, MaxTxSize};
 use crate::config::{AddressType, ChainConfig};
@@ -21,6 +24,10 @@ pub struct TxConfig {
     pub max_msg_num: MaxMsgNum,
     pub max_tx_size: MaxTxSize,
     pub extension_options: Vec<Any>,
+    pub fee_budget: Option<Arc<FeeBudgetTracker>>,
+    /// Limits the requests sent to the full node while submitting transactions,
+    /// shared with the queries made by the chain endpoint
+    pub rate_limiter: Arc<RateLimiter>,
 }
 
 impl<'a> TryFrom<&'a ChainConfig> for TxConfig {
@@ -48,6 +55,14 @@ impl<'a> TryFrom<&'a ChainConfig> for TxConfig {
             max_msg_num: config.max_msg_num,
             max_tx_size: config.max_tx_size,
             extension_options,
+            fee_budget: config
+                .fee_budget
+                .clone()
+                .map(|budget| Arc::new(FeeBudgetTracker::new(config.id.clone(), budget))),
+            rate_limiter: Arc::new(RateLimiter::new(
+                config.id.clone(),
+                config.rate_limit.as_ref(),
+            )),
         })
     }
 }
@@ -31,6 +31,14 @@ impl<'a> From<&'a ChainConfig> for GasConfig {
     }
 }
 
+impl GasConfig {
+    /// Pay the given price per unit of gas, in the denomination of the configured gas price
+    pub fn set_gas_price(&mut self, price: f64) {
+        self.gas_price.price = price;
+        self.max_fee.amount = vec![calculate_fee(self.max_gas, &self.gas_price)];
+    }
+}
+
 /// The default amount of gas the relayer is willing to pay for a transaction,
 /// when it cannot simulate the tx and therefore estimate the gas amount needed.
 pub fn default_gas_from_config(config: &ChainConfig) -> u64 {
@@ -1,6 +1,7 @@
 use ibc_proto::cosmos::tx::v1beta1::{AuthInfo, TxBody};
 use tendermint_rpc::endpoint::broadcast::tx_sync::Response;
 
+use crate::audit::TxAuditEntry;
 use crate::event::IbcEventWithHeight;
 
 pub struct SignedTx {
@@ -22,4 +23,6 @@ pub struct TxSyncResult {
     // the events generated by a Tx once executed
     pub events: Vec<IbcEventWithHeight>,
     pub status: TxStatus,
+    // the entry of the Tx in the audit log, if it is enabled
+    pub audit: Option<TxAuditEntry>,
 }
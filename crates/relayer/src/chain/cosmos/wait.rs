@@ -12,6 +12,7 @@ use tokio::time::sleep;
 use tracing::{debug, debug_span, trace};
 
 use crate::chain::cosmos::query::tx::query_tx_response;
+use crate::chain::cosmos::rate_limit::RateLimiter;
 use crate::chain::cosmos::types::events::from_tx_response_event;
 use crate::chain::cosmos::types::tx::{TxStatus, TxSyncResult};
 use crate::error::Error;
@@ -26,6 +27,7 @@ pub async fn wait_for_block_commits(
     chain_id: &ChainId,
     rpc_client: &HttpClient,
     rpc_address: &Url,
+    rate_limiter: &RateLimiter,
     rpc_timeout: &Duration,
     tx_sync_results: &mut [TxSyncResult],
 ) -> Result<(), Error> {
@@ -68,8 +70,14 @@ pub async fn wait_for_block_commits(
             thread::sleep(WAIT_BACKOFF);
 
             for tx_sync_result in tx_sync_results.iter_mut() {
-                let res =
-                    update_tx_sync_result(chain_id, rpc_client, rpc_address, tx_sync_result).await;
+                let res = update_tx_sync_result(
+                    chain_id,
+                    rpc_client,
+                    rpc_address,
+                    rate_limiter,
+                    tx_sync_result,
+                )
+                .await;
                 if let Err(e) = res {
                     debug!("update_tx_sync_result failed: {e}");
                 }
@@ -82,15 +90,25 @@ async fn update_tx_sync_result(
     chain_id: &ChainId,
     rpc_client: &HttpClient,
     rpc_address: &Url,
+    rate_limiter: &RateLimiter,
     tx_sync_result: &mut TxSyncResult,
 ) -> Result<(), Error> {
     if let TxStatus::Pending { message_count } = tx_sync_result.status {
-        let response =
-            query_tx_response(rpc_client, rpc_address, &tx_sync_result.response.hash).await?;
+        let response = rate_limiter
+            .throttle(query_tx_response(
+                rpc_client,
+                rpc_address,
+                &tx_sync_result.response.hash,
+            ))
+            .await?;
 
         if let Some(response) = response {
             tx_sync_result.status = TxStatus::ReceivedResponse;
 
+            if let Some(audit) = &mut tx_sync_result.audit {
+                audit.set_committed(&response);
+            }
+
             let height = Height::new(chain_id.version(), u64::from(response.height)).unwrap();
             if response.tx_result.code.is_err() {
                 tx_sync_result.events = vec![
@@ -126,10 +144,11 @@ fn all_tx_results_found(tx_sync_results: &[TxSyncResult]) -> bool {
 pub async fn wait_tx_succeed(
     rpc_client: &HttpClient,
     rpc_address: &Url,
+    rate_limiter: &RateLimiter,
     timeout: &Duration,
     tx_hash: &TxHash,
 ) -> Result<TxResponse, Error> {
-    let response = wait_tx_hash(rpc_client, rpc_address, timeout, tx_hash).await?;
+    let response = wait_tx_hash(rpc_client, rpc_address, rate_limiter, timeout, tx_hash).await?;
 
     let response_code = response.tx_result.code;
     if response_code.is_err() {
@@ -142,13 +161,16 @@ pub async fn wait_tx_succeed(
 pub async fn wait_tx_hash(
     rpc_client: &HttpClient,
     rpc_address: &Url,
+    rate_limiter: &RateLimiter,
     timeout: &Duration,
     tx_hash: &TxHash,
 ) -> Result<TxResponse, Error> {
     let start_time = Instant::now();
 
     loop {
-        let response = query_tx_response(rpc_client, rpc_address, tx_hash).await?;
+        let response = rate_limiter
+            .throttle(query_tx_response(rpc_client, rpc_address, tx_hash))
+            .await?;
 
         match response {
             None => {
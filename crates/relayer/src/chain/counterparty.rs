@@ -2,7 +2,7 @@ use std::collections::HashSet;
 
 use ibc_relayer_types::{
     core::{
-        ics02_client::client_state::ClientState,
+        ics02_client::{client_state::ClientState, client_type::ClientType},
         ics03_connection::connection::{
             ConnectionEnd, IdentifiedConnectionEnd, State as ConnectionState,
         },
@@ -31,6 +31,7 @@ use super::{
 use crate::chain::requests::QueryHeight;
 use crate::channel::ChannelError;
 use crate::client_state::IdentifiedAnyClientState;
+use crate::error::Error as RelayerError;
 use crate::path::PathIdentifiers;
 use crate::supervisor::Error;
 
@@ -648,3 +649,35 @@ pub fn pending_packet_summary(
         unreceived_acks: pending_acks,
     })
 }
+
+/// Synchronise the sequence of the proofs `chain` builds for its client `counterparty_client_id`
+/// on `counterparty_chain` with the state of that client, before building proofs for it.
+///
+/// Only the proofs of solo machines depend on the state of their client, hence this
+/// is a no-op for the clients of other chains.
+pub fn sync_proof_sequence(
+    chain: &impl ChainHandle,
+    counterparty_chain: &impl ChainHandle,
+    counterparty_client_id: &ClientId,
+) -> Result<(), RelayerError> {
+    if !counterparty_client_id
+        .as_str()
+        .starts_with(ClientId::prefix(ClientType::Solomachine))
+    {
+        return Ok(());
+    }
+
+    let (client_state, _) = counterparty_chain.query_client_state(
+        QueryClientStateRequest {
+            client_id: counterparty_client_id.clone(),
+            height: QueryHeight::Latest,
+        },
+        IncludeProof::No,
+    )?;
+
+    chain.sync_proof_sequence(
+        counterparty_chain.id(),
+        counterparty_client_id.clone(),
+        client_state,
+    )
+}
@@ -3,6 +3,7 @@ use core::convert::TryFrom;
 
 use tokio::runtime::Runtime as TokioRuntime;
 
+use ibc_proto::cosmos::tx::v1beta1::Fee;
 use ibc_proto::ibc::apps::fee::v1::{
     QueryIncentivizedPacketRequest, QueryIncentivizedPacketResponse,
 };
@@ -17,6 +18,7 @@ use ibc_relayer_types::core::ics03_connection::connection::{
 use ibc_relayer_types::core::ics03_connection::version::{get_compatible_versions, Version};
 use ibc_relayer_types::core::ics04_channel::channel::{ChannelEnd, IdentifiedChannelEnd};
 use ibc_relayer_types::core::ics04_channel::packet::{PacketMsgType, Sequence};
+use ibc_relayer_types::core::ics04_channel::upgrade::{ErrorReceipt, Upgrade};
 use ibc_relayer_types::core::ics23_commitment::commitment::{
     CommitmentPrefix, CommitmentProofBytes,
 };
@@ -32,6 +34,7 @@ use ibc_relayer_types::Height as ICSHeight;
 use tendermint_rpc::endpoint::broadcast::tx_sync::Response as TxResponse;
 
 use crate::account::Balance;
+use crate::chain::budget::FeeBudgetStatus;
 use crate::chain::client::ClientSettings;
 use crate::chain::handle::Subscription;
 use crate::chain::requests::*;
@@ -100,6 +103,12 @@ pub trait ChainEndpoint: Sized {
     /// Perform a health check
     fn health_check(&self) -> Result<HealthCheck, Error>;
 
+    /// Check the health of the full nodes of the chain, and switch over
+    /// to another node if the node in use is unhealthy
+    fn check_endpoints(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+
     // Events
     fn subscribe(&mut self) -> Result<Subscription, Error>;
 
@@ -124,6 +133,21 @@ pub trait ChainEndpoint: Sized {
         Ok(key_pair)
     }
 
+    /// Get the signing key pairs of all the keys signing transactions, along with their names,
+    /// starting with the main key
+    fn get_keys(&mut self) -> Result<Vec<(String, Self::SigningKeyPair)>, Error> {
+        self.config()
+            .signing_key_names()
+            .into_iter()
+            .map(|key_name| {
+                self.keybase()
+                    .get_key(key_name)
+                    .map(|key_pair| (key_name.to_string(), key_pair))
+                    .map_err(|e| Error::key_not_found(key_name.to_string(), e))
+            })
+            .collect()
+    }
+
     fn add_key(&mut self, key_name: &str, key_pair: Self::SigningKeyPair) -> Result<(), Error> {
         self.keybase_mut()
             .add_key(key_name, key_pair)
@@ -153,6 +177,14 @@ pub trait ChainEndpoint: Sized {
         tracked_msgs: TrackedMsgs,
     ) -> Result<Vec<TxResponse>, Error>;
 
+    /// Estimate the fee the relayer would pay to submit the given messages
+    /// in a single transaction, by simulating it.
+    fn estimate_fee(&mut self, tracked_msgs: TrackedMsgs) -> Result<Fee, Error>;
+
+    /// Simulate the transaction submitting the given messages, without broadcasting it,
+    /// and return the gas it uses, or the error which made the simulation fail.
+    fn simulate_messages(&mut self, tracked_msgs: TrackedMsgs) -> Result<u64, Error>;
+
     /// Fetch a header from the chain at the given height and verify it.
     fn verify_header(
         &mut self,
@@ -271,6 +303,24 @@ pub trait ChainEndpoint: Sized {
         include_proof: IncludeProof,
     ) -> Result<(ChannelEnd, Option<MerkleProof>), Error>;
 
+    /// Performs a query to retrieve the upgrade in progress for a given channel,
+    /// stored on the chain at path `path::ChannelUpgradesPath`. A proof can
+    /// optionally be returned along with the result.
+    fn query_upgrade(
+        &self,
+        request: QueryUpgradeRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(Upgrade, Option<MerkleProof>), Error>;
+
+    /// Performs a query to retrieve the last upgrade error receipt of a given channel,
+    /// stored on the chain at path `path::ChannelUpgradeErrorsPath`. A proof can
+    /// optionally be returned along with the result.
+    fn query_upgrade_error(
+        &self,
+        request: QueryUpgradeErrorRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(ErrorReceipt, Option<MerkleProof>), Error>;
+
     /// Performs a query to retrieve the client state for the channel associated
     /// with a given channel identifier.
     fn query_channel_client_state(
@@ -451,27 +501,32 @@ pub trait ChainEndpoint: Sized {
                 };
 
                 client_proof = Some(
-                    CommitmentProofBytes::try_from(client_state_proof)
+                    CommitmentProofBytes::try_from(client_state_proof.clone())
                         .map_err(Error::malformed_proof)?,
                 );
 
-                let consensus_state_proof = {
-                    let (_, maybe_consensus_state_proof) = self.query_consensus_state(
-                        QueryConsensusStateRequest {
-                            client_id: client_id.clone(),
-                            consensus_height: client_state_value.latest_height(),
-                            query_height: QueryHeight::Specific(height),
-                        },
-                        IncludeProof::Yes,
-                    )?;
-
-                    let Some(consensus_state_proof) = maybe_consensus_state_proof else {
-                        return Err(Error::queried_proof_not_found());
+                // Solo machine clients keep their consensus state in the client state,
+                // whose proof thus also proves the consensus state
+                let consensus_state_proof =
+                    if let AnyClientState::Solomachine(_) = client_state_value {
+                        client_state_proof
+                    } else {
+                        let (_, maybe_consensus_state_proof) = self.query_consensus_state(
+                            QueryConsensusStateRequest {
+                                client_id: client_id.clone(),
+                                consensus_height: client_state_value.latest_height(),
+                                query_height: QueryHeight::Specific(height),
+                            },
+                            IncludeProof::Yes,
+                        )?;
+
+                        let Some(consensus_state_proof) = maybe_consensus_state_proof else {
+                            return Err(Error::queried_proof_not_found());
+                        };
+
+                        consensus_state_proof
                     };
 
-                    consensus_state_proof
-                };
-
                 consensus_proof = Option::from(
                     ConsensusProof::new(
                         CommitmentProofBytes::try_from(consensus_state_proof)
@@ -527,7 +582,61 @@ pub trait ChainEndpoint: Sized {
             .map_err(Error::malformed_proof)
     }
 
+    /// Builds the proofs for channel upgrade handshake messages, which prove
+    /// both the channel end and the upgrade stored on this chain.
+    fn build_channel_upgrade_proofs(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        height: ICSHeight,
+    ) -> Result<(Upgrade, Proofs), Error> {
+        let (_, maybe_channel_proof) = self.query_channel(
+            QueryChannelRequest {
+                port_id: port_id.clone(),
+                channel_id: channel_id.clone(),
+                height: QueryHeight::Specific(height),
+            },
+            IncludeProof::Yes,
+        )?;
+
+        let Some(channel_proof) = maybe_channel_proof else {
+            return Err(Error::queried_proof_not_found());
+        };
+
+        let (upgrade, maybe_upgrade_proof) = self.query_upgrade(
+            QueryUpgradeRequest {
+                port_id: port_id.clone(),
+                channel_id: channel_id.clone(),
+                height: QueryHeight::Specific(height),
+            },
+            IncludeProof::Yes,
+        )?;
+
+        let Some(upgrade_proof) = maybe_upgrade_proof else {
+            return Err(Error::queried_proof_not_found());
+        };
+
+        let channel_proof_bytes =
+            CommitmentProofBytes::try_from(channel_proof).map_err(Error::malformed_proof)?;
+        let upgrade_proof_bytes =
+            CommitmentProofBytes::try_from(upgrade_proof).map_err(Error::malformed_proof)?;
+
+        let proofs = Proofs::new(
+            channel_proof_bytes,
+            None,
+            None,
+            Some(upgrade_proof_bytes),
+            height.increment(),
+        )
+        .map_err(Error::malformed_proof)?;
+
+        Ok((upgrade, proofs))
+    }
+
     /// Builds the proof for packet messages.
+    ///
+    /// Chains whose proofs are only valid once, ie. solo machines, must not build valid
+    /// proofs for [`ProofPurpose::Simulation`], as these are never submitted.
     fn build_packet_proofs(
         &self,
         packet_type: PacketMsgType,
@@ -535,6 +644,7 @@ pub trait ChainEndpoint: Sized {
         channel_id: ChannelId,
         sequence: Sequence,
         height: ICSHeight,
+        _purpose: ProofPurpose,
     ) -> Result<Proofs, Error> {
         let (maybe_packet_proof, channel_proof) = match packet_type {
             PacketMsgType::Recv => {
@@ -670,6 +780,20 @@ pub trait ChainEndpoint: Sized {
         Ok(proofs)
     }
 
+    /// Synchronise the sequence of the proofs built for the given client of this chain
+    /// with the state of that client on its host chain, before building proofs for it.
+    ///
+    /// Only chains whose proofs depend on the state of their client, ie. solo machines,
+    /// need to do so; other chains ignore it.
+    fn sync_proof_sequence(
+        &mut self,
+        _counterparty_chain_id: &ChainId,
+        _counterparty_client_id: &ClientId,
+        _counterparty_client_state: &AnyClientState,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
     fn maybe_register_counterparty_payee(
         &mut self,
         channel_id: &ChannelId,
@@ -677,6 +801,17 @@ pub trait ChainEndpoint: Sized {
         counterparty_payee: &Signer,
     ) -> Result<(), Error>;
 
+    /// Send `amount` of `denom` from the account of the key `from_key_name`
+    /// to the account at `to_address` on this chain, and wait for the
+    /// transaction to be committed.
+    fn send_coins(
+        &mut self,
+        from_key_name: &str,
+        to_address: &str,
+        amount: u128,
+        denom: &str,
+    ) -> Result<(), Error>;
+
     fn cross_chain_query(
         &self,
         requests: Vec<CrossChainQueryRequest>,
@@ -686,4 +821,7 @@ pub trait ChainEndpoint: Sized {
         &self,
         request: QueryIncentivizedPacketRequest,
     ) -> Result<QueryIncentivizedPacketResponse, Error>;
+
+    /// Query the state of the fee budget of the chain, if one is configured.
+    fn query_fee_budget(&self) -> Result<Option<FeeBudgetStatus>, Error>;
 }
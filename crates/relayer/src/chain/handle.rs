@@ -4,6 +4,7 @@ use core::fmt::{self, Debug, Display};
 use crossbeam_channel as channel;
 use tracing::Span;
 
+use ibc_proto::cosmos::tx::v1beta1::Fee;
 use ibc_proto::ibc::apps::fee::v1::{
     QueryIncentivizedPacketRequest, QueryIncentivizedPacketResponse,
 };
@@ -18,6 +19,7 @@ use ibc_relayer_types::{
         ics04_channel::{
             channel::{ChannelEnd, IdentifiedChannelEnd},
             packet::{PacketMsgType, Sequence},
+            upgrade::{ErrorReceipt, Upgrade},
         },
         ics23_commitment::{commitment::CommitmentPrefix, merkle::MerkleProof},
         ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId},
@@ -29,6 +31,7 @@ use ibc_relayer_types::{
 
 use crate::{
     account::Balance,
+    chain::budget::FeeBudgetStatus,
     client_state::{AnyClientState, IdentifiedAnyClientState},
     config::ChainConfig,
     connection::ConnectionMsgType,
@@ -123,6 +126,16 @@ pub enum ChainRequest {
         reply_to: ReplyTo<Vec<tendermint_rpc::endpoint::broadcast::tx_sync::Response>>,
     },
 
+    EstimateFee {
+        tracked_msgs: TrackedMsgs,
+        reply_to: ReplyTo<Fee>,
+    },
+
+    SimulateMessages {
+        tracked_msgs: TrackedMsgs,
+        reply_to: ReplyTo<u64>,
+    },
+
     Config {
         reply_to: ReplyTo<ChainConfig>,
     },
@@ -135,6 +148,10 @@ pub enum ChainRequest {
         reply_to: ReplyTo<AnySigningKeyPair>,
     },
 
+    GetKeys {
+        reply_to: ReplyTo<Vec<(String, AnySigningKeyPair)>>,
+    },
+
     AddKey {
         key_name: String,
         key: AnySigningKeyPair,
@@ -271,6 +288,18 @@ pub enum ChainRequest {
         reply_to: ReplyTo<(ChannelEnd, Option<MerkleProof>)>,
     },
 
+    QueryUpgrade {
+        request: QueryUpgradeRequest,
+        include_proof: IncludeProof,
+        reply_to: ReplyTo<(Upgrade, Option<MerkleProof>)>,
+    },
+
+    QueryUpgradeError {
+        request: QueryUpgradeErrorRequest,
+        include_proof: IncludeProof,
+        reply_to: ReplyTo<(ErrorReceipt, Option<MerkleProof>)>,
+    },
+
     QueryChannelClientState {
         request: QueryChannelClientStateRequest,
         reply_to: ReplyTo<Option<IdentifiedAnyClientState>>,
@@ -289,12 +318,20 @@ pub enum ChainRequest {
         reply_to: ReplyTo<Proofs>,
     },
 
+    BuildChannelUpgradeProofs {
+        port_id: PortId,
+        channel_id: ChannelId,
+        height: Height,
+        reply_to: ReplyTo<(Upgrade, Proofs)>,
+    },
+
     BuildPacketProofs {
         packet_type: PacketMsgType,
         port_id: PortId,
         channel_id: ChannelId,
         sequence: Sequence,
         height: Height,
+        purpose: ProofPurpose,
         reply_to: ReplyTo<Proofs>,
     },
 
@@ -358,6 +395,14 @@ pub enum ChainRequest {
         reply_to: ReplyTo<()>,
     },
 
+    SendCoins {
+        from_key_name: String,
+        to_address: String,
+        amount: u128,
+        denom: String,
+        reply_to: ReplyTo<()>,
+    },
+
     CrossChainQuery {
         request: Vec<CrossChainQueryRequest>,
         reply_to: ReplyTo<Vec<CrossChainQueryResponse>>,
@@ -367,6 +412,17 @@ pub enum ChainRequest {
         request: QueryIncentivizedPacketRequest,
         reply_to: ReplyTo<QueryIncentivizedPacketResponse>,
     },
+
+    QueryFeeBudget {
+        reply_to: ReplyTo<Option<FeeBudgetStatus>>,
+    },
+
+    SyncProofSequence {
+        counterparty_chain_id: ChainId,
+        counterparty_client_id: ClientId,
+        counterparty_client_state: AnyClientState,
+        reply_to: ReplyTo<()>,
+    },
 }
 
 pub trait ChainHandle: Clone + Display + Send + Sync + Debug + 'static {
@@ -400,12 +456,25 @@ pub trait ChainHandle: Clone + Display + Send + Sync + Debug + 'static {
         tracked_msgs: TrackedMsgs,
     ) -> Result<Vec<tendermint_rpc::endpoint::broadcast::tx_sync::Response>, Error>;
 
+    /// Estimate the fee the relayer would pay to submit the given messages
+    /// in a single transaction, by simulating it.
+    fn estimate_fee(&self, tracked_msgs: TrackedMsgs) -> Result<Fee, Error>;
+
+    /// Simulate the transaction the relayer would submit for the given messages,
+    /// without broadcasting it, and return the gas it uses, or the error which
+    /// made the simulation fail.
+    fn simulate_messages(&self, tracked_msgs: TrackedMsgs) -> Result<u64, Error>;
+
     fn get_signer(&self) -> Result<Signer, Error>;
 
     fn config(&self) -> Result<ChainConfig, Error>;
 
     fn get_key(&self) -> Result<AnySigningKeyPair, Error>;
 
+    /// Get all the keys signing transactions on this chain, along with their names,
+    /// starting with the main key.
+    fn get_keys(&self) -> Result<Vec<(String, AnySigningKeyPair)>, Error>;
+
     fn add_key(&self, key_name: String, key: AnySigningKeyPair) -> Result<(), Error>;
 
     /// Return the version of the IBC protocol that this chain is running, if known.
@@ -525,6 +594,22 @@ pub trait ChainHandle: Clone + Display + Send + Sync + Debug + 'static {
         include_proof: IncludeProof,
     ) -> Result<(ChannelEnd, Option<MerkleProof>), Error>;
 
+    /// Performs a query to retrieve the upgrade in progress for a given channel.
+    /// A proof can optionally be returned along with the result.
+    fn query_upgrade(
+        &self,
+        request: QueryUpgradeRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(Upgrade, Option<MerkleProof>), Error>;
+
+    /// Performs a query to retrieve the last upgrade error receipt of a given channel.
+    /// A proof can optionally be returned along with the result.
+    fn query_upgrade_error(
+        &self,
+        request: QueryUpgradeErrorRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(ErrorReceipt, Option<MerkleProof>), Error>;
+
     /// Performs a query to retrieve the client state for the channel associated
     /// with a given channel identifier.
     fn query_channel_client_state(
@@ -575,6 +660,13 @@ pub trait ChainHandle: Clone + Display + Send + Sync + Debug + 'static {
         height: Height,
     ) -> Result<Proofs, Error>;
 
+    fn build_channel_upgrade_proofs(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        height: Height,
+    ) -> Result<(Upgrade, Proofs), Error>;
+
     fn build_packet_proofs(
         &self,
         packet_type: PacketMsgType,
@@ -582,6 +674,7 @@ pub trait ChainHandle: Clone + Display + Send + Sync + Debug + 'static {
         channel_id: &ChannelId,
         sequence: Sequence,
         height: Height,
+        purpose: ProofPurpose,
     ) -> Result<Proofs, Error>;
 
     /// Performs a query to retrieve a stored packet commitment hash, stored on
@@ -669,6 +762,16 @@ pub trait ChainHandle: Clone + Display + Send + Sync + Debug + 'static {
         counterparty_payee: Signer,
     ) -> Result<(), Error>;
 
+    /// Send `amount` of `denom` from the account of the key `from_key_name`
+    /// to the account at `to_address` on this chain.
+    fn send_coins(
+        &self,
+        from_key_name: String,
+        to_address: String,
+        amount: u128,
+        denom: String,
+    ) -> Result<(), Error>;
+
     fn cross_chain_query(
         &self,
         request: Vec<CrossChainQueryRequest>,
@@ -678,4 +781,18 @@ pub trait ChainHandle: Clone + Display + Send + Sync + Debug + 'static {
         &self,
         request: QueryIncentivizedPacketRequest,
     ) -> Result<QueryIncentivizedPacketResponse, Error>;
+
+    /// Query the state of the fee budget of the chain, if one is configured.
+    fn query_fee_budget(&self) -> Result<Option<FeeBudgetStatus>, Error>;
+
+    /// Synchronise the sequence of the proofs built for the given client of this chain
+    /// with the state of that client, see [`ChainEndpoint::sync_proof_sequence`].
+    ///
+    /// [`ChainEndpoint::sync_proof_sequence`]: crate::chain::endpoint::ChainEndpoint::sync_proof_sequence
+    fn sync_proof_sequence(
+        &self,
+        counterparty_chain_id: ChainId,
+        counterparty_client_id: ClientId,
+        counterparty_client_state: AnyClientState,
+    ) -> Result<(), Error>;
 }
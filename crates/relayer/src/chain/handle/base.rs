@@ -3,6 +3,7 @@ use core::fmt::{Debug, Display, Error as FmtError, Formatter};
 use crossbeam_channel as channel;
 use tracing::Span;
 
+use ibc_proto::cosmos::tx::v1beta1::Fee;
 use ibc_proto::ibc::apps::fee::v1::{
     QueryIncentivizedPacketRequest, QueryIncentivizedPacketResponse,
 };
@@ -14,6 +15,7 @@ use ibc_relayer_types::{
         ics03_connection::version::Version,
         ics04_channel::channel::{ChannelEnd, IdentifiedChannelEnd},
         ics04_channel::packet::{PacketMsgType, Sequence},
+        ics04_channel::upgrade::{ErrorReceipt, Upgrade},
         ics23_commitment::{commitment::CommitmentPrefix, merkle::MerkleProof},
         ics24_host::identifier::ChainId,
         ics24_host::identifier::ChannelId,
@@ -26,7 +28,10 @@ use ibc_relayer_types::{
 
 use crate::{
     account::Balance,
-    chain::{client::ClientSettings, endpoint::ChainStatus, requests::*, tracking::TrackedMsgs},
+    chain::{
+        budget::FeeBudgetStatus, client::ClientSettings, endpoint::ChainStatus, requests::*,
+        tracking::TrackedMsgs,
+    },
     client_state::{AnyClientState, IdentifiedAnyClientState},
     config::ChainConfig,
     connection::ConnectionMsgType,
@@ -125,6 +130,20 @@ impl ChainHandle for BaseChainHandle {
         })
     }
 
+    fn estimate_fee(&self, tracked_msgs: TrackedMsgs) -> Result<Fee, Error> {
+        self.send(|reply_to| ChainRequest::EstimateFee {
+            tracked_msgs,
+            reply_to,
+        })
+    }
+
+    fn simulate_messages(&self, tracked_msgs: TrackedMsgs) -> Result<u64, Error> {
+        self.send(|reply_to| ChainRequest::SimulateMessages {
+            tracked_msgs,
+            reply_to,
+        })
+    }
+
     fn get_signer(&self) -> Result<Signer, Error> {
         self.send(|reply_to| ChainRequest::Signer { reply_to })
     }
@@ -137,6 +156,10 @@ impl ChainHandle for BaseChainHandle {
         self.send(|reply_to| ChainRequest::GetKey { reply_to })
     }
 
+    fn get_keys(&self) -> Result<Vec<(String, AnySigningKeyPair)>, Error> {
+        self.send(|reply_to| ChainRequest::GetKeys { reply_to })
+    }
+
     fn add_key(&self, key_name: String, key: AnySigningKeyPair) -> Result<(), Error> {
         self.send(|reply_to| ChainRequest::AddKey {
             key_name,
@@ -297,6 +320,30 @@ impl ChainHandle for BaseChainHandle {
         })
     }
 
+    fn query_upgrade(
+        &self,
+        request: QueryUpgradeRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(Upgrade, Option<MerkleProof>), Error> {
+        self.send(|reply_to| ChainRequest::QueryUpgrade {
+            request,
+            include_proof,
+            reply_to,
+        })
+    }
+
+    fn query_upgrade_error(
+        &self,
+        request: QueryUpgradeErrorRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(ErrorReceipt, Option<MerkleProof>), Error> {
+        self.send(|reply_to| ChainRequest::QueryUpgradeError {
+            request,
+            include_proof,
+            reply_to,
+        })
+    }
+
     fn query_channel_client_state(
         &self,
         request: QueryChannelClientStateRequest,
@@ -388,6 +435,20 @@ impl ChainHandle for BaseChainHandle {
         })
     }
 
+    fn build_channel_upgrade_proofs(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        height: Height,
+    ) -> Result<(Upgrade, Proofs), Error> {
+        self.send(|reply_to| ChainRequest::BuildChannelUpgradeProofs {
+            port_id: port_id.clone(),
+            channel_id: channel_id.clone(),
+            height,
+            reply_to,
+        })
+    }
+
     fn build_packet_proofs(
         &self,
         packet_type: PacketMsgType,
@@ -395,6 +456,7 @@ impl ChainHandle for BaseChainHandle {
         channel_id: &ChannelId,
         sequence: Sequence,
         height: Height,
+        purpose: ProofPurpose,
     ) -> Result<Proofs, Error> {
         self.send(|reply_to| ChainRequest::BuildPacketProofs {
             packet_type,
@@ -402,6 +464,7 @@ impl ChainHandle for BaseChainHandle {
             channel_id: channel_id.clone(),
             sequence,
             height,
+            purpose,
             reply_to,
         })
     }
@@ -502,6 +565,22 @@ impl ChainHandle for BaseChainHandle {
         })
     }
 
+    fn send_coins(
+        &self,
+        from_key_name: String,
+        to_address: String,
+        amount: u128,
+        denom: String,
+    ) -> Result<(), Error> {
+        self.send(|reply_to| ChainRequest::SendCoins {
+            from_key_name,
+            to_address,
+            amount,
+            denom,
+            reply_to,
+        })
+    }
+
     fn cross_chain_query(
         &self,
         request: Vec<CrossChainQueryRequest>,
@@ -515,4 +594,22 @@ impl ChainHandle for BaseChainHandle {
     ) -> Result<QueryIncentivizedPacketResponse, Error> {
         self.send(|reply_to| ChainRequest::QueryIncentivizedPacket { request, reply_to })
     }
+
+    fn query_fee_budget(&self) -> Result<Option<FeeBudgetStatus>, Error> {
+        self.send(|reply_to| ChainRequest::QueryFeeBudget { reply_to })
+    }
+
+    fn sync_proof_sequence(
+        &self,
+        counterparty_chain_id: ChainId,
+        counterparty_client_id: ClientId,
+        counterparty_client_state: AnyClientState,
+    ) -> Result<(), Error> {
+        self.send(|reply_to| ChainRequest::SyncProofSequence {
+            counterparty_chain_id,
+            counterparty_client_id,
+            counterparty_client_state,
+            reply_to,
+        })
+    }
 }
@@ -2,6 +2,7 @@ use core::fmt::{Display, Error as FmtError, Formatter};
 use crossbeam_channel as channel;
 use tracing::Span;
 
+use ibc_proto::cosmos::tx::v1beta1::Fee;
 use ibc_proto::ibc::apps::fee::v1::QueryIncentivizedPacketRequest;
 use ibc_proto::ibc::apps::fee::v1::QueryIncentivizedPacketResponse;
 use ibc_relayer_types::applications::ics31_icq::response::CrossChainQueryResponse;
@@ -12,6 +13,7 @@ use ibc_relayer_types::core::ics03_connection::version::Version;
 use ibc_relayer_types::core::ics04_channel::channel::ChannelEnd;
 use ibc_relayer_types::core::ics04_channel::channel::IdentifiedChannelEnd;
 use ibc_relayer_types::core::ics04_channel::packet::{PacketMsgType, Sequence};
+use ibc_relayer_types::core::ics04_channel::upgrade::{ErrorReceipt, Upgrade};
 use ibc_relayer_types::core::ics23_commitment::commitment::CommitmentPrefix;
 use ibc_relayer_types::core::ics23_commitment::merkle::MerkleProof;
 use ibc_relayer_types::core::ics24_host::identifier::{
@@ -23,6 +25,7 @@ use ibc_relayer_types::Height;
 
 use crate::account::Balance;
 use crate::cache::{Cache, CacheStatus};
+use crate::chain::budget::FeeBudgetStatus;
 use crate::chain::client::ClientSettings;
 use crate::chain::endpoint::{ChainStatus, HealthCheck};
 use crate::chain::handle::{ChainHandle, ChainRequest, Subscription};
@@ -106,6 +109,14 @@ impl<Handle: ChainHandle> ChainHandle for CachingChainHandle<Handle> {
         self.inner().send_messages_and_wait_check_tx(tracked_msgs)
     }
 
+    fn estimate_fee(&self, tracked_msgs: TrackedMsgs) -> Result<Fee, Error> {
+        self.inner().estimate_fee(tracked_msgs)
+    }
+
+    fn simulate_messages(&self, tracked_msgs: TrackedMsgs) -> Result<u64, Error> {
+        self.inner().simulate_messages(tracked_msgs)
+    }
+
     fn get_signer(&self) -> Result<Signer, Error> {
         self.inner().get_signer()
     }
@@ -118,6 +129,10 @@ impl<Handle: ChainHandle> ChainHandle for CachingChainHandle<Handle> {
         self.inner().get_key()
     }
 
+    fn get_keys(&self) -> Result<Vec<(String, AnySigningKeyPair)>, Error> {
+        self.inner().get_keys()
+    }
+
     fn add_key(&self, key_name: String, key: AnySigningKeyPair) -> Result<(), Error> {
         self.inner().add_key(key_name, key)
     }
@@ -334,6 +349,22 @@ impl<Handle: ChainHandle> ChainHandle for CachingChainHandle<Handle> {
         }
     }
 
+    fn query_upgrade(
+        &self,
+        request: QueryUpgradeRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(Upgrade, Option<MerkleProof>), Error> {
+        self.inner().query_upgrade(request, include_proof)
+    }
+
+    fn query_upgrade_error(
+        &self,
+        request: QueryUpgradeErrorRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(ErrorReceipt, Option<MerkleProof>), Error> {
+        self.inner().query_upgrade_error(request, include_proof)
+    }
+
     fn query_channel_client_state(
         &self,
         request: QueryChannelClientStateRequest,
@@ -404,6 +435,16 @@ impl<Handle: ChainHandle> ChainHandle for CachingChainHandle<Handle> {
             .build_channel_proofs(port_id, channel_id, height)
     }
 
+    fn build_channel_upgrade_proofs(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        height: Height,
+    ) -> Result<(Upgrade, Proofs), Error> {
+        self.inner()
+            .build_channel_upgrade_proofs(port_id, channel_id, height)
+    }
+
     fn build_packet_proofs(
         &self,
         packet_type: PacketMsgType,
@@ -411,9 +452,16 @@ impl<Handle: ChainHandle> ChainHandle for CachingChainHandle<Handle> {
         channel_id: &ChannelId,
         sequence: Sequence,
         height: Height,
+        purpose: ProofPurpose,
     ) -> Result<Proofs, Error> {
-        self.inner()
-            .build_packet_proofs(packet_type, port_id, channel_id, sequence, height)
+        self.inner().build_packet_proofs(
+            packet_type,
+            port_id,
+            channel_id,
+            sequence,
+            height,
+            purpose,
+        )
     }
 
     fn query_packet_commitment(
@@ -497,6 +545,17 @@ impl<Handle: ChainHandle> ChainHandle for CachingChainHandle<Handle> {
             .maybe_register_counterparty_payee(channel_id, port_id, counterparty_payee)
     }
 
+    fn send_coins(
+        &self,
+        from_key_name: String,
+        to_address: String,
+        amount: u128,
+        denom: String,
+    ) -> Result<(), Error> {
+        self.inner
+            .send_coins(from_key_name, to_address, amount, denom)
+    }
+
     fn cross_chain_query(
         &self,
         request: Vec<CrossChainQueryRequest>,
@@ -510,4 +569,21 @@ impl<Handle: ChainHandle> ChainHandle for CachingChainHandle<Handle> {
     ) -> Result<QueryIncentivizedPacketResponse, Error> {
         self.inner.query_incentivized_packet(request)
     }
+
+    fn query_fee_budget(&self) -> Result<Option<FeeBudgetStatus>, Error> {
+        self.inner.query_fee_budget()
+    }
+
+    fn sync_proof_sequence(
+        &self,
+        counterparty_chain_id: ChainId,
+        counterparty_client_id: ClientId,
+        counterparty_client_state: AnyClientState,
+    ) -> Result<(), Error> {
+        self.inner.sync_proof_sequence(
+            counterparty_chain_id,
+            counterparty_client_id,
+            counterparty_client_state,
+        )
+    }
 }
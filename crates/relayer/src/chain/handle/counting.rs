@@ -5,6 +5,7 @@ use std::sync::{Arc, RwLock, RwLockReadGuard};
 use crossbeam_channel as channel;
 use tracing::{debug, Span};
 
+use ibc_proto::cosmos::tx::v1beta1::Fee;
 use ibc_proto::ibc::apps::fee::v1::{
     QueryIncentivizedPacketRequest, QueryIncentivizedPacketResponse,
 };
@@ -16,6 +17,7 @@ use ibc_relayer_types::core::ics03_connection::version::Version;
 use ibc_relayer_types::core::ics04_channel::channel::ChannelEnd;
 use ibc_relayer_types::core::ics04_channel::channel::IdentifiedChannelEnd;
 use ibc_relayer_types::core::ics04_channel::packet::{PacketMsgType, Sequence};
+use ibc_relayer_types::core::ics04_channel::upgrade::{ErrorReceipt, Upgrade};
 use ibc_relayer_types::core::ics23_commitment::commitment::CommitmentPrefix;
 use ibc_relayer_types::core::ics23_commitment::merkle::MerkleProof;
 use ibc_relayer_types::core::ics24_host::identifier::{
@@ -26,6 +28,7 @@ use ibc_relayer_types::signer::Signer;
 use ibc_relayer_types::Height;
 
 use crate::account::Balance;
+use crate::chain::budget::FeeBudgetStatus;
 use crate::chain::client::ClientSettings;
 use crate::chain::endpoint::{ChainStatus, HealthCheck};
 use crate::chain::handle::{ChainHandle, ChainRequest, Subscription};
@@ -130,6 +133,16 @@ impl<Handle: ChainHandle> ChainHandle for CountingChainHandle<Handle> {
         self.inner().send_messages_and_wait_check_tx(tracked_msgs)
     }
 
+    fn estimate_fee(&self, tracked_msgs: TrackedMsgs) -> Result<Fee, Error> {
+        self.inc_metric("estimate_fee");
+        self.inner().estimate_fee(tracked_msgs)
+    }
+
+    fn simulate_messages(&self, tracked_msgs: TrackedMsgs) -> Result<u64, Error> {
+        self.inc_metric("simulate_messages");
+        self.inner().simulate_messages(tracked_msgs)
+    }
+
     fn get_signer(&self) -> Result<Signer, Error> {
         self.inc_metric("get_signer");
         self.inner().get_signer()
@@ -145,6 +158,11 @@ impl<Handle: ChainHandle> ChainHandle for CountingChainHandle<Handle> {
         self.inner().get_key()
     }
 
+    fn get_keys(&self) -> Result<Vec<(String, AnySigningKeyPair)>, Error> {
+        self.inc_metric("get_keys");
+        self.inner().get_keys()
+    }
+
     fn add_key(&self, key_name: String, key: AnySigningKeyPair) -> Result<(), Error> {
         self.inc_metric("add_key");
         self.inner().add_key(key_name, key)
@@ -306,6 +324,24 @@ impl<Handle: ChainHandle> ChainHandle for CountingChainHandle<Handle> {
         self.inner().query_channel(request, include_proof)
     }
 
+    fn query_upgrade(
+        &self,
+        request: QueryUpgradeRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(Upgrade, Option<MerkleProof>), Error> {
+        self.inc_metric("query_upgrade");
+        self.inner().query_upgrade(request, include_proof)
+    }
+
+    fn query_upgrade_error(
+        &self,
+        request: QueryUpgradeErrorRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(ErrorReceipt, Option<MerkleProof>), Error> {
+        self.inc_metric("query_upgrade_error");
+        self.inner().query_upgrade_error(request, include_proof)
+    }
+
     fn query_channel_client_state(
         &self,
         request: QueryChannelClientStateRequest,
@@ -383,6 +419,17 @@ impl<Handle: ChainHandle> ChainHandle for CountingChainHandle<Handle> {
             .build_channel_proofs(port_id, channel_id, height)
     }
 
+    fn build_channel_upgrade_proofs(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        height: Height,
+    ) -> Result<(Upgrade, Proofs), Error> {
+        self.inc_metric("build_channel_upgrade_proofs");
+        self.inner()
+            .build_channel_upgrade_proofs(port_id, channel_id, height)
+    }
+
     fn build_packet_proofs(
         &self,
         packet_type: PacketMsgType,
@@ -390,10 +437,17 @@ impl<Handle: ChainHandle> ChainHandle for CountingChainHandle<Handle> {
         channel_id: &ChannelId,
         sequence: Sequence,
         height: Height,
+        purpose: ProofPurpose,
     ) -> Result<Proofs, Error> {
         self.inc_metric("build_packet_proofs");
-        self.inner()
-            .build_packet_proofs(packet_type, port_id, channel_id, sequence, height)
+        self.inner().build_packet_proofs(
+            packet_type,
+            port_id,
+            channel_id,
+            sequence,
+            height,
+            purpose,
+        )
     }
 
     fn query_packet_commitment(
@@ -488,6 +542,18 @@ impl<Handle: ChainHandle> ChainHandle for CountingChainHandle<Handle> {
             .maybe_register_counterparty_payee(channel_id, port_id, counterparty_payee)
     }
 
+    fn send_coins(
+        &self,
+        from_key_name: String,
+        to_address: String,
+        amount: u128,
+        denom: String,
+    ) -> Result<(), Error> {
+        self.inc_metric("send_coins");
+        self.inner
+            .send_coins(from_key_name, to_address, amount, denom)
+    }
+
     fn cross_chain_query(
         &self,
         request: Vec<CrossChainQueryRequest>,
@@ -503,4 +569,23 @@ impl<Handle: ChainHandle> ChainHandle for CountingChainHandle<Handle> {
         self.inc_metric("query_incentivized_packet");
         self.inner.query_incentivized_packet(request)
     }
+
+    fn query_fee_budget(&self) -> Result<Option<FeeBudgetStatus>, Error> {
+        self.inc_metric("query_fee_budget");
+        self.inner.query_fee_budget()
+    }
+
+    fn sync_proof_sequence(
+        &self,
+        counterparty_chain_id: ChainId,
+        counterparty_client_id: ClientId,
+        counterparty_client_state: AnyClientState,
+    ) -> Result<(), Error> {
+        self.inc_metric("sync_proof_sequence");
+        self.inner.sync_proof_sequence(
+            counterparty_chain_id,
+            counterparty_client_id,
+            counterparty_client_state,
+        )
+    }
 }
@@ -0,0 +1,21 @@
+//! An in-process chain, which keeps its IBC state in memory and hosts mock light clients.
+//!
+//! A mock chain lets the relayer run handshakes and relay packets without any full node,
+//! which makes for fast and deterministic tests of the relaying logic.
+//! It produces a new block for every transaction it is given, and an empty block
+//! whenever its status is queried. Blocks are `BLOCK_TIME` apart, starting from
+//! the time the chain is bootstrapped, such that the heights and timestamps of a
+//! mock chain only depend on the requests it serves, and not on the wall clock.
+//! Mock chains do not verify the proofs of the messages they receive, and return
+//! placeholder proofs when queried with `IncludeProof::Yes`.
+
+pub mod error;
+
+#[cfg(any(test, feature = "mock"))]
+pub mod store;
+
+#[cfg(any(test, feature = "mock"))]
+mod endpoint;
+
+#[cfg(any(test, feature = "mock"))]
+pub use endpoint::{MockChain, BLOCK_TIME};
@@ -0,0 +1,1303 @@
+use alloc::sync::Arc;
+use core::time::Duration;
+use std::collections::BTreeMap;
+
+use ibc_proto::cosmos::tx::v1beta1::Fee;
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::apps::fee::v1::{
+    QueryIncentivizedPacketRequest, QueryIncentivizedPacketResponse,
+};
+use sha2::{Digest, Sha256};
+use tendermint::abci::Code;
+use tendermint::Hash as TxHash;
+use tendermint_rpc::endpoint::broadcast::tx_sync::Response as TxResponse;
+use tokio::runtime::Runtime as TokioRuntime;
+
+use ibc_relayer_types::applications::ics31_icq::response::CrossChainQueryResponse;
+use ibc_relayer_types::core::ics02_client::error::Error as ClientError;
+use ibc_relayer_types::core::ics02_client::events::{NewBlock, UpdateClient};
+use ibc_relayer_types::core::ics03_connection::connection::{
+    ConnectionEnd, IdentifiedConnectionEnd,
+};
+use ibc_relayer_types::core::ics04_channel::channel::{ChannelEnd, IdentifiedChannelEnd, Ordering};
+use ibc_relayer_types::core::ics04_channel::packet::Sequence;
+use ibc_relayer_types::core::ics04_channel::upgrade::{ErrorReceipt, Upgrade};
+use ibc_relayer_types::core::ics23_commitment::commitment::CommitmentPrefix;
+use ibc_relayer_types::core::ics23_commitment::merkle::MerkleProof;
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, ConnectionId, PortId};
+use ibc_relayer_types::events::{IbcEvent, WithBlockDataType};
+use ibc_relayer_types::mock::client_state::{MockClientState, MOCK_CHAIN_NAME};
+use ibc_relayer_types::mock::consensus_state::MockConsensusState;
+use ibc_relayer_types::mock::header::MockHeader;
+use ibc_relayer_types::signer::Signer;
+use ibc_relayer_types::timestamp::Timestamp;
+use ibc_relayer_types::Height as ICSHeight;
+
+use crate::account::Balance;
+use crate::chain::budget::FeeBudgetStatus;
+use crate::chain::client::ClientSettings;
+use crate::chain::endpoint::{ChainEndpoint, ChainStatus, HealthCheck};
+use crate::chain::handle::Subscription;
+use crate::chain::requests::*;
+use crate::chain::tracking::{TrackedMsgs, TrackingId};
+use crate::client_state::{AnyClientState, IdentifiedAnyClientState};
+use crate::config::ChainConfig;
+use crate::consensus_state::AnyConsensusState;
+use crate::denom::DenomTrace;
+use crate::error::Error;
+use crate::event::bus::EventBus;
+use crate::event::source::{EventBatch, Result as MonitorResult};
+use crate::event::IbcEventWithHeight;
+use crate::keyring::{KeyRing, Secp256k1KeyPair, SigningKeyPair};
+use crate::misbehaviour::MisbehaviourEvidence;
+use crate::util::lock::{LockExt, RwArc};
+
+use super::error::Error as MockChainError;
+use super::store::{HostContext, IbcStore};
+
+/// The time between two consecutive blocks of a mock chain.
+pub const BLOCK_TIME: Duration = Duration::from_millis(100);
+
+/// A block of a mock chain, along with the IBC state resulting from its execution.
+#[derive(Clone, Debug)]
+struct MockBlock {
+    header: MockHeader,
+    store: IbcStore,
+    events: Vec<IbcEvent>,
+}
+
+/// The outcome of a transaction executed by a mock chain.
+#[derive(Clone, Debug)]
+struct MockTx {
+    height: ICSHeight,
+    code: Code,
+    events: Vec<IbcEvent>,
+}
+
+struct MockState {
+    chain_id: ChainId,
+    blocks: Vec<MockBlock>,
+    txs: BTreeMap<TxHash, MockTx>,
+    event_bus: EventBus<Arc<MonitorResult<EventBatch>>>,
+}
+
+impl MockState {
+    fn new(chain_id: ChainId) -> Self {
+        let height = ICSHeight::new(chain_id.version(), 1).expect("non-zero height");
+
+        let genesis = MockBlock {
+            header: MockHeader::new(height),
+            store: IbcStore::default(),
+            events: Vec::new(),
+        };
+
+        Self {
+            chain_id,
+            blocks: vec![genesis],
+            txs: BTreeMap::new(),
+            event_bus: EventBus::new(),
+        }
+    }
+
+    fn latest(&self) -> &MockBlock {
+        self.blocks
+            .last()
+            .expect("mock chains always have a genesis block")
+    }
+
+    fn block(&self, height: QueryHeight) -> Result<&MockBlock, Error> {
+        match height {
+            QueryHeight::Latest => Ok(self.latest()),
+            QueryHeight::Specific(height) => self
+                .blocks
+                .iter()
+                .rev()
+                .find(|block| block.header.height == height)
+                .ok_or_else(|| Error::mock_chain(MockChainError::block_not_found(height))),
+        }
+    }
+
+    /// The header of the next block, [`BLOCK_TIME`] after the latest one.
+    fn next_header(&self) -> MockHeader {
+        let latest = self.latest().header;
+        let timestamp = (latest.timestamp + BLOCK_TIME).expect("timestamp overflow");
+
+        MockHeader::new(latest.height.increment()).with_timestamp(timestamp)
+    }
+
+    /// Produce an empty block.
+    fn tick(&mut self) {
+        let header = self.next_header();
+        let store = self.latest().store.clone();
+
+        self.commit_block(header, store, Vec::new());
+    }
+
+    /// Execute the given messages atomically, in a single transaction of a new block,
+    /// and return the hash of the transaction.
+    fn execute_tx(&mut self, msgs: Vec<Any>) -> TxHash {
+        let header = self.next_header();
+        let host = HostContext {
+            height: header.height,
+            timestamp: header.timestamp,
+        };
+
+        let hash = tx_hash(&header, &msgs);
+        let message_count = msgs.len();
+        let mut store = self.latest().store.clone();
+
+        let result = msgs.into_iter().try_fold(Vec::new(), |mut events, msg| {
+            events.extend(store.deliver(&host, msg)?);
+            Ok::<_, MockChainError>(events)
+        });
+
+        match result {
+            Ok(events) => {
+                let tx = MockTx {
+                    height: header.height,
+                    code: Code::Ok,
+                    events: events.clone(),
+                };
+
+                self.txs.insert(hash, tx);
+                self.commit_block(header, store, events);
+            }
+            Err(e) => {
+                let event = IbcEvent::ChainError(format!(
+                    "deliver_tx for {hash} reports error: code=1, log={:?}",
+                    e.to_string()
+                ));
+
+                let tx = MockTx {
+                    height: header.height,
+                    code: Code::Err(1.try_into().expect("non-zero code")),
+                    events: vec![event; message_count],
+                };
+
+                // A failed transaction leaves the state untouched
+                let store = self.latest().store.clone();
+
+                self.txs.insert(hash, tx);
+                self.commit_block(header, store, Vec::new());
+            }
+        }
+
+        hash
+    }
+
+    fn commit_block(&mut self, header: MockHeader, store: IbcStore, events: Vec<IbcEvent>) {
+        let height = header.height;
+
+        self.blocks.push(MockBlock {
+            header,
+            store,
+            events: events.clone(),
+        });
+
+        let events = core::iter::once(IbcEvent::NewBlock(NewBlock::new(height)))
+            .chain(events)
+            .map(|event| IbcEventWithHeight::new(event, height))
+            .collect();
+
+        self.event_bus.broadcast(Arc::new(Ok(EventBatch {
+            chain_id: self.chain_id.clone(),
+            tracking_id: TrackingId::new_uuid(),
+            height,
+            events,
+        })));
+    }
+}
+
+/// The hash of a transaction, computed from the messages it contains and the block it is included in.
+fn tx_hash(header: &MockHeader, msgs: &[Any]) -> TxHash {
+    let mut hasher = Sha256::new();
+    hasher.update(header.height.to_string());
+
+    for msg in msgs {
+        hasher.update(&msg.type_url);
+        hasher.update(&msg.value);
+    }
+
+    TxHash::Sha256(hasher.finalize().into())
+}
+
+fn placeholder_proof(include_proof: IncludeProof) -> Option<MerkleProof> {
+    match include_proof {
+        IncludeProof::Yes => Some(MerkleProof {
+            proofs: vec![Default::default()],
+        }),
+        IncludeProof::No => None,
+    }
+}
+
+pub struct MockChain {
+    config: ChainConfig,
+    keybase: KeyRing<Secp256k1KeyPair>,
+    state: RwArc<MockState>,
+}
+
+impl MockChain {
+    /// Query the IBC state at the given height.
+    fn query_store<T>(
+        &self,
+        height: QueryHeight,
+        f: impl FnOnce(&IbcStore) -> Result<T, MockChainError>,
+    ) -> Result<T, Error> {
+        let state = self.state.acquire_read();
+        let block = state.block(height)?;
+
+        f(&block.store).map_err(Error::mock_chain)
+    }
+
+    fn execute(&mut self, tracked_msgs: TrackedMsgs) -> (TxHash, MockTx) {
+        let mut state = self.state.acquire_write();
+        let hash = state.execute_tx(tracked_msgs.msgs);
+        let tx = state.txs[&hash].clone();
+
+        (hash, tx)
+    }
+
+    fn unsupported<T>(&self, operation: &str) -> Result<T, Error> {
+        Err(Error::mock_chain(MockChainError::unsupported(
+            operation.to_string(),
+        )))
+    }
+}
+
+impl ChainEndpoint for MockChain {
+    type LightBlock = MockHeader;
+    type Header = MockHeader;
+    type ConsensusState = MockConsensusState;
+    type ClientState = MockClientState;
+    type Time = Timestamp;
+    type SigningKeyPair = Secp256k1KeyPair;
+
+    fn config(&self) -> &ChainConfig {
+        &self.config
+    }
+
+    fn bootstrap(config: ChainConfig, _rt: Arc<TokioRuntime>) -> Result<Self, Error> {
+        // The clients of a mock chain derive its identifier from the revision number
+        // of the headers they hold, see `MockClientState::chain_id`.
+        if config.id != ChainId::new(MOCK_CHAIN_NAME.to_string(), config.id.version()) {
+            return Err(Error::mock_chain(MockChainError::invalid_chain_id(
+                config.id,
+            )));
+        }
+
+        let keybase =
+            KeyRing::from_config(config.key_store_type, &config).map_err(Error::key_base)?;
+
+        let state = RwArc::new_lock(MockState::new(config.id.clone()));
+
+        Ok(Self {
+            config,
+            keybase,
+            state,
+        })
+    }
+
+    fn shutdown(self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn health_check(&self) -> Result<HealthCheck, Error> {
+        Ok(HealthCheck::Healthy)
+    }
+
+    fn subscribe(&mut self) -> Result<Subscription, Error> {
+        Ok(self.state.acquire_write().event_bus.subscribe())
+    }
+
+    fn keybase(&self) -> &KeyRing<Self::SigningKeyPair> {
+        &self.keybase
+    }
+
+    fn keybase_mut(&mut self) -> &mut KeyRing<Self::SigningKeyPair> {
+        &mut self.keybase
+    }
+
+    /// Mock chains do not check signatures, hence the relayer
+    /// can submit messages without having a key for the chain.
+    fn get_signer(&self) -> Result<Signer, Error> {
+        match self.keybase.get_key(&self.config.key_name) {
+            Ok(key_pair) => key_pair
+                .account()
+                .parse()
+                .map_err(|e| Error::ics02(ClientError::signer(e))),
+            Err(_) => Ok(Signer::dummy()),
+        }
+    }
+
+    fn ibc_version(&self) -> Result<Option<semver::Version>, Error> {
+        Ok(None)
+    }
+
+    fn send_messages_and_wait_commit(
+        &mut self,
+        tracked_msgs: TrackedMsgs,
+    ) -> Result<Vec<IbcEventWithHeight>, Error> {
+        let (_, tx) = self.execute(tracked_msgs);
+
+        Ok(tx
+            .events
+            .into_iter()
+            .map(|event| IbcEventWithHeight::new(event, tx.height))
+            .collect())
+    }
+
+    fn send_messages_and_wait_check_tx(
+        &mut self,
+        tracked_msgs: TrackedMsgs,
+    ) -> Result<Vec<TxResponse>, Error> {
+        let (hash, tx) = self.execute(tracked_msgs);
+
+        Ok(vec![TxResponse {
+            code: tx.code,
+            data: Default::default(),
+            log: String::new(),
+            hash,
+        }])
+    }
+
+    fn estimate_fee(&mut self, tracked_msgs: TrackedMsgs) -> Result<Fee, Error> {
+        self.simulate_messages(tracked_msgs)?;
+
+        Ok(Fee::default())
+    }
+
+    fn simulate_messages(&mut self, tracked_msgs: TrackedMsgs) -> Result<u64, Error> {
+        let state = self.state.acquire_read();
+        let header = state.next_header();
+        let host = HostContext {
+            height: header.height,
+            timestamp: header.timestamp,
+        };
+
+        let mut store = state.latest().store.clone();
+
+        for msg in tracked_msgs.msgs {
+            store.deliver(&host, msg).map_err(Error::mock_chain)?;
+        }
+
+        Ok(0)
+    }
+
+    fn verify_header(
+        &mut self,
+        _trusted: ICSHeight,
+        target: ICSHeight,
+        _client_state: &AnyClientState,
+    ) -> Result<Self::LightBlock, Error> {
+        let state = self.state.acquire_read();
+        let block = state.block(QueryHeight::Specific(target))?;
+
+        Ok(block.header)
+    }
+
+    fn check_misbehaviour(
+        &mut self,
+        _update: &UpdateClient,
+        _client_state: &AnyClientState,
+    ) -> Result<Option<MisbehaviourEvidence>, Error> {
+        Ok(None)
+    }
+
+    fn query_balance(
+        &self,
+        _key_name: Option<&str>,
+        _denom: Option<&str>,
+    ) -> Result<Balance, Error> {
+        self.unsupported("balance queries")
+    }
+
+    fn query_all_balances(&self, _key_name: Option<&str>) -> Result<Vec<Balance>, Error> {
+        self.unsupported("balance queries")
+    }
+
+    fn query_denom_trace(&self, _hash: String) -> Result<DenomTrace, Error> {
+        self.unsupported("denomination trace queries")
+    }
+
+    fn query_commitment_prefix(&self) -> Result<CommitmentPrefix, Error> {
+        CommitmentPrefix::try_from(self.config.store_prefix.as_bytes().to_vec())
+            .map_err(|_| Error::ics02(ClientError::empty_prefix()))
+    }
+
+    /// Every query of the status of a mock chain produces a new block,
+    /// such that the chain makes progress whenever the relayer waits for it.
+    fn query_application_status(&self) -> Result<ChainStatus, Error> {
+        let mut state = self.state.acquire_write();
+        state.tick();
+
+        let header = state.latest().header;
+
+        Ok(ChainStatus {
+            height: header.height,
+            timestamp: header.timestamp,
+        })
+    }
+
+    fn query_clients(
+        &self,
+        _request: QueryClientStatesRequest,
+    ) -> Result<Vec<IdentifiedAnyClientState>, Error> {
+        self.query_store(QueryHeight::Latest, |store| {
+            Ok(store
+                .clients
+                .iter()
+                .map(|(client_id, record)| {
+                    IdentifiedAnyClientState::new(client_id.clone(), record.client_state.into())
+                })
+                .collect())
+        })
+    }
+
+    fn query_client_state(
+        &self,
+        request: QueryClientStateRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(AnyClientState, Option<MerkleProof>), Error> {
+        let client_state = self.query_store(request.height, |store| {
+            Ok(store.client(&request.client_id)?.client_state)
+        })?;
+
+        Ok((client_state.into(), placeholder_proof(include_proof)))
+    }
+
+    fn query_consensus_state(
+        &self,
+        request: QueryConsensusStateRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(AnyConsensusState, Option<MerkleProof>), Error> {
+        let consensus_state = self.query_store(request.query_height, |store| {
+            store
+                .client(&request.client_id)?
+                .consensus_states
+                .get(&request.consensus_height)
+                .cloned()
+                .ok_or_else(|| {
+                    MockChainError::consensus_state_not_found(
+                        request.client_id.clone(),
+                        request.consensus_height,
+                    )
+                })
+        })?;
+
+        Ok((consensus_state.into(), placeholder_proof(include_proof)))
+    }
+
+    fn query_consensus_state_heights(
+        &self,
+        request: QueryConsensusStateHeightsRequest,
+    ) -> Result<Vec<ICSHeight>, Error> {
+        self.query_store(QueryHeight::Latest, |store| {
+            Ok(store
+                .client(&request.client_id)?
+                .consensus_states
+                .keys()
+                .copied()
+                .collect())
+        })
+    }
+
+    fn query_upgraded_client_state(
+        &self,
+        _request: QueryUpgradedClientStateRequest,
+    ) -> Result<(AnyClientState, MerkleProof), Error> {
+        self.unsupported("chain upgrades")
+    }
+
+    fn query_upgraded_consensus_state(
+        &self,
+        _request: QueryUpgradedConsensusStateRequest,
+    ) -> Result<(AnyConsensusState, MerkleProof), Error> {
+        self.unsupported("chain upgrades")
+    }
+
+    fn query_connections(
+        &self,
+        _request: QueryConnectionsRequest,
+    ) -> Result<Vec<IdentifiedConnectionEnd>, Error> {
+        self.query_store(QueryHeight::Latest, |store| {
+            Ok(store
+                .connections
+                .iter()
+                .map(|(connection_id, connection_end)| {
+                    IdentifiedConnectionEnd::new(connection_id.clone(), connection_end.clone())
+                })
+                .collect())
+        })
+    }
+
+    fn query_client_connections(
+        &self,
+        request: QueryClientConnectionsRequest,
+    ) -> Result<Vec<ConnectionId>, Error> {
+        self.query_store(QueryHeight::Latest, |store| {
+            Ok(store
+                .connections
+                .iter()
+                .filter(|(_, connection_end)| connection_end.client_id() == &request.client_id)
+                .map(|(connection_id, _)| connection_id.clone())
+                .collect())
+        })
+    }
+
+    fn query_connection(
+        &self,
+        request: QueryConnectionRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(ConnectionEnd, Option<MerkleProof>), Error> {
+        let connection_end = self.query_store(request.height, |store| {
+            store.connection(&request.connection_id).cloned()
+        })?;
+
+        Ok((connection_end, placeholder_proof(include_proof)))
+    }
+
+    fn query_connection_channels(
+        &self,
+        request: QueryConnectionChannelsRequest,
+    ) -> Result<Vec<IdentifiedChannelEnd>, Error> {
+        let channels = self.query_channels(QueryChannelsRequest { pagination: None })?;
+
+        Ok(channels
+            .into_iter()
+            .filter(|channel| {
+                channel.channel_end.connection_hops().first() == Some(&request.connection_id)
+            })
+            .collect())
+    }
+
+    fn query_channels(
+        &self,
+        _request: QueryChannelsRequest,
+    ) -> Result<Vec<IdentifiedChannelEnd>, Error> {
+        self.query_store(QueryHeight::Latest, |store| {
+            Ok(store
+                .channels
+                .iter()
+                .map(|((port_id, channel_id), channel_end)| {
+                    IdentifiedChannelEnd::new(
+                        port_id.clone(),
+                        channel_id.clone(),
+                        channel_end.clone(),
+                    )
+                })
+                .collect())
+        })
+    }
+
+    fn query_channel(
+        &self,
+        request: QueryChannelRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(ChannelEnd, Option<MerkleProof>), Error> {
+        let channel_end = self.query_store(request.height, |store| {
+            store
+                .channel(&request.port_id, &request.channel_id)
+                .cloned()
+        })?;
+
+        Ok((channel_end, placeholder_proof(include_proof)))
+    }
+
+    fn query_upgrade(
+        &self,
+        _request: QueryUpgradeRequest,
+        _include_proof: IncludeProof,
+    ) -> Result<(Upgrade, Option<MerkleProof>), Error> {
+        self.unsupported("channel upgrades")
+    }
+
+    fn query_upgrade_error(
+        &self,
+        _request: QueryUpgradeErrorRequest,
+        _include_proof: IncludeProof,
+    ) -> Result<(ErrorReceipt, Option<MerkleProof>), Error> {
+        self.unsupported("channel upgrades")
+    }
+
+    fn query_channel_client_state(
+        &self,
+        request: QueryChannelClientStateRequest,
+    ) -> Result<Option<IdentifiedAnyClientState>, Error> {
+        self.query_store(QueryHeight::Latest, |store| {
+            let channel_end = store.channel(&request.port_id, &request.channel_id)?;
+
+            let Some(connection_id) = channel_end.connection_hops().first() else {
+                return Ok(None);
+            };
+
+            let client_id = store.connection(connection_id)?.client_id().clone();
+            let client_state = store.client(&client_id)?.client_state;
+
+            Ok(Some(IdentifiedAnyClientState::new(
+                client_id,
+                client_state.into(),
+            )))
+        })
+    }
+
+    fn query_packet_commitment(
+        &self,
+        request: QueryPacketCommitmentRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(Vec<u8>, Option<MerkleProof>), Error> {
+        let commitment = self.query_store(request.height, |store| {
+            Ok(store
+                .packet_commitments
+                .get(&(request.port_id, request.channel_id, request.sequence))
+                .cloned()
+                .unwrap_or_default())
+        })?;
+
+        Ok((commitment, placeholder_proof(include_proof)))
+    }
+
+    fn query_packet_commitments(
+        &self,
+        request: QueryPacketCommitmentsRequest,
+    ) -> Result<(Vec<Sequence>, ICSHeight), Error> {
+        let state = self.state.acquire_read();
+        let block = state.latest();
+
+        let sequences = block
+            .store
+            .packet_commitments
+            .keys()
+            .filter(|(port_id, channel_id, _)| {
+                port_id == &request.port_id && channel_id == &request.channel_id
+            })
+            .map(|(_, _, sequence)| *sequence)
+            .collect();
+
+        Ok((sequences, block.header.height))
+    }
+
+    fn query_packet_receipt(
+        &self,
+        request: QueryPacketReceiptRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(Vec<u8>, Option<MerkleProof>), Error> {
+        let receipt = self.query_store(request.height, |store| {
+            let received = store.packet_receipts.contains(&(
+                request.port_id,
+                request.channel_id,
+                request.sequence,
+            ));
+
+            Ok(if received { vec![1] } else { Vec::new() })
+        })?;
+
+        Ok((receipt, placeholder_proof(include_proof)))
+    }
+
+    fn query_unreceived_packets(
+        &self,
+        request: QueryUnreceivedPacketsRequest,
+    ) -> Result<Vec<Sequence>, Error> {
+        self.query_store(QueryHeight::Latest, |store| {
+            let channel_end = store.channel(&request.port_id, &request.channel_id)?;
+            let key = (request.port_id.clone(), request.channel_id.clone());
+
+            let is_received = |sequence: &Sequence| {
+                if channel_end.order_matches(&Ordering::Ordered) {
+                    store
+                        .next_sequence_recv
+                        .get(&key)
+                        .map_or(false, |next| sequence < next)
+                } else {
+                    store.packet_receipts.contains(&(
+                        request.port_id.clone(),
+                        request.channel_id.clone(),
+                        *sequence,
+                    ))
+                }
+            };
+
+            Ok(request
+                .packet_commitment_sequences
+                .iter()
+                .filter(|sequence| !is_received(sequence))
+                .copied()
+                .collect())
+        })
+    }
+
+    fn query_packet_acknowledgement(
+        &self,
+        request: QueryPacketAcknowledgementRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(Vec<u8>, Option<MerkleProof>), Error> {
+        let ack = self.query_store(request.height, |store| {
+            Ok(store
+                .packet_acks
+                .get(&(request.port_id, request.channel_id, request.sequence))
+                .cloned()
+                .unwrap_or_default())
+        })?;
+
+        Ok((ack, placeholder_proof(include_proof)))
+    }
+
+    fn query_packet_acknowledgements(
+        &self,
+        request: QueryPacketAcknowledgementsRequest,
+    ) -> Result<(Vec<Sequence>, ICSHeight), Error> {
+        let state = self.state.acquire_read();
+        let block = state.latest();
+
+        let sequences = block
+            .store
+            .packet_acks
+            .keys()
+            .filter(|(port_id, channel_id, sequence)| {
+                port_id == &request.port_id
+                    && channel_id == &request.channel_id
+                    && (request.packet_commitment_sequences.is_empty()
+                        || request.packet_commitment_sequences.contains(sequence))
+            })
+            .map(|(_, _, sequence)| *sequence)
+            .collect();
+
+        Ok((sequences, block.header.height))
+    }
+
+    fn query_unreceived_acknowledgements(
+        &self,
+        request: QueryUnreceivedAcksRequest,
+    ) -> Result<Vec<Sequence>, Error> {
+        self.query_store(QueryHeight::Latest, |store| {
+            Ok(request
+                .packet_ack_sequences
+                .iter()
+                .filter(|sequence| {
+                    store.packet_commitments.contains_key(&(
+                        request.port_id.clone(),
+                        request.channel_id.clone(),
+                        **sequence,
+                    ))
+                })
+                .copied()
+                .collect())
+        })
+    }
+
+    fn query_next_sequence_receive(
+        &self,
+        request: QueryNextSequenceReceiveRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(Sequence, Option<MerkleProof>), Error> {
+        let sequence = self.query_store(request.height, |store| {
+            store
+                .next_sequence_recv
+                .get(&(request.port_id.clone(), request.channel_id.clone()))
+                .copied()
+                .ok_or_else(|| {
+                    MockChainError::channel_not_found(
+                        request.port_id.clone(),
+                        request.channel_id.clone(),
+                    )
+                })
+        })?;
+
+        Ok((sequence, placeholder_proof(include_proof)))
+    }
+
+    fn query_txs(&self, request: QueryTxRequest) -> Result<Vec<IbcEventWithHeight>, Error> {
+        let state = self.state.acquire_read();
+
+        match request {
+            QueryTxRequest::Transaction(tx) => Ok(state
+                .txs
+                .get(&tx.0)
+                .map(|tx| {
+                    tx.events
+                        .iter()
+                        .map(|event| IbcEventWithHeight::new(event.clone(), tx.height))
+                        .collect()
+                })
+                .unwrap_or_default()),
+
+            QueryTxRequest::Client(request) => {
+                let max_height = match request.query_height {
+                    QueryHeight::Latest => state.latest().header.height,
+                    QueryHeight::Specific(height) => height,
+                };
+
+                let matches = |event: &IbcEvent| match (event, &request.event_id) {
+                    (IbcEvent::CreateClient(e), WithBlockDataType::CreateClient) => {
+                        e.client_id() == &request.client_id
+                            && e.0.consensus_height == request.consensus_height
+                    }
+                    (IbcEvent::UpdateClient(e), WithBlockDataType::UpdateClient) => {
+                        e.client_id() == &request.client_id
+                            && e.consensus_height() == request.consensus_height
+                    }
+                    _ => false,
+                };
+
+                // Return the latest matching event, as a full node would
+                let found = state
+                    .blocks
+                    .iter()
+                    .rev()
+                    .filter(|block| block.header.height <= max_height)
+                    .find_map(|block| {
+                        block
+                            .events
+                            .iter()
+                            .find(|event| matches(event))
+                            .map(|event| {
+                                IbcEventWithHeight::new(event.clone(), block.header.height)
+                            })
+                    });
+
+                Ok(found.into_iter().collect())
+            }
+        }
+    }
+
+    fn query_packet_events(
+        &self,
+        request: QueryPacketEventDataRequest,
+    ) -> Result<Vec<IbcEventWithHeight>, Error> {
+        let state = self.state.acquire_read();
+
+        let in_range = |height: ICSHeight| match request.height {
+            Qualified::Equal(QueryHeight::Specific(h)) => height == h,
+            Qualified::SmallerEqual(QueryHeight::Specific(h)) => height <= h,
+            Qualified::Equal(QueryHeight::Latest) => height == state.latest().header.height,
+            Qualified::SmallerEqual(QueryHeight::Latest) => true,
+        };
+
+        let matches = |event: &IbcEvent| {
+            let is_requested_type = matches!(
+                (event, &request.event_id),
+                (IbcEvent::SendPacket(_), WithBlockDataType::SendPacket)
+                    | (
+                        IbcEvent::WriteAcknowledgement(_),
+                        WithBlockDataType::WriteAck
+                    )
+            );
+
+            is_requested_type
+                && event.packet().map_or(false, |packet| {
+                    packet.source_port == request.source_port_id
+                        && packet.source_channel == request.source_channel_id
+                        && packet.destination_port == request.destination_port_id
+                        && packet.destination_channel == request.destination_channel_id
+                        && request.sequences.contains(&packet.sequence)
+                })
+        };
+
+        Ok(state
+            .blocks
+            .iter()
+            .filter(|block| in_range(block.header.height))
+            .flat_map(|block| {
+                block
+                    .events
+                    .iter()
+                    .filter(|event| matches(event))
+                    .map(|event| IbcEventWithHeight::new(event.clone(), block.header.height))
+            })
+            .collect())
+    }
+
+    fn query_host_consensus_state(
+        &self,
+        request: QueryHostConsensusStateRequest,
+    ) -> Result<Self::ConsensusState, Error> {
+        let state = self.state.acquire_read();
+        let block = state.block(request.height)?;
+
+        Ok(MockConsensusState::new(block.header))
+    }
+
+    fn build_client_state(
+        &self,
+        height: ICSHeight,
+        _settings: ClientSettings,
+    ) -> Result<Self::ClientState, Error> {
+        let state = self.state.acquire_read();
+        let block = state.block(QueryHeight::Specific(height))?;
+
+        Ok(MockClientState::new(block.header))
+    }
+
+    fn build_consensus_state(
+        &self,
+        light_block: Self::LightBlock,
+    ) -> Result<Self::ConsensusState, Error> {
+        Ok(MockConsensusState::new(light_block))
+    }
+
+    fn build_header(
+        &mut self,
+        trusted_height: ICSHeight,
+        target_height: ICSHeight,
+        client_state: &AnyClientState,
+    ) -> Result<(Self::Header, Vec<Self::Header>), Error> {
+        let header = self.verify_header(trusted_height, target_height, client_state)?;
+
+        Ok((header, vec![]))
+    }
+
+    fn maybe_register_counterparty_payee(
+        &mut self,
+        _channel_id: &ChannelId,
+        _port_id: &PortId,
+        _counterparty_payee: &Signer,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn send_coins(
+        &mut self,
+        _from_key_name: &str,
+        _to_address: &str,
+        _amount: u128,
+        _denom: &str,
+    ) -> Result<(), Error> {
+        self.unsupported("token transfers between accounts")
+    }
+
+    fn cross_chain_query(
+        &self,
+        _requests: Vec<CrossChainQueryRequest>,
+    ) -> Result<Vec<CrossChainQueryResponse>, Error> {
+        self.unsupported("cross-chain queries")
+    }
+
+    fn query_incentivized_packet(
+        &self,
+        _request: QueryIncentivizedPacketRequest,
+    ) -> Result<QueryIncentivizedPacketResponse, Error> {
+        self.unsupported("fee middleware queries")
+    }
+
+    fn query_fee_budget(&self) -> Result<Option<FeeBudgetStatus>, Error> {
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ibc_relayer_types::applications::transfer::Amount;
+    use ibc_relayer_types::core::ics02_client::client_state::ClientState;
+    use ibc_relayer_types::core::ics02_client::client_type::ClientType;
+    use ibc_relayer_types::core::ics02_client::msgs::create_client::MsgCreateClient;
+    use ibc_relayer_types::core::ics02_client::msgs::update_client::MsgUpdateClient;
+    use ibc_relayer_types::core::ics24_host::identifier::ClientId;
+    use ibc_relayer_types::tx_msg::Msg;
+
+    use crate::chain::cosmos::client::Settings;
+    use crate::chain::handle::{BaseChainHandle, ChainHandle};
+    use crate::chain::ChainType;
+    use crate::channel::Channel;
+    use crate::config::{load, Config};
+    use crate::connection::Connection;
+    use crate::foreign_client::ForeignClient;
+    use crate::keyring::Store;
+    use crate::link::{Link, LinkParameters};
+    use crate::spawn::spawn_chain_runtime;
+    use crate::transfer::{build_and_send_transfer_messages, TransferOptions};
+
+    fn config() -> Config {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/config/fixtures/relayer_conf_example.toml"
+        );
+
+        let mut config = load(path).unwrap();
+
+        for (index, chain) in config.chains.iter_mut().enumerate() {
+            chain.id = ChainId::new(MOCK_CHAIN_NAME.to_string(), index as u64);
+            chain.r#type = ChainType::Mock;
+            chain.key_store_type = Store::Memory;
+        }
+
+        config
+    }
+
+    fn chain(index: usize) -> MockChain {
+        let config = config().chains.remove(index);
+        let rt = Arc::new(TokioRuntime::new().unwrap());
+
+        MockChain::bootstrap(config, rt).unwrap()
+    }
+
+    /// Spawn the runtimes of two mock chains, for the relayer to interact with.
+    fn handles() -> (BaseChainHandle, BaseChainHandle) {
+        let config = config();
+        let rt = Arc::new(TokioRuntime::new().unwrap());
+
+        let spawn = |index: usize| {
+            spawn_chain_runtime::<BaseChainHandle>(&config, &config.chains[index].id, rt.clone())
+                .unwrap()
+        };
+
+        (spawn(0), spawn(1))
+    }
+
+    /// Open a transfer channel between the given chains, with the relayer
+    /// creating the clients and completing both handshakes.
+    fn open_channel(
+        chain_a: &BaseChainHandle,
+        chain_b: &BaseChainHandle,
+    ) -> Channel<BaseChainHandle, BaseChainHandle> {
+        let client_on_a = ForeignClient::new(chain_a.clone(), chain_b.clone()).unwrap();
+        let client_on_b = ForeignClient::new(chain_b.clone(), chain_a.clone()).unwrap();
+
+        let connection = Connection::new(client_on_a, client_on_b, Duration::ZERO).unwrap();
+
+        Channel::new(
+            connection,
+            Ordering::Unordered,
+            PortId::transfer(),
+            PortId::transfer(),
+            None,
+        )
+        .unwrap()
+    }
+
+    fn tracked(msgs: Vec<Any>) -> TrackedMsgs {
+        TrackedMsgs::new_static(msgs, "test")
+    }
+
+    /// Create a client of `src` on `dst`, and return its identifier.
+    fn create_client(src: &mut MockChain, dst: &mut MockChain) -> ClientId {
+        let height = src.query_application_status().unwrap().height;
+        let settings = ClientSettings::Tendermint(Settings {
+            max_clock_drift: Duration::from_secs(5),
+            trusting_period: None,
+            trust_threshold: Default::default(),
+        });
+
+        let client_state = src.build_client_state(height, settings).unwrap();
+        let light_block = src
+            .verify_header(height, height, &client_state.into())
+            .unwrap();
+        let consensus_state = src.build_consensus_state(light_block).unwrap();
+
+        let msg = MsgCreateClient::new(
+            client_state.into(),
+            consensus_state.into(),
+            dst.get_signer().unwrap(),
+        )
+        .unwrap();
+
+        let events = dst
+            .send_messages_and_wait_commit(tracked(vec![msg.to_any()]))
+            .unwrap();
+
+        match &events[0].event {
+            IbcEvent::CreateClient(e) => e.client_id().clone(),
+            event => panic!("expected a CreateClient event, got {event}"),
+        }
+    }
+
+    #[test]
+    fn create_and_update_client() {
+        let mut chain_a = chain(0);
+        let mut chain_b = chain(1);
+
+        let client_id = create_client(&mut chain_a, &mut chain_b);
+
+        let (client_state, proof) = chain_b
+            .query_client_state(
+                QueryClientStateRequest {
+                    client_id: client_id.clone(),
+                    height: QueryHeight::Latest,
+                },
+                IncludeProof::Yes,
+            )
+            .unwrap();
+
+        assert_eq!(&client_state.chain_id(), chain_a.id());
+        assert!(proof.is_some());
+
+        let trusted_height = client_state.latest_height();
+        let target_height = chain_a.query_application_status().unwrap().height;
+
+        let (header, _) = chain_a
+            .build_header(trusted_height, target_height, &client_state)
+            .unwrap();
+
+        let msg = MsgUpdateClient {
+            client_id: client_id.clone(),
+            header: header.into(),
+            signer: chain_b.get_signer().unwrap(),
+        };
+
+        chain_b
+            .send_messages_and_wait_commit(tracked(vec![msg.to_any()]))
+            .unwrap();
+
+        let heights = chain_b
+            .query_consensus_state_heights(QueryConsensusStateHeightsRequest {
+                client_id,
+                pagination: None,
+            })
+            .unwrap();
+
+        assert_eq!(heights, vec![trusted_height, target_height]);
+    }
+
+    #[test]
+    fn failed_transactions_leave_the_state_untouched() {
+        let mut chain_a = chain(0);
+        let mut chain_b = chain(1);
+
+        let client_id = create_client(&mut chain_a, &mut chain_b);
+        let height = chain_b.query_application_status().unwrap().height;
+
+        let header = chain_a.query_host_consensus_state(QueryHostConsensusStateRequest {
+            height: QueryHeight::Latest,
+        });
+
+        let msg = MsgUpdateClient {
+            client_id: ClientId::new(ClientType::Mock, 42).unwrap(),
+            header: header.unwrap().header.into(),
+            signer: chain_b.get_signer().unwrap(),
+        };
+
+        let events = chain_b
+            .send_messages_and_wait_commit(tracked(vec![msg.to_any()]))
+            .unwrap();
+
+        assert!(matches!(events[0].event, IbcEvent::ChainError(_)));
+        assert!(chain_b.query_application_status().unwrap().height > height);
+
+        let clients = chain_b
+            .query_clients(QueryClientStatesRequest { pagination: None })
+            .unwrap();
+
+        assert_eq!(clients.len(), 1);
+        assert_eq!(clients[0].client_id, client_id);
+    }
+
+    #[test]
+    fn status_queries_produce_blocks() {
+        let chain_a = chain(0);
+
+        let before = chain_a.query_application_status().unwrap();
+        let after = chain_a.query_application_status().unwrap();
+
+        assert_eq!(after.height, before.height.increment());
+        assert_eq!(
+            after.timestamp.duration_since(&before.timestamp),
+            Some(BLOCK_TIME)
+        );
+    }
+
+    #[test]
+    fn connection_and_channel_handshakes() {
+        let (chain_a, chain_b) = handles();
+
+        let channel = open_channel(&chain_a, &chain_b);
+
+        let sides = [
+            (
+                &chain_a,
+                channel.src_connection_id(),
+                channel.src_channel_id(),
+            ),
+            (
+                &chain_b,
+                channel.dst_connection_id(),
+                channel.dst_channel_id(),
+            ),
+        ];
+
+        for (chain, connection_id, channel_id) in sides {
+            let (connection_end, _) = chain
+                .query_connection(
+                    QueryConnectionRequest {
+                        connection_id: connection_id.clone(),
+                        height: QueryHeight::Latest,
+                    },
+                    IncludeProof::No,
+                )
+                .unwrap();
+
+            assert!(connection_end.is_open());
+
+            let (channel_end, _) = chain
+                .query_channel(
+                    QueryChannelRequest {
+                        port_id: PortId::transfer(),
+                        channel_id: channel_id.unwrap().clone(),
+                        height: QueryHeight::Latest,
+                    },
+                    IncludeProof::No,
+                )
+                .unwrap();
+
+            assert!(channel_end.is_open());
+        }
+    }
+
+    #[test]
+    fn packet_round_trip() {
+        let (chain_a, chain_b) = handles();
+
+        let channel = open_channel(&chain_a, &chain_b);
+        let channel_id = channel.src_channel_id().unwrap().clone();
+
+        let opts = TransferOptions {
+            src_port_id: PortId::transfer(),
+            src_channel_id: channel_id.clone(),
+            amount: Amount::from(1000u64),
+            denom: "samoleans".to_string(),
+            receiver: None,
+            timeout_height_offset: 1000,
+            timeout_duration: Duration::ZERO,
+            number_msgs: 1,
+            memo: None,
+        };
+
+        build_and_send_transfer_messages(&chain_a, &chain_b, &opts).unwrap();
+
+        let link = Link::new_from_opts(
+            chain_a.clone(),
+            chain_b.clone(),
+            LinkParameters {
+                src_port_id: PortId::transfer(),
+                src_channel_id: channel_id.clone(),
+            },
+            false,
+            false,
+        )
+        .unwrap();
+
+        let events = link.relay_recv_packet_and_timeout_messages(&[]).unwrap();
+        assert!(events
+            .iter()
+            .any(|event| matches!(event, IbcEvent::WriteAcknowledgement(_))));
+
+        let events = link
+            .reverse(false, false)
+            .unwrap()
+            .relay_ack_packet_messages(&[])
+            .unwrap();
+        assert!(events
+            .iter()
+            .any(|event| matches!(event, IbcEvent::AcknowledgePacket(_))));
+
+        let (commitments, _) = chain_a
+            .query_packet_commitments(QueryPacketCommitmentsRequest {
+                port_id: PortId::transfer(),
+                channel_id,
+                pagination: None,
+            })
+            .unwrap();
+
+        assert!(commitments.is_empty());
+    }
+}
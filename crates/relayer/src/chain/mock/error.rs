@@ -0,0 +1,78 @@
+use flex_error::define_error;
+
+use ibc_relayer_types::core::ics02_client::client_type::ClientType;
+use ibc_relayer_types::core::ics02_client::error::Error as ClientError;
+use ibc_relayer_types::core::ics04_channel::packet::Sequence;
+use ibc_relayer_types::core::ics24_host::identifier::{
+    ChainId, ChannelId, ClientId, ConnectionId, PortId,
+};
+use ibc_relayer_types::mock::client_state::MOCK_CHAIN_NAME;
+use ibc_relayer_types::Height;
+
+define_error! {
+    Error {
+        InvalidChainId
+            { chain_id: ChainId }
+            |e| { format!("mock chain identifiers must be of the form '{}-<revision number>', got '{}'", MOCK_CHAIN_NAME, e.chain_id) },
+
+        UnknownMessage
+            { type_url: String }
+            |e| { format!("mock chains do not handle messages of type '{}'", e.type_url) },
+
+        DecodeMessage
+            { type_url: String, reason: String }
+            |e| { format!("failed to decode message of type '{}': {}", e.type_url, e.reason) },
+
+        Client
+            [ ClientError ]
+            |_| { "invalid client state, consensus state or header" },
+
+        UnsupportedClientType
+            { client_type: ClientType }
+            |e| { format!("mock chains only host mock clients, not clients of type '{}'", e.client_type) },
+
+        ClientNotFound
+            { client_id: ClientId }
+            |e| { format!("client '{}' not found", e.client_id) },
+
+        ConsensusStateNotFound
+            { client_id: ClientId, height: Height }
+            |e| { format!("consensus state of client '{}' at height {} not found", e.client_id, e.height) },
+
+        ConnectionNotFound
+            { connection_id: ConnectionId }
+            |e| { format!("connection '{}' not found", e.connection_id) },
+
+        ChannelNotFound
+            { port_id: PortId, channel_id: ChannelId }
+            |e| { format!("channel '{}/{}' not found", e.port_id, e.channel_id) },
+
+        BlockNotFound
+            { height: Height }
+            |e| { format!("no block at height {}", e.height) },
+
+        InvalidState
+            { object: String, state: String, expected: String }
+            |e| { format!("{} is in state '{}' but must be {}", e.object, e.state, e.expected) },
+
+        PacketAlreadyReceived
+            { port_id: PortId, channel_id: ChannelId, sequence: Sequence }
+            |e| { format!("packet '{}' on channel '{}/{}' was already received", e.sequence, e.port_id, e.channel_id) },
+
+        PacketOutOfOrder
+            { sequence: Sequence, expected: Sequence }
+            |e| { format!("received packet '{}' on an ordered channel while expecting packet '{}'", e.sequence, e.expected) },
+
+        PacketTimedOut
+            { sequence: Sequence }
+            |e| { format!("packet '{}' has timed out", e.sequence) },
+
+        PacketCommitmentNotFound
+            { port_id: PortId, channel_id: ChannelId, sequence: Sequence }
+            |e| { format!("no commitment for packet '{}' on channel '{}/{}'", e.sequence, e.port_id, e.channel_id) },
+
+        Unsupported
+            { operation: String }
+            |e| { format!("mock chains do not support {}", e.operation) },
+    }
+}
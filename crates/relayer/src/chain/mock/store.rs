@@ -0,0 +1,775 @@
+//! In-memory IBC store of a mock chain, and the handlers applying
+//! the IBC messages submitted to the chain to that store.
+//!
+//! The handlers perform the state transitions of the IBC protocol,
+//! but do not verify any proof: mock clients trust their counterparty.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::protobuf::Protobuf;
+use sha2::{Digest, Sha256};
+
+use ibc_relayer_types::applications::transfer::msgs::transfer::{
+    MsgTransfer, TYPE_URL as TRANSFER_TYPE_URL,
+};
+use ibc_relayer_types::core::ics02_client::client_state::ClientState;
+use ibc_relayer_types::core::ics02_client::error::Error as ClientError;
+use ibc_relayer_types::core::ics02_client::events::{
+    Attributes as ClientAttributes, CreateClient, UpdateClient,
+};
+use ibc_relayer_types::core::ics02_client::header::Header;
+use ibc_relayer_types::core::ics02_client::msgs::create_client::{
+    MsgCreateClient, TYPE_URL as CREATE_CLIENT_TYPE_URL,
+};
+use ibc_relayer_types::core::ics02_client::msgs::update_client::{
+    MsgUpdateClient, TYPE_URL as UPDATE_CLIENT_TYPE_URL,
+};
+use ibc_relayer_types::core::ics03_connection::connection::{
+    ConnectionEnd, Counterparty as ConnectionCounterparty, State as ConnectionState,
+};
+use ibc_relayer_types::core::ics03_connection::events::{
+    Attributes as ConnectionAttributes, OpenAck as ConnectionOpenAck,
+    OpenConfirm as ConnectionOpenConfirm, OpenInit as ConnectionOpenInit,
+    OpenTry as ConnectionOpenTry,
+};
+use ibc_relayer_types::core::ics03_connection::msgs::conn_open_ack::{
+    MsgConnectionOpenAck, TYPE_URL as CONN_OPEN_ACK_TYPE_URL,
+};
+use ibc_relayer_types::core::ics03_connection::msgs::conn_open_confirm::{
+    MsgConnectionOpenConfirm, TYPE_URL as CONN_OPEN_CONFIRM_TYPE_URL,
+};
+use ibc_relayer_types::core::ics03_connection::msgs::conn_open_init::{
+    MsgConnectionOpenInit, TYPE_URL as CONN_OPEN_INIT_TYPE_URL,
+};
+use ibc_relayer_types::core::ics03_connection::msgs::conn_open_try::{
+    MsgConnectionOpenTry, TYPE_URL as CONN_OPEN_TRY_TYPE_URL,
+};
+use ibc_relayer_types::core::ics03_connection::version::{get_compatible_versions, pick_version};
+use ibc_relayer_types::core::ics04_channel::channel::{
+    ChannelEnd, Ordering, State as ChannelState,
+};
+use ibc_relayer_types::core::ics04_channel::events::{
+    AcknowledgePacket, CloseConfirm, CloseInit, OpenAck as ChannelOpenAck,
+    OpenConfirm as ChannelOpenConfirm, OpenInit as ChannelOpenInit, OpenTry as ChannelOpenTry,
+    ReceivePacket, SendPacket, TimeoutOnClosePacket, TimeoutPacket, WriteAcknowledgement,
+};
+use ibc_relayer_types::core::ics04_channel::msgs::acknowledgement::{
+    MsgAcknowledgement, TYPE_URL as ACKNOWLEDGEMENT_TYPE_URL,
+};
+use ibc_relayer_types::core::ics04_channel::msgs::chan_close_confirm::{
+    MsgChannelCloseConfirm, TYPE_URL as CHAN_CLOSE_CONFIRM_TYPE_URL,
+};
+use ibc_relayer_types::core::ics04_channel::msgs::chan_close_init::{
+    MsgChannelCloseInit, TYPE_URL as CHAN_CLOSE_INIT_TYPE_URL,
+};
+use ibc_relayer_types::core::ics04_channel::msgs::chan_open_ack::{
+    MsgChannelOpenAck, TYPE_URL as CHAN_OPEN_ACK_TYPE_URL,
+};
+use ibc_relayer_types::core::ics04_channel::msgs::chan_open_confirm::{
+    MsgChannelOpenConfirm, TYPE_URL as CHAN_OPEN_CONFIRM_TYPE_URL,
+};
+use ibc_relayer_types::core::ics04_channel::msgs::chan_open_init::{
+    MsgChannelOpenInit, TYPE_URL as CHAN_OPEN_INIT_TYPE_URL,
+};
+use ibc_relayer_types::core::ics04_channel::msgs::chan_open_try::{
+    MsgChannelOpenTry, TYPE_URL as CHAN_OPEN_TRY_TYPE_URL,
+};
+use ibc_relayer_types::core::ics04_channel::msgs::recv_packet::{
+    MsgRecvPacket, TYPE_URL as RECV_PACKET_TYPE_URL,
+};
+use ibc_relayer_types::core::ics04_channel::msgs::timeout::{
+    MsgTimeout, TYPE_URL as TIMEOUT_TYPE_URL,
+};
+use ibc_relayer_types::core::ics04_channel::msgs::timeout_on_close::{
+    MsgTimeoutOnClose, TYPE_URL as TIMEOUT_ON_CLOSE_TYPE_URL,
+};
+use ibc_relayer_types::core::ics04_channel::packet::{Packet, Sequence};
+use ibc_relayer_types::core::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
+use ibc_relayer_types::events::IbcEvent;
+use ibc_relayer_types::mock::client_state::MockClientState;
+use ibc_relayer_types::mock::consensus_state::MockConsensusState;
+use ibc_relayer_types::mock::header::MockHeader;
+use ibc_relayer_types::timestamp::Timestamp;
+use ibc_relayer_types::Height;
+
+use crate::client_state::AnyClientState;
+use crate::consensus_state::AnyConsensusState;
+use crate::light_client::AnyHeader;
+
+use super::error::Error;
+
+/// The acknowledgement written by a mock chain for every packet it receives,
+/// ie. a successful ICS-20 acknowledgement.
+pub const SUCCESS_ACK: &[u8] = br#"{"result":"AQ=="}"#;
+
+/// The state of a client hosted by a mock chain.
+#[derive(Clone, Debug)]
+pub struct ClientRecord {
+    pub client_state: MockClientState,
+    pub consensus_states: BTreeMap<Height, MockConsensusState>,
+}
+
+/// The IBC state of a mock chain at a given height.
+#[derive(Clone, Debug, Default)]
+pub struct IbcStore {
+    pub clients: BTreeMap<ClientId, ClientRecord>,
+    pub connections: BTreeMap<ConnectionId, ConnectionEnd>,
+    pub channels: BTreeMap<(PortId, ChannelId), ChannelEnd>,
+    pub next_sequence_send: BTreeMap<(PortId, ChannelId), Sequence>,
+    pub next_sequence_recv: BTreeMap<(PortId, ChannelId), Sequence>,
+    pub next_sequence_ack: BTreeMap<(PortId, ChannelId), Sequence>,
+    pub packet_commitments: BTreeMap<(PortId, ChannelId, Sequence), Vec<u8>>,
+    pub packet_receipts: BTreeSet<(PortId, ChannelId, Sequence)>,
+    pub packet_acks: BTreeMap<(PortId, ChannelId, Sequence), Vec<u8>>,
+    client_counter: u64,
+    connection_counter: u64,
+    channel_counter: u64,
+}
+
+/// The block in which a message is executed.
+pub struct HostContext {
+    pub height: Height,
+    pub timestamp: Timestamp,
+}
+
+impl IbcStore {
+    pub fn client(&self, client_id: &ClientId) -> Result<&ClientRecord, Error> {
+        self.clients
+            .get(client_id)
+            .ok_or_else(|| Error::client_not_found(client_id.clone()))
+    }
+
+    pub fn connection(&self, connection_id: &ConnectionId) -> Result<&ConnectionEnd, Error> {
+        self.connections
+            .get(connection_id)
+            .ok_or_else(|| Error::connection_not_found(connection_id.clone()))
+    }
+
+    pub fn channel(&self, port_id: &PortId, channel_id: &ChannelId) -> Result<&ChannelEnd, Error> {
+        self.channels
+            .get(&(port_id.clone(), channel_id.clone()))
+            .ok_or_else(|| Error::channel_not_found(port_id.clone(), channel_id.clone()))
+    }
+
+    fn channel_mut(
+        &mut self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<&mut ChannelEnd, Error> {
+        self.channels
+            .get_mut(&(port_id.clone(), channel_id.clone()))
+            .ok_or_else(|| Error::channel_not_found(port_id.clone(), channel_id.clone()))
+    }
+
+    fn connection_mut(
+        &mut self,
+        connection_id: &ConnectionId,
+    ) -> Result<&mut ConnectionEnd, Error> {
+        self.connections
+            .get_mut(connection_id)
+            .ok_or_else(|| Error::connection_not_found(connection_id.clone()))
+    }
+
+    /// Apply the given message to the store, and return the events it emits.
+    pub fn deliver(&mut self, host: &HostContext, msg: Any) -> Result<Vec<IbcEvent>, Error> {
+        let type_url = msg.type_url.clone();
+
+        macro_rules! decode {
+            ($msg_type:ty) => {
+                <$msg_type>::decode_vec(&msg.value)
+                    .map_err(|e| Error::decode_message(type_url.clone(), e.to_string()))?
+            };
+        }
+
+        match type_url.as_str() {
+            CREATE_CLIENT_TYPE_URL => self.create_client(decode!(MsgCreateClient)),
+            UPDATE_CLIENT_TYPE_URL => self.update_client(decode!(MsgUpdateClient)),
+            CONN_OPEN_INIT_TYPE_URL => self.conn_open_init(decode!(MsgConnectionOpenInit)),
+            CONN_OPEN_TRY_TYPE_URL => self.conn_open_try(decode!(MsgConnectionOpenTry)),
+            CONN_OPEN_ACK_TYPE_URL => self.conn_open_ack(decode!(MsgConnectionOpenAck)),
+            CONN_OPEN_CONFIRM_TYPE_URL => self.conn_open_confirm(decode!(MsgConnectionOpenConfirm)),
+            CHAN_OPEN_INIT_TYPE_URL => self.chan_open_init(decode!(MsgChannelOpenInit)),
+            CHAN_OPEN_TRY_TYPE_URL => self.chan_open_try(decode!(MsgChannelOpenTry)),
+            CHAN_OPEN_ACK_TYPE_URL => self.chan_open_ack(decode!(MsgChannelOpenAck)),
+            CHAN_OPEN_CONFIRM_TYPE_URL => self.chan_open_confirm(decode!(MsgChannelOpenConfirm)),
+            CHAN_CLOSE_INIT_TYPE_URL => self.chan_close_init(decode!(MsgChannelCloseInit)),
+            CHAN_CLOSE_CONFIRM_TYPE_URL => self.chan_close_confirm(decode!(MsgChannelCloseConfirm)),
+            TRANSFER_TYPE_URL => self.transfer(decode!(MsgTransfer)),
+            RECV_PACKET_TYPE_URL => self.recv_packet(host, decode!(MsgRecvPacket)),
+            ACKNOWLEDGEMENT_TYPE_URL => self.acknowledge_packet(decode!(MsgAcknowledgement)),
+            TIMEOUT_TYPE_URL => {
+                let msg = decode!(MsgTimeout);
+                self.timeout_packet(msg.packet, false)
+            }
+            TIMEOUT_ON_CLOSE_TYPE_URL => {
+                let msg = decode!(MsgTimeoutOnClose);
+                self.timeout_packet(msg.packet, true)
+            }
+            _ => Err(Error::unknown_message(type_url)),
+        }
+    }
+
+    fn create_client(&mut self, msg: MsgCreateClient) -> Result<Vec<IbcEvent>, Error> {
+        let client_state = match AnyClientState::try_from(msg.client_state)
+            .map_err(Error::client)?
+        {
+            AnyClientState::Mock(client_state) => client_state,
+            client_state => return Err(Error::unsupported_client_type(client_state.client_type())),
+        };
+
+        let consensus_state =
+            match AnyConsensusState::try_from(msg.consensus_state).map_err(Error::client)? {
+                AnyConsensusState::Mock(consensus_state) => consensus_state,
+                consensus_state => {
+                    return Err(Error::unsupported_client_type(
+                        consensus_state.client_type(),
+                    ))
+                }
+            };
+
+        let client_id = ClientId::new(client_state.client_type(), self.client_counter)
+            .map_err(|e| Error::client(ClientError::invalid_client_identifier(e)))?;
+        self.client_counter += 1;
+
+        let consensus_height = client_state.latest_height();
+
+        self.clients.insert(
+            client_id.clone(),
+            ClientRecord {
+                consensus_states: BTreeMap::from([(consensus_height, consensus_state)]),
+                client_state,
+            },
+        );
+
+        let event = CreateClient(ClientAttributes {
+            client_id,
+            client_type: client_state.client_type(),
+            consensus_height,
+        });
+
+        Ok(vec![event.into()])
+    }
+
+    fn update_client(&mut self, msg: MsgUpdateClient) -> Result<Vec<IbcEvent>, Error> {
+        let header: MockHeader = match AnyHeader::try_from(msg.header).map_err(Error::client)? {
+            AnyHeader::Mock(header) => header,
+            header => return Err(Error::unsupported_client_type(header.client_type())),
+        };
+
+        let record = self
+            .clients
+            .get_mut(&msg.client_id)
+            .ok_or_else(|| Error::client_not_found(msg.client_id.clone()))?;
+
+        if header.height() > record.client_state.latest_height() {
+            record.client_state.header = header;
+        }
+
+        record
+            .consensus_states
+            .insert(header.height(), MockConsensusState::new(header));
+
+        let event = UpdateClient {
+            common: ClientAttributes {
+                client_id: msg.client_id,
+                client_type: header.client_type(),
+                consensus_height: header.height(),
+            },
+            header: Some(Box::new(header)),
+        };
+
+        Ok(vec![event.into()])
+    }
+
+    fn conn_open_init(&mut self, msg: MsgConnectionOpenInit) -> Result<Vec<IbcEvent>, Error> {
+        self.client(&msg.client_id)?;
+
+        let connection_id = ConnectionId::new(self.connection_counter);
+        self.connection_counter += 1;
+
+        let versions = msg
+            .version
+            .map(|version| vec![version])
+            .unwrap_or_else(get_compatible_versions);
+
+        let connection_end = ConnectionEnd::new(
+            ConnectionState::Init,
+            msg.client_id.clone(),
+            msg.counterparty.clone(),
+            versions,
+            msg.delay_period,
+        );
+
+        self.connections
+            .insert(connection_id.clone(), connection_end);
+
+        let event = ConnectionOpenInit(ConnectionAttributes {
+            connection_id: Some(connection_id),
+            client_id: msg.client_id,
+            counterparty_connection_id: msg.counterparty.connection_id().cloned(),
+            counterparty_client_id: msg.counterparty.client_id().clone(),
+        });
+
+        Ok(vec![event.into()])
+    }
+
+    fn conn_open_try(&mut self, msg: MsgConnectionOpenTry) -> Result<Vec<IbcEvent>, Error> {
+        self.client(&msg.client_id)?;
+
+        let version = pick_version(get_compatible_versions(), msg.counterparty_versions.clone())
+            .map_err(|e| {
+                Error::invalid_state(
+                    "connection version".to_string(),
+                    e.to_string(),
+                    "compatible".to_string(),
+                )
+            })?;
+
+        let connection_id = ConnectionId::new(self.connection_counter);
+        self.connection_counter += 1;
+
+        let connection_end = ConnectionEnd::new(
+            ConnectionState::TryOpen,
+            msg.client_id.clone(),
+            msg.counterparty.clone(),
+            vec![version],
+            msg.delay_period,
+        );
+
+        self.connections
+            .insert(connection_id.clone(), connection_end);
+
+        let event = ConnectionOpenTry(ConnectionAttributes {
+            connection_id: Some(connection_id),
+            client_id: msg.client_id,
+            counterparty_connection_id: msg.counterparty.connection_id().cloned(),
+            counterparty_client_id: msg.counterparty.client_id().clone(),
+        });
+
+        Ok(vec![event.into()])
+    }
+
+    fn conn_open_ack(&mut self, msg: MsgConnectionOpenAck) -> Result<Vec<IbcEvent>, Error> {
+        let connection_end = self.connection_mut(&msg.connection_id)?;
+
+        if !connection_end.state_matches(&ConnectionState::Init) {
+            return Err(Error::invalid_state(
+                format!("connection '{}'", msg.connection_id),
+                connection_end.state().to_string(),
+                ConnectionState::Init.to_string(),
+            ));
+        }
+
+        let counterparty = ConnectionCounterparty::new(
+            connection_end.counterparty().client_id().clone(),
+            Some(msg.counterparty_connection_id.clone()),
+            connection_end.counterparty().prefix().clone(),
+        );
+
+        connection_end.set_state(ConnectionState::Open);
+        connection_end.set_version(msg.version);
+        connection_end.set_counterparty(counterparty);
+
+        let event = ConnectionOpenAck(ConnectionAttributes {
+            connection_id: Some(msg.connection_id),
+            client_id: connection_end.client_id().clone(),
+            counterparty_connection_id: Some(msg.counterparty_connection_id),
+            counterparty_client_id: connection_end.counterparty().client_id().clone(),
+        });
+
+        Ok(vec![event.into()])
+    }
+
+    fn conn_open_confirm(&mut self, msg: MsgConnectionOpenConfirm) -> Result<Vec<IbcEvent>, Error> {
+        let connection_end = self.connection_mut(&msg.connection_id)?;
+
+        if !connection_end.state_matches(&ConnectionState::TryOpen) {
+            return Err(Error::invalid_state(
+                format!("connection '{}'", msg.connection_id),
+                connection_end.state().to_string(),
+                ConnectionState::TryOpen.to_string(),
+            ));
+        }
+
+        connection_end.set_state(ConnectionState::Open);
+
+        let event = ConnectionOpenConfirm(ConnectionAttributes {
+            connection_id: Some(msg.connection_id),
+            client_id: connection_end.client_id().clone(),
+            counterparty_connection_id: connection_end.counterparty().connection_id().cloned(),
+            counterparty_client_id: connection_end.counterparty().client_id().clone(),
+        });
+
+        Ok(vec![event.into()])
+    }
+
+    /// Store a new channel end in the given state, and return its identifier.
+    fn new_channel(
+        &mut self,
+        port_id: &PortId,
+        mut channel_end: ChannelEnd,
+        state: ChannelState,
+    ) -> Result<(ChannelId, ConnectionId), Error> {
+        let connection_id = channel_end
+            .connection_hops()
+            .first()
+            .cloned()
+            .unwrap_or_default();
+
+        self.connection(&connection_id)?;
+
+        let channel_id = ChannelId::new(self.channel_counter);
+        self.channel_counter += 1;
+
+        channel_end.set_state(state);
+
+        let key = (port_id.clone(), channel_id.clone());
+        self.channels.insert(key.clone(), channel_end);
+        self.next_sequence_send
+            .insert(key.clone(), Sequence::from(1));
+        self.next_sequence_recv
+            .insert(key.clone(), Sequence::from(1));
+        self.next_sequence_ack.insert(key, Sequence::from(1));
+
+        Ok((channel_id, connection_id))
+    }
+
+    fn chan_open_init(&mut self, msg: MsgChannelOpenInit) -> Result<Vec<IbcEvent>, Error> {
+        let counterparty_port_id = msg.channel.counterparty().port_id().clone();
+
+        let (channel_id, connection_id) =
+            self.new_channel(&msg.port_id, msg.channel, ChannelState::Init)?;
+
+        let event = ChannelOpenInit {
+            port_id: msg.port_id,
+            channel_id: Some(channel_id),
+            connection_id,
+            counterparty_port_id,
+            counterparty_channel_id: None,
+        };
+
+        Ok(vec![event.into()])
+    }
+
+    fn chan_open_try(&mut self, msg: MsgChannelOpenTry) -> Result<Vec<IbcEvent>, Error> {
+        let counterparty = msg.channel.counterparty().clone();
+
+        let (channel_id, connection_id) =
+            self.new_channel(&msg.port_id, msg.channel, ChannelState::TryOpen)?;
+
+        let event = ChannelOpenTry {
+            port_id: msg.port_id,
+            channel_id: Some(channel_id),
+            connection_id,
+            counterparty_port_id: counterparty.port_id().clone(),
+            counterparty_channel_id: counterparty.channel_id().cloned(),
+        };
+
+        Ok(vec![event.into()])
+    }
+
+    /// Move the given channel from state `from` to state `to`, and return the channel end.
+    fn transition_channel(
+        &mut self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        from: &[ChannelState],
+        to: ChannelState,
+    ) -> Result<ChannelEnd, Error> {
+        let channel_end = self.channel_mut(port_id, channel_id)?;
+
+        if !from.iter().any(|state| channel_end.state_matches(state)) {
+            return Err(Error::invalid_state(
+                format!("channel '{port_id}/{channel_id}'"),
+                channel_end.state().to_string(),
+                from.iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(" or "),
+            ));
+        }
+
+        channel_end.set_state(to);
+
+        Ok(channel_end.clone())
+    }
+
+    fn chan_open_ack(&mut self, msg: MsgChannelOpenAck) -> Result<Vec<IbcEvent>, Error> {
+        let channel_end = self.channel_mut(&msg.port_id, &msg.channel_id)?;
+        channel_end.set_counterparty_channel_id(msg.counterparty_channel_id.clone());
+        channel_end.set_version(msg.counterparty_version);
+
+        let channel_end = self.transition_channel(
+            &msg.port_id,
+            &msg.channel_id,
+            &[ChannelState::Init],
+            ChannelState::Open,
+        )?;
+
+        let event = ChannelOpenAck {
+            port_id: msg.port_id,
+            channel_id: Some(msg.channel_id),
+            counterparty_channel_id: Some(msg.counterparty_channel_id),
+            connection_id: channel_end.connection_hops()[0].clone(),
+            counterparty_port_id: channel_end.counterparty().port_id().clone(),
+        };
+
+        Ok(vec![event.into()])
+    }
+
+    fn chan_open_confirm(&mut self, msg: MsgChannelOpenConfirm) -> Result<Vec<IbcEvent>, Error> {
+        let channel_end = self.transition_channel(
+            &msg.port_id,
+            &msg.channel_id,
+            &[ChannelState::TryOpen],
+            ChannelState::Open,
+        )?;
+
+        let event = ChannelOpenConfirm {
+            port_id: msg.port_id,
+            channel_id: Some(msg.channel_id),
+            connection_id: channel_end.connection_hops()[0].clone(),
+            counterparty_port_id: channel_end.counterparty().port_id().clone(),
+            counterparty_channel_id: channel_end.counterparty().channel_id().cloned(),
+        };
+
+        Ok(vec![event.into()])
+    }
+
+    fn chan_close_init(&mut self, msg: MsgChannelCloseInit) -> Result<Vec<IbcEvent>, Error> {
+        let channel_end = self.transition_channel(
+            &msg.port_id,
+            &msg.channel_id,
+            &[ChannelState::Open],
+            ChannelState::Closed,
+        )?;
+
+        let event = CloseInit {
+            port_id: msg.port_id,
+            channel_id: msg.channel_id,
+            connection_id: channel_end.connection_hops()[0].clone(),
+            counterparty_port_id: channel_end.counterparty().port_id().clone(),
+            counterparty_channel_id: channel_end.counterparty().channel_id().cloned(),
+        };
+
+        Ok(vec![event.into()])
+    }
+
+    fn chan_close_confirm(&mut self, msg: MsgChannelCloseConfirm) -> Result<Vec<IbcEvent>, Error> {
+        let channel_end = self.transition_channel(
+            &msg.port_id,
+            &msg.channel_id,
+            &[
+                ChannelState::Init,
+                ChannelState::TryOpen,
+                ChannelState::Open,
+            ],
+            ChannelState::Closed,
+        )?;
+
+        let event = CloseConfirm {
+            channel_id: Some(msg.channel_id),
+            port_id: msg.port_id,
+            connection_id: channel_end.connection_hops()[0].clone(),
+            counterparty_port_id: channel_end.counterparty().port_id().clone(),
+            counterparty_channel_id: channel_end.counterparty().channel_id().cloned(),
+        };
+
+        Ok(vec![event.into()])
+    }
+
+    /// Send an ICS-20 packet, without moving any funds since mock chains have no bank.
+    fn transfer(&mut self, msg: MsgTransfer) -> Result<Vec<IbcEvent>, Error> {
+        let channel_end = self.channel(&msg.source_port, &msg.source_channel)?;
+
+        if !channel_end.is_open() {
+            return Err(Error::invalid_state(
+                format!("channel '{}/{}'", msg.source_port, msg.source_channel),
+                channel_end.state().to_string(),
+                ChannelState::Open.to_string(),
+            ));
+        }
+
+        let destination_port = channel_end.counterparty().port_id().clone();
+        let destination_channel = channel_end
+            .counterparty()
+            .channel_id()
+            .cloned()
+            .unwrap_or_default();
+
+        let key = (msg.source_port.clone(), msg.source_channel.clone());
+        let sequence = self
+            .next_sequence_send
+            .get(&key)
+            .copied()
+            .unwrap_or_else(|| Sequence::from(1));
+        self.next_sequence_send.insert(key, sequence.increment());
+
+        let data = serde_json::json!({
+            "denom": msg.token.denom,
+            "amount": msg.token.amount,
+            "sender": msg.sender.to_string(),
+            "receiver": msg.receiver.to_string(),
+            "memo": msg.memo.unwrap_or_default(),
+        });
+
+        let packet = Packet {
+            sequence,
+            source_port: msg.source_port,
+            source_channel: msg.source_channel,
+            destination_port,
+            destination_channel,
+            data: data.to_string().into_bytes(),
+            timeout_height: msg.timeout_height,
+            timeout_timestamp: msg.timeout_timestamp,
+        };
+
+        self.packet_commitments.insert(
+            (
+                packet.source_port.clone(),
+                packet.source_channel.clone(),
+                packet.sequence,
+            ),
+            packet_commitment(&packet),
+        );
+
+        Ok(vec![SendPacket { packet }.into()])
+    }
+
+    fn recv_packet(
+        &mut self,
+        host: &HostContext,
+        msg: MsgRecvPacket,
+    ) -> Result<Vec<IbcEvent>, Error> {
+        let packet = msg.packet;
+        let port_id = packet.destination_port.clone();
+        let channel_id = packet.destination_channel.clone();
+        let channel_end = self.channel(&port_id, &channel_id)?;
+
+        if !channel_end.is_open() {
+            return Err(Error::invalid_state(
+                format!("channel '{port_id}/{channel_id}'"),
+                channel_end.state().to_string(),
+                ChannelState::Open.to_string(),
+            ));
+        }
+
+        if packet.timed_out(&host.timestamp, host.height) {
+            return Err(Error::packet_timed_out(packet.sequence));
+        }
+
+        let key = (port_id.clone(), channel_id.clone());
+
+        if channel_end.order_matches(&Ordering::Ordered) {
+            let expected = self
+                .next_sequence_recv
+                .get(&key)
+                .copied()
+                .unwrap_or_else(|| Sequence::from(1));
+
+            if packet.sequence != expected {
+                return Err(Error::packet_out_of_order(packet.sequence, expected));
+            }
+
+            self.next_sequence_recv.insert(key, expected.increment());
+        } else if !self.packet_receipts.insert((
+            port_id.clone(),
+            channel_id.clone(),
+            packet.sequence,
+        )) {
+            return Err(Error::packet_already_received(
+                port_id,
+                channel_id,
+                packet.sequence,
+            ));
+        }
+
+        self.packet_acks.insert(
+            (port_id, channel_id, packet.sequence),
+            Sha256::digest(SUCCESS_ACK).to_vec(),
+        );
+
+        Ok(vec![
+            ReceivePacket {
+                packet: packet.clone(),
+            }
+            .into(),
+            WriteAcknowledgement {
+                packet,
+                ack: SUCCESS_ACK.to_vec(),
+            }
+            .into(),
+        ])
+    }
+
+    /// Remove the commitment of a packet sent by this chain.
+    fn remove_commitment(&mut self, packet: &Packet) -> Result<&ChannelEnd, Error> {
+        let removed = self.packet_commitments.remove(&(
+            packet.source_port.clone(),
+            packet.source_channel.clone(),
+            packet.sequence,
+        ));
+
+        if removed.is_none() {
+            return Err(Error::packet_commitment_not_found(
+                packet.source_port.clone(),
+                packet.source_channel.clone(),
+                packet.sequence,
+            ));
+        }
+
+        self.channel(&packet.source_port, &packet.source_channel)
+    }
+
+    fn acknowledge_packet(&mut self, msg: MsgAcknowledgement) -> Result<Vec<IbcEvent>, Error> {
+        let packet = msg.packet;
+        let channel_end = self.remove_commitment(&packet)?;
+
+        if channel_end.order_matches(&Ordering::Ordered) {
+            self.next_sequence_ack.insert(
+                (packet.source_port.clone(), packet.source_channel.clone()),
+                packet.sequence.increment(),
+            );
+        }
+
+        Ok(vec![AcknowledgePacket { packet }.into()])
+    }
+
+    fn timeout_packet(&mut self, packet: Packet, on_close: bool) -> Result<Vec<IbcEvent>, Error> {
+        let channel_end = self.remove_commitment(&packet)?;
+
+        // A timeout closes ordered channels
+        if channel_end.order_matches(&Ordering::Ordered) {
+            self.channel_mut(&packet.source_port, &packet.source_channel)?
+                .set_state(ChannelState::Closed);
+        }
+
+        if on_close {
+            Ok(vec![TimeoutOnClosePacket { packet }.into()])
+        } else {
+            Ok(vec![TimeoutPacket { packet }.into()])
+        }
+    }
+}
+
+/// Compute the commitment to a packet, as done by ibc-go.
+pub fn packet_commitment(packet: &Packet) -> Vec<u8> {
+    let mut input = Vec::new();
+    input.extend(packet.timeout_timestamp.nanoseconds().to_be_bytes());
+    input.extend(
+        packet
+            .timeout_height
+            .commitment_revision_number()
+            .to_be_bytes(),
+    );
+    input.extend(
+        packet
+            .timeout_height
+            .commitment_revision_height()
+            .to_be_bytes(),
+    );
+    input.extend(Sha256::digest(&packet.data));
+
+    Sha256::digest(input).to_vec()
+}
@@ -82,6 +82,14 @@ pub enum IncludeProof {
     No,
 }
 
+/// Defines a type to be used when building proofs to specify whether they are meant to be
+/// submitted, or only simulated, eg. to estimate the cost of relaying a packet.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProofPurpose {
+    Submission,
+    Simulation,
+}
+
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct PageRequest {
     /// key is a value returned in PageResponse.next_key to begin
@@ -263,6 +271,20 @@ pub struct QueryChannelRequest {
     pub height: QueryHeight,
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct QueryUpgradeRequest {
+    pub port_id: PortId,
+    pub channel_id: ChannelId,
+    pub height: QueryHeight,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct QueryUpgradeErrorRequest {
+    pub port_id: PortId,
+    pub channel_id: ChannelId,
+    pub height: QueryHeight,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct QueryChannelClientStateRequest {
     pub port_id: PortId,
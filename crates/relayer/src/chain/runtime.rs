@@ -1,10 +1,12 @@
 use alloc::sync::Arc;
+use core::time::Duration;
 use std::thread;
 
 use crossbeam_channel as channel;
 use tokio::runtime::Runtime as TokioRuntime;
 use tracing::{error, Span};
 
+use ibc_proto::cosmos::tx::v1beta1::Fee;
 use ibc_proto::ibc::apps::fee::v1::{
     QueryIncentivizedPacketRequest, QueryIncentivizedPacketResponse,
 };
@@ -19,9 +21,10 @@ use ibc_relayer_types::{
         ics04_channel::{
             channel::{ChannelEnd, IdentifiedChannelEnd},
             packet::{PacketMsgType, Sequence},
+            upgrade::{ErrorReceipt, Upgrade},
         },
         ics23_commitment::{commitment::CommitmentPrefix, merkle::MerkleProof},
-        ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId},
+        ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId},
     },
     proofs::Proofs,
     signer::Signer,
@@ -30,6 +33,7 @@ use ibc_relayer_types::{
 
 use crate::{
     account::Balance,
+    chain::budget::FeeBudgetStatus,
     chain::requests::QueryPacketEventDataRequest,
     client_state::{AnyClientState, IdentifiedAnyClientState},
     config::ChainConfig,
@@ -51,6 +55,9 @@ use super::{
     tracking::TrackedMsgs,
 };
 
+/// How often the health of the full nodes of the chain is checked
+const ENDPOINT_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
 pub struct Threads {
     pub chain_runtime: thread::JoinHandle<()>,
     pub event_source: Option<thread::JoinHandle<()>>,
@@ -131,8 +138,16 @@ where
     }
 
     fn run(mut self) -> Result<(), Error> {
+        let endpoint_check = channel::tick(ENDPOINT_CHECK_INTERVAL);
+
         loop {
             channel::select! {
+                recv(endpoint_check) -> _ => {
+                    if let Err(e) = self.chain.check_endpoints() {
+                        error!("failed to switch over to another full node: {}", e);
+                    }
+                },
+
                 recv(self.request_receiver) -> event => {
                     let (span, event) = match event {
                         Ok((span, event)) => (span, event),
@@ -170,6 +185,14 @@ where
                             self.send_messages_and_wait_check_tx(tracked_msgs, reply_to)?
                         },
 
+                        ChainRequest::EstimateFee { tracked_msgs, reply_to } => {
+                            self.estimate_fee(tracked_msgs, reply_to)?
+                        },
+
+                        ChainRequest::SimulateMessages { tracked_msgs, reply_to } => {
+                            self.simulate_messages(tracked_msgs, reply_to)?
+                        },
+
                         ChainRequest::Signer { reply_to } => {
                             self.get_signer(reply_to)?
                         },
@@ -182,6 +205,10 @@ where
                             self.get_key(reply_to)?
                         },
 
+                        ChainRequest::GetKeys { reply_to } => {
+                            self.get_keys(reply_to)?
+                        },
+
                         ChainRequest::AddKey { key_name, key, reply_to } => {
                             self.add_key(key_name, key, reply_to)?
                         },
@@ -214,6 +241,10 @@ where
                             self.build_channel_proofs(port_id, channel_id, height, reply_to)?
                         },
 
+                        ChainRequest::BuildChannelUpgradeProofs { port_id, channel_id, height, reply_to } => {
+                            self.build_channel_upgrade_proofs(port_id, channel_id, height, reply_to)?
+                        },
+
                         ChainRequest::QueryBalance { key_name, denom, reply_to } => {
                             self.query_balance(key_name, denom, reply_to)?
                         },
@@ -286,12 +317,20 @@ where
                             self.query_channel(request, include_proof, reply_to)?
                         },
 
+                        ChainRequest::QueryUpgrade { request, include_proof, reply_to } => {
+                            self.query_upgrade(request, include_proof, reply_to)?
+                        },
+
+                        ChainRequest::QueryUpgradeError { request, include_proof, reply_to } => {
+                            self.query_upgrade_error(request, include_proof, reply_to)?
+                        },
+
                         ChainRequest::QueryChannelClientState { request, reply_to } => {
                             self.query_channel_client_state(request, reply_to)?
                         },
 
-                        ChainRequest::BuildPacketProofs { packet_type, port_id, channel_id, sequence, height, reply_to } => {
-                            self.build_packet_proofs(packet_type, port_id, channel_id, sequence, height, reply_to)?
+                        ChainRequest::BuildPacketProofs { packet_type, port_id, channel_id, sequence, height, purpose, reply_to } => {
+                            self.build_packet_proofs(packet_type, port_id, channel_id, sequence, height, purpose, reply_to)?
                         },
 
                         ChainRequest::QueryPacketCommitment { request, include_proof, reply_to } => {
@@ -342,6 +381,10 @@ where
                             self.maybe_register_counterparty_payee(&channel_id, &port_id, &counterparty_payee, reply_to)?
                         },
 
+                        ChainRequest::SendCoins { from_key_name, to_address, amount, denom, reply_to } => {
+                            self.send_coins(&from_key_name, &to_address, amount, &denom, reply_to)?
+                        },
+
                         ChainRequest::CrossChainQuery { request, reply_to } => {
                             self.cross_chain_query(request, reply_to)?
                         },
@@ -349,6 +392,14 @@ where
                         ChainRequest::QueryIncentivizedPacket { request, reply_to } => {
                             self.query_incentivized_packet(request, reply_to)?
                         },
+
+                        ChainRequest::QueryFeeBudget { reply_to } => {
+                            self.query_fee_budget(reply_to)?
+                        },
+
+                        ChainRequest::SyncProofSequence { counterparty_chain_id, counterparty_client_id, counterparty_client_state, reply_to } => {
+                            self.sync_proof_sequence(&counterparty_chain_id, &counterparty_client_id, &counterparty_client_state, reply_to)?
+                        },
                     }
                 },
             }
@@ -385,6 +436,24 @@ where
         reply_to.send(result).map_err(Error::send)
     }
 
+    fn estimate_fee(
+        &mut self,
+        tracked_msgs: TrackedMsgs,
+        reply_to: ReplyTo<Fee>,
+    ) -> Result<(), Error> {
+        let result = self.chain.estimate_fee(tracked_msgs);
+        reply_to.send(result).map_err(Error::send)
+    }
+
+    fn simulate_messages(
+        &mut self,
+        tracked_msgs: TrackedMsgs,
+        reply_to: ReplyTo<u64>,
+    ) -> Result<(), Error> {
+        let result = self.chain.simulate_messages(tracked_msgs);
+        reply_to.send(result).map_err(Error::send)
+    }
+
     fn query_balance(
         &self,
         key_name: Option<String>,
@@ -432,6 +501,19 @@ where
         reply_to.send(result).map_err(Error::send)
     }
 
+    fn get_keys(
+        &mut self,
+        reply_to: ReplyTo<Vec<(String, AnySigningKeyPair)>>,
+    ) -> Result<(), Error> {
+        let result = self.chain.get_keys().map(|keys| {
+            keys.into_iter()
+                .map(|(key_name, key_pair)| (key_name, key_pair.into()))
+                .collect()
+        });
+
+        reply_to.send(result).map_err(Error::send)
+    }
+
     fn add_key(
         &mut self,
         key_name: String,
@@ -658,6 +740,26 @@ where
         reply_to.send(result).map_err(Error::send)
     }
 
+    fn query_upgrade(
+        &self,
+        request: QueryUpgradeRequest,
+        include_proof: IncludeProof,
+        reply_to: ReplyTo<(Upgrade, Option<MerkleProof>)>,
+    ) -> Result<(), Error> {
+        let result = self.chain.query_upgrade(request, include_proof);
+        reply_to.send(result).map_err(Error::send)
+    }
+
+    fn query_upgrade_error(
+        &self,
+        request: QueryUpgradeErrorRequest,
+        include_proof: IncludeProof,
+        reply_to: ReplyTo<(ErrorReceipt, Option<MerkleProof>)>,
+    ) -> Result<(), Error> {
+        let result = self.chain.query_upgrade_error(request, include_proof);
+        reply_to.send(result).map_err(Error::send)
+    }
+
     fn query_channel_client_state(
         &self,
         request: QueryChannelClientStateRequest,
@@ -681,6 +783,20 @@ where
         reply_to.send(result).map_err(Error::send)
     }
 
+    fn build_channel_upgrade_proofs(
+        &self,
+        port_id: PortId,
+        channel_id: ChannelId,
+        height: Height,
+        reply_to: ReplyTo<(Upgrade, Proofs)>,
+    ) -> Result<(), Error> {
+        let result = self
+            .chain
+            .build_channel_upgrade_proofs(&port_id, &channel_id, height);
+
+        reply_to.send(result).map_err(Error::send)
+    }
+
     fn build_packet_proofs(
         &self,
         packet_type: PacketMsgType,
@@ -688,11 +804,17 @@ where
         channel_id: ChannelId,
         sequence: Sequence,
         height: Height,
+        purpose: ProofPurpose,
         reply_to: ReplyTo<Proofs>,
     ) -> Result<(), Error> {
-        let result =
-            self.chain
-                .build_packet_proofs(packet_type, port_id, channel_id, sequence, height);
+        let result = self.chain.build_packet_proofs(
+            packet_type,
+            port_id,
+            channel_id,
+            sequence,
+            height,
+            purpose,
+        );
 
         reply_to.send(result).map_err(Error::send)
     }
@@ -829,6 +951,23 @@ where
         Ok(())
     }
 
+    fn send_coins(
+        &mut self,
+        from_key_name: &str,
+        to_address: &str,
+        amount: u128,
+        denom: &str,
+        reply_to: ReplyTo<()>,
+    ) -> Result<(), Error> {
+        let result = self
+            .chain
+            .send_coins(from_key_name, to_address, amount, denom);
+
+        reply_to.send(result).map_err(Error::send)?;
+
+        Ok(())
+    }
+
     fn cross_chain_query(
         &self,
         request: Vec<CrossChainQueryRequest>,
@@ -850,4 +989,28 @@ where
 
         Ok(())
     }
+
+    fn query_fee_budget(&self, reply_to: ReplyTo<Option<FeeBudgetStatus>>) -> Result<(), Error> {
+        let result = self.chain.query_fee_budget();
+        reply_to.send(result).map_err(Error::send)?;
+
+        Ok(())
+    }
+
+    fn sync_proof_sequence(
+        &mut self,
+        counterparty_chain_id: &ChainId,
+        counterparty_client_id: &ClientId,
+        counterparty_client_state: &AnyClientState,
+        reply_to: ReplyTo<()>,
+    ) -> Result<(), Error> {
+        let result = self.chain.sync_proof_sequence(
+            counterparty_chain_id,
+            counterparty_client_id,
+            counterparty_client_state,
+        );
+        reply_to.send(result).map_err(Error::send)?;
+
+        Ok(())
+    }
 }
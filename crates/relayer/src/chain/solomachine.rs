@@ -0,0 +1,25 @@
+//! A solo machine endpoint, which lets the relayer act on behalf of a standalone
+//! signer whose key is in the Hermes keyring.
+//!
+//! Counterparty chains track the solo machine with `06-solomachine` clients, which
+//! verify every proof against the current key of the solo machine. The endpoint thus
+//! signs the values it proves, in the order in which the counterparty verifies them,
+//! with the sequence the client expects next.
+//!
+//! The IBC state of the solo machine is persisted by the relayer, in a database
+//! kept in `$HOME/.hermes/solomachine/<chain-id>` unless configured otherwise.
+//! The heights of the solo machine count the transactions it executed, and are distinct
+//! from the sequence of its clients, which is why the relayer never updates solo machine
+//! clients to a given height. Before building proofs, the relayer instead reads the
+//! sequence of the counterparty client, see [`ChainEndpoint::sync_proof_sequence`],
+//! so that proofs which were never submitted do not make the sequences diverge.
+//!
+//! [`ChainEndpoint::sync_proof_sequence`]: crate::chain::endpoint::ChainEndpoint::sync_proof_sequence
+
+pub mod error;
+
+mod endpoint;
+mod handler;
+mod store;
+
+pub use endpoint::SoloMachine;
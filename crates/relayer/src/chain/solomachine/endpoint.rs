@@ -0,0 +1,1767 @@
+use alloc::sync::Arc;
+use core::fmt::Display;
+use core::str::FromStr;
+use std::collections::HashMap;
+
+use ibc_proto::cosmos::tx::v1beta1::Fee;
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::apps::fee::v1::{
+    QueryIncentivizedPacketRequest, QueryIncentivizedPacketResponse,
+};
+use ibc_proto::ibc::core::connection::v1::ConnectionEnd as RawConnectionEnd;
+use ibc_proto::protobuf::Protobuf;
+use prost::Message;
+use sha2::{Digest, Sha256};
+use tendermint::abci::Code;
+use tendermint::Hash as TxHash;
+use tendermint_rpc::endpoint::broadcast::tx_sync::Response as TxResponse;
+use tokio::runtime::Runtime as TokioRuntime;
+
+use ibc_relayer_types::applications::ics31_icq::response::CrossChainQueryResponse;
+use ibc_relayer_types::clients::ics06_solomachine::client_state::ClientState as SoloClientState;
+use ibc_relayer_types::clients::ics06_solomachine::consensus_state::{
+    ConsensusState as SoloConsensusState, PublicKey,
+};
+use ibc_relayer_types::clients::ics06_solomachine::header::Header as SoloHeader;
+use ibc_relayer_types::clients::ics06_solomachine::proof::{
+    merkle_path, sign_bytes, signature_data, signature_proof,
+};
+use ibc_relayer_types::core::ics02_client::client_state::ClientState;
+use ibc_relayer_types::core::ics02_client::error::Error as ClientError;
+use ibc_relayer_types::core::ics02_client::events::{NewBlock, UpdateClient};
+use ibc_relayer_types::core::ics03_connection::connection::{
+    ConnectionEnd, IdentifiedConnectionEnd, State,
+};
+use ibc_relayer_types::core::ics04_channel::channel::{ChannelEnd, IdentifiedChannelEnd, Ordering};
+use ibc_relayer_types::core::ics04_channel::packet::{PacketMsgType, Sequence};
+use ibc_relayer_types::core::ics04_channel::upgrade::{ErrorReceipt, Upgrade};
+use ibc_relayer_types::core::ics23_commitment::commitment::{
+    CommitmentPrefix, CommitmentProofBytes,
+};
+use ibc_relayer_types::core::ics23_commitment::merkle::MerkleProof;
+use ibc_relayer_types::core::ics24_host::identifier::{
+    ChainId, ChannelId, ClientId, ConnectionId, PortId,
+};
+use ibc_relayer_types::core::ics24_host::path::{
+    AcksPath, ChannelEndsPath, ClientConsensusStatePath, ClientStatePath, CommitmentsPath,
+    ConnectionsPath, Path, ReceiptsPath, SeqRecvsPath,
+};
+use ibc_relayer_types::events::IbcEvent;
+use ibc_relayer_types::proofs::{ConsensusProof, Proofs};
+use ibc_relayer_types::signer::Signer;
+use ibc_relayer_types::timestamp::Timestamp;
+use ibc_relayer_types::Height as ICSHeight;
+
+use crate::account::Balance;
+use crate::chain::budget::FeeBudgetStatus;
+use crate::chain::client::ClientSettings;
+use crate::chain::endpoint::{ChainEndpoint, ChainStatus, HealthCheck};
+use crate::chain::handle::Subscription;
+use crate::chain::requests::*;
+use crate::chain::tracking::{TrackedMsgs, TrackingId};
+use crate::client_state::{AnyClientState, IdentifiedAnyClientState};
+use crate::config::ChainConfig;
+use crate::connection::ConnectionMsgType;
+use crate::consensus_state::AnyConsensusState;
+use crate::denom::DenomTrace;
+use crate::error::Error;
+use crate::event::bus::EventBus;
+use crate::event::source::{EventBatch, Result as MonitorResult};
+use crate::event::IbcEventWithHeight;
+use crate::keyring::{KeyRing, Secp256k1KeyPair, SigningKeyPair};
+use crate::misbehaviour::MisbehaviourEvidence;
+use crate::util::lock::{LockExt, RwArc};
+
+use super::error::Error as SoloMachineError;
+use super::handler::{deliver, HostContext};
+use super::store::{
+    decode_value, ibc_key, SoloStore, StateView, StoredSignature, SOLO_MACHINE_DEFAULT_FOLDER,
+};
+
+/// A client of the solo machine, identified by the chain hosting it and its identifier on that chain.
+type CounterpartyClient = (ChainId, ClientId);
+
+/// The signature carried by the proofs which are only simulated, with the length of actual signatures.
+const SIMULATED_SIGNATURE: [u8; 64] = [0; 64];
+
+/// The outcome of a transaction executed by the solo machine.
+struct ExecutedTx {
+    hash: TxHash,
+    height: ICSHeight,
+    result: Result<Vec<IbcEvent>, String>,
+}
+
+pub struct SoloMachine {
+    config: ChainConfig,
+    keybase: KeyRing<Secp256k1KeyPair>,
+    store: SoloStore,
+    event_bus: EventBus<Arc<MonitorResult<EventBatch>>>,
+    /// The sequence of the next proof signed for each client of the solo machine,
+    /// as synchronised from the client with [`ChainEndpoint::sync_proof_sequence`].
+    ///
+    /// This lock serialises the signing of proofs, which the store records before
+    /// they are released, see [`SoloMachine::sign_proof`].
+    proof_sequences: RwArc<HashMap<CounterpartyClient, u64>>,
+}
+
+impl SoloMachine {
+    fn new(config: ChainConfig, store: SoloStore) -> Result<Self, Error> {
+        if config.id.version() != 0 {
+            return Err(Error::solo_machine(SoloMachineError::invalid_revision(
+                config.id,
+            )));
+        }
+
+        let keybase =
+            KeyRing::from_config(config.key_store_type, &config).map_err(Error::key_base)?;
+
+        Ok(Self {
+            config,
+            keybase,
+            store,
+            event_bus: EventBus::new(),
+            proof_sequences: RwArc::new_lock(HashMap::new()),
+        })
+    }
+
+    /// The diversifier of the solo machine, which is its chain identifier.
+    fn diversifier(&self) -> &str {
+        self.config.id.as_str()
+    }
+
+    /// The key the solo machine signs with.
+    fn key(&self) -> Result<Secp256k1KeyPair, Error> {
+        self.keybase
+            .get_key(&self.config.key_name)
+            .map_err(Error::key_base)
+    }
+
+    /// The key of the keyring whose public key is the given one.
+    fn key_for(&self, public_key: &PublicKey) -> Result<Secp256k1KeyPair, Error> {
+        self.keybase
+            .keys()
+            .map_err(Error::key_base)?
+            .into_iter()
+            .map(|(_, key_pair)| key_pair)
+            .find(|key_pair| &solo_public_key(key_pair) == public_key)
+            .ok_or_else(|| {
+                Error::solo_machine(SoloMachineError::key_not_found(hex::encode(
+                    &public_key.value,
+                )))
+            })
+    }
+
+    /// The current consensus state of the solo machine, ie. its key and diversifier.
+    fn consensus_state(&self) -> Result<SoloConsensusState, Error> {
+        Ok(SoloConsensusState::new(
+            solo_public_key(&self.key()?),
+            self.diversifier().to_string(),
+            Timestamp::now(),
+        ))
+    }
+
+    /// The client of the solo machine which verifies the proofs about the given connection.
+    fn counterparty_client(
+        &self,
+        connection_end: &ConnectionEnd,
+    ) -> Result<CounterpartyClient, Error> {
+        let client_state = self
+            .store
+            .client_state(connection_end.client_id())
+            .map_err(Error::solo_machine)?;
+
+        Ok((
+            client_state.chain_id(),
+            connection_end.counterparty().client_id().clone(),
+        ))
+    }
+
+    /// The client of the solo machine which verifies the proofs about the given channel.
+    fn channel_counterparty_client(
+        &self,
+        channel_end: &ChannelEnd,
+    ) -> Result<CounterpartyClient, Error> {
+        let connection_id = channel_end
+            .connection_hops()
+            .first()
+            .ok_or_else(|| Error::solo_machine(SoloMachineError::missing_connection_hops()))?;
+
+        let connection_end = self
+            .store
+            .connection(connection_id)
+            .map_err(Error::solo_machine)?;
+
+        self.counterparty_client(&connection_end)
+    }
+
+    /// Sign `value` at `path` with the next sequence of the given client.
+    ///
+    /// Two signatures of different bytes at the same sequence are evidence of misbehaviour,
+    /// which freezes the client, hence a sequence is never signed twice differently: every
+    /// signature is recorded in the store before it is released, and a sequence the client
+    /// has not verified yet can only be signed again with the recorded bytes, ie. when the
+    /// same proof is built again, eg. because its transaction was never committed.
+    ///
+    /// Proofs which are only simulated carry no signature, and do not consume a sequence.
+    fn sign_proof(
+        &self,
+        client: &CounterpartyClient,
+        path: impl Into<Path>,
+        value: Vec<u8>,
+        purpose: ProofPurpose,
+    ) -> Result<CommitmentProofBytes, Error> {
+        if purpose == ProofPurpose::Simulation {
+            return signature_proof(SIMULATED_SIGNATURE.to_vec(), Timestamp::now())
+                .map_err(Error::malformed_proof);
+        }
+
+        let key_pair = self.key()?;
+        let prefix = self.query_commitment_prefix()?;
+        let (chain_id, client_id) = client;
+
+        let mut proof_sequences = self.proof_sequences.acquire_write();
+        let sequence = proof_sequences.get_mut(client).ok_or_else(|| {
+            Error::solo_machine(SoloMachineError::sequence_not_synced(
+                chain_id.clone(),
+                client_id.clone(),
+            ))
+        })?;
+
+        let already_signed = || {
+            Error::solo_machine(SoloMachineError::sequence_already_signed(
+                chain_id.clone(),
+                client_id.clone(),
+                *sequence,
+            ))
+        };
+
+        let path = merkle_path(&prefix, &path.into());
+
+        let signed = self
+            .store
+            .signature(chain_id, client_id, *sequence)
+            .map_err(Error::solo_machine)?;
+
+        let (timestamp, bytes) = match signed {
+            Some(signed) => {
+                let bytes =
+                    sign_bytes(*sequence, signed.timestamp, self.diversifier(), path, value);
+
+                if bytes != signed.sign_bytes {
+                    return Err(already_signed());
+                }
+
+                (signed.timestamp, bytes)
+            }
+            None => {
+                let next_unsigned = self
+                    .store
+                    .next_unsigned_sequence(chain_id, client_id)
+                    .map_err(Error::solo_machine)?;
+
+                if *sequence < next_unsigned {
+                    return Err(already_signed());
+                }
+
+                let timestamp = Timestamp::now();
+                let bytes = sign_bytes(*sequence, timestamp, self.diversifier(), path, value);
+
+                self.store
+                    .record_signature(
+                        chain_id,
+                        client_id,
+                        *sequence,
+                        &StoredSignature {
+                            timestamp,
+                            sign_bytes: bytes.clone(),
+                        },
+                    )
+                    .map_err(Error::solo_machine)?;
+
+                (timestamp, bytes)
+            }
+        };
+
+        let signature = key_pair.sign(&bytes).map_err(Error::key_base)?;
+        *sequence += 1;
+
+        signature_proof(signature, timestamp).map_err(Error::malformed_proof)
+    }
+
+    fn sign_channel(
+        &self,
+        client: &CounterpartyClient,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        channel_end: &ChannelEnd,
+        purpose: ProofPurpose,
+    ) -> Result<CommitmentProofBytes, Error> {
+        self.sign_proof(
+            client,
+            ChannelEndsPath(port_id.clone(), channel_id.clone()),
+            channel_end.encode_vec(),
+            purpose,
+        )
+    }
+
+    /// Execute the given messages atomically, in a transaction at the next height of the
+    /// solo machine, and return the hash of the transaction along with its events.
+    ///
+    /// A failed transaction leaves the state untouched, and emits a `ChainError` event
+    /// for each of its messages.
+    fn execute(&mut self, tracked_msgs: TrackedMsgs) -> Result<ExecutedTx, Error> {
+        let height = self
+            .store
+            .height()
+            .map_err(Error::solo_machine)?
+            .increment();
+        let host = HostContext {
+            height,
+            timestamp: Timestamp::now(),
+        };
+
+        let hash = tx_hash(height, &tracked_msgs.msgs);
+
+        let mut pending = self.store.pending();
+        let result = tracked_msgs
+            .msgs
+            .into_iter()
+            .try_fold(Vec::new(), |mut events, msg| {
+                events.extend(deliver(&mut pending, &host, msg)?);
+                Ok::<_, SoloMachineError>(events)
+            })
+            .map_err(|e| e.to_string());
+
+        let pending = match &result {
+            Ok(events) => {
+                pending
+                    .record_tx(&hash, height, Ok(events))
+                    .map_err(Error::solo_machine)?;
+                pending
+            }
+            Err(e) => {
+                let mut pending = self.store.pending();
+                pending
+                    .record_tx(&hash, height, Err(e.clone()))
+                    .map_err(Error::solo_machine)?;
+                pending
+            }
+        };
+
+        self.store.commit(pending).map_err(Error::solo_machine)?;
+
+        let events = result.as_ref().map(Vec::as_slice).unwrap_or_default();
+        self.broadcast(height, events);
+
+        Ok(ExecutedTx {
+            hash,
+            height,
+            result,
+        })
+    }
+
+    fn broadcast(&mut self, height: ICSHeight, events: &[IbcEvent]) {
+        let events = core::iter::once(IbcEvent::NewBlock(NewBlock::new(height)))
+            .chain(events.iter().cloned())
+            .map(|event| IbcEventWithHeight::new(event, height))
+            .collect();
+
+        self.event_bus.broadcast(Arc::new(Ok(EventBatch {
+            chain_id: self.config.id.clone(),
+            tracking_id: TrackingId::new_uuid(),
+            height,
+            events,
+        })));
+    }
+
+    /// The values stored under the given prefix, indexed by the rest of their path.
+    fn scan<K, T, R>(&self, prefix: &str) -> Result<Vec<(K, T)>, Error>
+    where
+        K: FromStr,
+        T: TryFrom<R>,
+        T::Error: Display,
+        R: Message + Default,
+    {
+        let entries = self.store.scan(prefix).map_err(Error::solo_machine)?;
+
+        entries
+            .into_iter()
+            .filter_map(|(key, value)| Some((key.parse().ok()?, key, value)))
+            .map(|(id, key, value)| {
+                let value =
+                    decode_value(&format!("{prefix}{key}"), &value).map_err(Error::solo_machine)?;
+
+                Ok((id, value))
+            })
+            .collect()
+    }
+
+    /// The sequences of the packets with a value stored under the given ICS-24 path prefix.
+    fn scan_sequences(&self, prefix: String) -> Result<Vec<Sequence>, Error> {
+        let entries = self.store.scan(prefix).map_err(Error::solo_machine)?;
+
+        let mut sequences: Vec<Sequence> = entries
+            .into_iter()
+            .filter_map(|(key, _)| key.parse().ok())
+            .collect();
+
+        sequences.sort();
+
+        Ok(sequences)
+    }
+
+    fn latest_height(&self) -> Result<ICSHeight, Error> {
+        self.store.height().map_err(Error::solo_machine)
+    }
+
+    fn unsupported<T>(&self, operation: &str) -> Result<T, Error> {
+        Err(Error::solo_machine(SoloMachineError::unsupported(
+            operation.to_string(),
+        )))
+    }
+
+    /// Solo machines prove their state by signing it, when building the proofs of a message.
+    fn no_proof(&self, include_proof: IncludeProof) -> Result<Option<MerkleProof>, Error> {
+        match include_proof {
+            IncludeProof::Yes => self.unsupported("Merkle proofs"),
+            IncludeProof::No => Ok(None),
+        }
+    }
+}
+
+fn solo_public_key(key_pair: &Secp256k1KeyPair) -> PublicKey {
+    PublicKey::secp256k1(&key_pair.public_key.serialize())
+}
+
+/// The hash of a transaction, computed from the messages it contains and its height.
+fn tx_hash(height: ICSHeight, msgs: &[Any]) -> TxHash {
+    let mut hasher = Sha256::new();
+    hasher.update(height.to_string());
+
+    for msg in msgs {
+        hasher.update(&msg.type_url);
+        hasher.update(&msg.value);
+    }
+
+    TxHash::Sha256(hasher.finalize().into())
+}
+
+fn chain_error(hash: &TxHash, log: &str) -> IbcEvent {
+    IbcEvent::ChainError(format!(
+        "deliver_tx for {hash} reports error: code=1, log={log:?}"
+    ))
+}
+
+impl ChainEndpoint for SoloMachine {
+    type LightBlock = SoloConsensusState;
+    type Header = SoloHeader;
+    type ConsensusState = SoloConsensusState;
+    type ClientState = SoloClientState;
+    type Time = Timestamp;
+    type SigningKeyPair = Secp256k1KeyPair;
+
+    fn config(&self) -> &ChainConfig {
+        &self.config
+    }
+
+    fn bootstrap(config: ChainConfig, _rt: Arc<TokioRuntime>) -> Result<Self, Error> {
+        let path = match config.solo_machine.as_ref().and_then(|c| c.path.clone()) {
+            Some(path) => path,
+            None => {
+                let home = dirs_next::home_dir().ok_or_else(|| {
+                    Error::solo_machine(SoloMachineError::home_location_unavailable())
+                })?;
+                home.join(SOLO_MACHINE_DEFAULT_FOLDER)
+                    .join(config.id.as_str())
+            }
+        };
+
+        let store = SoloStore::open(&path).map_err(Error::solo_machine)?;
+
+        Self::new(config, store)
+    }
+
+    fn shutdown(self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn health_check(&self) -> Result<HealthCheck, Error> {
+        match self.key() {
+            Ok(_) => Ok(HealthCheck::Healthy),
+            Err(e) => Ok(HealthCheck::Unhealthy(Box::new(e))),
+        }
+    }
+
+    fn subscribe(&mut self) -> Result<Subscription, Error> {
+        Ok(self.event_bus.subscribe())
+    }
+
+    fn keybase(&self) -> &KeyRing<Self::SigningKeyPair> {
+        &self.keybase
+    }
+
+    fn keybase_mut(&mut self) -> &mut KeyRing<Self::SigningKeyPair> {
+        &mut self.keybase
+    }
+
+    fn get_signer(&self) -> Result<Signer, Error> {
+        self.key()?
+            .account()
+            .parse()
+            .map_err(|e| Error::ics02(ClientError::signer(e)))
+    }
+
+    fn ibc_version(&self) -> Result<Option<semver::Version>, Error> {
+        Ok(None)
+    }
+
+    fn send_messages_and_wait_commit(
+        &mut self,
+        tracked_msgs: TrackedMsgs,
+    ) -> Result<Vec<IbcEventWithHeight>, Error> {
+        let message_count = tracked_msgs.msgs.len();
+        let ExecutedTx {
+            hash,
+            height,
+            result,
+        } = self.execute(tracked_msgs)?;
+
+        let events = match result {
+            Ok(events) => events,
+            Err(e) => vec![chain_error(&hash, &e); message_count],
+        };
+
+        Ok(events
+            .into_iter()
+            .map(|event| IbcEventWithHeight::new(event, height))
+            .collect())
+    }
+
+    fn send_messages_and_wait_check_tx(
+        &mut self,
+        tracked_msgs: TrackedMsgs,
+    ) -> Result<Vec<TxResponse>, Error> {
+        let ExecutedTx { hash, result, .. } = self.execute(tracked_msgs)?;
+
+        let (code, log) = match result {
+            Ok(_) => (Code::Ok, String::new()),
+            Err(e) => (Code::Err(1.try_into().expect("non-zero code")), e),
+        };
+
+        Ok(vec![TxResponse {
+            code,
+            data: Default::default(),
+            log,
+            hash,
+        }])
+    }
+
+    fn estimate_fee(&mut self, tracked_msgs: TrackedMsgs) -> Result<Fee, Error> {
+        self.simulate_messages(tracked_msgs)?;
+
+        Ok(Fee::default())
+    }
+
+    fn simulate_messages(&mut self, tracked_msgs: TrackedMsgs) -> Result<u64, Error> {
+        let host = HostContext {
+            height: self.latest_height()?.increment(),
+            timestamp: Timestamp::now(),
+        };
+
+        // The pending changes are dropped without being committed
+        let mut pending = self.store.pending();
+
+        for msg in tracked_msgs.msgs {
+            deliver(&mut pending, &host, msg).map_err(Error::solo_machine)?;
+        }
+
+        Ok(0)
+    }
+
+    fn verify_header(
+        &mut self,
+        _trusted: ICSHeight,
+        _target: ICSHeight,
+        _client_state: &AnyClientState,
+    ) -> Result<Self::LightBlock, Error> {
+        self.consensus_state()
+    }
+
+    /// Evidence of solo machine misbehaviour consists of two signatures
+    /// for the same sequence, which the solo machine does not make.
+    fn check_misbehaviour(
+        &mut self,
+        _update: &UpdateClient,
+        _client_state: &AnyClientState,
+    ) -> Result<Option<MisbehaviourEvidence>, Error> {
+        Ok(None)
+    }
+
+    fn query_balance(
+        &self,
+        _key_name: Option<&str>,
+        _denom: Option<&str>,
+    ) -> Result<Balance, Error> {
+        self.unsupported("balance queries")
+    }
+
+    fn query_all_balances(&self, _key_name: Option<&str>) -> Result<Vec<Balance>, Error> {
+        self.unsupported("balance queries")
+    }
+
+    fn query_denom_trace(&self, _hash: String) -> Result<DenomTrace, Error> {
+        self.unsupported("denomination trace queries")
+    }
+
+    fn query_commitment_prefix(&self) -> Result<CommitmentPrefix, Error> {
+        CommitmentPrefix::try_from(self.config.store_prefix.as_bytes().to_vec())
+            .map_err(|_| Error::ics02(ClientError::empty_prefix()))
+    }
+
+    fn query_application_status(&self) -> Result<ChainStatus, Error> {
+        Ok(ChainStatus {
+            height: self.latest_height()?,
+            timestamp: Timestamp::now(),
+        })
+    }
+
+    fn query_clients(
+        &self,
+        _request: QueryClientStatesRequest,
+    ) -> Result<Vec<IdentifiedAnyClientState>, Error> {
+        let entries = self.store.scan("clients/").map_err(Error::solo_machine)?;
+
+        entries
+            .into_iter()
+            .filter_map(|(key, _)| key.strip_suffix("/clientState")?.parse().ok())
+            .map(|client_id: ClientId| {
+                let client_state = self
+                    .store
+                    .client_state(&client_id)
+                    .map_err(Error::solo_machine)?;
+
+                Ok(IdentifiedAnyClientState::new(client_id, client_state))
+            })
+            .collect()
+    }
+
+    fn query_client_state(
+        &self,
+        request: QueryClientStateRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(AnyClientState, Option<MerkleProof>), Error> {
+        let client_state = self
+            .store
+            .client_state(&request.client_id)
+            .map_err(Error::solo_machine)?;
+
+        Ok((client_state, self.no_proof(include_proof)?))
+    }
+
+    fn query_consensus_state(
+        &self,
+        request: QueryConsensusStateRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(AnyConsensusState, Option<MerkleProof>), Error> {
+        let consensus_state = self
+            .store
+            .consensus_state(&request.client_id, request.consensus_height)
+            .map_err(Error::solo_machine)?;
+
+        Ok((consensus_state, self.no_proof(include_proof)?))
+    }
+
+    fn query_consensus_state_heights(
+        &self,
+        request: QueryConsensusStateHeightsRequest,
+    ) -> Result<Vec<ICSHeight>, Error> {
+        let prefix = format!("clients/{}/consensusStates/", request.client_id);
+        let entries = self.store.scan(prefix).map_err(Error::solo_machine)?;
+
+        let mut heights: Vec<ICSHeight> = entries
+            .into_iter()
+            .filter_map(|(key, _)| key.parse().ok())
+            .collect();
+
+        heights.sort();
+
+        Ok(heights)
+    }
+
+    fn query_upgraded_client_state(
+        &self,
+        _request: QueryUpgradedClientStateRequest,
+    ) -> Result<(AnyClientState, MerkleProof), Error> {
+        self.unsupported("chain upgrades")
+    }
+
+    fn query_upgraded_consensus_state(
+        &self,
+        _request: QueryUpgradedConsensusStateRequest,
+    ) -> Result<(AnyConsensusState, MerkleProof), Error> {
+        self.unsupported("chain upgrades")
+    }
+
+    fn query_connections(
+        &self,
+        _request: QueryConnectionsRequest,
+    ) -> Result<Vec<IdentifiedConnectionEnd>, Error> {
+        let connections = self.scan::<_, _, RawConnectionEnd>("connections/")?;
+
+        Ok(connections
+            .into_iter()
+            .map(|(connection_id, connection_end)| {
+                IdentifiedConnectionEnd::new(connection_id, connection_end)
+            })
+            .collect())
+    }
+
+    fn query_client_connections(
+        &self,
+        request: QueryClientConnectionsRequest,
+    ) -> Result<Vec<ConnectionId>, Error> {
+        let connections = self.query_connections(QueryConnectionsRequest { pagination: None })?;
+
+        Ok(connections
+            .into_iter()
+            .filter(|connection| connection.connection_end.client_id() == &request.client_id)
+            .map(|connection| connection.connection_id)
+            .collect())
+    }
+
+    fn query_connection(
+        &self,
+        request: QueryConnectionRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(ConnectionEnd, Option<MerkleProof>), Error> {
+        let connection_end = self
+            .store
+            .connection(&request.connection_id)
+            .map_err(Error::solo_machine)?;
+
+        Ok((connection_end, self.no_proof(include_proof)?))
+    }
+
+    fn query_connection_channels(
+        &self,
+        request: QueryConnectionChannelsRequest,
+    ) -> Result<Vec<IdentifiedChannelEnd>, Error> {
+        let channels = self.query_channels(QueryChannelsRequest { pagination: None })?;
+
+        Ok(channels
+            .into_iter()
+            .filter(|channel| {
+                channel.channel_end.connection_hops().first() == Some(&request.connection_id)
+            })
+            .collect())
+    }
+
+    fn query_channels(
+        &self,
+        _request: QueryChannelsRequest,
+    ) -> Result<Vec<IdentifiedChannelEnd>, Error> {
+        let entries = self
+            .store
+            .scan("channelEnds/ports/")
+            .map_err(Error::solo_machine)?;
+
+        entries
+            .into_iter()
+            .filter_map(|(key, _)| {
+                let (port_id, channel_id) = key.split_once("/channels/")?;
+                Some((port_id.parse().ok()?, channel_id.parse().ok()?))
+            })
+            .map(|(port_id, channel_id): (PortId, ChannelId)| {
+                let channel_end = self
+                    .store
+                    .channel(&port_id, &channel_id)
+                    .map_err(Error::solo_machine)?;
+
+                Ok(IdentifiedChannelEnd::new(port_id, channel_id, channel_end))
+            })
+            .collect()
+    }
+
+    fn query_channel(
+        &self,
+        request: QueryChannelRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(ChannelEnd, Option<MerkleProof>), Error> {
+        let channel_end = self
+            .store
+            .channel(&request.port_id, &request.channel_id)
+            .map_err(Error::solo_machine)?;
+
+        Ok((channel_end, self.no_proof(include_proof)?))
+    }
+
+    fn query_upgrade(
+        &self,
+        _request: QueryUpgradeRequest,
+        _include_proof: IncludeProof,
+    ) -> Result<(Upgrade, Option<MerkleProof>), Error> {
+        self.unsupported("channel upgrades")
+    }
+
+    fn query_upgrade_error(
+        &self,
+        _request: QueryUpgradeErrorRequest,
+        _include_proof: IncludeProof,
+    ) -> Result<(ErrorReceipt, Option<MerkleProof>), Error> {
+        self.unsupported("channel upgrades")
+    }
+
+    fn query_channel_client_state(
+        &self,
+        request: QueryChannelClientStateRequest,
+    ) -> Result<Option<IdentifiedAnyClientState>, Error> {
+        let channel_end = self
+            .store
+            .channel(&request.port_id, &request.channel_id)
+            .map_err(Error::solo_machine)?;
+
+        let Some(connection_id) = channel_end.connection_hops().first() else {
+            return Ok(None);
+        };
+
+        let client_id = self
+            .store
+            .connection(connection_id)
+            .map_err(Error::solo_machine)?
+            .client_id()
+            .clone();
+
+        let client_state = self
+            .store
+            .client_state(&client_id)
+            .map_err(Error::solo_machine)?;
+
+        Ok(Some(IdentifiedAnyClientState::new(client_id, client_state)))
+    }
+
+    fn query_packet_commitment(
+        &self,
+        request: QueryPacketCommitmentRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(Vec<u8>, Option<MerkleProof>), Error> {
+        let commitment = self
+            .store
+            .get_raw(&ibc_key(CommitmentsPath {
+                port_id: request.port_id,
+                channel_id: request.channel_id,
+                sequence: request.sequence,
+            }))
+            .map_err(Error::solo_machine)?;
+
+        Ok((
+            commitment.unwrap_or_default(),
+            self.no_proof(include_proof)?,
+        ))
+    }
+
+    fn query_packet_commitments(
+        &self,
+        request: QueryPacketCommitmentsRequest,
+    ) -> Result<(Vec<Sequence>, ICSHeight), Error> {
+        let sequences = self.scan_sequences(format!(
+            "commitments/ports/{}/channels/{}/sequences/",
+            request.port_id, request.channel_id
+        ))?;
+
+        Ok((sequences, self.latest_height()?))
+    }
+
+    fn query_packet_receipt(
+        &self,
+        request: QueryPacketReceiptRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(Vec<u8>, Option<MerkleProof>), Error> {
+        let receipt = self
+            .store
+            .get_raw(&ibc_key(ReceiptsPath {
+                port_id: request.port_id,
+                channel_id: request.channel_id,
+                sequence: request.sequence,
+            }))
+            .map_err(Error::solo_machine)?;
+
+        Ok((receipt.unwrap_or_default(), self.no_proof(include_proof)?))
+    }
+
+    fn query_unreceived_packets(
+        &self,
+        request: QueryUnreceivedPacketsRequest,
+    ) -> Result<Vec<Sequence>, Error> {
+        let channel_end = self
+            .store
+            .channel(&request.port_id, &request.channel_id)
+            .map_err(Error::solo_machine)?;
+
+        let next_sequence_recv = self
+            .store
+            .sequence(&ibc_key(SeqRecvsPath(
+                request.port_id.clone(),
+                request.channel_id.clone(),
+            )))
+            .map_err(Error::solo_machine)?;
+
+        let mut unreceived = Vec::new();
+
+        for sequence in request.packet_commitment_sequences {
+            let is_received = if channel_end.order_matches(&Ordering::Ordered) {
+                next_sequence_recv.map_or(false, |next| sequence < next)
+            } else {
+                self.store
+                    .contains(&ibc_key(ReceiptsPath {
+                        port_id: request.port_id.clone(),
+                        channel_id: request.channel_id.clone(),
+                        sequence,
+                    }))
+                    .map_err(Error::solo_machine)?
+            };
+
+            if !is_received {
+                unreceived.push(sequence);
+            }
+        }
+
+        Ok(unreceived)
+    }
+
+    fn query_packet_acknowledgement(
+        &self,
+        request: QueryPacketAcknowledgementRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(Vec<u8>, Option<MerkleProof>), Error> {
+        let ack = self
+            .store
+            .get_raw(&ibc_key(AcksPath {
+                port_id: request.port_id,
+                channel_id: request.channel_id,
+                sequence: request.sequence,
+            }))
+            .map_err(Error::solo_machine)?;
+
+        Ok((ack.unwrap_or_default(), self.no_proof(include_proof)?))
+    }
+
+    fn query_packet_acknowledgements(
+        &self,
+        request: QueryPacketAcknowledgementsRequest,
+    ) -> Result<(Vec<Sequence>, ICSHeight), Error> {
+        let sequences = self.scan_sequences(format!(
+            "acks/ports/{}/channels/{}/sequences/",
+            request.port_id, request.channel_id
+        ))?;
+
+        let sequences = sequences
+            .into_iter()
+            .filter(|sequence| {
+                request.packet_commitment_sequences.is_empty()
+                    || request.packet_commitment_sequences.contains(sequence)
+            })
+            .collect();
+
+        Ok((sequences, self.latest_height()?))
+    }
+
+    fn query_unreceived_acknowledgements(
+        &self,
+        request: QueryUnreceivedAcksRequest,
+    ) -> Result<Vec<Sequence>, Error> {
+        let mut unreceived = Vec::new();
+
+        for sequence in request.packet_ack_sequences {
+            let has_commitment = self
+                .store
+                .contains(&ibc_key(CommitmentsPath {
+                    port_id: request.port_id.clone(),
+                    channel_id: request.channel_id.clone(),
+                    sequence,
+                }))
+                .map_err(Error::solo_machine)?;
+
+            if has_commitment {
+                unreceived.push(sequence);
+            }
+        }
+
+        Ok(unreceived)
+    }
+
+    fn query_next_sequence_receive(
+        &self,
+        request: QueryNextSequenceReceiveRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(Sequence, Option<MerkleProof>), Error> {
+        let sequence = self
+            .store
+            .sequence(&ibc_key(SeqRecvsPath(
+                request.port_id.clone(),
+                request.channel_id.clone(),
+            )))
+            .map_err(Error::solo_machine)?
+            .ok_or_else(|| {
+                Error::solo_machine(SoloMachineError::channel_not_found(
+                    request.port_id,
+                    request.channel_id,
+                ))
+            })?;
+
+        Ok((sequence, self.no_proof(include_proof)?))
+    }
+
+    fn query_txs(&self, request: QueryTxRequest) -> Result<Vec<IbcEventWithHeight>, Error> {
+        match request {
+            QueryTxRequest::Transaction(tx) => {
+                let Some(stored) = self.store.tx(&tx.0).map_err(Error::solo_machine)? else {
+                    return Ok(Vec::new());
+                };
+
+                let events = match stored.result {
+                    Ok(events) => events
+                        .iter()
+                        .filter_map(|event| event.to_ibc_event())
+                        .collect(),
+                    Err(e) => vec![chain_error(&tx.0, &e)],
+                };
+
+                Ok(events
+                    .into_iter()
+                    .map(|event| IbcEventWithHeight::new(event, stored.height))
+                    .collect())
+            }
+
+            QueryTxRequest::Client(request) => {
+                let stored = self
+                    .store
+                    .client_event(
+                        &request.client_id,
+                        request.consensus_height,
+                        request.event_id.as_str(),
+                    )
+                    .map_err(Error::solo_machine)?;
+
+                let max_height = match request.query_height {
+                    QueryHeight::Latest => self.latest_height()?,
+                    QueryHeight::Specific(height) => height,
+                };
+
+                Ok(stored
+                    .filter(|stored| stored.height <= max_height)
+                    .and_then(|stored| {
+                        let event = stored.event.to_ibc_event()?;
+                        Some(IbcEventWithHeight::new(event, stored.height))
+                    })
+                    .into_iter()
+                    .collect())
+            }
+        }
+    }
+
+    fn query_packet_events(
+        &self,
+        request: QueryPacketEventDataRequest,
+    ) -> Result<Vec<IbcEventWithHeight>, Error> {
+        let latest_height = self.latest_height()?;
+
+        let in_range = |height: ICSHeight| match request.height {
+            Qualified::Equal(QueryHeight::Specific(h)) => height == h,
+            Qualified::SmallerEqual(QueryHeight::Specific(h)) => height <= h,
+            Qualified::Equal(QueryHeight::Latest) => height == latest_height,
+            Qualified::SmallerEqual(QueryHeight::Latest) => true,
+        };
+
+        let mut events = Vec::new();
+
+        for sequence in &request.sequences {
+            let stored = self
+                .store
+                .packet_event(
+                    request.event_id.as_str(),
+                    &request.source_port_id,
+                    &request.source_channel_id,
+                    *sequence,
+                )
+                .map_err(Error::solo_machine)?;
+
+            let Some(stored) = stored.filter(|stored| in_range(stored.height)) else {
+                continue;
+            };
+
+            let Some(event) = stored.event.to_ibc_event() else {
+                continue;
+            };
+
+            let matches_destination = event.packet().map_or(false, |packet| {
+                packet.destination_port == request.destination_port_id
+                    && packet.destination_channel == request.destination_channel_id
+            });
+
+            if matches_destination {
+                events.push(IbcEventWithHeight::new(event, stored.height));
+            }
+        }
+
+        Ok(events)
+    }
+
+    fn query_host_consensus_state(
+        &self,
+        _request: QueryHostConsensusStateRequest,
+    ) -> Result<Self::ConsensusState, Error> {
+        self.consensus_state()
+    }
+
+    /// Build the state of a new client of the solo machine, which expects its first
+    /// signature at sequence 1.
+    fn build_client_state(
+        &self,
+        _height: ICSHeight,
+        _settings: ClientSettings,
+    ) -> Result<Self::ClientState, Error> {
+        Ok(SoloClientState::new(1, self.consensus_state()?))
+    }
+
+    fn build_consensus_state(
+        &self,
+        light_block: Self::LightBlock,
+    ) -> Result<Self::ConsensusState, Error> {
+        Ok(light_block)
+    }
+
+    /// Sign a header at the sequence of the given client, with the key matching
+    /// the public key of the client. The header switches the client to the key and
+    /// diversifier currently configured for the solo machine.
+    fn build_header(
+        &mut self,
+        _trusted_height: ICSHeight,
+        _target_height: ICSHeight,
+        client_state: &AnyClientState,
+    ) -> Result<(Self::Header, Vec<Self::Header>), Error> {
+        let AnyClientState::Solomachine(client_state) = client_state else {
+            return Err(Error::solo_machine(
+                SoloMachineError::unexpected_client_type(client_state.client_type()),
+            ));
+        };
+
+        let current = &client_state.consensus_state;
+        let signing_key_pair = self.key_for(&current.public_key)?;
+        let new_public_key = solo_public_key(&self.key()?);
+        let new_diversifier = self.diversifier().to_string();
+
+        let timestamp = Timestamp::now();
+        let bytes = SoloHeader::sign_bytes(
+            client_state.sequence,
+            timestamp,
+            &current.diversifier,
+            &new_public_key,
+            &new_diversifier,
+        );
+
+        let signature = signing_key_pair.sign(&bytes).map_err(Error::key_base)?;
+
+        let header = SoloHeader {
+            sequence: client_state.sequence,
+            timestamp,
+            signature: signature_data(signature),
+            new_public_key,
+            new_diversifier,
+        };
+
+        Ok((header, vec![]))
+    }
+
+    /// Sign the connection end and, for `OpenTry` and `OpenAck`, the client and consensus
+    /// states of the counterparty, in the order in which the counterparty verifies them.
+    fn build_connection_proofs_and_client_state(
+        &self,
+        message_type: ConnectionMsgType,
+        connection_id: &ConnectionId,
+        client_id: &ClientId,
+        height: ICSHeight,
+    ) -> Result<(Option<AnyClientState>, Proofs), Error> {
+        let connection_end = self
+            .store
+            .connection(connection_id)
+            .map_err(Error::solo_machine)?;
+
+        let expected_states: &[State] = match message_type {
+            ConnectionMsgType::OpenTry => &[State::Init, State::TryOpen],
+            ConnectionMsgType::OpenAck => &[State::TryOpen, State::Open],
+            ConnectionMsgType::OpenConfirm => &[State::Open],
+        };
+
+        if !expected_states
+            .iter()
+            .any(|state| connection_end.state_matches(state))
+        {
+            return Err(Error::bad_connection_state());
+        }
+
+        let counterparty_client = self.counterparty_client(&connection_end)?;
+
+        let connection_proof = self.sign_proof(
+            &counterparty_client,
+            ConnectionsPath(connection_id.clone()),
+            connection_end.encode_vec(),
+            ProofPurpose::Submission,
+        )?;
+
+        if let ConnectionMsgType::OpenConfirm = message_type {
+            let proofs = Proofs::new(connection_proof, None, None, None, height.increment())
+                .map_err(Error::malformed_proof)?;
+
+            return Ok((None, proofs));
+        }
+
+        let client_state = self
+            .store
+            .client_state(client_id)
+            .map_err(Error::solo_machine)?;
+
+        let consensus_height = client_state.latest_height();
+
+        let consensus_state = self
+            .store
+            .consensus_state(client_id, consensus_height)
+            .map_err(Error::solo_machine)?;
+
+        let client_proof = self.sign_proof(
+            &counterparty_client,
+            ClientStatePath(client_id.clone()),
+            Any::from(client_state.clone()).encode_to_vec(),
+            ProofPurpose::Submission,
+        )?;
+
+        let consensus_proof = self.sign_proof(
+            &counterparty_client,
+            ClientConsensusStatePath {
+                client_id: client_id.clone(),
+                epoch: consensus_height.revision_number(),
+                height: consensus_height.revision_height(),
+            },
+            Any::from(consensus_state).encode_to_vec(),
+            ProofPurpose::Submission,
+        )?;
+
+        let proofs = Proofs::new(
+            connection_proof,
+            Some(client_proof),
+            Some(
+                ConsensusProof::new(consensus_proof, consensus_height)
+                    .map_err(Error::consensus_proof)?,
+            ),
+            None,
+            height.increment(),
+        )
+        .map_err(Error::malformed_proof)?;
+
+        Ok((Some(client_state), proofs))
+    }
+
+    fn build_channel_proofs(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        height: ICSHeight,
+    ) -> Result<Proofs, Error> {
+        let channel_end = self
+            .store
+            .channel(port_id, channel_id)
+            .map_err(Error::solo_machine)?;
+
+        let counterparty_client = self.channel_counterparty_client(&channel_end)?;
+        let channel_proof = self.sign_channel(
+            &counterparty_client,
+            port_id,
+            channel_id,
+            &channel_end,
+            ProofPurpose::Submission,
+        )?;
+
+        Proofs::new(channel_proof, None, None, None, height.increment())
+            .map_err(Error::malformed_proof)
+    }
+
+    /// Sign the packet data to prove and, for timeouts on close, the channel end,
+    /// in the order in which the counterparty verifies them.
+    fn build_packet_proofs(
+        &self,
+        packet_type: PacketMsgType,
+        port_id: PortId,
+        channel_id: ChannelId,
+        sequence: Sequence,
+        height: ICSHeight,
+        purpose: ProofPurpose,
+    ) -> Result<Proofs, Error> {
+        let channel_end = self
+            .store
+            .channel(&port_id, &channel_id)
+            .map_err(Error::solo_machine)?;
+
+        let counterparty_client = self.channel_counterparty_client(&channel_end)?;
+
+        // The counterparty checks that the channel is closed before verifying the packet proof
+        let channel_proof = match packet_type {
+            PacketMsgType::TimeoutOnCloseUnordered | PacketMsgType::TimeoutOnCloseOrdered => {
+                Some(self.sign_channel(
+                    &counterparty_client,
+                    &port_id,
+                    &channel_id,
+                    &channel_end,
+                    purpose,
+                )?)
+            }
+            _ => None,
+        };
+
+        let packet_proof = match packet_type {
+            PacketMsgType::Recv => {
+                let (commitment, _) = self.query_packet_commitment(
+                    QueryPacketCommitmentRequest {
+                        port_id: port_id.clone(),
+                        channel_id: channel_id.clone(),
+                        sequence,
+                        height: QueryHeight::Latest,
+                    },
+                    IncludeProof::No,
+                )?;
+
+                self.sign_proof(
+                    &counterparty_client,
+                    CommitmentsPath {
+                        port_id,
+                        channel_id,
+                        sequence,
+                    },
+                    commitment,
+                    purpose,
+                )?
+            }
+            PacketMsgType::Ack => {
+                let (ack_commitment, _) = self.query_packet_acknowledgement(
+                    QueryPacketAcknowledgementRequest {
+                        port_id: port_id.clone(),
+                        channel_id: channel_id.clone(),
+                        sequence,
+                        height: QueryHeight::Latest,
+                    },
+                    IncludeProof::No,
+                )?;
+
+                self.sign_proof(
+                    &counterparty_client,
+                    AcksPath {
+                        port_id,
+                        channel_id,
+                        sequence,
+                    },
+                    ack_commitment,
+                    purpose,
+                )?
+            }
+            // Proofs of absence sign an empty value
+            PacketMsgType::TimeoutUnordered | PacketMsgType::TimeoutOnCloseUnordered => self
+                .sign_proof(
+                    &counterparty_client,
+                    ReceiptsPath {
+                        port_id,
+                        channel_id,
+                        sequence,
+                    },
+                    Vec::new(),
+                    purpose,
+                )?,
+            PacketMsgType::TimeoutOrdered | PacketMsgType::TimeoutOnCloseOrdered => {
+                let (next_sequence, _) = self.query_next_sequence_receive(
+                    QueryNextSequenceReceiveRequest {
+                        port_id: port_id.clone(),
+                        channel_id: channel_id.clone(),
+                        height: QueryHeight::Latest,
+                    },
+                    IncludeProof::No,
+                )?;
+
+                self.sign_proof(
+                    &counterparty_client,
+                    SeqRecvsPath(port_id, channel_id),
+                    u64::from(next_sequence).to_be_bytes().to_vec(),
+                    purpose,
+                )?
+            }
+        };
+
+        Proofs::new(packet_proof, None, None, channel_proof, height.increment())
+            .map_err(Error::malformed_proof)
+    }
+
+    /// Restart the proofs for the given client from the sequence it expects next, and forget
+    /// the signatures recorded for the sequences it has moved past. The signatures recorded
+    /// for the sequences it expects are kept, as they may still be submitted.
+    fn sync_proof_sequence(
+        &mut self,
+        counterparty_chain_id: &ChainId,
+        counterparty_client_id: &ClientId,
+        counterparty_client_state: &AnyClientState,
+    ) -> Result<(), Error> {
+        let AnyClientState::Solomachine(client_state) = counterparty_client_state else {
+            return Err(Error::solo_machine(
+                SoloMachineError::unexpected_client_type(counterparty_client_state.client_type()),
+            ));
+        };
+
+        self.store
+            .forget_signatures_below(
+                counterparty_chain_id,
+                counterparty_client_id,
+                client_state.sequence,
+            )
+            .map_err(Error::solo_machine)?;
+
+        self.proof_sequences.acquire_write().insert(
+            (
+                counterparty_chain_id.clone(),
+                counterparty_client_id.clone(),
+            ),
+            client_state.sequence,
+        );
+
+        Ok(())
+    }
+
+    fn maybe_register_counterparty_payee(
+        &mut self,
+        _channel_id: &ChannelId,
+        _port_id: &PortId,
+        _counterparty_payee: &Signer,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn send_coins(
+        &mut self,
+        _from_key_name: &str,
+        _to_address: &str,
+        _amount: u128,
+        _denom: &str,
+    ) -> Result<(), Error> {
+        self.unsupported("token transfers between accounts")
+    }
+
+    fn cross_chain_query(
+        &self,
+        _requests: Vec<CrossChainQueryRequest>,
+    ) -> Result<Vec<CrossChainQueryResponse>, Error> {
+        self.unsupported("cross-chain queries")
+    }
+
+    fn query_incentivized_packet(
+        &self,
+        _request: QueryIncentivizedPacketRequest,
+    ) -> Result<QueryIncentivizedPacketResponse, Error> {
+        self.unsupported("fee middleware queries")
+    }
+
+    fn query_fee_budget(&self) -> Result<Option<FeeBudgetStatus>, Error> {
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use hdpath::StandardHDPath;
+    use ibc_proto::cosmos::tx::signing::v1beta1::signature_descriptor::data::Sum;
+    use ibc_proto::cosmos::tx::signing::v1beta1::signature_descriptor::Data as SignatureData;
+    use secp256k1::{ecdsa::Signature, Message as SecpMessage, Secp256k1};
+
+    use ibc_relayer_types::clients::ics07_tendermint::client_state::test_util::get_dummy_tendermint_client_state;
+    use ibc_relayer_types::clients::ics07_tendermint::consensus_state::ConsensusState as TmConsensusState;
+    use ibc_relayer_types::clients::ics07_tendermint::header::test_util::get_dummy_tendermint_header;
+    use ibc_relayer_types::core::ics02_client::client_type::ClientType;
+    use ibc_relayer_types::core::ics02_client::msgs::create_client::MsgCreateClient;
+    use ibc_relayer_types::core::ics03_connection::connection::Counterparty;
+    use ibc_relayer_types::core::ics03_connection::msgs::conn_open_init::MsgConnectionOpenInit;
+    use ibc_relayer_types::tx_msg::Msg;
+
+    use crate::chain::ChainType;
+    use crate::config::{load, AddressType};
+    use crate::keyring::Store;
+
+    const MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon \
+                            abandon abandon abandon abandon abandon about";
+
+    fn solo_machine(db: &sled::Db) -> SoloMachine {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/config/fixtures/relayer_conf_example.toml"
+        );
+
+        let mut config = load(path).unwrap().chains.remove(0);
+        config.r#type = ChainType::SoloMachine;
+        config.key_store_type = Store::Memory;
+
+        let store = SoloStore::from_db(db).unwrap();
+        let mut solo = SoloMachine::new(config, store).unwrap();
+
+        let key_pair = Secp256k1KeyPair::from_mnemonic(
+            MNEMONIC,
+            &StandardHDPath::from_str("m/44'/118'/0'/0/0").unwrap(),
+            &AddressType::Cosmos,
+            "cosmos",
+        )
+        .unwrap();
+
+        let key_name = solo.config.key_name.clone();
+        solo.add_key(&key_name, key_pair).unwrap();
+
+        solo
+    }
+
+    fn temporary_db() -> sled::Db {
+        sled::Config::new().temporary(true).open().unwrap()
+    }
+
+    fn new_client_state(solo: &SoloMachine) -> SoloClientState {
+        let height = solo.query_application_status().unwrap().height;
+        let settings = ClientSettings::Tendermint(crate::chain::cosmos::client::Settings {
+            max_clock_drift: core::time::Duration::from_secs(5),
+            trusting_period: None,
+            trust_threshold: Default::default(),
+        });
+
+        solo.build_client_state(height, settings).unwrap()
+    }
+
+    fn counterparty_client() -> CounterpartyClient {
+        (
+            ChainId::from_string("ibc-1"),
+            ClientId::new(ClientType::Solomachine, 0).unwrap(),
+        )
+    }
+
+    fn next_sequence(solo: &SoloMachine) -> Option<u64> {
+        solo.proof_sequences
+            .acquire_read()
+            .get(&counterparty_client())
+            .copied()
+    }
+
+    fn tracked(msgs: Vec<Any>) -> TrackedMsgs {
+        TrackedMsgs::new_static(msgs, "test")
+    }
+
+    /// Host a Tendermint client on the solo machine, and return its identifier.
+    fn create_client(solo: &mut SoloMachine) -> ClientId {
+        let header = get_dummy_tendermint_header();
+        let client_state = get_dummy_tendermint_client_state(header.clone());
+
+        let msg = MsgCreateClient::new(
+            AnyClientState::from(client_state).into(),
+            AnyConsensusState::from(TmConsensusState::from(header)).into(),
+            solo.get_signer().unwrap(),
+        )
+        .unwrap();
+
+        let events = solo
+            .send_messages_and_wait_commit(tracked(vec![msg.to_any()]))
+            .unwrap();
+
+        match &events[0].event {
+            IbcEvent::CreateClient(e) => e.client_id().clone(),
+            event => panic!("expected a CreateClient event, got {event}"),
+        }
+    }
+
+    fn sync(solo: &mut SoloMachine, sequence: u64) {
+        let client_state = SoloClientState::new(sequence, new_client_state(solo).consensus_state);
+        let (chain_id, client_id) = counterparty_client();
+
+        solo.sync_proof_sequence(&chain_id, &client_id, &client_state.into())
+            .unwrap();
+    }
+
+    fn sign(solo: &SoloMachine, value: u8) -> Result<CommitmentProofBytes, Error> {
+        solo.sign_proof(
+            &counterparty_client(),
+            ConnectionsPath(ConnectionId::new(0)),
+            vec![value],
+            ProofPurpose::Submission,
+        )
+    }
+
+    #[test]
+    fn proofs_require_a_synchronised_sequence() {
+        let solo = solo_machine(&temporary_db());
+
+        assert!(sign(&solo, 1).is_err());
+    }
+
+    #[test]
+    fn sequences_are_never_signed_twice_differently() {
+        let mut solo = solo_machine(&temporary_db());
+
+        sync(&mut solo, 1);
+        let first = sign(&solo, 1).unwrap();
+        sign(&solo, 2).unwrap();
+        assert_eq!(next_sequence(&solo), Some(3));
+
+        // The proofs were never submitted, hence the client still expects sequence 1
+        sync(&mut solo, 1);
+        assert_eq!(next_sequence(&solo), Some(1));
+
+        // The same proof can be built again, but not a different one
+        assert_eq!(sign(&solo, 1).unwrap(), first);
+        assert!(sign(&solo, 3).is_err());
+        assert_eq!(next_sequence(&solo), Some(2));
+
+        // Once the client moved past the signed sequences, proofs are signed again
+        sync(&mut solo, 3);
+        sign(&solo, 3).unwrap();
+        assert_eq!(next_sequence(&solo), Some(4));
+    }
+
+    #[test]
+    fn signed_sequences_survive_restarts() {
+        let db = temporary_db();
+
+        let mut solo = solo_machine(&db);
+        sync(&mut solo, 1);
+        sign(&solo, 1).unwrap();
+        drop(solo);
+
+        let mut solo = solo_machine(&db);
+        sync(&mut solo, 1);
+        assert!(sign(&solo, 2).is_err());
+    }
+
+    #[test]
+    fn simulated_proofs_consume_no_sequence() {
+        let mut solo = solo_machine(&temporary_db());
+        let (chain_id, client_id) = counterparty_client();
+
+        sync(&mut solo, 1);
+        solo.sign_proof(
+            &counterparty_client(),
+            ConnectionsPath(ConnectionId::new(0)),
+            vec![1],
+            ProofPurpose::Simulation,
+        )
+        .unwrap();
+
+        assert_eq!(next_sequence(&solo), Some(1));
+        assert_eq!(
+            solo.store
+                .next_unsigned_sequence(&chain_id, &client_id)
+                .unwrap(),
+            0
+        );
+
+        sign(&solo, 2).unwrap();
+        assert_eq!(next_sequence(&solo), Some(2));
+    }
+
+    #[test]
+    fn state_survives_restarts() {
+        let db = temporary_db();
+
+        let mut solo = solo_machine(&db);
+        let client_id = create_client(&mut solo);
+        let height = solo.query_application_status().unwrap().height;
+        drop(solo);
+
+        let solo = solo_machine(&db);
+        assert_eq!(solo.query_application_status().unwrap().height, height);
+
+        let clients = solo
+            .query_clients(QueryClientStatesRequest { pagination: None })
+            .unwrap();
+        assert_eq!(clients.len(), 1);
+        assert_eq!(clients[0].client_id, client_id);
+
+        let consensus_height = clients[0].client_state.latest_height();
+        let events = solo
+            .query_txs(QueryTxRequest::Client(QueryClientEventRequest {
+                query_height: QueryHeight::Latest,
+                event_id: ibc_relayer_types::events::WithBlockDataType::CreateClient,
+                client_id,
+                consensus_height,
+            }))
+            .unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].height, height);
+    }
+
+    #[test]
+    fn failed_transactions_leave_the_state_untouched() {
+        let mut solo = solo_machine(&temporary_db());
+        let height = solo.query_application_status().unwrap().height;
+
+        let msg = MsgConnectionOpenInit {
+            client_id: ClientId::new(ClientType::Tendermint, 42).unwrap(),
+            counterparty: Counterparty::new(
+                ClientId::new(ClientType::Solomachine, 0).unwrap(),
+                None,
+                solo.query_commitment_prefix().unwrap(),
+            ),
+            version: None,
+            delay_period: Default::default(),
+            signer: solo.get_signer().unwrap(),
+        };
+
+        let events = solo
+            .send_messages_and_wait_commit(tracked(vec![msg.to_any()]))
+            .unwrap();
+
+        assert!(matches!(events[0].event, IbcEvent::ChainError(_)));
+        assert!(solo.query_application_status().unwrap().height > height);
+
+        let connections = solo
+            .query_connections(QueryConnectionsRequest { pagination: None })
+            .unwrap();
+        assert!(connections.is_empty());
+    }
+
+    #[test]
+    fn headers_are_signed_at_the_sequence_of_the_client() {
+        let mut solo = solo_machine(&temporary_db());
+        let height = solo.query_application_status().unwrap().height;
+
+        // A client which verified more proofs than the solo machine signed since its creation
+        let client_state = SoloClientState::new(5, new_client_state(&solo).consensus_state);
+
+        let (header, _) = solo
+            .build_header(height, height, &client_state.clone().into())
+            .unwrap();
+
+        assert_eq!(header.sequence, 5);
+        assert_eq!(
+            client_state.with_header(header.clone()).latest_height(),
+            ICSHeight::new(0, 6).unwrap()
+        );
+
+        let Some(Sum::Single(single)) = SignatureData::decode(header.signature.as_slice())
+            .unwrap()
+            .sum
+        else {
+            panic!("expected a signature made by a single key");
+        };
+
+        let sign_bytes = SoloHeader::sign_bytes(
+            header.sequence,
+            header.timestamp,
+            solo.diversifier(),
+            &header.new_public_key,
+            &header.new_diversifier,
+        );
+
+        let message = SecpMessage::from_slice(&Sha256::digest(sign_bytes)).unwrap();
+        let signature = Signature::from_compact(&single.signature).unwrap();
+
+        Secp256k1::verification_only()
+            .verify_ecdsa(&message, &signature, &solo.key().unwrap().public_key)
+            .unwrap();
+    }
+}
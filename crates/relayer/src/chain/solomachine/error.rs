@@ -0,0 +1,111 @@
+use flex_error::{define_error, TraceError};
+
+use ibc_relayer_types::core::ics02_client::client_type::ClientType;
+use ibc_relayer_types::core::ics02_client::error::Error as ClientError;
+use ibc_relayer_types::core::ics04_channel::packet::Sequence;
+use ibc_relayer_types::core::ics24_host::identifier::{
+    ChainId, ChannelId, ClientId, ConnectionId, PortId,
+};
+use ibc_relayer_types::Height;
+
+define_error! {
+    Error {
+        InvalidRevision
+            { chain_id: ChainId }
+            |e| { format!("the identifier of solo machine '{}' must have revision number 0, as the heights of solo machines do", e.chain_id) },
+
+        UnexpectedClientType
+            { client_type: ClientType }
+            |e| { format!("solo machines only sign headers for solo machine clients, not clients of type '{}'", e.client_type) },
+
+        KeyNotFound
+            { public_key: String }
+            |e| { format!("no key in the keyring of the solo machine matches the public key of its client: {}", e.public_key) },
+
+        SequenceNotSynced
+            { chain_id: ChainId, client_id: ClientId }
+            |e| { format!("the sequence of client '{}' on chain '{}' must be synchronised before the solo machine signs proofs for it", e.client_id, e.chain_id) },
+
+        SequenceAlreadySigned
+            { chain_id: ChainId, client_id: ClientId, sequence: u64 }
+            |e| { format!("the solo machine already signed a different proof at sequence {} of client '{}' on chain '{}', which must be verified before the solo machine signs further proofs for that client", e.sequence, e.client_id, e.chain_id) },
+
+        Db
+            [ TraceError<sled::Error> ]
+            |_| { "solo machine state database error" },
+
+        Encode
+            [ TraceError<serde_json::Error> ]
+            |_| { "failed to encode solo machine state entry" },
+
+        Decode
+            [ TraceError<serde_json::Error> ]
+            |_| { "failed to decode solo machine state entry" },
+
+        DecodeValue
+            { key: String, reason: String }
+            |e| { format!("failed to decode the value stored at '{}': {}", e.key, e.reason) },
+
+        HomeLocationUnavailable
+            |_| { "home location is unavailable" },
+
+        UnknownMessage
+            { type_url: String }
+            |e| { format!("solo machines do not handle messages of type '{}'", e.type_url) },
+
+        DecodeMessage
+            { type_url: String, reason: String }
+            |e| { format!("failed to decode message of type '{}': {}", e.type_url, e.reason) },
+
+        Client
+            [ ClientError ]
+            |_| { "invalid client state, consensus state or header" },
+
+        UnsupportedClientType
+            { client_type: ClientType }
+            |e| { format!("solo machines only host Tendermint clients, not clients of type '{}'", e.client_type) },
+
+        ClientNotFound
+            { client_id: ClientId }
+            |e| { format!("client '{}' not found", e.client_id) },
+
+        ConsensusStateNotFound
+            { client_id: ClientId, height: Height }
+            |e| { format!("consensus state of client '{}' at height {} not found", e.client_id, e.height) },
+
+        ConnectionNotFound
+            { connection_id: ConnectionId }
+            |e| { format!("connection '{}' not found", e.connection_id) },
+
+        ChannelNotFound
+            { port_id: PortId, channel_id: ChannelId }
+            |e| { format!("channel '{}/{}' not found", e.port_id, e.channel_id) },
+
+        MissingConnectionHops
+            |_| { "channel end has no connection hops" },
+
+        InvalidState
+            { object: String, state: String, expected: String }
+            |e| { format!("{} is in state '{}' but must be {}", e.object, e.state, e.expected) },
+
+        PacketAlreadyReceived
+            { port_id: PortId, channel_id: ChannelId, sequence: Sequence }
+            |e| { format!("packet '{}' on channel '{}/{}' was already received", e.sequence, e.port_id, e.channel_id) },
+
+        PacketOutOfOrder
+            { sequence: Sequence, expected: Sequence }
+            |e| { format!("received packet '{}' on an ordered channel while expecting packet '{}'", e.sequence, e.expected) },
+
+        PacketTimedOut
+            { sequence: Sequence }
+            |e| { format!("packet '{}' has timed out", e.sequence) },
+
+        PacketCommitmentNotFound
+            { port_id: PortId, channel_id: ChannelId, sequence: Sequence }
+            |e| { format!("no commitment for packet '{}' on channel '{}/{}'", e.sequence, e.port_id, e.channel_id) },
+
+        Unsupported
+            { operation: String }
+            |e| { format!("solo machines do not support {}", e.operation) },
+    }
+}
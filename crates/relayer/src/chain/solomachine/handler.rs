@@ -0,0 +1,828 @@
+//! Handlers applying the IBC messages submitted to a solo machine to its state.
+//!
+//! The handlers perform the state transitions of the IBC protocol, but do not verify
+//! any proof nor header: the solo machine trusts the relayer, which holds its key.
+
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::protobuf::Protobuf;
+use sha2::{Digest, Sha256};
+
+use ibc_relayer_types::applications::transfer::msgs::transfer::{
+    MsgTransfer, TYPE_URL as TRANSFER_TYPE_URL,
+};
+use ibc_relayer_types::core::ics02_client::error::Error as ClientError;
+use ibc_relayer_types::core::ics02_client::events::{
+    Attributes as ClientAttributes, CreateClient, UpdateClient,
+};
+use ibc_relayer_types::core::ics02_client::header::Header;
+use ibc_relayer_types::core::ics02_client::msgs::create_client::{
+    MsgCreateClient, TYPE_URL as CREATE_CLIENT_TYPE_URL,
+};
+use ibc_relayer_types::core::ics02_client::msgs::update_client::{
+    MsgUpdateClient, TYPE_URL as UPDATE_CLIENT_TYPE_URL,
+};
+use ibc_relayer_types::core::ics03_connection::connection::{
+    ConnectionEnd, Counterparty as ConnectionCounterparty, State as ConnectionState,
+};
+use ibc_relayer_types::core::ics03_connection::events::{
+    Attributes as ConnectionAttributes, OpenAck as ConnectionOpenAck,
+    OpenConfirm as ConnectionOpenConfirm, OpenInit as ConnectionOpenInit,
+    OpenTry as ConnectionOpenTry,
+};
+use ibc_relayer_types::core::ics03_connection::msgs::conn_open_ack::{
+    MsgConnectionOpenAck, TYPE_URL as CONN_OPEN_ACK_TYPE_URL,
+};
+use ibc_relayer_types::core::ics03_connection::msgs::conn_open_confirm::{
+    MsgConnectionOpenConfirm, TYPE_URL as CONN_OPEN_CONFIRM_TYPE_URL,
+};
+use ibc_relayer_types::core::ics03_connection::msgs::conn_open_init::{
+    MsgConnectionOpenInit, TYPE_URL as CONN_OPEN_INIT_TYPE_URL,
+};
+use ibc_relayer_types::core::ics03_connection::msgs::conn_open_try::{
+    MsgConnectionOpenTry, TYPE_URL as CONN_OPEN_TRY_TYPE_URL,
+};
+use ibc_relayer_types::core::ics03_connection::version::{get_compatible_versions, pick_version};
+use ibc_relayer_types::core::ics04_channel::channel::{
+    ChannelEnd, Ordering, State as ChannelState,
+};
+use ibc_relayer_types::core::ics04_channel::events::{
+    AcknowledgePacket, CloseConfirm, CloseInit, OpenAck as ChannelOpenAck,
+    OpenConfirm as ChannelOpenConfirm, OpenInit as ChannelOpenInit, OpenTry as ChannelOpenTry,
+    ReceivePacket, SendPacket, TimeoutOnClosePacket, TimeoutPacket, WriteAcknowledgement,
+};
+use ibc_relayer_types::core::ics04_channel::msgs::acknowledgement::{
+    MsgAcknowledgement, TYPE_URL as ACKNOWLEDGEMENT_TYPE_URL,
+};
+use ibc_relayer_types::core::ics04_channel::msgs::chan_close_confirm::{
+    MsgChannelCloseConfirm, TYPE_URL as CHAN_CLOSE_CONFIRM_TYPE_URL,
+};
+use ibc_relayer_types::core::ics04_channel::msgs::chan_close_init::{
+    MsgChannelCloseInit, TYPE_URL as CHAN_CLOSE_INIT_TYPE_URL,
+};
+use ibc_relayer_types::core::ics04_channel::msgs::chan_open_ack::{
+    MsgChannelOpenAck, TYPE_URL as CHAN_OPEN_ACK_TYPE_URL,
+};
+use ibc_relayer_types::core::ics04_channel::msgs::chan_open_confirm::{
+    MsgChannelOpenConfirm, TYPE_URL as CHAN_OPEN_CONFIRM_TYPE_URL,
+};
+use ibc_relayer_types::core::ics04_channel::msgs::chan_open_init::{
+    MsgChannelOpenInit, TYPE_URL as CHAN_OPEN_INIT_TYPE_URL,
+};
+use ibc_relayer_types::core::ics04_channel::msgs::chan_open_try::{
+    MsgChannelOpenTry, TYPE_URL as CHAN_OPEN_TRY_TYPE_URL,
+};
+use ibc_relayer_types::core::ics04_channel::msgs::recv_packet::{
+    MsgRecvPacket, TYPE_URL as RECV_PACKET_TYPE_URL,
+};
+use ibc_relayer_types::core::ics04_channel::msgs::timeout::{
+    MsgTimeout, TYPE_URL as TIMEOUT_TYPE_URL,
+};
+use ibc_relayer_types::core::ics04_channel::msgs::timeout_on_close::{
+    MsgTimeoutOnClose, TYPE_URL as TIMEOUT_ON_CLOSE_TYPE_URL,
+};
+use ibc_relayer_types::core::ics04_channel::packet::{Packet, Sequence};
+use ibc_relayer_types::core::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
+use ibc_relayer_types::core::ics24_host::path::{
+    AcksPath, ChannelEndsPath, ClientConsensusStatePath, ClientStatePath, CommitmentsPath,
+    ConnectionsPath, ReceiptsPath, SeqAcksPath, SeqRecvsPath, SeqSendsPath,
+};
+use ibc_relayer_types::events::IbcEvent;
+use ibc_relayer_types::timestamp::Timestamp;
+use ibc_relayer_types::Height;
+
+use crate::client_state::AnyClientState;
+use crate::consensus_state::AnyConsensusState;
+use crate::light_client::AnyHeader;
+
+use super::error::Error;
+use super::store::{ibc_key, Pending, StateView};
+
+/// The acknowledgement written by a solo machine for every packet it receives,
+/// ie. a successful ICS-20 acknowledgement.
+pub const SUCCESS_ACK: &[u8] = br#"{"result":"AQ=="}"#;
+
+/// The receipt of a packet received on an unordered channel, as written by ibc-go.
+const RECEIPT: &[u8] = &[1];
+
+/// The height and time at which a message is executed.
+pub struct HostContext {
+    pub height: Height,
+    pub timestamp: Timestamp,
+}
+
+/// Apply the given message to the pending state, and return the events it emits.
+pub fn deliver(
+    state: &mut Pending<'_>,
+    host: &HostContext,
+    msg: Any,
+) -> Result<Vec<IbcEvent>, Error> {
+    let type_url = msg.type_url.clone();
+
+    macro_rules! decode {
+        ($msg_type:ty) => {
+            <$msg_type>::decode_vec(&msg.value)
+                .map_err(|e| Error::decode_message(type_url.clone(), e.to_string()))?
+        };
+    }
+
+    match type_url.as_str() {
+        CREATE_CLIENT_TYPE_URL => create_client(state, decode!(MsgCreateClient)),
+        UPDATE_CLIENT_TYPE_URL => update_client(state, decode!(MsgUpdateClient)),
+        CONN_OPEN_INIT_TYPE_URL => conn_open_init(state, decode!(MsgConnectionOpenInit)),
+        CONN_OPEN_TRY_TYPE_URL => conn_open_try(state, decode!(MsgConnectionOpenTry)),
+        CONN_OPEN_ACK_TYPE_URL => conn_open_ack(state, decode!(MsgConnectionOpenAck)),
+        CONN_OPEN_CONFIRM_TYPE_URL => conn_open_confirm(state, decode!(MsgConnectionOpenConfirm)),
+        CHAN_OPEN_INIT_TYPE_URL => chan_open_init(state, decode!(MsgChannelOpenInit)),
+        CHAN_OPEN_TRY_TYPE_URL => chan_open_try(state, decode!(MsgChannelOpenTry)),
+        CHAN_OPEN_ACK_TYPE_URL => chan_open_ack(state, decode!(MsgChannelOpenAck)),
+        CHAN_OPEN_CONFIRM_TYPE_URL => chan_open_confirm(state, decode!(MsgChannelOpenConfirm)),
+        CHAN_CLOSE_INIT_TYPE_URL => chan_close_init(state, decode!(MsgChannelCloseInit)),
+        CHAN_CLOSE_CONFIRM_TYPE_URL => chan_close_confirm(state, decode!(MsgChannelCloseConfirm)),
+        TRANSFER_TYPE_URL => transfer(state, decode!(MsgTransfer)),
+        RECV_PACKET_TYPE_URL => recv_packet(state, host, decode!(MsgRecvPacket)),
+        ACKNOWLEDGEMENT_TYPE_URL => acknowledge_packet(state, decode!(MsgAcknowledgement)),
+        TIMEOUT_TYPE_URL => {
+            let msg = decode!(MsgTimeout);
+            timeout_packet(state, msg.packet, false)
+        }
+        TIMEOUT_ON_CLOSE_TYPE_URL => {
+            let msg = decode!(MsgTimeoutOnClose);
+            timeout_packet(state, msg.packet, true)
+        }
+        _ => Err(Error::unknown_message(type_url)),
+    }
+}
+
+fn insert_consensus_state(
+    state: &mut Pending<'_>,
+    client_id: &ClientId,
+    height: Height,
+    consensus_state: AnyConsensusState,
+) {
+    state.insert(
+        ibc_key(ClientConsensusStatePath {
+            client_id: client_id.clone(),
+            epoch: height.revision_number(),
+            height: height.revision_height(),
+        }),
+        consensus_state.encode_vec(),
+    );
+}
+
+fn create_client(state: &mut Pending<'_>, msg: MsgCreateClient) -> Result<Vec<IbcEvent>, Error> {
+    let client_state = AnyClientState::try_from(msg.client_state).map_err(Error::client)?;
+    let consensus_state =
+        AnyConsensusState::try_from(msg.consensus_state).map_err(Error::client)?;
+
+    // The handlers only know how to update Tendermint clients
+    if !matches!(
+        (&client_state, &consensus_state),
+        (
+            AnyClientState::Tendermint(_),
+            AnyConsensusState::Tendermint(_)
+        )
+    ) {
+        return Err(Error::unsupported_client_type(client_state.client_type()));
+    }
+
+    let client_id = ClientId::new(client_state.client_type(), state.next_counter("clients")?)
+        .map_err(|e| Error::client(ClientError::invalid_client_identifier(e)))?;
+
+    let client_type = client_state.client_type();
+    let consensus_height = client_state.latest_height();
+
+    state.insert(
+        ibc_key(ClientStatePath(client_id.clone())),
+        client_state.encode_vec(),
+    );
+    insert_consensus_state(state, &client_id, consensus_height, consensus_state);
+
+    let event = CreateClient(ClientAttributes {
+        client_id,
+        client_type,
+        consensus_height,
+    });
+
+    Ok(vec![event.into()])
+}
+
+fn update_client(state: &mut Pending<'_>, msg: MsgUpdateClient) -> Result<Vec<IbcEvent>, Error> {
+    let header = AnyHeader::try_from(msg.header).map_err(Error::client)?;
+
+    let client_state = match (state.client_state(&msg.client_id)?, header.clone()) {
+        (AnyClientState::Tendermint(client_state), AnyHeader::Tendermint(header)) => {
+            let height = header.height();
+
+            let client_state = if height > client_state.latest_height() {
+                client_state
+                    .with_header(header.clone())
+                    .map_err(|e| Error::client(e.into()))?
+            } else {
+                client_state
+            };
+
+            insert_consensus_state(
+                state,
+                &msg.client_id,
+                height,
+                AnyConsensusState::Tendermint(header.into()),
+            );
+
+            AnyClientState::from(client_state)
+        }
+        _ => return Err(Error::unsupported_client_type(header.client_type())),
+    };
+
+    state.insert(
+        ibc_key(ClientStatePath(msg.client_id.clone())),
+        client_state.encode_vec(),
+    );
+
+    let event = UpdateClient {
+        common: ClientAttributes {
+            client_id: msg.client_id,
+            client_type: header.client_type(),
+            consensus_height: header.height(),
+        },
+        header: Some(Box::new(header)),
+    };
+
+    Ok(vec![event.into()])
+}
+
+fn insert_connection(state: &mut Pending<'_>, connection_id: &ConnectionId, end: &ConnectionEnd) {
+    state.insert(
+        ibc_key(ConnectionsPath(connection_id.clone())),
+        end.encode_vec(),
+    );
+}
+
+fn conn_open_init(
+    state: &mut Pending<'_>,
+    msg: MsgConnectionOpenInit,
+) -> Result<Vec<IbcEvent>, Error> {
+    state.client_state(&msg.client_id)?;
+
+    let connection_id = ConnectionId::new(state.next_counter("connections")?);
+
+    let versions = msg
+        .version
+        .map(|version| vec![version])
+        .unwrap_or_else(get_compatible_versions);
+
+    let connection_end = ConnectionEnd::new(
+        ConnectionState::Init,
+        msg.client_id.clone(),
+        msg.counterparty.clone(),
+        versions,
+        msg.delay_period,
+    );
+
+    insert_connection(state, &connection_id, &connection_end);
+
+    let event = ConnectionOpenInit(ConnectionAttributes {
+        connection_id: Some(connection_id),
+        client_id: msg.client_id,
+        counterparty_connection_id: msg.counterparty.connection_id().cloned(),
+        counterparty_client_id: msg.counterparty.client_id().clone(),
+    });
+
+    Ok(vec![event.into()])
+}
+
+fn conn_open_try(
+    state: &mut Pending<'_>,
+    msg: MsgConnectionOpenTry,
+) -> Result<Vec<IbcEvent>, Error> {
+    state.client_state(&msg.client_id)?;
+
+    let version = pick_version(get_compatible_versions(), msg.counterparty_versions.clone())
+        .map_err(|e| {
+            Error::invalid_state(
+                "connection version".to_string(),
+                e.to_string(),
+                "compatible".to_string(),
+            )
+        })?;
+
+    let connection_id = ConnectionId::new(state.next_counter("connections")?);
+
+    let connection_end = ConnectionEnd::new(
+        ConnectionState::TryOpen,
+        msg.client_id.clone(),
+        msg.counterparty.clone(),
+        vec![version],
+        msg.delay_period,
+    );
+
+    insert_connection(state, &connection_id, &connection_end);
+
+    let event = ConnectionOpenTry(ConnectionAttributes {
+        connection_id: Some(connection_id),
+        client_id: msg.client_id,
+        counterparty_connection_id: msg.counterparty.connection_id().cloned(),
+        counterparty_client_id: msg.counterparty.client_id().clone(),
+    });
+
+    Ok(vec![event.into()])
+}
+
+fn conn_open_ack(
+    state: &mut Pending<'_>,
+    msg: MsgConnectionOpenAck,
+) -> Result<Vec<IbcEvent>, Error> {
+    let mut connection_end = state.connection(&msg.connection_id)?;
+
+    if !connection_end.state_matches(&ConnectionState::Init) {
+        return Err(Error::invalid_state(
+            format!("connection '{}'", msg.connection_id),
+            connection_end.state().to_string(),
+            ConnectionState::Init.to_string(),
+        ));
+    }
+
+    let counterparty = ConnectionCounterparty::new(
+        connection_end.counterparty().client_id().clone(),
+        Some(msg.counterparty_connection_id.clone()),
+        connection_end.counterparty().prefix().clone(),
+    );
+
+    connection_end.set_state(ConnectionState::Open);
+    connection_end.set_version(msg.version);
+    connection_end.set_counterparty(counterparty);
+
+    insert_connection(state, &msg.connection_id, &connection_end);
+
+    let event = ConnectionOpenAck(ConnectionAttributes {
+        connection_id: Some(msg.connection_id),
+        client_id: connection_end.client_id().clone(),
+        counterparty_connection_id: Some(msg.counterparty_connection_id),
+        counterparty_client_id: connection_end.counterparty().client_id().clone(),
+    });
+
+    Ok(vec![event.into()])
+}
+
+fn conn_open_confirm(
+    state: &mut Pending<'_>,
+    msg: MsgConnectionOpenConfirm,
+) -> Result<Vec<IbcEvent>, Error> {
+    let mut connection_end = state.connection(&msg.connection_id)?;
+
+    if !connection_end.state_matches(&ConnectionState::TryOpen) {
+        return Err(Error::invalid_state(
+            format!("connection '{}'", msg.connection_id),
+            connection_end.state().to_string(),
+            ConnectionState::TryOpen.to_string(),
+        ));
+    }
+
+    connection_end.set_state(ConnectionState::Open);
+    insert_connection(state, &msg.connection_id, &connection_end);
+
+    let event = ConnectionOpenConfirm(ConnectionAttributes {
+        connection_id: Some(msg.connection_id),
+        client_id: connection_end.client_id().clone(),
+        counterparty_connection_id: connection_end.counterparty().connection_id().cloned(),
+        counterparty_client_id: connection_end.counterparty().client_id().clone(),
+    });
+
+    Ok(vec![event.into()])
+}
+
+fn insert_channel(
+    state: &mut Pending<'_>,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    channel_end: &ChannelEnd,
+) {
+    state.insert(
+        ibc_key(ChannelEndsPath(port_id.clone(), channel_id.clone())),
+        channel_end.encode_vec(),
+    );
+}
+
+/// Store a new channel end in the given state, and return its identifier.
+fn new_channel(
+    state: &mut Pending<'_>,
+    port_id: &PortId,
+    mut channel_end: ChannelEnd,
+    channel_state: ChannelState,
+) -> Result<(ChannelId, ConnectionId), Error> {
+    let connection_id = channel_end
+        .connection_hops()
+        .first()
+        .cloned()
+        .unwrap_or_default();
+
+    state.connection(&connection_id)?;
+
+    let channel_id = ChannelId::new(state.next_counter("channels")?);
+    channel_end.set_state(channel_state);
+
+    insert_channel(state, port_id, &channel_id, &channel_end);
+
+    let first = Sequence::from(1);
+    state.set_sequence(
+        ibc_key(SeqSendsPath(port_id.clone(), channel_id.clone())),
+        first,
+    );
+    state.set_sequence(
+        ibc_key(SeqRecvsPath(port_id.clone(), channel_id.clone())),
+        first,
+    );
+    state.set_sequence(
+        ibc_key(SeqAcksPath(port_id.clone(), channel_id.clone())),
+        first,
+    );
+
+    Ok((channel_id, connection_id))
+}
+
+fn chan_open_init(
+    state: &mut Pending<'_>,
+    msg: MsgChannelOpenInit,
+) -> Result<Vec<IbcEvent>, Error> {
+    let counterparty_port_id = msg.channel.counterparty().port_id().clone();
+
+    let (channel_id, connection_id) =
+        new_channel(state, &msg.port_id, msg.channel, ChannelState::Init)?;
+
+    let event = ChannelOpenInit {
+        port_id: msg.port_id,
+        channel_id: Some(channel_id),
+        connection_id,
+        counterparty_port_id,
+        counterparty_channel_id: None,
+    };
+
+    Ok(vec![event.into()])
+}
+
+fn chan_open_try(state: &mut Pending<'_>, msg: MsgChannelOpenTry) -> Result<Vec<IbcEvent>, Error> {
+    let counterparty = msg.channel.counterparty().clone();
+
+    let (channel_id, connection_id) =
+        new_channel(state, &msg.port_id, msg.channel, ChannelState::TryOpen)?;
+
+    let event = ChannelOpenTry {
+        port_id: msg.port_id,
+        channel_id: Some(channel_id),
+        connection_id,
+        counterparty_port_id: counterparty.port_id().clone(),
+        counterparty_channel_id: counterparty.channel_id().cloned(),
+    };
+
+    Ok(vec![event.into()])
+}
+
+/// Move the given channel from one of the states `from` to state `to`, and return the channel end.
+fn transition_channel(
+    state: &mut Pending<'_>,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    from: &[ChannelState],
+    to: ChannelState,
+    update: impl FnOnce(&mut ChannelEnd),
+) -> Result<ChannelEnd, Error> {
+    let mut channel_end = state.channel(port_id, channel_id)?;
+
+    if !from.iter().any(|s| channel_end.state_matches(s)) {
+        return Err(Error::invalid_state(
+            format!("channel '{port_id}/{channel_id}'"),
+            channel_end.state().to_string(),
+            from.iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(" or "),
+        ));
+    }
+
+    update(&mut channel_end);
+    channel_end.set_state(to);
+    insert_channel(state, port_id, channel_id, &channel_end);
+
+    Ok(channel_end)
+}
+
+fn chan_open_ack(state: &mut Pending<'_>, msg: MsgChannelOpenAck) -> Result<Vec<IbcEvent>, Error> {
+    let channel_end = transition_channel(
+        state,
+        &msg.port_id,
+        &msg.channel_id,
+        &[ChannelState::Init],
+        ChannelState::Open,
+        |channel_end| {
+            channel_end.set_counterparty_channel_id(msg.counterparty_channel_id.clone());
+            channel_end.set_version(msg.counterparty_version.clone());
+        },
+    )?;
+
+    let event = ChannelOpenAck {
+        port_id: msg.port_id,
+        channel_id: Some(msg.channel_id),
+        counterparty_channel_id: Some(msg.counterparty_channel_id),
+        connection_id: channel_end.connection_hops()[0].clone(),
+        counterparty_port_id: channel_end.counterparty().port_id().clone(),
+    };
+
+    Ok(vec![event.into()])
+}
+
+fn chan_open_confirm(
+    state: &mut Pending<'_>,
+    msg: MsgChannelOpenConfirm,
+) -> Result<Vec<IbcEvent>, Error> {
+    let channel_end = transition_channel(
+        state,
+        &msg.port_id,
+        &msg.channel_id,
+        &[ChannelState::TryOpen],
+        ChannelState::Open,
+        |_| {},
+    )?;
+
+    let event = ChannelOpenConfirm {
+        port_id: msg.port_id,
+        channel_id: Some(msg.channel_id),
+        connection_id: channel_end.connection_hops()[0].clone(),
+        counterparty_port_id: channel_end.counterparty().port_id().clone(),
+        counterparty_channel_id: channel_end.counterparty().channel_id().cloned(),
+    };
+
+    Ok(vec![event.into()])
+}
+
+fn chan_close_init(
+    state: &mut Pending<'_>,
+    msg: MsgChannelCloseInit,
+) -> Result<Vec<IbcEvent>, Error> {
+    let channel_end = transition_channel(
+        state,
+        &msg.port_id,
+        &msg.channel_id,
+        &[ChannelState::Open],
+        ChannelState::Closed,
+        |_| {},
+    )?;
+
+    let event = CloseInit {
+        port_id: msg.port_id,
+        channel_id: msg.channel_id,
+        connection_id: channel_end.connection_hops()[0].clone(),
+        counterparty_port_id: channel_end.counterparty().port_id().clone(),
+        counterparty_channel_id: channel_end.counterparty().channel_id().cloned(),
+    };
+
+    Ok(vec![event.into()])
+}
+
+fn chan_close_confirm(
+    state: &mut Pending<'_>,
+    msg: MsgChannelCloseConfirm,
+) -> Result<Vec<IbcEvent>, Error> {
+    let channel_end = transition_channel(
+        state,
+        &msg.port_id,
+        &msg.channel_id,
+        &[
+            ChannelState::Init,
+            ChannelState::TryOpen,
+            ChannelState::Open,
+        ],
+        ChannelState::Closed,
+        |_| {},
+    )?;
+
+    let event = CloseConfirm {
+        channel_id: Some(msg.channel_id),
+        port_id: msg.port_id,
+        connection_id: channel_end.connection_hops()[0].clone(),
+        counterparty_port_id: channel_end.counterparty().port_id().clone(),
+        counterparty_channel_id: channel_end.counterparty().channel_id().cloned(),
+    };
+
+    Ok(vec![event.into()])
+}
+
+fn open_channel(
+    state: &Pending<'_>,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+) -> Result<ChannelEnd, Error> {
+    let channel_end = state.channel(port_id, channel_id)?;
+
+    if !channel_end.is_open() {
+        return Err(Error::invalid_state(
+            format!("channel '{port_id}/{channel_id}'"),
+            channel_end.state().to_string(),
+            ChannelState::Open.to_string(),
+        ));
+    }
+
+    Ok(channel_end)
+}
+
+/// Send an ICS-20 packet. The solo machine has no bank, hence the tokens it sends
+/// are accounted for by the solo machine itself, outside of the relayer.
+fn transfer(state: &mut Pending<'_>, msg: MsgTransfer) -> Result<Vec<IbcEvent>, Error> {
+    let channel_end = open_channel(state, &msg.source_port, &msg.source_channel)?;
+
+    let destination_port = channel_end.counterparty().port_id().clone();
+    let destination_channel = channel_end
+        .counterparty()
+        .channel_id()
+        .cloned()
+        .unwrap_or_default();
+
+    let next_send_key = ibc_key(SeqSendsPath(
+        msg.source_port.clone(),
+        msg.source_channel.clone(),
+    ));
+    let sequence = state
+        .sequence(&next_send_key)?
+        .unwrap_or_else(|| Sequence::from(1));
+    state.set_sequence(next_send_key, sequence.increment());
+
+    let data = serde_json::json!({
+        "denom": msg.token.denom,
+        "amount": msg.token.amount,
+        "sender": msg.sender.to_string(),
+        "receiver": msg.receiver.to_string(),
+        "memo": msg.memo.unwrap_or_default(),
+    });
+
+    let packet = Packet {
+        sequence,
+        source_port: msg.source_port,
+        source_channel: msg.source_channel,
+        destination_port,
+        destination_channel,
+        data: data.to_string().into_bytes(),
+        timeout_height: msg.timeout_height,
+        timeout_timestamp: msg.timeout_timestamp,
+    };
+
+    state.insert(
+        ibc_key(CommitmentsPath {
+            port_id: packet.source_port.clone(),
+            channel_id: packet.source_channel.clone(),
+            sequence: packet.sequence,
+        }),
+        packet_commitment(&packet),
+    );
+
+    Ok(vec![SendPacket { packet }.into()])
+}
+
+fn recv_packet(
+    state: &mut Pending<'_>,
+    host: &HostContext,
+    msg: MsgRecvPacket,
+) -> Result<Vec<IbcEvent>, Error> {
+    let packet = msg.packet;
+    let port_id = packet.destination_port.clone();
+    let channel_id = packet.destination_channel.clone();
+    let channel_end = open_channel(state, &port_id, &channel_id)?;
+
+    if packet.timed_out(&host.timestamp, host.height) {
+        return Err(Error::packet_timed_out(packet.sequence));
+    }
+
+    if channel_end.order_matches(&Ordering::Ordered) {
+        let next_recv_key = ibc_key(SeqRecvsPath(port_id.clone(), channel_id.clone()));
+        let expected = state
+            .sequence(&next_recv_key)?
+            .unwrap_or_else(|| Sequence::from(1));
+
+        if packet.sequence != expected {
+            return Err(Error::packet_out_of_order(packet.sequence, expected));
+        }
+
+        state.set_sequence(next_recv_key, expected.increment());
+    } else {
+        let receipt_key = ibc_key(ReceiptsPath {
+            port_id: port_id.clone(),
+            channel_id: channel_id.clone(),
+            sequence: packet.sequence,
+        });
+
+        if state.contains(&receipt_key)? {
+            return Err(Error::packet_already_received(
+                port_id,
+                channel_id,
+                packet.sequence,
+            ));
+        }
+
+        state.insert(receipt_key, RECEIPT.to_vec());
+    }
+
+    state.insert(
+        ibc_key(AcksPath {
+            port_id,
+            channel_id,
+            sequence: packet.sequence,
+        }),
+        Sha256::digest(SUCCESS_ACK).to_vec(),
+    );
+
+    Ok(vec![
+        ReceivePacket {
+            packet: packet.clone(),
+        }
+        .into(),
+        WriteAcknowledgement {
+            packet,
+            ack: SUCCESS_ACK.to_vec(),
+        }
+        .into(),
+    ])
+}
+
+/// Remove the commitment of a packet sent by the solo machine, and return its channel end.
+fn remove_commitment(state: &mut Pending<'_>, packet: &Packet) -> Result<ChannelEnd, Error> {
+    let key = ibc_key(CommitmentsPath {
+        port_id: packet.source_port.clone(),
+        channel_id: packet.source_channel.clone(),
+        sequence: packet.sequence,
+    });
+
+    if !state.contains(&key)? {
+        return Err(Error::packet_commitment_not_found(
+            packet.source_port.clone(),
+            packet.source_channel.clone(),
+            packet.sequence,
+        ));
+    }
+
+    state.remove(key);
+
+    state.channel(&packet.source_port, &packet.source_channel)
+}
+
+fn acknowledge_packet(
+    state: &mut Pending<'_>,
+    msg: MsgAcknowledgement,
+) -> Result<Vec<IbcEvent>, Error> {
+    let packet = msg.packet;
+    let channel_end = remove_commitment(state, &packet)?;
+
+    if channel_end.order_matches(&Ordering::Ordered) {
+        state.set_sequence(
+            ibc_key(SeqAcksPath(
+                packet.source_port.clone(),
+                packet.source_channel.clone(),
+            )),
+            packet.sequence.increment(),
+        );
+    }
+
+    Ok(vec![AcknowledgePacket { packet }.into()])
+}
+
+fn timeout_packet(
+    state: &mut Pending<'_>,
+    packet: Packet,
+    on_close: bool,
+) -> Result<Vec<IbcEvent>, Error> {
+    let mut channel_end = remove_commitment(state, &packet)?;
+
+    // A timeout closes ordered channels
+    if channel_end.order_matches(&Ordering::Ordered) {
+        channel_end.set_state(ChannelState::Closed);
+        insert_channel(
+            state,
+            &packet.source_port,
+            &packet.source_channel,
+            &channel_end,
+        );
+    }
+
+    if on_close {
+        Ok(vec![TimeoutOnClosePacket { packet }.into()])
+    } else {
+        Ok(vec![TimeoutPacket { packet }.into()])
+    }
+}
+
+/// Compute the commitment to a packet, as done by ibc-go.
+pub fn packet_commitment(packet: &Packet) -> Vec<u8> {
+    let mut input = Vec::new();
+    input.extend(packet.timeout_timestamp.nanoseconds().to_be_bytes());
+    input.extend(
+        packet
+            .timeout_height
+            .commitment_revision_number()
+            .to_be_bytes(),
+    );
+    input.extend(
+        packet
+            .timeout_height
+            .commitment_revision_height()
+            .to_be_bytes(),
+    );
+    input.extend(Sha256::digest(&packet.data));
+
+    Sha256::digest(input).to_vec()
+}
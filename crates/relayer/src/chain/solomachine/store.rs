@@ -0,0 +1,632 @@
+//! Persistent IBC state of a solo machine.
+//!
+//! The state is kept in an embedded [sled](https://docs.rs/sled) database, which maps the
+//! ICS-24 path of every value to its protobuf encoding, ie. to the bytes the solo machine
+//! signs when proving the value to its counterparty. Along with the IBC state, the database
+//! holds the latest height of the solo machine, the outcome of its recent transactions,
+//! the packet and client events the relayer looks up when clearing packets or updating clients,
+//! and the signatures made for the proofs not verified yet by the clients of the solo machine.
+//!
+//! The solo machine keeps no history of its state: queries at past heights are answered
+//! from the latest state, as the counterparty only ever verifies the latest signed values.
+
+use std::collections::BTreeMap;
+use std::path::Path as FsPath;
+
+use core::fmt::Display;
+
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::core::channel::v1::Channel as RawChannel;
+use ibc_proto::ibc::core::connection::v1::ConnectionEnd as RawConnectionEnd;
+use prost::Message;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use tendermint::abci;
+use tendermint::Hash as TxHash;
+
+use ibc_relayer_types::core::ics03_connection::connection::ConnectionEnd;
+use ibc_relayer_types::core::ics04_channel::channel::ChannelEnd;
+use ibc_relayer_types::core::ics04_channel::packet::Sequence;
+use ibc_relayer_types::core::ics24_host::identifier::{
+    ChainId, ChannelId, ClientId, ConnectionId, PortId,
+};
+use ibc_relayer_types::core::ics24_host::path::{
+    ChannelEndsPath, ClientConsensusStatePath, ClientStatePath, ConnectionsPath, Path,
+};
+use ibc_relayer_types::events::IbcEvent;
+use ibc_relayer_types::timestamp::Timestamp;
+use ibc_relayer_types::Height;
+
+use crate::client_state::AnyClientState;
+use crate::consensus_state::AnyConsensusState;
+use crate::event::ibc_event_try_from_abci_event;
+
+use super::error::Error;
+
+pub const SOLO_MACHINE_DEFAULT_FOLDER: &str = ".hermes/solomachine/";
+
+/// Number of transactions whose outcome is kept in the store
+const MAX_STORED_TXS: u64 = 1000;
+
+const STATE_TREE: &str = "state";
+
+const IBC_PREFIX: &str = "ibc/";
+const HEIGHT_KEY: &str = "meta/height";
+const COUNTERS_PREFIX: &str = "meta/counters/";
+const TXS_PREFIX: &str = "txs/";
+const TX_HEIGHTS_PREFIX: &str = "tx_heights/";
+const CLIENT_EVENTS_PREFIX: &str = "events/clients/";
+const PACKET_EVENTS_PREFIX: &str = "events/packets/";
+const SIGNATURES_PREFIX: &str = "signatures/";
+const SIGNED_SEQUENCES_PREFIX: &str = "meta/signed/";
+
+/// The key under which the value at the given ICS-24 path is stored
+pub fn ibc_key(path: impl Into<Path>) -> String {
+    format!("{IBC_PREFIX}{}", path.into())
+}
+
+/// An IBC event, as stored in the database
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StoredEvent {
+    pub kind: String,
+    pub attributes: Vec<(String, String)>,
+}
+
+impl StoredEvent {
+    fn new(event: &IbcEvent) -> Option<Self> {
+        let event = abci::Event::try_from(event.clone()).ok()?;
+
+        Some(Self {
+            kind: event.kind,
+            attributes: event
+                .attributes
+                .into_iter()
+                .map(|attribute| (attribute.key, attribute.value))
+                .collect(),
+        })
+    }
+
+    pub fn to_ibc_event(&self) -> Option<IbcEvent> {
+        let event = abci::Event::new(self.kind.clone(), self.attributes.clone());
+
+        ibc_event_try_from_abci_event(&event).ok()
+    }
+}
+
+/// The outcome of a transaction executed by the solo machine
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StoredTx {
+    pub height: Height,
+    pub result: Result<Vec<StoredEvent>, String>,
+}
+
+/// An event emitted at the given height
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StoredEventWithHeight {
+    pub height: Height,
+    pub event: StoredEvent,
+}
+
+/// The bytes signed by the solo machine at some sequence of one of its clients, along with
+/// the timestamp they include, so that the same proof can be signed again at that sequence
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoredSignature {
+    pub timestamp: Timestamp,
+    pub sign_bytes: Vec<u8>,
+}
+
+/// Read access to the IBC state of a solo machine
+pub trait StateView {
+    fn get_raw(&self, key: &str) -> Result<Option<Vec<u8>>, Error>;
+
+    fn contains(&self, key: &str) -> Result<bool, Error> {
+        Ok(self.get_raw(key)?.is_some())
+    }
+
+    fn get<T, R>(&self, key: &str) -> Result<Option<T>, Error>
+    where
+        T: TryFrom<R>,
+        T::Error: Display,
+        R: Message + Default,
+    {
+        self.get_raw(key)?
+            .map(|value| decode_value(key, &value))
+            .transpose()
+    }
+
+    fn client_state(&self, client_id: &ClientId) -> Result<AnyClientState, Error> {
+        self.get::<_, Any>(&ibc_key(ClientStatePath(client_id.clone())))?
+            .ok_or_else(|| Error::client_not_found(client_id.clone()))
+    }
+
+    fn consensus_state(
+        &self,
+        client_id: &ClientId,
+        height: Height,
+    ) -> Result<AnyConsensusState, Error> {
+        self.get::<_, Any>(&ibc_key(ClientConsensusStatePath {
+            client_id: client_id.clone(),
+            epoch: height.revision_number(),
+            height: height.revision_height(),
+        }))?
+        .ok_or_else(|| Error::consensus_state_not_found(client_id.clone(), height))
+    }
+
+    fn connection(&self, connection_id: &ConnectionId) -> Result<ConnectionEnd, Error> {
+        self.get::<_, RawConnectionEnd>(&ibc_key(ConnectionsPath(connection_id.clone())))?
+            .ok_or_else(|| Error::connection_not_found(connection_id.clone()))
+    }
+
+    fn channel(&self, port_id: &PortId, channel_id: &ChannelId) -> Result<ChannelEnd, Error> {
+        self.get::<_, RawChannel>(&ibc_key(ChannelEndsPath(
+            port_id.clone(),
+            channel_id.clone(),
+        )))?
+        .ok_or_else(|| Error::channel_not_found(port_id.clone(), channel_id.clone()))
+    }
+
+    /// The sequence stored at the given path, encoded as ibc-go does
+    fn sequence(&self, key: &str) -> Result<Option<Sequence>, Error> {
+        self.get_raw(key)?
+            .map(|value| {
+                let bytes = <[u8; 8]>::try_from(value.as_slice()).map_err(|_| {
+                    Error::decode_value(key.to_string(), "invalid sequence".to_string())
+                })?;
+
+                Ok(Sequence::from(u64::from_be_bytes(bytes)))
+            })
+            .transpose()
+    }
+}
+
+/// The database of a solo machine
+pub struct SoloStore {
+    tree: sled::Tree,
+}
+
+impl SoloStore {
+    /// Open the store in the given folder, creating it if needed
+    pub fn open(path: &FsPath) -> Result<Self, Error> {
+        let db = sled::open(path).map_err(Error::db)?;
+        Self::from_db(&db)
+    }
+
+    pub fn from_db(db: &sled::Db) -> Result<Self, Error> {
+        Ok(Self {
+            tree: db.open_tree(STATE_TREE).map_err(Error::db)?,
+        })
+    }
+
+    /// The height of the latest transaction executed by the solo machine
+    pub fn height(&self) -> Result<Height, Error> {
+        let height = get_json(&self.tree, HEIGHT_KEY)?;
+
+        Ok(height.unwrap_or_else(|| Height::new(0, 1).expect("non-zero height")))
+    }
+
+    /// Start a set of changes to the state, which is applied atomically by [`SoloStore::commit`]
+    pub fn pending(&self) -> Pending<'_> {
+        Pending {
+            store: self,
+            writes: BTreeMap::new(),
+        }
+    }
+
+    pub fn commit(&self, pending: Pending<'_>) -> Result<(), Error> {
+        let mut batch = sled::Batch::default();
+
+        for (key, value) in pending.writes {
+            match value {
+                Some(value) => batch.insert(key.as_bytes(), value),
+                None => batch.remove(key.as_bytes()),
+            }
+        }
+
+        self.tree.apply_batch(batch).map_err(Error::db)?;
+        self.tree.flush().map_err(Error::db)?;
+
+        Ok(())
+    }
+
+    /// The values stored under the given prefix of the IBC state, along with
+    /// the rest of their path
+    pub fn scan(&self, prefix: impl Into<String>) -> Result<Vec<(String, Vec<u8>)>, Error> {
+        let prefix = format!("{IBC_PREFIX}{}", prefix.into());
+
+        self.tree
+            .scan_prefix(&prefix)
+            .map(|entry| {
+                let (key, value) = entry.map_err(Error::db)?;
+                let key = String::from_utf8_lossy(&key[prefix.len()..]).into_owned();
+
+                Ok((key, value.to_vec()))
+            })
+            .collect()
+    }
+
+    pub fn tx(&self, hash: &TxHash) -> Result<Option<StoredTx>, Error> {
+        get_json(&self.tree, &format!("{TXS_PREFIX}{hash}"))
+    }
+
+    /// The event emitted when the given client was created, or updated to the given height
+    pub fn client_event(
+        &self,
+        client_id: &ClientId,
+        consensus_height: Height,
+        kind: &str,
+    ) -> Result<Option<StoredEventWithHeight>, Error> {
+        get_json(
+            &self.tree,
+            &client_event_key(client_id, consensus_height, kind),
+        )
+    }
+
+    /// The `SendPacket` or `WriteAcknowledgement` event of the given packet
+    pub fn packet_event(
+        &self,
+        kind: &str,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        sequence: Sequence,
+    ) -> Result<Option<StoredEventWithHeight>, Error> {
+        get_json(
+            &self.tree,
+            &packet_event_key(kind, port_id, channel_id, sequence),
+        )
+    }
+}
+
+impl SoloStore {
+    /// The signature made at the given sequence of the given client, if the client
+    /// has not moved past that sequence yet
+    pub fn signature(
+        &self,
+        chain_id: &ChainId,
+        client_id: &ClientId,
+        sequence: u64,
+    ) -> Result<Option<StoredSignature>, Error> {
+        get_json(&self.tree, &signature_key(chain_id, client_id, sequence))
+    }
+
+    /// The lowest sequence of the given client at which the solo machine never signed
+    pub fn next_unsigned_sequence(
+        &self,
+        chain_id: &ChainId,
+        client_id: &ClientId,
+    ) -> Result<u64, Error> {
+        self.get_raw(&signed_sequence_key(chain_id, client_id))?
+            .map(|value| decode_u64(&signed_sequence_key(chain_id, client_id), &value))
+            .transpose()
+            .map(Option::unwrap_or_default)
+    }
+
+    /// Record the signature made at the given sequence of the given client, which must be
+    /// done before the signature is released, and move the next unsigned sequence past it
+    pub fn record_signature(
+        &self,
+        chain_id: &ChainId,
+        client_id: &ClientId,
+        sequence: u64,
+        signature: &StoredSignature,
+    ) -> Result<(), Error> {
+        let next_unsigned = self.next_unsigned_sequence(chain_id, client_id)?;
+
+        let mut pending = self.pending();
+        pending.insert_json(signature_key(chain_id, client_id, sequence), signature)?;
+        pending.insert(
+            signed_sequence_key(chain_id, client_id),
+            next_unsigned.max(sequence + 1).to_be_bytes().to_vec(),
+        );
+
+        self.commit(pending)
+    }
+
+    /// Forget the signatures made below the given sequence of the given client,
+    /// which the client has moved past
+    pub fn forget_signatures_below(
+        &self,
+        chain_id: &ChainId,
+        client_id: &ClientId,
+        sequence: u64,
+    ) -> Result<(), Error> {
+        let prefix = format!("{SIGNATURES_PREFIX}{chain_id}/{client_id}/");
+        let bound = signature_key(chain_id, client_id, sequence);
+
+        let mut pending = self.pending();
+
+        for entry in self.tree.range(prefix.as_str()..bound.as_str()) {
+            let (key, _) = entry.map_err(Error::db)?;
+            pending.remove(String::from_utf8_lossy(&key).into_owned());
+        }
+
+        self.commit(pending)
+    }
+}
+
+impl StateView for SoloStore {
+    fn get_raw(&self, key: &str) -> Result<Option<Vec<u8>>, Error> {
+        Ok(self
+            .tree
+            .get(key)
+            .map_err(Error::db)?
+            .map(|value| value.to_vec()))
+    }
+}
+
+/// Changes to the state of a solo machine, not applied yet
+pub struct Pending<'a> {
+    store: &'a SoloStore,
+    writes: BTreeMap<String, Option<Vec<u8>>>,
+}
+
+impl StateView for Pending<'_> {
+    fn get_raw(&self, key: &str) -> Result<Option<Vec<u8>>, Error> {
+        match self.writes.get(key) {
+            Some(value) => Ok(value.clone()),
+            None => self.store.get_raw(key),
+        }
+    }
+}
+
+impl Pending<'_> {
+    pub fn insert(&mut self, key: String, value: Vec<u8>) {
+        self.writes.insert(key, Some(value));
+    }
+
+    pub fn remove(&mut self, key: String) {
+        self.writes.insert(key, None);
+    }
+
+    pub fn set_sequence(&mut self, key: String, sequence: Sequence) {
+        self.insert(key, u64::from(sequence).to_be_bytes().to_vec());
+    }
+
+    /// Return the value of the given counter, and increment it
+    pub fn next_counter(&mut self, name: &str) -> Result<u64, Error> {
+        let key = format!("{COUNTERS_PREFIX}{name}");
+
+        let counter = match self.get_raw(&key)? {
+            Some(value) => decode_u64(&key, &value)?,
+            None => 0,
+        };
+
+        self.insert(key, (counter + 1).to_be_bytes().to_vec());
+
+        Ok(counter)
+    }
+
+    /// Record the outcome of a transaction executed at the given height, which becomes
+    /// the latest height of the solo machine, along with the events it emitted
+    pub fn record_tx(
+        &mut self,
+        hash: &TxHash,
+        height: Height,
+        result: Result<&[IbcEvent], String>,
+    ) -> Result<(), Error> {
+        let result = result.map(|events| {
+            events
+                .iter()
+                .filter_map(|event| {
+                    let stored = StoredEvent::new(event)?;
+                    self.index_event(event, height, &stored);
+
+                    Some(stored)
+                })
+                .collect()
+        });
+
+        self.insert_json(format!("{TXS_PREFIX}{hash}"), &StoredTx { height, result })?;
+        self.insert(
+            tx_height_key(height.revision_height()),
+            hash.to_string().into_bytes(),
+        );
+
+        // Forget the oldest transaction, if there are too many
+        if let Some(oldest) = height.revision_height().checked_sub(MAX_STORED_TXS) {
+            let oldest_key = tx_height_key(oldest);
+
+            if let Some(oldest_hash) = self.get_raw(&oldest_key)? {
+                self.remove(format!(
+                    "{TXS_PREFIX}{}",
+                    String::from_utf8_lossy(&oldest_hash)
+                ));
+                self.remove(oldest_key);
+            }
+        }
+
+        self.insert_json(HEIGHT_KEY.to_string(), &height)
+    }
+
+    /// Index the events the relayer looks up by client or by packet
+    fn index_event(&mut self, event: &IbcEvent, height: Height, stored: &StoredEvent) {
+        let key = match event {
+            IbcEvent::CreateClient(e) => {
+                client_event_key(e.client_id(), e.0.consensus_height, &stored.kind)
+            }
+            IbcEvent::UpdateClient(e) => {
+                client_event_key(e.client_id(), e.consensus_height(), &stored.kind)
+            }
+            IbcEvent::SendPacket(e) => packet_event_key(
+                &stored.kind,
+                &e.packet.source_port,
+                &e.packet.source_channel,
+                e.packet.sequence,
+            ),
+            IbcEvent::WriteAcknowledgement(e) => packet_event_key(
+                &stored.kind,
+                &e.packet.source_port,
+                &e.packet.source_channel,
+                e.packet.sequence,
+            ),
+            _ => return,
+        };
+
+        let event = StoredEventWithHeight {
+            height,
+            event: stored.clone(),
+        };
+
+        if let Ok(value) = serde_json::to_vec(&event) {
+            self.insert(key, value);
+        }
+    }
+
+    fn insert_json<T: Serialize>(&mut self, key: String, value: &T) -> Result<(), Error> {
+        let value = serde_json::to_vec(value).map_err(Error::encode)?;
+        self.insert(key, value);
+
+        Ok(())
+    }
+}
+
+/// Decode the protobuf encoding of a value, as stored at the given key
+pub fn decode_value<T, R>(key: &str, value: &[u8]) -> Result<T, Error>
+where
+    T: TryFrom<R>,
+    T::Error: Display,
+    R: Message + Default,
+{
+    let raw = R::decode(value).map_err(|e| Error::decode_value(key.to_string(), e.to_string()))?;
+
+    T::try_from(raw).map_err(|e| Error::decode_value(key.to_string(), e.to_string()))
+}
+
+fn decode_u64(key: &str, value: &[u8]) -> Result<u64, Error> {
+    <[u8; 8]>::try_from(value)
+        .map(u64::from_be_bytes)
+        .map_err(|_| Error::decode_value(key.to_string(), "invalid integer".to_string()))
+}
+
+fn tx_height_key(revision_height: u64) -> String {
+    format!("{TX_HEIGHTS_PREFIX}{revision_height:020}")
+}
+
+fn client_event_key(client_id: &ClientId, consensus_height: Height, kind: &str) -> String {
+    format!("{CLIENT_EVENTS_PREFIX}{client_id}/{consensus_height}/{kind}")
+}
+
+fn packet_event_key(
+    kind: &str,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    sequence: Sequence,
+) -> String {
+    format!("{PACKET_EVENTS_PREFIX}{kind}/{port_id}/{channel_id}/{sequence}")
+}
+
+fn signature_key(chain_id: &ChainId, client_id: &ClientId, sequence: u64) -> String {
+    format!("{SIGNATURES_PREFIX}{chain_id}/{client_id}/{sequence:020}")
+}
+
+fn signed_sequence_key(chain_id: &ChainId, client_id: &ClientId) -> String {
+    format!("{SIGNED_SEQUENCES_PREFIX}{chain_id}/{client_id}")
+}
+
+fn get_json<T: DeserializeOwned>(tree: &sled::Tree, key: &str) -> Result<Option<T>, Error> {
+    tree.get(key)
+        .map_err(Error::db)?
+        .map(|value| serde_json::from_slice(&value).map_err(Error::decode))
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ibc_relayer_types::core::ics24_host::path::SeqSendsPath;
+
+    #[test]
+    fn state_survives_reopening_the_store() {
+        let db = sled::Config::new().temporary(true).open().unwrap();
+        let key = ibc_key(SeqSendsPath(PortId::transfer(), ChannelId::new(0)));
+
+        let store = SoloStore::from_db(&db).unwrap();
+        let mut pending = store.pending();
+        pending.set_sequence(key.clone(), Sequence::from(7));
+
+        let counter = pending.next_counter("clients").unwrap();
+        assert_eq!(counter, 0);
+
+        // Pending changes are visible to the handlers, but not committed yet
+        assert_eq!(pending.sequence(&key).unwrap(), Some(Sequence::from(7)));
+        assert_eq!(store.sequence(&key).unwrap(), None);
+
+        store.commit(pending).unwrap();
+        drop(store);
+
+        let store = SoloStore::from_db(&db).unwrap();
+        assert_eq!(store.sequence(&key).unwrap(), Some(Sequence::from(7)));
+        assert_eq!(store.pending().next_counter("clients").unwrap(), 1);
+    }
+
+    #[test]
+    fn signatures_are_forgotten_once_verified() {
+        let db = sled::Config::new().temporary(true).open().unwrap();
+        let store = SoloStore::from_db(&db).unwrap();
+
+        let chain_id = ChainId::from_string("ibc-1");
+        let client_id = ClientId::default();
+        let signature = |byte| StoredSignature {
+            timestamp: Timestamp::none(),
+            sign_bytes: vec![byte],
+        };
+
+        assert_eq!(
+            store.next_unsigned_sequence(&chain_id, &client_id).unwrap(),
+            0
+        );
+
+        for sequence in [3, 4] {
+            store
+                .record_signature(&chain_id, &client_id, sequence, &signature(sequence as u8))
+                .unwrap();
+        }
+        assert_eq!(
+            store.next_unsigned_sequence(&chain_id, &client_id).unwrap(),
+            5
+        );
+
+        store
+            .forget_signatures_below(&chain_id, &client_id, 4)
+            .unwrap();
+        drop(store);
+
+        // The next unsigned sequence only moves forward, even once signatures are forgotten
+        let store = SoloStore::from_db(&db).unwrap();
+        assert_eq!(store.signature(&chain_id, &client_id, 3).unwrap(), None);
+        assert_eq!(
+            store.signature(&chain_id, &client_id, 4).unwrap(),
+            Some(signature(4))
+        );
+        assert_eq!(
+            store.next_unsigned_sequence(&chain_id, &client_id).unwrap(),
+            5
+        );
+    }
+
+    #[test]
+    fn oldest_transactions_are_forgotten() {
+        let db = sled::Config::new().temporary(true).open().unwrap();
+        let store = SoloStore::from_db(&db).unwrap();
+
+        let hash_at = |height: u64| {
+            let mut hash = [0; 32];
+            hash[..8].copy_from_slice(&height.to_be_bytes());
+            TxHash::Sha256(hash)
+        };
+
+        for height in 1..=MAX_STORED_TXS + 1 {
+            let mut pending = store.pending();
+            pending
+                .record_tx(&hash_at(height), Height::new(0, height).unwrap(), Ok(&[]))
+                .unwrap();
+            store.commit(pending).unwrap();
+        }
+
+        assert!(store.tx(&hash_at(1)).unwrap().is_none());
+        assert!(store.tx(&hash_at(2)).unwrap().is_some());
+        assert_eq!(
+            store.height().unwrap(),
+            Height::new(0, MAX_STORED_TXS + 1).unwrap()
+        );
+    }
+}
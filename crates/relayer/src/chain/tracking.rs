@@ -65,17 +65,25 @@ impl Display for TrackingId {
 pub struct TrackedMsgs {
     pub msgs: Vec<Any>,
     pub tracking_id: TrackingId,
+    /// Whether the messages relay packets on an unordered channel,
+    /// such that the chain may deliver these packets in any order.
+    pub unordered_channel: bool,
 }
 
 impl TrackedMsgs {
     pub fn new(msgs: Vec<Any>, tracking_id: TrackingId) -> Self {
-        Self { msgs, tracking_id }
+        Self {
+            msgs,
+            tracking_id,
+            unordered_channel: false,
+        }
     }
 
     pub fn new_static(msgs: Vec<Any>, tracking_id: &'static str) -> Self {
         Self {
             msgs,
             tracking_id: TrackingId::Static(tracking_id),
+            unordered_channel: false,
         }
     }
 
@@ -83,6 +91,7 @@ impl TrackedMsgs {
         Self {
             msgs,
             tracking_id: TrackingId::Uuid(tracking_id),
+            unordered_channel: false,
         }
     }
 
@@ -90,6 +99,7 @@ impl TrackedMsgs {
         Self {
             msgs: vec![msg],
             tracking_id: TrackingId::Static(tracking_id),
+            unordered_channel: false,
         }
     }
 
@@ -97,6 +107,15 @@ impl TrackedMsgs {
         Self {
             msgs: vec![msg],
             tracking_id: TrackingId::Uuid(tracking_id),
+            unordered_channel: false,
+        }
+    }
+
+    /// See [`TrackedMsgs::unordered_channel`]
+    pub fn with_unordered_channel(self, unordered_channel: bool) -> Self {
+        Self {
+            unordered_channel,
+            ..self
         }
     }
 
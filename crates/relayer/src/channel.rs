@@ -6,6 +6,7 @@ use serde::Serialize;
 use tracing::{debug, error, info, warn};
 
 pub use error::ChannelError;
+use ibc_relayer_types::clients::ics09_localhost::sentinel_proofs;
 use ibc_relayer_types::core::ics04_channel::channel::{
     ChannelEnd, Counterparty, IdentifiedChannelEnd, Ordering, State,
 };
@@ -15,21 +16,34 @@ use ibc_relayer_types::core::ics04_channel::msgs::chan_open_ack::MsgChannelOpenA
 use ibc_relayer_types::core::ics04_channel::msgs::chan_open_confirm::MsgChannelOpenConfirm;
 use ibc_relayer_types::core::ics04_channel::msgs::chan_open_init::MsgChannelOpenInit;
 use ibc_relayer_types::core::ics04_channel::msgs::chan_open_try::MsgChannelOpenTry;
+use ibc_relayer_types::core::ics04_channel::msgs::chan_upgrade_ack::MsgChannelUpgradeAck;
+use ibc_relayer_types::core::ics04_channel::msgs::chan_upgrade_cancel::MsgChannelUpgradeCancel;
+use ibc_relayer_types::core::ics04_channel::msgs::chan_upgrade_confirm::MsgChannelUpgradeConfirm;
+use ibc_relayer_types::core::ics04_channel::msgs::chan_upgrade_init::MsgChannelUpgradeInit;
+use ibc_relayer_types::core::ics04_channel::msgs::chan_upgrade_open::MsgChannelUpgradeOpen;
+use ibc_relayer_types::core::ics04_channel::msgs::chan_upgrade_timeout::MsgChannelUpgradeTimeout;
+use ibc_relayer_types::core::ics04_channel::msgs::chan_upgrade_try::MsgChannelUpgradeTry;
+use ibc_relayer_types::core::ics04_channel::upgrade::UpgradeFields;
+use ibc_relayer_types::core::ics23_commitment::commitment::CommitmentProofBytes;
 use ibc_relayer_types::core::ics24_host::identifier::{
     ChainId, ChannelId, ClientId, ConnectionId, PortId,
 };
 use ibc_relayer_types::events::IbcEvent;
+use ibc_relayer_types::proofs::Proofs;
 use ibc_relayer_types::tx_msg::Msg;
 use ibc_relayer_types::Height;
 
-use crate::chain::counterparty::{channel_connection_client, channel_state_on_destination};
+use crate::chain::counterparty::{
+    channel_connection_client, channel_state_on_destination, sync_proof_sequence,
+};
 use crate::chain::handle::ChainHandle;
 use crate::chain::requests::{
     IncludeProof, PageRequest, QueryChannelRequest, QueryConnectionChannelsRequest,
-    QueryConnectionRequest, QueryHeight,
+    QueryConnectionRequest, QueryHeight, QueryUpgradeErrorRequest, QueryUpgradeRequest,
 };
 use crate::chain::tracking::TrackedMsgs;
 use crate::connection::Connection;
+use crate::error::Error as RelayerError;
 use crate::foreign_client::{ForeignClient, HasExpiredOrFrozenError};
 use crate::object::Channel as WorkerChannelObject;
 use crate::supervisor::error::Error as SupervisorError;
@@ -283,6 +297,33 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
         })
     }
 
+    /// Recreates a 'Channel' object from a channel upgrade event emitted by `chain`.
+    /// The upgrade events do not include the connection of the channel, hence the
+    /// channel is restored from its latest state on chain.
+    pub fn restore_from_upgrade_event(
+        chain: ChainA,
+        counterparty_chain: ChainB,
+        upgrade_event: &IbcEvent,
+    ) -> Result<Channel<ChainA, ChainB>, ChannelError> {
+        let attributes = upgrade_event
+            .upgrade_attributes()
+            .ok_or_else(|| ChannelError::invalid_event(upgrade_event.clone()))?;
+
+        let height = chain
+            .query_latest_height()
+            .map_err(|e| ChannelError::query(chain.id(), e))?;
+
+        let channel = WorkerChannelObject {
+            dst_chain_id: counterparty_chain.id(),
+            src_chain_id: chain.id(),
+            src_channel_id: attributes.channel_id().clone(),
+            src_port_id: attributes.port_id().clone(),
+        };
+
+        Self::restore_from_state(chain, counterparty_chain, channel, height)
+            .map(|(channel, _)| channel)
+    }
+
     /// Recreates a 'Channel' object from the worker's object built from chain state scanning.
     /// The channel must exist on chain and its connection must be initialized on both chains.
     pub fn restore_from_state(
@@ -405,6 +446,23 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
         &self.b_side.connection_id
     }
 
+    /// Whether both ends of the channel are on the same chain,
+    /// over the connection of the localhost client.
+    pub fn is_localhost(&self) -> bool {
+        self.src_connection_id().is_localhost()
+    }
+
+    /// Replaces the given proofs with the sentinel proof if the channel is over the
+    /// localhost connection, since the localhost client reads the state of its host
+    /// instead of verifying proofs.
+    pub fn adapt_proofs(&self, proofs: Proofs) -> Proofs {
+        if self.is_localhost() {
+            sentinel_proofs(&proofs)
+        } else {
+            proofs
+        }
+    }
+
     pub fn src_port_id(&self) -> &PortId {
         &self.a_side.port_id
     }
@@ -767,19 +825,163 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
             (State::Closed, State::Closed) => return Ok((None, Next::Abort)),
             (State::Closed, _) => Some(self.build_chan_close_confirm_and_send()?),
 
+            // Upgrade handshake steps
+            (State::Flushing, State::Open) => Some(self.build_chan_upgrade_ack_and_send()?),
+            (State::Flushing | State::FlushComplete, State::Flushing) => {
+                Some(self.build_chan_upgrade_confirm_and_send()?)
+            }
+            (State::Open | State::FlushComplete, State::FlushComplete) => {
+                Some(self.build_chan_upgrade_open_and_send()?)
+            }
+
+            // The destination completed flushing its in-flight packets, but it can only be
+            // opened once the source completed flushing as well, which the worker polls for.
+            (State::Flushing, State::FlushComplete) => return Ok((None, Next::Continue)),
+
             _ => None,
         };
 
-        // Abort if the channel is at OpenAck, OpenConfirm or CloseConfirm stage, as there is
-        // nothing more for the worker to do
+        // Abort if the channel is at OpenAck, OpenConfirm, CloseConfirm or UpgradeOpen stage,
+        // as there is nothing more for the worker to do
         match event {
             Some(IbcEvent::OpenConfirmChannel(_))
             | Some(IbcEvent::OpenAckChannel(_))
-            | Some(IbcEvent::CloseConfirmChannel(_)) => Ok((event, Next::Abort)),
+            | Some(IbcEvent::CloseConfirmChannel(_))
+            | Some(IbcEvent::UpgradeOpenChannel(_))
+            | Some(IbcEvent::UpgradeErrorChannel(_)) => Ok((event, Next::Abort)),
+            _ => Ok((event, Next::Continue)),
+        }
+    }
+
+    /// Relays the upgrade handshake step following the given upgrade event,
+    /// which was emitted by the source chain.
+    pub fn upgrade_step(
+        &mut self,
+        event: &IbcEvent,
+    ) -> Result<(Option<IbcEvent>, Next), ChannelError> {
+        let event = match event {
+            IbcEvent::UpgradeInitChannel(_) => Some(self.build_chan_upgrade_try_and_send()?),
+            IbcEvent::UpgradeTryChannel(_) => Some(self.build_chan_upgrade_ack_and_send()?),
+            IbcEvent::UpgradeAckChannel(_) => Some(self.build_chan_upgrade_confirm_and_send()?),
+            IbcEvent::UpgradeErrorChannel(_) => Some(self.build_chan_upgrade_cancel_and_send()?),
+
+            // Whether the upgrade can be opened on the destination depends on
+            // both channel ends having completed flushing their in-flight packets
+            IbcEvent::UpgradeConfirmChannel(_)
+            | IbcEvent::UpgradeOpenChannel(_)
+            | IbcEvent::FlushCompleteChannel(_) => return self.upgrade_flush_step(),
+
+            // The upgrade was aborted on the source, there is nothing left to relay
+            _ => return Ok((None, Next::Abort)),
+        };
+
+        match event {
+            Some(IbcEvent::UpgradeOpenChannel(_))
+            | Some(IbcEvent::UpgradeCancelChannel(_))
+            | Some(IbcEvent::UpgradeErrorChannel(_)) => Ok((event, Next::Abort)),
             _ => Ok((event, Next::Continue)),
         }
     }
 
+    /// Relays the end of the upgrade handshake, once both channel ends agreed on the upgrade
+    /// and are flushing their in-flight packets.
+    ///
+    /// The destination channel end is opened once both ends completed flushing. If the source
+    /// channel end is still flushing after the timeout of its upgrade, the upgrade is timed out
+    /// on the destination instead, which writes the error receipt the source needs to cancel it.
+    pub fn upgrade_flush_step(&mut self) -> Result<(Option<IbcEvent>, Next), ChannelError> {
+        let state = *self.a_channel(self.src_channel_id())?.state();
+
+        let event = match (state, self.counterparty_state()?) {
+            (State::Open | State::FlushComplete, State::FlushComplete) => {
+                self.build_chan_upgrade_open_and_send()?
+            }
+            (State::Flushing, State::Flushing | State::FlushComplete)
+                if self.src_upgrade_timed_out()? =>
+            {
+                self.build_chan_upgrade_timeout_and_send()?
+            }
+
+            // The upgrade is over on the destination, or was completed or aborted on both ends
+            (_, State::Open | State::Closed) => return Ok((None, Next::Abort)),
+
+            // Some in-flight packets are still being flushed
+            _ => return Ok((None, Next::Continue)),
+        };
+
+        match event {
+            IbcEvent::UpgradeOpenChannel(_) | IbcEvent::UpgradeErrorChannel(_) => {
+                Ok((Some(event), Next::Abort))
+            }
+            _ => Ok((Some(event), Next::Continue)),
+        }
+    }
+
+    /// Whether the timeout of the upgrade of the source channel end elapsed on the source chain.
+    fn src_upgrade_timed_out(&self) -> Result<bool, ChannelError> {
+        let src_channel_id = self
+            .src_channel_id()
+            .ok_or_else(ChannelError::missing_local_channel_id)?;
+
+        let (upgrade, _) = self
+            .src_chain()
+            .query_upgrade(
+                QueryUpgradeRequest {
+                    port_id: self.src_port_id().clone(),
+                    channel_id: src_channel_id.clone(),
+                    height: QueryHeight::Latest,
+                },
+                IncludeProof::No,
+            )
+            .map_err(|e| ChannelError::query(self.src_chain().id(), e))?;
+
+        let status = self
+            .src_chain()
+            .query_application_status()
+            .map_err(|e| ChannelError::query(self.src_chain().id(), e))?;
+
+        Ok(upgrade
+            .timeout
+            .has_elapsed(status.height, &status.timestamp))
+    }
+
+    /// Polls the progress of the upgrade of the channel, see [`Channel::upgrade_flush_step`].
+    pub fn step_upgrade_flush(&mut self) -> Next {
+        match self.upgrade_flush_step() {
+            Err(e) => {
+                error!("failed to complete the channel upgrade: {}", e);
+                Next::Continue
+            }
+            Ok((Some(ev), next)) => {
+                info!("channel upgrade step completed with events: {}", ev);
+                next
+            }
+            Ok((None, next)) => next,
+        }
+    }
+
+    pub fn step_upgrade_event(&mut self, event: &IbcEvent, index: u64) -> RetryResult<Next, u64> {
+        match self.upgrade_step(event) {
+            Err(e) => {
+                if e.is_expired_or_frozen_error() {
+                    error!("failed to upgrade channel on frozen client: {}", e);
+                    RetryResult::Err(index)
+                } else {
+                    error!(
+                        "failed channel upgrade step after {} with error: {}",
+                        event, e
+                    );
+                    RetryResult::Retry(index)
+                }
+            }
+            Ok((Some(ev), next)) => {
+                info!("channel upgrade step completed with events: {}", ev);
+                RetryResult::Ok(next)
+            }
+            Ok((None, next)) => RetryResult::Ok(next),
+        }
+    }
+
     pub fn step_state(&mut self, state: State, index: u64) -> RetryResult<Next, u64> {
         match self.handshake_step(state) {
             Err(e) => {
@@ -1009,9 +1211,13 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
             .query_latest_height()
             .map_err(|e| ChannelError::query(self.src_chain().id(), e))?;
 
+        sync_proof_sequence(self.src_chain(), self.dst_chain(), self.dst_client_id())
+            .map_err(ChannelError::relayer)?;
+
         let proofs = self
             .src_chain()
             .build_channel_proofs(self.src_port_id(), src_channel_id, query_height)
+            .map(|proofs| self.adapt_proofs(proofs))
             .map_err(ChannelError::channel_proof)?;
 
         // Build message(s) to update client on destination
@@ -1129,9 +1335,13 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
             .query_latest_height()
             .map_err(|e| ChannelError::query(self.src_chain().id(), e))?;
 
+        sync_proof_sequence(self.src_chain(), self.dst_chain(), self.dst_client_id())
+            .map_err(ChannelError::relayer)?;
+
         let proofs = self
             .src_chain()
             .build_channel_proofs(self.src_port_id(), src_channel_id, query_height)
+            .map(|proofs| self.adapt_proofs(proofs))
             .map_err(ChannelError::channel_proof)?;
 
         // Build message(s) to update client on destination
@@ -1237,9 +1447,13 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
             .query_latest_height()
             .map_err(|e| ChannelError::query(self.src_chain().id(), e))?;
 
+        sync_proof_sequence(self.src_chain(), self.dst_chain(), self.dst_client_id())
+            .map_err(ChannelError::relayer)?;
+
         let proofs = self
             .src_chain()
             .build_channel_proofs(self.src_port_id(), src_channel_id, query_height)
+            .map(|proofs| self.adapt_proofs(proofs))
             .map_err(ChannelError::channel_proof)?;
 
         // Build message(s) to update client on destination
@@ -1408,9 +1622,13 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
             .query_latest_height()
             .map_err(|e| ChannelError::query(self.src_chain().id(), e))?;
 
+        sync_proof_sequence(self.src_chain(), self.dst_chain(), self.dst_client_id())
+            .map_err(ChannelError::relayer)?;
+
         let proofs = self
             .src_chain()
             .build_channel_proofs(self.src_port_id(), src_channel_id, query_height)
+            .map(|proofs| self.adapt_proofs(proofs))
             .map_err(ChannelError::channel_proof)?;
 
         // Build message(s) to update client on destination
@@ -1465,6 +1683,467 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
         }
     }
 
+    /// Sends the given channel upgrade handshake messages to the destination chain, and
+    /// returns the first event matching `is_expected` from the response.
+    fn send_chan_upgrade_msgs(
+        &self,
+        msgs: Vec<Any>,
+        tracking_id: &'static str,
+        is_expected: impl Fn(&IbcEvent) -> bool,
+    ) -> Result<IbcEvent, ChannelError> {
+        let tm = TrackedMsgs::new_static(msgs, tracking_id);
+
+        let events = self
+            .dst_chain()
+            .send_messages_and_wait_commit(tm)
+            .map_err(|e| ChannelError::submit(self.dst_chain().id(), e))?;
+
+        let result = events
+            .into_iter()
+            .find(|event_with_height| {
+                is_expected(&event_with_height.event)
+                    || matches!(
+                        event_with_height.event,
+                        IbcEvent::UpgradeErrorChannel(_) | IbcEvent::ChainError(_)
+                    )
+            })
+            .ok_or_else(|| {
+                ChannelError::missing_event(format!("no {tracking_id} event was in the response"))
+            })?;
+
+        match &result.event {
+            IbcEvent::ChainError(e) => Err(ChannelError::tx_response(e.clone())),
+            // The upgrade was aborted by the destination chain, which wrote an error
+            // receipt for the source chain to cancel its side of the upgrade.
+            IbcEvent::UpgradeErrorChannel(_) => {
+                warn!(
+                    "channel upgrade failed on {}: {}",
+                    self.dst_chain().id(),
+                    result
+                );
+                Ok(result.event)
+            }
+            event if is_expected(event) => {
+                info!("🎊  {} => {}", self.dst_chain().id(), result);
+                Ok(result.event)
+            }
+            _ => Err(ChannelError::invalid_event(result.event)),
+        }
+    }
+
+    /// Queries the source channel end at the given height, at which the proofs
+    /// of the upgrade handshake messages are built.
+    fn src_channel_at(&self, height: Height) -> Result<ChannelEnd, ChannelError> {
+        let src_channel_id = self
+            .src_channel_id()
+            .ok_or_else(ChannelError::missing_local_channel_id)?;
+
+        self.src_chain()
+            .query_channel(
+                QueryChannelRequest {
+                    port_id: self.src_port_id().clone(),
+                    channel_id: src_channel_id.clone(),
+                    height: QueryHeight::Specific(height),
+                },
+                IncludeProof::No,
+            )
+            .map(|(channel_end, _)| channel_end)
+            .map_err(|e| ChannelError::query(self.src_chain().id(), e))
+    }
+
+    /// Returns the latest height of the source chain, once the proofs it builds
+    /// are in sync with the state of its client on the destination chain.
+    fn chan_upgrade_proof_height(&self) -> Result<Height, ChannelError> {
+        let query_height = self
+            .src_chain()
+            .query_latest_height()
+            .map_err(|e| ChannelError::query(self.src_chain().id(), e))?;
+
+        sync_proof_sequence(self.src_chain(), self.dst_chain(), self.dst_client_id())
+            .map_err(ChannelError::relayer)?;
+
+        Ok(query_height)
+    }
+
+    /// Builds the message proposing to upgrade the destination channel end.
+    ///
+    /// The parameters which are not specified are kept unchanged from the current
+    /// channel end, e.g. to only wrap a transfer channel with the fee middleware
+    /// a new version is enough.
+    pub fn build_chan_upgrade_init(
+        &self,
+        version: Option<Version>,
+        ordering: Option<Ordering>,
+        connection_hops: Option<Vec<ConnectionId>>,
+    ) -> Result<Vec<Any>, ChannelError> {
+        // Destination channel ID must be specified
+        let dst_channel_id = self
+            .dst_channel_id()
+            .ok_or_else(ChannelError::missing_counterparty_channel_id)?;
+
+        // Channel must exist on destination
+        let (dst_channel, _) = self
+            .dst_chain()
+            .query_channel(
+                QueryChannelRequest {
+                    port_id: self.dst_port_id().clone(),
+                    channel_id: dst_channel_id.clone(),
+                    height: QueryHeight::Latest,
+                },
+                IncludeProof::No,
+            )
+            .map_err(|e| ChannelError::query(self.dst_chain().id(), e))?;
+
+        let fields = UpgradeFields::new(
+            ordering.unwrap_or(dst_channel.ordering),
+            connection_hops.unwrap_or(dst_channel.connection_hops),
+            version.unwrap_or(dst_channel.version),
+        );
+
+        let signer = self
+            .dst_chain()
+            .get_signer()
+            .map_err(|e| ChannelError::fetch_signer(self.dst_chain().id(), e))?;
+
+        // Build the domain type message
+        let new_msg = MsgChannelUpgradeInit::new(
+            self.dst_port_id().clone(),
+            dst_channel_id.clone(),
+            fields,
+            signer,
+        );
+
+        Ok(vec![new_msg.to_any()])
+    }
+
+    pub fn build_chan_upgrade_init_and_send(
+        &self,
+        version: Option<Version>,
+        ordering: Option<Ordering>,
+        connection_hops: Option<Vec<ConnectionId>>,
+    ) -> Result<IbcEvent, ChannelError> {
+        let dst_msgs = self.build_chan_upgrade_init(version, ordering, connection_hops)?;
+
+        self.send_chan_upgrade_msgs(dst_msgs, "ChannelUpgradeInit", |event| {
+            matches!(event, IbcEvent::UpgradeInitChannel(_))
+        })
+    }
+
+    pub fn build_chan_upgrade_try(&self) -> Result<Vec<Any>, ChannelError> {
+        // Source and destination channel IDs must be specified
+        let src_channel_id = self
+            .src_channel_id()
+            .ok_or_else(ChannelError::missing_local_channel_id)?;
+        let dst_channel_id = self
+            .dst_channel_id()
+            .ok_or_else(ChannelError::missing_counterparty_channel_id)?;
+
+        let query_height = self.chan_upgrade_proof_height()?;
+        let src_channel = self.src_channel_at(query_height)?;
+
+        let (upgrade, proofs) = self
+            .src_chain()
+            .build_channel_upgrade_proofs(self.src_port_id(), src_channel_id, query_height)
+            .map_err(ChannelError::channel_proof)?;
+        let proofs = self.adapt_proofs(proofs);
+
+        // Build message(s) to update client on destination
+        let mut msgs = self.build_update_client_on_dst(proofs.height())?;
+
+        // Get signer
+        let signer = self
+            .dst_chain()
+            .get_signer()
+            .map_err(|e| ChannelError::fetch_signer(self.dst_chain().id(), e))?;
+
+        // Build the domain type message
+        let new_msg = MsgChannelUpgradeTry {
+            port_id: self.dst_port_id().clone(),
+            channel_id: dst_channel_id.clone(),
+            proposed_upgrade_connection_hops: vec![self.dst_connection_id().clone()],
+            counterparty_upgrade_fields: upgrade.fields,
+            counterparty_upgrade_sequence: src_channel.upgrade_sequence(),
+            proofs,
+            signer,
+        };
+
+        msgs.push(new_msg.to_any());
+        Ok(msgs)
+    }
+
+    pub fn build_chan_upgrade_try_and_send(&self) -> Result<IbcEvent, ChannelError> {
+        let dst_msgs = self.build_chan_upgrade_try()?;
+
+        self.send_chan_upgrade_msgs(dst_msgs, "ChannelUpgradeTry", |event| {
+            matches!(event, IbcEvent::UpgradeTryChannel(_))
+        })
+    }
+
+    pub fn build_chan_upgrade_ack(&self) -> Result<Vec<Any>, ChannelError> {
+        // Source and destination channel IDs must be specified
+        let src_channel_id = self
+            .src_channel_id()
+            .ok_or_else(ChannelError::missing_local_channel_id)?;
+        let dst_channel_id = self
+            .dst_channel_id()
+            .ok_or_else(ChannelError::missing_counterparty_channel_id)?;
+
+        let query_height = self.chan_upgrade_proof_height()?;
+
+        let (upgrade, proofs) = self
+            .src_chain()
+            .build_channel_upgrade_proofs(self.src_port_id(), src_channel_id, query_height)
+            .map_err(ChannelError::channel_proof)?;
+        let proofs = self.adapt_proofs(proofs);
+
+        // Build message(s) to update client on destination
+        let mut msgs = self.build_update_client_on_dst(proofs.height())?;
+
+        // Get signer
+        let signer = self
+            .dst_chain()
+            .get_signer()
+            .map_err(|e| ChannelError::fetch_signer(self.dst_chain().id(), e))?;
+
+        // Build the domain type message
+        let new_msg = MsgChannelUpgradeAck {
+            port_id: self.dst_port_id().clone(),
+            channel_id: dst_channel_id.clone(),
+            counterparty_upgrade: upgrade,
+            proofs,
+            signer,
+        };
+
+        msgs.push(new_msg.to_any());
+        Ok(msgs)
+    }
+
+    pub fn build_chan_upgrade_ack_and_send(&self) -> Result<IbcEvent, ChannelError> {
+        let dst_msgs = self.build_chan_upgrade_ack()?;
+
+        self.send_chan_upgrade_msgs(dst_msgs, "ChannelUpgradeAck", |event| {
+            matches!(event, IbcEvent::UpgradeAckChannel(_))
+        })
+    }
+
+    pub fn build_chan_upgrade_confirm(&self) -> Result<Vec<Any>, ChannelError> {
+        // Source and destination channel IDs must be specified
+        let src_channel_id = self
+            .src_channel_id()
+            .ok_or_else(ChannelError::missing_local_channel_id)?;
+        let dst_channel_id = self
+            .dst_channel_id()
+            .ok_or_else(ChannelError::missing_counterparty_channel_id)?;
+
+        let query_height = self.chan_upgrade_proof_height()?;
+        let src_channel = self.src_channel_at(query_height)?;
+
+        let (upgrade, proofs) = self
+            .src_chain()
+            .build_channel_upgrade_proofs(self.src_port_id(), src_channel_id, query_height)
+            .map_err(ChannelError::channel_proof)?;
+        let proofs = self.adapt_proofs(proofs);
+
+        // Build message(s) to update client on destination
+        let mut msgs = self.build_update_client_on_dst(proofs.height())?;
+
+        // Get signer
+        let signer = self
+            .dst_chain()
+            .get_signer()
+            .map_err(|e| ChannelError::fetch_signer(self.dst_chain().id(), e))?;
+
+        // Build the domain type message
+        let new_msg = MsgChannelUpgradeConfirm {
+            port_id: self.dst_port_id().clone(),
+            channel_id: dst_channel_id.clone(),
+            counterparty_channel_state: src_channel.state,
+            counterparty_upgrade: upgrade,
+            proofs,
+            signer,
+        };
+
+        msgs.push(new_msg.to_any());
+        Ok(msgs)
+    }
+
+    pub fn build_chan_upgrade_confirm_and_send(&self) -> Result<IbcEvent, ChannelError> {
+        let dst_msgs = self.build_chan_upgrade_confirm()?;
+
+        // The destination channel end is opened right away if the source already
+        // completed flushing, in which case no confirm event is emitted.
+        self.send_chan_upgrade_msgs(dst_msgs, "ChannelUpgradeConfirm", |event| {
+            matches!(
+                event,
+                IbcEvent::UpgradeConfirmChannel(_) | IbcEvent::UpgradeOpenChannel(_)
+            )
+        })
+    }
+
+    pub fn build_chan_upgrade_open(&self) -> Result<Vec<Any>, ChannelError> {
+        // Source and destination channel IDs must be specified
+        let src_channel_id = self
+            .src_channel_id()
+            .ok_or_else(ChannelError::missing_local_channel_id)?;
+        let dst_channel_id = self
+            .dst_channel_id()
+            .ok_or_else(ChannelError::missing_counterparty_channel_id)?;
+
+        let query_height = self.chan_upgrade_proof_height()?;
+        let src_channel = self.src_channel_at(query_height)?;
+
+        let proofs = self
+            .src_chain()
+            .build_channel_proofs(self.src_port_id(), src_channel_id, query_height)
+            .map(|proofs| self.adapt_proofs(proofs))
+            .map_err(ChannelError::channel_proof)?;
+
+        // Build message(s) to update client on destination
+        let mut msgs = self.build_update_client_on_dst(proofs.height())?;
+
+        // Get signer
+        let signer = self
+            .dst_chain()
+            .get_signer()
+            .map_err(|e| ChannelError::fetch_signer(self.dst_chain().id(), e))?;
+
+        // Build the domain type message
+        let new_msg = MsgChannelUpgradeOpen {
+            port_id: self.dst_port_id().clone(),
+            channel_id: dst_channel_id.clone(),
+            counterparty_channel_state: src_channel.state,
+            counterparty_upgrade_sequence: src_channel.upgrade_sequence(),
+            proofs,
+            signer,
+        };
+
+        msgs.push(new_msg.to_any());
+        Ok(msgs)
+    }
+
+    pub fn build_chan_upgrade_open_and_send(&self) -> Result<IbcEvent, ChannelError> {
+        let dst_msgs = self.build_chan_upgrade_open()?;
+
+        self.send_chan_upgrade_msgs(dst_msgs, "ChannelUpgradeOpen", |event| {
+            matches!(event, IbcEvent::UpgradeOpenChannel(_))
+        })
+    }
+
+    /// Builds the message aborting the upgrade of the destination channel end,
+    /// after its timeout elapsed on the source chain.
+    pub fn build_chan_upgrade_timeout(&self) -> Result<Vec<Any>, ChannelError> {
+        // Source and destination channel IDs must be specified
+        let src_channel_id = self
+            .src_channel_id()
+            .ok_or_else(ChannelError::missing_local_channel_id)?;
+        let dst_channel_id = self
+            .dst_channel_id()
+            .ok_or_else(ChannelError::missing_counterparty_channel_id)?;
+
+        let query_height = self.chan_upgrade_proof_height()?;
+        let src_channel = self.src_channel_at(query_height)?;
+
+        let proofs = self
+            .src_chain()
+            .build_channel_proofs(self.src_port_id(), src_channel_id, query_height)
+            .map(|proofs| self.adapt_proofs(proofs))
+            .map_err(ChannelError::channel_proof)?;
+
+        // Build message(s) to update client on destination
+        let mut msgs = self.build_update_client_on_dst(proofs.height())?;
+
+        // Get signer
+        let signer = self
+            .dst_chain()
+            .get_signer()
+            .map_err(|e| ChannelError::fetch_signer(self.dst_chain().id(), e))?;
+
+        // Build the domain type message
+        let new_msg = MsgChannelUpgradeTimeout {
+            port_id: self.dst_port_id().clone(),
+            channel_id: dst_channel_id.clone(),
+            counterparty_channel: src_channel,
+            proofs,
+            signer,
+        };
+
+        msgs.push(new_msg.to_any());
+        Ok(msgs)
+    }
+
+    pub fn build_chan_upgrade_timeout_and_send(&self) -> Result<IbcEvent, ChannelError> {
+        let dst_msgs = self.build_chan_upgrade_timeout()?;
+
+        self.send_chan_upgrade_msgs(dst_msgs, "ChannelUpgradeTimeout", |event| {
+            matches!(event, IbcEvent::UpgradeTimeoutChannel(_))
+        })
+    }
+
+    /// Builds the message aborting the upgrade of the destination channel end,
+    /// using the error receipt written by the source chain.
+    pub fn build_chan_upgrade_cancel(&self) -> Result<Vec<Any>, ChannelError> {
+        // Source and destination channel IDs must be specified
+        let src_channel_id = self
+            .src_channel_id()
+            .ok_or_else(ChannelError::missing_local_channel_id)?;
+        let dst_channel_id = self
+            .dst_channel_id()
+            .ok_or_else(ChannelError::missing_counterparty_channel_id)?;
+
+        let query_height = self.chan_upgrade_proof_height()?;
+
+        let (error_receipt, maybe_receipt_proof) = self
+            .src_chain()
+            .query_upgrade_error(
+                QueryUpgradeErrorRequest {
+                    port_id: self.src_port_id().clone(),
+                    channel_id: src_channel_id.clone(),
+                    height: QueryHeight::Specific(query_height),
+                },
+                IncludeProof::Yes,
+            )
+            .map_err(|e| ChannelError::query(self.src_chain().id(), e))?;
+
+        let receipt_proof = maybe_receipt_proof
+            .ok_or_else(|| ChannelError::channel_proof(RelayerError::queried_proof_not_found()))?;
+
+        let receipt_proof = CommitmentProofBytes::try_from(receipt_proof)
+            .map_err(|e| ChannelError::channel_proof(RelayerError::malformed_proof(e)))?;
+
+        let proofs = Proofs::new(receipt_proof, None, None, None, query_height.increment())
+            .map(|proofs| self.adapt_proofs(proofs))
+            .map_err(|e| ChannelError::channel_proof(RelayerError::malformed_proof(e)))?;
+
+        // Build message(s) to update client on destination
+        let mut msgs = self.build_update_client_on_dst(proofs.height())?;
+
+        // Get signer
+        let signer = self
+            .dst_chain()
+            .get_signer()
+            .map_err(|e| ChannelError::fetch_signer(self.dst_chain().id(), e))?;
+
+        // Build the domain type message
+        let new_msg = MsgChannelUpgradeCancel {
+            port_id: self.dst_port_id().clone(),
+            channel_id: dst_channel_id.clone(),
+            error_receipt,
+            proofs,
+            signer,
+        };
+
+        msgs.push(new_msg.to_any());
+        Ok(msgs)
+    }
+
+    pub fn build_chan_upgrade_cancel_and_send(&self) -> Result<IbcEvent, ChannelError> {
+        let dst_msgs = self.build_chan_upgrade_cancel()?;
+
+        self.send_chan_upgrade_msgs(dst_msgs, "ChannelUpgradeCancel", |event| {
+            matches!(event, IbcEvent::UpgradeCancelChannel(_))
+        })
+    }
+
     pub fn map_chain<ChainC: ChainHandle, ChainD: ChainHandle>(
         self,
         mapper_a: impl Fn(ChainA) -> ChainC,
@@ -2,16 +2,23 @@ use core::time::Duration;
 
 use ibc_proto::ibc::core::client::v1::IdentifiedClientState;
 use ibc_proto::ibc::lightclients::tendermint::v1::ClientState as RawClientState;
-#[cfg(test)]
-use ibc_proto::ibc::mock::ClientState as RawMockClientState;
 use ibc_proto::protobuf::Protobuf;
 use serde::{Deserialize, Serialize};
 
 use ibc_proto::google::protobuf::Any;
+use ibc_relayer_types::clients::ics06_solomachine::client_state::{
+    ClientState as SoloClientState, SOLOMACHINE_CLIENT_STATE_TYPE_URL,
+};
 use ibc_relayer_types::clients::ics07_tendermint::client_state::{
     ClientState as TmClientState, UpgradeOptions as TmUpgradeOptions,
     TENDERMINT_CLIENT_STATE_TYPE_URL,
 };
+use ibc_relayer_types::clients::ics08_wasm::client_state::{
+    ClientState as WasmClientState, WASM_CLIENT_STATE_TYPE_URL,
+};
+use ibc_relayer_types::clients::ics09_localhost::client_state::{
+    ClientState as LocalhostClientState, LOCALHOST_CLIENT_STATE_TYPE_URL,
+};
 use ibc_relayer_types::core::ics02_client::client_state::{
     downcast_client_state, ClientState, UpgradeOptions,
 };
@@ -21,9 +28,9 @@ use ibc_relayer_types::core::ics02_client::trust_threshold::TrustThreshold;
 
 use ibc_relayer_types::core::ics24_host::error::ValidationError;
 use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ClientId};
-#[cfg(test)]
+#[cfg(any(test, feature = "mock"))]
 use ibc_relayer_types::mock::client_state::MockClientState;
-#[cfg(test)]
+#[cfg(any(test, feature = "mock"))]
 use ibc_relayer_types::mock::client_state::MOCK_CLIENT_STATE_TYPE_URL;
 use ibc_relayer_types::Height;
 
@@ -32,7 +39,7 @@ use ibc_relayer_types::Height;
 pub enum AnyUpgradeOptions {
     Tendermint(TmUpgradeOptions),
 
-    #[cfg(test)]
+    #[cfg(any(test, feature = "mock"))]
     Mock(()),
 }
 
@@ -40,7 +47,7 @@ impl AnyUpgradeOptions {
     fn as_tm_upgrade_options(&self) -> Option<&TmUpgradeOptions> {
         match self {
             AnyUpgradeOptions::Tendermint(tm) => Some(tm),
-            #[cfg(test)]
+            #[cfg(any(test, feature = "mock"))]
             AnyUpgradeOptions::Mock(_) => None,
         }
     }
@@ -52,8 +59,11 @@ impl UpgradeOptions for AnyUpgradeOptions {}
 #[serde(tag = "type")]
 pub enum AnyClientState {
     Tendermint(TmClientState),
+    Solomachine(SoloClientState),
+    Wasm(WasmClientState),
+    Localhost(LocalhostClientState),
 
-    #[cfg(test)]
+    #[cfg(any(test, feature = "mock"))]
     Mock(MockClientState),
 }
 
@@ -61,8 +71,11 @@ impl AnyClientState {
     pub fn latest_height(&self) -> Height {
         match self {
             Self::Tendermint(tm_state) => tm_state.latest_height(),
+            Self::Solomachine(solo_state) => solo_state.latest_height(),
+            Self::Wasm(wasm_state) => wasm_state.latest_height(),
+            Self::Localhost(local_state) => local_state.latest_height(),
 
-            #[cfg(test)]
+            #[cfg(any(test, feature = "mock"))]
             Self::Mock(mock_state) => mock_state.latest_height(),
         }
     }
@@ -70,8 +83,11 @@ impl AnyClientState {
     pub fn frozen_height(&self) -> Option<Height> {
         match self {
             Self::Tendermint(tm_state) => tm_state.frozen_height(),
+            Self::Solomachine(solo_state) => solo_state.frozen_height(),
+            Self::Wasm(wasm_state) => wasm_state.frozen_height(),
+            Self::Localhost(local_state) => local_state.frozen_height(),
 
-            #[cfg(test)]
+            #[cfg(any(test, feature = "mock"))]
             Self::Mock(mock_state) => mock_state.frozen_height(),
         }
     }
@@ -79,8 +95,11 @@ impl AnyClientState {
     pub fn trust_threshold(&self) -> Option<TrustThreshold> {
         match self {
             AnyClientState::Tendermint(state) => Some(state.trust_threshold),
+            AnyClientState::Solomachine(_) => None,
+            AnyClientState::Wasm(state) => Some(state.inner.trust_threshold),
+            AnyClientState::Localhost(_) => None,
 
-            #[cfg(test)]
+            #[cfg(any(test, feature = "mock"))]
             AnyClientState::Mock(_) => None,
         }
     }
@@ -88,8 +107,11 @@ impl AnyClientState {
     pub fn max_clock_drift(&self) -> Duration {
         match self {
             AnyClientState::Tendermint(state) => state.max_clock_drift,
+            AnyClientState::Solomachine(_) => Duration::new(0, 0),
+            AnyClientState::Wasm(state) => state.inner.max_clock_drift,
+            AnyClientState::Localhost(_) => Duration::new(0, 0),
 
-            #[cfg(test)]
+            #[cfg(any(test, feature = "mock"))]
             AnyClientState::Mock(_) => Duration::new(0, 0),
         }
     }
@@ -97,8 +119,11 @@ impl AnyClientState {
     pub fn client_type(&self) -> ClientType {
         match self {
             Self::Tendermint(state) => state.client_type(),
+            Self::Solomachine(state) => state.client_type(),
+            Self::Wasm(state) => state.client_type(),
+            Self::Localhost(state) => state.client_type(),
 
-            #[cfg(test)]
+            #[cfg(any(test, feature = "mock"))]
             Self::Mock(state) => state.client_type(),
         }
     }
@@ -106,11 +131,34 @@ impl AnyClientState {
     pub fn refresh_period(&self) -> Option<Duration> {
         match self {
             AnyClientState::Tendermint(tm_state) => tm_state.refresh_time(),
+            AnyClientState::Solomachine(_) => None,
+            AnyClientState::Wasm(wasm_state) => wasm_state.inner.refresh_time(),
+            AnyClientState::Localhost(_) => None,
 
-            #[cfg(test)]
+            #[cfg(any(test, feature = "mock"))]
             AnyClientState::Mock(mock_state) => mock_state.refresh_time(),
         }
     }
+
+    /// Records the chain hosting this client state, for the client
+    /// states which do not carry the identifier of their chain.
+    pub fn hosted_on(self, chain_id: &ChainId) -> Self {
+        match self {
+            Self::Localhost(local_state) => {
+                Self::Localhost(local_state.with_chain_id(chain_id.clone()))
+            }
+            client_state => client_state,
+        }
+    }
+
+    /// The client state wrapped by a Wasm client, which the relayer handles as it
+    /// would a regular client of that type, or this client state for other clients.
+    pub fn unwrap_wasm(self) -> Self {
+        match self {
+            Self::Wasm(wasm_state) => Self::Tendermint(wasm_state.inner),
+            client_state => client_state,
+        }
+    }
 }
 
 impl Protobuf<Any> for AnyClientState {}
@@ -127,12 +175,19 @@ impl TryFrom<Any> for AnyClientState {
                     .map_err(Error::decode_raw_client_state)?,
             )),
 
-            #[cfg(test)]
-            MOCK_CLIENT_STATE_TYPE_URL => Ok(AnyClientState::Mock(
-                Protobuf::<RawMockClientState>::decode_vec(&raw.value)
-                    .map_err(Error::decode_raw_client_state)?,
+            SOLOMACHINE_CLIENT_STATE_TYPE_URL => {
+                Ok(AnyClientState::Solomachine(SoloClientState::try_from(raw)?))
+            }
+
+            WASM_CLIENT_STATE_TYPE_URL => Ok(AnyClientState::Wasm(WasmClientState::try_from(raw)?)),
+
+            LOCALHOST_CLIENT_STATE_TYPE_URL => Ok(AnyClientState::Localhost(
+                LocalhostClientState::try_from(raw)?,
             )),
 
+            #[cfg(any(test, feature = "mock"))]
+            MOCK_CLIENT_STATE_TYPE_URL => Ok(AnyClientState::Mock(MockClientState::try_from(raw)?)),
+
             _ => Err(Error::unknown_client_state_type(raw.type_url)),
         }
     }
@@ -145,11 +200,11 @@ impl From<AnyClientState> for Any {
                 type_url: TENDERMINT_CLIENT_STATE_TYPE_URL.to_string(),
                 value: Protobuf::<RawClientState>::encode_vec(&value),
             },
-            #[cfg(test)]
-            AnyClientState::Mock(value) => Any {
-                type_url: MOCK_CLIENT_STATE_TYPE_URL.to_string(),
-                value: Protobuf::<RawMockClientState>::encode_vec(&value),
-            },
+            AnyClientState::Solomachine(value) => value.into(),
+            AnyClientState::Wasm(value) => value.into(),
+            AnyClientState::Localhost(value) => value.into(),
+            #[cfg(any(test, feature = "mock"))]
+            AnyClientState::Mock(value) => value.into(),
         }
     }
 }
@@ -158,8 +213,11 @@ impl ClientState for AnyClientState {
     fn chain_id(&self) -> ChainId {
         match self {
             AnyClientState::Tendermint(tm_state) => tm_state.chain_id(),
+            AnyClientState::Solomachine(solo_state) => solo_state.chain_id(),
+            AnyClientState::Wasm(wasm_state) => wasm_state.chain_id(),
+            AnyClientState::Localhost(local_state) => local_state.chain_id(),
 
-            #[cfg(test)]
+            #[cfg(any(test, feature = "mock"))]
             AnyClientState::Mock(mock_state) => mock_state.chain_id(),
         }
     }
@@ -194,7 +252,21 @@ impl ClientState for AnyClientState {
                 chain_id,
             ),
 
-            #[cfg(test)]
+            AnyClientState::Solomachine(solo_state) => {
+                solo_state.upgrade(upgrade_height, upgrade_options, chain_id)
+            }
+
+            AnyClientState::Wasm(wasm_state) => wasm_state.upgrade(
+                upgrade_height,
+                upgrade_options.as_tm_upgrade_options().unwrap(),
+                chain_id,
+            ),
+
+            AnyClientState::Localhost(local_state) => {
+                local_state.upgrade(upgrade_height, upgrade_options, chain_id)
+            }
+
+            #[cfg(any(test, feature = "mock"))]
             AnyClientState::Mock(mock_state) => {
                 mock_state.upgrade(upgrade_height, upgrade_options, chain_id)
             }
@@ -204,8 +276,11 @@ impl ClientState for AnyClientState {
     fn expired(&self, elapsed_since_latest: Duration) -> bool {
         match self {
             AnyClientState::Tendermint(tm_state) => tm_state.expired(elapsed_since_latest),
+            AnyClientState::Solomachine(solo_state) => solo_state.expired(elapsed_since_latest),
+            AnyClientState::Wasm(wasm_state) => wasm_state.expired(elapsed_since_latest),
+            AnyClientState::Localhost(local_state) => local_state.expired(elapsed_since_latest),
 
-            #[cfg(test)]
+            #[cfg(any(test, feature = "mock"))]
             AnyClientState::Mock(mock_state) => mock_state.expired(elapsed_since_latest),
         }
     }
@@ -217,7 +292,25 @@ impl From<TmClientState> for AnyClientState {
     }
 }
 
-#[cfg(test)]
+impl From<SoloClientState> for AnyClientState {
+    fn from(cs: SoloClientState) -> Self {
+        Self::Solomachine(cs)
+    }
+}
+
+impl From<WasmClientState> for AnyClientState {
+    fn from(cs: WasmClientState) -> Self {
+        Self::Wasm(cs)
+    }
+}
+
+impl From<LocalhostClientState> for AnyClientState {
+    fn from(cs: LocalhostClientState) -> Self {
+        Self::Localhost(cs)
+    }
+}
+
+#[cfg(any(test, feature = "mock"))]
 impl From<MockClientState> for AnyClientState {
     fn from(cs: MockClientState) -> Self {
         Self::Mock(cs)
@@ -226,11 +319,23 @@ impl From<MockClientState> for AnyClientState {
 
 impl From<&dyn ClientState> for AnyClientState {
     fn from(client_state: &dyn ClientState) -> Self {
-        #[cfg(test)]
+        #[cfg(any(test, feature = "mock"))]
         if let Some(cs) = downcast_client_state::<MockClientState>(client_state) {
             return AnyClientState::from(*cs);
         }
 
+        if let Some(cs) = downcast_client_state::<SoloClientState>(client_state) {
+            return AnyClientState::from(cs.clone());
+        }
+
+        if let Some(cs) = downcast_client_state::<WasmClientState>(client_state) {
+            return AnyClientState::from(cs.clone());
+        }
+
+        if let Some(cs) = downcast_client_state::<LocalhostClientState>(client_state) {
+            return AnyClientState::from(cs.clone());
+        }
+
         if let Some(cs) = downcast_client_state::<TmClientState>(client_state) {
             AnyClientState::from(cs.clone())
         } else {
@@ -287,6 +392,11 @@ mod tests {
     use ibc_proto::google::protobuf::Any;
     use ibc_relayer_types::clients::ics07_tendermint::client_state::test_util::get_dummy_tendermint_client_state;
     use ibc_relayer_types::clients::ics07_tendermint::header::test_util::get_dummy_tendermint_header;
+    use ibc_relayer_types::clients::ics08_wasm::client_state::ClientState as WasmClientState;
+    use ibc_relayer_types::clients::ics09_localhost::client_state::ClientState as LocalhostClientState;
+    use ibc_relayer_types::core::ics02_client::client_state::ClientState;
+    use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+    use ibc_relayer_types::Height;
     use test_log::test;
 
     use super::AnyClientState;
@@ -300,4 +410,36 @@ mod tests {
         let tm_client_state_back = AnyClientState::try_from(raw).unwrap();
         assert_eq!(tm_client_state, tm_client_state_back);
     }
+
+    #[test]
+    fn localhost_client_state_is_hosted_on_its_chain() {
+        let chain_id = ChainId::new("ibc".to_string(), 1);
+        let client_state: AnyClientState =
+            LocalhostClientState::new(chain_id.clone(), Height::new(1, 10).unwrap()).into();
+
+        let raw: Any = client_state.clone().into();
+        let decoded = AnyClientState::try_from(raw).unwrap();
+        assert_eq!(decoded.chain_id(), ChainId::default());
+
+        let hosted = decoded.hosted_on(&chain_id);
+        assert_eq!(hosted.chain_id(), chain_id);
+        assert_eq!(hosted, client_state);
+    }
+
+    #[test]
+    fn wasm_client_state_unwraps_to_tendermint() {
+        let tm_client_state = get_dummy_tendermint_client_state(get_dummy_tendermint_header());
+        let client_state: AnyClientState =
+            WasmClientState::new(vec![0xab; 32], tm_client_state.clone()).into();
+
+        let raw: Any = client_state.clone().into();
+        let decoded = AnyClientState::try_from(raw).unwrap();
+        assert_eq!(decoded, client_state);
+        assert_eq!(decoded.latest_height(), tm_client_state.latest_height);
+
+        assert_eq!(
+            decoded.unwrap_wasm(),
+            AnyClientState::Tendermint(tm_client_state)
+        );
+    }
 }
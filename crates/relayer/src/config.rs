@@ -5,12 +5,14 @@ pub mod filter;
 pub mod gas_multiplier;
 pub mod proof_specs;
 pub mod types;
+pub mod validate;
 
 use alloc::collections::BTreeMap;
 use byte_unit::Byte;
 use core::{
     cmp::Ordering,
     fmt::{Display, Error as FmtError, Formatter},
+    num::{NonZeroU32, NonZeroUsize},
     str::FromStr,
     time::Duration,
 };
@@ -42,6 +44,7 @@ pub use crate::config::Error as ConfigError;
 pub use error::Error;
 
 pub use filter::PacketFilter;
+pub use validate::{validate_config, Diagnostic};
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct GasPrice {
@@ -55,6 +58,32 @@ impl GasPrice {
     }
 }
 
+/// Derive the gas price from the base fee of the chain's fee market,
+/// which is queried once per block.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DynamicGasPrice {
+    /// The factor by which the base fee is multiplied, so that transactions are still
+    /// accepted if the base fee rises by the time they are included in a block
+    #[serde(default)]
+    pub multiplier: GasMultiplier,
+
+    /// The maximum gas price the relayer is willing to pay, in the denomination of `gas_price`
+    pub max: f64,
+}
+
+/// The maximum amount of fees the relayer may spend on a chain over each period.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FeeBudget {
+    /// The maximum amount of fees to spend per period, in the given denomination
+    pub amount: u64,
+    pub denom: String,
+    /// The duration of each period, after which the budget is replenished
+    #[serde(with = "humantime_serde")]
+    pub period: Duration,
+}
+
 impl Display for GasPrice {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
         write!(f, "{}{}", self.price, self.denom)
@@ -174,6 +203,26 @@ pub mod default {
         Duration::from_secs(10)
     }
 
+    pub fn max_resume_age() -> Duration {
+        Duration::from_secs(60 * 60)
+    }
+
+    pub fn audit_max_file_size() -> Byte {
+        Byte::from_bytes(100 * 1024 * 1024)
+    }
+
+    pub fn audit_rotation_period() -> Duration {
+        Duration::from_secs(24 * 60 * 60)
+    }
+
+    pub fn otlp_endpoint() -> String {
+        "http://127.0.0.1:4317".to_string()
+    }
+
+    pub fn service_name() -> String {
+        "hermes".to_string()
+    }
+
     pub fn poll_interval() -> Duration {
         Duration::from_secs(1)
     }
@@ -202,6 +251,14 @@ pub mod default {
         false
     }
 
+    pub fn remote_signer_timeout() -> Duration {
+        Duration::from_secs(5)
+    }
+
+    pub fn light_block_cache_capacity() -> u64 {
+        100
+    }
+
     pub fn max_grpc_decoding_size() -> Byte {
         Byte::from_bytes(33554432)
     }
@@ -238,6 +295,12 @@ pub struct Config {
     pub rest: RestConfig,
     #[serde(default)]
     pub telemetry: TelemetryConfig,
+    #[serde(default)]
+    pub state_store: StateStoreConfig,
+    #[serde(default)]
+    pub tracing: TracingConfig,
+    #[serde(default)]
+    pub audit: AuditConfig,
     #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
     pub chains: Vec<ChainConfig>,
 }
@@ -482,6 +545,11 @@ pub struct RestConfig {
     pub enabled: bool,
     pub host: String,
     pub port: u16,
+
+    /// Bearer token which must be provided to the endpoints of the REST API
+    /// which act on the relayer. These endpoints are disabled if no token is set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth_token: Option<String>,
 }
 
 impl Default for RestConfig {
@@ -490,6 +558,88 @@ impl Default for RestConfig {
             enabled: false,
             host: "127.0.0.1".to_string(),
             port: 3000,
+            auth_token: None,
+        }
+    }
+}
+
+/// Settings of the store persisting the state of the relayer across restarts
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct StateStoreConfig {
+    pub enabled: bool,
+
+    /// The folder of the store. Default: `$HOME/.hermes/state`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<PathBuf>,
+
+    /// How long the relayer can be stopped for and still resume from the stored state,
+    /// instead of clearing all the pending packets on start
+    #[serde(default = "default::max_resume_age", with = "humantime_serde")]
+    pub max_resume_age: Duration,
+}
+
+impl Default for StateStoreConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            path: None,
+            max_resume_age: default::max_resume_age(),
+        }
+    }
+}
+
+/// Settings of the export of the tracing spans to an OpenTelemetry collector
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct TracingConfig {
+    pub enabled: bool,
+
+    /// The gRPC endpoint of the OTLP receiver of the collector
+    #[serde(default = "default::otlp_endpoint")]
+    pub endpoint: String,
+
+    /// The name of the service the spans are reported under
+    #[serde(default = "default::service_name")]
+    pub service_name: String,
+}
+
+impl Default for TracingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            endpoint: default::otlp_endpoint(),
+            service_name: default::service_name(),
+        }
+    }
+}
+
+/// Settings of the audit log of the transactions submitted by the relayer
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct AuditConfig {
+    pub enabled: bool,
+
+    /// The file the entries are appended to. Default: `$HOME/.hermes/audit/txs.jsonl`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<PathBuf>,
+
+    /// The size above which the file is rotated
+    #[serde(default = "default::audit_max_file_size")]
+    pub max_file_size: Byte,
+
+    /// How long entries are appended to the same file before it is rotated
+    #[serde(default = "default::audit_rotation_period", with = "humantime_serde")]
+    pub rotation_period: Duration,
+}
+
+impl Default for AuditConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            path: None,
+            max_file_size: default::audit_max_file_size(),
+            rotation_period: default::audit_rotation_period(),
         }
     }
 }
@@ -530,6 +680,117 @@ pub struct GenesisRestart {
     pub archive_addr: Url,
 }
 
+/// The endpoints of a full node to fall back to when the node a chain is
+/// connected to lags behind or becomes unreachable
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct FallbackEndpoint {
+    /// The RPC URL of the node
+    pub rpc_addr: Url,
+
+    /// The gRPC URL of the node
+    pub grpc_addr: Url,
+
+    /// The WebSocket URL of the node, only used with a push-based event source.
+    /// Defaults to the `/websocket` path of the RPC URL.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub websocket_addr: Option<WebSocketClientUrl>,
+}
+
+/// Settings of the external signer holding the keys of a chain
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct RemoteSignerConfig {
+    /// The HTTP(S) URL of the signer
+    pub url: Url,
+
+    /// Timeout of the requests sent to the signer
+    #[serde(default = "default::remote_signer_timeout", with = "humantime_serde")]
+    pub timeout: Duration,
+
+    /// Bearer token sent in the `Authorization` header of the requests to the signer
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth_token: Option<String>,
+}
+
+/// Settings for monitoring the balance of the relayer wallets of a chain.
+///
+/// Balances are expressed in the denomination of the chain's gas price.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct WalletConfig {
+    /// Balance below which an alert is raised for a wallet
+    pub min_balance: u64,
+
+    /// Balance to which a wallet is topped up from the treasury account
+    /// once its balance drops below `min_balance`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_balance: Option<u64>,
+
+    /// Name of the key of the treasury account, which funds the top-ups
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub treasury_key_name: Option<String>,
+}
+
+impl WalletConfig {
+    /// The balance to top up to and the key of the treasury account funding it,
+    /// if top-ups are enabled
+    pub fn top_up(&self) -> Option<(u64, &str)> {
+        self.target_balance.zip(self.treasury_key_name.as_deref())
+    }
+}
+
+/// Settings of a solo machine chain
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct SoloMachineConfig {
+    /// The folder of the database holding the IBC state of the solo machine.
+    /// Default: `$HOME/.hermes/solomachine/<chain-id>`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<PathBuf>,
+}
+
+/// Cache of the light blocks fetched from the full node by the light client,
+/// which lets client updates and misbehaviour checks reuse the blocks fetched
+/// for earlier verifications instead of fetching them again.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct LightBlockCacheConfig {
+    /// Maximum number of light blocks kept in the cache, `0` disables the cache
+    #[serde(default = "default::light_block_cache_capacity")]
+    pub capacity: u64,
+
+    /// Folder in which to persist the cached light blocks across restarts.
+    /// The blocks are only kept in memory when it is not set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<PathBuf>,
+}
+
+impl Default for LightBlockCacheConfig {
+    fn default() -> Self {
+        Self {
+            capacity: default::light_block_cache_capacity(),
+            path: None,
+        }
+    }
+}
+
+/// Limits on the requests Hermes sends to the full node of a chain.
+///
+/// The limits apply separately to each full node, should Hermes switch
+/// over to one of the fallback nodes of the chain.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct RateLimitConfig {
+    /// Maximum number of requests sent per second
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_requests_per_second: Option<NonZeroU32>,
+
+    /// Maximum number of requests awaiting a response at any time
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_concurrent_requests: Option<NonZeroUsize>,
+}
+
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 #[serde(tag = "mode", rename_all = "lowercase")]
 pub enum EventSourceMode {
@@ -571,6 +832,11 @@ pub struct ChainConfig {
     /// The type of event source and associated settings
     pub event_source: EventSourceMode,
 
+    /// The full nodes to fall back to, in order of preference,
+    /// when the node above lags behind or becomes unreachable
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fallback_endpoints: Vec<FallbackEndpoint>,
+
     /// Timeout used when issuing RPC queries
     #[serde(default = "default::rpc_timeout", with = "humantime_serde")]
     pub rpc_timeout: Duration,
@@ -581,6 +847,12 @@ pub struct ChainConfig {
 
     pub account_prefix: String,
     pub key_name: String,
+
+    /// Additional keys used to sign transactions on this chain, alongside `key_name`.
+    /// Batches of messages are spread across all these keys, each with its own account.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub key_names: Vec<String>,
+
     #[serde(default)]
     pub key_store_type: Store,
     pub key_store_folder: Option<PathBuf>,
@@ -654,15 +926,61 @@ pub struct ChainConfig {
 
     pub gas_price: GasPrice,
 
+    /// Query the gas price from the fee market of the chain instead of
+    /// always using the static `gas_price`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dynamic_gas_price: Option<DynamicGasPrice>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee_budget: Option<FeeBudget>,
+
     #[serde(default)]
     pub packet_filter: PacketFilter,
 
+    #[serde(default)]
+    pub light_block_cache: LightBlockCacheConfig,
+
+    /// Limit the requests sent to the full node, to avoid being
+    /// rate limited or banned by public and shared nodes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_limit: Option<RateLimitConfig>,
+
+    /// Delegate transaction signing to an external signer instead of
+    /// loading the keys from the key store
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remote_signer: Option<RemoteSignerConfig>,
+
+    /// Raise alerts when the balance of the relayer wallets runs low,
+    /// and optionally top them up from a treasury account
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wallet: Option<WalletConfig>,
+
+    /// Settings of the chain when it is a solo machine
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub solo_machine: Option<SoloMachineConfig>,
+
     #[serde(default)]
     pub address_type: AddressType,
     #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
     pub extension_options: Vec<ExtensionOption>,
 }
 
+impl ChainConfig {
+    /// The names of all the keys which sign transactions on this chain,
+    /// starting with the main key `key_name`, without duplicates.
+    pub fn signing_key_names(&self) -> Vec<&str> {
+        let mut key_names = vec![self.key_name.as_str()];
+
+        for key_name in &self.key_names {
+            if !key_names.contains(&key_name.as_str()) {
+                key_names.push(key_name);
+            }
+        }
+
+        key_names
+    }
+}
+
 /// Attempt to load and parse the TOML config file as a `Config`.
 pub fn load(path: impl AsRef<Path>) -> Result<Config, Error> {
     let config_toml = std::fs::read_to_string(&path).map_err(Error::io)?;
@@ -696,9 +1014,10 @@ pub(crate) fn store_writer(config: &Config, mut writer: impl Write) -> Result<()
 #[cfg(test)]
 mod tests {
     use core::str::FromStr;
+    use core::time::Duration;
 
     use super::{load, parse_gas_prices, store_writer};
-    use crate::config::GasPrice;
+    use crate::config::{AuditConfig, GasPrice, TracingConfig};
     use test_log::test;
 
     #[test]
@@ -759,6 +1078,25 @@ mod tests {
         assert!(load(path).is_err());
     }
 
+    #[test]
+    fn parse_tracing_with_defaults() {
+        let config = toml::from_str::<TracingConfig>("enabled = true").unwrap();
+
+        assert!(config.enabled);
+        assert_eq!(config.endpoint, "http://127.0.0.1:4317");
+        assert_eq!(config.service_name, "hermes");
+    }
+
+    #[test]
+    fn parse_audit_with_defaults() {
+        let config = toml::from_str::<AuditConfig>("enabled = true").unwrap();
+
+        assert!(config.enabled);
+        assert_eq!(config.path, None);
+        assert_eq!(config.max_file_size.get_bytes(), 100 * 1024 * 1024);
+        assert_eq!(config.rotation_period, Duration::from_secs(24 * 60 * 60));
+    }
+
     #[test]
     fn serialize_valid_config() {
         let path = concat!(
@@ -772,6 +1110,22 @@ mod tests {
         store_writer(&config, &mut buffer).unwrap();
     }
 
+    #[test]
+    fn signing_key_names_start_with_main_key() {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/config/fixtures/relayer_conf_example.toml"
+        );
+
+        let config = load(path).expect("could not parse config");
+
+        assert_eq!(config.chains[0].signing_key_names(), vec!["testkey"]);
+        assert_eq!(
+            config.chains[1].signing_key_names(),
+            vec!["testkey", "otherkey"]
+        );
+    }
+
     #[test]
     fn gas_price_from_str() {
         let gp_original = GasPrice::new(10.0, "atom".to_owned());
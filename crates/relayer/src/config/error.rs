@@ -1,4 +1,6 @@
 use flex_error::{define_error, TraceError};
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+use tendermint_light_client::verifier::types::TrustThreshold;
 
 define_error! {
     Error {
@@ -17,5 +19,64 @@ define_error! {
         InvalidGasPrice
             { price: String }
             |e| { format!("invalid gas price: {}", e.price) },
+
+        InvalidMode
+            { reason: String, }
+            |e| {
+                format!("config file specifies invalid mode config, caused by: {0}",
+                    e.reason)
+            },
+
+        DuplicateChains
+            { chain_id: ChainId }
+            |e| {
+                format!("config file has duplicate entry for the chain '{0}'",
+                    e.chain_id)
+            },
+
+        InvalidTrustThreshold
+            {
+                threshold: TrustThreshold,
+                chain_id: ChainId,
+                reason: String
+            }
+            |e| {
+                format!("config file specifies an invalid `trust_threshold` ({0}) for the chain '{1}', caused by: {2}",
+                    e.threshold, e.chain_id, e.reason)
+            },
+
+        DeprecatedGasAdjustment
+            {
+                gas_adjustment: f64,
+                gas_multiplier: f64,
+                chain_id: ChainId,
+            }
+            |e| {
+                format!(
+                    "config file specifies deprecated setting `gas_adjustment = {1}` for the chain '{0}'; \
+                    to get the same behavior, use `gas_multiplier = {2}",
+                    e.chain_id, e.gas_adjustment, e.gas_multiplier
+                )
+            },
+
+        InvalidWalletConfig
+            {
+                chain_id: ChainId,
+                reason: String,
+            }
+            |e| {
+                format!("config file specifies an invalid `wallet` configuration for the chain '{0}', caused by: {1}",
+                    e.chain_id, e.reason)
+            },
+
+        InvalidProfitability
+            {
+                chain_id: ChainId,
+                reason: String,
+            }
+            |e| {
+                format!("config file specifies an invalid `profitability` policy for the chain '{0}', caused by: {1}",
+                    e.chain_id, e.reason)
+            },
     }
 }
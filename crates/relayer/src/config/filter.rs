@@ -13,7 +13,7 @@ use ibc_relayer_types::core::ics24_host::identifier::{ChannelId, PortId};
 use ibc_relayer_types::events::IbcEventType;
 
 /// Represents all the filtering policies for packets.
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct PacketFilter {
     #[serde(flatten)]
     pub channel_policy: ChannelPolicy,
@@ -68,27 +68,154 @@ pub enum ChannelPolicy {
 }
 
 /// Represents the policy used to filter incentivized packets.
-/// Currently only filtering on `recv_fee` is authorized.
-#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+///
+/// The `recv_fee` of a packet must be at least one of the `recv` minimum fees.
+/// If `profitability` is specified, the fees of the packet must also cover the
+/// estimated cost of relaying it, and the `recv` minimum fees may be left empty,
+/// in which case only profitability is checked. Without `profitability`, a policy
+/// with no `recv` minimum fee relays no packet.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub struct FeePolicy {
+    #[serde(default)]
     recv: Vec<MinFee>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    profitability: Option<Profitability>,
 }
 
 impl FeePolicy {
     pub fn new(recv: Vec<MinFee>) -> Self {
-        Self { recv }
+        Self {
+            recv,
+            profitability: None,
+        }
+    }
+
+    pub fn with_profitability(self, profitability: Profitability) -> Self {
+        Self {
+            profitability: Some(profitability),
+            ..self
+        }
+    }
+
+    pub fn profitability(&self) -> Option<&Profitability> {
+        self.profitability.as_ref()
     }
 
     pub fn should_relay(&self, event_type: IbcEventType, fees: &[RawCoin]) -> bool {
         match event_type {
-            IbcEventType::SendPacket => fees
-                .iter()
-                .any(|fee| self.recv.iter().any(|e| e.is_enough(fee))),
+            IbcEventType::SendPacket => {
+                (self.recv.is_empty() && self.profitability.is_some())
+                    || fees
+                        .iter()
+                        .any(|fee| self.recv.iter().any(|e| e.is_enough(fee)))
+            }
             _ => true,
         }
     }
 }
 
+/// Represents the policy used to relay incentivized packets only if they are profitable,
+/// ie. if their fees cover the estimated cost of relaying them, plus a margin.
+///
+/// Fees and costs in different denominations are compared by converting them
+/// to a common unit, using the exchange rate of each denomination.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Profitability {
+    /// Fraction of the cost which the fees must exceed it by, eg. 0.1 for 10%
+    #[serde(default)]
+    margin: f64,
+    /// Value of one unit of each denomination, in a common unit
+    exchange_rates: HashMap<String, f64>,
+}
+
+impl Profitability {
+    pub fn new(margin: f64, exchange_rates: HashMap<String, f64>) -> Self {
+        Self {
+            margin,
+            exchange_rates,
+        }
+    }
+
+    pub fn margin(&self) -> f64 {
+        self.margin
+    }
+
+    pub fn exchange_rates(&self) -> &HashMap<String, f64> {
+        &self.exchange_rates
+    }
+
+    /// Checks whether the given fees cover the given costs plus the margin.
+    ///
+    /// Fees in a denomination without an exchange rate are not accounted for,
+    /// while costs in such a denomination cannot be evaluated.
+    pub fn check(&self, fees: &[RawCoin], costs: &[RawCoin]) -> Result<(), SkipReason> {
+        let fee = fees.iter().filter_map(|fee| self.value(fee)).sum::<f64>();
+
+        let cost = costs
+            .iter()
+            .map(|cost| {
+                self.value(cost)
+                    .ok_or_else(|| SkipReason::MissingExchangeRate(cost.denom.clone()))
+            })
+            .sum::<Result<f64, _>>()?;
+
+        if fee >= cost * (1.0 + self.margin) {
+            Ok(())
+        } else {
+            Err(SkipReason::Unprofitable { fee, cost })
+        }
+    }
+
+    fn value(&self, coin: &RawCoin) -> Option<f64> {
+        let rate = self.exchange_rates.get(&coin.denom)?;
+        let amount = coin.amount.to_string().parse::<f64>().ok()?;
+
+        Some(amount * rate)
+    }
+}
+
+/// The reason why an incentivized packet is not relayed.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SkipReason {
+    /// The `recv_fee` is lower than the configured minimum fees
+    MinFee,
+    /// The cost of relaying the packet could not be estimated
+    EstimationFailed,
+    /// There is no exchange rate for the denomination of a cost
+    MissingExchangeRate(String),
+    /// The fees do not cover the cost of relaying the packet plus the margin
+    Unprofitable { fee: f64, cost: f64 },
+}
+
+impl SkipReason {
+    /// A short identifier of the reason, used in telemetry
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::MinFee => "min_fee",
+            Self::EstimationFailed => "estimation_failed",
+            Self::MissingExchangeRate(_) => "missing_exchange_rate",
+            Self::Unprofitable { .. } => "unprofitable",
+        }
+    }
+}
+
+impl fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MinFee => write!(f, "recv fee is lower than the minimum fees"),
+            Self::EstimationFailed => write!(f, "failed to estimate the cost of relaying"),
+            Self::MissingExchangeRate(denom) => write!(f, "no exchange rate for denom {denom}"),
+            Self::Unprofitable { fee, cost } => {
+                write!(
+                    f,
+                    "fees worth {fee} do not cover the cost of {cost} plus margin"
+                )
+            }
+        }
+    }
+}
+
 /// Represents the minimum fee authorized when filtering.
 /// If no denom is specified, any denom is allowed.
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -605,4 +732,68 @@ mod tests {
         let wildcard = "ica*".parse::<Wildcard>().unwrap();
         assert_eq!(wildcard.to_string(), "ica*".to_string());
     }
+
+    #[test]
+    fn deserialize_fee_policy_with_profitability() {
+        let toml_content = r#"
+            profitability = { margin = 0.1, exchange_rates = { stake = 1.0, uatom = 2.0 } }
+            "#;
+
+        let fp: FeePolicy = toml::from_str(toml_content).expect("could not parse fee policy");
+
+        let profitability = Profitability::new(
+            0.1,
+            HashMap::from([("stake".to_owned(), 1.0), ("uatom".to_owned(), 2.0)]),
+        );
+
+        assert_eq!(fp, FeePolicy::new(vec![]).with_profitability(profitability));
+        assert!(fp.should_relay(IbcEventType::SendPacket, &[]));
+    }
+
+    #[test]
+    fn fee_policy_without_min_fees() {
+        use ibc_relayer_types::applications::transfer::Coin;
+
+        let fees = [Coin::new("stake".to_owned(), 100u64)];
+
+        let fp = FeePolicy::new(vec![]);
+        assert!(!fp.should_relay(IbcEventType::SendPacket, &fees));
+
+        let fp = fp.with_profitability(Profitability::new(
+            0.0,
+            HashMap::from([("stake".to_owned(), 1.0)]),
+        ));
+        assert!(fp.should_relay(IbcEventType::SendPacket, &fees));
+    }
+
+    #[test]
+    fn profitability_check() {
+        use ibc_relayer_types::applications::transfer::Coin;
+
+        let profitability = Profitability::new(
+            0.1,
+            HashMap::from([("stake".to_owned(), 1.0), ("uatom".to_owned(), 2.0)]),
+        );
+
+        let fees = [
+            Coin::new("uatom".to_owned(), 50u64),
+            Coin::new("samoleans".to_owned(), 1000u64),
+        ];
+
+        assert_eq!(
+            profitability.check(&fees, &[Coin::new("stake".to_owned(), 90u64)]),
+            Ok(())
+        );
+        assert_eq!(
+            profitability.check(&fees, &[Coin::new("stake".to_owned(), 91u64)]),
+            Err(SkipReason::Unprofitable {
+                fee: 100.0,
+                cost: 91.0
+            })
+        );
+        assert_eq!(
+            profitability.check(&fees, &[Coin::new("samoleans".to_owned(), 1u64)]),
+            Err(SkipReason::MissingExchangeRate("samoleans".to_owned()))
+        );
+    }
 }
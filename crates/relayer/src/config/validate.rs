@@ -0,0 +1,227 @@
+//! Validation of the semantics of the relayer configuration, beyond what
+//! its deserialization checks.
+
+use alloc::collections::BTreeSet;
+
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+use tendermint_light_client::verifier::types::TrustThreshold;
+
+use crate::config::{ChainConfig, Config, Error, ModeConfig};
+
+#[derive(Clone, Debug)]
+pub enum Diagnostic<E> {
+    Warning(E),
+    Error(E),
+}
+
+/// Method for syntactic validation of the input configuration file.
+pub fn validate_config(config: &Config) -> Result<(), Diagnostic<Error>> {
+    // Check for duplicate chain configuration and invalid trust thresholds
+    let mut unique_chain_ids = BTreeSet::new();
+    for c in config.chains.iter() {
+        let already_present = !unique_chain_ids.insert(c.id.clone());
+        if already_present {
+            return Err(Diagnostic::Error(Error::duplicate_chains(c.id.clone())));
+        }
+
+        validate_trust_threshold(&c.id, c.trust_threshold)?;
+
+        // Validate gas-related settings
+        validate_gas_settings(&c.id, c)?;
+
+        // Validate the wallet balance monitoring settings
+        validate_wallet_settings(&c.id, c)?;
+
+        // Validate the profitability policies of the incentivized packets
+        validate_profitability(&c.id, c)?;
+    }
+
+    // Check for invalid mode config
+    validate_mode(&config.mode)?;
+
+    Ok(())
+}
+
+fn validate_mode(mode: &ModeConfig) -> Result<(), Diagnostic<Error>> {
+    if mode.all_disabled() {
+        return Err(Diagnostic::Warning(Error::invalid_mode(
+            "all operation modes of Hermes are disabled, relayer won't perform any action aside from subscribing to events".to_string(),
+        )));
+    }
+
+    if mode.clients.enabled && !mode.clients.refresh && !mode.clients.misbehaviour {
+        return Err(Diagnostic::Error(Error::invalid_mode(
+            "either `refresh` or `misbehaviour` must be set to true if `clients.enabled` is set to true".to_string(),
+        )));
+    }
+
+    Ok(())
+}
+
+/// Check that the trust threshold is:
+///
+/// a) non-zero
+/// b) greater or equal to 1/3
+/// c) strictly less than 1
+fn validate_trust_threshold(
+    id: &ChainId,
+    trust_threshold: TrustThreshold,
+) -> Result<(), Diagnostic<Error>> {
+    if trust_threshold.denominator() == 0 {
+        return Err(Diagnostic::Error(Error::invalid_trust_threshold(
+            trust_threshold,
+            id.clone(),
+            "trust threshold denominator cannot be zero".to_string(),
+        )));
+    }
+
+    if trust_threshold.numerator() * 3 < trust_threshold.denominator() {
+        return Err(Diagnostic::Error(Error::invalid_trust_threshold(
+            trust_threshold,
+            id.clone(),
+            "trust threshold cannot be < 1/3".to_string(),
+        )));
+    }
+
+    if trust_threshold.numerator() >= trust_threshold.denominator() {
+        return Err(Diagnostic::Error(Error::invalid_trust_threshold(
+            trust_threshold,
+            id.clone(),
+            "trust threshold cannot be >= 1".to_string(),
+        )));
+    }
+
+    Ok(())
+}
+
+fn validate_gas_settings(id: &ChainId, config: &ChainConfig) -> Result<(), Diagnostic<Error>> {
+    // Check that the gas_adjustment option is not set
+    if let Some(gas_adjustment) = config.gas_adjustment {
+        let gas_multiplier = gas_adjustment + 1.0;
+
+        return Err(Diagnostic::Error(Error::deprecated_gas_adjustment(
+            gas_adjustment,
+            gas_multiplier,
+            id.clone(),
+        )));
+    }
+
+    Ok(())
+}
+
+/// Check that the balance to top up the wallets to is set along with the
+/// treasury key funding it, and that it is strictly greater than the minimum balance.
+fn validate_wallet_settings(id: &ChainId, config: &ChainConfig) -> Result<(), Diagnostic<Error>> {
+    let Some(wallet) = &config.wallet else {
+        return Ok(());
+    };
+
+    match (wallet.target_balance, &wallet.treasury_key_name) {
+        (None, None) => Ok(()),
+        (Some(target_balance), Some(_)) if target_balance <= wallet.min_balance => {
+            Err(Diagnostic::Error(Error::invalid_wallet_config(
+                id.clone(),
+                "`target_balance` must be greater than `min_balance`".to_string(),
+            )))
+        }
+        (Some(_), Some(treasury_key_name)) if *treasury_key_name == config.key_name => {
+            Err(Diagnostic::Error(Error::invalid_wallet_config(
+                id.clone(),
+                "`treasury_key_name` must differ from `key_name`".to_string(),
+            )))
+        }
+        (Some(_), Some(_)) => Ok(()),
+        _ => Err(Diagnostic::Error(Error::invalid_wallet_config(
+            id.clone(),
+            "`target_balance` and `treasury_key_name` must be set together".to_string(),
+        ))),
+    }
+}
+
+/// Check that the margin of each profitability policy is not negative,
+/// and that its exchange rates are strictly positive.
+fn validate_profitability(id: &ChainId, config: &ChainConfig) -> Result<(), Diagnostic<Error>> {
+    let policies = config
+        .packet_filter
+        .min_fees
+        .values()
+        .filter_map(|fee_policy| fee_policy.profitability());
+
+    for profitability in policies {
+        let margin = profitability.margin();
+
+        if margin.is_nan() || margin < 0.0 {
+            return Err(Diagnostic::Error(Error::invalid_profitability(
+                id.clone(),
+                format!("`margin` must not be negative, got {margin}"),
+            )));
+        }
+
+        for (denom, rate) in profitability.exchange_rates() {
+            if !rate.is_finite() || *rate <= 0.0 {
+                return Err(Diagnostic::Error(Error::invalid_profitability(
+                    id.clone(),
+                    format!("the exchange rate of `{denom}` must be greater than 0, got {rate}"),
+                )));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::{validate_config, Diagnostic};
+
+    use crate::config::error::ErrorDetail;
+    use crate::config::filter::{FeePolicy, FilterPattern, Profitability};
+    use crate::config::{load, Config};
+
+    fn config_with_profitability(margin: f64, rate: f64) -> Config {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/config/fixtures/relayer_conf_example.toml"
+        );
+
+        let mut config = load(path).unwrap();
+
+        let profitability = Profitability::new(margin, HashMap::from([("stake".to_owned(), rate)]));
+
+        config.chains[0].packet_filter.min_fees.insert(
+            FilterPattern::Exact("channel-0".parse().unwrap()),
+            FeePolicy::new(vec![]).with_profitability(profitability),
+        );
+
+        config
+    }
+
+    fn is_invalid_profitability(config: &Config) -> bool {
+        matches!(
+            validate_config(config),
+            Err(Diagnostic::Error(e)) if matches!(e.detail(), ErrorDetail::InvalidProfitability(_))
+        )
+    }
+
+    #[test]
+    fn profitability_is_validated() {
+        assert!(validate_config(&config_with_profitability(0.1, 2.0)).is_ok());
+        assert!(validate_config(&config_with_profitability(0.0, 2.0)).is_ok());
+
+        assert!(is_invalid_profitability(&config_with_profitability(
+            -0.1, 2.0
+        )));
+        assert!(is_invalid_profitability(&config_with_profitability(
+            f64::NAN,
+            2.0
+        )));
+        assert!(is_invalid_profitability(&config_with_profitability(
+            0.1, 0.0
+        )));
+        assert!(is_invalid_profitability(&config_with_profitability(
+            0.1, -1.0
+        )));
+    }
+}
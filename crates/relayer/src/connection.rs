@@ -19,7 +19,7 @@ use ibc_relayer_types::events::IbcEvent;
 use ibc_relayer_types::timestamp::ZERO_DURATION;
 use ibc_relayer_types::tx_msg::Msg;
 
-use crate::chain::counterparty::connection_state_on_destination;
+use crate::chain::counterparty::{connection_state_on_destination, sync_proof_sequence};
 use crate::chain::handle::ChainHandle;
 use crate::chain::requests::{
     IncludeProof, PageRequest, QueryConnectionRequest, QueryConnectionsRequest, QueryHeight,
@@ -1011,6 +1011,9 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Connection<ChainA, ChainB> {
             .src_chain()
             .query_latest_height()
             .map_err(|e| ConnectionError::chain_query(self.src_chain().id(), e))?;
+        sync_proof_sequence(&self.src_chain(), &self.dst_chain(), self.dst_client_id())
+            .map_err(ConnectionError::relayer)?;
+
         let (client_state, proofs) = self
             .src_chain()
             .build_connection_proofs_and_client_state(
@@ -1153,6 +1156,9 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Connection<ChainA, ChainB> {
             .query_latest_height()
             .map_err(|e| ConnectionError::chain_query(self.src_chain().id(), e))?;
 
+        sync_proof_sequence(&self.src_chain(), &self.dst_chain(), self.dst_client_id())
+            .map_err(ConnectionError::relayer)?;
+
         let (client_state, proofs) = self
             .src_chain()
             .build_connection_proofs_and_client_state(
@@ -1249,6 +1255,9 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Connection<ChainA, ChainB> {
 
         // TODO - check that the src connection is consistent with the confirm options
 
+        sync_proof_sequence(&self.src_chain(), &self.dst_chain(), self.dst_client_id())
+            .map_err(ConnectionError::relayer)?;
+
         let (_, proofs) = self
             .src_chain()
             .build_connection_proofs_and_client_state(
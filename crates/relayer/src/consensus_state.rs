@@ -1,21 +1,27 @@
 use ibc_proto::google::protobuf::Any;
 use ibc_proto::ibc::core::client::v1::ConsensusStateWithHeight;
 use ibc_proto::ibc::lightclients::tendermint::v1::ConsensusState as RawConsensusState;
-#[cfg(test)]
+#[cfg(any(test, feature = "mock"))]
 use ibc_proto::ibc::mock::ConsensusState as RawMockConsensusState;
 use ibc_proto::protobuf::Protobuf;
+use ibc_relayer_types::clients::ics06_solomachine::consensus_state::{
+    ConsensusState as SoloConsensusState, SOLOMACHINE_CONSENSUS_STATE_TYPE_URL,
+};
 use ibc_relayer_types::clients::ics07_tendermint::consensus_state::{
     ConsensusState as TmConsensusState, TENDERMINT_CONSENSUS_STATE_TYPE_URL,
 };
+use ibc_relayer_types::clients::ics08_wasm::consensus_state::{
+    ConsensusState as WasmConsensusState, WASM_CONSENSUS_STATE_TYPE_URL,
+};
 use ibc_relayer_types::core::ics02_client::client_type::ClientType;
 use ibc_relayer_types::core::ics02_client::consensus_state::{
     downcast_consensus_state, ConsensusState,
 };
 use ibc_relayer_types::core::ics02_client::error::Error;
 use ibc_relayer_types::core::ics23_commitment::commitment::CommitmentRoot;
-#[cfg(test)]
+#[cfg(any(test, feature = "mock"))]
 use ibc_relayer_types::mock::consensus_state::MockConsensusState;
-#[cfg(test)]
+#[cfg(any(test, feature = "mock"))]
 use ibc_relayer_types::mock::consensus_state::MOCK_CONSENSUS_STATE_TYPE_URL;
 use ibc_relayer_types::timestamp::Timestamp;
 use ibc_relayer_types::Height;
@@ -26,8 +32,10 @@ use serde::{Deserialize, Serialize};
 #[non_exhaustive]
 pub enum AnyConsensusState {
     Tendermint(TmConsensusState),
+    Solomachine(SoloConsensusState),
+    Wasm(WasmConsensusState),
 
-    #[cfg(test)]
+    #[cfg(any(test, feature = "mock"))]
     Mock(MockConsensusState),
 }
 
@@ -35,8 +43,10 @@ impl AnyConsensusState {
     pub fn timestamp(&self) -> Timestamp {
         match self {
             Self::Tendermint(cs_state) => cs_state.timestamp.into(),
+            Self::Solomachine(cs_state) => cs_state.timestamp,
+            Self::Wasm(cs_state) => cs_state.inner.timestamp.into(),
 
-            #[cfg(test)]
+            #[cfg(any(test, feature = "mock"))]
             Self::Mock(mock_state) => mock_state.timestamp(),
         }
     }
@@ -44,8 +54,10 @@ impl AnyConsensusState {
     pub fn client_type(&self) -> ClientType {
         match self {
             AnyConsensusState::Tendermint(_cs) => ClientType::Tendermint,
+            AnyConsensusState::Solomachine(_cs) => ClientType::Solomachine,
+            AnyConsensusState::Wasm(_cs) => ClientType::Wasm,
 
-            #[cfg(test)]
+            #[cfg(any(test, feature = "mock"))]
             AnyConsensusState::Mock(_cs) => ClientType::Mock,
         }
     }
@@ -65,7 +77,15 @@ impl TryFrom<Any> for AnyConsensusState {
                     .map_err(Error::decode_raw_client_state)?,
             )),
 
-            #[cfg(test)]
+            SOLOMACHINE_CONSENSUS_STATE_TYPE_URL => Ok(AnyConsensusState::Solomachine(
+                SoloConsensusState::try_from(value)?,
+            )),
+
+            WASM_CONSENSUS_STATE_TYPE_URL => Ok(AnyConsensusState::Wasm(
+                WasmConsensusState::try_from(value)?,
+            )),
+
+            #[cfg(any(test, feature = "mock"))]
             MOCK_CONSENSUS_STATE_TYPE_URL => Ok(AnyConsensusState::Mock(
                 Protobuf::<RawMockConsensusState>::decode_vec(&value.value)
                     .map_err(Error::decode_raw_client_state)?,
@@ -83,7 +103,9 @@ impl From<AnyConsensusState> for Any {
                 type_url: TENDERMINT_CONSENSUS_STATE_TYPE_URL.to_string(),
                 value: Protobuf::<RawConsensusState>::encode_vec(&value),
             },
-            #[cfg(test)]
+            AnyConsensusState::Solomachine(value) => value.into(),
+            AnyConsensusState::Wasm(value) => value.into(),
+            #[cfg(any(test, feature = "mock"))]
             AnyConsensusState::Mock(value) => Any {
                 type_url: MOCK_CONSENSUS_STATE_TYPE_URL.to_string(),
                 value: Protobuf::<RawMockConsensusState>::encode_vec(&value),
@@ -92,7 +114,7 @@ impl From<AnyConsensusState> for Any {
     }
 }
 
-#[cfg(test)]
+#[cfg(any(test, feature = "mock"))]
 impl From<MockConsensusState> for AnyConsensusState {
     fn from(cs: MockConsensusState) -> Self {
         Self::Mock(cs)
@@ -105,13 +127,33 @@ impl From<TmConsensusState> for AnyConsensusState {
     }
 }
 
+impl From<SoloConsensusState> for AnyConsensusState {
+    fn from(cs: SoloConsensusState) -> Self {
+        Self::Solomachine(cs)
+    }
+}
+
+impl From<WasmConsensusState> for AnyConsensusState {
+    fn from(cs: WasmConsensusState) -> Self {
+        Self::Wasm(cs)
+    }
+}
+
 impl From<&dyn ConsensusState> for AnyConsensusState {
     fn from(cs: &dyn ConsensusState) -> Self {
-        #[cfg(test)]
+        #[cfg(any(test, feature = "mock"))]
         if let Some(cs) = downcast_consensus_state::<MockConsensusState>(cs) {
             return AnyConsensusState::from(cs.clone());
         }
 
+        if let Some(cs) = downcast_consensus_state::<SoloConsensusState>(cs) {
+            return AnyConsensusState::from(cs.clone());
+        }
+
+        if let Some(cs) = downcast_consensus_state::<WasmConsensusState>(cs) {
+            return AnyConsensusState::from(cs.clone());
+        }
+
         if let Some(cs) = downcast_consensus_state::<TmConsensusState>(cs) {
             AnyConsensusState::from(cs.clone())
         } else {
@@ -165,8 +207,10 @@ impl ConsensusState for AnyConsensusState {
     fn root(&self) -> &CommitmentRoot {
         match self {
             Self::Tendermint(cs_state) => cs_state.root(),
+            Self::Solomachine(cs_state) => cs_state.root(),
+            Self::Wasm(cs_state) => cs_state.root(),
 
-            #[cfg(test)]
+            #[cfg(any(test, feature = "mock"))]
             Self::Mock(mock_state) => mock_state.root(),
         }
     }
@@ -40,6 +40,8 @@ use ibc_relayer_types::{
 
 use crate::chain::cosmos::version;
 use crate::chain::cosmos::BLOCK_MAX_BYTES_MAX_FRACTION;
+use crate::chain::mock::error::Error as MockChainError;
+use crate::chain::solomachine::error::Error as SoloMachineError;
 use crate::config::Error as ConfigError;
 use crate::event::source;
 use crate::keyring::{errors::Error as KeyringError, KeyType};
@@ -503,6 +505,42 @@ define_error! {
                 format!("semantic config validation failed for option `gas_multiplier` of chain '{}', reason: gas multiplier ({}) is smaller than `1.1`, which could trigger gas fee errors in production", e.chain_id, e.gas_multiplier)
             },
 
+        ConfigValidationDynamicGasPriceMaxLow
+            {
+                chain_id: ChainId,
+                max: f64,
+                gas_price: f64,
+            }
+            |e| {
+                format!("semantic config validation failed for option `dynamic_gas_price.max` of chain '{}', reason: maximum gas price ({}) is smaller than the static gas price ({})", e.chain_id, e.max, e.gas_price)
+            },
+
+        ConfigValidationFeeBudget
+            {
+                chain_id: ChainId,
+                reason: String,
+            }
+            |e| {
+                format!("semantic config validation failed for option `fee_budget` of chain '{}', reason: {}", e.chain_id, e.reason)
+            },
+
+        FeeBudgetExhausted
+            {
+                chain_id: ChainId,
+                amount: u64,
+                denom: String,
+                resets_in: Duration,
+            }
+            |e| {
+                format!("fee budget of {}{} for chain '{}' is exhausted, only client updates and misbehaviour are submitted until it is replenished in {:?}", e.amount, e.denom, e.chain_id, e.resets_in)
+            },
+
+        DryRun
+            { chain_id: ChainId }
+            |e| {
+                format!("dry run: not broadcasting transaction to chain '{}'", e.chain_id)
+            },
+
         SdkModuleVersion
             {
                 chain_id: ChainId,
@@ -591,6 +629,18 @@ define_error! {
             { address: String }
             [ TendermintRpcError ]
             |e| { format!("invalid archive node address {}", e.address) },
+
+        InvalidBaseFee
+            { base_fee: String }
+            |e| { format!("invalid base fee returned by the fee market: {}", e.base_fee) },
+
+        MockChain
+            [ MockChainError ]
+            |_| { "mock chain error" },
+
+        SoloMachine
+            [ SoloMachineError ]
+            |_| { "solo machine error" },
     }
 }
 
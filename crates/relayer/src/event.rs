@@ -17,7 +17,10 @@ use ibc_relayer_types::{
     },
     core::ics04_channel::{
         error::Error as ChannelError,
-        events::{self as channel_events, Attributes as ChannelAttributes},
+        events::{
+            self as channel_events, Attributes as ChannelAttributes,
+            UpgradeAttributes as ChannelUpgradeAttributes,
+        },
         packet::Packet,
         timeout::TimeoutHeight,
     },
@@ -110,6 +113,51 @@ pub fn ibc_event_try_from_abci_event(abci_event: &AbciEvent) -> Result<IbcEvent,
             channel_close_confirm_try_from_abci_event(abci_event)
                 .map_err(IbcEventError::channel)?,
         )),
+        Ok(IbcEventType::UpgradeInitChannel) => Ok(IbcEvent::UpgradeInitChannel(
+            channel_upgrade_extract_attributes_from_tx(abci_event)
+                .map(channel_events::UpgradeInit)
+                .map_err(IbcEventError::channel)?,
+        )),
+        Ok(IbcEventType::UpgradeTryChannel) => Ok(IbcEvent::UpgradeTryChannel(
+            channel_upgrade_extract_attributes_from_tx(abci_event)
+                .map(channel_events::UpgradeTry)
+                .map_err(IbcEventError::channel)?,
+        )),
+        Ok(IbcEventType::UpgradeAckChannel) => Ok(IbcEvent::UpgradeAckChannel(
+            channel_upgrade_extract_attributes_from_tx(abci_event)
+                .map(channel_events::UpgradeAck)
+                .map_err(IbcEventError::channel)?,
+        )),
+        Ok(IbcEventType::UpgradeConfirmChannel) => Ok(IbcEvent::UpgradeConfirmChannel(
+            channel_upgrade_extract_attributes_from_tx(abci_event)
+                .map(channel_events::UpgradeConfirm)
+                .map_err(IbcEventError::channel)?,
+        )),
+        Ok(IbcEventType::UpgradeOpenChannel) => Ok(IbcEvent::UpgradeOpenChannel(
+            channel_upgrade_extract_attributes_from_tx(abci_event)
+                .map(channel_events::UpgradeOpen)
+                .map_err(IbcEventError::channel)?,
+        )),
+        Ok(IbcEventType::UpgradeTimeoutChannel) => Ok(IbcEvent::UpgradeTimeoutChannel(
+            channel_upgrade_extract_attributes_from_tx(abci_event)
+                .map(channel_events::UpgradeTimeout)
+                .map_err(IbcEventError::channel)?,
+        )),
+        Ok(IbcEventType::UpgradeCancelChannel) => Ok(IbcEvent::UpgradeCancelChannel(
+            channel_upgrade_extract_attributes_from_tx(abci_event)
+                .map(channel_events::UpgradeCancel)
+                .map_err(IbcEventError::channel)?,
+        )),
+        Ok(IbcEventType::UpgradeErrorChannel) => Ok(IbcEvent::UpgradeErrorChannel(
+            channel_upgrade_extract_attributes_from_tx(abci_event)
+                .map(channel_events::UpgradeError)
+                .map_err(IbcEventError::channel)?,
+        )),
+        Ok(IbcEventType::FlushCompleteChannel) => Ok(IbcEvent::FlushCompleteChannel(
+            channel_upgrade_extract_attributes_from_tx(abci_event)
+                .map(channel_events::FlushComplete)
+                .map_err(IbcEventError::channel)?,
+        )),
         Ok(IbcEventType::SendPacket) => Ok(IbcEvent::SendPacket(
             send_packet_try_from_abci_event(abci_event).map_err(IbcEventError::channel)?,
         )),
@@ -398,6 +446,54 @@ fn channel_extract_attributes_from_tx(
     Ok(attr)
 }
 
+fn channel_upgrade_extract_attributes_from_tx(
+    event: &AbciEvent,
+) -> Result<ChannelUpgradeAttributes, ChannelError> {
+    let mut attr = ChannelUpgradeAttributes::default();
+
+    for tag in &event.attributes {
+        let key = tag.key.as_str();
+        let value = tag.value.as_str();
+        match key {
+            channel_events::PORT_ID_ATTRIBUTE_KEY => {
+                attr.port_id = value.parse().map_err(ChannelError::identifier)?
+            }
+            channel_events::CHANNEL_ID_ATTRIBUTE_KEY => {
+                attr.channel_id = value.parse().map_err(ChannelError::identifier)?;
+            }
+            channel_events::COUNTERPARTY_PORT_ID_ATTRIBUTE_KEY => {
+                attr.counterparty_port_id = value.parse().map_err(ChannelError::identifier)?;
+            }
+            channel_events::COUNTERPARTY_CHANNEL_ID_ATTRIBUTE_KEY => {
+                attr.counterparty_channel_id = value.parse().ok();
+            }
+            channel_events::UPGRADE_SEQUENCE_ATTRIBUTE_KEY => {
+                attr.upgrade_sequence = value
+                    .parse::<u64>()
+                    .map_err(|e| ChannelError::invalid_string_as_sequence(value.to_string(), e))?
+                    .into()
+            }
+            channel_events::UPGRADE_CONNECTION_HOPS_ATTRIBUTE_KEY => {
+                attr.upgrade_connection_hops = value
+                    .split(',')
+                    .filter(|hop| !hop.is_empty())
+                    .map(|hop| hop.parse())
+                    .collect::<Result<_, _>>()
+                    .map_err(ChannelError::identifier)?;
+            }
+            channel_events::UPGRADE_VERSION_ATTRIBUTE_KEY => {
+                attr.upgrade_version = value.to_string().into();
+            }
+            channel_events::UPGRADE_ORDERING_ATTRIBUTE_KEY => {
+                attr.upgrade_ordering = value.parse()?;
+            }
+            _ => {}
+        }
+    }
+
+    Ok(attr)
+}
+
 pub fn extract_packet_and_write_ack_from_tx(
     event: &AbciEvent,
 ) -> Result<(Packet, Vec<u8>), ChannelError> {
@@ -467,6 +563,7 @@ mod tests {
     use ibc_relayer_types::clients::ics07_tendermint::header::test_util::get_dummy_ics07_header;
     use ibc_relayer_types::clients::ics07_tendermint::header::Header as TmHeader;
     use ibc_relayer_types::core::ics02_client::header::downcast_header;
+    use ibc_relayer_types::core::ics04_channel::channel::Ordering;
     use ibc_relayer_types::core::ics04_channel::packet::Sequence;
     use ibc_relayer_types::timestamp::Timestamp;
 
@@ -565,6 +662,55 @@ mod tests {
         }
     }
 
+    #[test]
+    fn channel_upgrade_event_to_abci_event() {
+        let attributes = ChannelUpgradeAttributes {
+            port_id: "transfer".parse().unwrap(),
+            channel_id: "channel-0".parse().unwrap(),
+            counterparty_port_id: "transfer".parse().unwrap(),
+            counterparty_channel_id: Some("channel-1".parse().unwrap()),
+            upgrade_sequence: Sequence::from(1),
+            upgrade_connection_hops: vec!["connection-0".parse().unwrap()],
+            upgrade_version: "{\"fee_version\":\"ics29-1\",\"app_version\":\"ics20-1\"}"
+                .to_string()
+                .into(),
+            upgrade_ordering: Ordering::Unordered,
+        };
+        let mut abci_events = vec![];
+        let upgrade_init = channel_events::UpgradeInit::from(attributes.clone());
+        abci_events.push(AbciEvent::from(upgrade_init.clone()));
+        let upgrade_try = channel_events::UpgradeTry::from(attributes.clone());
+        abci_events.push(AbciEvent::from(upgrade_try.clone()));
+        let upgrade_open = channel_events::UpgradeOpen::from(attributes.clone());
+        abci_events.push(AbciEvent::from(upgrade_open.clone()));
+        let upgrade_cancel = channel_events::UpgradeCancel::from(ChannelUpgradeAttributes {
+            upgrade_connection_hops: vec![],
+            upgrade_version: Default::default(),
+            upgrade_ordering: Default::default(),
+            ..attributes
+        });
+        abci_events.push(AbciEvent::from(upgrade_cancel.clone()));
+        let flush_complete = channel_events::FlushComplete::from(ChannelUpgradeAttributes {
+            upgrade_sequence: Default::default(),
+            ..upgrade_cancel.attributes().clone()
+        });
+        abci_events.push(AbciEvent::from(flush_complete.clone()));
+
+        for abci_event in abci_events {
+            match ibc_event_try_from_abci_event(&abci_event).ok() {
+                Some(ibc_event) => match ibc_event {
+                    IbcEvent::UpgradeInitChannel(e) => assert_eq!(e, upgrade_init),
+                    IbcEvent::UpgradeTryChannel(e) => assert_eq!(e, upgrade_try),
+                    IbcEvent::UpgradeOpenChannel(e) => assert_eq!(e, upgrade_open),
+                    IbcEvent::UpgradeCancelChannel(e) => assert_eq!(e, upgrade_cancel),
+                    IbcEvent::FlushCompleteChannel(e) => assert_eq!(e, flush_complete),
+                    _ => panic!("unexpected event type"),
+                },
+                None => panic!("converted event was wrong"),
+            }
+        }
+    }
+
     #[test]
     fn packet_event_to_abci_event() {
         let packet = Packet {
@@ -45,6 +45,14 @@ define_error! {
             [ TraceError<RpcError> ]
             |_| { "subscription cancelled" },
 
+        ReplayFailed
+            { height: u64, reason: String }
+            |e| { format!("failed to replay the events emitted after height {0}: {1}", e.height, e.reason) },
+
+        EndpointSwitched
+            { address: WebSocketClientUrl }
+            |e| { format!("event source switched over to WebSocket endpoint {0}", e.address) },
+
         Rpc
             [ TraceError<RpcError> ]
             |_| { "RPC error" },
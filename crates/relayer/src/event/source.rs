@@ -6,6 +6,7 @@ use std::{sync::Arc, time::Duration};
 use crossbeam_channel as channel;
 
 use futures::Stream;
+use tendermint::block::Height as BlockHeight;
 use tendermint_rpc::{
     client::CompatMode, event::Event as RpcEvent, Error as RpcError, HttpClient, WebSocketClientUrl,
 };
@@ -53,6 +54,15 @@ impl EventSource {
         Ok((Self::Rpc(source), tx))
     }
 
+    /// Replay the events emitted after the given height on start,
+    /// fetching them with the given RPC client
+    pub fn resume_from(&mut self, height: BlockHeight, rpc_client: HttpClient) {
+        match self {
+            Self::WebSocket(source) => source.resume_from(height, rpc_client),
+            Self::Rpc(source) => source.resume_from(height),
+        }
+    }
+
     pub fn run(self) {
         match self {
             Self::WebSocket(source) => source.run(),
@@ -96,12 +106,27 @@ impl TxEventSourceCmd {
         let subscription = rx.recv().map_err(|_| Error::channel_recv_failed())?;
         Ok(subscription)
     }
+
+    /// Switch the event source over to another full node
+    pub fn switch_endpoint(
+        &self,
+        rpc_client: HttpClient,
+        ws_url: Option<WebSocketClientUrl>,
+    ) -> Result<()> {
+        self.0
+            .send(EventSourceCmd::SwitchEndpoint { rpc_client, ws_url })
+            .map_err(|_| Error::channel_send_failed())
+    }
 }
 
 #[derive(Debug)]
 pub enum EventSourceCmd {
     Shutdown,
     Subscribe(channel::Sender<Subscription>),
+    SwitchEndpoint {
+        rpc_client: HttpClient,
+        ws_url: Option<WebSocketClientUrl>,
+    },
 }
 
 // TODO: These are SDK specific, should be eventually moved.
@@ -56,6 +56,9 @@ pub struct EventSource {
 
     /// Last fetched block height
     last_fetched_height: BlockHeight,
+
+    /// Height after which to replay the events on start, if any
+    resume_height: Option<BlockHeight>,
 }
 
 impl EventSource {
@@ -76,11 +79,18 @@ impl EventSource {
             event_bus,
             rx_cmd,
             last_fetched_height: BlockHeight::from(0_u32),
+            resume_height: None,
         };
 
         Ok((source, TxEventSourceCmd(tx_cmd)))
     }
 
+    /// Replay the events emitted after the given height on start,
+    /// before fetching the events of the new blocks
+    pub fn resume_from(&mut self, height: BlockHeight) {
+        self.resume_height = Some(height);
+    }
+
     pub fn run(mut self) {
         let _span = error_span!("event_source.rpc", chain.id = %self.chain_id).entered();
 
@@ -91,9 +101,23 @@ impl EventSource {
         rt.block_on(async {
             let mut backoff = poll_backoff(self.poll_interval);
 
-            // Initialize the latest fetched height
-            if let Ok(latest_height) = latest_height(&self.rpc_client).await {
-                self.last_fetched_height = latest_height;
+            let replayed = match self.resume_height.take() {
+                Some(height) => {
+                    // Wait for the first subscriber, so that the replayed events are not lost
+                    if let Next::Abort = self.wait_for_subscriber() {
+                        return;
+                    }
+
+                    self.replay_since(height).await
+                }
+                None => false,
+            };
+
+            // Initialize the latest fetched height, unless it was set while replaying events
+            if !replayed {
+                if let Ok(latest_height) = latest_height(&self.rpc_client).await {
+                    self.last_fetched_height = latest_height;
+                }
             }
 
             // Continuously run the event loop, so that when it aborts
@@ -175,18 +199,65 @@ impl EventSource {
         Ok(Next::Continue)
     }
 
+    /// Replay the events emitted after the given height, up to the latest height.
+    /// Returns whether the events were replayed.
+    async fn replay_since(&mut self, height: BlockHeight) -> bool {
+        match fetch_batches_since(&self.rpc_client, &self.chain_id, height).await {
+            Ok((batches, latest_height)) => {
+                debug!("replaying the events emitted from height {height} to {latest_height}");
+
+                for batch in batches {
+                    self.broadcast_batch(batch);
+                }
+
+                self.last_fetched_height = latest_height;
+
+                true
+            }
+            Err(e) => {
+                error!("failed to replay the events emitted after height {height}: {e}");
+
+                self.event_bus.broadcast(Arc::new(Err(Error::replay_failed(
+                    height.value(),
+                    e.to_string(),
+                ))));
+
+                false
+            }
+        }
+    }
+
+    /// Block until a command is received, and process it.
+    fn wait_for_subscriber(&mut self) -> Next {
+        match self.rx_cmd.recv() {
+            Ok(cmd) => self.process_cmd(cmd),
+            Err(_) => Next::Abort,
+        }
+    }
+
     /// Process any pending commands, if any.
     fn try_process_cmd(&mut self) -> Next {
-        if let Ok(cmd) = self.rx_cmd.try_recv() {
-            match cmd {
-                EventSourceCmd::Shutdown => return Next::Abort,
+        match self.rx_cmd.try_recv() {
+            Ok(cmd) => self.process_cmd(cmd),
+            Err(_) => Next::Continue,
+        }
+    }
 
-                EventSourceCmd::Subscribe(tx) => {
-                    if let Err(e) = tx.send(self.event_bus.subscribe()) {
-                        error!("failed to send back subscription: {e}");
-                    }
+    fn process_cmd(&mut self, cmd: EventSourceCmd) -> Next {
+        match cmd {
+            EventSourceCmd::Shutdown => return Next::Abort,
+
+            EventSourceCmd::Subscribe(tx) => {
+                if let Err(e) = tx.send(self.event_bus.subscribe()) {
+                    error!("failed to send back subscription: {e}");
                 }
             }
+
+            // Events are fetched block by block, so the new node
+            // picks up where the previous one left off.
+            EventSourceCmd::SwitchEndpoint { rpc_client, .. } => {
+                self.rpc_client = rpc_client;
+            }
         }
 
         Next::Continue
@@ -353,6 +424,26 @@ async fn fetch_all_events(
     Ok(events)
 }
 
+/// Fetch the events emitted after the given height, up to the latest height of the chain,
+/// and return them along with the latest height.
+pub(super) async fn fetch_batches_since(
+    rpc_client: &HttpClient,
+    chain_id: &ChainId,
+    height: BlockHeight,
+) -> Result<(Vec<EventBatch>, BlockHeight)> {
+    let latest_height = latest_height(rpc_client).await?;
+
+    let mut batches = Vec::new();
+
+    for height in HeightRangeInclusive::new(height.increment(), latest_height) {
+        if let Some(batch) = collect_events(rpc_client, chain_id, height).await? {
+            batches.push(batch);
+        }
+    }
+
+    Ok((batches, latest_height))
+}
+
 async fn latest_height(rpc_client: &HttpClient) -> Result<BlockHeight> {
     rpc_client
         .abci_info()
@@ -14,8 +14,9 @@ use tokio::task::JoinHandle;
 use tokio::{runtime::Runtime as TokioRuntime, sync::mpsc};
 use tracing::{debug, error, info, instrument, trace};
 
+use tendermint::block::Height as BlockHeight;
 use tendermint_rpc::{
-    client::CompatMode, event::Event as RpcEvent, query::Query, SubscriptionClient,
+    client::CompatMode, event::Event as RpcEvent, query::Query, HttpClient, SubscriptionClient,
     WebSocketClient, WebSocketClientDriver, WebSocketClientUrl,
 };
 
@@ -86,6 +87,8 @@ pub struct EventSource {
     subscriptions: Box<SubscriptionStream>,
     /// Tokio runtime
     rt: Arc<TokioRuntime>,
+    /// Height after which to replay the events on start, and the RPC client to fetch them with
+    resume: Option<(BlockHeight, HttpClient)>,
 }
 
 impl EventSource {
@@ -132,6 +135,7 @@ impl EventSource {
             ws_url,
             rpc_compat,
             subscriptions: Box::new(futures::stream::empty()),
+            resume: None,
         };
 
         Ok((source, TxEventSourceCmd(tx_cmd)))
@@ -226,33 +230,93 @@ impl EventSource {
         skip_all,
         fields(chain = %self.chain_id)
     )]
-    fn reconnect(&mut self) {
+    fn reconnect(&mut self) -> Next {
         let result = retry_with_index(retry_strategy::default(), |_| {
+            // Process any shutdown command, or switch over to another endpoint,
+            // which may have been requested while the previous one was unreachable.
+            if let Next::Abort = self.try_process_cmd() {
+                return RetryResult::Err(Next::Abort);
+            }
+
             // Try to reconnect
             if let Err(e) = self.try_reconnect() {
                 trace!("error when reconnecting: {}", e);
-                return RetryResult::Retry(());
+                return RetryResult::Retry(Next::Continue);
             }
 
             // Try to resubscribe
             if let Err(e) = self.try_resubscribe() {
                 trace!("error when resubscribing: {}", e);
-                return RetryResult::Retry(());
+                return RetryResult::Retry(Next::Continue);
             }
 
             RetryResult::Ok(())
         });
 
         match result {
-            Ok(()) => info!(
-                "successfully reconnected to WebSocket endpoint {}",
-                self.ws_url
-            ),
-            Err(e) => error!(
-                "failed to reconnect to {} after {} retries",
-                self.ws_url, e.tries
-            ),
+            Ok(()) => {
+                info!(
+                    "successfully reconnected to WebSocket endpoint {}",
+                    self.ws_url
+                );
+
+                Next::Continue
+            }
+            Err(e) => {
+                if let Next::Abort = e.error {
+                    return Next::Abort;
+                }
+
+                error!(
+                    "failed to reconnect to {} after {} retries",
+                    self.ws_url, e.tries
+                );
+
+                Next::Continue
+            }
+        }
+    }
+
+    /// Replay the events emitted after the given height on start, fetching them with
+    /// the given RPC client, before processing the events received over WebSocket
+    pub fn resume_from(&mut self, height: BlockHeight, rpc_client: HttpClient) {
+        self.resume = Some((height, rpc_client));
+    }
+
+    /// Replay the events emitted since the height to resume from, if any
+    fn replay_missed_events(&mut self) -> Next {
+        let (height, rpc_client) = match self.resume.take() {
+            Some(resume) => resume,
+            None => return Next::Continue,
+        };
+
+        // Wait for the first subscriber, so that the replayed events are not lost
+        if let Next::Abort = self.wait_for_subscriber() {
+            return Next::Abort;
+        }
+
+        let result = self.rt.block_on(super::rpc::fetch_batches_since(
+            &rpc_client,
+            &self.chain_id,
+            height,
+        ));
+
+        match result {
+            Ok((batches, latest_height)) => {
+                debug!("replaying the events emitted from height {height} to {latest_height}");
+
+                for batch in batches {
+                    self.broadcast_batch(batch);
+                }
+            }
+            Err(e) => {
+                error!("failed to replay the events emitted after height {height}: {e}");
+
+                self.propagate_error(Error::replay_failed(height.value(), e.to_string()));
+            }
         }
+
+        Next::Continue
     }
 
     /// Event source loop
@@ -269,19 +333,24 @@ impl EventSource {
         // work around double borrow
         let rt = self.rt.clone();
 
+        let mut next = self.replay_missed_events();
+
         // Continuously run the event loop, so that when it aborts
         // because of WebSocket client restart, we pick up the work again.
         loop {
-            match rt.block_on(self.run_loop()) {
-                Next::Continue => continue,
+            match next {
+                Next::Continue => (),
                 Next::Abort => break,
                 Next::Reconnect => {
                     telemetry!(ws_reconnect, &self.chain_id);
-                    self.reconnect();
 
-                    continue;
+                    if let Next::Abort = self.reconnect() {
+                        break;
+                    }
                 }
             }
+
+            next = rt.block_on(self.run_loop());
         }
 
         debug!("event source is shutting down");
@@ -308,8 +377,9 @@ impl EventSource {
 
         loop {
             // Process any shutdown or subscription commands before we start doing any work.
-            if let Next::Abort = self.try_process_cmd() {
-                return Next::Abort;
+            match self.try_process_cmd() {
+                Next::Continue => (),
+                next => return next,
             }
 
             let result = tokio::select! {
@@ -325,8 +395,9 @@ impl EventSource {
             //
             // It also allows subscribers to receive the latest event batch even if they
             // subscribe while the batch being fetched.
-            if let Next::Abort = self.try_process_cmd() {
-                return Next::Abort;
+            match self.try_process_cmd() {
+                Next::Continue => (),
+                next => return next,
             }
 
             match result {
@@ -376,18 +447,48 @@ impl EventSource {
         self.event_bus.broadcast(Arc::new(Ok(batch)));
     }
 
+    /// Block until a command is received, and process it.
+    fn wait_for_subscriber(&mut self) -> Next {
+        match self.rx_cmd.recv() {
+            Ok(cmd) => self.process_cmd(cmd),
+            Err(_) => Next::Abort,
+        }
+    }
+
     /// Process a pending command, if any.
     fn try_process_cmd(&mut self) -> Next {
-        if let Ok(cmd) = self.rx_cmd.try_recv() {
-            match cmd {
-                EventSourceCmd::Shutdown => return Next::Abort,
+        match self.rx_cmd.try_recv() {
+            Ok(cmd) => self.process_cmd(cmd),
+            Err(_) => Next::Continue,
+        }
+    }
 
-                EventSourceCmd::Subscribe(tx) => {
-                    if let Err(e) = tx.send(self.event_bus.subscribe()) {
-                        error!("failed to send back subscription: {e}");
-                    }
+    fn process_cmd(&mut self, cmd: EventSourceCmd) -> Next {
+        match cmd {
+            EventSourceCmd::Shutdown => return Next::Abort,
+
+            EventSourceCmd::Subscribe(tx) => {
+                if let Err(e) = tx.send(self.event_bus.subscribe()) {
+                    error!("failed to send back subscription: {e}");
                 }
             }
+
+            EventSourceCmd::SwitchEndpoint {
+                ws_url: Some(ws_url),
+                ..
+            } => {
+                info!("switching over to WebSocket endpoint {ws_url}");
+
+                self.ws_url = ws_url.clone();
+
+                // Let the subscribers know that events may have been
+                // missed while switching over to the new endpoint.
+                self.propagate_error(Error::endpoint_switched(ws_url));
+
+                return Next::Reconnect;
+            }
+
+            EventSourceCmd::SwitchEndpoint { ws_url: None, .. } => {}
         }
 
         Next::Continue
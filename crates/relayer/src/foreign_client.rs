@@ -13,7 +13,11 @@ use itertools::Itertools;
 use tracing::{debug, error, info, instrument, trace, warn};
 
 use flex_error::define_error;
+use ibc_relayer_types::clients::ics08_wasm::client_message::ClientMessage as WasmClientMessage;
+use ibc_relayer_types::clients::ics08_wasm::client_state::ClientState as WasmClientState;
+use ibc_relayer_types::clients::ics08_wasm::consensus_state::ConsensusState as WasmConsensusState;
 use ibc_relayer_types::core::ics02_client::client_state::ClientState;
+use ibc_relayer_types::core::ics02_client::client_type::ClientType;
 use ibc_relayer_types::core::ics02_client::error::Error as ClientError;
 use ibc_relayer_types::core::ics02_client::events::UpdateClient;
 use ibc_relayer_types::core::ics02_client::header::Header;
@@ -310,6 +314,9 @@ pub struct CreateOptions {
     pub max_clock_drift: Option<Duration>,
     pub trusting_period: Option<Duration>,
     pub trust_threshold: Option<TrustThreshold>,
+    /// The hash of the code of a Wasm light client contract, which wraps
+    /// the client to create in a `08-wasm` client when specified.
+    pub wasm_code_hash: Option<Vec<u8>>,
 }
 
 /// Captures the diagnostic of verifying whether a certain
@@ -594,6 +601,7 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
                 e,
             )
         })?;
+        let wasm_code_hash = options.wasm_code_hash.clone();
         let settings = ClientSettings::for_create_command(options, &src_config, &dst_config);
 
         let client_state: AnyClientState = self
@@ -622,6 +630,29 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
                 )
             })?;
 
+        let (client_state, consensus_state) = match wasm_code_hash {
+            Some(code_hash) => match (client_state, consensus_state) {
+                (
+                    AnyClientState::Tendermint(client_state),
+                    AnyConsensusState::Tendermint(consensus_state),
+                ) => (
+                    WasmClientState::new(code_hash, client_state).into(),
+                    WasmConsensusState::new(consensus_state).into(),
+                ),
+                (client_state, _) => {
+                    return Err(ForeignClientError::client_create(
+                        self.src_chain.id(),
+                        "only Tendermint clients can be wrapped in a Wasm client".to_string(),
+                        RelayerError::client_type_mismatch(
+                            ClientType::Tendermint,
+                            client_state.client_type(),
+                        ),
+                    ))
+                }
+            },
+            None => (client_state, consensus_state),
+        };
+
         //TODO Get acct_prefix
         let msg = MsgCreateClient::new(client_state.into(), consensus_state.into(), signer)
             .map_err(ForeignClientError::client)?;
@@ -712,6 +743,17 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
             ));
         }
 
+        // Solo machine clients never expire, and keep their only
+        // consensus state in the client state rather than at a height
+        if let AnyClientState::Solomachine(_) = client_state {
+            return Ok((client_state, None));
+        }
+
+        // The localhost client reads the state of its host, hence it has no consensus states
+        if let AnyClientState::Localhost(_) = client_state {
+            return Ok((client_state, None));
+        }
+
         match self
             .check_consensus_state_trusting_period(&client_state, &client_state.latest_height())?
         {
@@ -1114,7 +1156,40 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
         // Get the latest client state on destination.
         let (client_state, _) = self.validated_client_state()?;
 
+        // Wasm clients are updated with the headers of the client they wrap,
+        // themselves wrapped in a client message for the contract.
+        let is_wasm = matches!(client_state, AnyClientState::Wasm(_));
+        let client_state = client_state.unwrap_wasm();
+
+        let wrap_header = |header: AnyHeader| -> Any {
+            if is_wasm {
+                WasmClientMessage::new(header.into()).into()
+            } else {
+                header.into()
+            }
+        };
+
+        // The localhost client is kept up to date by its host at every block.
+        if let AnyClientState::Localhost(_) = client_state {
+            debug!("skipping update: the localhost client is updated by its host");
+
+            return Ok(vec![]);
+        }
+
+        // Solo machine clients verify proofs against the current key of the solo machine,
+        // hence they need no update to a given height. A header is built only when a trusted
+        // height is given explicitly, which rotates the client to the key and diversifier
+        // currently configured for the solo machine.
+        let is_solomachine = matches!(client_state, AnyClientState::Solomachine(_));
+
+        if is_solomachine && maybe_trusted_height.is_none() {
+            debug!("skipping update: solo machine clients are not updated to a target height");
+
+            return Ok(vec![]);
+        }
+
         let trusted_height = match maybe_trusted_height {
+            Some(_) if is_solomachine => client_state.latest_height(),
             Some(trusted_height) => {
                 self.validate_trusted_height(trusted_height, &client_state)?;
                 trusted_height
@@ -1146,7 +1221,7 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
             }
         }
 
-        if trusted_height >= target_height {
+        if !is_solomachine && trusted_height >= target_height {
             warn!(
                 "skipping update: trusted height ({}) >= chain target height ({})",
                 trusted_height, target_height
@@ -1185,7 +1260,7 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
             );
 
             msgs.push(MsgUpdateClient {
-                header: header.into(),
+                header: wrap_header(header),
                 client_id: self.id.clone(),
                 signer: signer.clone(),
             });
@@ -1198,7 +1273,7 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
         );
 
         msgs.push(MsgUpdateClient {
-            header: header.into(),
+            header: wrap_header(header),
             signer,
             client_id: self.id.clone(),
         });
@@ -1497,6 +1572,21 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
                 })?
         };
 
+        // Evidence of solo machine misbehaviour consists of two signatures for the same
+        // sequence, which the relayer does not collect
+        if let AnyClientState::Solomachine(_) = client_state {
+            return Ok(None);
+        }
+
+        // The localhost client has no headers, hence cannot misbehave
+        if let AnyClientState::Localhost(_) = client_state {
+            return Ok(None);
+        }
+
+        // Wasm clients are checked as the client they wrap, whose headers
+        // are decoded from the client messages of the update events.
+        let client_state = client_state.unwrap_wasm();
+
         let consensus_state_heights = if let Some(event) = update {
             vec![event.consensus_height()]
         } else {
@@ -1643,12 +1733,40 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
             )
         })?;
 
+        let (client_state, _) = self
+            .dst_chain()
+            .query_client_state(
+                QueryClientStateRequest {
+                    client_id: self.id().clone(),
+                    height: QueryHeight::Latest,
+                },
+                IncludeProof::No,
+            )
+            .map_err(|e| {
+                ForeignClientError::misbehaviour(
+                    format!("failed querying client state on dst chain {}", self.id),
+                    e,
+                )
+            })?;
+
+        // The evidence submitted to a Wasm client is wrapped in client
+        // messages for the contract, as the headers of its updates are.
+        let is_wasm = matches!(client_state, AnyClientState::Wasm(_));
+
+        let wrap = |message: Any| -> Any {
+            if is_wasm {
+                WasmClientMessage::new(message).into()
+            } else {
+                message
+            }
+        };
+
         let mut msgs = vec![];
 
         for header in evidence.supporting_headers {
             msgs.push(
                 MsgUpdateClient {
-                    header: header.into(),
+                    header: wrap(header.into()),
                     client_id: self.id.clone(),
                     signer: signer.clone(),
                 }
@@ -1658,7 +1776,7 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
 
         msgs.push(
             MsgSubmitMisbehaviour {
-                misbehaviour: evidence.misbehaviour.into(),
+                misbehaviour: wrap(evidence.misbehaviour.into()),
                 client_id: self.id.clone(),
                 signer,
             }
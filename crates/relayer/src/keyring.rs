@@ -1,7 +1,11 @@
+pub mod encrypted;
 pub mod errors;
+pub mod remote;
 pub use any_signing_key_pair::AnySigningKeyPair;
 pub use ed25519_key_pair::Ed25519KeyPair;
+pub use encrypted::Encrypted;
 pub use key_type::KeyType;
+pub use remote::{Remote, RemoteSigner};
 pub use secp256k1_key_pair::Secp256k1KeyPair;
 pub use signing_key_pair::{SigningKeyPair, SigningKeyPairSized};
 
@@ -21,7 +25,10 @@ use std::path::PathBuf;
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 use serde::{Deserialize, Serialize};
 
-use crate::{chain::ChainType, config::ChainConfig};
+use crate::{
+    chain::ChainType,
+    config::{AddressType, ChainConfig, RemoteSignerConfig},
+};
 use errors::Error;
 
 pub const KEYSTORE_DEFAULT_FOLDER: &str = ".hermes/keys/";
@@ -185,6 +192,18 @@ impl<S: SigningKeyPairSized> KeyStore<S> for Test {
 pub enum Store {
     Memory,
     Test,
+    Encrypted,
+}
+
+impl Store {
+    /// The store to use when managing keys outside of a running relayer,
+    /// eg. from the CLI, where keys added to an in-memory store would be lost.
+    pub fn persistent(self) -> Self {
+        match self {
+            Self::Memory | Self::Test => Self::Test,
+            Self::Encrypted => Self::Encrypted,
+        }
+    }
 }
 
 impl Default for Store {
@@ -197,6 +216,8 @@ impl Default for Store {
 pub enum KeyRing<S> {
     Memory(Memory<S>),
     Test(Test),
+    Encrypted(Encrypted),
+    Remote(Remote),
 }
 
 impl<S: SigningKeyPairSized> KeyRing<S> {
@@ -210,22 +231,58 @@ impl<S: SigningKeyPairSized> KeyRing<S> {
             Store::Memory => Ok(Self::Memory(Memory::new(account_prefix.to_string()))),
 
             Store::Test => {
-                let keys_folder = disk_store_path(chain_id.as_str(), ks_folder)?;
-
-                // Create keys folder if it does not exist
-                fs::create_dir_all(&keys_folder).map_err(|e| {
-                    Error::key_file_io(
-                        keys_folder.display().to_string(),
-                        "failed to create keys folder".to_string(),
-                        e,
-                    )
-                })?;
+                let keys_folder =
+                    create_disk_store(chain_id.as_str(), KEYSTORE_DISK_BACKEND, ks_folder)?;
 
                 Ok(Self::Test(Test::new(
                     account_prefix.to_string(),
                     keys_folder,
                 )))
             }
+
+            Store::Encrypted => {
+                let keys_folder = create_disk_store(
+                    chain_id.as_str(),
+                    encrypted::KEYSTORE_ENCRYPTED_BACKEND,
+                    ks_folder,
+                )?;
+
+                Ok(Self::Encrypted(Encrypted::new(
+                    account_prefix.to_string(),
+                    keys_folder,
+                )))
+            }
+        }
+    }
+
+    /// Create a key ring whose keys are held by the given remote signer.
+    pub fn new_remote(
+        signer: RemoteSignerConfig,
+        account_prefix: &str,
+        address_type: &AddressType,
+    ) -> Self {
+        Self::Remote(Remote::new(
+            account_prefix.to_string(),
+            address_type.clone(),
+            signer,
+        ))
+    }
+
+    /// Create the key ring configured for the given chain, ie. backed by its remote signer
+    /// if one is configured, or by the given key store otherwise.
+    pub fn from_config(store: Store, config: &ChainConfig) -> Result<Self, Error> {
+        match &config.remote_signer {
+            Some(signer) => Ok(Self::new_remote(
+                signer.clone(),
+                &config.account_prefix,
+                &config.address_type,
+            )),
+            None => Self::new(
+                store,
+                &config.account_prefix,
+                &config.id,
+                &config.key_store_folder,
+            ),
         }
     }
 
@@ -233,6 +290,8 @@ impl<S: SigningKeyPairSized> KeyRing<S> {
         match self {
             Self::Memory(m) => m.get_key(key_name),
             Self::Test(d) => d.get_key(key_name),
+            Self::Encrypted(e) => e.get_key(key_name),
+            Self::Remote(r) => r.get_key(key_name),
         }
     }
 
@@ -240,6 +299,8 @@ impl<S: SigningKeyPairSized> KeyRing<S> {
         match self {
             Self::Memory(m) => m.add_key(key_name, key_entry),
             Self::Test(d) => d.add_key(key_name, key_entry),
+            Self::Encrypted(e) => e.add_key(key_name, key_entry),
+            Self::Remote(r) => r.add_key(key_name, key_entry),
         }
     }
 
@@ -247,6 +308,8 @@ impl<S: SigningKeyPairSized> KeyRing<S> {
         match self {
             Self::Memory(m) => m.remove_key(key_name),
             Self::Test(d) => <Test as KeyStore<S>>::remove_key(d, key_name),
+            Self::Encrypted(e) => <Encrypted as KeyStore<S>>::remove_key(e, key_name),
+            Self::Remote(r) => <Remote as KeyStore<S>>::remove_key(r, key_name),
         }
     }
 
@@ -254,6 +317,8 @@ impl<S: SigningKeyPairSized> KeyRing<S> {
         match self {
             Self::Memory(m) => m.keys(),
             Self::Test(d) => d.keys(),
+            Self::Encrypted(e) => e.keys(),
+            Self::Remote(r) => r.keys(),
         }
     }
 
@@ -261,6 +326,8 @@ impl<S: SigningKeyPairSized> KeyRing<S> {
         match self {
             Self::Memory(m) => &m.account_prefix,
             Self::Test(d) => &d.account_prefix,
+            Self::Encrypted(e) => &e.account_prefix,
+            Self::Remote(r) => &r.account_prefix,
         }
     }
 }
@@ -289,12 +356,10 @@ impl KeyRing<Ed25519KeyPair> {
 
 pub fn list_keys(config: &ChainConfig) -> Result<Vec<(String, AnySigningKeyPair)>, Error> {
     let keys = match config.r#type {
-        ChainType::CosmosSdk => {
-            let keyring = KeyRing::new_secp256k1(
-                Store::Test,
-                &config.account_prefix,
-                &config.id,
-                &config.key_store_folder,
+        ChainType::CosmosSdk | ChainType::Mock | ChainType::SoloMachine => {
+            let keyring = KeyRing::<Secp256k1KeyPair>::from_config(
+                config.key_store_type.persistent(),
+                config,
             )?;
             keyring
                 .keys()?
@@ -306,7 +371,73 @@ pub fn list_keys(config: &ChainConfig) -> Result<Vec<(String, AnySigningKeyPair)
     Ok(keys)
 }
 
-fn disk_store_path(folder_name: &str, keystore_folder: &Option<PathBuf>) -> Result<PathBuf, Error> {
+/// Migrate the keys stored in plaintext in the `keyring-test` store of the given chain
+/// to its encrypted store, optionally removing the plaintext key files afterwards.
+///
+/// Returns the names of the keys which were migrated.
+pub fn migrate_to_encrypted(
+    config: &ChainConfig,
+    remove_plaintext: bool,
+) -> Result<Vec<String>, Error> {
+    let migrated = match config.r#type {
+        ChainType::CosmosSdk | ChainType::Mock | ChainType::SoloMachine => {
+            let mut source = Test::new(
+                config.account_prefix.clone(),
+                disk_store_path(
+                    config.id.as_str(),
+                    KEYSTORE_DISK_BACKEND,
+                    &config.key_store_folder,
+                )?,
+            );
+
+            let mut target = Encrypted::new(
+                config.account_prefix.clone(),
+                create_disk_store(
+                    config.id.as_str(),
+                    encrypted::KEYSTORE_ENCRYPTED_BACKEND,
+                    &config.key_store_folder,
+                )?,
+            );
+
+            let migrated = target.import_from::<Secp256k1KeyPair>(&source)?;
+
+            if remove_plaintext {
+                for key_name in &migrated {
+                    <Test as KeyStore<Secp256k1KeyPair>>::remove_key(&mut source, key_name)?;
+                }
+            }
+
+            migrated
+        }
+    };
+
+    Ok(migrated)
+}
+
+fn create_disk_store(
+    folder_name: &str,
+    backend: &str,
+    keystore_folder: &Option<PathBuf>,
+) -> Result<PathBuf, Error> {
+    let keys_folder = disk_store_path(folder_name, backend, keystore_folder)?;
+
+    // Create keys folder if it does not exist
+    fs::create_dir_all(&keys_folder).map_err(|e| {
+        Error::key_file_io(
+            keys_folder.display().to_string(),
+            "failed to create keys folder".to_string(),
+            e,
+        )
+    })?;
+
+    Ok(keys_folder)
+}
+
+fn disk_store_path(
+    folder_name: &str,
+    backend: &str,
+    keystore_folder: &Option<PathBuf>,
+) -> Result<PathBuf, Error> {
     let ks_folder = match keystore_folder {
         Some(folder) => folder.to_owned(),
         None => {
@@ -315,7 +446,7 @@ fn disk_store_path(folder_name: &str, keystore_folder: &Option<PathBuf>) -> Resu
         }
     };
 
-    let folder = ks_folder.join(folder_name).join(KEYSTORE_DISK_BACKEND);
+    let folder = ks_folder.join(folder_name).join(backend);
 
     Ok(folder)
 }
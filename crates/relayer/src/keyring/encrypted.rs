@@ -0,0 +1,465 @@
+//! On-disk key store which encrypts key material at rest.
+//!
+//! Each key is stored in its own JSON file, next to the parameters needed to
+//! decrypt it. The encryption key is derived from a passphrase with scrypt,
+//! and the serialized key pair is sealed with ChaCha20-Poly1305.
+//!
+//! The passphrase is resolved once per process, either explicitly through
+//! [`unlock`], or from the [`PASSPHRASE_ENV_VAR`] environment variable.
+
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use once_cell::sync::OnceCell;
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+use super::errors::Error;
+use super::{KeyStore, SigningKeyPairSized, Test, KEYSTORE_FILE_EXTENSION};
+use crate::util::lock::{LockExt, RwArc};
+
+pub const KEYSTORE_ENCRYPTED_BACKEND: &str = "keyring-encrypted";
+
+/// Environment variable from which the key store passphrase is read
+/// when it has not been set explicitly with [`unlock`].
+pub const PASSPHRASE_ENV_VAR: &str = "HERMES_KEYRING_PASSPHRASE";
+
+const ENCRYPTED_KEY_FILE_VERSION: u8 = 1;
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+
+/// Default scrypt cost parameters, as recommended for interactive logins.
+const SCRYPT_LOG_N: u8 = 15;
+const SCRYPT_R: u32 = 8;
+const SCRYPT_P: u32 = 1;
+
+static PASSPHRASE: OnceCell<String> = OnceCell::new();
+
+/// Set the passphrase used to unlock encrypted key stores for the rest of
+/// the lifetime of the process.
+///
+/// Returns an error if a passphrase was already set.
+pub fn unlock(passphrase: String) -> Result<(), Error> {
+    if passphrase.is_empty() {
+        return Err(Error::empty_passphrase());
+    }
+
+    PASSPHRASE
+        .set(passphrase)
+        .map_err(|_| Error::passphrase_already_set())
+}
+
+/// Whether or not a passphrase is available, either because it was set
+/// with [`unlock`] or because the [`PASSPHRASE_ENV_VAR`] is defined.
+pub fn is_unlocked() -> bool {
+    PASSPHRASE.get().is_some() || std::env::var(PASSPHRASE_ENV_VAR).is_ok()
+}
+
+fn passphrase() -> Result<String, Error> {
+    if let Some(passphrase) = PASSPHRASE.get() {
+        return Ok(passphrase.clone());
+    }
+
+    match std::env::var(PASSPHRASE_ENV_VAR) {
+        Ok(passphrase) if !passphrase.is_empty() => Ok(passphrase),
+        Ok(_) => Err(Error::empty_passphrase()),
+        Err(_) => Err(Error::passphrase_unavailable(
+            PASSPHRASE_ENV_VAR.to_string(),
+        )),
+    }
+}
+
+/// Parameters used to derive the encryption key from the passphrase.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum KdfParams {
+    Scrypt {
+        log_n: u8,
+        r: u32,
+        p: u32,
+        #[serde(with = "hex::serde")]
+        salt: Vec<u8>,
+    },
+}
+
+impl KdfParams {
+    fn derive_key(&self, passphrase: &str) -> Result<Key, Error> {
+        match self {
+            Self::Scrypt { log_n, r, p, salt } => {
+                let params = scrypt::Params::new(*log_n, *r, *p, 32)
+                    .map_err(|e| Error::key_derivation(e.to_string()))?;
+
+                let mut key = Key::default();
+                scrypt::scrypt(passphrase.as_bytes(), salt, &params, &mut key)
+                    .map_err(|e| Error::key_derivation(e.to_string()))?;
+
+                Ok(key)
+            }
+        }
+    }
+}
+
+/// JSON file holding an encrypted key pair
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct EncryptedKeyFile {
+    version: u8,
+    kdf: KdfParams,
+    #[serde(with = "hex::serde")]
+    nonce: Vec<u8>,
+    #[serde(with = "hex::serde")]
+    ciphertext: Vec<u8>,
+}
+
+impl EncryptedKeyFile {
+    fn seal(passphrase: &str, plaintext: &[u8]) -> Result<Self, Error> {
+        let mut salt = vec![0; SALT_LEN];
+        OsRng.fill_bytes(&mut salt);
+
+        let mut nonce = vec![0; NONCE_LEN];
+        OsRng.fill_bytes(&mut nonce);
+
+        let kdf = KdfParams::Scrypt {
+            log_n: SCRYPT_LOG_N,
+            r: SCRYPT_R,
+            p: SCRYPT_P,
+            salt,
+        };
+
+        let cipher = ChaCha20Poly1305::new(&kdf.derive_key(passphrase)?);
+        let ciphertext = cipher
+            .encrypt(Nonce::from_slice(&nonce), plaintext)
+            .map_err(|_| Error::key_encryption())?;
+
+        Ok(Self {
+            version: ENCRYPTED_KEY_FILE_VERSION,
+            kdf,
+            nonce,
+            ciphertext,
+        })
+    }
+
+    fn open(&self, passphrase: &str, file_path: &Path) -> Result<Vec<u8>, Error> {
+        if self.version != ENCRYPTED_KEY_FILE_VERSION || self.nonce.len() != NONCE_LEN {
+            return Err(Error::unsupported_key_file_format(
+                file_path.display().to_string(),
+            ));
+        }
+
+        let cipher = ChaCha20Poly1305::new(&self.kdf.derive_key(passphrase)?);
+
+        cipher
+            .decrypt(Nonce::from_slice(&self.nonce), self.ciphertext.as_slice())
+            .map_err(|_| Error::key_decryption(file_path.display().to_string()))
+    }
+}
+
+/// Read and decrypt the key file at the given path
+fn read_key_file(key_file: &Path, passphrase: &str) -> Result<Zeroizing<Vec<u8>>, Error> {
+    let file = File::open(key_file).map_err(|e| {
+        Error::key_file_io(
+            key_file.display().to_string(),
+            "failed to open file".to_string(),
+            e,
+        )
+    })?;
+
+    let encrypted: EncryptedKeyFile = serde_json::from_reader(file)
+        .map_err(|e| Error::key_file_decode(format!("{}", key_file.display()), e))?;
+
+    Ok(Zeroizing::new(encrypted.open(passphrase, key_file)?))
+}
+
+/// Key store which keeps every key encrypted on disk
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Encrypted {
+    pub(super) account_prefix: String,
+    store: PathBuf,
+
+    /// Keys which were already decrypted, by key file path, so that the
+    /// expensive key derivation only runs once per key.
+    /// The plaintext is wiped from memory when it is evicted or dropped.
+    #[serde(skip)]
+    decrypted: RwArc<HashMap<PathBuf, Zeroizing<Vec<u8>>>>,
+}
+
+impl Encrypted {
+    pub fn new(account_prefix: String, store: PathBuf) -> Self {
+        Self {
+            account_prefix,
+            store,
+            decrypted: Default::default(),
+        }
+    }
+
+    fn key_file_path(&self, key_name: &str) -> PathBuf {
+        let mut key_file = self.store.join(key_name);
+        key_file.set_extension(KEYSTORE_FILE_EXTENSION);
+        key_file
+    }
+
+    /// Encrypt and copy every key of the given plaintext `Test` key store
+    /// into this key store, skipping keys which already exist in it.
+    ///
+    /// Returns the names of the keys which were imported.
+    pub fn import_from<S: SigningKeyPairSized>(
+        &mut self,
+        source: &Test,
+    ) -> Result<Vec<String>, Error> {
+        let keys: Vec<(String, S)> = source.keys()?;
+
+        let mut imported = Vec::with_capacity(keys.len());
+
+        for (key_name, key_pair) in keys {
+            if self.key_file_path(&key_name).exists() {
+                continue;
+            }
+
+            self.add_key(&key_name, key_pair)?;
+            imported.push(key_name);
+        }
+
+        Ok(imported)
+    }
+}
+
+impl<S: SigningKeyPairSized> KeyStore<S> for Encrypted {
+    fn get_key(&self, key_name: &str) -> Result<S, Error> {
+        let key_file = self.key_file_path(key_name);
+
+        if !key_file.as_path().exists() {
+            return Err(Error::key_file_not_found(format!("{}", key_file.display())));
+        }
+
+        if let Some(plaintext) = self.decrypted.acquire_read().get(&key_file) {
+            return serde_json::from_slice(plaintext)
+                .map_err(|e| Error::key_file_decode(format!("{}", key_file.display()), e));
+        }
+
+        let plaintext = read_key_file(&key_file, &passphrase()?)?;
+
+        let key_entry = serde_json::from_slice(&plaintext)
+            .map_err(|e| Error::key_file_decode(format!("{}", key_file.display()), e))?;
+
+        self.decrypted.acquire_write().insert(key_file, plaintext);
+
+        Ok(key_entry)
+    }
+
+    fn add_key(&mut self, key_name: &str, key_entry: S) -> Result<(), Error> {
+        let filename = self.key_file_path(key_name);
+        let file_path = filename.display().to_string();
+
+        let plaintext = Zeroizing::new(
+            serde_json::to_vec(&key_entry)
+                .map_err(|e| Error::key_file_encode(file_path.clone(), e))?,
+        );
+
+        let encrypted = EncryptedKeyFile::seal(&passphrase()?, &plaintext)?;
+
+        let file = File::create(&filename).map_err(|e| {
+            Error::key_file_io(file_path.clone(), "failed to create file".to_string(), e)
+        })?;
+
+        serde_json::to_writer_pretty(file, &encrypted)
+            .map_err(|e| Error::key_file_encode(file_path, e))?;
+
+        self.decrypted.acquire_write().insert(filename, plaintext);
+
+        Ok(())
+    }
+
+    fn remove_key(&mut self, key_name: &str) -> Result<(), Error> {
+        let filename = self.key_file_path(key_name);
+
+        fs::remove_file(filename.clone())
+            .map_err(|e| Error::remove_io_fail(filename.display().to_string(), e))?;
+
+        self.decrypted.acquire_write().remove(&filename);
+
+        Ok(())
+    }
+
+    fn keys(&self) -> Result<Vec<(String, S)>, Error> {
+        let dir = fs::read_dir(&self.store).map_err(|e| {
+            Error::key_file_io(
+                self.store.display().to_string(),
+                "failed to list keys".to_string(),
+                e,
+            )
+        })?;
+
+        let ext = OsStr::new(KEYSTORE_FILE_EXTENSION);
+
+        dir.into_iter()
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension() == Some(ext))
+            .flat_map(|path| path.file_stem().map(OsStr::to_owned))
+            .flat_map(|stem| stem.to_str().map(ToString::to_string))
+            .map(|name| self.get_key(&name).map(|key| (name, key)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{read_key_file, unlock, Encrypted, EncryptedKeyFile};
+
+    use std::ffi::OsStr;
+    use std::fs;
+    use std::path::{Path, PathBuf};
+
+    use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+    use crate::config;
+    use crate::keyring::errors::ErrorDetail;
+    use crate::keyring::{
+        migrate_to_encrypted, KeyStore, Secp256k1KeyPair, SigningKeyPair, Test,
+        KEYSTORE_DISK_BACKEND, KEYSTORE_FILE_EXTENSION,
+    };
+
+    const COSMOS_HD_PATH: &str = "m/44'/118'/0'/0/0";
+    const KEY_NAME: &str = "relayer";
+    const PASSPHRASE: &str = "correct horse";
+
+    fn key_pair() -> Secp256k1KeyPair {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/config/fixtures/relayer-seed.json"
+        );
+        let seed_file_content = fs::read_to_string(path).unwrap();
+        let hd_path = COSMOS_HD_PATH.parse().unwrap();
+
+        Secp256k1KeyPair::from_seed_file(&seed_file_content, &hd_path).unwrap()
+    }
+
+    /// The passphrase is set once for the whole process, hence
+    /// every test unlocking the key store uses the same one.
+    fn unlock_store() {
+        let _ = unlock(PASSPHRASE.to_string());
+    }
+
+    fn temp_store_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("hermes-keyring-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn key_files_in(dir: &Path) -> usize {
+        fs::read_dir(dir)
+            .unwrap()
+            .flatten()
+            .filter(|entry| entry.path().extension() == Some(OsStr::new(KEYSTORE_FILE_EXTENSION)))
+            .count()
+    }
+
+    #[test]
+    fn seal_and_open_roundtrip() {
+        let plaintext = b"{\"private_key\":\"deadbeef\"}";
+
+        let sealed = EncryptedKeyFile::seal("correct horse", plaintext).unwrap();
+        assert_ne!(sealed.ciphertext, plaintext.to_vec());
+
+        let opened = sealed
+            .open("correct horse", Path::new("test.json"))
+            .unwrap();
+        assert_eq!(opened, plaintext.to_vec());
+    }
+
+    #[test]
+    fn open_with_wrong_passphrase_fails() {
+        let sealed = EncryptedKeyFile::seal("correct horse", b"secret").unwrap();
+
+        assert!(sealed
+            .open("battery staple", Path::new("test.json"))
+            .is_err());
+    }
+
+    #[test]
+    fn encrypted_key_file_serde_roundtrip() {
+        let sealed = EncryptedKeyFile::seal("correct horse", b"secret").unwrap();
+
+        let json = serde_json::to_string(&sealed).unwrap();
+        let decoded: EncryptedKeyFile = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(decoded, sealed);
+    }
+
+    #[test]
+    fn key_store_roundtrip() {
+        unlock_store();
+
+        let dir = temp_store_dir("roundtrip");
+        let key_pair = key_pair();
+
+        let mut store = Encrypted::new("cosmos".to_string(), dir.clone());
+        store.add_key(KEY_NAME, key_pair.clone()).unwrap();
+
+        // The key file holds no plaintext key material
+        let key_file = dir.join(KEY_NAME).with_extension(KEYSTORE_FILE_EXTENSION);
+        let contents = fs::read_to_string(&key_file).unwrap();
+        assert!(!contents.contains("private_key"));
+
+        // A store reopened on the same folder has no decrypted keys cached
+        let reopened = Encrypted::new("cosmos".to_string(), dir.clone());
+        let key: Secp256k1KeyPair = reopened.get_key(KEY_NAME).unwrap();
+        assert_eq!(key.account(), key_pair.account());
+
+        let result = read_key_file(&key_file, "battery staple");
+        let e = result.expect_err("decrypting with the wrong passphrase should fail");
+        assert!(matches!(e.detail(), ErrorDetail::KeyDecryption(_)));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn migrate_plaintext_keys() {
+        unlock_store();
+
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/config/fixtures/relayer_conf_example.toml"
+        );
+        let config = config::load(path).expect("could not parse config");
+        let mut chain_config = config
+            .find_chain(&ChainId::from_string("chain_A"))
+            .unwrap()
+            .clone();
+
+        let dir = temp_store_dir("migrate");
+        chain_config.key_store_folder = Some(dir.clone());
+
+        let plaintext_dir = dir
+            .join(chain_config.id.as_str())
+            .join(KEYSTORE_DISK_BACKEND);
+        fs::create_dir_all(&plaintext_dir).unwrap();
+
+        let key_pair = key_pair();
+        let mut plaintext = Test::new("cosmos".to_string(), plaintext_dir.clone());
+        plaintext.add_key(KEY_NAME, key_pair.clone()).unwrap();
+
+        let migrated = migrate_to_encrypted(&chain_config, true).unwrap();
+        assert_eq!(migrated, vec![KEY_NAME.to_string()]);
+
+        // No plaintext key file is left behind
+        assert_eq!(key_files_in(&plaintext_dir), 0);
+
+        let encrypted_dir = dir
+            .join(chain_config.id.as_str())
+            .join(super::KEYSTORE_ENCRYPTED_BACKEND);
+        assert_eq!(key_files_in(&encrypted_dir), 1);
+
+        let store = Encrypted::new("cosmos".to_string(), encrypted_dir);
+        let key: Secp256k1KeyPair = store.get_key(KEY_NAME).unwrap();
+        assert_eq!(key.account(), key_pair.account());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -95,6 +95,65 @@ define_error! {
         HomeLocationUnavailable
             |_| { "home location is unavailable" },
 
+        EmptyPassphrase
+            |_| { "the key store passphrase cannot be empty" },
+
+        PassphraseAlreadySet
+            |_| { "the key store passphrase has already been set" },
+
+        PassphraseUnavailable
+            { env_var: String }
+            |e| {
+                format!("no passphrase available to unlock the encrypted key store, set it with the '{}' environment variable",
+                    e.env_var)
+            },
+
+        KeyDerivation
+            { reason: String }
+            |e| { format!("failed to derive encryption key from passphrase: {}", e.reason) },
+
+        KeyEncryption
+            |_| { "failed to encrypt key" },
+
+        KeyDecryption
+            { file_path: String }
+            |e| {
+                format!("failed to decrypt key file at '{}', is the passphrase correct?",
+                    e.file_path)
+            },
+
+        RemoteSigner
+            {
+                url: String,
+                reason: String,
+            }
+            |e| {
+                format!("error communicating with the remote signer at '{}': {}",
+                    e.url, e.reason)
+            },
+
+        RemoteSignerUnsupported
+            { key_type: KeyType }
+            |e| { format!("remote signers are not supported for {} keys", e.key_type) },
+
+        RemoteKeyStoreReadOnly
+            |_| { "keys held by a remote signer can only be managed on the signer itself" },
+
+        InvalidRemotePublicKey
+            { key_name: String }
+            |e| { format!("the remote signer returned an invalid public key for key '{}'", e.key_name) },
+
+        InvalidRemoteSignature
+            { key_name: String }
+            |e| { format!("the remote signer returned an invalid signature for key '{}'", e.key_name) },
+
+        UnsupportedKeyFileFormat
+            { file_path: String }
+            |e| {
+                format!("unsupported encrypted key file format at '{}'",
+                    e.file_path)
+            },
+
         RemoveIoFail
             {
                 file_path: String,
@@ -0,0 +1,537 @@
+//! Key store which delegates signing to an external signer,
+//! so that private keys never have to be loaded by the relayer.
+//!
+//! The signer is expected to expose the following HTTP endpoints:
+//!
+//! - `GET /keys`: list the names of the available keys,
+//!   as `{ "keys": ["<name>", ...] }`
+//! - `GET /keys/<name>`: get the public key of a key,
+//!   as `{ "public_key": "<hex>" }`
+//! - `POST /keys/<name>/sign`: sign `{ "message": "<hex>", "hash": "sha256" | "keccak256" }`
+//!   with a key, responding with `{ "signature": "<hex>" }`
+//!
+//! Key names are percent-encoded in the paths, and the `auth_token` of the signer
+//! configuration, if any, is sent as a bearer token in the `Authorization` header.
+
+use alloc::sync::Arc;
+use std::thread;
+
+use crossbeam_channel::Sender;
+use once_cell::sync::OnceCell;
+use reqwest::blocking::Client;
+use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use tendermint_rpc::Url;
+
+use super::errors::Error;
+use super::{KeyStore, SigningKeyPairSized};
+use crate::config::{AddressType, RemoteSignerConfig};
+
+/// The hash function the signer must apply to the message before signing it
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HashFunction {
+    Sha256,
+    Keccak256,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ListKeysResponse {
+    pub keys: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PublicKeyResponse {
+    pub public_key: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SignRequest {
+    pub message: String,
+    pub hash: HashFunction,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SignResponse {
+    pub signature: String,
+}
+
+/// A request to the signer, run with the HTTP client of the signer,
+/// or with the reason why the client could not be built
+type Job = Box<dyn FnOnce(Result<&Client, &str>) + Send>;
+
+/// HTTP client of a remote signer, shared by all the keys of a key store.
+///
+/// Signing happens from within async code, where the blocking HTTP client can
+/// neither be created, used nor dropped, so the client lives on its own thread,
+/// spawned on the first request, which runs the requests of all the keys in turn.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(from = "RemoteSignerConfig", into = "RemoteSignerConfig")]
+pub struct SignerClient {
+    config: RemoteSignerConfig,
+    jobs: Arc<OnceCell<Sender<Job>>>,
+}
+
+impl SignerClient {
+    pub fn new(config: RemoteSignerConfig) -> Self {
+        Self {
+            config,
+            jobs: Arc::new(OnceCell::new()),
+        }
+    }
+
+    pub fn url(&self) -> &Url {
+        &self.config.url
+    }
+
+    /// List the names of the keys held by the signer
+    pub fn list_keys(&self) -> Result<Vec<String>, Error> {
+        let response: ListKeysResponse = self.request(&["keys"], None::<()>)?;
+
+        Ok(response.keys)
+    }
+
+    /// Send a request to the endpoint made of the given path segments, which are
+    /// percent-encoded, with the given JSON body if any, and decode the JSON response
+    fn request<Req, Res>(&self, path: &[&str], body: Option<Req>) -> Result<Res, Error>
+    where
+        Req: Serialize + Send + 'static,
+        Res: DeserializeOwned + Send + 'static,
+    {
+        let url = self.config.url.to_string();
+        let endpoint = endpoint(&self.config.url, path)?;
+
+        let (reply_sender, reply_receiver) = crossbeam_channel::bounded(1);
+
+        let job: Job = Box::new(move |client| {
+            let result = client.map_err(str::to_string).and_then(|client| {
+                let request = match &body {
+                    Some(body) => client.post(endpoint).json(body),
+                    None => client.get(endpoint),
+                };
+
+                request
+                    .send()
+                    .and_then(|response| response.error_for_status())
+                    .and_then(|response| response.json())
+                    .map_err(|e| e.to_string())
+            });
+
+            // The caller may have given up on the response
+            let _ = reply_sender.send(result);
+        });
+
+        self.jobs()
+            .send(job)
+            .map_err(|_| "the signer client has stopped".to_string())
+            .and_then(|()| {
+                reply_receiver
+                    .recv()
+                    .unwrap_or_else(|_| Err("the signer client has stopped".to_string()))
+            })
+            .map_err(|reason| Error::remote_signer(url, reason))
+    }
+
+    /// The queue of requests of the thread running the HTTP client, spawned on first use
+    fn jobs(&self) -> &Sender<Job> {
+        self.jobs.get_or_init(|| {
+            let (sender, receiver) = crossbeam_channel::unbounded::<Job>();
+            let config = self.config.clone();
+
+            // Should the thread fail to spawn, the receiver is dropped, and the requests fail
+            let _ = thread::Builder::new()
+                .name("remote-signer".to_string())
+                .spawn(move || {
+                    let client = build_client(&config).map_err(|e| e.to_string());
+
+                    for job in receiver {
+                        job(client.as_ref().map_err(String::as_str));
+                    }
+                });
+
+            sender
+        })
+    }
+}
+
+impl From<RemoteSignerConfig> for SignerClient {
+    fn from(config: RemoteSignerConfig) -> Self {
+        Self::new(config)
+    }
+}
+
+impl From<SignerClient> for RemoteSignerConfig {
+    fn from(client: SignerClient) -> Self {
+        client.config
+    }
+}
+
+impl PartialEq for SignerClient {
+    fn eq(&self, other: &Self) -> bool {
+        self.config == other.config
+    }
+}
+
+impl Eq for SignerClient {}
+
+fn build_client(config: &RemoteSignerConfig) -> Result<Client, String> {
+    let mut headers = HeaderMap::new();
+
+    if let Some(auth_token) = &config.auth_token {
+        let mut value = HeaderValue::from_str(&format!("Bearer {auth_token}"))
+            .map_err(|e| format!("invalid `auth_token`: {e}"))?;
+        value.set_sensitive(true);
+
+        headers.insert(AUTHORIZATION, value);
+    }
+
+    Client::builder()
+        .timeout(config.timeout)
+        .default_headers(headers)
+        .build()
+        .map_err(|e| e.to_string())
+}
+
+/// The URL of the signer endpoint made of the given path segments, appended
+/// to the path of the signer URL once percent-encoded
+fn endpoint(url: &Url, path: &[&str]) -> Result<reqwest::Url, Error> {
+    let to_error = |reason: String| Error::remote_signer(url.to_string(), reason);
+
+    let mut endpoint =
+        reqwest::Url::parse(&url.to_string()).map_err(|e| to_error(e.to_string()))?;
+
+    endpoint
+        .path_segments_mut()
+        .map_err(|()| to_error("the signer URL cannot have a path".to_string()))?
+        .pop_if_empty()
+        .extend(path);
+
+    Ok(endpoint)
+}
+
+/// Handle to a single key held by a remote signer
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct RemoteSigner {
+    url: Url,
+    key_name: String,
+    #[serde(skip)]
+    client: SignerClient,
+}
+
+impl RemoteSigner {
+    pub fn new(client: &SignerClient, key_name: &str) -> Self {
+        Self {
+            url: client.url().clone(),
+            key_name: key_name.to_string(),
+            client: client.clone(),
+        }
+    }
+
+    pub fn key_name(&self) -> &str {
+        &self.key_name
+    }
+
+    /// Fetch the encoded public key of this key from the signer
+    pub fn public_key(&self) -> Result<Vec<u8>, Error> {
+        let response: PublicKeyResponse =
+            self.client.request(&["keys", &self.key_name], None::<()>)?;
+
+        decode_hex(&self.url, "public key", &response.public_key)
+    }
+
+    /// Ask the signer to sign the given message with this key
+    pub fn sign(&self, message: &[u8], hash: HashFunction) -> Result<Vec<u8>, Error> {
+        let sign_request = SignRequest {
+            message: hex::encode(message),
+            hash,
+        };
+
+        let response: SignResponse = self
+            .client
+            .request(&["keys", &self.key_name, "sign"], Some(sign_request))?;
+
+        decode_hex(&self.url, "signature", &response.signature)
+    }
+}
+
+fn decode_hex(url: &Url, field: &str, value: &str) -> Result<Vec<u8>, Error> {
+    hex::decode(value).map_err(|e| {
+        Error::remote_signer(url.to_string(), format!("invalid hex-encoded {field}: {e}"))
+    })
+}
+
+/// Key store backed by a remote signer.
+///
+/// Keys can only be managed on the signer itself,
+/// adding or removing them through this store is not supported.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Remote {
+    pub(super) account_prefix: String,
+    address_type: AddressType,
+    signer: SignerClient,
+}
+
+impl Remote {
+    pub fn new(
+        account_prefix: String,
+        address_type: AddressType,
+        signer: RemoteSignerConfig,
+    ) -> Self {
+        Self {
+            account_prefix,
+            address_type,
+            signer: SignerClient::new(signer),
+        }
+    }
+}
+
+impl<S: SigningKeyPairSized> KeyStore<S> for Remote {
+    fn get_key(&self, key_name: &str) -> Result<S, Error> {
+        S::from_remote_signer(
+            RemoteSigner::new(&self.signer, key_name),
+            &self.address_type,
+            &self.account_prefix,
+        )
+    }
+
+    fn add_key(&mut self, _key_name: &str, _key_entry: S) -> Result<(), Error> {
+        Err(Error::remote_key_store_read_only())
+    }
+
+    fn remove_key(&mut self, _key_name: &str) -> Result<(), Error> {
+        Err(Error::remote_key_store_read_only())
+    }
+
+    fn keys(&self) -> Result<Vec<(String, S)>, Error> {
+        self.signer
+            .list_keys()?
+            .into_iter()
+            .map(|name| self.get_key(&name).map(|key| (name, key)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        endpoint, HashFunction, ListKeysResponse, PublicKeyResponse, Remote, SignRequest,
+        SignResponse,
+    };
+
+    use core::time::Duration;
+    use std::fs;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::{TcpListener, TcpStream};
+    use std::thread;
+
+    use ibc_proto::google::protobuf::Any;
+    use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+    use crate::chain::cosmos::encode::sign_tx;
+    use crate::chain::cosmos::gas::gas_amount_to_fee;
+    use crate::chain::cosmos::types::account::{
+        Account, AccountAddress, AccountNumber, AccountSequence,
+    };
+    use crate::chain::cosmos::types::config::TxConfig;
+    use crate::chain::cosmos::types::gas::max_gas_from_config;
+    use crate::config::{self, AddressType, RemoteSignerConfig};
+    use crate::keyring::{KeyStore, Secp256k1KeyPair, SigningKeyPair};
+
+    const COSMOS_HD_PATH: &str = "m/44'/118'/0'/0/0";
+    const KEY_NAME: &str = "relayer";
+    const AUTH_TOKEN: &str = "secret";
+
+    fn local_key_pair() -> Secp256k1KeyPair {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/config/fixtures/relayer-seed.json"
+        );
+        let seed_file_content = fs::read_to_string(path).unwrap();
+        let hd_path = COSMOS_HD_PATH.parse().unwrap();
+
+        Secp256k1KeyPair::from_seed_file(&seed_file_content, &hd_path).unwrap()
+    }
+
+    /// Test double for a remote signer, holding a single key in memory
+    /// and serving the signer HTTP API on a local port to the holders of `AUTH_TOKEN`.
+    fn spawn_signer(key_pair: Secp256k1KeyPair) -> RemoteSignerConfig {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                handle_request(stream, &key_pair);
+            }
+        });
+
+        RemoteSignerConfig {
+            url: format!("http://127.0.0.1:{port}").parse().unwrap(),
+            timeout: Duration::from_secs(5),
+            auth_token: Some(AUTH_TOKEN.to_string()),
+        }
+    }
+
+    fn handle_request(mut stream: TcpStream, key_pair: &Secp256k1KeyPair) {
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line).unwrap();
+
+        let mut content_length = 0;
+        let mut authorized = false;
+        loop {
+            let mut header = String::new();
+            reader.read_line(&mut header).unwrap();
+
+            if header.trim().is_empty() {
+                break;
+            }
+
+            if let Some((name, value)) = header.split_once(':') {
+                if name.eq_ignore_ascii_case("content-length") {
+                    content_length = value.trim().parse().unwrap();
+                }
+
+                if name.eq_ignore_ascii_case("authorization") {
+                    authorized = value.trim() == format!("Bearer {AUTH_TOKEN}");
+                }
+            }
+        }
+
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body).unwrap();
+
+        if !authorized {
+            write!(
+                stream,
+                "HTTP/1.1 401 Unauthorized\r\nContent-Length: 0\r\n\r\n"
+            )
+            .unwrap();
+            return;
+        }
+
+        let path = request_line.split_whitespace().nth(1).unwrap();
+        let public_key_path = format!("/keys/{KEY_NAME}");
+        let sign_path = format!("/keys/{KEY_NAME}/sign");
+
+        let response = match path {
+            "/keys" => serde_json::to_string(&ListKeysResponse {
+                keys: vec![KEY_NAME.to_string()],
+            }),
+            p if p == public_key_path => serde_json::to_string(&PublicKeyResponse {
+                public_key: hex::encode(key_pair.public_key.serialize()),
+            }),
+            p if p == sign_path => {
+                let request: SignRequest = serde_json::from_slice(&body).unwrap();
+                assert_eq!(request.hash, HashFunction::Sha256);
+
+                let message = hex::decode(request.message).unwrap();
+                let signature = key_pair.sign(&message).unwrap();
+
+                serde_json::to_string(&SignResponse {
+                    signature: hex::encode(signature),
+                })
+            }
+            _ => {
+                write!(
+                    stream,
+                    "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\n\r\n"
+                )
+                .unwrap();
+                return;
+            }
+        }
+        .unwrap();
+
+        write!(
+            stream,
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+            response.len(),
+            response
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn remote_key_matches_local_key() {
+        let local = local_key_pair();
+        let signer = spawn_signer(local.clone());
+        let mut store = Remote::new("cosmos".to_string(), AddressType::Cosmos, signer);
+
+        let remote: Secp256k1KeyPair = store.get_key(KEY_NAME).unwrap();
+        assert_eq!(remote.account(), local.account());
+        assert_eq!(remote.public_key, local.public_key);
+
+        let keys: Vec<(String, Secp256k1KeyPair)> = store.keys().unwrap();
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].0, KEY_NAME);
+
+        assert!(KeyStore::<Secp256k1KeyPair>::remove_key(&mut store, KEY_NAME).is_err());
+    }
+
+    #[test]
+    fn unknown_remote_key_fails() {
+        let signer = spawn_signer(local_key_pair());
+        let store = Remote::new("cosmos".to_string(), AddressType::Cosmos, signer);
+
+        assert!(KeyStore::<Secp256k1KeyPair>::get_key(&store, "unknown").is_err());
+    }
+
+    #[test]
+    fn remote_signer_requires_auth_token() {
+        let mut signer = spawn_signer(local_key_pair());
+        signer.auth_token = Some("wrong".to_string());
+
+        let store = Remote::new("cosmos".to_string(), AddressType::Cosmos, signer);
+
+        assert!(KeyStore::<Secp256k1KeyPair>::get_key(&store, KEY_NAME).is_err());
+    }
+
+    #[test]
+    fn key_names_are_percent_encoded() {
+        let url = "http://127.0.0.1:8200/signer/".parse().unwrap();
+        let endpoint = endpoint(&url, &["keys", "my key/../1", "sign"]).unwrap();
+
+        assert_eq!(
+            endpoint.as_str(),
+            "http://127.0.0.1:8200/signer/keys/my%20key%2F..%2F1/sign"
+        );
+    }
+
+    #[test]
+    fn remote_signed_tx_matches_locally_signed_tx() {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/config/fixtures/relayer_conf_example.toml"
+        );
+        let config = config::load(path).expect("could not parse config");
+        let chain_config = config.find_chain(&ChainId::from_string("chain_A")).unwrap();
+        let tx_config = TxConfig::try_from(chain_config).expect("could not obtain tx config");
+
+        let local = local_key_pair();
+        let signer = spawn_signer(local.clone());
+        let store = Remote::new("cosmos".to_string(), AddressType::Cosmos, signer);
+        let remote: Secp256k1KeyPair = store.get_key(KEY_NAME).unwrap();
+
+        let account = Account {
+            address: AccountAddress::new(local.account()),
+            number: AccountNumber::new(12),
+            sequence: AccountSequence::new(34),
+        };
+        let messages = vec![Any {
+            type_url: "/example.Foo".into(),
+            value: vec![1, 2, 3],
+        }];
+        let fee = gas_amount_to_fee(&tx_config.gas_config, max_gas_from_config(chain_config));
+        let memo = Default::default();
+
+        let locally_signed = sign_tx(&tx_config, &local, &account, &memo, &messages, &fee).unwrap();
+        let remotely_signed =
+            sign_tx(&tx_config, &remote, &account, &memo, &messages, &fee).unwrap();
+
+        // Signatures are deterministic (RFC 6979), so both must be identical
+        assert_eq!(remotely_signed.signatures, locally_signed.signatures);
+        assert_eq!(remotely_signed.auth_info, locally_signed.auth_info);
+    }
+}
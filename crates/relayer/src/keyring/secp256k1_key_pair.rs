@@ -9,8 +9,8 @@ use digest::Digest;
 use generic_array::{typenum::U32, GenericArray};
 use hdpath::StandardHDPath;
 use ripemd::Ripemd160;
-use secp256k1::{Message, PublicKey, Secp256k1, SecretKey};
-use serde::{Deserialize, Serialize};
+use secp256k1::{ecdsa::Signature, Message, PublicKey, Secp256k1, SecretKey};
+use serde::{Deserialize, Serialize, Serializer};
 use sha2::Sha256;
 use strum::{EnumIter, IntoEnumIterator};
 
@@ -18,6 +18,7 @@ use super::{
     errors::Error,
     key_utils::{decode_bech32, encode_bech32, keccak256_hash},
     pub_key::EncodedPubKey,
+    remote::{HashFunction, RemoteSigner},
     KeyFile, KeyType, SigningKeyPair,
 };
 use crate::config::AddressType;
@@ -132,16 +133,57 @@ fn encode_address(account_prefix: &str, address: &[u8]) -> Result<String, Error>
 // This uses `VersionedKeyPair` to allow for backwards-
 // compatible deserialization.
 // /!\ /!\ /!\ /!\ /!\ /!\ /!\ /!\ /!\ /!\ /!\ /!\ /!\ /!\ /!\
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize)]
 #[serde(try_from = "VersionedKeyPair")]
 pub struct Secp256k1KeyPair {
-    private_key: SecretKey,
+    signer: Secp256k1Signer,
     pub public_key: PublicKey,
     address: [u8; 20],
     address_type: Secp256k1AddressType,
     account: String,
 }
 
+/// Holder of the private key of a `Secp256k1KeyPair`
+#[derive(Clone, Debug)]
+enum Secp256k1Signer {
+    Local(SecretKey),
+    Remote(RemoteSigner),
+}
+
+// Keys backed by a local private key serialize to the same format as `KeyPairV2`.
+// Keys backed by a remote signer have no private key, and serialize
+// to a format which can be displayed but not deserialized.
+impl Serialize for Secp256k1KeyPair {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[derive(Serialize)]
+        struct KeyPairRef<'a> {
+            #[serde(skip_serializing_if = "Option::is_none")]
+            private_key: Option<&'a SecretKey>,
+            public_key: &'a PublicKey,
+            address: &'a [u8; 20],
+            address_type: &'a Secp256k1AddressType,
+            account: &'a str,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            remote_signer: Option<&'a RemoteSigner>,
+        }
+
+        let (private_key, remote_signer) = match &self.signer {
+            Secp256k1Signer::Local(private_key) => (Some(private_key), None),
+            Secp256k1Signer::Remote(remote_signer) => (None, Some(remote_signer)),
+        };
+
+        KeyPairRef {
+            private_key,
+            public_key: &self.public_key,
+            address: &self.address,
+            address_type: &self.address_type,
+            account: &self.account,
+            remote_signer,
+        }
+        .serialize(serializer)
+    }
+}
+
 // The old `KeyEntry` type
 #[derive(Debug, Deserialize)]
 struct KeyPairV1 {
@@ -185,7 +227,7 @@ impl TryFrom<VersionedKeyPair> for Secp256k1KeyPair {
                     .map_err(|address_bytes| Error::invalid_address_length(address_bytes, 20))?;
                 let address_type = Secp256k1AddressType::derive(&public_key.public_key, &address)?;
                 Ok(Self {
-                    private_key: private_key.private_key,
+                    signer: Secp256k1Signer::Local(private_key.private_key),
                     public_key: public_key.public_key,
                     address,
                     address_type,
@@ -199,7 +241,7 @@ impl TryFrom<VersionedKeyPair> for Secp256k1KeyPair {
                 address_type,
                 account,
             }) => Ok(Self {
-                private_key,
+                signer: Secp256k1Signer::Local(private_key),
                 public_key,
                 address,
                 address_type,
@@ -222,7 +264,7 @@ impl Secp256k1KeyPair {
         let account = encode_address(account_prefix, &address)?;
 
         Ok(Self {
-            private_key: private_key.private_key,
+            signer: Secp256k1Signer::Local(private_key.private_key),
             public_key: public_key.public_key,
             address,
             address_type,
@@ -268,7 +310,7 @@ impl SigningKeyPair for Secp256k1KeyPair {
         let address_type = Secp256k1AddressType::derive(&derived_pubkey.public_key, &address)?;
 
         Ok(Self {
-            private_key: private_key.private_key,
+            signer: Secp256k1Signer::Local(private_key.private_key),
             public_key: derived_pubkey.public_key,
             address,
             address_type,
@@ -285,6 +327,27 @@ impl SigningKeyPair for Secp256k1KeyPair {
         Self::from_mnemonic_internal(mnemonic, hd_path, address_type.try_into()?, account_prefix)
     }
 
+    fn from_remote_signer(
+        signer: RemoteSigner,
+        address_type: &AddressType,
+        account_prefix: &str,
+    ) -> Result<Self, Error> {
+        let public_key = PublicKey::from_slice(&signer.public_key()?)
+            .map_err(|_| Error::invalid_remote_public_key(signer.key_name().to_string()))?;
+
+        let address_type = address_type.try_into()?;
+        let address = get_address(&public_key, address_type);
+        let account = encode_address(account_prefix, &address)?;
+
+        Ok(Self {
+            signer: Secp256k1Signer::Remote(signer),
+            public_key,
+            address,
+            address_type,
+            account,
+        })
+    }
+
     fn account(&self) -> String {
         self.account.to_owned()
     }
@@ -304,12 +367,39 @@ impl SigningKeyPair for Secp256k1KeyPair {
 
         // SAFETY: hashed_message is 32 bytes, as expected in `Message::from_slice`,
         // so `unwrap` is safe.
-        let message = Message::from_slice(&hashed_message).unwrap();
-
-        Ok(Secp256k1::signing_only()
-            .sign_ecdsa(&message, &self.private_key)
-            .serialize_compact()
-            .to_vec())
+        let hashed_message = Message::from_slice(&hashed_message).unwrap();
+
+        match &self.signer {
+            Secp256k1Signer::Local(private_key) => Ok(Secp256k1::signing_only()
+                .sign_ecdsa(&hashed_message, private_key)
+                .serialize_compact()
+                .to_vec()),
+
+            Secp256k1Signer::Remote(remote_signer) => {
+                let hash = match self.address_type {
+                    Secp256k1AddressType::Ethermint => HashFunction::Keccak256,
+                    Secp256k1AddressType::Cosmos => HashFunction::Sha256,
+                };
+
+                let signature = remote_signer.sign(message, hash)?;
+
+                // Make sure the signer signed the expected message with the expected key,
+                // as the chain would otherwise reject the transaction with a less helpful error.
+                Signature::from_compact(&signature)
+                    .and_then(|sig| {
+                        Secp256k1::verification_only().verify_ecdsa(
+                            &hashed_message,
+                            &sig,
+                            &self.public_key,
+                        )
+                    })
+                    .map_err(|_| {
+                        Error::invalid_remote_signature(remote_signer.key_name().to_string())
+                    })?;
+
+                Ok(signature)
+            }
+        }
     }
 
     fn as_any(&self) -> &dyn Any {
@@ -3,7 +3,7 @@ use core::any::Any;
 use hdpath::StandardHDPath;
 use serde::{de::DeserializeOwned, Serialize};
 
-use super::{errors::Error, KeyFile, KeyType};
+use super::{errors::Error, remote::RemoteSigner, KeyFile, KeyType};
 use crate::config::AddressType;
 
 pub trait SigningKeyPair {
@@ -30,6 +30,18 @@ pub trait SigningKeyPair {
     where
         Self: Sized;
 
+    /// Build a key pair whose private key is held by the given remote signer.
+    fn from_remote_signer(
+        _signer: RemoteSigner,
+        _address_type: &AddressType,
+        _account_prefix: &str,
+    ) -> Result<Self, Error>
+    where
+        Self: Sized,
+    {
+        Err(Error::remote_signer_unsupported(Self::KEY_TYPE))
+    }
+
     fn account(&self) -> String;
     fn sign(&self, message: &[u8]) -> Result<Vec<u8>, Error>;
 
@@ -21,6 +21,7 @@
 extern crate alloc;
 
 pub mod account;
+pub mod audit;
 pub mod cache;
 pub mod chain;
 pub mod channel;
@@ -43,6 +44,7 @@ pub mod registry;
 pub mod rest;
 pub mod sdk_error;
 pub mod spawn;
+pub mod store;
 pub mod supervisor;
 pub mod telemetry;
 pub mod transfer;
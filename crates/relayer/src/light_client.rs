@@ -1,3 +1,4 @@
+pub mod cache;
 pub mod io;
 pub mod tendermint;
 
@@ -6,13 +7,21 @@ use core::ops::Deref;
 use ibc_proto::google::protobuf::Any;
 use ibc_proto::ibc::lightclients::tendermint::v1::Header as RawTmHeader;
 use ibc_proto::protobuf::Protobuf as ErasedProtobuf;
+use ibc_relayer_types::clients::ics06_solomachine::header::{
+    decode_header as solo_decode_header, Header as SoloHeader, SOLOMACHINE_HEADER_TYPE_URL,
+};
 use ibc_relayer_types::clients::ics07_tendermint::header::{
     decode_header as tm_decode_header, Header as TendermintHeader, TENDERMINT_HEADER_TYPE_URL,
 };
+use ibc_relayer_types::clients::ics08_wasm::client_message::{
+    ClientMessage as WasmClientMessage, WASM_CLIENT_MESSAGE_TYPE_URL,
+};
 use ibc_relayer_types::core::ics02_client::client_type::ClientType;
 use ibc_relayer_types::core::ics02_client::error::Error;
 use ibc_relayer_types::core::ics02_client::events::UpdateClient;
 use ibc_relayer_types::core::ics02_client::header::Header;
+#[cfg(any(test, feature = "mock"))]
+use ibc_relayer_types::mock::header::{MockHeader, MOCK_HEADER_TYPE_URL};
 use ibc_relayer_types::timestamp::Timestamp;
 use ibc_relayer_types::Height;
 use serde::{Deserialize, Serialize};
@@ -70,37 +79,56 @@ pub trait LightClient<C: ChainEndpoint>: Send + Sync {
 
 /// Decodes an encoded header into a known `Header` type,
 pub fn decode_header(header_bytes: &[u8]) -> Result<Box<dyn Header>, Error> {
-    // For now, we only have tendermint; however when there is more than one, we
-    // can try decoding into all the known types, and return an error only if
-    // none work
-    let header: TendermintHeader =
+    let header: AnyHeader =
         ErasedProtobuf::<Any>::decode(header_bytes).map_err(Error::invalid_raw_header)?;
 
-    Ok(Box::new(header))
+    match header {
+        AnyHeader::Tendermint(header) => Ok(Box::new(header)),
+        AnyHeader::Solomachine(header) => Ok(Box::new(header)),
+
+        #[cfg(any(test, feature = "mock"))]
+        AnyHeader::Mock(header) => Ok(Box::new(header)),
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 #[allow(clippy::large_enum_variant)]
 pub enum AnyHeader {
     Tendermint(TendermintHeader),
+    Solomachine(SoloHeader),
+
+    #[cfg(any(test, feature = "mock"))]
+    Mock(MockHeader),
 }
 
 impl Header for AnyHeader {
     fn client_type(&self) -> ClientType {
         match self {
             Self::Tendermint(header) => header.client_type(),
+            Self::Solomachine(header) => header.client_type(),
+
+            #[cfg(any(test, feature = "mock"))]
+            Self::Mock(header) => header.client_type(),
         }
     }
 
     fn height(&self) -> Height {
         match self {
             Self::Tendermint(header) => header.height(),
+            Self::Solomachine(header) => header.height(),
+
+            #[cfg(any(test, feature = "mock"))]
+            Self::Mock(header) => header.height(),
         }
     }
 
     fn timestamp(&self) -> Timestamp {
         match self {
             Self::Tendermint(header) => header.timestamp(),
+            Self::Solomachine(header) => header.timestamp(),
+
+            #[cfg(any(test, feature = "mock"))]
+            Self::Mock(header) => header.timestamp(),
         }
     }
 }
@@ -118,6 +146,22 @@ impl TryFrom<Any> for AnyHeader {
                 Ok(AnyHeader::Tendermint(val))
             }
 
+            SOLOMACHINE_HEADER_TYPE_URL => {
+                let val = solo_decode_header(raw.value.deref())?;
+
+                Ok(AnyHeader::Solomachine(val))
+            }
+
+            // The headers of Wasm clients are those of the client they wrap
+            WASM_CLIENT_MESSAGE_TYPE_URL => {
+                let message = WasmClientMessage::try_from(raw)?;
+
+                AnyHeader::try_from(message.into_inner())
+            }
+
+            #[cfg(any(test, feature = "mock"))]
+            MOCK_HEADER_TYPE_URL => Ok(AnyHeader::Mock(MockHeader::try_from(raw)?)),
+
             _ => Err(Error::unknown_header_type(raw.type_url)),
         }
     }
@@ -130,6 +174,11 @@ impl From<AnyHeader> for Any {
                 type_url: TENDERMINT_HEADER_TYPE_URL.to_string(),
                 value: ErasedProtobuf::<RawTmHeader>::encode_vec(&header),
             },
+
+            AnyHeader::Solomachine(header) => header.into(),
+
+            #[cfg(any(test, feature = "mock"))]
+            AnyHeader::Mock(header) => header.into(),
         }
     }
 }
@@ -139,3 +188,16 @@ impl From<TendermintHeader> for AnyHeader {
         Self::Tendermint(header)
     }
 }
+
+impl From<SoloHeader> for AnyHeader {
+    fn from(header: SoloHeader) -> Self {
+        Self::Solomachine(header)
+    }
+}
+
+#[cfg(any(test, feature = "mock"))]
+impl From<MockHeader> for AnyHeader {
+    fn from(header: MockHeader) -> Self {
+        Self::Mock(header)
+    }
+}
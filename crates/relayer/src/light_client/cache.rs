@@ -0,0 +1,273 @@
+//! Cache of the light blocks fetched by the light client of a chain.
+//!
+//! The light block at a given height never changes once committed, hence a light block
+//! fetched for a verification can be reused by any later verification on the same chain,
+//! instead of fetching it again from the full node. Cached light blocks are not trusted:
+//! they are verified by the light client against the trusted state of each client,
+//! like the light blocks fetched from the full node.
+
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use moka::sync::Cache as MokaCache;
+use tendermint_light_client::verifier::types::{Height as TMHeight, LightBlock};
+use tracing::{debug, warn};
+
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+use crate::config::LightBlockCacheConfig;
+
+/// A bounded cache of the light blocks of a chain, kept in memory and optionally
+/// persisted on disk, each holding at most `capacity` light blocks.
+///
+/// Once full, the memory cache evicts the light blocks which are the least likely to be
+/// reused given how frequently and recently they were accessed (TinyLFU), whereas the
+/// disk cache evicts the lowest heights first.
+pub struct LightBlockCache {
+    chain_id: ChainId,
+    capacity: u64,
+    memory: MokaCache<u64, LightBlock>,
+    disk: Option<DiskCache>,
+}
+
+struct DiskCache {
+    db: sled::Db,
+    len: AtomicUsize,
+}
+
+impl LightBlockCache {
+    /// Create the cache of the given chain, persisting the light blocks in a folder
+    /// named after the chain within the configured folder, if any.
+    ///
+    /// The light blocks are only kept in memory if the folder cannot be opened,
+    /// e.g. because it is already in use by another instance of Hermes.
+    pub fn new(chain_id: ChainId, config: &LightBlockCacheConfig) -> Self {
+        let db = match &config.path {
+            Some(path) if config.capacity > 0 => sled::open(path.join(chain_id.as_str()))
+                .map_err(|e| {
+                    warn!(
+                        chain = %chain_id,
+                        "failed to open the light block cache in {}, \
+                        keeping light blocks in memory only: {e}",
+                        path.display()
+                    )
+                })
+                .ok(),
+            _ => None,
+        };
+
+        Self::with_db(chain_id, config.capacity, db)
+    }
+
+    fn with_db(chain_id: ChainId, capacity: u64, db: Option<sled::Db>) -> Self {
+        Self {
+            chain_id,
+            capacity,
+            memory: MokaCache::builder().max_capacity(capacity).build(),
+            disk: db.map(|db| DiskCache {
+                len: AtomicUsize::new(db.len()),
+                db,
+            }),
+        }
+    }
+
+    /// The light block at the given height, if it is in the cache
+    pub fn get(&self, height: TMHeight) -> Option<LightBlock> {
+        if self.capacity == 0 {
+            return None;
+        }
+
+        let key = height.value();
+
+        if let Some(block) = self.memory.get(&key) {
+            return Some(block);
+        }
+
+        let block = self.disk.as_ref()?.get(key).unwrap_or_else(|e| {
+            debug!(chain = %self.chain_id, %height, "failed to read light block from cache: {e}");
+            None
+        })?;
+
+        self.memory.insert(key, block.clone());
+
+        Some(block)
+    }
+
+    /// Clear the light blocks persisted on disk unless the latest of them is
+    /// confirmed by `is_same_chain` to belong to the chain of the full node.
+    ///
+    /// The light blocks persisted by a previous run may belong to an earlier chain
+    /// with the same identifier, e.g. a testnet restarted from a new genesis,
+    /// whose light blocks would fail the verifications they are used in.
+    pub fn validate_persisted(&self, is_same_chain: impl FnOnce(&LightBlock) -> bool) {
+        let Some(disk) = &self.disk else {
+            return;
+        };
+
+        let latest = disk.latest().unwrap_or_else(|e| {
+            debug!(chain = %self.chain_id, "failed to read light block from cache: {e}");
+            None
+        });
+
+        let Some(latest) = latest else {
+            return;
+        };
+
+        if is_same_chain(&latest) {
+            return;
+        }
+
+        warn!(
+            chain = %self.chain_id, height = %latest.height(),
+            "light blocks in cache do not belong to the chain of the full node, clearing the cache"
+        );
+
+        self.memory.invalidate_all();
+
+        if let Err(e) = disk.clear() {
+            warn!(chain = %self.chain_id, "failed to clear the light block cache: {e}");
+        }
+    }
+
+    /// Add a light block to the cache, evicting other light blocks if the cache is full
+    pub fn insert(&self, block: &LightBlock) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let key = block.height().value();
+
+        self.memory.insert(key, block.clone());
+
+        if let Some(disk) = &self.disk {
+            if let Err(e) = disk.insert(key, block, self.capacity) {
+                debug!(
+                    chain = %self.chain_id, height = %block.height(),
+                    "failed to write light block to cache: {e}"
+                );
+            }
+        }
+    }
+}
+
+impl DiskCache {
+    fn get(&self, height: u64) -> Result<Option<LightBlock>, String> {
+        self.db
+            .get(height.to_be_bytes())
+            .map_err(|e| e.to_string())?
+            .map(|bytes| serde_json::from_slice(&bytes).map_err(|e| e.to_string()))
+            .transpose()
+    }
+
+    fn latest(&self) -> Result<Option<LightBlock>, String> {
+        self.db
+            .last()
+            .map_err(|e| e.to_string())?
+            .map(|(_, bytes)| serde_json::from_slice(&bytes).map_err(|e| e.to_string()))
+            .transpose()
+    }
+
+    fn clear(&self) -> Result<(), String> {
+        self.db.clear().map_err(|e| e.to_string())?;
+        self.len.store(0, Ordering::SeqCst);
+
+        Ok(())
+    }
+
+    fn insert(&self, height: u64, block: &LightBlock, capacity: u64) -> Result<(), String> {
+        let bytes = serde_json::to_vec(block).map_err(|e| e.to_string())?;
+
+        // Heights are stored in big endian, such that the keys are ordered by height
+        let previous = self
+            .db
+            .insert(height.to_be_bytes(), bytes)
+            .map_err(|e| e.to_string())?;
+
+        if previous.is_none() {
+            self.len.fetch_add(1, Ordering::SeqCst);
+        }
+
+        while self.len.load(Ordering::SeqCst) as u64 > capacity {
+            if self.db.pop_min().map_err(|e| e.to_string())?.is_none() {
+                break;
+            }
+
+            self.len.fetch_sub(1, Ordering::SeqCst);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use tendermint_testgen::{light_block::LightBlock as TestgenLightBlock, Generator};
+
+    fn light_block(height: u64) -> LightBlock {
+        let block = TestgenLightBlock::new_default(height).generate().unwrap();
+
+        LightBlock {
+            signed_header: block.signed_header,
+            validators: block.validators,
+            next_validators: block.next_validators,
+            provider: block.provider,
+        }
+    }
+
+    fn height(height: u64) -> TMHeight {
+        TMHeight::try_from(height).unwrap()
+    }
+
+    fn temporary_db() -> sled::Db {
+        sled::Config::new().temporary(true).open().unwrap()
+    }
+
+    #[test]
+    fn disk_cache_evicts_lowest_heights() {
+        let db = temporary_db();
+        let chain_id = ChainId::new("chain".to_string(), 0);
+
+        let cache = LightBlockCache::with_db(chain_id.clone(), 2, Some(db.clone()));
+        for h in [3, 1, 2] {
+            cache.insert(&light_block(h));
+        }
+        drop(cache);
+
+        // A fresh cache only finds the blocks persisted on disk
+        let cache = LightBlockCache::with_db(chain_id, 2, Some(db));
+        assert_eq!(cache.get(height(1)), None);
+        assert_eq!(cache.get(height(2)), Some(light_block(2)));
+        assert_eq!(cache.get(height(3)), Some(light_block(3)));
+    }
+
+    #[test]
+    fn disk_cache_of_another_chain_is_cleared() {
+        let db = temporary_db();
+        let chain_id = ChainId::new("chain".to_string(), 0);
+
+        let cache = LightBlockCache::with_db(chain_id.clone(), 2, Some(db.clone()));
+        cache.insert(&light_block(1));
+        cache.insert(&light_block(2));
+
+        cache.validate_persisted(|block| block.height() == height(2));
+        assert_eq!(cache.get(height(2)), Some(light_block(2)));
+
+        cache.validate_persisted(|_| false);
+        assert_eq!(cache.get(height(1)), None);
+        assert_eq!(cache.get(height(2)), None);
+        drop(cache);
+
+        let cache = LightBlockCache::with_db(chain_id, 2, Some(db));
+        assert_eq!(cache.get(height(2)), None);
+    }
+
+    #[test]
+    fn disabled_cache() {
+        let chain_id = ChainId::new("chain".to_string(), 0);
+        let cache = LightBlockCache::with_db(chain_id, 0, Some(temporary_db()));
+
+        cache.insert(&light_block(1));
+        assert_eq!(cache.get(height(1)), None);
+    }
+}
@@ -1,9 +1,13 @@
+use std::sync::Arc;
+
 use tendermint::{account, block::Height};
 use tendermint_light_client::{
     components::io::{AtHeight, Io, IoError, ProdIo},
-    types::LightBlock,
+    types::{LightBlock, PeerId},
 };
 
+use super::cache::LightBlockCache;
+
 #[derive(Clone, Debug)]
 pub enum AnyIo {
     Prod(ProdIo),
@@ -83,3 +87,47 @@ impl Io for RestartAwareIo {
         io.fetch_light_block(height)
     }
 }
+
+/// Fetches light blocks through the light block cache of the chain,
+/// only fetching them from the full node when they are not in the cache.
+#[derive(Clone)]
+pub struct CachingIo {
+    peer_id: PeerId,
+    io: AnyIo,
+    cache: Arc<LightBlockCache>,
+}
+
+impl CachingIo {
+    pub fn new(peer_id: PeerId, io: AnyIo, cache: Arc<LightBlockCache>) -> Self {
+        Self { peer_id, io, cache }
+    }
+
+    pub fn rpc_client(&self) -> &tendermint_rpc::HttpClient {
+        self.io.rpc_client()
+    }
+
+    pub fn fetch_validator_set(
+        &self,
+        height: AtHeight,
+        proposer_address: Option<account::Id>,
+    ) -> Result<tendermint::validator::Set, IoError> {
+        self.io.fetch_validator_set(height, proposer_address)
+    }
+}
+
+impl Io for CachingIo {
+    fn fetch_light_block(&self, height: AtHeight) -> Result<LightBlock, IoError> {
+        if let AtHeight::At(height) = height {
+            if let Some(mut block) = self.cache.get(height) {
+                // The block may have been fetched from another full node of the chain
+                block.provider = self.peer_id;
+                return Ok(block);
+            }
+        }
+
+        let block = self.io.fetch_light_block(height)?;
+        self.cache.insert(&block);
+
+        Ok(block)
+    }
+}
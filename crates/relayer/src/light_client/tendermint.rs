@@ -1,5 +1,6 @@
 mod detector;
 
+use std::sync::Arc;
 use std::time::Duration;
 
 use itertools::Itertools;
@@ -41,14 +42,15 @@ use crate::{
 };
 
 use super::{
-    io::{AnyIo, RestartAwareIo},
+    cache::LightBlockCache,
+    io::{AnyIo, CachingIo, RestartAwareIo},
     Verified,
 };
 
 pub struct LightClient {
     chain_id: ChainId,
     peer_id: PeerId,
-    io: AnyIo,
+    io: CachingIo,
     enable_verification: bool,
 }
 
@@ -267,7 +269,13 @@ fn io_for_addr(
 }
 
 impl LightClient {
-    pub fn from_config(config: &ChainConfig, peer_id: PeerId) -> Result<Self, Error> {
+    /// Create the light client of a chain, which fetches light blocks from the
+    /// configured full node, through the given light block cache of the chain.
+    pub fn from_config(
+        config: &ChainConfig,
+        peer_id: PeerId,
+        cache: Arc<LightBlockCache>,
+    ) -> Result<Self, Error> {
         let live_io = io_for_addr(&config.rpc_addr, peer_id, Some(config.rpc_timeout))?;
 
         let io = match &config.genesis_restart {
@@ -295,8 +303,7 @@ impl LightClient {
         Ok(Self {
             chain_id: config.id.clone(),
             peer_id,
-            io,
-
+            io: CachingIo::new(peer_id, io, cache),
             enable_verification,
         })
     }
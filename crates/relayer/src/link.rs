@@ -13,6 +13,7 @@ use crate::link::error::LinkError;
 
 pub mod cli;
 pub mod error;
+pub mod journal;
 pub mod operational_data;
 pub mod packet_events;
 
@@ -27,7 +28,9 @@ use tx_hashes::TxHashes;
 // Re-export the telemetries summary
 pub use relay_summary::RelaySummary;
 
-pub use relay_path::{RelayPath, Resubmit};
+pub use relay_path::{
+    BlockingPacket, ChannelDiagnosis, PacketRelayCost, RelayPath, Resubmit, SimulationOutcome,
+};
 
 #[derive(Clone, Debug)]
 pub struct LinkParameters {
@@ -77,8 +80,11 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Link<ChainA, ChainB> {
                 )
             })?;
 
+        // The in-flight packets of a channel being upgraded must be relayed
+        // for the channel ends to complete flushing.
         if !a_channel.state_matches(&ChannelState::Open)
             && !a_channel.state_matches(&ChannelState::Closed)
+            && !a_channel.state().is_upgrading()
         {
             return Err(LinkError::invalid_channel_state(
                 a_channel_id.clone(),
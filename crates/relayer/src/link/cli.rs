@@ -1,4 +1,5 @@
 use std::convert::TryInto;
+use std::ops::RangeInclusive;
 use std::thread;
 use std::time::{Duration, Instant};
 
@@ -71,12 +72,19 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
 }
 
 impl<ChainA: ChainHandle, ChainB: ChainHandle> Link<ChainA, ChainB> {
-    pub fn relay_recv_packet_and_timeout_messages(&self) -> Result<Vec<IbcEvent>, LinkError> {
-        self.relay_recv_packet_and_timeout_messages_with_packet_data_query_height(None)
+    /// Relays the packets whose sequence is within the given ranges,
+    /// or all the unreceived packets if no range is given.
+    pub fn relay_recv_packet_and_timeout_messages(
+        &self,
+        sequences: &[RangeInclusive<Sequence>],
+    ) -> Result<Vec<IbcEvent>, LinkError> {
+        self.relay_recv_packet_and_timeout_messages_with_packet_data_query_height(sequences, None)
     }
+
     /// Implements the `packet-recv` CLI
     pub fn relay_recv_packet_and_timeout_messages_with_packet_data_query_height(
         &self,
+        sequences: &[RangeInclusive<Sequence>],
         packet_data_query_height: Option<Height>,
     ) -> Result<Vec<IbcEvent>, LinkError> {
         let _span = error_span!(
@@ -89,13 +97,15 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Link<ChainA, ChainB> {
         .entered();
 
         // Find the sequence numbers of unreceived packets
-        let (sequences, src_response_height) = unreceived_packets(
+        let (unreceived, src_response_height) = unreceived_packets(
             self.a_to_b.dst_chain(),
             self.a_to_b.src_chain(),
             &self.a_to_b.path_id,
         )
         .map_err(LinkError::supervisor)?;
 
+        let sequences = filter_sequences(unreceived, sequences);
+
         if sequences.is_empty() {
             return Ok(vec![]);
         }
@@ -119,13 +129,19 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Link<ChainA, ChainB> {
         )
     }
 
-    pub fn relay_ack_packet_messages(&self) -> Result<Vec<IbcEvent>, LinkError> {
-        self.relay_ack_packet_messages_with_packet_data_query_height(None)
+    /// Relays the acknowledgements of the packets whose sequence is within the given ranges,
+    /// or all the unreceived acknowledgements if no range is given.
+    pub fn relay_ack_packet_messages(
+        &self,
+        sequences: &[RangeInclusive<Sequence>],
+    ) -> Result<Vec<IbcEvent>, LinkError> {
+        self.relay_ack_packet_messages_with_packet_data_query_height(sequences, None)
     }
 
     /// Implements the `packet-ack` CLI
     pub fn relay_ack_packet_messages_with_packet_data_query_height(
         &self,
+        sequences: &[RangeInclusive<Sequence>],
         packet_data_query_height: Option<Height>,
     ) -> Result<Vec<IbcEvent>, LinkError> {
         let _span = error_span!(
@@ -138,12 +154,17 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Link<ChainA, ChainB> {
         .entered();
 
         // Find the sequence numbers of unreceived acknowledgements
-        let Some((sequences, src_response_height)) = unreceived_acknowledgements(
+        let Some((unreceived, src_response_height)) = unreceived_acknowledgements(
             self.a_to_b.dst_chain(),
             self.a_to_b.src_chain(),
             &self.a_to_b.path_id,
         )
-        .map_err(LinkError::supervisor)? else { return Ok(vec![]) };
+        .map_err(LinkError::supervisor)?
+        else {
+            return Ok(vec![]);
+        };
+
+        let sequences = filter_sequences(unreceived, sequences);
 
         if sequences.is_empty() {
             return Ok(vec![]);
@@ -216,6 +237,22 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Link<ChainA, ChainB> {
     }
 }
 
+/// Keeps the sequences which are within any of the given ranges,
+/// or all of them if no range is given.
+fn filter_sequences(
+    sequences: Vec<Sequence>,
+    ranges: &[RangeInclusive<Sequence>],
+) -> Vec<Sequence> {
+    if ranges.is_empty() {
+        return sequences;
+    }
+
+    sequences
+        .into_iter()
+        .filter(|sequence| ranges.iter().any(|range| range.contains(sequence)))
+        .collect()
+}
+
 fn wait_for_conn_delay<ChainTime, MaxBlockTime, LatestHeight>(
     odata: OperationalData,
     chain_time: &ChainTime,
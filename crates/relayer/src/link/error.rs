@@ -1,12 +1,15 @@
+use core::time::Duration;
+
 use flex_error::define_error;
 use ibc_relayer_types::core::ics02_client::error::Error as Ics02Error;
+use ibc_relayer_types::core::ics04_channel::packet::Sequence;
 use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
 use ibc_relayer_types::events::IbcEvent;
 use ibc_relayer_types::Height;
 
 use crate::channel::ChannelError;
 use crate::connection::ConnectionError;
-use crate::error::Error;
+use crate::error::{Error, ErrorDetail};
 use crate::foreign_client::{ForeignClientError, HasExpiredOrFrozenError};
 use crate::supervisor::Error as SupervisorError;
 use crate::transfer::TransferError;
@@ -148,9 +151,48 @@ define_error! {
 
         UpdateClientFailed
              |_| { "failed to update client" },
+
+        UnorderedChannel
+            {
+                channel_id: ChannelId,
+                chain_id: ChainId,
+            }
+            |e| {
+                format!("channel {} on chain {} is not ordered", e.channel_id, e.chain_id)
+            },
+
+        SendPacketEventNotFound
+            {
+                sequence: Sequence,
+                chain_id: ChainId,
+            }
+            |e| {
+                format!("send packet event for sequence {} not found on chain {}", e.sequence, e.chain_id)
+            },
+
+        FeeEstimation
+            { chain_id: ChainId }
+            [ Error ]
+            |e| {
+                format!("failed to estimate the fee of the messages on chain {}", e.chain_id)
+            },
    }
 }
 
+impl LinkError {
+    /// How long until the fee budget of the target chain is replenished,
+    /// if the messages could not be submitted because it is exhausted
+    pub fn fee_budget_resets_in(&self) -> Option<Duration> {
+        match self.detail() {
+            LinkErrorDetail::Relayer(e) => match e.source.detail() {
+                ErrorDetail::FeeBudgetExhausted(e) => Some(e.resets_in),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
 impl HasExpiredOrFrozenError for LinkErrorDetail {
     fn is_expired_or_frozen_error(&self) -> bool {
         match self {
@@ -0,0 +1,407 @@
+//! Journal of the packets relayed by this instance of Hermes.
+//!
+//! For every packet it relays, Hermes records when the packet was sent, the transactions
+//! submitted to receive, acknowledge or time it out, and when these were committed. The
+//! journal only lives in memory, and keeps the traces of the most recently sent packets.
+
+use alloc::collections::VecDeque;
+use core::time::Duration;
+use std::collections::HashMap;
+use std::time::SystemTime;
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use tendermint::Hash as TxHash;
+
+use ibc_relayer_types::core::ics04_channel::packet::{Packet, Sequence};
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+use ibc_relayer_types::events::IbcEvent;
+use ibc_relayer_types::Height;
+
+use crate::event::IbcEventWithHeight;
+use crate::link::operational_data::{OperationalData, OperationalDataTarget};
+use crate::util::lock::{LockExt, RwArc};
+
+/// The maximum number of packets kept in the journal, the oldest packets being evicted first
+const MAX_TRACES: usize = 10_000;
+
+static JOURNAL: Lazy<RwArc<Journal>> = Lazy::new(|| RwArc::new_lock(Journal::default()));
+
+/// Identifies a packet by the chain, port and channel it was sent from, and its sequence
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PacketKey {
+    pub chain_id: ChainId,
+    pub port_id: PortId,
+    pub channel_id: ChannelId,
+    pub sequence: Sequence,
+}
+
+impl PacketKey {
+    fn new(chain_id: &ChainId, packet: &Packet) -> Self {
+        Self {
+            chain_id: chain_id.clone(),
+            port_id: packet.source_port.clone(),
+            channel_id: packet.source_channel.clone(),
+            sequence: packet.sequence,
+        }
+    }
+}
+
+/// The journey of a packet, as observed by Hermes
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PacketTrace {
+    #[serde(flatten)]
+    pub key: PacketKey,
+    pub dst_chain_id: ChainId,
+    pub dst_port_id: PortId,
+    pub dst_channel_id: ChannelId,
+    pub sent: Option<PacketSent>,
+    pub recv: PacketStep,
+    pub ack: PacketStep,
+    pub timeout: PacketStep,
+    /// The last error reported while relaying the packet
+    pub last_error: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PacketSent {
+    pub height: Height,
+    /// When Hermes first observed the packet
+    #[serde(with = "humantime_serde")]
+    pub observed_at: SystemTime,
+}
+
+/// The relaying of a packet to the counterparty chain, or back to the chain it was sent from
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PacketStep {
+    /// Number of times Hermes submitted a message for this step, such that
+    /// any attempt beyond the first one is a retry
+    pub attempts: u32,
+    pub tx_hash: Option<String>,
+    /// Height at which the message for this step was committed
+    pub height: Option<Height>,
+    /// When Hermes observed that the message for this step was committed
+    #[serde(default, with = "humantime_serde")]
+    pub committed_at: Option<SystemTime>,
+    /// Time elapsed between the packet being first observed and this step being committed
+    #[serde(default, with = "humantime_serde")]
+    pub latency: Option<Duration>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Step {
+    Recv,
+    Ack,
+    Timeout,
+}
+
+#[derive(Default)]
+struct Journal {
+    traces: HashMap<PacketKey, PacketTrace>,
+    order: VecDeque<PacketKey>,
+}
+
+impl Journal {
+    fn trace_mut(
+        &mut self,
+        src_chain_id: &ChainId,
+        dst_chain_id: &ChainId,
+        packet: &Packet,
+    ) -> &mut PacketTrace {
+        let key = PacketKey::new(src_chain_id, packet);
+
+        if !self.traces.contains_key(&key) {
+            if self.order.len() >= MAX_TRACES {
+                if let Some(oldest) = self.order.pop_front() {
+                    self.traces.remove(&oldest);
+                }
+            }
+
+            self.order.push_back(key.clone());
+            self.traces.insert(
+                key.clone(),
+                PacketTrace {
+                    key: key.clone(),
+                    dst_chain_id: dst_chain_id.clone(),
+                    dst_port_id: packet.destination_port.clone(),
+                    dst_channel_id: packet.destination_channel.clone(),
+                    sent: None,
+                    recv: PacketStep::default(),
+                    ack: PacketStep::default(),
+                    timeout: PacketStep::default(),
+                    last_error: None,
+                },
+            );
+        }
+
+        self.traces.get_mut(&key).expect("trace was just inserted")
+    }
+}
+
+impl PacketTrace {
+    fn step_mut(&mut self, step: Step) -> &mut PacketStep {
+        match step {
+            Step::Recv => &mut self.recv,
+            Step::Ack => &mut self.ack,
+            Step::Timeout => &mut self.timeout,
+        }
+    }
+}
+
+/// The trace of the given packet, if it was relayed recently by this instance of Hermes
+pub fn packet_trace(key: &PacketKey) -> Option<PacketTrace> {
+    JOURNAL.acquire_read().traces.get(key).cloned()
+}
+
+/// Records a packet sent from `src_chain_id` to `dst_chain_id` at the given height
+pub(crate) fn record_sent(
+    src_chain_id: &ChainId,
+    dst_chain_id: &ChainId,
+    packet: &Packet,
+    height: Height,
+) {
+    let mut journal = JOURNAL.acquire_write();
+    let trace = journal.trace_mut(src_chain_id, dst_chain_id, packet);
+
+    if trace.sent.is_none() {
+        trace.sent = Some(PacketSent {
+            height,
+            observed_at: SystemTime::now(),
+        });
+    }
+}
+
+/// Records the submission of the messages of the given operational data to `chain_id`,
+/// in a single transaction with the given hash if known
+pub(crate) fn record_submitted(
+    chain_id: &ChainId,
+    counterparty_chain_id: &ChainId,
+    odata: &OperationalData,
+    tx_hash: Option<&TxHash>,
+) {
+    let mut journal = JOURNAL.acquire_write();
+
+    for (src_chain_id, dst_chain_id, packet, step) in
+        submitted_packets(chain_id, counterparty_chain_id, odata)
+    {
+        let step = journal
+            .trace_mut(src_chain_id, dst_chain_id, packet)
+            .step_mut(step);
+
+        step.attempts += 1;
+
+        if let Some(tx_hash) = tx_hash {
+            step.tx_hash = Some(tx_hash.to_string());
+        }
+    }
+}
+
+/// Records the failure to relay the packets of the given operational data to `chain_id`
+pub(crate) fn record_failed(
+    chain_id: &ChainId,
+    counterparty_chain_id: &ChainId,
+    odata: &OperationalData,
+    error: &str,
+) {
+    let mut journal = JOURNAL.acquire_write();
+
+    for (src_chain_id, dst_chain_id, packet, _) in
+        submitted_packets(chain_id, counterparty_chain_id, odata)
+    {
+        journal
+            .trace_mut(src_chain_id, dst_chain_id, packet)
+            .last_error = Some(error.to_string());
+    }
+}
+
+/// Records a packet event emitted by `chain_id` when committing the transaction
+/// with the given hash, if known
+pub(crate) fn record_committed(
+    chain_id: &ChainId,
+    counterparty_chain_id: &ChainId,
+    event_with_height: &IbcEventWithHeight,
+    tx_hash: Option<&TxHash>,
+) {
+    let (src_chain_id, dst_chain_id, step) = match &event_with_height.event {
+        IbcEvent::ReceivePacket(_) | IbcEvent::WriteAcknowledgement(_) => {
+            (counterparty_chain_id, chain_id, Step::Recv)
+        }
+        IbcEvent::AcknowledgePacket(_) => (chain_id, counterparty_chain_id, Step::Ack),
+        IbcEvent::TimeoutPacket(_) | IbcEvent::TimeoutOnClosePacket(_) => {
+            (chain_id, counterparty_chain_id, Step::Timeout)
+        }
+        _ => return,
+    };
+
+    let Some(packet) = event_with_height.event.packet() else {
+        return;
+    };
+
+    let mut journal = JOURNAL.acquire_write();
+    let trace = journal.trace_mut(src_chain_id, dst_chain_id, packet);
+    let observed_at = trace.sent.as_ref().map(|sent| sent.observed_at);
+    let step = trace.step_mut(step);
+
+    if let Some(tx_hash) = tx_hash {
+        step.tx_hash = Some(tx_hash.to_string());
+    }
+
+    if step.committed_at.is_none() {
+        let now = SystemTime::now();
+
+        step.height = Some(event_with_height.height);
+        step.committed_at = Some(now);
+        step.latency = observed_at.and_then(|observed_at| now.duration_since(observed_at).ok());
+    }
+}
+
+/// The packets relayed by the messages of the given operational data, submitted to `chain_id`,
+/// along with the chains they were sent from and to, and the step of their relaying
+fn submitted_packets<'a>(
+    chain_id: &'a ChainId,
+    counterparty_chain_id: &'a ChainId,
+    odata: &'a OperationalData,
+) -> impl Iterator<Item = (&'a ChainId, &'a ChainId, &'a Packet, Step)> {
+    odata.batch.iter().filter_map(
+        move |msg| match (&msg.event_with_height.event, odata.target) {
+            (IbcEvent::SendPacket(event), OperationalDataTarget::Destination) => {
+                Some((counterparty_chain_id, chain_id, &event.packet, Step::Recv))
+            }
+            (IbcEvent::SendPacket(event), OperationalDataTarget::Source) => Some((
+                chain_id,
+                counterparty_chain_id,
+                &event.packet,
+                Step::Timeout,
+            )),
+            (IbcEvent::WriteAcknowledgement(event), _) => {
+                Some((chain_id, counterparty_chain_id, &event.packet, Step::Ack))
+            }
+            _ => None,
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ibc_proto::google::protobuf::Any;
+    use ibc_relayer_types::core::ics04_channel::events::{
+        AcknowledgePacket, ReceivePacket, SendPacket, WriteAcknowledgement,
+    };
+
+    use crate::chain::tracking::TrackingId;
+    use crate::link::operational_data::TransitMessage;
+
+    fn packet(sequence: u64) -> Packet {
+        Packet {
+            sequence: sequence.into(),
+            source_port: PortId::transfer(),
+            source_channel: ChannelId::new(0),
+            destination_port: PortId::transfer(),
+            destination_channel: ChannelId::new(1),
+            ..Default::default()
+        }
+    }
+
+    fn height(height: u64) -> Height {
+        Height::new(0, height).unwrap()
+    }
+
+    fn odata(target: OperationalDataTarget, event: IbcEvent) -> OperationalData {
+        let mut odata = OperationalData::new(
+            height(10),
+            target,
+            TrackingId::new_static("test"),
+            Duration::ZERO,
+        );
+
+        odata.push(TransitMessage {
+            event_with_height: IbcEventWithHeight::new(event, height(10)),
+            msg: Any::default(),
+        });
+
+        odata
+    }
+
+    #[test]
+    fn packet_relayed_and_acknowledged() {
+        let src = ChainId::from_string("journal-src");
+        let dst = ChainId::from_string("journal-dst");
+        let packet = packet(1);
+        let key = PacketKey::new(&src, &packet);
+        let tx_hash = TxHash::None;
+
+        record_sent(&src, &dst, &packet, height(10));
+
+        let recv = odata(
+            OperationalDataTarget::Destination,
+            IbcEvent::SendPacket(SendPacket {
+                packet: packet.clone(),
+            }),
+        );
+        record_submitted(&dst, &src, &recv, None);
+        record_failed(&dst, &src, &recv, "account sequence mismatch");
+        record_submitted(&dst, &src, &recv, Some(&tx_hash));
+
+        let received = IbcEvent::ReceivePacket(ReceivePacket {
+            packet: packet.clone(),
+        });
+        record_committed(
+            &dst,
+            &src,
+            &IbcEventWithHeight::new(received, height(20)),
+            None,
+        );
+
+        let write_ack = IbcEvent::WriteAcknowledgement(WriteAcknowledgement {
+            packet: packet.clone(),
+            ack: vec![],
+        });
+        let ack = odata(OperationalDataTarget::Destination, write_ack);
+        record_submitted(&src, &dst, &ack, None);
+
+        let acknowledged = IbcEvent::AcknowledgePacket(AcknowledgePacket { packet });
+        record_committed(
+            &src,
+            &dst,
+            &IbcEventWithHeight::new(acknowledged, height(12)),
+            None,
+        );
+
+        let trace = packet_trace(&key).unwrap();
+
+        assert_eq!(trace.dst_chain_id, dst);
+        assert_eq!(trace.sent.map(|sent| sent.height), Some(height(10)));
+        assert_eq!(trace.recv.attempts, 2);
+        assert_eq!(trace.recv.tx_hash, Some(tx_hash.to_string()));
+        assert_eq!(trace.recv.height, Some(height(20)));
+        assert!(trace.recv.latency.is_some());
+        assert_eq!(trace.ack.attempts, 1);
+        assert_eq!(trace.ack.height, Some(height(12)));
+        assert_eq!(trace.timeout, PacketStep::default());
+        assert_eq!(
+            trace.last_error.as_deref(),
+            Some("account sequence mismatch")
+        );
+    }
+
+    #[test]
+    fn oldest_packets_evicted() {
+        let src = ChainId::from_string("journal-src");
+        let dst = ChainId::from_string("journal-dst");
+        let mut journal = Journal::default();
+
+        for sequence in 1..=(MAX_TRACES as u64 + 1) {
+            journal.trace_mut(&src, &dst, &packet(sequence));
+        }
+
+        assert_eq!(journal.traces.len(), MAX_TRACES);
+        assert!(!journal
+            .traces
+            .contains_key(&PacketKey::new(&src, &packet(1))));
+        assert!(journal
+            .traces
+            .contains_key(&PacketKey::new(&src, &packet(2))));
+    }
+}
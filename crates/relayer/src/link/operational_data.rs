@@ -6,6 +6,8 @@ use ibc_proto::google::protobuf::Any;
 use tracing::{debug, info};
 
 use ibc_relayer_types::core::ics02_client::client_state::ClientState;
+use ibc_relayer_types::core::ics04_channel::channel::Ordering;
+use ibc_relayer_types::core::ics04_channel::packet::Sequence;
 use ibc_relayer_types::Height;
 
 use crate::chain::handle::ChainHandle;
@@ -126,6 +128,15 @@ impl OperationalData {
         self.batch.push(msg)
     }
 
+    /// The sequences of the packets relayed by the messages of this operational data
+    pub fn sequences(&self) -> Vec<Sequence> {
+        self.batch
+            .iter()
+            .filter_map(|msg| msg.event_with_height.event.packet())
+            .map(|packet| packet.sequence)
+            .collect()
+    }
+
     /// Returns displayable information on the operation's data.
     pub fn info(&self) -> OperationalInfo {
         OperationalInfo {
@@ -215,7 +226,8 @@ impl OperationalData {
             .chain(self.batch.iter().map(|gm| gm.msg.clone()))
             .collect();
 
-        let tm = TrackedMsgs::new(msgs, self.tracking_id);
+        let unordered_channel = relay_path.channel().ordering == Ordering::Unordered;
+        let tm = TrackedMsgs::new(msgs, self.tracking_id).with_unordered_channel(unordered_channel);
 
         info!("assembled batch of {} message(s)", tm.messages().len());
 
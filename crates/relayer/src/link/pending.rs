@@ -6,11 +6,15 @@ use tracing::{debug, error, trace, trace_span};
 
 use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
 use ibc_relayer_types::events::IbcEvent;
+use tendermint::Hash as TxHash;
 
+use crate::audit;
 use crate::chain::requests::{QueryTxHash, QueryTxRequest};
 use crate::chain::tracking::TrackingId;
 use crate::error::Error as RelayerError;
-use crate::link::{error::LinkError, RelayPath};
+use crate::event::IbcEventWithHeight;
+use crate::link::{error::LinkError, journal, RelayPath};
+use crate::store::StoredTx;
 use crate::telemetry;
 use crate::util::queue::Queue;
 use crate::{
@@ -126,20 +130,46 @@ impl<Chain: ChainHandle> PendingTxs<Chain> {
         self.pending_queue.push_back(u);
     }
 
-    fn check_tx_events(&self, tx_hashes: &TxHashes) -> Result<Option<Vec<IbcEvent>>, RelayerError> {
+    /// The events of the given transactions, along with the hash of the transaction
+    /// which emitted them, or `None` if any of the transactions is not committed yet
+    fn check_tx_events(
+        &self,
+        tx_hashes: &TxHashes,
+    ) -> Result<Option<Vec<(TxHash, IbcEventWithHeight)>>, RelayerError> {
         let mut all_events = Vec::new();
         for hash in &tx_hashes.0 {
-            let mut events = self
+            let events = self
                 .chain
                 .query_txs(QueryTxRequest::Transaction(QueryTxHash(*hash)))?;
 
             if events.is_empty() {
                 return Ok(None);
             } else {
-                all_events.append(&mut events)
+                all_events.extend(events.into_iter().map(|event| (*hash, event)))
             }
         }
-        Ok(Some(all_events.into_iter().map(|ev| ev.event).collect()))
+        Ok(Some(all_events))
+    }
+
+    /// The transactions pending confirmation, to be persisted in the state store
+    pub fn stored_txs(&self) -> Vec<StoredTx> {
+        self.pending_queue
+            .clone_vec()
+            .into_iter()
+            .filter(|pending| !pending.tx_hashes.0.is_empty())
+            .map(|pending| StoredTx {
+                chain_id: self.chain_id(),
+                tx_hashes: pending.tx_hashes.0,
+                sequences: pending.original_od.sequences(),
+            })
+            .collect()
+    }
+
+    /// Whether the given transactions, submitted before the relayer was restarted,
+    /// were committed on chain
+    pub fn is_committed(&self, tx: &StoredTx) -> Result<bool, RelayerError> {
+        let events = self.check_tx_events(&TxHashes(tx.tx_hashes.clone()))?;
+        Ok(events.is_some())
     }
 
     /// Try and process one pending transaction within the given timeout duration if one
@@ -166,6 +196,7 @@ impl<Chain: ChainHandle> PendingTxs<Chain> {
 
             let span = trace_span!(
                 "processing pending tx",
+                tracking_id = %pending.tracking_id(),
                 chain = %self.chain_id(),
                 counterparty_chain = %self.counterparty_chain_id,
                 port = %self.port_id,
@@ -191,6 +222,10 @@ impl<Chain: ChainHandle> PendingTxs<Chain> {
                         // relayer to resubmit the transaction to the chain again.
                         error!("timed out while confirming {}", tx_hashes);
 
+                        for tx_hash in &tx_hashes.0 {
+                            audit::untrack_commit(&self.chain_id(), &tx_hash.to_string());
+                        }
+
                         match resubmit {
                             Some(f) => {
                                 // The pending tx needs to be resubmitted. This involves replacing the tx's
@@ -203,6 +238,13 @@ impl<Chain: ChainHandle> PendingTxs<Chain> {
 
                                 match new_od.map(f) {
                                     Some(Ok(reply)) => {
+                                        journal::record_submitted(
+                                            &self.chain_id(),
+                                            &self.counterparty_chain_id,
+                                            &pending.original_od,
+                                            None,
+                                        );
+
                                         self.insert_new_pending_tx(reply, pending.original_od);
                                         Ok(None)
                                     }
@@ -229,7 +271,7 @@ impl<Chain: ChainHandle> PendingTxs<Chain> {
                         Ok(None)
                     }
                 }
-                Ok(Some(mut events)) => {
+                Ok(Some(tx_events)) => {
                     // We get a list of events for the transaction hashes,
                     // Meaning the transaction has been committed successfully
                     // to the chain.
@@ -251,6 +293,18 @@ impl<Chain: ChainHandle> PendingTxs<Chain> {
                         &self.counterparty_chain_id
                     );
 
+                    for (tx_hash, event_with_height) in &tx_events {
+                        journal::record_committed(
+                            &self.chain_id(),
+                            &self.counterparty_chain_id,
+                            event_with_height,
+                            Some(tx_hash),
+                        );
+                    }
+
+                    let mut events: Vec<IbcEvent> =
+                        tx_events.into_iter().map(|(_, ev)| ev.event).collect();
+
                     // Append the events corresponding to errors from the pending tx.
                     events.extend(pending.error_events);
 
@@ -3,10 +3,13 @@ use alloc::collections::VecDeque;
 use std::ops::Sub;
 use std::time::{Duration, Instant};
 
+use ibc_proto::cosmos::base::v1beta1::Coin as ProtoCoin;
 use ibc_proto::google::protobuf::Any;
 use itertools::Itertools;
+use serde::Serialize;
 use tracing::{debug, error, info, span, trace, warn, Level};
 
+use ibc_relayer_types::applications::transfer::RawCoin;
 use ibc_relayer_types::core::ics02_client::events::ClientMisbehaviour as ClientMisbehaviourEvent;
 use ibc_relayer_types::core::ics04_channel::channel::{
     ChannelEnd, Ordering, State as ChannelState,
@@ -16,18 +19,24 @@ use ibc_relayer_types::core::ics04_channel::msgs::{
     acknowledgement::MsgAcknowledgement, chan_close_confirm::MsgChannelCloseConfirm,
     recv_packet::MsgRecvPacket, timeout::MsgTimeout, timeout_on_close::MsgTimeoutOnClose,
 };
-use ibc_relayer_types::core::ics04_channel::packet::{Packet, PacketMsgType};
-use ibc_relayer_types::core::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
+use ibc_relayer_types::core::ics04_channel::packet::{Packet, PacketMsgType, Sequence};
+use ibc_relayer_types::core::ics24_host::identifier::{
+    ChainId, ChannelId, ClientId, ConnectionId, PortId,
+};
 use ibc_relayer_types::events::{IbcEvent, IbcEventType, WithBlockDataType};
 use ibc_relayer_types::signer::Signer;
 use ibc_relayer_types::timestamp::Timestamp;
 use ibc_relayer_types::tx_msg::Msg;
 use ibc_relayer_types::Height;
 
+use crate::chain::cosmos::gas::calculate_fee;
+use crate::chain::cosmos::types::gas::default_gas_from_config;
+use crate::chain::counterparty::sync_proof_sequence;
 use crate::chain::counterparty::unreceived_acknowledgements;
 use crate::chain::counterparty::unreceived_packets;
 use crate::chain::endpoint::ChainStatus;
 use crate::chain::handle::ChainHandle;
+use crate::chain::requests::ProofPurpose;
 use crate::chain::requests::QueryChannelRequest;
 use crate::chain::requests::QueryClientEventRequest;
 use crate::chain::requests::QueryHeight;
@@ -42,10 +51,12 @@ use crate::chain::tracking::TrackedMsgs;
 use crate::chain::tracking::TrackingId;
 use crate::channel::error::ChannelError;
 use crate::channel::Channel;
+use crate::error::ErrorDetail;
 use crate::event::source::EventBatch;
 use crate::event::IbcEventWithHeight;
 use crate::foreign_client::{ForeignClient, ForeignClientError};
 use crate::link::error::{self, LinkError};
+use crate::link::journal;
 use crate::link::operational_data::{
     OperationalData, OperationalDataTarget, TrackedEvents, TransitMessage,
 };
@@ -56,8 +67,12 @@ use crate::link::pending::PendingTxs;
 use crate::link::relay_sender::{AsyncReply, SubmitReply};
 use crate::link::relay_summary::RelaySummary;
 use crate::link::{pending, relay_sender};
+use crate::object;
 use crate::path::PathIdentifiers;
+use crate::sdk_error::{sdk_error_from_simulation_message, SdkErrorDetail};
+use crate::store::{self, StoredBacklog};
 use crate::telemetry;
+use crate::transfer::TransferError;
 use crate::util::collate::CollatedIterExt;
 use crate::util::pretty::PrettyEvents;
 use crate::util::queue::Queue;
@@ -85,6 +100,55 @@ impl Resubmit {
     }
 }
 
+/// The estimated cost of relaying a packet, ie. of the fees paid by the relayer
+/// to submit the messages relaying it.
+#[derive(Clone, Debug)]
+pub enum PacketRelayCost {
+    /// Cost of receiving the packet on the destination chain,
+    /// and of acknowledging it on the source chain
+    RecvAndAck(Vec<RawCoin>),
+    /// Cost of timing out the packet on the source chain
+    Timeout(Vec<RawCoin>),
+}
+
+/// The diagnosis of an ordered channel, identifying the packet blocking it, if any.
+#[derive(Clone, Debug, Serialize)]
+pub struct ChannelDiagnosis {
+    /// The sequence of the next packet the destination chain accepts on the channel
+    pub next_sequence_receive: Sequence,
+    /// Number of packets sent on the source chain but not received on the destination chain
+    pub unreceived_packets: usize,
+    /// The first unreceived packet, which all the other unreceived packets wait for
+    pub blocking_packet: Option<BlockingPacket>,
+}
+
+/// The packet blocking an ordered channel, and the outcome of the simulation
+/// of the messages relaying it.
+#[derive(Clone, Debug, Serialize)]
+pub struct BlockingPacket {
+    pub sequence: Sequence,
+    /// Whether the packet has timed out, in which case it can no longer be received
+    /// on the destination chain, and timing it out on the source chain closes the channel
+    pub timed_out: bool,
+    /// The chain on which the messages relaying the packet were simulated,
+    /// ie. the destination chain, or the source chain if the packet has timed out
+    pub simulated_on: ChainId,
+    pub simulation: SimulationOutcome,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "result", rename_all = "snake_case")]
+pub enum SimulationOutcome {
+    Success {
+        gas_used: u64,
+    },
+    Failure {
+        error: String,
+        /// The ibc-go error the simulation failed with, if it could be identified
+        sdk_error: Option<String>,
+    },
+}
+
 pub struct RelayPath<ChainA: ChainHandle, ChainB: ChainHandle> {
     channel: Channel<ChainA, ChainB>,
 
@@ -313,6 +377,16 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         self.channel.ordering == Ordering::Ordered
     }
 
+    /// Synchronise the sequences of the proofs built by both chains with the clients
+    /// verifying them, before building messages, see [`sync_proof_sequence`].
+    fn sync_proof_sequences(&self) -> Result<(), LinkError> {
+        sync_proof_sequence(self.src_chain(), self.dst_chain(), self.dst_client_id())
+            .map_err(LinkError::relayer)?;
+
+        sync_proof_sequence(self.dst_chain(), self.src_chain(), self.src_client_id())
+            .map_err(LinkError::relayer)
+    }
+
     pub fn build_update_client_on_dst(&self, height: Height) -> Result<Vec<Any>, LinkError> {
         let client = self.restore_dst_client();
         client
@@ -350,6 +424,7 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         let proofs = self
             .src_chain()
             .build_channel_proofs(self.src_port_id(), src_channel_id, event.height)
+            .map(|proofs| self.channel.adapt_proofs(proofs))
             .map_err(|e| LinkError::channel(ChannelError::channel_proof(e)))?;
 
         // Build the domain type message
@@ -518,6 +593,8 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
             Some(ev) => ev.height,
         };
 
+        self.sync_proof_sequences()?;
+
         let dst_latest_info = self
             .dst_chain()
             .query_application_status()
@@ -569,6 +646,13 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
                     }
                 }
                 IbcEvent::SendPacket(ref event) => {
+                    journal::record_sent(
+                        &self.src_chain().id(),
+                        &self.dst_chain().id(),
+                        &event.packet,
+                        event_with_height.height,
+                    );
+
                     if self.send_packet_event_handled(event)? {
                         debug!(?event, "SendPacket event has already been handled");
 
@@ -578,10 +662,19 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
                             event,
                             &dst_latest_info,
                             event_with_height.height,
+                            ProofPurpose::Submission,
                         )?
                     }
                 }
                 IbcEvent::WriteAcknowledgement(ref event) => {
+                    // The packet was received on the source chain of this path
+                    journal::record_committed(
+                        &self.src_chain().id(),
+                        &self.dst_chain().id(),
+                        event_with_height,
+                        None,
+                    );
+
                     if self
                         .dst_channel(QueryHeight::Latest)?
                         .state_matches(&ChannelState::Closed)
@@ -642,7 +735,13 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         initial_od: OperationalData,
     ) -> Result<S::Reply, LinkError> {
         // We will operate on potentially different operational data if the initial one fails.
-        let _span = span!(Level::INFO, "relay", odata = %initial_od.info()).entered();
+        let _span = span!(
+            Level::INFO,
+            "relay",
+            tracking_id = %initial_od.tracking_id,
+            odata = %initial_od.info(),
+        )
+        .entered();
 
         let mut odata = initial_od;
 
@@ -674,6 +773,10 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
                 Err(LinkError(error::LinkErrorDetail::Send(e), _)) => {
                     // This error means we could retry
                     error!("error {}", e.event);
+
+                    let (chain, counterparty) = self.target_chain_ids(odata.target);
+                    journal::record_failed(&chain, &counterparty, &odata, &e.event.to_string());
+
                     if i + 1 == MAX_RETRIES {
                         error!("{}/{} retries exhausted. giving up", i + 1, MAX_RETRIES)
                     } else {
@@ -685,6 +788,9 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
                     }
                 }
                 Err(e) => {
+                    let (chain, counterparty) = self.target_chain_ids(odata.target);
+                    journal::record_failed(&chain, &counterparty, &odata, &e.to_string());
+
                     // Unrecoverable error, propagate up the stack
                     return Err(e);
                 }
@@ -798,6 +904,19 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
     }
 
     fn enqueue_pending_tx(&self, reply: AsyncReply, odata: OperationalData) {
+        let (chain, counterparty) = self.target_chain_ids(odata.target);
+
+        // The messages are only known to be in a given transaction if they fit in a single one
+        let tx_hash = match reply.responses.as_slice() {
+            [response] => Some(&response.hash),
+            _ => None,
+        };
+        journal::record_submitted(&chain, &counterparty, &odata, tx_hash);
+
+        for response in reply.responses.iter().filter(|r| r.code.is_err()) {
+            journal::record_failed(&chain, &counterparty, &odata, &response.log);
+        }
+
         if !self.confirm_txes {
             return;
         }
@@ -812,6 +931,14 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         }
     }
 
+    /// The identifiers of the chain targeted by operational data, and of its counterparty
+    fn target_chain_ids(&self, target: OperationalDataTarget) -> (ChainId, ChainId) {
+        match target {
+            OperationalDataTarget::Source => (self.src_chain().id(), self.dst_chain().id()),
+            OperationalDataTarget::Destination => (self.dst_chain().id(), self.src_chain().id()),
+        }
+    }
+
     /// Checks if a sent packet has been received on destination.
     fn send_packet_received_on_dst(&self, packet: &Packet) -> Result<bool, LinkError> {
         let unreceived_packet = self
@@ -1095,6 +1222,16 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         &self,
         opt_query_height: Option<Height>,
         tracking_id: TrackingId,
+    ) -> Result<(), LinkError> {
+        self.schedule_recv_packet_and_timeout_msgs_for(opt_query_height, tracking_id, |_| true)
+    }
+
+    /// Schedules the relaying of the unreceived packets whose sequence matches the given predicate.
+    fn schedule_recv_packet_and_timeout_msgs_for(
+        &self,
+        opt_query_height: Option<Height>,
+        tracking_id: TrackingId,
+        relay_sequence: impl Fn(&Sequence) -> bool,
     ) -> Result<(), LinkError> {
         let _span = span!(
             Level::ERROR,
@@ -1104,10 +1241,12 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         .entered();
 
         // Pull the s.n. of all packets that the destination chain has not yet received.
-        let (sequences, src_response_height) =
+        let (mut sequences, src_response_height) =
             unreceived_packets(self.dst_chain(), self.src_chain(), &self.path_id)
                 .map_err(LinkError::supervisor)?;
 
+        sequences.retain(relay_sequence);
+
         let query_height = opt_query_height.unwrap_or(src_response_height);
 
         // Skip: no relevant events found.
@@ -1154,6 +1293,17 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         &self,
         opt_query_height: Option<Height>,
         tracking_id: TrackingId,
+    ) -> Result<(), LinkError> {
+        self.schedule_packet_ack_msgs_for(opt_query_height, tracking_id, |_| true)
+    }
+
+    /// Schedules the relaying of the unreceived acknowledgements whose sequence matches
+    /// the given predicate.
+    fn schedule_packet_ack_msgs_for(
+        &self,
+        opt_query_height: Option<Height>,
+        tracking_id: TrackingId,
+        relay_sequence: impl Fn(&Sequence) -> bool,
     ) -> Result<(), LinkError> {
         let _span = span!(
             Level::ERROR,
@@ -1166,7 +1316,11 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
             unreceived_acknowledgements(self.dst_chain(), self.src_chain(), &self.path_id)
                 .map_err(LinkError::supervisor)?;
 
-        let Some((sequences, src_response_height)) = sequences_and_height else { return Ok(()) };
+        let Some((mut sequences, src_response_height)) = sequences_and_height else {
+            return Ok(());
+        };
+
+        sequences.retain(relay_sequence);
 
         let query_height = opt_query_height.unwrap_or(src_response_height);
 
@@ -1198,7 +1352,204 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         Ok(())
     }
 
-    fn build_recv_packet(&self, packet: &Packet, height: Height) -> Result<Option<Any>, LinkError> {
+    /// Estimates the cost of relaying the packet sent with the given event,
+    /// by simulating the `MsgRecvPacket` on the destination chain, or the `MsgTimeout`
+    /// on the source chain if the packet has already timed out.
+    ///
+    /// The `MsgAcknowledgement` cannot be simulated before the packet is received,
+    /// so its cost is estimated using the default gas of the source chain.
+    pub fn estimate_packet_relay_cost(
+        &self,
+        event: &SendPacket,
+        height: Height,
+    ) -> Result<PacketRelayCost, LinkError> {
+        self.sync_proof_sequences()?;
+
+        let dst_status = self
+            .dst_chain()
+            .query_application_status()
+            .map_err(|e| LinkError::query(self.dst_chain().id(), e))?;
+
+        let (recv, timeout) = self.build_recv_or_timeout_from_send_packet_event(
+            event,
+            &dst_status,
+            height,
+            ProofPurpose::Simulation,
+        )?;
+
+        // The proofs are only verified against a consensus state at the proof height,
+        // which the client update submitted along with the message installs.
+        if let Some(timeout) = timeout {
+            let mut msgs = self.build_update_client_on_src(dst_status.height.increment())?;
+            msgs.push(timeout);
+
+            let timeout_cost = Self::estimate_msgs_cost(self.src_chain(), msgs)?;
+
+            return Ok(PacketRelayCost::Timeout(timeout_cost));
+        }
+
+        let mut costs = match recv {
+            Some(recv) => {
+                let mut msgs = self.build_update_client_on_dst(height.increment())?;
+                msgs.push(recv);
+
+                Self::estimate_msgs_cost(self.dst_chain(), msgs)?
+            }
+            None => vec![],
+        };
+
+        let src_config = self
+            .src_chain()
+            .config()
+            .map_err(|e| LinkError::query(self.src_chain().id(), e))?;
+
+        let ack_fee = calculate_fee(default_gas_from_config(&src_config), &src_config.gas_price);
+        costs.push(to_raw_coin(ack_fee)?);
+
+        Ok(PacketRelayCost::RecvAndAck(costs))
+    }
+
+    /// Diagnoses an ordered channel which does not make progress, by identifying the
+    /// first packet not received on the destination chain, which blocks all the subsequent
+    /// packets, and by simulating the messages relaying it, to surface the error preventing
+    /// its relaying.
+    ///
+    /// If the packet has timed out, the simulated messages time it out on the source chain.
+    pub fn diagnose_ordered_channel(&self) -> Result<ChannelDiagnosis, LinkError> {
+        if !self.ordered_channel() {
+            return Err(LinkError::unordered_channel(
+                self.src_channel_id().clone(),
+                self.src_chain().id(),
+            ));
+        }
+
+        let (next_sequence_receive, _) = self
+            .dst_chain()
+            .query_next_sequence_receive(
+                QueryNextSequenceReceiveRequest {
+                    port_id: self.dst_port_id().clone(),
+                    channel_id: self.dst_channel_id().clone(),
+                    height: QueryHeight::Latest,
+                },
+                IncludeProof::No,
+            )
+            .map_err(|e| LinkError::query(self.dst_chain().id(), e))?;
+
+        let (sequences, src_response_height) =
+            unreceived_packets(self.dst_chain(), self.src_chain(), &self.path_id)
+                .map_err(LinkError::supervisor)?;
+
+        let Some(&sequence) = sequences.first() else {
+            return Ok(ChannelDiagnosis {
+                next_sequence_receive,
+                unreceived_packets: 0,
+                blocking_packet: None,
+            });
+        };
+
+        let (event, height) = query_packet_events_with(
+            &[sequence],
+            Qualified::SmallerEqual(src_response_height),
+            self.src_chain(),
+            &self.path_id,
+            query_send_packet_events,
+        )
+        .flatten()
+        .find_map(|event_with_height| match event_with_height.event {
+            IbcEvent::SendPacket(event) => Some((event, event_with_height.height)),
+            _ => None,
+        })
+        .ok_or_else(|| LinkError::send_packet_event_not_found(sequence, self.src_chain().id()))?;
+
+        self.sync_proof_sequences()?;
+
+        let dst_status = self
+            .dst_chain()
+            .query_application_status()
+            .map_err(|e| LinkError::query(self.dst_chain().id(), e))?;
+
+        let (recv, timeout) = self.build_recv_or_timeout_from_send_packet_event(
+            &event,
+            &dst_status,
+            height,
+            ProofPurpose::Simulation,
+        )?;
+
+        let blocking_packet = match timeout {
+            Some(timeout) => {
+                let mut msgs = self.build_update_client_on_src(dst_status.height.increment())?;
+                msgs.push(timeout);
+
+                BlockingPacket {
+                    sequence,
+                    timed_out: true,
+                    simulated_on: self.src_chain().id(),
+                    simulation: Self::simulate_msgs(self.src_chain(), msgs),
+                }
+            }
+            None => {
+                let mut msgs = self.build_update_client_on_dst(height.increment())?;
+                msgs.extend(recv);
+
+                BlockingPacket {
+                    sequence,
+                    timed_out: false,
+                    simulated_on: self.dst_chain().id(),
+                    simulation: Self::simulate_msgs(self.dst_chain(), msgs),
+                }
+            }
+        };
+
+        Ok(ChannelDiagnosis {
+            next_sequence_receive,
+            unreceived_packets: sequences.len(),
+            blocking_packet: Some(blocking_packet),
+        })
+    }
+
+    fn simulate_msgs<Chain: ChainHandle>(chain: &Chain, msgs: Vec<Any>) -> SimulationOutcome {
+        match chain.simulate_messages(TrackedMsgs::new_static(msgs, "diagnose channel")) {
+            Ok(gas_used) => SimulationOutcome::Success { gas_used },
+            Err(e) => {
+                let sdk_error = match e.detail() {
+                    ErrorDetail::GrpcStatus(detail) => {
+                        sdk_error_from_simulation_message(detail.status.message())
+                    }
+                    _ => None,
+                };
+
+                SimulationOutcome::Failure {
+                    error: e.detail().to_string(),
+                    sdk_error: sdk_error.map(|e| match e.detail() {
+                        SdkErrorDetail::Channel(detail) => detail.source.to_string(),
+                        detail => detail.to_string(),
+                    }),
+                }
+            }
+        }
+    }
+
+    /// Estimates the fee paid to submit the given messages in a single transaction.
+    ///
+    /// Fails if the estimation fails, eg. because the simulation of the messages is
+    /// rejected, as the default gas of the chain may well underestimate their cost.
+    fn estimate_msgs_cost<Chain: ChainHandle>(
+        chain: &Chain,
+        msgs: Vec<Any>,
+    ) -> Result<Vec<RawCoin>, LinkError> {
+        let fee = chain
+            .estimate_fee(TrackedMsgs::new_static(msgs, "estimate packet relay cost"))
+            .map_err(|e| LinkError::fee_estimation(chain.id(), e))?;
+
+        fee.amount.into_iter().map(to_raw_coin).collect()
+    }
+
+    fn build_recv_packet(
+        &self,
+        packet: &Packet,
+        height: Height,
+        purpose: ProofPurpose,
+    ) -> Result<Option<Any>, LinkError> {
         let proofs = self
             .src_chain()
             .build_packet_proofs(
@@ -1207,7 +1558,9 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
                 &packet.source_channel,
                 packet.sequence,
                 height,
+                purpose,
             )
+            .map(|proofs| self.channel.adapt_proofs(proofs))
             .map_err(|e| LinkError::packet_proofs_constructor(self.src_chain().id(), e))?;
 
         let msg = MsgRecvPacket::new(packet.clone(), proofs.clone(), self.dst_signer()?);
@@ -1232,7 +1585,9 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
                 &packet.destination_channel,
                 packet.sequence,
                 height,
+                ProofPurpose::Submission,
             )
+            .map(|proofs| self.channel.adapt_proofs(proofs))
             .map_err(|e| LinkError::packet_proofs_constructor(self.src_chain().id(), e))?;
 
         let msg = MsgAcknowledgement::new(
@@ -1251,6 +1606,7 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         &self,
         packet: &Packet,
         height: Height,
+        purpose: ProofPurpose,
     ) -> Result<Option<Any>, LinkError> {
         let dst_channel_id = self.dst_channel_id();
 
@@ -1282,7 +1638,9 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
                 &packet.destination_channel,
                 next_sequence_received,
                 height,
+                purpose,
             )
+            .map(|proofs| self.channel.adapt_proofs(proofs))
             .map_err(|e| LinkError::packet_proofs_constructor(self.dst_chain().id(), e))?;
 
         let msg = MsgTimeout::new(
@@ -1301,6 +1659,7 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         &self,
         packet: &Packet,
         height: Height,
+        purpose: ProofPurpose,
     ) -> Result<Option<Any>, LinkError> {
         let dst_channel_id = self.dst_channel_id();
 
@@ -1331,7 +1690,9 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
                 &packet.destination_channel,
                 next_sequence_received,
                 height,
+                purpose,
             )
+            .map(|proofs| self.channel.adapt_proofs(proofs))
             .map_err(|e| LinkError::packet_proofs_constructor(self.dst_chain().id(), e))?;
 
         let msg = MsgTimeoutOnClose::new(
@@ -1350,15 +1711,16 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         &self,
         event: &SendPacket,
         dst_info: &ChainStatus,
+        purpose: ProofPurpose,
     ) -> Result<Option<Any>, LinkError> {
         let packet = event.packet.clone();
         if self
             .dst_channel(QueryHeight::Specific(dst_info.height))?
             .state_matches(&ChannelState::Closed)
         {
-            Ok(self.build_timeout_on_close_packet(&event.packet, dst_info.height)?)
+            Ok(self.build_timeout_on_close_packet(&event.packet, dst_info.height, purpose)?)
         } else if packet.timed_out(&dst_info.timestamp, dst_info.height) {
-            Ok(self.build_timeout_packet(&event.packet, dst_info.height)?)
+            Ok(self.build_timeout_packet(&event.packet, dst_info.height, purpose)?)
         } else {
             Ok(None)
         }
@@ -1369,12 +1731,16 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         event: &SendPacket,
         dst_info: &ChainStatus,
         height: Height,
+        purpose: ProofPurpose,
     ) -> Result<(Option<Any>, Option<Any>), LinkError> {
-        let timeout = self.build_timeout_from_send_packet_event(event, dst_info)?;
+        let timeout = self.build_timeout_from_send_packet_event(event, dst_info, purpose)?;
         if timeout.is_some() {
             Ok((None, timeout))
         } else {
-            Ok((self.build_recv_packet(&event.packet, height)?, None))
+            Ok((
+                self.build_recv_packet(&event.packet, height, purpose)?,
+                None,
+            ))
         }
     }
 
@@ -1436,6 +1802,11 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
                             // The relaying process failed; return all of the subsequent pieces of operational
                             // data along with the underlying error that occurred.
                             Err(e) => {
+                                // The operational data is kept until the fee budget is replenished
+                                if e.fee_budget_resets_in().is_some() {
+                                    unprocessed.push_back(od);
+                                }
+
                                 unprocessed.extend(operations);
 
                                 return Err((unprocessed, e));
@@ -1469,7 +1840,8 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
     /// operational data items to both the source and destination chains.
     ///
     /// Any operational data items that do not get successfully relayed are
-    /// dropped. Subsequent pending operational data items that went unprocessed
+    /// dropped, unless the fee budget of the target chain is exhausted.
+    /// Subsequent pending operational data items that went unprocessed
     /// are queued up again for re-submission.
     pub fn execute_schedule(&mut self) -> Result<(), LinkError> {
         let src_od_iter = self.src_operational_data.take().into_iter();
@@ -1555,6 +1927,111 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         Ok(res)
     }
 
+    /// Persists the packets scheduled for relaying and the transactions pending
+    /// confirmation on this path, if the state store is enabled.
+    pub fn store_backlog(&self) {
+        if !store::is_enabled() {
+            return;
+        }
+
+        let scheduled = self
+            .src_operational_data
+            .clone_vec()
+            .iter()
+            .chain(self.dst_operational_data.clone_vec().iter())
+            .flat_map(|od| od.sequences())
+            .collect();
+
+        let pending_txs = self
+            .pending_txs_src
+            .stored_txs()
+            .into_iter()
+            .chain(self.pending_txs_dst.stored_txs())
+            .collect();
+
+        store::store_backlog(&StoredBacklog {
+            path: self.packet_object(),
+            scheduled,
+            pending_txs,
+        });
+    }
+
+    /// Reschedules the packets left to relay before the relayer was restarted, according
+    /// to the backlog persisted in the state store, if any: the packets which were scheduled
+    /// but not submitted, and the packets whose transactions were not committed.
+    ///
+    /// Returns `true` if the packets could not be rescheduled, in which case the pending
+    /// packets must be cleared on this path.
+    pub fn recover_backlog(&self) -> bool {
+        let backlog = match store::take_backlog(&self.packet_object()) {
+            Some(backlog) => backlog,
+            None => return false,
+        };
+
+        let mut sequences = backlog.scheduled;
+
+        for tx in &backlog.pending_txs {
+            let committed = if tx.chain_id == self.src_chain().id() {
+                self.pending_txs_src.is_committed(tx)
+            } else {
+                self.pending_txs_dst.is_committed(tx)
+            };
+
+            match committed {
+                Ok(true) => {}
+                Ok(false) => sequences.extend(&tx.sequences),
+                Err(e) => {
+                    warn!(
+                        "failed to check whether the transactions {:?} were committed on chain {}, resubmitting them: {}",
+                        tx.tx_hashes, tx.chain_id, e
+                    );
+
+                    sequences.extend(&tx.sequences);
+                }
+            }
+        }
+
+        if sequences.is_empty() {
+            return false;
+        }
+
+        sequences.sort();
+        sequences.dedup();
+
+        info!(
+            total = sequences.len(),
+            sequences = %sequences.iter().copied().collated().format(", "),
+            "rescheduling the packets left to relay before the restart",
+        );
+
+        let tracking_id = TrackingId::new_cleared_uuid();
+        let relay_sequence = |sequence: &Sequence| sequences.binary_search(sequence).is_ok();
+
+        let rescheduled = self
+            .schedule_recv_packet_and_timeout_msgs_for(None, tracking_id, relay_sequence)
+            .and_then(|()| self.schedule_packet_ack_msgs_for(None, tracking_id, relay_sequence));
+
+        match rescheduled {
+            Ok(()) => {
+                self.store_backlog();
+                false
+            }
+            Err(e) => {
+                error!("failed to reschedule the packets left to relay before the restart: {e}");
+                true
+            }
+        }
+    }
+
+    fn packet_object(&self) -> object::Packet {
+        object::Packet {
+            dst_chain_id: self.dst_chain().id(),
+            src_chain_id: self.src_chain().id(),
+            src_channel_id: self.src_channel_id().clone(),
+            src_port_id: self.src_port_id().clone(),
+        }
+    }
+
     /// Refreshes the scheduled batches.
     /// Verifies if any sendPacket messages timed-out. If so, moves them from destination op. data
     /// to source operational data, and adjusts the events and messages accordingly.
@@ -1566,6 +2043,8 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
             return Ok(());
         }
 
+        self.sync_proof_sequences()?;
+
         let dst_status = self
             .dst_chain()
             .query_application_status()
@@ -1594,9 +2073,11 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
                         // Catch any SendPacket event that timed-out
                         if self.send_packet_event_handled(event)? {
                             debug!(?event, "SendPacket event has already been handled");
-                        } else if let Some(new_msg) =
-                            self.build_timeout_from_send_packet_event(event, &dst_status)?
-                        {
+                        } else if let Some(new_msg) = self.build_timeout_from_send_packet_event(
+                            event,
+                            &dst_status,
+                            ProofPurpose::Submission,
+                        )? {
                             debug!(
                                 "found a timed-out message in the operational data: {}",
                                 odata.info(),
@@ -1779,8 +2260,8 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         &self,
         target: OperationalDataTarget,
     ) -> (
-        ibc_relayer_types::core::ics24_host::identifier::ChainId, // source chain
-        ibc_relayer_types::core::ics24_host::identifier::ChainId, // destination chain
+        ChainId, // source chain
+        ChainId, // destination chain
         &ChannelId,
         &PortId,
     ) {
@@ -1875,3 +2356,7 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         }
     }
 }
+
+fn to_raw_coin(coin: ProtoCoin) -> Result<RawCoin, LinkError> {
+    RawCoin::try_from(coin).map_err(|e| LinkError::packet(TransferError::token_transfer(e)))
+}
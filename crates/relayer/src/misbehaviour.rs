@@ -1,4 +1,7 @@
 use ibc_proto::{google::protobuf::Any, protobuf::Protobuf};
+use ibc_relayer_types::clients::ics06_solomachine::misbehaviour::{
+    Misbehaviour as SoloMisbehaviour, SOLOMACHINE_MISBEHAVIOUR_TYPE_URL,
+};
 use ibc_relayer_types::clients::ics07_tendermint::misbehaviour::{
     Misbehaviour as TmMisbehaviour, TENDERMINT_MISBEHAVIOR_TYPE_URL,
 };
@@ -6,9 +9,9 @@ use ibc_relayer_types::core::{
     ics02_client::{error::Error, misbehaviour::Misbehaviour},
     ics24_host::identifier::ClientId,
 };
-#[cfg(test)]
+#[cfg(any(test, feature = "mock"))]
 use ibc_relayer_types::mock::misbehaviour::Misbehaviour as MockMisbehaviour;
-#[cfg(test)]
+#[cfg(any(test, feature = "mock"))]
 use ibc_relayer_types::mock::misbehaviour::MOCK_MISBEHAVIOUR_TYPE_URL;
 use ibc_relayer_types::Height;
 use serde::{Deserialize, Serialize};
@@ -25,8 +28,9 @@ pub struct MisbehaviourEvidence {
 #[allow(clippy::large_enum_variant)]
 pub enum AnyMisbehaviour {
     Tendermint(TmMisbehaviour),
+    Solomachine(SoloMisbehaviour),
 
-    #[cfg(test)]
+    #[cfg(any(test, feature = "mock"))]
     Mock(MockMisbehaviour),
 }
 
@@ -34,8 +38,9 @@ impl Misbehaviour for AnyMisbehaviour {
     fn client_id(&self) -> &ClientId {
         match self {
             Self::Tendermint(misbehaviour) => misbehaviour.client_id(),
+            Self::Solomachine(misbehaviour) => misbehaviour.client_id(),
 
-            #[cfg(test)]
+            #[cfg(any(test, feature = "mock"))]
             Self::Mock(misbehaviour) => misbehaviour.client_id(),
         }
     }
@@ -43,8 +48,9 @@ impl Misbehaviour for AnyMisbehaviour {
     fn height(&self) -> Height {
         match self {
             Self::Tendermint(misbehaviour) => misbehaviour.height(),
+            Self::Solomachine(misbehaviour) => misbehaviour.height(),
 
-            #[cfg(test)]
+            #[cfg(any(test, feature = "mock"))]
             Self::Mock(misbehaviour) => misbehaviour.height(),
         }
     }
@@ -61,7 +67,11 @@ impl TryFrom<Any> for AnyMisbehaviour {
                 TmMisbehaviour::decode_vec(&raw.value).map_err(Error::decode_raw_misbehaviour)?,
             )),
 
-            #[cfg(test)]
+            SOLOMACHINE_MISBEHAVIOUR_TYPE_URL => Ok(AnyMisbehaviour::Solomachine(
+                SoloMisbehaviour::decode_vec(&raw.value).map_err(Error::decode_raw_misbehaviour)?,
+            )),
+
+            #[cfg(any(test, feature = "mock"))]
             MOCK_MISBEHAVIOUR_TYPE_URL => Ok(AnyMisbehaviour::Mock(
                 MockMisbehaviour::decode_vec(&raw.value).map_err(Error::decode_raw_misbehaviour)?,
             )),
@@ -79,7 +89,12 @@ impl From<AnyMisbehaviour> for Any {
                 value: misbehaviour.encode_vec(),
             },
 
-            #[cfg(test)]
+            AnyMisbehaviour::Solomachine(misbehaviour) => Any {
+                type_url: SOLOMACHINE_MISBEHAVIOUR_TYPE_URL.to_string(),
+                value: misbehaviour.encode_vec(),
+            },
+
+            #[cfg(any(test, feature = "mock"))]
             AnyMisbehaviour::Mock(misbehaviour) => Any {
                 type_url: MOCK_MISBEHAVIOUR_TYPE_URL.to_string(),
                 value: misbehaviour.encode_vec(),
@@ -92,8 +107,9 @@ impl core::fmt::Display for AnyMisbehaviour {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> Result<(), core::fmt::Error> {
         match self {
             AnyMisbehaviour::Tendermint(tm) => write!(f, "{tm}"),
+            AnyMisbehaviour::Solomachine(solo) => write!(f, "{solo}"),
 
-            #[cfg(test)]
+            #[cfg(any(test, feature = "mock"))]
             AnyMisbehaviour::Mock(mock) => write!(f, "{mock:?}"),
         }
     }
@@ -105,7 +121,13 @@ impl From<TmMisbehaviour> for AnyMisbehaviour {
     }
 }
 
-#[cfg(test)]
+impl From<SoloMisbehaviour> for AnyMisbehaviour {
+    fn from(misbehaviour: SoloMisbehaviour) -> Self {
+        Self::Solomachine(misbehaviour)
+    }
+}
+
+#[cfg(any(test, feature = "mock"))]
 impl From<MockMisbehaviour> for AnyMisbehaviour {
     fn from(misbehaviour: MockMisbehaviour) -> Self {
         Self::Mock(misbehaviour)
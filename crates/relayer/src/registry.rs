@@ -81,6 +81,12 @@ impl<Chain: ChainHandle> Registry<Chain> {
         }
     }
 
+    /// Replace the configuration used to spawn the chain runtimes,
+    /// without affecting the runtimes which are already spawned.
+    pub fn update_config(&mut self, config: Config) {
+        self.config = config;
+    }
+
     /// Shutdown the runtime associated with the given chain identifier.
     pub fn shutdown(&mut self, chain_id: &ChainId) {
         if let Some(handle) = self.handles.remove(chain_id) {
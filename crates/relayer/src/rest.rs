@@ -1,11 +1,16 @@
 use crossbeam_channel::TryRecvError;
 use tracing::{error, trace};
 
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, ClientId, PortId};
+
 use crate::{
     config::Config,
+    link::journal::packet_trace,
+    object::Object,
     rest::request::ReplySender,
     rest::request::{Request, VersionInfo},
-    supervisor::dump_state::SupervisorState,
+    supervisor::{dump_state::SupervisorState, reload::ConfigChanges},
+    worker::{wallet::low_balance_alerts, PausePolicy},
 };
 
 pub mod request;
@@ -26,11 +31,41 @@ pub type Receiver = crossbeam_channel::Receiver<Request>;
 
 // TODO: Unify this enum with `SupervisorCmd`
 //  We won't unify yet as it is possible we will never implement
-//  REST API `/chain` adding endpoint; instead of `/chain` we might
-//  implement `/reload` for supporting a broader range of functionality
-//  e.g., adjusting chain config, removing chains, etc.
+//  REST API `/chain` adding endpoint; instead, `/reload` supports
+//  a broader range of functionality, e.g., adjusting chain config,
+//  removing chains, etc.
 pub enum Command {
     DumpState(ReplySender<SupervisorState>),
+    ReloadConfig(ReplySender<ConfigChanges>),
+    ClearPackets {
+        chain_id: ChainId,
+        port_id: PortId,
+        channel_id: ChannelId,
+        reply_to: ReplySender<Vec<Object>>,
+    },
+    UpdateClient {
+        chain_id: ChainId,
+        client_id: ClientId,
+        reply_to: ReplySender<()>,
+    },
+    PauseWorker {
+        object: Object,
+        policy: PausePolicy,
+        reply_to: ReplySender<()>,
+    },
+    ResumeWorker {
+        object: Object,
+        reply_to: ReplySender<()>,
+    },
+    PauseChain {
+        chain_id: ChainId,
+        policy: PausePolicy,
+        reply_to: ReplySender<()>,
+    },
+    ResumeChain {
+        chain_id: ChainId,
+        reply_to: ReplySender<()>,
+    },
 }
 
 /// Process incoming REST requests.
@@ -77,11 +112,103 @@ pub fn process_incoming_requests(config: &Config, channel: &Receiver) -> Option<
                     .unwrap_or_else(|e| error!("error replying to a REST request {}", e));
             }
 
+            Request::LowBalanceAlerts { reply_to } => {
+                trace!("LowBalanceAlerts");
+
+                reply_to
+                    .send(Ok(low_balance_alerts()))
+                    .unwrap_or_else(|e| error!("error replying to a REST request {}", e));
+            }
+
+            Request::PacketTrace { key, reply_to } => {
+                trace!("PacketTrace {:?}", key);
+
+                reply_to
+                    .send(Ok(packet_trace(&key)))
+                    .unwrap_or_else(|e| error!("error replying to a REST request {}", e));
+            }
+
             Request::State { reply_to } => {
                 trace!("State");
 
                 return Some(Command::DumpState(reply_to));
             }
+
+            Request::ReloadConfig { reply_to } => {
+                trace!("ReloadConfig");
+
+                return Some(Command::ReloadConfig(reply_to));
+            }
+
+            Request::ClearPackets {
+                chain_id,
+                port_id,
+                channel_id,
+                reply_to,
+            } => {
+                trace!("ClearPackets {}/{} on {}", port_id, channel_id, chain_id);
+
+                return Some(Command::ClearPackets {
+                    chain_id,
+                    port_id,
+                    channel_id,
+                    reply_to,
+                });
+            }
+
+            Request::UpdateClient {
+                chain_id,
+                client_id,
+                reply_to,
+            } => {
+                trace!("UpdateClient {} on {}", client_id, chain_id);
+
+                return Some(Command::UpdateClient {
+                    chain_id,
+                    client_id,
+                    reply_to,
+                });
+            }
+
+            Request::PauseWorker {
+                object,
+                policy,
+                reply_to,
+            } => {
+                trace!("PauseWorker {} ({})", object.short_name(), policy);
+
+                return Some(Command::PauseWorker {
+                    object,
+                    policy,
+                    reply_to,
+                });
+            }
+
+            Request::ResumeWorker { object, reply_to } => {
+                trace!("ResumeWorker {}", object.short_name());
+
+                return Some(Command::ResumeWorker { object, reply_to });
+            }
+
+            Request::PauseChain {
+                chain_id,
+                policy,
+                reply_to,
+            } => {
+                trace!("PauseChain {} ({})", chain_id, policy);
+
+                return Some(Command::PauseChain {
+                    chain_id,
+                    policy,
+                    reply_to,
+                });
+            }
+
+            Request::ResumeChain { chain_id, reply_to } => {
+                trace!("ResumeChain {}", chain_id);
+
+                return Some(Command::ResumeChain { chain_id, reply_to });
+            }
         },
         Err(e) => {
             if !matches!(e, TryRecvError::Empty) {
@@ -23,6 +23,18 @@ pub enum RestApiError {
     #[error("failed while parsing the request body into a chain configuration: {0}")]
     InvalidChainConfig(String),
 
+    #[error("failed to reload the configuration: {0}")]
+    ConfigReload(String),
+
+    #[error("{0}")]
+    Supervisor(String),
+
+    #[error("missing or invalid bearer token")]
+    Unauthorized,
+
+    #[error("write endpoints are disabled as no authentication token is configured")]
+    WriteDisabled,
+
     #[error("not implemented")]
     Unimplemented,
 }
@@ -36,9 +48,17 @@ impl RestApiError {
             RestApiError::ChainConfigNotFound(_) => "ChainConfigNotFound",
             RestApiError::InvalidChainId(_, _) => "InvalidChainId",
             RestApiError::InvalidChainConfig(_) => "InvalidChainConfig",
+            RestApiError::ConfigReload(_) => "ConfigReload",
+            RestApiError::Supervisor(_) => "Supervisor",
+            RestApiError::Unauthorized => "Unauthorized",
+            RestApiError::WriteDisabled => "WriteDisabled",
             RestApiError::Unimplemented => "Unimplemented",
         }
     }
+
+    pub fn supervisor(e: impl core::fmt::Display) -> Self {
+        RestApiError::Supervisor(e.to_string())
+    }
 }
 
 impl Serialize for RestApiError {
@@ -1,8 +1,15 @@
 use serde::{Deserialize, Serialize};
 
-use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, ClientId, PortId};
 
-use crate::{config::ChainConfig, rest::RestApiError, supervisor::dump_state::SupervisorState};
+use crate::{
+    config::ChainConfig,
+    link::journal::{PacketKey, PacketTrace},
+    object::Object,
+    rest::RestApiError,
+    supervisor::{dump_state::SupervisorState, reload::ConfigChanges},
+    worker::{wallet::LowBalanceAlert, PausePolicy},
+};
 
 pub type ReplySender<T> = crossbeam_channel::Sender<Result<T, RestApiError>>;
 pub type ReplyReceiver<T> = crossbeam_channel::Receiver<Result<T, RestApiError>>;
@@ -36,4 +43,52 @@ pub enum Request {
         chain_id: ChainId,
         reply_to: ReplySender<ChainConfig>,
     },
+
+    LowBalanceAlerts {
+        reply_to: ReplySender<Vec<LowBalanceAlert>>,
+    },
+
+    PacketTrace {
+        key: PacketKey,
+        reply_to: ReplySender<Option<PacketTrace>>,
+    },
+
+    ReloadConfig {
+        reply_to: ReplySender<ConfigChanges>,
+    },
+
+    ClearPackets {
+        chain_id: ChainId,
+        port_id: PortId,
+        channel_id: ChannelId,
+        reply_to: ReplySender<Vec<Object>>,
+    },
+
+    UpdateClient {
+        chain_id: ChainId,
+        client_id: ClientId,
+        reply_to: ReplySender<()>,
+    },
+
+    PauseWorker {
+        object: Object,
+        policy: PausePolicy,
+        reply_to: ReplySender<()>,
+    },
+
+    ResumeWorker {
+        object: Object,
+        reply_to: ReplySender<()>,
+    },
+
+    PauseChain {
+        chain_id: ChainId,
+        policy: PausePolicy,
+        reply_to: ReplySender<()>,
+    },
+
+    ResumeChain {
+        chain_id: ChainId,
+        reply_to: ReplySender<()>,
+    },
 }
@@ -8,6 +8,10 @@ define_error! {
             [ ClientError ]
             |_| { "ICS02 Client Error" },
 
+        Channel
+            [ ChannelError ]
+            |_| { "ICS04 Channel Error" },
+
         UnexpectedOk
             |_| { "expected error code, instead got Ok" },
 
@@ -126,6 +130,89 @@ define_error! {
     }
 }
 
+define_error! {
+    ChannelError {
+        ChannelAlreadyExists
+            |_| { "channel already exists" },
+
+        ChannelNotFound
+            |_| { "channel not found" },
+
+        InvalidChannel
+            |_| { "invalid channel" },
+
+        InvalidChannelState
+            |_| { "invalid channel state" },
+
+        InvalidChannelOrdering
+            |_| { "invalid channel ordering" },
+
+        InvalidCounterparty
+            |_| { "invalid counterparty channel" },
+
+        InvalidChannelCapability
+            |_| { "invalid channel capability" },
+
+        ChannelCapabilityNotFound
+            |_| { "channel capability not found" },
+
+        SequenceSendNotFound
+            |_| { "sequence send not found" },
+
+        SequenceReceiveNotFound
+            |_| { "sequence receive not found" },
+
+        SequenceAckNotFound
+            |_| { "sequence acknowledgement not found" },
+
+        InvalidPacket
+            |_| { "invalid packet" },
+
+        PacketTimeout
+            |_| { "packet timeout" },
+
+        TooManyConnectionHops
+            |_| { "too many connection hops" },
+
+        InvalidAcknowledgement
+            |_| { "invalid acknowledgement" },
+
+        AcknowledgementExists
+            |_| { "acknowledgement for packet already exists" },
+
+        InvalidChannelIdentifier
+            |_| { "invalid channel identifier" },
+
+        PacketReceived
+            |_| { "packet already received" },
+
+        PacketCommitmentNotFound
+            |_| { "packet commitment not found" },
+
+        PacketSequenceOutOfOrder
+            |_| { "packet sequence is out of order" },
+
+        RedundantTx
+            |_| { "packet messages are redundant" },
+
+        NoOpMsg
+            |_| { "message is redundant, no-op will be performed" },
+
+        InvalidChannelVersion
+            |_| { "invalid channel version" },
+
+        PacketNotSent
+            |_| { "packet has not been sent" },
+
+        InvalidTimeout
+            |_| { "invalid packet timeout" },
+
+        UnknownChannel
+            { code: u32 }
+            |e| { format!("unknown channel error: {}", e.code) },
+    }
+}
+
 // The error code mapping follows the Go code at
 // ibc-go/modules/core/02-client/types/errors.go
 fn client_error_from_code(code: u32) -> ClientError {
@@ -162,6 +249,39 @@ fn client_error_from_code(code: u32) -> ClientError {
     }
 }
 
+// The error code mapping follows the Go code at
+// ibc-go/modules/core/04-channel/types/errors.go
+fn channel_error_from_code(code: u32) -> ChannelError {
+    match code {
+        2 => ChannelError::channel_already_exists(),
+        3 => ChannelError::channel_not_found(),
+        4 => ChannelError::invalid_channel(),
+        5 => ChannelError::invalid_channel_state(),
+        6 => ChannelError::invalid_channel_ordering(),
+        7 => ChannelError::invalid_counterparty(),
+        8 => ChannelError::invalid_channel_capability(),
+        9 => ChannelError::channel_capability_not_found(),
+        10 => ChannelError::sequence_send_not_found(),
+        11 => ChannelError::sequence_receive_not_found(),
+        12 => ChannelError::sequence_ack_not_found(),
+        13 => ChannelError::invalid_packet(),
+        14 => ChannelError::packet_timeout(),
+        15 => ChannelError::too_many_connection_hops(),
+        16 => ChannelError::invalid_acknowledgement(),
+        17 => ChannelError::acknowledgement_exists(),
+        18 => ChannelError::invalid_channel_identifier(),
+        19 => ChannelError::packet_received(),
+        20 => ChannelError::packet_commitment_not_found(),
+        21 => ChannelError::packet_sequence_out_of_order(),
+        22 => ChannelError::redundant_tx(),
+        23 => ChannelError::no_op_msg(),
+        24 => ChannelError::invalid_channel_version(),
+        25 => ChannelError::packet_not_sent(),
+        26 => ChannelError::invalid_timeout(),
+        _ => ChannelError::unknown_channel(code),
+    }
+}
+
 // Converts the error in a CheckTx or DeliverTx result into SdkError with the same
 // mapping as defined in ibc-go and cosmos-sdk. This assumes the
 // target chain we are interacting with are using cosmos-sdk and ibc-go.
@@ -174,6 +294,8 @@ pub fn sdk_error_from_tx_result(code: Code, codespace: &str) -> SdkError {
         Code::Err(code) => {
             if codespace == "client" {
                 SdkError::client(client_error_from_code(code.into()))
+            } else if codespace == "channel" {
+                SdkError::channel(channel_error_from_code(code.into()))
             } else {
                 // TODO: Implement mapping for other codespaces in ibc-go
                 SdkError::unknown_sdk(codespace.to_owned(), code.into())
@@ -182,6 +304,25 @@ pub fn sdk_error_from_tx_result(code: Code, codespace: &str) -> SdkError {
     }
 }
 
+/// Recovers the channel error which made the simulation of a transaction fail,
+/// from the message of the error returned by the simulation.
+///
+/// Simulation errors only carry the error message, in which ibc-go wraps the
+/// description of the registered error with the details of the failure, eg.
+/// `"...: packet sequence ≠ next receive sequence (5 ≠ 4): packet sequence is out of order"`.
+pub fn sdk_error_from_simulation_message(message: &str) -> Option<SdkError> {
+    let description = message.rsplit(": ").next()?;
+
+    // Take the longest matching description, as some descriptions are
+    // the prefix of others, eg. "invalid packet" and "invalid packet timeout"
+    (2..=26)
+        .map(channel_error_from_code)
+        .map(|e| (e.detail().to_string(), e))
+        .filter(|(registered, _)| description.starts_with(registered.as_str()))
+        .max_by_key(|(registered, _)| registered.len())
+        .map(|(_, e)| SdkError::channel(e))
+}
+
 /// Converts error codes originating from `broadcast_tx_sync` responses
 /// into IBC relayer domain-type errors.
 /// See [`tendermint_rpc::endpoint::broadcast::tx_sync::Response`].
@@ -196,3 +337,45 @@ pub fn sdk_error_from_tx_sync_error_code(code: u32) -> SdkError {
         _ => SdkError::unknown_tx_sync(code),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn channel_error_from_tx_result() {
+        let error = sdk_error_from_tx_result(Code::Err(21.try_into().unwrap()), "channel");
+
+        assert!(matches!(
+            error.detail(),
+            SdkErrorDetail::Channel(e)
+                if matches!(e.source, ChannelErrorDetail::PacketSequenceOutOfOrder(_))
+        ));
+    }
+
+    #[test]
+    fn channel_error_from_simulation_message() {
+        let error = sdk_error_from_simulation_message(
+            "failed to execute message; message index: 1: \
+            packet sequence ≠ next receive sequence (5 ≠ 4): packet sequence is out of order",
+        )
+        .unwrap();
+        assert!(matches!(
+            error.detail(),
+            SdkErrorDetail::Channel(e)
+                if matches!(e.source, ChannelErrorDetail::PacketSequenceOutOfOrder(_))
+        ));
+
+        let error = sdk_error_from_simulation_message(
+            "failed to execute message; message index: 1: \
+            timeout height and timeout timestamp cannot both be 0: invalid packet timeout",
+        )
+        .unwrap();
+        assert!(matches!(
+            error.detail(),
+            SdkErrorDetail::Channel(e) if matches!(e.source, ChannelErrorDetail::InvalidTimeout(_))
+        ));
+
+        assert!(sdk_error_from_simulation_message("account sequence mismatch").is_none());
+    }
+}
@@ -6,11 +6,17 @@ use tokio::runtime::Runtime as TokioRuntime;
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 
 use crate::{
-    chain::{cosmos::CosmosSdkChain, handle::ChainHandle, runtime::ChainRuntime, ChainType},
+    chain::{
+        cosmos::CosmosSdkChain, handle::ChainHandle, runtime::ChainRuntime,
+        solomachine::SoloMachine, ChainType,
+    },
     config::Config,
     error::Error as RelayerError,
 };
 
+#[cfg(any(test, feature = "mock"))]
+use crate::chain::mock::MockChain;
+
 define_error! {
     SpawnError {
         Relayer
@@ -25,6 +31,12 @@ define_error! {
             | e | {
                 format_args!("missing chain config for '{}' in configuration file", e.chain_id)
             },
+
+        MockChainsDisabled
+            { chain_id: ChainId }
+            | e | {
+                format_args!("chain '{}' is a mock chain, but the relayer was built without the `mock` feature", e.chain_id)
+            },
     }
 }
 
@@ -54,6 +66,14 @@ pub fn spawn_chain_runtime<Handle: ChainHandle>(
 
     let handle = match chain_config.r#type {
         ChainType::CosmosSdk => ChainRuntime::<CosmosSdkChain>::spawn::<Handle>(chain_config, rt),
+
+        #[cfg(any(test, feature = "mock"))]
+        ChainType::Mock => ChainRuntime::<MockChain>::spawn::<Handle>(chain_config, rt),
+
+        #[cfg(not(any(test, feature = "mock")))]
+        ChainType::Mock => return Err(SpawnError::mock_chains_disabled(chain_id.clone())),
+
+        ChainType::SoloMachine => ChainRuntime::<SoloMachine>::spawn::<Handle>(chain_config, rt),
     }
     .map_err(SpawnError::relayer)?;
 
@@ -0,0 +1,484 @@
+//! Persistent store of the state of the relayer, which lets it resume relaying
+//! after a restart instead of clearing all the pending packets on start.
+//!
+//! The store records the last height processed on each chain, as well as the packets
+//! scheduled for relaying and the transactions pending confirmation on each path.
+//!
+//! A height is only recorded as processed once every worker its events were dispatched to
+//! has persisted the packets it scheduled for relaying, and once all the lower heights of
+//! the same chain were processed as well, so that no event is lost if the relayer stops
+//! in the meantime. If the events at some height are not relayed, e.g. because scheduling
+//! them failed or because they were dropped by a paused worker, the last processed height
+//! of the chain is kept below that height until the relayer restarts and replays them.
+
+pub mod error;
+mod sled_store;
+
+pub use error::Error;
+pub use sled_store::SledStore;
+
+use core::time::Duration;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use once_cell::sync::{Lazy, OnceCell};
+use serde::{Deserialize, Serialize};
+use tendermint::Hash as TxHash;
+use tracing::{debug, info, warn};
+
+use ibc_relayer_types::core::ics02_client::height::Height;
+use ibc_relayer_types::core::ics04_channel::packet::Sequence;
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+use crate::config::StateStoreConfig;
+use crate::object::Packet;
+
+pub const STATE_STORE_DEFAULT_FOLDER: &str = ".hermes/state/";
+
+/// The last height processed by the relayer on a chain
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoredHeight {
+    pub height: Height,
+
+    /// When the height was processed, in seconds since the Unix epoch
+    pub processed_at: u64,
+}
+
+/// Transactions submitted by the relayer but not confirmed yet
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoredTx {
+    /// The chain the transactions were submitted to
+    pub chain_id: ChainId,
+    pub tx_hashes: Vec<TxHash>,
+
+    /// The sequences of the packets relayed by the transactions
+    pub sequences: Vec<Sequence>,
+}
+
+/// The packets which were not relayed yet on a path
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoredBacklog {
+    pub path: Packet,
+
+    /// The sequences of the packets whose messages are scheduled but not submitted yet
+    pub scheduled: Vec<Sequence>,
+
+    /// The transactions pending confirmation
+    pub pending_txs: Vec<StoredTx>,
+}
+
+impl StoredBacklog {
+    pub fn is_empty(&self) -> bool {
+        self.scheduled.is_empty() && self.pending_txs.is_empty()
+    }
+}
+
+/// A store persisting the state of the relayer across restarts
+pub trait RelayStore: Send + Sync {
+    fn last_height(&self, chain_id: &ChainId) -> Result<Option<StoredHeight>, Error>;
+
+    fn set_last_height(&self, chain_id: &ChainId, height: &StoredHeight) -> Result<(), Error>;
+
+    fn backlog(&self, path: &Packet) -> Result<Option<StoredBacklog>, Error>;
+
+    /// Store the backlog of a path, or remove it if it is empty
+    fn set_backlog(&self, backlog: &StoredBacklog) -> Result<(), Error>;
+
+    fn remove_backlog(&self, path: &Packet) -> Result<(), Error>;
+}
+
+struct GlobalStore {
+    store: Box<dyn RelayStore>,
+    max_resume_age: Duration,
+}
+
+static STORE: OnceCell<GlobalStore> = OnceCell::new();
+
+/// The heights dispatched to the workers on each chain, but not processed yet
+static DISPATCHED: Lazy<Mutex<HashMap<ChainId, DispatchedHeights>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Open the state store, if it is enabled in the given configuration.
+///
+/// The store is shared by the whole process, and subsequent calls have no effect.
+pub fn init(config: &StateStoreConfig) -> Result<(), Error> {
+    if !config.enabled || STORE.get().is_some() {
+        return Ok(());
+    }
+
+    let path = match &config.path {
+        Some(path) => path.clone(),
+        None => {
+            let home = dirs_next::home_dir().ok_or_else(Error::home_location_unavailable)?;
+            home.join(STATE_STORE_DEFAULT_FOLDER)
+        }
+    };
+
+    let store = SledStore::open(&path)?;
+
+    info!(path = %path.display(), "opened state store");
+
+    let _ = STORE.set(GlobalStore {
+        store: Box::new(store),
+        max_resume_age: config.max_resume_age,
+    });
+
+    Ok(())
+}
+
+/// Whether the state store is enabled
+pub fn is_enabled() -> bool {
+    STORE.get().is_some()
+}
+
+/// A guard recording that the events of a chain at some height were processed once it,
+/// along with all its clones, is marked as [processed](HeightGuard::processed).
+///
+/// The supervisor creates a guard for every batch of events, and hands a clone of it to each
+/// packet worker the events are dispatched to, which marks it as processed once it has persisted
+/// its schedule. If any clone is dropped without being marked as processed, the events are
+/// considered as not relayed, and the height is never recorded as processed.
+#[derive(Clone, Debug, Default)]
+pub struct HeightGuard {
+    height: Option<Arc<DispatchedHeight>>,
+    processed: bool,
+}
+
+impl HeightGuard {
+    /// Track the processing of the events of the given chain at the given height,
+    /// if the state store is enabled
+    pub fn new(chain_id: &ChainId, height: Height) -> Self {
+        if !is_enabled() {
+            return Self::default();
+        }
+
+        if let Ok(mut dispatched) = DISPATCHED.lock() {
+            dispatched
+                .entry(chain_id.clone())
+                .or_default()
+                .dispatch(height);
+        }
+
+        Self {
+            height: Some(Arc::new(DispatchedHeight {
+                chain_id: chain_id.clone(),
+                height,
+                abandoned: AtomicBool::new(false),
+            })),
+            processed: false,
+        }
+    }
+
+    /// Mark the events as processed by the holder of this guard
+    pub fn processed(mut self) {
+        self.processed = true;
+    }
+}
+
+impl Drop for HeightGuard {
+    fn drop(&mut self) {
+        if let Some(height) = &self.height {
+            if !self.processed {
+                height.abandoned.store(true, Ordering::Relaxed);
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+struct DispatchedHeight {
+    chain_id: ChainId,
+    height: Height,
+    /// Whether some guard was dropped without its events being processed
+    abandoned: AtomicBool,
+}
+
+impl Drop for DispatchedHeight {
+    fn drop(&mut self) {
+        // Keep the lock while recording the height, so that concurrent workers
+        // cannot record heights out of order
+        if let Ok(mut dispatched) = DISPATCHED.lock() {
+            let Some(heights) = dispatched.get_mut(&self.chain_id) else {
+                return;
+            };
+
+            let processed = if self.abandoned.load(Ordering::Relaxed) {
+                debug!(
+                    chain = %self.chain_id, height = %self.height,
+                    "events were not relayed, keeping the last processed height below their height"
+                );
+
+                heights.abandon(self.height)
+            } else {
+                heights.process(self.height)
+            };
+
+            if let Some(height) = processed {
+                record_height(&self.chain_id, height);
+            }
+        }
+    }
+}
+
+/// The heights of a chain whose events are being processed by the workers
+#[derive(Debug, Default)]
+struct DispatchedHeights {
+    /// The number of guards for each height which were not dropped yet
+    pending: BTreeMap<Height, usize>,
+
+    /// The heights which were processed, but not recorded yet
+    /// because some lower height is still pending
+    processed: BTreeSet<Height>,
+
+    /// The lowest height whose events were not relayed, above which
+    /// no height can be recorded as processed anymore
+    abandoned: Option<Height>,
+}
+
+impl DispatchedHeights {
+    fn dispatch(&mut self, height: Height) {
+        *self.pending.entry(height).or_default() += 1;
+    }
+
+    /// Mark the given height as processed, and return the highest height which can be
+    /// recorded as processed, if any
+    fn process(&mut self, height: Height) -> Option<Height> {
+        self.complete(height);
+
+        if self.abandoned.map_or(true, |abandoned| height < abandoned) {
+            self.processed.insert(height);
+        }
+
+        self.recordable()
+    }
+
+    /// Mark the events at the given height as not relayed, and return the highest height
+    /// which can be recorded as processed, if any
+    fn abandon(&mut self, height: Height) -> Option<Height> {
+        self.complete(height);
+
+        let abandoned = self
+            .abandoned
+            .map_or(height, |abandoned| abandoned.min(height));
+
+        self.abandoned = Some(abandoned);
+        self.processed.retain(|processed| *processed < abandoned);
+
+        self.recordable()
+    }
+
+    fn complete(&mut self, height: Height) {
+        if let Some(count) = self.pending.get_mut(&height) {
+            *count -= 1;
+
+            if *count == 0 {
+                self.pending.remove(&height);
+            }
+        }
+    }
+
+    fn recordable(&mut self) -> Option<Height> {
+        let lowest_pending = self.pending.keys().next().copied();
+
+        let recorded = match lowest_pending.into_iter().chain(self.abandoned).min() {
+            Some(bound) => self.processed.range(..bound).next_back(),
+            None => self.processed.iter().next_back(),
+        }
+        .copied()?;
+
+        self.processed = self.processed.split_off(&recorded.increment());
+
+        Some(recorded)
+    }
+}
+
+/// Record the last height processed on the given chain
+fn record_height(chain_id: &ChainId, height: Height) {
+    if let Some(global) = STORE.get() {
+        let stored = StoredHeight {
+            height,
+            processed_at: now(),
+        };
+
+        if let Err(e) = global.store.set_last_height(chain_id, &stored) {
+            warn!(chain = %chain_id, "failed to store the last processed height: {e}");
+        }
+    }
+}
+
+/// The height from which the relayer can resume processing the events of the given chain,
+/// if it processed events of that chain recently enough
+pub fn resume_height(chain_id: &ChainId) -> Option<Height> {
+    let global = STORE.get()?;
+
+    let stored = global
+        .store
+        .last_height(chain_id)
+        .map_err(|e| warn!(chain = %chain_id, "failed to read the last processed height: {e}"))
+        .ok()??;
+
+    is_resumable(&stored, chain_id, global.max_resume_age, now()).then_some(stored.height)
+}
+
+/// Whether the relayer can resume relaying packets on the given path from the stored state,
+/// instead of clearing all the pending packets
+pub fn can_resume(path: &Packet) -> bool {
+    resume_height(&path.src_chain_id).is_some() && resume_height(&path.dst_chain_id).is_some()
+}
+
+/// Store the backlog of a path
+pub fn store_backlog(backlog: &StoredBacklog) {
+    if let Some(global) = STORE.get() {
+        if let Err(e) = global.store.set_backlog(backlog) {
+            warn!(path = %backlog.path.short_name(), "failed to store the backlog: {e}");
+        }
+    }
+}
+
+/// Remove the backlog of a path from the store and return it, if any
+pub fn take_backlog(path: &Packet) -> Option<StoredBacklog> {
+    let global = STORE.get()?;
+
+    let result = global.store.backlog(path).and_then(|backlog| {
+        global.store.remove_backlog(path)?;
+        Ok(backlog)
+    });
+
+    match result {
+        Ok(backlog) => backlog,
+        Err(e) => {
+            warn!(path = %path.short_name(), "failed to read the stored backlog: {e}");
+            None
+        }
+    }
+}
+
+/// A stored height can be resumed from if it was processed recently enough,
+/// and the chain was not upgraded to a new revision in the meantime
+fn is_resumable(stored: &StoredHeight, chain_id: &ChainId, max_age: Duration, now: u64) -> bool {
+    stored.height.revision_number() == chain_id.version()
+        && now.saturating_sub(stored.processed_at) <= max_age.as_secs()
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ibc_relayer_types::core::ics24_host::identifier::{ChannelId, PortId};
+
+    fn path() -> Packet {
+        Packet {
+            dst_chain_id: ChainId::from_string("ibc-1"),
+            src_chain_id: ChainId::from_string("ibc-0"),
+            src_channel_id: ChannelId::new(0),
+            src_port_id: PortId::transfer(),
+        }
+    }
+
+    #[test]
+    fn heights_roundtrip() {
+        let store = SledStore::temporary().unwrap();
+        let chain_id = ChainId::from_string("ibc-0");
+
+        assert_eq!(store.last_height(&chain_id).unwrap(), None);
+
+        let stored = StoredHeight {
+            height: Height::new(0, 42).unwrap(),
+            processed_at: 1000,
+        };
+
+        store.set_last_height(&chain_id, &stored).unwrap();
+        assert_eq!(store.last_height(&chain_id).unwrap(), Some(stored));
+    }
+
+    #[test]
+    fn empty_backlog_is_removed() {
+        let store = SledStore::temporary().unwrap();
+
+        let mut backlog = StoredBacklog {
+            path: path(),
+            scheduled: vec![Sequence::from(1), Sequence::from(2)],
+            pending_txs: vec![StoredTx {
+                chain_id: ChainId::from_string("ibc-1"),
+                tx_hashes: vec![TxHash::Sha256([0xab; 32])],
+                sequences: vec![Sequence::from(3)],
+            }],
+        };
+
+        store.set_backlog(&backlog).unwrap();
+        assert_eq!(store.backlog(&path()).unwrap(), Some(backlog.clone()));
+
+        backlog.scheduled.clear();
+        backlog.pending_txs.clear();
+
+        store.set_backlog(&backlog).unwrap();
+        assert_eq!(store.backlog(&path()).unwrap(), None);
+    }
+
+    #[test]
+    fn heights_are_processed_in_order() {
+        let height = |h| Height::new(0, h).unwrap();
+
+        let mut dispatched = DispatchedHeights::default();
+
+        dispatched.dispatch(height(1));
+        dispatched.dispatch(height(1));
+        dispatched.dispatch(height(2));
+        dispatched.dispatch(height(3));
+
+        assert_eq!(dispatched.process(height(2)), None);
+        assert_eq!(dispatched.process(height(1)), None);
+        assert_eq!(dispatched.process(height(1)), Some(height(2)));
+        assert_eq!(dispatched.process(height(3)), Some(height(3)));
+
+        dispatched.dispatch(height(4));
+        assert_eq!(dispatched.process(height(4)), Some(height(4)));
+    }
+
+    #[test]
+    fn heights_are_not_recorded_past_abandoned_heights() {
+        let height = |h| Height::new(0, h).unwrap();
+
+        let mut dispatched = DispatchedHeights::default();
+
+        dispatched.dispatch(height(1));
+        dispatched.dispatch(height(2));
+        dispatched.dispatch(height(2));
+        dispatched.dispatch(height(3));
+
+        assert_eq!(dispatched.process(height(2)), None);
+        assert_eq!(dispatched.abandon(height(2)), None);
+        assert_eq!(dispatched.process(height(3)), None);
+        assert_eq!(dispatched.process(height(1)), Some(height(1)));
+
+        dispatched.dispatch(height(4));
+        assert_eq!(dispatched.process(height(4)), None);
+        assert!(dispatched.processed.is_empty());
+    }
+
+    #[test]
+    fn resumable_heights() {
+        let chain_id = ChainId::from_string("ibc-0");
+        let max_age = Duration::from_secs(3600);
+
+        let stored = StoredHeight {
+            height: Height::new(0, 42).unwrap(),
+            processed_at: 1000,
+        };
+
+        assert!(is_resumable(&stored, &chain_id, max_age, 1000 + 3600));
+        assert!(!is_resumable(&stored, &chain_id, max_age, 1000 + 3601));
+
+        let upgraded = ChainId::from_string("ibc-1");
+        assert!(!is_resumable(&stored, &upgraded, max_age, 1000));
+    }
+}
@@ -0,0 +1,20 @@
+use flex_error::{define_error, TraceError};
+
+define_error! {
+    Error {
+        Db
+            [ TraceError<sled::Error> ]
+            |_| { "state store database error" },
+
+        Encode
+            [ TraceError<serde_json::Error> ]
+            |_| { "failed to encode state store entry" },
+
+        Decode
+            [ TraceError<serde_json::Error> ]
+            |_| { "failed to decode state store entry" },
+
+        HomeLocationUnavailable
+            |_| { "home location is unavailable" },
+    }
+}
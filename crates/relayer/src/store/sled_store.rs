@@ -0,0 +1,91 @@
+use std::path::Path;
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+use crate::object::Packet;
+
+use super::error::Error;
+use super::{RelayStore, StoredBacklog, StoredHeight};
+
+const HEIGHTS_TREE: &str = "heights";
+const BACKLOGS_TREE: &str = "backlogs";
+
+/// A [`RelayStore`] backed by an embedded [sled](https://docs.rs/sled) database
+pub struct SledStore {
+    heights: sled::Tree,
+    backlogs: sled::Tree,
+}
+
+impl SledStore {
+    /// Open the store in the given folder, creating it if needed
+    pub fn open(path: &Path) -> Result<Self, Error> {
+        let db = sled::open(path).map_err(Error::db)?;
+        Self::from_db(&db)
+    }
+
+    /// Open a store which is deleted once dropped
+    pub fn temporary() -> Result<Self, Error> {
+        let db = sled::Config::new()
+            .temporary(true)
+            .open()
+            .map_err(Error::db)?;
+
+        Self::from_db(&db)
+    }
+
+    fn from_db(db: &sled::Db) -> Result<Self, Error> {
+        Ok(Self {
+            heights: db.open_tree(HEIGHTS_TREE).map_err(Error::db)?,
+            backlogs: db.open_tree(BACKLOGS_TREE).map_err(Error::db)?,
+        })
+    }
+}
+
+impl RelayStore for SledStore {
+    fn last_height(&self, chain_id: &ChainId) -> Result<Option<StoredHeight>, Error> {
+        get(&self.heights, chain_id.as_str())
+    }
+
+    fn set_last_height(&self, chain_id: &ChainId, height: &StoredHeight) -> Result<(), Error> {
+        insert(&self.heights, chain_id.as_str(), height)
+    }
+
+    fn backlog(&self, path: &Packet) -> Result<Option<StoredBacklog>, Error> {
+        get(&self.backlogs, &path.short_name())
+    }
+
+    fn set_backlog(&self, backlog: &StoredBacklog) -> Result<(), Error> {
+        if backlog.is_empty() {
+            return self.remove_backlog(&backlog.path);
+        }
+
+        insert(&self.backlogs, &backlog.path.short_name(), backlog)
+    }
+
+    fn remove_backlog(&self, path: &Packet) -> Result<(), Error> {
+        self.backlogs
+            .remove(path.short_name())
+            .map(|_| ())
+            .map_err(Error::db)
+    }
+}
+
+fn get<T: DeserializeOwned>(tree: &sled::Tree, key: &str) -> Result<Option<T>, Error> {
+    tree.get(key)
+        .map_err(Error::db)?
+        .map(|value| serde_json::from_slice(&value).map_err(Error::decode))
+        .transpose()
+}
+
+/// Insert the given value, unless it is already stored under the given key
+fn insert<T: Serialize>(tree: &sled::Tree, key: &str, value: &T) -> Result<(), Error> {
+    let value = serde_json::to_vec(value).map_err(Error::encode)?;
+
+    if tree.get(key).map_err(Error::db)?.as_deref() != Some(value.as_slice()) {
+        tree.insert(key, value).map_err(Error::db)?;
+    }
+
+    Ok(())
+}
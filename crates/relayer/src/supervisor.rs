@@ -3,6 +3,7 @@ use alloc::sync::Arc;
 use core::convert::Infallible;
 use core::ops::Deref;
 use core::time::Duration;
+use std::path::PathBuf;
 use std::sync::RwLock;
 
 use crossbeam_channel::{unbounded, Receiver, Sender};
@@ -10,28 +11,39 @@ use itertools::Itertools;
 use tracing::{debug, error, error_span, info, instrument, trace, warn};
 
 use ibc_relayer_types::{
-    core::ics24_host::identifier::{ChainId, ChannelId, PortId},
+    core::{
+        ics02_client::client_state::ClientState,
+        ics24_host::identifier::{ChainId, ChannelId, ClientId, PortId},
+    },
     events::IbcEvent,
     Height,
 };
 
 use crate::{
-    chain::{endpoint::HealthCheck, handle::ChainHandle, tracking::TrackingId},
+    chain::{
+        counterparty::channel_connection_client,
+        endpoint::HealthCheck,
+        handle::ChainHandle,
+        requests::{IncludeProof, QueryClientStateRequest, QueryHeight},
+        tracking::TrackingId,
+    },
     config::Config,
     event::{
         source::{self, Error as EventError, ErrorDetail as EventErrorDetail, EventBatch},
         IbcEventWithHeight,
     },
-    object::Object,
+    foreign_client::ForeignClient,
+    object::{Object, Packet},
     registry::{Registry, SharedRegistry},
     rest,
+    store::HeightGuard,
     supervisor::scan::ScanMode,
     telemetry,
     util::{
-        lock::LockExt,
+        lock::{LockExt, RwArc},
         task::{spawn_background_task, Next, TaskError, TaskHandle},
     },
-    worker::WorkerMap,
+    worker::{PausePolicy, WorkerMap},
 };
 
 pub mod client_state_filter;
@@ -49,6 +61,9 @@ pub mod spawn;
 pub mod cmd;
 use cmd::SupervisorCmd;
 
+pub mod reload;
+use reload::{ConfigChanges, Reloader};
+
 use self::{scan::ChainScanner, spawn::SpawnContext};
 
 type ArcBatch = Arc<source::Result<EventBatch>>;
@@ -66,7 +81,7 @@ pub struct SupervisorHandle {
 }
 
 /// Options for the supervisor
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct SupervisorOptions {
     /// Perform a health check of all chains we connect to
     pub health_check: bool,
@@ -75,6 +90,10 @@ pub struct SupervisorOptions {
     /// even when an allow list is configured for a chain and the full scan could
     /// be omitted.
     pub force_full_scan: bool,
+
+    /// Path to the configuration file, from which the configuration
+    /// is reloaded when requested. Reloading is disabled if unset.
+    pub config_path: Option<PathBuf>,
 }
 
 /**
@@ -131,6 +150,82 @@ impl SupervisorHandle {
 
         Ok(state)
     }
+
+    /// Ask the supervisor to reload its configuration
+    pub fn reload_config(&self) -> Result<ConfigChanges, Error> {
+        let (tx, rx) = crossbeam_channel::bounded(1);
+
+        self.sender
+            .send(SupervisorCmd::ReloadConfig(tx))
+            .map_err(|_| Error::handle_send())?;
+
+        rx.recv().map_err(|_| Error::handle_recv())?
+    }
+
+    /// Ask the supervisor to clear the pending packets on the given channel
+    pub fn clear_packets(
+        &self,
+        chain_id: ChainId,
+        port_id: PortId,
+        channel_id: ChannelId,
+    ) -> Result<Vec<Object>, Error> {
+        self.send_cmd(|reply_to| SupervisorCmd::ClearPackets {
+            chain_id,
+            port_id,
+            channel_id,
+            reply_to,
+        })
+    }
+
+    /// Ask the supervisor to update the given client
+    pub fn update_client(&self, chain_id: ChainId, client_id: ClientId) -> Result<(), Error> {
+        self.send_cmd(|reply_to| SupervisorCmd::UpdateClient {
+            chain_id,
+            client_id,
+            reply_to,
+        })
+    }
+
+    /// Ask the supervisor to pause the worker for the given object
+    pub fn pause_worker(&self, object: Object, policy: PausePolicy) -> Result<(), Error> {
+        self.send_cmd(|reply_to| SupervisorCmd::PauseWorker {
+            object,
+            policy,
+            reply_to,
+        })
+    }
+
+    /// Ask the supervisor to resume the worker for the given object
+    pub fn resume_worker(&self, object: Object) -> Result<(), Error> {
+        self.send_cmd(|reply_to| SupervisorCmd::ResumeWorker { object, reply_to })
+    }
+
+    /// Ask the supervisor to pause all the workers of the given chain
+    pub fn pause_chain(&self, chain_id: ChainId, policy: PausePolicy) -> Result<(), Error> {
+        self.send_cmd(|reply_to| SupervisorCmd::PauseChain {
+            chain_id,
+            policy,
+            reply_to,
+        })
+    }
+
+    /// Ask the supervisor to resume the workers of the given chain
+    pub fn resume_chain(&self, chain_id: ChainId) -> Result<(), Error> {
+        self.send_cmd(|reply_to| SupervisorCmd::ResumeChain { chain_id, reply_to })
+    }
+
+    fn send_cmd<T>(
+        &self,
+        cmd: impl FnOnce(Sender<Result<T, Error>>) -> SupervisorCmd,
+    ) -> Result<T, Error> {
+        let (tx, rx) = crossbeam_channel::bounded(1);
+
+        self.sender
+            .send(cmd(tx))
+            .map_err(|_| Error::handle_send())?;
+
+        rx.recv().map_err(|_| Error::handle_recv())?
+    }
 }
 
 /// Whether the supervisor should scan the chains for clients, connections, and channels.
@@ -196,21 +291,33 @@ pub fn spawn_supervisor_tasks<Chain: ChainHandle>(
 
     let subscriptions = init_subscriptions(&config, &mut registry.write())?;
 
+    let config = Arc::new(RwLock::new(config));
+
     let batch_tasks = spawn_batch_workers(
         &config,
         registry.clone(),
-        client_state_filter,
+        client_state_filter.clone(),
         workers.clone(),
         subscriptions,
     );
 
-    let cmd_task = spawn_cmd_worker(registry.clone(), workers.clone(), cmd_rx);
+    // The batch workers are owned by the reloader, which
+    // replaces the ones of the chains it restarts.
+    let reloader = Reloader {
+        config,
+        registry,
+        client_state_filter,
+        workers: workers.clone(),
+        batch_tasks: Arc::new(RwLock::new(batch_tasks)),
+        options,
+    };
+
+    let cmd_task = spawn_cmd_worker(reloader.clone(), cmd_rx);
 
     let mut tasks = vec![cmd_task];
-    tasks.extend(batch_tasks);
 
     if let Some(rest_rx) = rest_rx {
-        let rest_task = spawn_rest_worker(config, registry, workers.clone(), rest_rx);
+        let rest_task = spawn_rest_worker(reloader, rest_rx);
         tasks.push(rest_task);
     }
 
@@ -221,48 +328,59 @@ pub fn spawn_supervisor_tasks<Chain: ChainHandle>(
 }
 
 fn spawn_batch_workers<Chain: ChainHandle>(
-    config: &Config,
+    config: &RwArc<Config>,
     registry: SharedRegistry<Chain>,
-    client_state_filter: Arc<RwLock<FilterPolicy>>,
-    workers: Arc<RwLock<WorkerMap>>,
+    client_state_filter: RwArc<FilterPolicy>,
+    workers: RwArc<WorkerMap>,
     subscriptions: Vec<(Chain, Subscription)>,
-) -> Vec<TaskHandle> {
-    let mut handles = Vec::with_capacity(subscriptions.len());
-
-    for (chain, subscription) in subscriptions {
-        let config = config.clone();
-        let registry = registry.clone();
-        let client_state_filter = client_state_filter.clone();
-        let workers = workers.clone();
-
-        let handle = spawn_background_task(
-            error_span!("worker.batch", chain = %chain.id()),
-            Some(Duration::from_millis(5)),
-            move || -> Result<Next, TaskError<Infallible>> {
-                if let Ok(batch) = subscription.try_recv() {
-                    handle_batch(
-                        &config,
-                        &mut registry.write(),
-                        &mut client_state_filter.acquire_write(),
-                        &mut workers.acquire_write(),
-                        chain.clone(),
-                        batch,
-                    );
-                }
+) -> HashMap<ChainId, TaskHandle> {
+    subscriptions
+        .into_iter()
+        .map(|(chain, subscription)| {
+            let handle = spawn_batch_worker(
+                config.clone(),
+                registry.clone(),
+                client_state_filter.clone(),
+                workers.clone(),
+                chain.clone(),
+                subscription,
+            );
 
-                Ok(Next::Continue)
-            },
-        );
+            (chain.id(), handle)
+        })
+        .collect()
+}
 
-        handles.push(handle);
-    }
+fn spawn_batch_worker<Chain: ChainHandle>(
+    config: RwArc<Config>,
+    registry: SharedRegistry<Chain>,
+    client_state_filter: RwArc<FilterPolicy>,
+    workers: RwArc<WorkerMap>,
+    chain: Chain,
+    subscription: Subscription,
+) -> TaskHandle {
+    spawn_background_task(
+        error_span!("worker.batch", chain = %chain.id()),
+        Some(Duration::from_millis(5)),
+        move || -> Result<Next, TaskError<Infallible>> {
+            if let Ok(batch) = subscription.try_recv() {
+                handle_batch(
+                    &config.acquire_read(),
+                    &mut registry.write(),
+                    &mut client_state_filter.acquire_write(),
+                    &mut workers.acquire_write(),
+                    chain.clone(),
+                    batch,
+                );
+            }
 
-    handles
+            Ok(Next::Continue)
+        },
+    )
 }
 
 pub fn spawn_cmd_worker<Chain: ChainHandle>(
-    registry: SharedRegistry<Chain>,
-    workers: Arc<RwLock<WorkerMap>>,
+    reloader: Reloader<Chain>,
     cmd_rx: Receiver<SupervisorCmd>,
 ) -> TaskHandle {
     spawn_background_task(
@@ -272,7 +390,81 @@ pub fn spawn_cmd_worker<Chain: ChainHandle>(
             if let Ok(cmd) = cmd_rx.try_recv() {
                 match cmd {
                     SupervisorCmd::DumpState(reply_to) => {
-                        dump_state(&registry.read(), &workers.acquire_read(), reply_to);
+                        dump_state(
+                            &reloader.registry.read(),
+                            &reloader.workers.acquire_read(),
+                            reply_to,
+                        );
+                    }
+                    SupervisorCmd::ReloadConfig(reply_to) => {
+                        let result = reloader.reload();
+
+                        if let Err(e) = &result {
+                            error!("failed to reload configuration: {}", e);
+                        }
+
+                        let _ = reply_to.try_send(result);
+                    }
+                    SupervisorCmd::ClearPackets {
+                        chain_id,
+                        port_id,
+                        channel_id,
+                        reply_to,
+                    } => {
+                        let result = clear_packets(
+                            &reloader.config.acquire_read(),
+                            &mut reloader.registry.write(),
+                            &mut reloader.workers.acquire_write(),
+                            &chain_id,
+                            &port_id,
+                            &channel_id,
+                        );
+
+                        let _ = reply_to.try_send(result);
+                    }
+                    SupervisorCmd::UpdateClient {
+                        chain_id,
+                        client_id,
+                        reply_to,
+                    } => {
+                        spawn_update_client(
+                            &mut reloader.registry.write(),
+                            &chain_id,
+                            &client_id,
+                            move |result| {
+                                let _ = reply_to.try_send(result);
+                            },
+                        );
+                    }
+                    SupervisorCmd::PauseWorker {
+                        object,
+                        policy,
+                        reply_to,
+                    } => {
+                        let result =
+                            pause_worker(&mut reloader.workers.acquire_write(), &object, policy);
+                        let _ = reply_to.try_send(result);
+                    }
+                    SupervisorCmd::ResumeWorker { object, reply_to } => {
+                        let result = resume_worker(&mut reloader.workers.acquire_write(), &object);
+                        let _ = reply_to.try_send(result);
+                    }
+                    SupervisorCmd::PauseChain {
+                        chain_id,
+                        policy,
+                        reply_to,
+                    } => {
+                        let result = pause_chain(
+                            &reloader.config.acquire_read(),
+                            &mut reloader.workers.acquire_write(),
+                            &chain_id,
+                            policy,
+                        );
+                        let _ = reply_to.try_send(result);
+                    }
+                    SupervisorCmd::ResumeChain { chain_id, reply_to } => {
+                        let result = resume_chain(&mut reloader.workers.acquire_write(), &chain_id);
+                        let _ = reply_to.try_send(result);
                     }
                 }
             }
@@ -283,16 +475,14 @@ pub fn spawn_cmd_worker<Chain: ChainHandle>(
 }
 
 pub fn spawn_rest_worker<Chain: ChainHandle>(
-    config: Config,
-    registry: SharedRegistry<Chain>,
-    workers: Arc<RwLock<WorkerMap>>,
+    reloader: Reloader<Chain>,
     rest_rx: rest::Receiver,
 ) -> TaskHandle {
     spawn_background_task(
         error_span!("rest"),
         Some(Duration::from_millis(500)),
         move || -> Result<Next, TaskError<Infallible>> {
-            handle_rest_requests(&config, &registry.read(), &workers.acquire_read(), &rest_rx);
+            handle_rest_requests(&reloader, &rest_rx);
 
             Ok(Next::Continue)
         },
@@ -539,6 +729,28 @@ pub fn collect_events(
                     || Object::client_from_chan_open_events(&attributes, src_chain).ok(),
                 );
             }
+            IbcEvent::UpgradeInitChannel(..)
+            | IbcEvent::UpgradeTryChannel(..)
+            | IbcEvent::UpgradeAckChannel(..)
+            | IbcEvent::UpgradeConfirmChannel(..)
+            | IbcEvent::UpgradeOpenChannel(..)
+            | IbcEvent::UpgradeErrorChannel(..)
+            | IbcEvent::FlushCompleteChannel(..) => {
+                // If handshake message relaying is enabled create worker to complete the channel upgrade
+                collect_event(
+                    &mut collected,
+                    event_with_height.clone(),
+                    mode.channels.enabled,
+                    || {
+                        event_with_height
+                            .event
+                            .upgrade_attributes()
+                            .and_then(|attr| {
+                                Object::channel_from_chan_upgrade_events(attr, src_chain).ok()
+                            })
+                    },
+                );
+            }
             IbcEvent::SendPacket(ref packet) => {
                 collect_event(
                     &mut collected,
@@ -696,33 +908,314 @@ fn dump_state<Chain: ChainHandle>(
 /// as a [`SupervisorState`].
 fn state<Chain: ChainHandle>(registry: &Registry<Chain>, workers: &WorkerMap) -> SupervisorState {
     let chains = registry.chains().map(|c| c.id()).collect_vec();
-    SupervisorState::new(chains, workers.handles())
+
+    let fee_budgets = registry
+        .chains()
+        .filter_map(|chain| match chain.query_fee_budget() {
+            Ok(budget) => budget.map(|budget| (chain.id(), budget)),
+            Err(e) => {
+                warn!(chain = %chain.id(), "failed to query the fee budget: {e}");
+                None
+            }
+        })
+        .collect();
+
+    SupervisorState::from_workers(chains, workers).with_fee_budgets(fee_budgets)
 }
 
-fn handle_rest_requests<Chain: ChainHandle>(
-    config: &Config,
-    registry: &Registry<Chain>,
-    workers: &WorkerMap,
-    rest_rx: &rest::Receiver,
-) {
-    if let Some(cmd) = rest::process_incoming_requests(config, rest_rx) {
-        handle_rest_cmd(registry, workers, cmd);
+fn handle_rest_requests<Chain: ChainHandle>(reloader: &Reloader<Chain>, rest_rx: &rest::Receiver) {
+    let cmd = rest::process_incoming_requests(&reloader.config.acquire_read(), rest_rx);
+
+    if let Some(cmd) = cmd {
+        handle_rest_cmd(reloader, cmd);
     }
 }
 
 #[instrument(name = "supervisor.handle_rest_cmd", level = "error", skip_all)]
-fn handle_rest_cmd<Chain: ChainHandle>(
-    registry: &Registry<Chain>,
-    workers: &WorkerMap,
-    m: rest::Command,
-) {
+fn handle_rest_cmd<Chain: ChainHandle>(reloader: &Reloader<Chain>, m: rest::Command) {
     match m {
         rest::Command::DumpState(reply) => {
-            let state = state(registry, workers);
+            let state = state(&reloader.registry.read(), &reloader.workers.acquire_read());
             reply
                 .send(Ok(state))
                 .unwrap_or_else(|e| error!("error replying to a REST request {}", e));
         }
+        rest::Command::ReloadConfig(reply) => {
+            let result = reloader.reload().map_err(|e| {
+                error!("failed to reload configuration: {}", e);
+                rest::RestApiError::ConfigReload(e.to_string())
+            });
+
+            reply
+                .send(result)
+                .unwrap_or_else(|e| error!("error replying to a REST request {}", e));
+        }
+        rest::Command::ClearPackets {
+            chain_id,
+            port_id,
+            channel_id,
+            reply_to,
+        } => {
+            let result = clear_packets(
+                &reloader.config.acquire_read(),
+                &mut reloader.registry.write(),
+                &mut reloader.workers.acquire_write(),
+                &chain_id,
+                &port_id,
+                &channel_id,
+            );
+
+            reply_to
+                .send(result.map_err(rest::RestApiError::supervisor))
+                .unwrap_or_else(|e| error!("error replying to a REST request {}", e));
+        }
+        rest::Command::UpdateClient {
+            chain_id,
+            client_id,
+            reply_to,
+        } => {
+            spawn_update_client(
+                &mut reloader.registry.write(),
+                &chain_id,
+                &client_id,
+                move |result| {
+                    reply_to
+                        .send(result.map_err(rest::RestApiError::supervisor))
+                        .unwrap_or_else(|e| error!("error replying to a REST request {}", e));
+                },
+            );
+        }
+        rest::Command::PauseWorker {
+            object,
+            policy,
+            reply_to,
+        } => {
+            let result = pause_worker(&mut reloader.workers.acquire_write(), &object, policy);
+
+            reply_to
+                .send(result.map_err(rest::RestApiError::supervisor))
+                .unwrap_or_else(|e| error!("error replying to a REST request {}", e));
+        }
+        rest::Command::ResumeWorker { object, reply_to } => {
+            let result = resume_worker(&mut reloader.workers.acquire_write(), &object);
+
+            reply_to
+                .send(result.map_err(rest::RestApiError::supervisor))
+                .unwrap_or_else(|e| error!("error replying to a REST request {}", e));
+        }
+        rest::Command::PauseChain {
+            chain_id,
+            policy,
+            reply_to,
+        } => {
+            let result = pause_chain(
+                &reloader.config.acquire_read(),
+                &mut reloader.workers.acquire_write(),
+                &chain_id,
+                policy,
+            );
+
+            reply_to
+                .send(result.map_err(rest::RestApiError::supervisor))
+                .unwrap_or_else(|e| error!("error replying to a REST request {}", e));
+        }
+        rest::Command::ResumeChain { chain_id, reply_to } => {
+            let result = resume_chain(&mut reloader.workers.acquire_write(), &chain_id);
+
+            reply_to
+                .send(result.map_err(rest::RestApiError::supervisor))
+                .unwrap_or_else(|e| error!("error replying to a REST request {}", e));
+        }
+    }
+}
+
+/// Instruct the packet workers for the given channel to clear its pending packets,
+/// in both directions, spawning the workers if they do not exist yet.
+///
+/// Returns the objects of the packet workers which were instructed to clear packets.
+#[instrument(
+    name = "supervisor.clear_packets",
+    level = "error",
+    skip_all,
+    fields(chain = %chain_id, port = %port_id, channel = %channel_id)
+)]
+fn clear_packets<Chain: ChainHandle>(
+    config: &Config,
+    registry: &mut Registry<Chain>,
+    workers: &mut WorkerMap,
+    chain_id: &ChainId,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+) -> Result<Vec<Object>, Error> {
+    if !is_channel_allowed(config, chain_id, port_id, channel_id) {
+        return Err(Error::channel_not_allowed(
+            port_id.clone(),
+            channel_id.clone(),
+            chain_id.clone(),
+        ));
+    }
+
+    let chain = registry.get_or_spawn(chain_id).map_err(Error::spawn)?;
+    let chan_conn_client = channel_connection_client(&chain, port_id, channel_id)?;
+
+    let channel = chan_conn_client.channel;
+    let counterparty_chain_id = chan_conn_client.client.client_state.chain_id();
+
+    let counterparty_chain = registry
+        .get_or_spawn(&counterparty_chain_id)
+        .map_err(Error::spawn)?;
+
+    let mut paths = vec![(
+        Packet {
+            dst_chain_id: counterparty_chain_id.clone(),
+            src_chain_id: chain_id.clone(),
+            src_channel_id: channel_id.clone(),
+            src_port_id: port_id.clone(),
+        },
+        chain.clone(),
+        counterparty_chain.clone(),
+    )];
+
+    let counterparty = channel.channel_end.counterparty();
+
+    if let Some(counterparty_channel_id) = counterparty.channel_id() {
+        paths.push((
+            Packet {
+                dst_chain_id: chain_id.clone(),
+                src_chain_id: counterparty_chain_id,
+                src_channel_id: counterparty_channel_id.clone(),
+                src_port_id: counterparty.port_id().clone(),
+            },
+            counterparty_chain,
+            chain,
+        ));
+    }
+
+    let mut objects = Vec::with_capacity(paths.len());
+
+    for (path, src, dst) in paths {
+        let object = Object::Packet(path);
+
+        info!("clearing pending packets on {}", object.short_name());
+
+        workers
+            .get_or_spawn(object.clone(), src, dst, config)
+            .clear_pending_packets();
+
+        objects.push(object);
+    }
+
+    Ok(objects)
+}
+
+/// Update the given client to the latest height of the chain it tracks, in a
+/// background thread, and call `on_done` with the outcome of the update.
+fn spawn_update_client<Chain: ChainHandle>(
+    registry: &mut Registry<Chain>,
+    chain_id: &ChainId,
+    client_id: &ClientId,
+    on_done: impl FnOnce(Result<(), Error>) + Send + 'static,
+) {
+    let client = restore_client(registry, chain_id, client_id);
+
+    let client = match client {
+        Ok(client) => client,
+        Err(e) => return on_done(Err(e)),
+    };
+
+    std::thread::spawn(move || {
+        let _span =
+            error_span!("update_client", chain = %client.dst_chain.id(), client = %client.id)
+                .entered();
+
+        info!("updating client");
+
+        let result = client
+            .update()
+            .map_err(|e| Error::client_update(client.id.clone(), client.dst_chain.id(), e));
+
+        if let Err(e) = &result {
+            error!("{}", e);
+        }
+
+        on_done(result)
+    });
+}
+
+/// Restore the client with the given identifier hosted on the given chain,
+/// along with the chain it tracks.
+fn restore_client<Chain: ChainHandle>(
+    registry: &mut Registry<Chain>,
+    chain_id: &ChainId,
+    client_id: &ClientId,
+) -> Result<ForeignClient<Chain, Chain>, Error> {
+    let chain = registry.get_or_spawn(chain_id).map_err(Error::spawn)?;
+
+    let (client_state, _) = chain
+        .query_client_state(
+            QueryClientStateRequest {
+                client_id: client_id.clone(),
+                height: QueryHeight::Latest,
+            },
+            IncludeProof::No,
+        )
+        .map_err(Error::relayer)?;
+
+    let counterparty_chain = registry
+        .get_or_spawn(&client_state.chain_id())
+        .map_err(Error::spawn)?;
+
+    Ok(ForeignClient::restore(
+        client_id.clone(),
+        chain,
+        counterparty_chain,
+    ))
+}
+
+fn pause_worker(
+    workers: &mut WorkerMap,
+    object: &Object,
+    policy: PausePolicy,
+) -> Result<(), Error> {
+    if workers.pause_worker(object, policy) {
+        info!("paused worker {} ({})", object.short_name(), policy);
+        Ok(())
+    } else {
+        Err(Error::worker_not_found(object.short_name()))
+    }
+}
+
+fn resume_worker(workers: &mut WorkerMap, object: &Object) -> Result<(), Error> {
+    if workers.resume_worker(object) {
+        info!("resumed worker {}", object.short_name());
+        Ok(())
+    } else {
+        Err(Error::worker_not_paused(object.short_name()))
+    }
+}
+
+fn pause_chain(
+    config: &Config,
+    workers: &mut WorkerMap,
+    chain_id: &ChainId,
+    policy: PausePolicy,
+) -> Result<(), Error> {
+    if config.find_chain(chain_id).is_none() {
+        return Err(Error::chain_not_found(chain_id.clone()));
+    }
+
+    workers.pause_chain(chain_id, policy);
+    info!(chain = %chain_id, "paused the workers of the chain ({})", policy);
+
+    Ok(())
+}
+
+fn resume_chain(workers: &mut WorkerMap, chain_id: &ChainId) -> Result<(), Error> {
+    if workers.resume_chain(chain_id) {
+        info!(chain = %chain_id, "resumed the workers of the chain");
+        Ok(())
+    } else {
+        Err(Error::chain_not_paused(chain_id.clone()))
     }
 }
 
@@ -761,6 +1254,12 @@ fn process_batch<Chain: ChainHandle>(
 
     let collected = collect_events(config, workers, &src_chain, batch);
 
+    // The height is recorded as processed once every packet worker the events are dispatched to
+    // has processed its clone of the guard, or when this function returns if no packet worker
+    // is concerned by the events. The other workers do not persist the outcome of their events,
+    // so they are not handed the guard.
+    let height_guard = HeightGuard::new(&batch.chain_id, batch.height);
+
     // If there is a NewBlock event, forward this event first to any workers affected by it.
     if let Some(IbcEvent::NewBlock(new_block)) = collected.new_block {
         workers.notify_new_block(&src_chain.id(), batch.height, new_block);
@@ -801,6 +1300,11 @@ fn process_batch<Chain: ChainHandle>(
             telemetry!(send_telemetry(&src, &dst, &events_with_heights, _path));
         }
 
+        let worker_height_guard = match object {
+            Object::Packet(_) => height_guard.clone(),
+            _ => HeightGuard::default(),
+        };
+
         let worker = workers.get_or_spawn(object, src, dst, config);
 
         worker.send_events(
@@ -808,9 +1312,12 @@ fn process_batch<Chain: ChainHandle>(
             events_with_heights,
             batch.chain_id.clone(),
             batch.tracking_id,
+            worker_height_guard,
         );
     }
 
+    height_guard.processed();
+
     Ok(())
 }
 
@@ -826,12 +1333,8 @@ fn process_batch<Chain: ChainHandle>(
 /// event with `chain_id = A` and `counterparty_chain_id = B` and then a WriteAcknowlegment
 /// event with `chain_id = B` and `counterparty_chain_id = A`.
 #[cfg(feature = "telemetry")]
-fn send_telemetry<Src, Dst>(
-    src: &Src,
-    dst: &Dst,
-    events: &[IbcEventWithHeight],
-    path: &crate::object::Packet,
-) where
+fn send_telemetry<Src, Dst>(src: &Src, dst: &Dst, events: &[IbcEventWithHeight], path: &Packet)
+where
     Src: ChainHandle,
     Dst: ChainHandle,
 {
@@ -904,6 +1407,24 @@ fn handle_batch<Chain: ChainHandle>(
             let _ = clear_pending_packets(workers, &chain_id)
                 .map_err(|e| error!("error during clearing pending packets: {}", e));
         }
+        Err(EventError(EventErrorDetail::EndpointSwitched(e), _)) => {
+            warn!(
+                "event source switched over to {}, clearing pending packets",
+                e.address
+            );
+
+            let _ = clear_pending_packets(workers, &chain_id)
+                .map_err(|e| error!("error during clearing pending packets: {}", e));
+        }
+        Err(EventError(EventErrorDetail::ReplayFailed(e), _)) => {
+            warn!(
+                "failed to replay the events emitted after height {}, clearing pending packets",
+                e.height
+            );
+
+            let _ = clear_pending_packets(workers, &chain_id)
+                .map_err(|e| error!("error during clearing pending packets: {}", e));
+        }
         Err(e) => {
             error!("error when receiving event batch: {}", e)
         }
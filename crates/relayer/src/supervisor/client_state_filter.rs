@@ -203,6 +203,9 @@ impl FilterPolicy {
                     Permission::Allow
                 }
             }
+            // The localhost client reads the state of its host, hence it
+            // is as trustworthy as the chain itself
+            None if matches!(state, AnyClientState::Localhost(_)) => Permission::Allow,
             None => {
                 trace!(
                     "client {} on chain {} does not have a trust threshold set",
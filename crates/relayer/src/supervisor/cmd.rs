@@ -1,8 +1,57 @@
 use crossbeam_channel::Sender;
 
-use super::dump_state::SupervisorState;
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, ClientId, PortId};
+
+use crate::{object::Object, worker::PausePolicy};
+
+use super::{dump_state::SupervisorState, reload::ConfigChanges, Error};
 
 #[derive(Clone, Debug)]
 pub enum SupervisorCmd {
     DumpState(Sender<SupervisorState>),
+
+    ReloadConfig(Sender<Result<ConfigChanges, Error>>),
+
+    /// Clear the pending packets on the given channel, in both directions,
+    /// replying with the packet workers instructed to clear them
+    ClearPackets {
+        chain_id: ChainId,
+        port_id: PortId,
+        channel_id: ChannelId,
+        reply_to: Sender<Result<Vec<Object>, Error>>,
+    },
+
+    /// Update the given client, hosted on the given chain, to the latest
+    /// height of the chain it tracks
+    UpdateClient {
+        chain_id: ChainId,
+        client_id: ClientId,
+        reply_to: Sender<Result<(), Error>>,
+    },
+
+    /// Pause the worker for the given object until it is resumed
+    PauseWorker {
+        object: Object,
+        policy: PausePolicy,
+        reply_to: Sender<Result<(), Error>>,
+    },
+
+    /// Resume the paused worker for the given object
+    ResumeWorker {
+        object: Object,
+        reply_to: Sender<Result<(), Error>>,
+    },
+
+    /// Pause all the workers of the given chain until it is resumed
+    PauseChain {
+        chain_id: ChainId,
+        policy: PausePolicy,
+        reply_to: Sender<Result<(), Error>>,
+    },
+
+    /// Resume the paused workers of the given chain
+    ResumeChain {
+        chain_id: ChainId,
+        reply_to: Sender<Result<(), Error>>,
+    },
 }
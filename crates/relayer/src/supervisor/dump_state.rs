@@ -7,8 +7,9 @@ use serde::{Deserialize, Serialize};
 use tracing::info;
 
 use crate::{
+    chain::budget::FeeBudgetStatus,
     object::{Object, ObjectType},
-    worker::{WorkerData, WorkerHandle, WorkerId},
+    worker::{PausePolicy, WorkerData, WorkerHandle, WorkerId, WorkerMap},
 };
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -16,18 +17,43 @@ pub struct WorkerDesc {
     pub id: WorkerId,
     pub object: Object,
     pub data: Option<WorkerData>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paused: Option<PausePolicy>,
 }
 
 impl WorkerDesc {
-    pub fn new(id: WorkerId, object: Object, data: Option<WorkerData>) -> Self {
-        Self { id, object, data }
+    pub fn new(
+        id: WorkerId,
+        object: Object,
+        data: Option<WorkerData>,
+        paused: Option<PausePolicy>,
+    ) -> Self {
+        Self {
+            id,
+            object,
+            data,
+            paused,
+        }
     }
 }
 
+/// An object whose workers are paused until it is explicitly resumed
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PausedObject {
+    pub object: Object,
+    pub policy: PausePolicy,
+}
+
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct SupervisorState {
     pub chains: Vec<ChainId>,
     pub workers: BTreeMap<ObjectType, Vec<WorkerDesc>>,
+    #[serde(default)]
+    pub paused_chains: BTreeMap<ChainId, PausePolicy>,
+    #[serde(default)]
+    pub paused_objects: Vec<PausedObject>,
+    #[serde(default)]
+    pub fee_budgets: BTreeMap<ChainId, FeeBudgetStatus>,
 }
 
 impl SupervisorState {
@@ -38,13 +64,50 @@ impl SupervisorState {
         chains.sort();
 
         let workers = workers
-            .map(|h| WorkerDesc::new(h.id(), h.object().clone(), h.data().cloned()))
+            .map(|h| WorkerDesc::new(h.id(), h.object().clone(), h.data().cloned(), h.paused()))
             .into_group_map_by(|desc| desc.object.object_type())
             .into_iter()
             .update(|(_, os)| os.sort_by_key(|desc| desc.object.short_name()))
             .collect::<BTreeMap<_, _>>();
 
-        Self { chains, workers }
+        Self {
+            chains,
+            workers,
+            paused_chains: BTreeMap::new(),
+            paused_objects: Vec::new(),
+            fee_budgets: BTreeMap::new(),
+        }
+    }
+
+    /// Build the state of the given chains and of the workers in the given map,
+    /// including the chains and objects which are paused.
+    pub fn from_workers(chains: Vec<ChainId>, workers: &WorkerMap) -> Self {
+        let paused_chains = workers
+            .paused_chains()
+            .map(|(chain_id, policy)| (chain_id.clone(), *policy))
+            .collect();
+
+        let paused_objects = workers
+            .paused_objects()
+            .map(|(object, policy)| PausedObject {
+                object: object.clone(),
+                policy: *policy,
+            })
+            .collect();
+
+        Self {
+            paused_chains,
+            paused_objects,
+            ..Self::new(chains, workers.handles())
+        }
+    }
+
+    /// Add the state of the fee budgets of the chains which have one.
+    pub fn with_fee_budgets(self, fee_budgets: BTreeMap<ChainId, FeeBudgetStatus>) -> Self {
+        Self {
+            fee_budgets,
+            ..self
+        }
     }
 
     pub fn print_info(&self) {
@@ -58,10 +121,49 @@ impl Display for SupervisorState {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
         writeln!(f)?;
         writeln!(f, "* Chains: {}", self.chains.iter().join(", "))?;
+        if !self.paused_chains.is_empty() {
+            writeln!(
+                f,
+                "* Paused chains: {}",
+                self.paused_chains
+                    .iter()
+                    .map(|(chain_id, policy)| format!("{chain_id} ({policy})"))
+                    .join(", ")
+            )?;
+        }
+        if !self.fee_budgets.is_empty() {
+            writeln!(f, "* Fee budgets:")?;
+            for (chain_id, budget) in &self.fee_budgets {
+                writeln!(
+                    f,
+                    "  - {}: {}/{}{} remaining, replenished in {}",
+                    chain_id,
+                    budget.remaining,
+                    budget.amount,
+                    budget.denom,
+                    humantime::format_duration(budget.resets_in)
+                )?;
+            }
+        }
+        if !self.paused_objects.is_empty() {
+            writeln!(f, "* Paused workers:")?;
+            for paused in &self.paused_objects {
+                writeln!(f, "  - {} ({})", paused.object.short_name(), paused.policy)?;
+            }
+        }
         for (tpe, objects) in &self.workers {
             writeln!(f, "* {tpe:?} workers:")?;
             for desc in objects {
-                writeln!(f, "  - {} (id: {})", desc.object.short_name(), desc.id)?;
+                match desc.paused {
+                    Some(policy) => writeln!(
+                        f,
+                        "  - {} (id: {}, paused: {})",
+                        desc.object.short_name(),
+                        desc.id,
+                        policy
+                    )?,
+                    None => writeln!(f, "  - {} (id: {})", desc.object.short_name(), desc.id)?,
+                }
                 if let Some(WorkerData::Client {
                     misbehaviour,
                     refresh,
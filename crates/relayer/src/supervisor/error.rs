@@ -1,9 +1,13 @@
 use flex_error::define_error;
 
 use ibc_relayer_types::core::ics03_connection::connection::Counterparty;
-use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, ConnectionId, PortId};
+use ibc_relayer_types::core::ics24_host::identifier::{
+    ChainId, ChannelId, ClientId, ConnectionId, PortId,
+};
 
+use crate::config::Error as ConfigError;
 use crate::error::Error as RelayerError;
+use crate::foreign_client::ForeignClientError;
 use crate::spawn::SpawnError;
 use crate::supervisor::scan::Error as ScanError;
 
@@ -75,6 +79,63 @@ define_error! {
 
         HandleRecv
             |_| { "failed to receive the result of a command from the supervisor through a channel" },
+
+        Config
+            [ ConfigError ]
+            |_| { "failed to load the configuration to reload" },
+
+        MissingConfigPath
+            |_| { "the configuration cannot be reloaded as the supervisor does not know the path of the configuration file" },
+
+        InvalidConfig
+            [ ConfigError ]
+            |_| { "the configuration to reload is invalid" },
+
+        ChannelNotAllowed
+            {
+                port_id: PortId,
+                channel_id: ChannelId,
+                chain_id: ChainId,
+            }
+            |e| {
+                format_args!("channel {0}/{1} on chain {2} is not allowed by the packet filter",
+                    e.port_id, e.channel_id, e.chain_id)
+            },
+
+        ClientUpdate
+            {
+                client_id: ClientId,
+                chain_id: ChainId,
+            }
+            [ ForeignClientError ]
+            |e| {
+                format_args!("failed to update client {0} on chain {1}",
+                    e.client_id, e.chain_id)
+            },
+
+        WorkerNotFound
+            { object: String }
+            |e| {
+                format_args!("there is no worker for object {0}", e.object)
+            },
+
+        WorkerNotPaused
+            { object: String }
+            |e| {
+                format_args!("the worker for object {0} is not paused", e.object)
+            },
+
+        ChainNotFound
+            { chain_id: ChainId }
+            |e| {
+                format_args!("chain {0} is not in the configuration", e.chain_id)
+            },
+
+        ChainNotPaused
+            { chain_id: ChainId }
+            |e| {
+                format_args!("the workers of chain {0} are not paused", e.chain_id)
+            },
     }
 }
 
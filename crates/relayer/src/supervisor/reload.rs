@@ -0,0 +1,453 @@
+//! Reloading of the configuration of a running supervisor.
+//!
+//! The chains of the new configuration are compared with the ones of the current configuration:
+//! - the workers, event subscription and runtime of the chains which were removed are shut down;
+//! - the chains which were added are subscribed to, scanned, and their workers are spawned;
+//! - the chains whose configuration changed are restarted, as if they were removed and added back;
+//! - for the chains whose packet filter is the only setting which changed, the workers for the
+//!   channels which are no longer allowed are shut down, and the chain is scanned again in order
+//!   to spawn the workers for the channels which are now allowed.
+//!
+//! Any other setting of the new configuration only applies to the workers spawned after the reload.
+
+use alloc::collections::btree_map::BTreeMap as HashMap;
+use alloc::collections::BTreeSet;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use tracing::{error, error_span, info, warn};
+
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+
+use crate::{
+    chain::{
+        handle::ChainHandle,
+        requests::{IncludeProof, QueryChannelRequest, QueryHeight},
+    },
+    config::{self, validate_config, ChainConfig, Config, Diagnostic},
+    object::Object,
+    registry::SharedRegistry,
+    util::{
+        diff::{gdiff, Change},
+        lock::{LockExt, RwArc},
+        task::TaskHandle,
+    },
+    worker::WorkerMap,
+};
+
+use super::{
+    chain_scanner, client_state_filter::FilterPolicy, is_channel_allowed, scan::ScanMode,
+    should_scan, spawn_batch_worker, spawn_context, Error, SupervisorOptions,
+};
+
+/// The changes to the chains of the configuration applied by a reload
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConfigChanges {
+    /// Chains which were added to the configuration
+    pub added: Vec<ChainId>,
+
+    /// Chains which were removed from the configuration
+    pub removed: Vec<ChainId>,
+
+    /// Chains whose configuration changed, and which were restarted
+    pub updated: Vec<ChainId>,
+
+    /// Chains whose packet filter is the only setting which changed
+    pub filter_updated: Vec<ChainId>,
+}
+
+impl ConfigChanges {
+    /// Compute the changes to the chains between the `prev` and `next` configurations
+    pub fn between(prev: &Config, next: &Config) -> Self {
+        let prev_chains = chains_by_id(prev);
+        let next_chains = chains_by_id(next);
+
+        let mut changes = Self::default();
+
+        for change in gdiff(&prev_chains, &next_chains, |a, b| a == b) {
+            match change {
+                Change::Added(chain_id) => changes.added.push(chain_id.clone()),
+                Change::Removed(chain_id) => changes.removed.push(chain_id.clone()),
+                Change::Updated(chain_id) => {
+                    if only_packet_filter_changed(prev_chains[chain_id], next_chains[chain_id]) {
+                        changes.filter_updated.push(chain_id.clone());
+                    } else {
+                        changes.updated.push(chain_id.clone());
+                    }
+                }
+            }
+        }
+
+        changes
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.updated.is_empty()
+            && self.filter_updated.is_empty()
+    }
+}
+
+fn chains_by_id(config: &Config) -> HashMap<ChainId, &ChainConfig> {
+    config
+        .chains
+        .iter()
+        .map(|chain_config| (chain_config.id.clone(), chain_config))
+        .collect()
+}
+
+fn only_packet_filter_changed(prev: &ChainConfig, next: &ChainConfig) -> bool {
+    let next = ChainConfig {
+        packet_filter: prev.packet_filter.clone(),
+        ..next.clone()
+    };
+
+    *prev == next
+}
+
+/// Load the configuration to reload from the given file, and validate it
+/// the same way as the configuration loaded when Hermes starts
+fn load_config(path: &Path) -> Result<Config, Error> {
+    let config = config::load(path).map_err(Error::config)?;
+
+    match validate_config(&config) {
+        Ok(()) => Ok(config),
+        Err(Diagnostic::Warning(e)) => {
+            warn!("relayer may be misconfigured: {}", e);
+            Ok(config)
+        }
+        Err(Diagnostic::Error(e)) => Err(Error::invalid_config(e)),
+    }
+}
+
+/// Reloads the configuration of the supervisor, and applies it to
+/// the chain runtimes and the workers of the supervisor.
+#[derive(Clone)]
+pub struct Reloader<Chain: ChainHandle> {
+    pub(super) config: RwArc<Config>,
+    pub(super) registry: SharedRegistry<Chain>,
+    pub(super) client_state_filter: RwArc<FilterPolicy>,
+    pub(super) workers: RwArc<WorkerMap>,
+    pub(super) batch_tasks: RwArc<HashMap<ChainId, TaskHandle>>,
+    pub(super) options: SupervisorOptions,
+}
+
+impl<Chain: ChainHandle> Reloader<Chain> {
+    /// Reload the configuration from the file it was initially loaded from,
+    /// returning the changes which were applied to the chains.
+    pub fn reload(&self) -> Result<ConfigChanges, Error> {
+        let _span = error_span!("reload").entered();
+
+        let path = self
+            .options
+            .config_path
+            .as_ref()
+            .ok_or_else(Error::missing_config_path)?;
+
+        let new_config = load_config(path)?;
+
+        // Holding on to the batch workers for the whole reload
+        // prevents concurrent reloads from interleaving.
+        let mut batch_tasks = self.batch_tasks.acquire_write();
+
+        let changes = ConfigChanges::between(&self.config.acquire_read(), &new_config);
+
+        info!(
+            added = ?changes.added,
+            removed = ?changes.removed,
+            updated = ?changes.updated,
+            filter_updated = ?changes.filter_updated,
+            "reloading configuration from {}",
+            path.display()
+        );
+
+        let stopped = || changes.removed.iter().chain(&changes.updated);
+
+        // Stop processing the events of the stopped chains first,
+        // as the batch workers take the locks needed below.
+        for chain_id in stopped() {
+            if let Some(task) = batch_tasks.remove(chain_id) {
+                task.shutdown_and_wait();
+            }
+        }
+
+        // The chains whose workers must be spawned again once the configuration is reloaded
+        let mut rescan = BTreeSet::new();
+
+        {
+            let mut workers = self.workers.acquire_write();
+
+            for chain_id in stopped() {
+                for object in workers.objects_for_chain(chain_id) {
+                    rescan.insert(object.src_chain_id().clone());
+                    rescan.insert(object.dst_chain_id().clone());
+
+                    workers.shutdown_worker(&object);
+                }
+            }
+        }
+
+        {
+            let mut registry = self.registry.write();
+
+            for chain_id in stopped() {
+                registry.shutdown(chain_id);
+            }
+
+            registry.update_config(new_config.clone());
+        }
+
+        *self.config.acquire_write() = new_config;
+
+        let config = self.config.acquire_read();
+
+        for chain_id in &changes.filter_updated {
+            self.shutdown_filtered_workers(&config, chain_id);
+            rescan.insert(chain_id.clone());
+        }
+
+        rescan.extend(changes.added.iter().chain(&changes.updated).cloned());
+
+        for chain_id in changes.added.iter().chain(&changes.updated) {
+            match self.subscribe(chain_id) {
+                Ok(task) => {
+                    batch_tasks.insert(chain_id.clone(), task);
+                }
+                Err(e) => error!(chain = %chain_id, "failed to subscribe to events: {}", e),
+            }
+        }
+
+        if should_scan(&config, &self.options) {
+            let chains = rescan
+                .iter()
+                .filter_map(|chain_id| config.find_chain(chain_id));
+
+            for chain_config in chains {
+                self.spawn_workers(&config, chain_config);
+            }
+        }
+
+        info!("configuration reloaded");
+
+        Ok(changes)
+    }
+
+    /// Shut down the workers relaying on the channels of the given chain
+    /// which are no longer allowed by its packet filter, whether the chain
+    /// is the source or the destination of the workers.
+    fn shutdown_filtered_workers(&self, config: &Config, chain_id: &ChainId) {
+        // The channel ends on the destination chain are queried from the source chain,
+        // hence the workers are only locked once the filtered workers are known.
+        let objects = self.workers.acquire_read().objects_for_chain(chain_id);
+
+        let filtered: Vec<_> = objects
+            .into_iter()
+            .filter(|object| match object {
+                Object::Packet(p) => !self.is_worker_channel_allowed(
+                    config,
+                    chain_id,
+                    &p.src_chain_id,
+                    &p.src_port_id,
+                    &p.src_channel_id,
+                ),
+                Object::Channel(c) => !self.is_worker_channel_allowed(
+                    config,
+                    chain_id,
+                    &c.src_chain_id,
+                    &c.src_port_id,
+                    &c.src_channel_id,
+                ),
+                _ => false,
+            })
+            .collect();
+
+        let mut workers = self.workers.acquire_write();
+
+        for object in filtered {
+            info!(
+                "shutting down worker {} for a channel which is no longer allowed",
+                object.short_name()
+            );
+
+            workers.shutdown_worker(&object);
+        }
+    }
+
+    /// Whether the packet filter of the given chain allows its end of the channel
+    /// of a worker, which is either the source channel of the worker, or the
+    /// counterparty of the source channel if the worker relays to the given chain.
+    ///
+    /// The channel is deemed allowed if its counterparty cannot be determined.
+    fn is_worker_channel_allowed(
+        &self,
+        config: &Config,
+        chain_id: &ChainId,
+        src_chain_id: &ChainId,
+        src_port_id: &PortId,
+        src_channel_id: &ChannelId,
+    ) -> bool {
+        if src_chain_id == chain_id {
+            return is_channel_allowed(config, chain_id, src_port_id, src_channel_id);
+        }
+
+        let counterparty = self
+            .registry
+            .get_or_spawn(src_chain_id)
+            .map_err(Error::spawn)
+            .and_then(|chain| {
+                chain
+                    .query_channel(
+                        QueryChannelRequest {
+                            port_id: src_port_id.clone(),
+                            channel_id: src_channel_id.clone(),
+                            height: QueryHeight::Latest,
+                        },
+                        IncludeProof::No,
+                    )
+                    .map_err(Error::relayer)
+            });
+
+        match counterparty {
+            Ok((channel_end, _)) => match channel_end.counterparty().channel_id() {
+                Some(channel_id) => is_channel_allowed(
+                    config,
+                    chain_id,
+                    channel_end.counterparty().port_id(),
+                    channel_id,
+                ),
+                None => true,
+            },
+            Err(e) => {
+                warn!(
+                    chain = %chain_id,
+                    "failed to query the counterparty of channel {}/{} on chain {}, \
+                    keeping its workers: {}",
+                    src_port_id, src_channel_id, src_chain_id, e
+                );
+
+                true
+            }
+        }
+    }
+
+    /// Subscribe to the events of the given chain,
+    /// and spawn the worker processing them.
+    fn subscribe(&self, chain_id: &ChainId) -> Result<TaskHandle, Error> {
+        let chain = self.registry.get_or_spawn(chain_id).map_err(Error::spawn)?;
+
+        let subscription = chain.subscribe().map_err(Error::relayer)?;
+
+        Ok(spawn_batch_worker(
+            self.config.clone(),
+            self.registry.clone(),
+            self.client_state_filter.clone(),
+            self.workers.clone(),
+            chain,
+            subscription,
+        ))
+    }
+
+    /// Scan the given chain and spawn the workers for it.
+    fn spawn_workers(&self, config: &Config, chain_config: &ChainConfig) {
+        let scan_mode = if self.options.force_full_scan {
+            ScanMode::Full
+        } else {
+            ScanMode::Auto
+        };
+
+        let scan = chain_scanner(
+            config,
+            &mut self.registry.write(),
+            &mut self.client_state_filter.acquire_write(),
+            scan_mode,
+        )
+        .scan_chain(chain_config);
+
+        match scan {
+            Ok(scan) => spawn_context(
+                config,
+                &mut self.registry.write(),
+                &mut self.workers.acquire_write(),
+            )
+            .spawn_workers_for_chain(scan),
+
+            Err(e) => error!(chain = %chain_config.id, "failed to scan chain: {}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{load_config, ConfigChanges};
+
+    use crate::config::{filter::PacketFilter, load, store, Config};
+    use crate::supervisor::error::ErrorDetail;
+
+    fn config() -> Config {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/config/fixtures/relayer_conf_example.toml"
+        );
+
+        load(path).unwrap()
+    }
+
+    #[test]
+    fn identical_configs_have_no_changes() {
+        assert!(ConfigChanges::between(&config(), &config()).is_empty());
+    }
+
+    #[test]
+    fn chain_changes_are_classified() {
+        let prev = config();
+        let mut next = config();
+
+        let removed = next.chains.remove(0);
+        let updated = next.chains[0].id.clone();
+
+        next.chains[0].account_prefix = "osmo".to_string();
+
+        let mut added = next.chains[0].clone();
+        added.id = "chain_C".parse().unwrap();
+        next.chains.push(added.clone());
+
+        let changes = ConfigChanges::between(&prev, &next);
+
+        assert_eq!(changes.added, vec![added.id]);
+        assert_eq!(changes.removed, vec![removed.id]);
+        assert_eq!(changes.updated, vec![updated]);
+        assert!(changes.filter_updated.is_empty());
+    }
+
+    #[test]
+    fn packet_filter_changes_are_classified() {
+        let prev = config();
+        let mut next = config();
+
+        next.chains[0].packet_filter = PacketFilter::default();
+
+        let changes = ConfigChanges::between(&prev, &next);
+
+        assert_eq!(changes.filter_updated, vec![prev.chains[0].id.clone()]);
+        assert!(changes.updated.is_empty());
+    }
+
+    #[test]
+    fn invalid_config_is_not_reloaded() {
+        let mut config = config();
+        config.chains.push(config.chains[0].clone());
+
+        let path = std::env::temp_dir().join(format!(
+            "hermes-reload-invalid-config-{}.toml",
+            std::process::id()
+        ));
+
+        store(&config, &path).unwrap();
+        let result = load_config(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        let e = result.expect_err("a config with duplicate chains must be rejected");
+        assert!(matches!(e.detail(), ErrorDetail::InvalidConfig(_)));
+    }
+}
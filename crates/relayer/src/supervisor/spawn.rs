@@ -12,7 +12,6 @@ use crate::{
     object::{Channel, Client, Connection, Object, Packet, Wallet},
     registry::Registry,
     supervisor::error::Error as SupervisorError,
-    telemetry,
     worker::WorkerMap,
 };
 
@@ -71,10 +70,17 @@ impl<'a, Chain: ChainHandle> SpawnContext<'a, Chain> {
             self.spawn_workers_for_client(chain.clone(), client_scan);
         }
 
-        // Let's only spawn the wallet worker if telemetry is enabled,
-        // otherwise the worker just ends up issuing queries to the node
-        // without making anything of the result
-        telemetry!(self.spawn_wallet_worker(chain));
+        // Let's only spawn the wallet worker if telemetry is enabled or if the
+        // balance of the wallets must be monitored, otherwise the worker just
+        // ends up issuing queries to the node without making anything of the result
+        let monitor_wallet = self
+            .config
+            .find_chain(&scan.chain_id)
+            .map_or(false, |chain_config| chain_config.wallet.is_some());
+
+        if cfg!(feature = "telemetry") || monitor_wallet {
+            self.spawn_wallet_worker(chain);
+        }
     }
 
     pub fn spawn_wallet_worker(&mut self, chain: Chain) {
@@ -161,6 +167,11 @@ impl<'a, Chain: ChainHandle> SpawnContext<'a, Chain> {
     ) -> Result<bool, Error> {
         let config_conn_enabled = self.config.mode.connections.enabled;
 
+        // The localhost connection is open from genesis, and both of its ends are on this chain
+        if connection.connection_id.is_localhost() {
+            return Ok(false);
+        }
+
         let counterparty_chain = self
             .registry
             .get_or_spawn(&client.client_state.chain_id())
@@ -244,11 +255,20 @@ impl<'a, Chain: ChainHandle> SpawnContext<'a, Chain> {
             chan_state_dst
         );
 
+        // A channel being upgraded keeps relaying the packets it sent before the upgrade,
+        // while its channel worker completes the upgrade handshake.
+        let upgrading = chan_state_src.is_upgrading() || chan_state_dst.is_upgrading();
+
         if (mode.clients.enabled || mode.packets.enabled)
-            && chan_state_src.is_open()
-            && (chan_state_dst.is_open() || chan_state_dst.is_closed())
+            && (chan_state_src.is_open() || chan_state_src.is_upgrading())
+            && (chan_state_dst.is_open()
+                || chan_state_dst.is_closed()
+                || chan_state_dst.is_upgrading())
         {
-            if mode.clients.enabled {
+            // The localhost client is updated by its host, hence needs no client worker.
+            // Its counterparty chain is the host itself, so the packet worker below relays
+            // between two channel ends of the same chain.
+            if mode.clients.enabled && !client.client_id.is_localhost() {
                 // Spawn the client worker
                 let client_object = Object::Client(Client {
                     dst_client_id: client.client_id.clone(),
@@ -302,6 +322,24 @@ impl<'a, Chain: ChainHandle> SpawnContext<'a, Chain> {
                 }
             }
 
+            if mode.channels.enabled && upgrading {
+                let channel_object = Object::Channel(Channel {
+                    dst_chain_id: counterparty_chain.id(),
+                    src_chain_id: chain.id(),
+                    src_channel_id: channel_scan.channel.channel_id.clone(),
+                    src_port_id: channel_scan.channel.port_id.clone(),
+                });
+
+                self.workers
+                    .spawn(
+                        chain.clone(),
+                        counterparty_chain.clone(),
+                        &channel_object,
+                        self.config,
+                    )
+                    .then(|| info!("spawned channel worker: {}", channel_object.short_name()));
+            }
+
             Ok(mode.clients.enabled)
         } else if mode.channels.enabled {
             let has_packets = || {
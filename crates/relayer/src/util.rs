@@ -4,6 +4,7 @@ pub use block_on::{block_on, spawn_blocking};
 pub mod collate;
 pub mod debug_section;
 pub mod diff;
+pub mod dry_run;
 pub mod iter;
 pub mod lock;
 pub mod pretty;
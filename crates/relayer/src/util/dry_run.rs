@@ -0,0 +1,17 @@
+//! Dry-run mode, in which the relayer builds and simulates the transactions
+//! it would submit, but never broadcasts them.
+//!
+//! The transactions are checked for dry-run mode right before being broadcast,
+//! such that no transaction can be broadcast while it is enabled.
+
+use core::sync::atomic::{AtomicBool, Ordering::Relaxed};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+pub fn enable(enabled: bool) {
+    ENABLED.store(enabled, Relaxed);
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Relaxed)
+}
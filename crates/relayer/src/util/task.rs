@@ -8,6 +8,10 @@ use tracing::{debug, error, warn};
 
 use crate::util::lock::LockExt;
 
+/// How long a paused task without an interval pause
+/// waits before checking again whether it was resumed
+const PAUSED_INTERVAL: Duration = Duration::from_millis(100);
+
 /**
    A task handle holds the endpoints for stopping or waiting for a
    background task to terminate.
@@ -18,10 +22,14 @@ use crate::util::lock::LockExt;
 
    Otherwise, when the `TaskHandle` is dropped, it will stop the background
    task and wait for the background task to terminate before returning.
+
+   The background task can also be temporarily suspended by calling
+   [`pause`](TaskHandle::pause), until [`resume`](TaskHandle::resume) is called.
 */
 pub struct TaskHandle {
     shutdown_sender: Sender<()>,
     stopped: Arc<RwLock<bool>>,
+    paused: Arc<RwLock<bool>>,
     join_handle: DropJoinHandle,
 }
 
@@ -95,6 +103,9 @@ pub fn spawn_background_task<E: Display>(
     let stopped = Arc::new(RwLock::new(false));
     let write_stopped = stopped.clone();
 
+    let paused = Arc::new(RwLock::new(false));
+    let read_paused = paused.clone();
+
     let (shutdown_sender, receiver) = bounded(1);
 
     let join_handle = thread::spawn(move || {
@@ -104,6 +115,11 @@ pub fn spawn_background_task<E: Display>(
                 Ok(()) => {
                     break;
                 }
+                // The step runner is not called while the task is paused
+                _ if *read_paused.acquire_read() => {
+                    thread::sleep(interval_pause.unwrap_or(PAUSED_INTERVAL));
+                    continue;
+                }
                 _ => match step_runner() {
                     Ok(Next::Continue) => {}
                     Ok(Next::Abort) => {
@@ -132,6 +148,7 @@ pub fn spawn_background_task<E: Display>(
     TaskHandle {
         shutdown_sender,
         stopped,
+        paused,
         join_handle: DropJoinHandle(Some(join_handle)),
     }
 }
@@ -179,6 +196,31 @@ impl TaskHandle {
     pub fn is_stopped(&self) -> bool {
         *self.stopped.acquire_read()
     }
+
+    /**
+       Stop calling the step runner of the background task until
+       [`resume`](TaskHandle::resume) is called, without terminating the task.
+
+       A step which is already running when the task is paused completes
+       before the pause takes effect.
+    */
+    pub fn pause(&self) {
+        *self.paused.acquire_write() = true;
+    }
+
+    /**
+       Resume calling the step runner of a paused background task.
+    */
+    pub fn resume(&self) {
+        *self.paused.acquire_write() = false;
+    }
+
+    /**
+       Check whether the background task is paused.
+    */
+    pub fn is_paused(&self) -> bool {
+        *self.paused.acquire_read()
+    }
 }
 
 impl Drop for DropJoinHandle {
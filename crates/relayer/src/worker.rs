@@ -3,7 +3,7 @@ use core::fmt::{Display, Error as FmtError, Formatter};
 use ibc_relayer_types::core::ics04_channel::channel::Ordering;
 use serde::{Deserialize, Serialize};
 use std::sync::Mutex;
-use tracing::error;
+use tracing::{error, info};
 
 use crate::foreign_client::ForeignClient;
 use crate::link::{Link, LinkParameters, Resubmit};
@@ -11,6 +11,7 @@ use crate::{
     chain::handle::{ChainHandle, ChainHandlePair},
     config::Config,
     object::Object,
+    store,
 };
 
 pub mod retry_strategy;
@@ -19,7 +20,7 @@ mod error;
 pub use error::RunError;
 
 mod handle;
-pub use handle::{WorkerData, WorkerHandle};
+pub use handle::{PausePolicy, WorkerData, WorkerHandle};
 
 mod cmd;
 pub use cmd::WorkerCmd;
@@ -125,8 +126,17 @@ pub fn spawn_worker_tasks<ChainA: ChainHandle, ChainB: ChainHandle>(
             match link_res {
                 Ok(link) => {
                     let channel_ordering = link.a_to_b.channel().ordering;
-                    let should_clear_on_start =
-                        packets_config.clear_on_start || channel_ordering == Ordering::Ordered;
+                    let resume = store::can_resume(path);
+                    if resume {
+                        info!("resuming relaying from the stored state, skipping packet clearing on start");
+                    }
+
+                    // Always take the stored backlog, so that it does not outlive this worker
+                    let recovery_failed = link.a_to_b.recover_backlog();
+
+                    let should_clear_on_start = (packets_config.clear_on_start && !resume)
+                        || recovery_failed
+                        || channel_ordering == Ordering::Ordered;
 
                     let (cmd_tx, cmd_rx) = crossbeam_channel::unbounded();
                     let link = Arc::new(Mutex::new(link));
@@ -1,5 +1,6 @@
 use core::time::Duration;
 use crossbeam_channel::Receiver;
+use ibc_relayer_types::core::ics04_channel::channel::State;
 use tracing::{debug, error_span};
 
 use crate::channel::{channel_handshake_retry, Channel as RelayChannel};
@@ -34,6 +35,8 @@ pub fn spawn_channel_worker<ChainA: ChainHandle, ChainB: ChainHandle>(
     cmd_rx: Receiver<WorkerCmd>,
 ) -> TaskHandle {
     let mut complete_handshake_on_new_block = true;
+    // Whether an upgrade is in progress, whose flushing must be polled on every new block
+    let mut complete_upgrade_on_new_block = false;
     spawn_background_task(
         error_span!("worker.channel", channel = %channel.short_name()),
         Some(Duration::from_millis(200)),
@@ -41,7 +44,7 @@ pub fn spawn_channel_worker<ChainA: ChainHandle, ChainB: ChainHandle>(
             let max_block_times = max_block_times(&chains);
             if let Ok(cmd) = cmd_rx.try_recv() {
                 match cmd {
-                    WorkerCmd::IbcEvents { batch } => {
+                    WorkerCmd::IbcEvents { batch, .. } => {
                         // there can be up to two event for this channel, e.g. init and try.
                         // process the last event, the one with highest "rank".
                         let last_event = batch.events.last();
@@ -49,16 +52,38 @@ pub fn spawn_channel_worker<ChainA: ChainHandle, ChainB: ChainHandle>(
 
                         complete_handshake_on_new_block = false;
                         if let Some(event_with_height) = last_event {
+                            let event = &event_with_height.event;
+
                             retry_with_index(
                                 channel_handshake_retry::default_strategy(max_block_times),
-                                |index| match RelayChannel::restore_from_event(
-                                    chains.a.clone(),
-                                    chains.b.clone(),
-                                    event_with_height.event.clone(),
-                                ) {
-                                    Ok(mut handshake_channel) => handshake_channel
-                                        .step_event(&event_with_height.event, index),
-                                    Err(_) => RetryResult::Retry(index),
+                                |index| {
+                                    // The upgrade events do not carry the connection of the channel,
+                                    // hence the channel is restored from its state on chain instead.
+                                    if event.upgrade_attributes().is_some() {
+                                        complete_upgrade_on_new_block = true;
+
+                                        match RelayChannel::restore_from_upgrade_event(
+                                            chains.a.clone(),
+                                            chains.b.clone(),
+                                            event,
+                                        ) {
+                                            Ok(mut upgrade_channel) => {
+                                                upgrade_channel.step_upgrade_event(event, index)
+                                            }
+                                            Err(_) => RetryResult::Retry(index),
+                                        }
+                                    } else {
+                                        match RelayChannel::restore_from_event(
+                                            chains.a.clone(),
+                                            chains.b.clone(),
+                                            event.clone(),
+                                        ) {
+                                            Ok(mut handshake_channel) => {
+                                                handshake_channel.step_event(event, index)
+                                            }
+                                            Err(_) => RetryResult::Retry(index),
+                                        }
+                                    }
                                 },
                             )
                             .map_err(|e| TaskError::Fatal(RunError::retry(e)))
@@ -87,6 +112,10 @@ pub fn spawn_channel_worker<ChainA: ChainHandle, ChainB: ChainHandle>(
                                 height,
                             ) {
                                 Ok((mut handshake_channel, state)) => {
+                                    if matches!(state, State::Flushing | State::FlushComplete) {
+                                        complete_upgrade_on_new_block = true;
+                                    }
+
                                     handshake_channel.step_state(state, index)
                                 }
                                 Err(_) => RetryResult::Retry(index),
@@ -95,6 +124,27 @@ pub fn spawn_channel_worker<ChainA: ChainHandle, ChainB: ChainHandle>(
                         .map_err(|e| TaskError::Fatal(RunError::retry(e)))
                     }
 
+                    WorkerCmd::NewBlock {
+                        height: current_height,
+                        new_block: _,
+                    } if complete_upgrade_on_new_block => {
+                        let height = current_height
+                            .decrement()
+                            .map_err(|e| TaskError::Fatal(RunError::ics02(e)))?;
+
+                        match RelayChannel::restore_from_state(
+                            chains.a.clone(),
+                            chains.b.clone(),
+                            channel.clone(),
+                            height,
+                        ) {
+                            Ok((mut upgrade_channel, _)) => {
+                                Ok(upgrade_channel.step_upgrade_flush())
+                            }
+                            Err(_) => Ok(Next::Continue),
+                        }
+                    }
+
                     // nothing to do
                     _ => Ok(Next::Continue),
                 }
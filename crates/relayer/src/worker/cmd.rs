@@ -3,12 +3,17 @@ use core::fmt::{Display, Error as FmtError, Formatter};
 use ibc_relayer_types::{core::ics02_client::events::NewBlock, Height};
 
 use crate::event::source::EventBatch;
+use crate::store::HeightGuard;
 
 /// A command for a [`WorkerHandle`](crate::worker::WorkerHandle).
 #[derive(Debug, Clone)]
 pub enum WorkerCmd {
     /// A batch of packet events need to be relayed
-    IbcEvents { batch: EventBatch },
+    IbcEvents {
+        batch: EventBatch,
+        /// Records the height of the batch as processed once the batch is relayed
+        height_guard: HeightGuard,
+    },
 
     /// A new block has been committed
     NewBlock { height: Height, new_block: NewBlock },
@@ -46,7 +51,7 @@ impl WorkerCmd {
 impl Display for WorkerCmd {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
         match self {
-            WorkerCmd::IbcEvents { batch } => {
+            WorkerCmd::IbcEvents { batch, .. } => {
                 write!(f, "IbcEvents batch from {}: ", batch.chain_id)?;
                 for e in &batch.events {
                     write!(f, "{e}; ")?;
@@ -13,6 +13,7 @@ use ibc_relayer_types::{
 
 use crate::chain::tracking::TrackingId;
 use crate::event::IbcEventWithHeight;
+use crate::store::HeightGuard;
 use crate::util::lock::{LockExt, RwArc};
 use crate::util::task::TaskHandle;
 use crate::{event::source::EventBatch, object::Object};
@@ -25,11 +26,34 @@ pub enum WorkerData {
     Client { misbehaviour: bool, refresh: bool },
 }
 
+/// What to do with the commands sent to a paused worker.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PausePolicy {
+    /// Buffer the commands, and process them once the worker is resumed
+    #[default]
+    Buffer,
+
+    /// Drop the commands, and clear the pending packets once the worker is resumed.
+    /// The heights of the dropped events are not recorded as processed.
+    Drop,
+}
+
+impl fmt::Display for PausePolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PausePolicy::Buffer => write!(f, "buffer"),
+            PausePolicy::Drop => write!(f, "drop"),
+        }
+    }
+}
+
 pub struct WorkerHandle {
     id: WorkerId,
     object: Object,
     data: Option<WorkerData>,
     tx: RwArc<Option<Sender<WorkerCmd>>>,
+    paused: RwArc<Option<PausePolicy>>,
     task_handles: Vec<TaskHandle>,
 }
 
@@ -46,11 +70,17 @@ impl WorkerHandle {
             object,
             data,
             tx: <RwArc<_>>::new_lock(tx),
+            paused: <RwArc<_>>::new_lock(None),
             task_handles,
         }
     }
 
     pub fn try_send_command(&self, cmd: WorkerCmd) {
+        if self.paused() == Some(PausePolicy::Drop) {
+            trace!(worker = %self.object.short_name(), "dropping command {} sent to paused worker", cmd);
+            return;
+        }
+
         let res = if let Some(tx) = self.tx.acquire_read().as_ref() {
             tx.send(cmd)
         } else {
@@ -70,6 +100,7 @@ impl WorkerHandle {
         events: Vec<IbcEventWithHeight>,
         chain_id: ChainId,
         tracking_id: TrackingId,
+        height_guard: HeightGuard,
     ) {
         let batch = EventBatch {
             chain_id,
@@ -78,7 +109,10 @@ impl WorkerHandle {
             tracking_id,
         };
 
-        self.try_send_command(WorkerCmd::IbcEvents { batch });
+        self.try_send_command(WorkerCmd::IbcEvents {
+            batch,
+            height_guard,
+        });
     }
 
     /// Send a batch of [`NewBlock`] event to the worker.
@@ -107,6 +141,36 @@ impl WorkerHandle {
         // Drop handle automatically handles the waiting for tasks to terminate.
     }
 
+    /// Pause all worker tasks, until [`resume`](WorkerHandle::resume) is called.
+    /// The commands sent to the worker while it is paused are buffered
+    /// or dropped according to the given policy.
+    pub fn pause(&self, policy: PausePolicy) {
+        *self.paused.acquire_write() = Some(policy);
+
+        for task in self.task_handles.iter() {
+            task.pause()
+        }
+    }
+
+    /// Resume all worker tasks.
+    pub fn resume(&self) {
+        let policy = self.paused.acquire_write().take();
+
+        for task in self.task_handles.iter() {
+            task.resume()
+        }
+
+        // Pick up the packets whose events were dropped while the worker was paused
+        if policy == Some(PausePolicy::Drop) {
+            self.clear_pending_packets();
+        }
+    }
+
+    /// The policy the worker was paused with, if it is paused.
+    pub fn paused(&self) -> Option<PausePolicy> {
+        *self.paused.acquire_read()
+    }
+
     pub fn is_stopped(&self) -> bool {
         for task in self.task_handles.iter() {
             if !task.is_stopped() {
@@ -167,6 +231,59 @@ impl fmt::Debug for WorkerHandle {
         f.debug_struct("WorkerHandle")
             .field("id", &self.id)
             .field("object", &self.object)
+            .field("paused", &self.paused())
             .finish_non_exhaustive()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::object::Packet;
+
+    fn worker() -> (WorkerHandle, crossbeam_channel::Receiver<WorkerCmd>) {
+        let object = Object::Packet(Packet {
+            dst_chain_id: "chain-b".parse().unwrap(),
+            src_chain_id: "chain-a".parse().unwrap(),
+            src_channel_id: "channel-0".parse().unwrap(),
+            src_port_id: "transfer".parse().unwrap(),
+        });
+
+        let (tx, rx) = crossbeam_channel::unbounded();
+        let handle = WorkerHandle::new(WorkerId::new(1), object, None, Some(tx), vec![]);
+
+        (handle, rx)
+    }
+
+    #[test]
+    fn paused_worker_buffers_commands() {
+        let (worker, rx) = worker();
+
+        worker.pause(PausePolicy::Buffer);
+        worker.clear_pending_packets();
+
+        assert_eq!(worker.paused(), Some(PausePolicy::Buffer));
+        assert!(rx.try_recv().unwrap().is_clear_pending_packets());
+
+        worker.resume();
+
+        assert_eq!(worker.paused(), None);
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn paused_worker_drops_commands() {
+        let (worker, rx) = worker();
+
+        worker.pause(PausePolicy::Drop);
+        worker.clear_pending_packets();
+
+        assert!(rx.try_recv().is_err());
+
+        // Resuming the worker clears the packets whose events were dropped
+        worker.resume();
+
+        assert!(rx.try_recv().unwrap().is_clear_pending_packets());
+    }
+}
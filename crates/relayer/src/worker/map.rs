@@ -13,12 +13,18 @@ use crate::{
     telemetry,
 };
 
-use super::{spawn_worker_tasks, WorkerHandle, WorkerId};
+use super::{spawn_worker_tasks, PausePolicy, WorkerHandle, WorkerId};
 
 /// Manage the lifecycle of [`WorkerHandle`]s associated with [`Object`]s.
+///
+/// The workers which are paused, either individually or along with all the
+/// workers of a chain, are paused again when they are respawned, until they are
+/// explicitly resumed.
 #[derive(Debug)]
 pub struct WorkerMap {
     workers: HashMap<Object, WorkerHandle>,
+    paused_objects: HashMap<Object, PausePolicy>,
+    paused_chains: HashMap<ChainId, PausePolicy>,
     latest_worker_id: WorkerId,
 }
 
@@ -26,6 +32,8 @@ impl Default for WorkerMap {
     fn default() -> Self {
         Self {
             workers: HashMap::new(),
+            paused_objects: HashMap::new(),
+            paused_chains: HashMap::new(),
             latest_worker_id: WorkerId::new(0),
         }
     }
@@ -161,12 +169,21 @@ impl WorkerMap {
     ) -> WorkerHandle {
         telemetry!(worker, metric_type(object), 1);
 
-        spawn_worker_tasks(
+        let worker = spawn_worker_tasks(
             ChainHandlePair { a: src, b: dst },
             self.next_worker_id(),
             object.clone(),
             config,
-        )
+        );
+
+        // The worker cannot have received any command yet,
+        // so nothing is submitted before it is paused.
+        if let Some(policy) = self.pause_policy(object) {
+            debug!(worker.object = %object.short_name(), "pausing respawned worker");
+            worker.pause(policy);
+        }
+
+        worker
     }
 
     /// Compute the next worker id
@@ -199,6 +216,94 @@ impl WorkerMap {
         self.workers.values()
     }
 
+    /// Pause the worker associated with the given [`Object`], as well as
+    /// the workers spawned for that object until it is resumed.
+    ///
+    /// Returns whether or not there is a worker for the given object.
+    pub fn pause_worker(&mut self, object: &Object, policy: PausePolicy) -> bool {
+        if !self.workers.contains_key(object) {
+            return false;
+        }
+
+        self.paused_objects.insert(object.clone(), policy);
+        self.apply_pause(object);
+
+        true
+    }
+
+    /// Resume the worker associated with the given [`Object`], unless
+    /// the workers of one of its chains are paused.
+    ///
+    /// Returns whether or not the worker was paused.
+    pub fn resume_worker(&mut self, object: &Object) -> bool {
+        if self.paused_objects.remove(object).is_none() {
+            return false;
+        }
+
+        self.apply_pause(object);
+
+        true
+    }
+
+    /// Pause all the workers of the given chain, as well as
+    /// the workers spawned for that chain until it is resumed.
+    pub fn pause_chain(&mut self, chain_id: &ChainId, policy: PausePolicy) {
+        self.paused_chains.insert(chain_id.clone(), policy);
+
+        for object in self.objects_for_chain(chain_id) {
+            self.apply_pause(&object);
+        }
+    }
+
+    /// Resume the workers of the given chain, except for the ones which are
+    /// paused individually or whose counterparty chain is paused.
+    ///
+    /// Returns whether or not the chain was paused.
+    pub fn resume_chain(&mut self, chain_id: &ChainId) -> bool {
+        if self.paused_chains.remove(chain_id).is_none() {
+            return false;
+        }
+
+        for object in self.objects_for_chain(chain_id) {
+            self.apply_pause(&object);
+        }
+
+        true
+    }
+
+    /// The objects which are paused individually, along with their pause policy.
+    pub fn paused_objects(&self) -> impl Iterator<Item = (&Object, &PausePolicy)> {
+        self.paused_objects.iter()
+    }
+
+    /// The chains whose workers are paused, along with their pause policy.
+    pub fn paused_chains(&self) -> impl Iterator<Item = (&ChainId, &PausePolicy)> {
+        self.paused_chains.iter()
+    }
+
+    /// The policy with which the worker for the given object must be paused, if any.
+    ///
+    /// The pause of the object itself takes precedence over the pause of its chains.
+    fn pause_policy(&self, object: &Object) -> Option<PausePolicy> {
+        self.paused_objects
+            .get(object)
+            .or_else(|| self.paused_chains.get(object.src_chain_id()))
+            .or_else(|| self.paused_chains.get(object.dst_chain_id()))
+            .copied()
+    }
+
+    /// Pause or resume the worker for the given object according to
+    /// the pauses of the object and its chains.
+    fn apply_pause(&self, object: &Object) {
+        if let Some(worker) = self.workers.get(object) {
+            match self.pause_policy(object) {
+                Some(policy) => worker.pause(policy),
+                None if worker.paused().is_some() => worker.resume(),
+                None => {}
+            }
+        }
+    }
+
     /// Shutdown the worker associated with the given [`Object`], synchronously.
     pub fn shutdown_worker(&mut self, object: &Object) {
         if let Some(handle) = self.workers.remove(object) {
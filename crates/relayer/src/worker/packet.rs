@@ -9,6 +9,7 @@ use itertools::Itertools;
 use moka::sync::Cache;
 use std::borrow::BorrowMut;
 use std::sync::{Arc, Mutex};
+use std::time::Instant;
 use tracing::{debug, warn};
 
 use crossbeam_channel::Receiver;
@@ -16,7 +17,7 @@ use ibc_proto::ibc::apps::fee::v1::{IdentifiedPacketFees, QueryIncentivizedPacke
 use ibc_proto::ibc::core::channel::v1::PacketId;
 use ibc_relayer_types::applications::ics29_fee::events::IncentivizedPacket;
 use ibc_relayer_types::applications::transfer::{Amount, Coin, RawCoin};
-use ibc_relayer_types::core::ics04_channel::events::WriteAcknowledgement;
+use ibc_relayer_types::core::ics04_channel::events::{SendPacket, WriteAcknowledgement};
 use ibc_relayer_types::core::ics04_channel::packet::Sequence;
 use ibc_relayer_types::events::{IbcEvent, IbcEventType};
 use tracing::{error, error_span, trace};
@@ -24,11 +25,11 @@ use tracing::{error, error_span, trace};
 use ibc_relayer_types::Height;
 
 use crate::chain::handle::ChainHandle;
-use crate::config::filter::FeePolicy;
+use crate::config::filter::{FeePolicy, SkipReason};
 use crate::event::source::EventBatch;
 use crate::foreign_client::HasExpiredOrFrozenError;
-use crate::link::Resubmit;
 use crate::link::{error::LinkError, Link};
+use crate::link::{PacketRelayCost, RelayPath, Resubmit};
 use crate::object::Packet;
 use crate::telemetry;
 use crate::util::lock::{LockExt, RwArc};
@@ -73,8 +74,21 @@ pub fn spawn_packet_worker<ChainA: ChainHandle, ChainB: ChainHandle>(
         )
     };
 
+    // Set while the fee budget of either chain is exhausted
+    let mut paused_until = None;
+
     spawn_background_task(span, Some(Duration::from_millis(1000)), move || {
-        handle_execute_schedule(&mut link.lock().unwrap(), &path, resubmit)?;
+        if paused_until.map_or(false, |until| Instant::now() < until) {
+            return Ok(Next::Continue);
+        }
+
+        handle_execute_schedule(
+            &mut link.lock().unwrap(),
+            &path,
+            resubmit,
+            &mut paused_until,
+        )?;
+
         Ok(Next::Continue)
     })
 }
@@ -205,7 +219,7 @@ fn handle_packet_cmd<ChainA: ChainHandle, ChainB: ChainHandle>(
 ) -> Result<(), TaskError<RunError>> {
     // Handle packet clearing which is triggered from a command
     let (do_clear, maybe_height) = match &cmd {
-        WorkerCmd::IbcEvents { batch } => {
+        WorkerCmd::IbcEvents { batch, .. } => {
             if *should_clear_on_start {
                 (true, Some(batch.height))
             } else {
@@ -236,8 +250,17 @@ fn handle_packet_cmd<ChainA: ChainHandle, ChainB: ChainHandle>(
     }
 
     // Handle command-specific task
-    if let WorkerCmd::IbcEvents { batch } = cmd {
+    if let WorkerCmd::IbcEvents {
+        batch,
+        height_guard,
+    } = cmd
+    {
+        // The height of the batch is left unprocessed if its events could not be scheduled
         handle_update_schedule(link, clear_interval, path, batch)?;
+
+        // Persist the new schedule before the height of the batch is recorded as processed
+        link.a_to_b.store_backlog();
+        height_guard.processed();
     }
 
     Ok(())
@@ -261,7 +284,11 @@ fn handle_incentivized_packet_cmd<ChainA: ChainHandle, ChainB: ChainHandle>(
     fee_filter: &FeePolicy,
 ) -> Result<(), TaskError<RunError>> {
     // Handle command-specific task
-    if let WorkerCmd::IbcEvents { mut batch } = cmd {
+    if let WorkerCmd::IbcEvents {
+        mut batch,
+        height_guard,
+    } = cmd
+    {
         // Iterate through the batch in order to retrieve the IncentivizedPacket
         // which will be used to confirm if a SendPacket event is incentivized.
         for event in batch.events.clone() {
@@ -278,8 +305,20 @@ fn handle_incentivized_packet_cmd<ChainA: ChainHandle, ChainB: ChainHandle>(
             // In addition if the WriteAcknowledgment are not relayed, no fees will be paid.
             //IbcEvent::WriteAcknowledgement(ack) => get_incentivized_for_write_acknowledgement(link, ack, event.height.revision_height(), incentivized_ack_cache.clone()),
         }
-        filter_batch(batch.borrow_mut(), incentivized_recv_cache, fee_filter);
-        handle_update_schedule(link, 0, path, batch)
+        filter_batch(
+            &link.a_to_b,
+            batch.borrow_mut(),
+            incentivized_recv_cache,
+            fee_filter,
+        );
+        // The height of the batch is left unprocessed if its events could not be scheduled
+        handle_update_schedule(link, 0, path, batch)?;
+
+        // Persist the new schedule before the height of the batch is recorded as processed
+        link.a_to_b.store_backlog();
+        height_guard.processed();
+
+        Ok(())
     } else {
         Ok(())
     }
@@ -330,7 +369,8 @@ fn _get_incentivized_for_write_acknowledgement<ChainA: ChainHandle, ChainB: Chai
 /// Using the configured FeesFilter and observed/queried information for
 /// incentivized packets, determine if the SendPacket and WriteAcknowledgement events
 /// should be relayed or not.
-fn filter_batch(
+fn filter_batch<ChainA: ChainHandle, ChainB: ChainHandle>(
+    relay_path: &RelayPath<ChainA, ChainB>,
     batch: &mut EventBatch,
     incentivized_recv_cache: &RwArc<Cache<Sequence, IncentivizedPacket>>,
     fee_filter: &FeePolicy,
@@ -340,24 +380,94 @@ fn filter_batch(
             .acquire_read()
             .get(&packet.packet.sequence)
             .map_or(false, |incentivized_event| {
-                let grouped_amounts =
-                    retrieve_all_fees_from_incentivized_packet(incentivized_event);
-
-                fee_filter.should_relay(IbcEventType::SendPacket, &grouped_amounts)
+                match check_fees(
+                    relay_path,
+                    fee_filter,
+                    packet,
+                    &incentivized_event,
+                    e.height,
+                ) {
+                    Ok(()) => true,
+                    Err(reason) => {
+                        debug!(
+                            sequence = %packet.packet.sequence,
+                            "not relaying incentivized packet: {reason}"
+                        );
+
+                        telemetry!(
+                            ics29_packet_skipped,
+                            &relay_path.src_chain().id(),
+                            relay_path.src_channel_id(),
+                            relay_path.src_port_id(),
+                            reason.as_str()
+                        );
+
+                        false
+                    }
+                }
             }),
         _ => true,
     });
 }
 
+/// Check that the fees of an incentivized packet satisfy the fee policy.
+///
+/// The `recv_fee` is checked against the minimum fees. If a profitability
+/// policy is configured, the `recv_fee` and `ack_fee` must also cover the estimated
+/// cost of relaying the packet, or the `timeout_fee` the cost of timing it out.
+fn check_fees<ChainA: ChainHandle, ChainB: ChainHandle>(
+    relay_path: &RelayPath<ChainA, ChainB>,
+    fee_filter: &FeePolicy,
+    event: &SendPacket,
+    incentivized_packet: &IncentivizedPacket,
+    height: Height,
+) -> Result<(), SkipReason> {
+    let recv_fees = group_fees_by_denom(incentivized_packet.total_recv_fee.iter());
+
+    if !fee_filter.should_relay(IbcEventType::SendPacket, &recv_fees) {
+        return Err(SkipReason::MinFee);
+    }
+
+    let profitability = match fee_filter.profitability() {
+        Some(profitability) => profitability,
+        None => return Ok(()),
+    };
+
+    let cost = relay_path
+        .estimate_packet_relay_cost(event, height)
+        .map_err(|e| {
+            warn!(
+                sequence = %event.packet.sequence,
+                "failed to estimate the cost of relaying incentivized packet: {e}"
+            );
+
+            SkipReason::EstimationFailed
+        })?;
+
+    match cost {
+        PacketRelayCost::RecvAndAck(costs) => {
+            let fees = group_fees_by_denom(
+                incentivized_packet
+                    .total_recv_fee
+                    .iter()
+                    .chain(incentivized_packet.total_ack_fee.iter()),
+            );
+
+            profitability.check(&fees, &costs)
+        }
+        PacketRelayCost::Timeout(costs) => {
+            let fees = group_fees_by_denom(incentivized_packet.total_timeout_fee.iter());
+
+            profitability.check(&fees, &costs)
+        }
+    }
+}
+
 /// Multiple fees with different denoms can be specified as rewards,
-/// in an `IncentivizedPacket`. This method extract all and groups all
-/// the fees with the same denom.
-fn retrieve_all_fees_from_incentivized_packet(
-    incentivized_packet: IncentivizedPacket,
-) -> Vec<RawCoin> {
-    incentivized_packet
-        .total_recv_fee
-        .iter()
+/// in an `IncentivizedPacket`. This method groups all the fees
+/// with the same denom.
+fn group_fees_by_denom<'a>(fees: impl Iterator<Item = &'a RawCoin>) -> Vec<RawCoin> {
+    fees.sorted_by(|a, b| a.denom.cmp(&b.denom))
         .group_by(|a| &a.denom)
         .into_iter()
         .map(|(key, group)| {
@@ -407,6 +517,7 @@ fn handle_execute_schedule<ChainA: ChainHandle, ChainB: ChainHandle>(
     link: &mut Link<ChainA, ChainB>,
     _path: &Packet,
     resubmit: Resubmit,
+    paused_until: &mut Option<Instant>,
 ) -> Result<(), TaskError<RunError>> {
     link.a_to_b
         .refresh_schedule()
@@ -415,6 +526,15 @@ fn handle_execute_schedule<ChainA: ChainHandle, ChainB: ChainHandle>(
     link.a_to_b.execute_schedule().map_err(|e| {
         if e.is_expired_or_frozen_error() {
             TaskError::Fatal(RunError::link(e))
+        } else if let Some(resets_in) = e.fee_budget_resets_in() {
+            // The pending operational data is kept, and submitted once the budget is replenished
+            warn!(
+                "pausing packet relaying until the fee budget is replenished in {}",
+                humantime::format_duration(resets_in)
+            );
+
+            *paused_until = Some(Instant::now() + resets_in);
+            TaskError::Ignore(RunError::link(e))
         } else {
             error!("will retry: schedule execution encountered error: {}", e,);
             TaskError::Ignore(RunError::link(e))
@@ -434,6 +554,8 @@ fn handle_execute_schedule<ChainA: ChainHandle, ChainB: ChainHandle>(
         ));
     }
 
+    link.a_to_b.store_backlog();
+
     Ok(())
 }
 
@@ -1,53 +1,216 @@
+use std::collections::BTreeMap;
 use std::time::Duration;
 
-use tracing::{error_span, trace, warn};
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, error, error_span, info, trace, warn};
 
 use crate::{
+    account::Balance,
     chain::handle::ChainHandle,
+    config::WalletConfig,
     telemetry,
+    util::lock::{LockExt, RwArc},
     util::task::{spawn_background_task, Next, TaskError, TaskHandle},
 };
 
+/// Alert raised while the balance of a relayer wallet is below the configured `min_balance`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LowBalanceAlert {
+    pub chain_id: ChainId,
+    pub key_name: String,
+    pub account: String,
+    pub balance: String,
+    pub denom: String,
+    pub min_balance: u64,
+}
+
+/// The alerts currently raised, by chain and account
+static LOW_BALANCE_ALERTS: Lazy<RwArc<BTreeMap<(ChainId, String), LowBalanceAlert>>> =
+    Lazy::new(|| RwArc::new_lock(BTreeMap::new()));
+
+/// The alerts currently raised for the relayer wallets whose balance is too low, on all chains
+pub fn low_balance_alerts() -> Vec<LowBalanceAlert> {
+    LOW_BALANCE_ALERTS
+        .acquire_read()
+        .values()
+        .cloned()
+        .collect()
+}
+
 pub fn spawn_wallet_worker<Chain: ChainHandle>(chain: Chain) -> TaskHandle {
     let span = error_span!("wallet", chain = %chain.id());
 
     spawn_background_task(span, Some(Duration::from_secs(5)), move || {
-        let key = chain.get_key().map_err(|e| {
-            TaskError::Fatal(format!("failed to get key in use by the relayer: {e}"))
+        let config = chain.config().map_err(|e| {
+            TaskError::Ignore(format!("failed to get the configuration of the chain: {e}"))
         })?;
 
-        let balance = chain.query_balance(None, None).map_err(|e| {
-            TaskError::Ignore(format!("failed to query balance for the account: {e}"))
+        let keys = chain.get_keys().map_err(|e| {
+            TaskError::Fatal(format!("failed to get keys in use by the relayer: {e}"))
         })?;
 
-        match balance.amount.parse::<f64>() {
-            Ok(amount) => {
-                telemetry!(
-                    wallet_balance,
-                    &chain.id(),
-                    &key.account(),
-                    amount,
-                    &balance.denom,
-                );
-                trace!(%amount, denom = %balance.denom, account = %key.account(), "wallet balance");
-                telemetry!(
-                    update_period_fees,
-                    &chain.id(),
-                    &key.account(),
-                    &balance.denom
-                );
+        for (key_name, key) in keys {
+            // A failure to query the balance of one account must not prevent
+            // the balances of the other accounts from being checked
+            let balance = match chain.query_balance(Some(key_name.clone()), None) {
+                Ok(balance) => balance,
+                Err(e) => {
+                    error!(
+                        %key_name, account = %key.account(),
+                        "failed to query balance for the account: {e}"
+                    );
+                    continue;
+                }
+            };
+
+            match balance.amount.parse::<f64>() {
+                Ok(amount) => {
+                    telemetry!(
+                        wallet_balance,
+                        &chain.id(),
+                        &key.account(),
+                        amount,
+                        &balance.denom,
+                    );
+                    trace!(%amount, denom = %balance.denom, account = %key.account(), "wallet balance");
+                    telemetry!(
+                        update_period_fees,
+                        &chain.id(),
+                        &key.account(),
+                        &balance.denom
+                    );
+                }
+                Err(e) => {
+                    warn!(
+                        %balance.amount, denom = %balance.denom, account = %key.account(),
+                        "unable to parse the wallet balance into a f64, the balance will therefore not be reported to telemetry. Reason: {}", e
+                    );
+                }
             }
-            Err(e) => {
-                warn!(
-                    %balance.amount, denom = %balance.denom, account = %key.account(),
-                    "unable to parse the wallet balance into a f64, the balance will therefore not be reported to telemetry. Reason: {}", e
-                );
+
+            if let Some(wallet_config) = &config.wallet {
+                check_balance(&chain, wallet_config, &key_name, &key.account(), &balance);
             }
         }
+
         Ok(Next::Continue)
     })
 }
 
+/// Raise or clear the low balance alert of the given wallet, and top it up
+/// from the treasury account if its balance is below the minimum.
+fn check_balance<Chain: ChainHandle>(
+    chain: &Chain,
+    config: &WalletConfig,
+    key_name: &str,
+    account: &str,
+    balance: &Balance,
+) {
+    let chain_id = chain.id();
+
+    let amount = match balance.amount.parse::<u128>() {
+        Ok(amount) => amount,
+        Err(e) => {
+            warn!(
+                %balance.amount, denom = %balance.denom, %account,
+                "unable to parse the wallet balance, skipping the minimum balance check. Reason: {}", e
+            );
+            return;
+        }
+    };
+
+    let low_balance = amount < u128::from(config.min_balance);
+
+    telemetry!(
+        wallet_low_balance,
+        &chain_id,
+        account,
+        &balance.denom,
+        low_balance
+    );
+
+    let alert_key = (chain_id.clone(), account.to_string());
+
+    if !low_balance {
+        if LOW_BALANCE_ALERTS
+            .acquire_write()
+            .remove(&alert_key)
+            .is_some()
+        {
+            info!(
+                %key_name, %account, balance = %amount, denom = %balance.denom,
+                "wallet balance is back above the configured minimum"
+            );
+        }
+
+        return;
+    }
+
+    let alert = LowBalanceAlert {
+        chain_id: chain_id.clone(),
+        key_name: key_name.to_string(),
+        account: account.to_string(),
+        balance: balance.amount.clone(),
+        denom: balance.denom.clone(),
+        min_balance: config.min_balance,
+    };
+
+    if LOW_BALANCE_ALERTS
+        .acquire_write()
+        .insert(alert_key, alert)
+        .is_none()
+    {
+        warn!(
+            %key_name, %account, balance = %amount, denom = %balance.denom, min_balance = config.min_balance,
+            "wallet balance is below the configured minimum"
+        );
+    } else {
+        debug!(
+            %key_name, %account, balance = %amount, denom = %balance.denom, min_balance = config.min_balance,
+            "wallet balance is still below the configured minimum"
+        );
+    }
+
+    let Some((target_balance, treasury_key_name)) = config.top_up() else {
+        return;
+    };
+
+    if treasury_key_name == key_name {
+        return;
+    }
+
+    let top_up = u128::from(target_balance).saturating_sub(amount);
+
+    info!(
+        %key_name, %account, amount = %top_up, denom = %balance.denom, treasury = %treasury_key_name,
+        "topping up wallet from the treasury account"
+    );
+
+    match chain.send_coins(
+        treasury_key_name.to_string(),
+        account.to_string(),
+        top_up,
+        balance.denom.clone(),
+    ) {
+        Ok(()) => {
+            telemetry!(wallet_top_up, &chain_id, account, &balance.denom);
+
+            info!(
+                %key_name, %account, amount = %top_up, denom = %balance.denom,
+                "topped up wallet from the treasury account"
+            );
+        }
+        Err(e) => {
+            error!(
+                %key_name, %account, amount = %top_up, denom = %balance.denom, treasury = %treasury_key_name,
+                "failed to top up wallet from the treasury account: {}", e
+            );
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use ibc_relayer_types::bigint::U256;
@@ -125,12 +125,27 @@ pub struct TelemetryState {
     /// How many IBC events did Hermes receive via the WebSocket subscription, per chain
     ws_events: Counter<u64>,
 
+    /// Whether each full node configured for a chain is the one in use (1) or not (0)
+    endpoint_active: ObservableGauge<u64>,
+
+    /// Number of times Hermes switched over to another full node, per chain
+    endpoint_switches: Counter<u64>,
+
+    /// Number of queries delayed by the rate limits of a chain, per chain and reason
+    throttled_queries: Counter<u64>,
+
     /// Number of messages submitted to a specific chain
     messages_submitted: Counter<u64>,
 
     /// The balance of each wallet Hermes uses per chain
     wallet_balance: ObservableGauge<f64>,
 
+    /// Whether the balance of each wallet Hermes uses is below the configured minimum
+    wallet_low_balance: ObservableGauge<u64>,
+
+    /// Number of top-ups of the wallets Hermes uses from the treasury account, per chain
+    wallet_top_ups: Counter<u64>,
+
     /// Indicates the latency for all transactions submitted to a specific chain,
     /// i.e. the difference between the moment when Hermes received a batch of events
     /// until the corresponding transaction(s) were submitted. Milliseconds.
@@ -193,6 +208,12 @@ pub struct TelemetryState {
 
     /// Sum of rewarded fees over the past FEE_LIFETIME seconds
     period_fees: ObservableGauge<u64>,
+
+    /// Number of incentivized packets which were not relayed, per reason
+    ics29_skipped_packets: Counter<u64>,
+
+    /// The fees Hermes may still spend on each chain in the current period of its fee budget
+    fee_budget_remaining: ObservableGauge<u64>,
 }
 
 impl TelemetryState {
@@ -270,6 +291,21 @@ impl TelemetryState {
                 .with_description("Number of times Hermes reconnected to the websocket endpoint")
                 .init(),
 
+            endpoint_active: meter
+                .u64_observable_gauge("endpoint_active")
+                .with_description("Whether each full node configured for a chain is the one Hermes is using (1) or not (0)")
+                .init(),
+
+            endpoint_switches: meter
+                .u64_counter("endpoint_switches")
+                .with_description("Number of times Hermes switched over to another full node of a chain")
+                .init(),
+
+            throttled_queries: meter
+                .u64_counter("throttled_queries")
+                .with_description("Number of queries delayed by the rate limits of a chain")
+                .init(),
+
             ws_events: meter
                 .u64_counter("ws_events")
                 .with_description("How many IBC events did Hermes receive via the websocket subscription")
@@ -285,6 +321,16 @@ impl TelemetryState {
                 .with_description("The balance of each wallet Hermes uses per chain. Please note that when converting the balance to f64 a loss in precision might be introduced in the displayed value")
                 .init(),
 
+            wallet_low_balance: meter
+                .u64_observable_gauge("wallet_low_balance")
+                .with_description("Whether the balance of each wallet Hermes uses per chain is below the configured `min_balance` (1) or not (0)")
+                .init(),
+
+            wallet_top_ups: meter
+                .u64_counter("wallet_top_ups")
+                .with_description("Number of top-ups of the wallets Hermes uses from the configured treasury account, per chain")
+                .init(),
+
             send_packet_events: meter
                 .u64_counter("send_packet_events")
                 .with_description("Number of SendPacket events received")
@@ -362,6 +408,16 @@ impl TelemetryState {
                 .u64_observable_gauge("ics29_period_fees")
                 .with_description("Amount of ICS29 fees rewarded over the past 7 days")
                 .init(),
+
+            ics29_skipped_packets: meter
+                .u64_counter("ics29_skipped_packets")
+                .with_description("Number of incentivized packets which Hermes did not relay, per reason")
+                .init(),
+
+            fee_budget_remaining: meter
+                .u64_observable_gauge("fee_budget_remaining")
+                .with_description("The fees Hermes may still spend on a chain in the current period of its fee budget")
+                .init(),
         }
     }
 
@@ -380,6 +436,7 @@ impl TelemetryState {
         let labels = &[KeyValue::new("chain", chain_id.to_string())];
 
         self.ws_reconnect.add(&cx, 0, labels);
+        self.endpoint_switches.add(&cx, 0, labels);
         self.ws_events.add(&cx, 0, labels);
         self.messages_submitted.add(&cx, 0, labels);
 
@@ -658,6 +715,52 @@ impl TelemetryState {
         self.ws_events.add(&cx, count, labels);
     }
 
+    /// Whether the full node with the given RPC address is the one in use for the chain
+    pub fn endpoint_active(&self, chain_id: &ChainId, rpc_addr: &str, active: bool) {
+        let cx = Context::current();
+
+        let labels = &[
+            KeyValue::new("chain", chain_id.to_string()),
+            KeyValue::new("rpc_addr", rpc_addr.to_string()),
+        ];
+
+        self.endpoint_active.observe(&cx, active.into(), labels);
+    }
+
+    /// Number of times Hermes switched over to another full node of the chain
+    pub fn endpoint_switch(&self, chain_id: &ChainId) {
+        let cx = Context::current();
+
+        let labels = &[KeyValue::new("chain", chain_id.to_string())];
+
+        self.endpoint_switches.add(&cx, 1, labels);
+    }
+
+    /// Number of queries delayed by the rate limits of a chain, either the limits
+    /// configured for the chain (`rate_limit`) or those of the full node (`node`)
+    pub fn throttled_query(&self, chain_id: &ChainId, reason: &'static str) {
+        let cx = Context::current();
+
+        let labels = &[
+            KeyValue::new("chain", chain_id.to_string()),
+            KeyValue::new("reason", reason),
+        ];
+
+        self.throttled_queries.add(&cx, 1, labels);
+    }
+
+    /// The fees Hermes may still spend on the chain in the current period of its fee budget
+    pub fn fee_budget_remaining(&self, chain_id: &ChainId, denom: &str, remaining: u64) {
+        let cx = Context::current();
+
+        let labels = &[
+            KeyValue::new("chain", chain_id.to_string()),
+            KeyValue::new("denom", denom.to_string()),
+        ];
+
+        self.fee_budget_remaining.observe(&cx, remaining, labels);
+    }
+
     /// How many messages Hermes submitted to the chain
     pub fn messages_submitted(&self, chain_id: &ChainId, count: u64) {
         let cx = Context::current();
@@ -681,6 +784,34 @@ impl TelemetryState {
         self.wallet_balance.observe(&cx, amount, labels);
     }
 
+    /// Whether the balance of a wallet that Hermes is using is below the
+    /// configured minimum balance, per account, denom and chain.
+    pub fn wallet_low_balance(&self, chain_id: &ChainId, account: &str, denom: &str, low: bool) {
+        let cx = Context::current();
+
+        let labels = &[
+            KeyValue::new("chain", chain_id.to_string()),
+            KeyValue::new("account", account.to_string()),
+            KeyValue::new("denom", denom.to_string()),
+        ];
+
+        self.wallet_low_balance.observe(&cx, low.into(), labels);
+    }
+
+    /// Number of top-ups of a wallet that Hermes is using from the treasury account,
+    /// per account, denom and chain.
+    pub fn wallet_top_up(&self, chain_id: &ChainId, account: &str, denom: &str) {
+        let cx = Context::current();
+
+        let labels = &[
+            KeyValue::new("chain", chain_id.to_string()),
+            KeyValue::new("account", account.to_string()),
+            KeyValue::new("denom", denom.to_string()),
+        ];
+
+        self.wallet_top_ups.add(&cx, 1, labels);
+    }
+
     pub fn received_event_batch(&self, tracking_id: impl ToString) {
         self.in_flight_events
             .insert(tracking_id.to_string(), Instant::now());
@@ -1017,6 +1148,26 @@ impl TelemetryState {
         self.period_fees.observe(&cx, sum, labels);
     }
 
+    /// Record an incentivized packet which was not relayed, with the reason why
+    pub fn ics29_packet_skipped(
+        &self,
+        chain_id: &ChainId,
+        channel_id: &ChannelId,
+        port_id: &PortId,
+        reason: &'static str,
+    ) {
+        let cx = Context::current();
+
+        let labels = &[
+            KeyValue::new("chain", chain_id.to_string()),
+            KeyValue::new("channel", channel_id.to_string()),
+            KeyValue::new("port", port_id.to_string()),
+            KeyValue::new("reason", reason),
+        ];
+
+        self.ics29_skipped_packets.add(&cx, 1, labels);
+    }
+
     pub fn update_period_fees(&self, chain_id: &ChainId, receiver: &String, denom: &String) {
         let cx = Context::current();
 
@@ -1101,9 +1252,11 @@ impl AggregatorSelector for CustomAggregatorSelector {
     fn aggregator_for(&self, descriptor: &Descriptor) -> Option<Arc<dyn Aggregator + Send + Sync>> {
         match descriptor.name() {
             "wallet_balance" => Some(Arc::new(last_value())),
+            "wallet_low_balance" => Some(Arc::new(last_value())),
             "backlog_oldest_sequence" => Some(Arc::new(last_value())),
             "backlog_oldest_timestamp" => Some(Arc::new(last_value())),
             "backlog_size" => Some(Arc::new(last_value())),
+            "fee_budget_remaining" => Some(Arc::new(last_value())),
             // Prometheus' supports only collector for histogram, sum, and last value aggregators.
             // https://docs.rs/opentelemetry-prometheus/0.10.0/src/opentelemetry_prometheus/lib.rs.html#411-418
             // TODO: Once quantile sketches are supported, replace histograms with that.
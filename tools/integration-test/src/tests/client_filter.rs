@@ -48,6 +48,7 @@ impl TestOverrides for ClientFilterBlocksConnectionTest {
             max_clock_drift: Some(Duration::from_secs(3)),
             trusting_period: Some(Duration::from_secs(120_000)),
             trust_threshold: Some(TrustThreshold::new(20, 23).unwrap()),
+            wasm_code_hash: None,
         }
     }
 
@@ -56,6 +57,7 @@ impl TestOverrides for ClientFilterBlocksConnectionTest {
             max_clock_drift: Some(Duration::from_secs(6)),
             trusting_period: Some(Duration::from_secs(340_000)),
             trust_threshold: Some(TrustThreshold::TWO_THIRDS),
+            wasm_code_hash: None,
         }
     }
 }
@@ -66,6 +68,7 @@ impl TestOverrides for ClientFilterAllowsConnectionTest {
             max_clock_drift: Some(Duration::from_secs(3)),
             trusting_period: Some(Duration::from_secs(120_000)),
             trust_threshold: Some(TrustThreshold::new(13, 23).unwrap()),
+            wasm_code_hash: None,
         }
     }
 
@@ -74,6 +77,7 @@ impl TestOverrides for ClientFilterAllowsConnectionTest {
             max_clock_drift: Some(Duration::from_secs(6)),
             trusting_period: Some(Duration::from_secs(340_000)),
             trust_threshold: Some(TrustThreshold::ONE_THIRD),
+            wasm_code_hash: None,
         }
     }
 }
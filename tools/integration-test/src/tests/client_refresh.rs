@@ -32,6 +32,7 @@ impl TestOverrides for ClientDefaultsTest {
             max_clock_drift: Some(Duration::from_secs(3)),
             trusting_period: Some(Duration::from_secs(60)),
             trust_threshold: Some(TrustThreshold::new(13, 23).unwrap()),
+            wasm_code_hash: None,
         }
     }
 
@@ -40,6 +41,7 @@ impl TestOverrides for ClientDefaultsTest {
             max_clock_drift: Some(Duration::from_secs(6)),
             trusting_period: Some(Duration::from_secs(60)),
             trust_threshold: Some(TrustThreshold::TWO_THIRDS),
+            wasm_code_hash: None,
         }
     }
 }
@@ -104,6 +106,7 @@ impl TestOverrides for ClientFailsTest {
             max_clock_drift: Some(Duration::from_secs(3)),
             trusting_period: Some(Duration::from_secs(60)),
             trust_threshold: Some(TrustThreshold::new(13, 23).unwrap()),
+            wasm_code_hash: None,
         }
     }
 
@@ -112,6 +115,7 @@ impl TestOverrides for ClientFailsTest {
             max_clock_drift: Some(Duration::from_secs(6)),
             trusting_period: Some(Duration::from_secs(60)),
             trust_threshold: Some(TrustThreshold::TWO_THIRDS),
+            wasm_code_hash: None,
         }
     }
 }
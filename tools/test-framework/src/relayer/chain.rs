@@ -23,10 +23,12 @@
 use crossbeam_channel as channel;
 use tracing::Span;
 
+use ibc_proto::cosmos::tx::v1beta1::Fee;
 use ibc_proto::ibc::apps::fee::v1::{
     QueryIncentivizedPacketRequest, QueryIncentivizedPacketResponse,
 };
 use ibc_relayer::account::Balance;
+use ibc_relayer::chain::budget::FeeBudgetStatus;
 use ibc_relayer::chain::client::ClientSettings;
 use ibc_relayer::chain::endpoint::{ChainStatus, HealthCheck};
 use ibc_relayer::chain::handle::{ChainHandle, ChainRequest, Subscription};
@@ -50,6 +52,7 @@ use ibc_relayer_types::core::ics03_connection::version::Version;
 use ibc_relayer_types::core::ics04_channel::channel::ChannelEnd;
 use ibc_relayer_types::core::ics04_channel::channel::IdentifiedChannelEnd;
 use ibc_relayer_types::core::ics04_channel::packet::{PacketMsgType, Sequence};
+use ibc_relayer_types::core::ics04_channel::upgrade::{ErrorReceipt, Upgrade};
 use ibc_relayer_types::core::ics23_commitment::commitment::CommitmentPrefix;
 use ibc_relayer_types::core::ics23_commitment::merkle::MerkleProof;
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
@@ -105,6 +108,14 @@ where
         self.value().send_messages_and_wait_check_tx(tracked_msgs)
     }
 
+    fn estimate_fee(&self, tracked_msgs: TrackedMsgs) -> Result<Fee, Error> {
+        self.value().estimate_fee(tracked_msgs)
+    }
+
+    fn simulate_messages(&self, tracked_msgs: TrackedMsgs) -> Result<u64, Error> {
+        self.value().simulate_messages(tracked_msgs)
+    }
+
     fn get_signer(&self) -> Result<Signer, Error> {
         self.value().get_signer()
     }
@@ -117,6 +128,10 @@ where
         self.value().get_key()
     }
 
+    fn get_keys(&self) -> Result<Vec<(String, AnySigningKeyPair)>, Error> {
+        self.value().get_keys()
+    }
+
     fn add_key(&self, key_name: String, key: AnySigningKeyPair) -> Result<(), Error> {
         self.value().add_key(key_name, key)
     }
@@ -238,6 +253,22 @@ where
         self.value().query_channel(request, include_proof)
     }
 
+    fn query_upgrade(
+        &self,
+        request: QueryUpgradeRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(Upgrade, Option<MerkleProof>), Error> {
+        self.value().query_upgrade(request, include_proof)
+    }
+
+    fn query_upgrade_error(
+        &self,
+        request: QueryUpgradeErrorRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(ErrorReceipt, Option<MerkleProof>), Error> {
+        self.value().query_upgrade_error(request, include_proof)
+    }
+
     fn query_channel_client_state(
         &self,
         request: QueryChannelClientStateRequest,
@@ -308,6 +339,16 @@ where
             .build_channel_proofs(port_id, channel_id, height)
     }
 
+    fn build_channel_upgrade_proofs(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        height: Height,
+    ) -> Result<(Upgrade, Proofs), Error> {
+        self.value()
+            .build_channel_upgrade_proofs(port_id, channel_id, height)
+    }
+
     fn build_packet_proofs(
         &self,
         packet_type: PacketMsgType,
@@ -315,9 +356,16 @@ where
         channel_id: &ChannelId,
         sequence: Sequence,
         height: Height,
+        purpose: ProofPurpose,
     ) -> Result<Proofs, Error> {
-        self.value()
-            .build_packet_proofs(packet_type, port_id, channel_id, sequence, height)
+        self.value().build_packet_proofs(
+            packet_type,
+            port_id,
+            channel_id,
+            sequence,
+            height,
+            purpose,
+        )
     }
 
     fn query_packet_commitment(
@@ -413,6 +461,17 @@ where
             .maybe_register_counterparty_payee(channel_id, port_id, counterparty_payee)
     }
 
+    fn send_coins(
+        &self,
+        from_key_name: String,
+        to_address: String,
+        amount: u128,
+        denom: String,
+    ) -> Result<(), Error> {
+        self.value()
+            .send_coins(from_key_name, to_address, amount, denom)
+    }
+
     fn query_denom_trace(&self, hash: String) -> Result<DenomTrace, Error> {
         self.value().query_denom_trace(hash)
     }
@@ -430,4 +489,21 @@ where
     ) -> Result<QueryIncentivizedPacketResponse, Error> {
         self.value().query_incentivized_packet(request)
     }
+
+    fn query_fee_budget(&self) -> Result<Option<FeeBudgetStatus>, Error> {
+        self.value().query_fee_budget()
+    }
+
+    fn sync_proof_sequence(
+        &self,
+        counterparty_chain_id: ChainId,
+        counterparty_client_id: ClientId,
+        counterparty_client_state: AnyClientState,
+    ) -> Result<(), Error> {
+        self.value().sync_proof_sequence(
+            counterparty_chain_id,
+            counterparty_client_id,
+            counterparty_client_state,
+        )
+    }
 }
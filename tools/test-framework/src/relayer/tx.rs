@@ -1,3 +1,4 @@
+use alloc::sync::Arc;
 use core::str::FromStr;
 use core::time::Duration;
 
@@ -5,6 +6,7 @@ use http::uri::Uri;
 
 use ibc_proto::cosmos::tx::v1beta1::Fee;
 use ibc_relayer::chain::cosmos::gas::calculate_fee;
+use ibc_relayer::chain::cosmos::rate_limit::RateLimiter;
 use ibc_relayer::chain::cosmos::types::config::TxConfig;
 use ibc_relayer::chain::cosmos::types::gas::GasConfig;
 use ibc_relayer::config::{AddressType, GasPrice};
@@ -51,6 +53,7 @@ pub fn new_tx_config_for_test(
     let max_msg_num = Default::default();
     let max_tx_size = Default::default();
     let extension_options = Default::default();
+    let rate_limiter = Arc::new(RateLimiter::new(chain_id.clone(), None));
 
     Ok(TxConfig {
         chain_id,
@@ -62,5 +65,7 @@ pub fn new_tx_config_for_test(
         max_msg_num,
         max_tx_size,
         extension_options,
+        fee_budget: None,
+        rate_limiter,
     })
 }
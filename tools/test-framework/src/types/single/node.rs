@@ -143,11 +143,13 @@ impl FullNode {
                 url: WebSocketClientUrl::from_str(&self.chain_driver.websocket_address())?,
                 batch_delay: config::default::batch_delay(),
             },
+            fallback_endpoints: Vec::new(),
             rpc_timeout: config::default::rpc_timeout(),
             trusted_node: false,
             genesis_restart: None,
             account_prefix: self.chain_driver.account_prefix.clone(),
             key_name: self.wallets.relayer.id.0.clone(),
+            key_names: Vec::new(),
             key_store_type: Store::Test,
             key_store_folder: Some(hermes_keystore_dir.into()),
             store_prefix: "ibc".to_string(),
@@ -165,7 +167,14 @@ impl FullNode {
             ccv_consumer_chain: false,
             trust_threshold: Default::default(),
             gas_price: config::GasPrice::new(0.003, "stake".to_string()),
+            dynamic_gas_price: None,
+            fee_budget: None,
             packet_filter: Default::default(),
+            light_block_cache: Default::default(),
+            rate_limit: None,
+            remote_signer: None,
+            wallet: None,
+            solo_machine: None,
             address_type: chain_type.address_type(),
             memo_prefix: Default::default(),
             proof_specs: Default::default(),